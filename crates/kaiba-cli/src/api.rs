@@ -1,13 +1,100 @@
 //! Kaiba API Client
 
 use anyhow::{bail, Context, Result};
-use reqwest::Client;
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::sync::OnceLock;
+use std::time::Duration;
 use uuid::Uuid;
 
+/// Items requested per page when fetching whole lists (the server maximum)
+const PAGE_SIZE: usize = 200;
+
+/// Longest wait between retries
+const MAX_BACKOFF: Duration = Duration::from_secs(8);
+
+static CLIENT_OPTIONS: OnceLock<ClientOptions> = OnceLock::new();
+
+/// Timeout and retry settings for every client, set once from the global flags
+#[derive(Debug, Clone, Copy)]
+pub struct ClientOptions {
+    pub timeout: Duration,
+    /// Extra attempts after a transient failure
+    pub retries: u32,
+}
+
+impl Default for ClientOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(60),
+            retries: 2,
+        }
+    }
+}
+
+impl ClientOptions {
+    /// Use these options for all clients created afterwards
+    pub fn install(self) {
+        let _ = CLIENT_OPTIONS.set(self);
+    }
+}
+
+/// `send()` with the configured timeout, retrying transient failures
+///
+/// Connection failures, where the request never reached the server, are
+/// retried for every method. Timeouts and 502/503/504 responses (e.g. a
+/// cold-starting Shuttle deployment) may come after the server acted on the
+/// request, so they are only retried for idempotent methods (not POST).
+trait SendRetrying {
+    async fn send_retrying(self, options: &ClientOptions) -> reqwest::Result<Response>;
+}
+
+impl SendRetrying for RequestBuilder {
+    async fn send_retrying(self, options: &ClientOptions) -> reqwest::Result<Response> {
+        let (client, request) = self.timeout(options.timeout).build_split();
+        let request = request?;
+        let idempotent = request.method().is_idempotent();
+
+        let mut backoff = Duration::from_millis(500);
+        for _ in 0..options.retries {
+            // Streaming bodies cannot be cloned, so those get a single attempt
+            let Some(attempt) = request.try_clone() else {
+                break;
+            };
+            match client.execute(attempt).await {
+                Ok(resp) if idempotent && is_transient(resp.status()) => {}
+                Err(e) if e.is_connect() || (idempotent && e.is_timeout()) => {}
+                result => return result,
+            }
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+
+        client.execute(request).await
+    }
+}
+
+fn is_transient(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Readable message from an error body: the `detail` of a problem+json
+/// response, or the body as-is from older servers
+fn error_detail(body: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|v| v.get("detail")?.as_str().map(str::to_string))
+        .unwrap_or_else(|| body.to_string())
+}
+
 /// API Client for Kaiba
 pub struct KaibaClient {
     client: Client,
+    options: ClientOptions,
     base_url: String,
     api_key: String,
 }
@@ -16,34 +103,163 @@ pub struct KaibaClient {
 // API Response Types
 // ============================================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct ReiResponse {
     pub id: Uuid,
     pub name: String,
     pub role: String,
-    #[allow(dead_code)]
     pub avatar_url: Option<String>,
+    #[serde(default)]
+    pub manifest: serde_json::Value,
     pub state: ReiStateResponse,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreateReiRequest {
+    pub name: String,
+    pub role: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manifest: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct UpdateReiRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manifest: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct ReiStateResponse {
     pub energy_level: i32,
-    #[allow(dead_code)]
     pub mood: String,
+    pub token_budget: i32,
+    pub tokens_used: i32,
+    pub energy_regen_per_hour: i32,
+    /// False while autonomy is paused (servers without pausing omit it)
+    #[serde(default = "autonomy_default")]
+    pub autonomy_enabled: bool,
+}
+
+fn autonomy_default() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScheduleResponse {
+    pub cron: String,
+    pub timezone: String,
+    pub next_run_at: Option<DateTime<Utc>>,
+    pub last_run_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UpsertScheduleRequest {
+    pub cron: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct UpdateReiStateRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub energy_level: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mood: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_budget: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tokens_used: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub energy_regen_per_hour: Option<i32>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RechargeResponse {
+    pub previous_energy: i32,
+    pub current_energy: i32,
+    pub energy_regen_per_hour: i32,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct MemoryResponse {
-    #[allow(dead_code)]
     pub id: String,
     pub content: String,
     pub memory_type: String,
-    #[allow(dead_code)]
     pub importance: f32,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub similarity: Option<f32>,
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct UpdateMemoryRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub importance: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+}
+
+/// `/health` response
+#[derive(Debug, Deserialize, Serialize)]
+pub struct HealthResponse {
+    pub status: String,
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Not reported by older servers
+    #[serde(default)]
+    pub services: Option<ServerServices>,
+}
+
+/// Optional services enabled on the server
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ServerServices {
+    pub memory: bool,
+    pub embedding: bool,
+    pub web_search: bool,
+    pub digest: bool,
+}
+
+/// Past call to a Rei
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CallLogResponse {
+    pub id: Uuid,
+    pub message: String,
+    pub response: String,
+    pub tokens_consumed: i32,
+    pub session_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Live event from `GET /kaiba/rei/{id}/events`
+#[derive(Debug, Deserialize, Serialize)]
+pub struct StreamEvent {
+    pub id: Uuid,
+    pub rei_id: Uuid,
+    /// Event type (`memory_added`, `response_completed`, `webhook_delivery`, ...)
+    pub event: String,
+    #[serde(default)]
+    pub data: serde_json::Value,
+    pub occurred_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct PromptResponse {
     pub system_prompt: String,
     pub format: String,
@@ -51,7 +267,7 @@ pub struct PromptResponse {
     pub memories_included: usize,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct ReiSummary {
     #[allow(dead_code)]
     pub id: uuid::Uuid,
@@ -62,6 +278,27 @@ pub struct ReiSummary {
     pub mood: String,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TeiResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub provider: String,
+    pub model_id: String,
+    pub is_fallback: bool,
+    pub priority: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreateTeiRequest {
+    pub name: String,
+    pub provider: String,
+    pub model_id: String,
+    pub is_fallback: bool,
+    pub priority: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config: Option<serde_json::Value>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct CreateMemoryRequest {
     pub content: String,
@@ -78,13 +315,25 @@ pub struct SearchMemoriesRequest {
     pub query: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_type: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// "any" (default) or "all"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags_match_mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_importance: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_after: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct WebhookResponse {
     pub id: Uuid,
+    /// None for global webhooks
     #[allow(dead_code)]
-    pub rei_id: Uuid,
+    pub rei_id: Option<Uuid>,
     pub name: String,
     pub url: String,
     pub enabled: bool,
@@ -126,7 +375,7 @@ pub struct UpdateWebhookRequest {
     pub payload_format: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct WebhookDeliveryResponse {
     pub id: Uuid,
     #[allow(dead_code)]
@@ -140,182 +389,1052 @@ pub struct WebhookDeliveryResponse {
     pub completed_at: Option<String>,
 }
 
-impl KaibaClient {
-    /// Create a new API client
-    pub fn new(base_url: &str, api_key: &str) -> Self {
-        Self {
-            client: Client::new(),
-            base_url: base_url.trim_end_matches('/').to_string(),
-            api_key: api_key.to_string(),
+/// Rendered webhook request from a dry run
+#[derive(Debug, Deserialize, Serialize)]
+pub struct WebhookDryRunResponse {
+    pub url: String,
+    pub event: String,
+    pub would_deliver: bool,
+    pub headers: std::collections::BTreeMap<String, String>,
+    pub body: Option<serde_json::Value>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CallRequest {
+    pub tei_ids: Vec<Uuid>,
+    pub message: String,
+    pub context: CallContext,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CallContext {
+    pub include_memories: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CallResponse {
+    pub response: String,
+    pub tei_used: Uuid,
+    pub tokens_consumed: i32,
+    pub memories_included: Vec<CallMemoryReference>,
+    #[serde(default)]
+    pub session_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CallMemoryReference {
+    pub id: String,
+    pub similarity: f32,
+}
+
+/// Registered custom event
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EventDefinitionResponse {
+    pub name: String,
+    pub event: String,
+    pub description: Option<String>,
+    pub schema: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreateEventDefinitionRequest {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EmitEventResponse {
+    pub event_id: Uuid,
+    pub event: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DashboardResponse {
+    pub rei: DashboardReiInfo,
+    pub state: DashboardState,
+    pub activity: DashboardActivity,
+    pub stats: DashboardStats,
+    pub webhooks: DashboardWebhooks,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DashboardReiInfo {
+    pub id: Uuid,
+    pub name: String,
+    pub role: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DashboardState {
+    pub energy_level: i32,
+    pub mood: String,
+    pub tokens_used: i32,
+    pub token_budget: i32,
+    pub energy_regen_per_hour: i32,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DashboardActivity {
+    pub last_active_at: Option<DateTime<Utc>>,
+    pub last_learn_at: Option<DateTime<Utc>>,
+    pub last_digest_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DashboardStats {
+    pub memory_count: u64,
+    pub tei_count: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DashboardWebhooks {
+    pub webhook_count: i64,
+    /// Failed or dead-lettered deliveries in the last 24 hours
+    pub recent_failures: i64,
+    #[serde(default)]
+    pub per_webhook: Vec<WebhookStats>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct WebhookStats {
+    pub name: String,
+    pub failed: i64,
+    pub last_failure_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LearnRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_queries: Option<usize>,
+    pub force: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct LearnResponse {
+    pub success: bool,
+    pub session: Option<LearningSession>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct LearningSession {
+    pub rei_name: String,
+    pub queries_generated: Vec<String>,
+    pub searches_completed: usize,
+    /// Queries skipped because the Rei already knew the topic
+    #[serde(default)]
+    pub searches_skipped: usize,
+    pub memories_stored: usize,
+    /// Items learned from feeds
+    #[serde(default)]
+    pub items_learned: usize,
+    pub errors: Vec<String>,
+    /// Queries put off to the next session by a rate limit
+    #[serde(default)]
+    pub queries_deferred: Vec<String>,
+    /// When web search may resume after a rate limit
+    #[serde(default)]
+    pub resume_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DigestResponse {
+    pub success: bool,
+    pub result: Option<DigestResult>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DigestResult {
+    pub memories_processed: usize,
+    pub expertise_created: bool,
+    pub summary: String,
+}
+
+impl KaibaClient {
+    /// Create a new API client
+    pub fn new(base_url: &str, api_key: &str) -> Self {
+        let options = CLIENT_OPTIONS.get().copied().unwrap_or_default();
+        // The overall timeout is per request, so streamed calls are not cut off
+        let client = Client::builder()
+            .connect_timeout(options.timeout)
+            .build()
+            .unwrap_or_default();
+        Self {
+            client,
+            options,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: api_key.to_string(),
+        }
+    }
+
+    /// Test connection with health check
+    pub async fn health(&self) -> Result<bool> {
+        let url = format!("{}/health", self.base_url);
+        let resp = self.client.get(&url).send_retrying(&self.options).await?;
+        Ok(resp.status().is_success())
+    }
+
+    /// Server version and optional services
+    pub async fn server_info(&self) -> Result<HealthResponse> {
+        let url = format!("{}/health", self.base_url);
+        let resp = self
+            .client
+            .get(&url)
+            .send_retrying(&self.options)
+            .await
+            .context("Failed to connect to Kaiba API")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            bail!("API error ({}): {}", status, error_detail(&body));
+        }
+
+        let info: HealthResponse = resp.json().await.context("Failed to parse response")?;
+        Ok(info)
+    }
+
+    /// List all Reis
+    pub async fn list_reis(&self) -> Result<Vec<ReiResponse>> {
+        self.get_all_pages(&format!("{}/kaiba/rei", self.base_url))
+            .await
+    }
+
+    /// Fetch every page of a paginated list endpoint
+    async fn get_all_pages<T: DeserializeOwned>(&self, url: &str) -> Result<Vec<T>> {
+        let mut items: Vec<T> = Vec::new();
+        loop {
+            let resp = self
+                .client
+                .get(url)
+                .query(&[("limit", PAGE_SIZE), ("offset", items.len())])
+                .header("Authorization", format!("Bearer {}", self.api_key))
+                .send_retrying(&self.options)
+                .await
+                .context("Failed to connect to Kaiba API")?;
+
+            if !resp.status().is_success() {
+                let status = resp.status();
+                let body = resp.text().await.unwrap_or_default();
+                bail!("API error ({}): {}", status, error_detail(&body));
+            }
+
+            let total = resp
+                .headers()
+                .get("x-total-count")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<usize>().ok());
+            let page: Vec<T> = resp.json().await.context("Failed to parse response")?;
+            let fetched = page.len();
+            items.extend(page);
+
+            // Servers without pagination send everything and no total
+            match total {
+                Some(total) if fetched > 0 && items.len() < total => {}
+                _ => return Ok(items),
+            }
+        }
+    }
+
+    /// Get a specific Rei
+    pub async fn get_rei(&self, rei_id: &str) -> Result<ReiResponse> {
+        let url = format!("{}/kaiba/rei/{}", self.base_url, rei_id);
+        let resp = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .send_retrying(&self.options)
+            .await
+            .context("Failed to connect to Kaiba API")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            bail!("API error ({}): {}", status, error_detail(&body));
+        }
+
+        let rei: ReiResponse = resp.json().await.context("Failed to parse response")?;
+
+        Ok(rei)
+    }
+
+    /// Create a Rei
+    pub async fn create_rei(&self, request: &CreateReiRequest) -> Result<ReiResponse> {
+        let url = format!("{}/kaiba/rei", self.base_url);
+        let resp = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .json(request)
+            .send_retrying(&self.options)
+            .await
+            .context("Failed to connect to Kaiba API")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            bail!("API error ({}): {}", status, error_detail(&body));
+        }
+
+        let rei: ReiResponse = resp.json().await.context("Failed to parse response")?;
+
+        Ok(rei)
+    }
+
+    /// Update a Rei (only the fields that are set)
+    ///
+    /// With `if_match`, the update is refused (409) if the Rei changed since
+    /// that `updated_at` was read.
+    pub async fn update_rei(
+        &self,
+        rei_id: &str,
+        request: &UpdateReiRequest,
+        if_match: Option<DateTime<Utc>>,
+    ) -> Result<ReiResponse> {
+        let url = format!("{}/kaiba/rei/{}", self.base_url, rei_id);
+        let mut builder = self
+            .client
+            .put(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key));
+        if let Some(updated_at) = if_match {
+            builder = builder.header("If-Match", format!("\"{}\"", updated_at.to_rfc3339()));
+        }
+        let resp = builder
+            .json(request)
+            .send_retrying(&self.options)
+            .await
+            .context("Failed to connect to Kaiba API")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            bail!("API error ({}): {}", status, error_detail(&body));
+        }
+
+        let rei: ReiResponse = resp.json().await.context("Failed to parse response")?;
+
+        Ok(rei)
+    }
+
+    /// Delete a Rei
+    pub async fn delete_rei(&self, rei_id: &str) -> Result<()> {
+        let url = format!("{}/kaiba/rei/{}", self.base_url, rei_id);
+        let resp = self
+            .client
+            .delete(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .send_retrying(&self.options)
+            .await
+            .context("Failed to connect to Kaiba API")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            bail!("API error ({}): {}", status, error_detail(&body));
+        }
+
+        Ok(())
+    }
+
+    /// Restore a deleted Rei
+    pub async fn restore_rei(&self, rei_id: &str) -> Result<ReiResponse> {
+        let url = format!("{}/kaiba/rei/{}/restore", self.base_url, rei_id);
+        let resp = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .send_retrying(&self.options)
+            .await
+            .context("Failed to connect to Kaiba API")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            bail!("API error ({}): {}", status, error_detail(&body));
+        }
+
+        let rei: ReiResponse = resp.json().await.context("Failed to parse response")?;
+
+        Ok(rei)
+    }
+
+    /// Get a Rei's schedule (`None` when it follows the global interval)
+    pub async fn get_schedule(&self, rei_id: &str) -> Result<Option<ScheduleResponse>> {
+        let url = format!("{}/kaiba/rei/{}/schedule", self.base_url, rei_id);
+        let resp = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .send_retrying(&self.options)
+            .await
+            .context("Failed to connect to Kaiba API")?;
+
+        if resp.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            bail!("API error ({}): {}", status, error_detail(&body));
+        }
+
+        let schedule: ScheduleResponse = resp.json().await.context("Failed to parse response")?;
+
+        Ok(Some(schedule))
+    }
+
+    /// Set a Rei's cron schedule
+    pub async fn set_schedule(
+        &self,
+        rei_id: &str,
+        request: &UpsertScheduleRequest,
+    ) -> Result<ScheduleResponse> {
+        let url = format!("{}/kaiba/rei/{}/schedule", self.base_url, rei_id);
+        let resp = self
+            .client
+            .put(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .json(request)
+            .send_retrying(&self.options)
+            .await
+            .context("Failed to connect to Kaiba API")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            bail!("API error ({}): {}", status, error_detail(&body));
+        }
+
+        let schedule: ScheduleResponse = resp.json().await.context("Failed to parse response")?;
+
+        Ok(schedule)
+    }
+
+    /// Remove a Rei's schedule
+    pub async fn clear_schedule(&self, rei_id: &str) -> Result<()> {
+        let url = format!("{}/kaiba/rei/{}/schedule", self.base_url, rei_id);
+        let resp = self
+            .client
+            .delete(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .send_retrying(&self.options)
+            .await
+            .context("Failed to connect to Kaiba API")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            bail!("API error ({}): {}", status, error_detail(&body));
+        }
+
+        Ok(())
+    }
+
+    /// Update a Rei's state (energy, mood, token budget)
+    pub async fn update_rei_state(
+        &self,
+        rei_id: &str,
+        request: &UpdateReiStateRequest,
+    ) -> Result<ReiStateResponse> {
+        let url = format!("{}/kaiba/rei/{}/state", self.base_url, rei_id);
+        let resp = self
+            .client
+            .put(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .json(request)
+            .send_retrying(&self.options)
+            .await
+            .context("Failed to connect to Kaiba API")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            bail!("API error ({}): {}", status, error_detail(&body));
+        }
+
+        let state: ReiStateResponse = resp.json().await.context("Failed to parse response")?;
+        Ok(state)
+    }
+
+    /// Pause (`false`) or resume (`true`) a Rei's autonomy
+    pub async fn set_autonomy(&self, rei_id: &str, enabled: bool) -> Result<ReiStateResponse> {
+        let url = format!(
+            "{}/kaiba/rei/{}/autonomy/{}",
+            self.base_url,
+            rei_id,
+            if enabled { "resume" } else { "pause" }
+        );
+        let resp = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .send_retrying(&self.options)
+            .await
+            .context("Failed to connect to Kaiba API")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            bail!("API error ({}): {}", status, error_detail(&body));
+        }
+
+        let state: ReiStateResponse = resp.json().await.context("Failed to parse response")?;
+        Ok(state)
+    }
+
+    /// Add (or drain, if negative) energy
+    pub async fn recharge(&self, rei_id: &str, energy: i32) -> Result<RechargeResponse> {
+        let url = format!("{}/kaiba/rei/{}/recharge", self.base_url, rei_id);
+        let resp = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .json(&serde_json::json!({ "energy": energy }))
+            .send_retrying(&self.options)
+            .await
+            .context("Failed to connect to Kaiba API")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            bail!("API error ({}): {}", status, error_detail(&body));
+        }
+
+        let recharged: RechargeResponse = resp.json().await.context("Failed to parse response")?;
+        Ok(recharged)
+    }
+
+    /// List all Teis
+    pub async fn list_teis(&self) -> Result<Vec<TeiResponse>> {
+        self.get_all_pages(&format!("{}/kaiba/tei", self.base_url))
+            .await
+    }
+
+    /// List Teis associated with a Rei
+    pub async fn list_rei_teis(&self, rei_id: &str) -> Result<Vec<TeiResponse>> {
+        self.get_teis(&format!("{}/kaiba/rei/{}/teis", self.base_url, rei_id))
+            .await
+    }
+
+    async fn get_teis(&self, url: &str) -> Result<Vec<TeiResponse>> {
+        let resp = self
+            .client
+            .get(url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .send_retrying(&self.options)
+            .await
+            .context("Failed to connect to Kaiba API")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            bail!("API error ({}): {}", status, error_detail(&body));
+        }
+
+        let teis: Vec<TeiResponse> = resp.json().await.context("Failed to parse response")?;
+
+        Ok(teis)
+    }
+
+    /// Create a Tei
+    pub async fn create_tei(&self, request: &CreateTeiRequest) -> Result<TeiResponse> {
+        let url = format!("{}/kaiba/tei", self.base_url);
+        let resp = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .json(request)
+            .send_retrying(&self.options)
+            .await
+            .context("Failed to connect to Kaiba API")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            bail!("API error ({}): {}", status, error_detail(&body));
+        }
+
+        let tei: TeiResponse = resp.json().await.context("Failed to parse response")?;
+
+        Ok(tei)
+    }
+
+    /// Delete a Tei
+    pub async fn delete_tei(&self, tei_id: &str) -> Result<()> {
+        let url = format!("{}/kaiba/tei/{}", self.base_url, tei_id);
+        self.send_expecting_ok(self.client.delete(&url)).await
+    }
+
+    /// Associate a Tei with a Rei
+    pub async fn link_tei(&self, rei_id: &str, tei_id: &str) -> Result<()> {
+        let url = format!("{}/kaiba/rei/{}/teis", self.base_url, rei_id);
+        self.send_expecting_ok(
+            self.client
+                .post(&url)
+                .json(&serde_json::json!({ "tei_id": tei_id })),
+        )
+        .await
+    }
+
+    /// Remove a Tei from a Rei
+    pub async fn unlink_tei(&self, rei_id: &str, tei_id: &str) -> Result<()> {
+        let url = format!("{}/kaiba/rei/{}/teis/{}", self.base_url, rei_id, tei_id);
+        self.send_expecting_ok(self.client.delete(&url)).await
+    }
+
+    async fn send_expecting_ok(&self, request: reqwest::RequestBuilder) -> Result<()> {
+        let resp = request
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .send_retrying(&self.options)
+            .await
+            .context("Failed to connect to Kaiba API")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            bail!("API error ({}): {}", status, error_detail(&body));
+        }
+
+        Ok(())
+    }
+
+    /// Add a memory
+    pub async fn add_memory(
+        &self,
+        rei_id: &str,
+        content: &str,
+        memory_type: Option<&str>,
+        importance: Option<f32>,
+        tags: &[String],
+    ) -> Result<MemoryResponse> {
+        let url = format!("{}/kaiba/rei/{}/memories", self.base_url, rei_id);
+
+        let request = CreateMemoryRequest {
+            content: content.to_string(),
+            memory_type: memory_type.map(|s| s.to_string()),
+            importance,
+            tags: tags.to_vec(),
+        };
+
+        let resp = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .json(&request)
+            .send_retrying(&self.options)
+            .await
+            .context("Failed to connect to Kaiba API")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            bail!("API error ({}): {}", status, error_detail(&body));
+        }
+
+        let memory: MemoryResponse = resp.json().await.context("Failed to parse response")?;
+
+        Ok(memory)
+    }
+
+    /// Get prompt for external Tei
+    pub async fn get_prompt(
+        &self,
+        rei_id: &str,
+        format: Option<&str>,
+        include_memories: bool,
+        context: Option<&str>,
+    ) -> Result<PromptResponse> {
+        let mut url = format!("{}/kaiba/rei/{}/prompt", self.base_url, rei_id);
+
+        // Build query params
+        let mut params = vec![];
+        if let Some(f) = format {
+            params.push(format!("format={}", f));
+        }
+        if include_memories {
+            params.push("include_memories=true".to_string());
+        }
+        if let Some(ctx) = context {
+            params.push(format!("context={}", urlencoding::encode(ctx)));
+        }
+        if !params.is_empty() {
+            url = format!("{}?{}", url, params.join("&"));
+        }
+
+        let resp = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .send_retrying(&self.options)
+            .await
+            .context("Failed to connect to Kaiba API")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            bail!("API error ({}): {}", status, error_detail(&body));
+        }
+
+        let prompt: PromptResponse = resp.json().await.context("Failed to parse response")?;
+
+        Ok(prompt)
+    }
+
+    /// Call a Rei (LLM invocation with optional RAG)
+    ///
+    /// With `stream`, asks for `text/event-stream` and passes each
+    /// `data: {"delta": "..."}` chunk to `on_delta`; the final `data:` event
+    /// carries the full CallResponse. Servers that do not stream answer with
+    /// plain JSON, in which case `on_delta` is never called.
+    pub async fn call_rei(
+        &self,
+        rei_id: &str,
+        request: &CallRequest,
+        stream: bool,
+        mut on_delta: impl FnMut(&str),
+    ) -> Result<CallResponse> {
+        let url = format!("{}/kaiba/rei/{}/call", self.base_url, rei_id);
+
+        let mut builder = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .json(request);
+        if stream {
+            builder = builder.header("Accept", "text/event-stream");
+        }
+
+        let mut resp = if stream {
+            // No overall timeout: the answer streams for as long as the Tei takes
+            builder.send().await
+        } else {
+            builder.send_retrying(&self.options).await
+        }
+        .context("Failed to connect to Kaiba API")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            bail!("API error ({}): {}", status, error_detail(&body));
+        }
+
+        let is_event_stream = resp
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.starts_with("text/event-stream"));
+        if !is_event_stream {
+            let call: CallResponse = resp.json().await.context("Failed to parse response")?;
+            return Ok(call);
+        }
+
+        let mut buffer = String::new();
+        let mut final_response = None;
+        while let Some(chunk) = resp.chunk().await.context("Failed to read stream")? {
+            buffer.push_str(&String::from_utf8_lossy(&chunk));
+            while let Some(newline) = buffer.find('\n') {
+                let line: String = buffer.drain(..=newline).collect();
+                let Some(data) = line.trim_end().strip_prefix("data:") else {
+                    continue;
+                };
+                let value: serde_json::Value = match serde_json::from_str(data.trim()) {
+                    Ok(value) => value,
+                    Err(_) => continue,
+                };
+                if let Some(delta) = value.get("delta").and_then(|d| d.as_str()) {
+                    on_delta(delta);
+                } else if value.get("response").is_some() {
+                    final_response =
+                        Some(serde_json::from_value(value).context("Failed to parse response")?);
+                }
+            }
+        }
+
+        final_response.context("Stream ended without a final response")
+    }
+
+    /// Follow a Rei's live event stream, passing each event to `on_event`
+    ///
+    /// Returns `Ok(true)` when the server closes the stream (e.g. on
+    /// restart) and `Ok(false)` if the server has no event stream, so the
+    /// caller can fall back to polling.
+    pub async fn stream_events(
+        &self,
+        rei_id: &str,
+        mut on_event: impl FnMut(StreamEvent) -> Result<()>,
+    ) -> Result<bool> {
+        let url = format!("{}/kaiba/rei/{}/events", self.base_url, rei_id);
+
+        // No overall timeout: the stream stays open until the server ends it
+        let mut resp = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .header("Accept", "text/event-stream")
+            .send()
+            .await
+            .context("Failed to connect to Kaiba API")?;
+
+        let content_type = resp
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+
+        // Older servers have no such route; a missing Rei is a problem+json 404
+        if matches!(
+            resp.status(),
+            StatusCode::NOT_FOUND | StatusCode::METHOD_NOT_ALLOWED
+        ) && !content_type.starts_with("application/problem+json")
+        {
+            return Ok(false);
+        }
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            bail!("API error ({}): {}", status, error_detail(&body));
+        }
+
+        let mut buffer = String::new();
+        while let Some(chunk) = resp.chunk().await.context("Failed to read stream")? {
+            buffer.push_str(&String::from_utf8_lossy(&chunk));
+            while let Some(newline) = buffer.find('\n') {
+                let line: String = buffer.drain(..=newline).collect();
+                let Some(data) = line.trim_end().strip_prefix("data:") else {
+                    continue;
+                };
+                // `lagged` notices and unknown payloads are skipped
+                if let Ok(event) = serde_json::from_str::<StreamEvent>(data.trim()) {
+                    on_event(event)?;
+                }
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Get the dashboard overview for a Rei
+    pub async fn get_dashboard(&self, rei_id: &str) -> Result<DashboardResponse> {
+        let url = format!("{}/kaiba/rei/{}/dashboard", self.base_url, rei_id);
+        let resp = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .send_retrying(&self.options)
+            .await
+            .context("Failed to connect to Kaiba API")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            bail!("API error ({}): {}", status, error_detail(&body));
+        }
+
+        let dashboard: DashboardResponse = resp.json().await.context("Failed to parse response")?;
+        Ok(dashboard)
+    }
+
+    /// Run a self-learning session for a Rei
+    pub async fn learn(&self, rei_id: &str, request: &LearnRequest) -> Result<LearnResponse> {
+        let url = format!("{}/kaiba/rei/{}/learn", self.base_url, rei_id);
+        let resp = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .json(request)
+            .send_retrying(&self.options)
+            .await
+            .context("Failed to connect to Kaiba API")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            bail!("API error ({}): {}", status, error_detail(&body));
         }
+
+        let learned: LearnResponse = resp.json().await.context("Failed to parse response")?;
+        Ok(learned)
     }
 
-    /// Test connection with health check
-    pub async fn health(&self) -> Result<bool> {
-        let url = format!("{}/health", self.base_url);
-        let resp = self.client.get(&url).send().await?;
-        Ok(resp.status().is_success())
+    /// Digest recent learning memories into expertise
+    pub async fn digest(&self, rei_id: &str) -> Result<DigestResponse> {
+        let url = format!("{}/kaiba/rei/{}/digest", self.base_url, rei_id);
+        let resp = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .send_retrying(&self.options)
+            .await
+            .context("Failed to connect to Kaiba API")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            bail!("API error ({}): {}", status, error_detail(&body));
+        }
+
+        let digested: DigestResponse = resp.json().await.context("Failed to parse response")?;
+        Ok(digested)
     }
 
-    /// List all Reis
-    pub async fn list_reis(&self) -> Result<Vec<ReiResponse>> {
-        let url = format!("{}/kaiba/rei", self.base_url);
+    /// Search memories
+    pub async fn search_memories(
+        &self,
+        rei_id: &str,
+        request: &SearchMemoriesRequest,
+    ) -> Result<Vec<MemoryResponse>> {
+        let url = format!("{}/kaiba/rei/{}/memories/search", self.base_url, rei_id);
+
         let resp = self
             .client
-            .get(&url)
+            .post(&url)
             .header("Authorization", format!("Bearer {}", self.api_key))
-            .send()
+            .json(request)
+            .send_retrying(&self.options)
             .await
             .context("Failed to connect to Kaiba API")?;
 
         if !resp.status().is_success() {
             let status = resp.status();
             let body = resp.text().await.unwrap_or_default();
-            bail!("API error ({}): {}", status, body);
+            bail!("API error ({}): {}", status, error_detail(&body));
         }
 
-        let reis: Vec<ReiResponse> = resp.json().await.context("Failed to parse response")?;
+        let memories: Vec<MemoryResponse> =
+            resp.json().await.context("Failed to parse response")?;
 
-        Ok(reis)
+        Ok(memories)
     }
 
-    /// Get a specific Rei
-    pub async fn get_rei(&self, rei_id: &str) -> Result<ReiResponse> {
-        let url = format!("{}/kaiba/rei/{}", self.base_url, rei_id);
+    /// Get a single memory
+    pub async fn get_memory(&self, rei_id: &str, memory_id: &str) -> Result<MemoryResponse> {
+        let url = format!(
+            "{}/kaiba/rei/{}/memories/{}",
+            self.base_url, rei_id, memory_id
+        );
         let resp = self
             .client
             .get(&url)
             .header("Authorization", format!("Bearer {}", self.api_key))
-            .send()
+            .send_retrying(&self.options)
             .await
             .context("Failed to connect to Kaiba API")?;
 
         if !resp.status().is_success() {
             let status = resp.status();
             let body = resp.text().await.unwrap_or_default();
-            bail!("API error ({}): {}", status, body);
+            bail!("API error ({}): {}", status, error_detail(&body));
         }
 
-        let rei: ReiResponse = resp.json().await.context("Failed to parse response")?;
-
-        Ok(rei)
+        let memory: MemoryResponse = resp.json().await.context("Failed to parse response")?;
+        Ok(memory)
     }
 
-    /// Add a memory
-    pub async fn add_memory(
+    /// Update a memory's content, type, importance or tags
+    pub async fn update_memory(
         &self,
         rei_id: &str,
-        content: &str,
-        memory_type: Option<&str>,
-        importance: Option<f32>,
-        tags: &[String],
+        memory_id: &str,
+        request: &UpdateMemoryRequest,
     ) -> Result<MemoryResponse> {
-        let url = format!("{}/kaiba/rei/{}/memories", self.base_url, rei_id);
-
-        let request = CreateMemoryRequest {
-            content: content.to_string(),
-            memory_type: memory_type.map(|s| s.to_string()),
-            importance,
-            tags: tags.to_vec(),
-        };
-
+        let url = format!(
+            "{}/kaiba/rei/{}/memories/{}",
+            self.base_url, rei_id, memory_id
+        );
         let resp = self
             .client
-            .post(&url)
+            .put(&url)
             .header("Authorization", format!("Bearer {}", self.api_key))
-            .json(&request)
-            .send()
+            .json(request)
+            .send_retrying(&self.options)
             .await
             .context("Failed to connect to Kaiba API")?;
 
         if !resp.status().is_success() {
             let status = resp.status();
             let body = resp.text().await.unwrap_or_default();
-            bail!("API error ({}): {}", status, body);
+            bail!("API error ({}): {}", status, error_detail(&body));
         }
 
         let memory: MemoryResponse = resp.json().await.context("Failed to parse response")?;
-
         Ok(memory)
     }
 
-    /// Get prompt for external Tei
-    pub async fn get_prompt(
+    /// List recent memories (newest first), optionally only those after `since`
+    pub async fn list_memories(
         &self,
         rei_id: &str,
-        format: Option<&str>,
-        include_memories: bool,
-        context: Option<&str>,
-    ) -> Result<PromptResponse> {
-        let mut url = format!("{}/kaiba/rei/{}/prompt", self.base_url, rei_id);
-
-        // Build query params
-        let mut params = vec![];
-        if let Some(f) = format {
-            params.push(format!("format={}", f));
-        }
-        if include_memories {
-            params.push("include_memories=true".to_string());
-        }
-        if let Some(ctx) = context {
-            params.push(format!("context={}", urlencoding::encode(ctx)));
-        }
-        if !params.is_empty() {
-            url = format!("{}?{}", url, params.join("&"));
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<MemoryResponse>> {
+        let mut url = format!("{}/kaiba/rei/{}/memories", self.base_url, rei_id);
+        if let Some(since) = since {
+            url.push_str(&format!(
+                "?since={}",
+                urlencoding::encode(&since.to_rfc3339())
+            ));
         }
 
         let resp = self
             .client
             .get(&url)
             .header("Authorization", format!("Bearer {}", self.api_key))
-            .send()
+            .send_retrying(&self.options)
             .await
             .context("Failed to connect to Kaiba API")?;
 
         if !resp.status().is_success() {
             let status = resp.status();
             let body = resp.text().await.unwrap_or_default();
-            bail!("API error ({}): {}", status, body);
+            bail!("API error ({}): {}", status, error_detail(&body));
         }
 
-        let prompt: PromptResponse = resp.json().await.context("Failed to parse response")?;
+        let memories: Vec<MemoryResponse> =
+            resp.json().await.context("Failed to parse response")?;
 
-        Ok(prompt)
+        Ok(memories)
     }
 
-    /// Search memories
-    pub async fn search_memories(
+    /// List recent calls (newest first), optionally only those after `since`
+    pub async fn list_calls(
         &self,
         rei_id: &str,
-        query: &str,
-        limit: Option<usize>,
-    ) -> Result<Vec<MemoryResponse>> {
-        let url = format!("{}/kaiba/rei/{}/memories/search", self.base_url, rei_id);
-
-        let request = SearchMemoriesRequest {
-            query: query.to_string(),
-            limit,
-        };
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<CallLogResponse>> {
+        let mut url = format!("{}/kaiba/rei/{}/calls", self.base_url, rei_id);
+        if let Some(since) = since {
+            url.push_str(&format!(
+                "?since={}",
+                urlencoding::encode(&since.to_rfc3339())
+            ));
+        }
 
         let resp = self
             .client
-            .post(&url)
+            .get(&url)
             .header("Authorization", format!("Bearer {}", self.api_key))
-            .json(&request)
-            .send()
+            .send_retrying(&self.options)
             .await
             .context("Failed to connect to Kaiba API")?;
 
         if !resp.status().is_success() {
             let status = resp.status();
             let body = resp.text().await.unwrap_or_default();
-            bail!("API error ({}): {}", status, body);
+            bail!("API error ({}): {}", status, error_detail(&body));
         }
 
-        let memories: Vec<MemoryResponse> =
-            resp.json().await.context("Failed to parse response")?;
+        let calls: Vec<CallLogResponse> = resp.json().await.context("Failed to parse response")?;
 
-        Ok(memories)
+        Ok(calls)
     }
 
     /// List webhooks for a Rei
@@ -325,14 +1444,14 @@ impl KaibaClient {
             .client
             .get(&url)
             .header("Authorization", format!("Bearer {}", self.api_key))
-            .send()
+            .send_retrying(&self.options)
             .await
             .context("Failed to connect to Kaiba API")?;
 
         if !resp.status().is_success() {
             let status = resp.status();
             let body = resp.text().await.unwrap_or_default();
-            bail!("API error ({}): {}", status, body);
+            bail!("API error ({}): {}", status, error_detail(&body));
         }
 
         let webhooks: Vec<WebhookResponse> =
@@ -347,6 +1466,7 @@ impl KaibaClient {
         rei_id: &str,
         name: &str,
         url: &str,
+        secret: Option<String>,
         events: Option<Vec<String>>,
         payload_format: Option<String>,
     ) -> Result<WebhookResponse> {
@@ -355,7 +1475,7 @@ impl KaibaClient {
         let request = CreateWebhookRequest {
             name: name.to_string(),
             url: url.to_string(),
-            secret: None,
+            secret,
             events,
             payload_format,
         };
@@ -365,14 +1485,14 @@ impl KaibaClient {
             .post(&api_url)
             .header("Authorization", format!("Bearer {}", self.api_key))
             .json(&request)
-            .send()
+            .send_retrying(&self.options)
             .await
             .context("Failed to connect to Kaiba API")?;
 
         if !resp.status().is_success() {
             let status = resp.status();
             let body = resp.text().await.unwrap_or_default();
-            bail!("API error ({}): {}", status, body);
+            bail!("API error ({}): {}", status, error_detail(&body));
         }
 
         let webhook: WebhookResponse = resp.json().await.context("Failed to parse response")?;
@@ -410,14 +1530,14 @@ impl KaibaClient {
             .put(&api_url)
             .header("Authorization", format!("Bearer {}", self.api_key))
             .json(&request)
-            .send()
+            .send_retrying(&self.options)
             .await
             .context("Failed to connect to Kaiba API")?;
 
         if !resp.status().is_success() {
             let status = resp.status();
             let body = resp.text().await.unwrap_or_default();
-            bail!("API error ({}): {}", status, body);
+            bail!("API error ({}): {}", status, error_detail(&body));
         }
 
         let webhook: WebhookResponse = resp.json().await.context("Failed to parse response")?;
@@ -436,14 +1556,14 @@ impl KaibaClient {
             .client
             .delete(&url)
             .header("Authorization", format!("Bearer {}", self.api_key))
-            .send()
+            .send_retrying(&self.options)
             .await
             .context("Failed to connect to Kaiba API")?;
 
         if !resp.status().is_success() {
             let status = resp.status();
             let body = resp.text().await.unwrap_or_default();
-            bail!("API error ({}): {}", status, body);
+            bail!("API error ({}): {}", status, error_detail(&body));
         }
 
         Ok(())
@@ -470,14 +1590,14 @@ impl KaibaClient {
             .post(&url)
             .header("Authorization", format!("Bearer {}", self.api_key))
             .json(&payload)
-            .send()
+            .send_retrying(&self.options)
             .await
             .context("Failed to connect to Kaiba API")?;
 
         if !resp.status().is_success() {
             let status = resp.status();
             let body = resp.text().await.unwrap_or_default();
-            bail!("API error ({}): {}", status, body);
+            bail!("API error ({}): {}", status, error_detail(&body));
         }
 
         let delivery: WebhookDeliveryResponse =
@@ -486,29 +1606,71 @@ impl KaibaClient {
         Ok(delivery)
     }
 
+    /// Render a webhook request without sending it
+    pub async fn dry_run_webhook(
+        &self,
+        rei_id: &str,
+        webhook_id: &str,
+        event: Option<String>,
+    ) -> Result<WebhookDryRunResponse> {
+        let url = format!(
+            "{}/kaiba/rei/{}/webhooks/{}/trigger",
+            self.base_url, rei_id, webhook_id
+        );
+
+        let payload = serde_json::json!({
+            "event": event,
+            "dry_run": true,
+        });
+
+        let resp = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .json(&payload)
+            .send_retrying(&self.options)
+            .await
+            .context("Failed to connect to Kaiba API")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            bail!("API error ({}): {}", status, error_detail(&body));
+        }
+
+        let rendered: WebhookDryRunResponse =
+            resp.json().await.context("Failed to parse response")?;
+
+        Ok(rendered)
+    }
+
     /// List webhook deliveries
     pub async fn list_deliveries(
         &self,
         rei_id: &str,
         webhook_id: &str,
+        status: Option<&str>,
     ) -> Result<Vec<WebhookDeliveryResponse>> {
-        let url = format!(
+        let mut url = format!(
             "{}/kaiba/rei/{}/webhooks/{}/deliveries",
             self.base_url, rei_id, webhook_id
         );
+        if let Some(status) = status {
+            url.push_str(&format!("?status={}", urlencoding::encode(status)));
+        }
 
         let resp = self
             .client
             .get(&url)
             .header("Authorization", format!("Bearer {}", self.api_key))
-            .send()
+            .send_retrying(&self.options)
             .await
             .context("Failed to connect to Kaiba API")?;
 
         if !resp.status().is_success() {
             let status = resp.status();
             let body = resp.text().await.unwrap_or_default();
-            bail!("API error ({}): {}", status, body);
+            bail!("API error ({}): {}", status, error_detail(&body));
         }
 
         let deliveries: Vec<WebhookDeliveryResponse> =
@@ -516,4 +1678,145 @@ impl KaibaClient {
 
         Ok(deliveries)
     }
+
+    /// Replay a past webhook delivery
+    pub async fn replay_delivery(
+        &self,
+        rei_id: &str,
+        webhook_id: &str,
+        delivery_id: &str,
+    ) -> Result<WebhookDeliveryResponse> {
+        let url = format!(
+            "{}/kaiba/rei/{}/webhooks/{}/deliveries/{}/replay",
+            self.base_url, rei_id, webhook_id, delivery_id
+        );
+
+        let resp = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .send_retrying(&self.options)
+            .await
+            .context("Failed to connect to Kaiba API")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            bail!("API error ({}): {}", status, error_detail(&body));
+        }
+
+        let delivery: WebhookDeliveryResponse =
+            resp.json().await.context("Failed to parse response")?;
+
+        Ok(delivery)
+    }
+
+    /// List registered custom events
+    pub async fn list_events(&self) -> Result<Vec<EventDefinitionResponse>> {
+        let url = format!("{}/kaiba/events", self.base_url);
+        let resp = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .send_retrying(&self.options)
+            .await
+            .context("Failed to connect to Kaiba API")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            bail!("API error ({}): {}", status, error_detail(&body));
+        }
+
+        let events: Vec<EventDefinitionResponse> =
+            resp.json().await.context("Failed to parse response")?;
+
+        Ok(events)
+    }
+
+    /// Register a custom event
+    pub async fn define_event(
+        &self,
+        name: &str,
+        description: Option<String>,
+        schema: Option<serde_json::Value>,
+    ) -> Result<EventDefinitionResponse> {
+        let url = format!("{}/kaiba/events", self.base_url);
+
+        let request = CreateEventDefinitionRequest {
+            name: name.to_string(),
+            description,
+            schema,
+        };
+
+        let resp = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .json(&request)
+            .send_retrying(&self.options)
+            .await
+            .context("Failed to connect to Kaiba API")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            bail!("API error ({}): {}", status, error_detail(&body));
+        }
+
+        let event: EventDefinitionResponse =
+            resp.json().await.context("Failed to parse response")?;
+
+        Ok(event)
+    }
+
+    /// Unregister a custom event
+    pub async fn delete_event(&self, name: &str) -> Result<()> {
+        let url = format!("{}/kaiba/events/{}", self.base_url, name);
+
+        let resp = self
+            .client
+            .delete(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .send_retrying(&self.options)
+            .await
+            .context("Failed to connect to Kaiba API")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            bail!("API error ({}): {}", status, error_detail(&body));
+        }
+
+        Ok(())
+    }
+
+    /// Emit a custom event for a Rei
+    pub async fn emit_event(
+        &self,
+        rei_id: &str,
+        name: &str,
+        data: Option<serde_json::Value>,
+    ) -> Result<EmitEventResponse> {
+        let url = format!("{}/kaiba/rei/{}/events/{}", self.base_url, rei_id, name);
+
+        let resp = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .json(&serde_json::json!({ "data": data }))
+            .send_retrying(&self.options)
+            .await
+            .context("Failed to connect to Kaiba API")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            bail!("API error ({}): {}", status, error_detail(&body));
+        }
+
+        let emitted: EmitEventResponse = resp.json().await.context("Failed to parse response")?;
+
+        Ok(emitted)
+    }
 }
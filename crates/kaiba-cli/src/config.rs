@@ -1,16 +1,37 @@
 //! Configuration management for Kaiba CLI
 //!
 //! Stores API key, profiles, and default settings in ~/.config/kaiba/config.toml
+//!
+//! The top-level `base_url`/`api_key` form the `default` context. Extra named
+//! contexts (e.g. a local server) can be added and switched between.
+//!
+//! A context marked `keychain = true` keeps its API key in the OS keychain
+//! instead of the file.
+//!
+//! `KAIBA_BASE_URL` and `KAIBA_API_KEY` override the current context without
+//! touching the file, for CI and containers.
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
+use crate::keychain;
+
 const CONFIG_DIR: &str = "kaiba";
 const CONFIG_FILE: &str = "config.toml";
 
+/// Name of the context backed by the top-level `base_url`/`api_key`
+pub const DEFAULT_CONTEXT: &str = "default";
+
+const ENV_BASE_URL: &str = "KAIBA_BASE_URL";
+const ENV_API_KEY: &str = "KAIBA_API_KEY";
+
+/// Keys accepted by `kaiba config get/set/unset`
+pub const SETTINGS: [&str; 3] = ["base_url", "api_key", "default_profile"];
+
 /// Profile for a Rei
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Profile {
@@ -20,17 +41,86 @@ pub struct Profile {
     pub name: Option<String>,
 }
 
+/// Named server connection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerContext {
+    pub base_url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    /// API key is stored in the OS keychain
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub keychain: bool,
+}
+
+/// Profile change made by `kaiba profile sync`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum ProfileChange {
+    Added {
+        profile: String,
+        rei_id: String,
+    },
+    /// Display name refreshed from the Rei
+    Updated {
+        profile: String,
+        rei_id: String,
+    },
+    /// Rei no longer exists (only with prune)
+    Removed {
+        profile: String,
+        rei_id: String,
+    },
+}
+
+/// Profile name for a Rei name: lowercase, runs of other characters become `-`
+pub fn slugify(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
 /// CLI Configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub api_key: Option<String>,
+    /// API key of the default context is stored in the OS keychain
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub keychain: bool,
     #[serde(default = "default_base_url")]
     pub base_url: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default_profile: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_context: Option<String>,
     #[serde(default)]
     pub profiles: HashMap<String, Profile>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub contexts: HashMap<String, ServerContext>,
+    /// Keychain API key of the current context, read on load
+    #[serde(skip)]
+    keychain_key: Option<String>,
+    /// `KAIBA_BASE_URL`, never saved
+    #[serde(skip)]
+    env_base_url: Option<String>,
+    /// `KAIBA_API_KEY`, never saved
+    #[serde(skip)]
+    env_api_key: Option<String>,
+}
+
+/// Read a non-empty environment variable
+fn env_var(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|v| !v.is_empty())
 }
 
 fn default_base_url() -> String {
@@ -41,9 +131,15 @@ impl Default for Config {
     fn default() -> Self {
         Self {
             api_key: None,
+            keychain: false,
             base_url: default_base_url(),
             default_profile: None,
+            current_context: None,
             profiles: HashMap::new(),
+            contexts: HashMap::new(),
+            keychain_key: None,
+            env_base_url: None,
+            env_api_key: None,
         }
     }
 }
@@ -62,23 +158,46 @@ impl Config {
         Ok(Self::config_dir()?.join(CONFIG_FILE))
     }
 
-    /// Load config from file, or create default
+    /// Load config from file, or create default, then apply env overrides
     pub fn load() -> Result<Self> {
         let path = Self::config_path()?;
 
-        if !path.exists() {
-            return Ok(Self::default());
-        }
+        let mut config = if path.exists() {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read config from {:?}", path))?;
+            toml::from_str(&content).with_context(|| "Failed to parse config file")?
+        } else {
+            Self::default()
+        };
 
-        let content = fs::read_to_string(&path)
-            .with_context(|| format!("Failed to read config from {:?}", path))?;
+        config.env_base_url = env_var(ENV_BASE_URL);
+        config.env_api_key = env_var(ENV_API_KEY);
 
-        let config: Config =
-            toml::from_str(&content).with_context(|| "Failed to parse config file")?;
+        config.load_keychain_key();
 
         Ok(config)
     }
 
+    /// Read the current context's API key from the OS keychain, if kept there
+    ///
+    /// An unavailable keychain (e.g. on a headless machine) only warns, so
+    /// commands that need no key still run.
+    fn load_keychain_key(&mut self) {
+        self.keychain_key = None;
+        // No keychain lookup when the key comes from the environment (CI has none)
+        if self.env_api_key.is_some() || !self.uses_keychain() {
+            return;
+        }
+        match keychain::get(self.current_context_name()) {
+            Ok(key) => self.keychain_key = key,
+            Err(e) => eprintln!(
+                "{} {:#}; continuing without an API key",
+                "Warning:".yellow(),
+                e
+            ),
+        }
+    }
+
     /// Save config to file
     pub fn save(&self) -> Result<()> {
         let dir = Self::config_dir()?;
@@ -94,9 +213,212 @@ impl Config {
         Ok(())
     }
 
-    /// Set API key
-    pub fn set_api_key(&mut self, key: String) {
-        self.api_key = Some(key);
+    /// Base URL of the current context
+    pub fn base_url(&self) -> &str {
+        if let Some(url) = &self.env_base_url {
+            return url;
+        }
+        match self.active_context() {
+            Some(ctx) => &ctx.base_url,
+            None => &self.base_url,
+        }
+    }
+
+    /// API key of the current context
+    pub fn api_key(&self) -> Option<&str> {
+        if self.env_api_key.is_some() {
+            return self.env_api_key.as_deref();
+        }
+        if self.uses_keychain() {
+            return self.keychain_key.as_deref();
+        }
+        match self.active_context() {
+            Some(ctx) => ctx.api_key.as_deref(),
+            None => self.api_key.as_deref(),
+        }
+    }
+
+    /// Whether the current context keeps its API key in the OS keychain
+    pub fn uses_keychain(&self) -> bool {
+        match self.active_context() {
+            Some(ctx) => ctx.keychain,
+            None => self.keychain,
+        }
+    }
+
+    /// Set API key for the current context, in the OS keychain or the file
+    pub fn set_api_key(&mut self, key: String, use_keychain: bool) -> Result<()> {
+        let name = self.current_context_name().to_string();
+        if use_keychain {
+            keychain::set(&name, &key)?;
+        } else if self.uses_keychain() {
+            // Moving back to the file; a stale keychain entry is harmless
+            let _ = keychain::delete(&name);
+        }
+
+        let file_key = if use_keychain {
+            None
+        } else {
+            Some(key.clone())
+        };
+        match self.contexts.get_mut(&name) {
+            Some(ctx) => {
+                ctx.api_key = file_key;
+                ctx.keychain = use_keychain;
+            }
+            None => {
+                self.api_key = file_key;
+                self.keychain = use_keychain;
+            }
+        }
+        self.keychain_key = use_keychain.then_some(key);
+        Ok(())
+    }
+
+    /// Environment variables currently overriding the file
+    pub fn env_overrides(&self) -> Vec<&'static str> {
+        let mut names = Vec::new();
+        if self.env_base_url.is_some() {
+            names.push(ENV_BASE_URL);
+        }
+        if self.env_api_key.is_some() {
+            names.push(ENV_API_KEY);
+        }
+        names
+    }
+
+    /// Effective value of a setting (see [`SETTINGS`])
+    pub fn get_setting(&self, key: &str) -> Result<Option<String>> {
+        match key {
+            "base_url" => Ok(Some(self.base_url().to_string())),
+            "api_key" => Ok(self.api_key().map(|k| k.to_string())),
+            "default_profile" => Ok(self.default_profile.clone()),
+            other => bail!(
+                "Unknown setting '{}'. Valid: {}",
+                other,
+                SETTINGS.join(", ")
+            ),
+        }
+    }
+
+    /// Set a setting; `base_url` and `api_key` apply to the current context
+    pub fn set_setting(&mut self, key: &str, value: String) -> Result<()> {
+        match key {
+            "base_url" => {
+                let name = self.current_context_name().to_string();
+                match self.contexts.get_mut(&name) {
+                    Some(ctx) => ctx.base_url = value,
+                    None => self.base_url = value,
+                }
+            }
+            "api_key" => self.set_api_key(value, self.uses_keychain())?,
+            "default_profile" => {
+                if !self.set_default_profile(value.clone()) {
+                    bail!("Profile '{}' not found", value);
+                }
+            }
+            other => bail!(
+                "Unknown setting '{}'. Valid: {}",
+                other,
+                SETTINGS.join(", ")
+            ),
+        }
+        Ok(())
+    }
+
+    /// Clear a setting (`base_url` of the default context resets to the built-in URL)
+    pub fn unset_setting(&mut self, key: &str) -> Result<()> {
+        match key {
+            "base_url" => {
+                if self.active_context().is_some() {
+                    bail!(
+                        "A named context needs a base_url. Use 'kaiba context rm {}' instead",
+                        self.current_context_name()
+                    );
+                }
+                self.base_url = default_base_url();
+            }
+            "api_key" => {
+                let name = self.current_context_name().to_string();
+                if self.uses_keychain() {
+                    let _ = keychain::delete(&name);
+                }
+                match self.contexts.get_mut(&name) {
+                    Some(ctx) => {
+                        ctx.api_key = None;
+                        ctx.keychain = false;
+                    }
+                    None => {
+                        self.api_key = None;
+                        self.keychain = false;
+                    }
+                }
+                self.keychain_key = None;
+            }
+            "default_profile" => self.default_profile = None,
+            other => bail!(
+                "Unknown setting '{}'. Valid: {}",
+                other,
+                SETTINGS.join(", ")
+            ),
+        }
+        Ok(())
+    }
+
+    /// Name of the current context
+    pub fn current_context_name(&self) -> &str {
+        match self.active_context() {
+            Some(_) => self.current_context.as_deref().unwrap_or(DEFAULT_CONTEXT),
+            None => DEFAULT_CONTEXT,
+        }
+    }
+
+    fn active_context(&self) -> Option<&ServerContext> {
+        self.current_context
+            .as_ref()
+            .and_then(|name| self.contexts.get(name))
+    }
+
+    /// Add or replace a named context
+    pub fn add_context(&mut self, name: String, base_url: String, api_key: Option<String>) {
+        self.contexts.insert(
+            name,
+            ServerContext {
+                base_url,
+                api_key,
+                keychain: false,
+            },
+        );
+    }
+
+    /// Remove a context, falling back to `default` if it was current
+    pub fn remove_context(&mut self, name: &str) -> bool {
+        if self.current_context.as_deref() == Some(name) {
+            self.current_context = None;
+        }
+        match self.contexts.remove(name) {
+            Some(ctx) => {
+                if ctx.keychain {
+                    let _ = keychain::delete(name);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Switch the current context (`default` selects the top-level settings)
+    pub fn use_context(&mut self, name: &str) -> bool {
+        if name == DEFAULT_CONTEXT {
+            self.current_context = None;
+        } else if self.contexts.contains_key(name) {
+            self.current_context = Some(name.to_string());
+        } else {
+            return false;
+        }
+        // The cached keychain key belonged to the previous context
+        self.load_keychain_key();
+        true
     }
 
     /// Add a profile
@@ -125,6 +447,68 @@ impl Config {
         }
     }
 
+    /// Create or refresh a profile for each `(rei_id, name)`
+    ///
+    /// Reis that already have a profile keep its name; only the display name
+    /// is updated. New Reis get a slug of their name, with the short ID
+    /// appended if that name is taken. With `prune`, profiles of Reis not in
+    /// the list are removed.
+    pub fn sync_profiles(&mut self, reis: &[(String, String)], prune: bool) -> Vec<ProfileChange> {
+        let mut changes = Vec::new();
+
+        for (rei_id, rei_name) in reis {
+            let existing = self
+                .profiles
+                .iter_mut()
+                .find(|(_, profile)| &profile.rei_id == rei_id);
+            if let Some((name, profile)) = existing {
+                if profile.name.as_ref() != Some(rei_name) {
+                    profile.name = Some(rei_name.clone());
+                    changes.push(ProfileChange::Updated {
+                        profile: name.clone(),
+                        rei_id: rei_id.clone(),
+                    });
+                }
+                continue;
+            }
+
+            let short_id = rei_id.get(..8).unwrap_or(rei_id);
+            let mut name = slugify(rei_name);
+            if name.is_empty() {
+                name = format!("rei-{}", short_id);
+            } else if self.profiles.contains_key(&name) {
+                name = format!("{}-{}", name, short_id);
+            }
+            self.add_profile(name.clone(), rei_id.clone(), Some(rei_name.clone()));
+            changes.push(ProfileChange::Added {
+                profile: name,
+                rei_id: rei_id.clone(),
+            });
+        }
+
+        if prune {
+            let mut stale: Vec<(String, String)> = self
+                .profiles
+                .iter()
+                .filter(|(_, profile)| !reis.iter().any(|(id, _)| id == &profile.rei_id))
+                .map(|(name, profile)| (name.clone(), profile.rei_id.clone()))
+                .collect();
+            stale.sort();
+            for (name, rei_id) in stale {
+                self.remove_profile(&name);
+                if self.default_profile.as_ref() == Some(&name) {
+                    self.default_profile = None;
+                }
+                changes.push(ProfileChange::Removed {
+                    profile: name,
+                    rei_id,
+                });
+            }
+        }
+
+        changes
+    }
+
     /// Get the active profile (specified or default)
     pub fn get_profile(&self, name: Option<&str>) -> Option<&Profile> {
         let profile_name = name
@@ -139,3 +523,57 @@ impl Config {
         self.get_profile(profile).map(|p| p.rei_id.clone())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Shii-chan"), "shii-chan");
+        assert_eq!(slugify("  Rust Engineer (v2)! "), "rust-engineer-v2");
+        assert_eq!(slugify("しぃ"), "しぃ");
+        assert_eq!(slugify("!!!"), "");
+    }
+
+    #[test]
+    fn test_sync_profiles() {
+        let mut config = Config::default();
+        config.add_profile("mine".to_string(), "id-aaaaaaaa".to_string(), None);
+        config.add_profile("mai".to_string(), "id-gone0000".to_string(), None);
+        config.default_profile = Some("mai".to_string());
+
+        let reis = vec![
+            ("id-aaaaaaaa".to_string(), "Shii".to_string()),
+            ("id-bbbbbbbb".to_string(), "Mai".to_string()),
+            ("id-cccccccc".to_string(), "???".to_string()),
+        ];
+        let changes = config.sync_profiles(&reis, true);
+
+        assert_eq!(
+            changes,
+            vec![
+                ProfileChange::Updated {
+                    profile: "mine".to_string(),
+                    rei_id: "id-aaaaaaaa".to_string(),
+                },
+                ProfileChange::Added {
+                    profile: "mai-id-bbbbb".to_string(),
+                    rei_id: "id-bbbbbbbb".to_string(),
+                },
+                ProfileChange::Added {
+                    profile: "rei-id-ccccc".to_string(),
+                    rei_id: "id-cccccccc".to_string(),
+                },
+                ProfileChange::Removed {
+                    profile: "mai".to_string(),
+                    rei_id: "id-gone0000".to_string(),
+                },
+            ]
+        );
+        assert_eq!(config.default_profile, None);
+
+        // A second sync has nothing to do
+        assert!(config.sync_profiles(&reis, true).is_empty());
+    }
+}
@@ -0,0 +1,94 @@
+//! Doctor - setup checks for `kaiba doctor`, each with a suggested fix
+
+use colored::Colorize;
+use serde::Serialize;
+
+/// Outcome of a single check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+}
+
+/// A single diagnostic result
+#[derive(Debug, Serialize)]
+pub struct Check {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+    /// What to do about a warning or failure
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<String>,
+}
+
+impl Check {
+    pub fn ok(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Ok,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    pub fn warn(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Warn,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    pub fn fail(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Fail,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    pub fn print(&self) {
+        let mark = match self.status {
+            CheckStatus::Ok => "✓".green(),
+            CheckStatus::Warn => "⚠".yellow(),
+            CheckStatus::Fail => "✗".red(),
+        };
+        println!("{} {:<10} {}", mark, self.name.bold(), self.detail);
+        if let Some(fix) = &self.fix {
+            println!("  {} {}", "→".dimmed(), fix);
+        }
+    }
+}
+
+/// Whether a server version works with this CLI: same major version, and
+/// same minor version while below 1.0
+pub fn versions_compatible(cli: &str, server: &str) -> bool {
+    let parse = |v: &str| -> Option<(u64, u64)> {
+        let mut parts = v.trim_start_matches('v').split('.');
+        Some((parts.next()?.parse().ok()?, parts.next()?.parse().ok()?))
+    };
+
+    match (parse(cli), parse(server)) {
+        (Some((0, cli_minor)), Some((0, server_minor))) => cli_minor == server_minor,
+        (Some((cli_major, _)), Some((server_major, _))) => cli_major == server_major,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versions_compatible() {
+        assert!(versions_compatible("0.2.1", "0.2.0"));
+        assert!(!versions_compatible("0.2.1", "0.3.0"));
+        assert!(versions_compatible("1.4.0", "v1.0.2"));
+        assert!(!versions_compatible("1.4.0", "2.0.0"));
+        assert!(!versions_compatible("0.2.1", "unknown"));
+    }
+}
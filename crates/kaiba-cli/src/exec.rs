@@ -0,0 +1,81 @@
+//! Exec - run an external command with the persona prompt substituted in
+//!
+//! `{}` in an argument becomes the prompt text, `{file}` the path of a
+//! temporary file holding the prompt (for tools that take a file).
+
+/// Placeholder replaced with the prompt text
+pub const PROMPT_PLACEHOLDER: &str = "{}";
+/// Placeholder replaced with the path of a file containing the prompt
+pub const FILE_PLACEHOLDER: &str = "{file}";
+
+/// Whether any argument needs the prompt written to a file
+pub fn needs_prompt_file(args: &[String]) -> bool {
+    args.iter().any(|arg| arg.contains(FILE_PLACEHOLDER))
+}
+
+/// Whether any argument contains a placeholder
+pub fn has_placeholder(args: &[String]) -> bool {
+    args.iter()
+        .any(|arg| arg.contains(PROMPT_PLACEHOLDER) || arg.contains(FILE_PLACEHOLDER))
+}
+
+/// Substitute placeholders in each argument
+pub fn render_args(args: &[String], prompt: &str, prompt_file: Option<&str>) -> Vec<String> {
+    args.iter()
+        .map(|arg| {
+            let arg = match prompt_file {
+                Some(path) => arg.replace(FILE_PLACEHOLDER, path),
+                None => arg.clone(),
+            };
+            arg.replace(PROMPT_PLACEHOLDER, prompt)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_render_args() {
+        let rendered = render_args(
+            &args(&["claude", "--system-prompt", "{}"]),
+            "You are Mai",
+            None,
+        );
+        assert_eq!(
+            rendered,
+            args(&["claude", "--system-prompt", "You are Mai"])
+        );
+
+        let rendered = render_args(
+            &args(&["tool", "--prompt-file={file}", "-v"]),
+            "You are Mai",
+            Some("/tmp/kaiba-prompt.md"),
+        );
+        assert_eq!(
+            rendered,
+            args(&["tool", "--prompt-file=/tmp/kaiba-prompt.md", "-v"])
+        );
+    }
+
+    #[test]
+    fn test_render_args_does_not_expand_prompt_contents() {
+        // Braces inside the prompt are left alone
+        let rendered = render_args(&args(&["x", "{}"]), "use {file} and {}", Some("/p"));
+        assert_eq!(rendered, args(&["x", "use {file} and {}"]));
+    }
+
+    #[test]
+    fn test_placeholders() {
+        assert!(has_placeholder(&args(&["a", "{}"])));
+        assert!(has_placeholder(&args(&["a", "--f={file}"])));
+        assert!(!has_placeholder(&args(&["a", "b"])));
+        assert!(needs_prompt_file(&args(&["{file}"])));
+        assert!(!needs_prompt_file(&args(&["{}"])));
+    }
+}
@@ -0,0 +1,130 @@
+//! Front-mattered markdown for `kaiba memory edit`
+//!
+//! ```text
+//! ---
+//! type: learning
+//! importance: 0.7
+//! tags: rust, async
+//! ---
+//!
+//! Memory content...
+//! ```
+
+use anyhow::{bail, Context, Result};
+
+const FENCE: &str = "---";
+
+/// Editable fields of a memory
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemoryDocument {
+    pub memory_type: String,
+    pub importance: f32,
+    pub tags: Vec<String>,
+    pub content: String,
+}
+
+impl MemoryDocument {
+    pub fn render(&self) -> String {
+        format!(
+            "{fence}\ntype: {}\nimportance: {}\ntags: {}\n{fence}\n\n{}\n",
+            self.memory_type,
+            self.importance,
+            self.tags.join(", "),
+            self.content,
+            fence = FENCE
+        )
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut lines = text.lines();
+        if lines.next().map(str::trim) != Some(FENCE) {
+            bail!("Missing front matter: the file must start with '{}'", FENCE);
+        }
+
+        let mut memory_type = None;
+        let mut importance = None;
+        let mut tags = Vec::new();
+        let mut closed = false;
+
+        for line in lines.by_ref() {
+            let line = line.trim();
+            if line == FENCE {
+                closed = true;
+                break;
+            }
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once(':')
+                .with_context(|| format!("Invalid front matter line '{}'", line))?;
+            let value = value.trim();
+            match key.trim() {
+                "type" => memory_type = Some(value.to_string()),
+                "importance" => {
+                    let parsed: f32 = value
+                        .parse()
+                        .with_context(|| format!("Invalid importance '{}'", value))?;
+                    if !(0.0..=1.0).contains(&parsed) {
+                        bail!("importance must be between 0.0 and 1.0");
+                    }
+                    importance = Some(parsed);
+                }
+                "tags" => {
+                    tags = value
+                        .split(',')
+                        .map(|t| t.trim().to_string())
+                        .filter(|t| !t.is_empty())
+                        .collect();
+                }
+                other => bail!("Unknown front matter key '{}'", other),
+            }
+        }
+
+        if !closed {
+            bail!("Front matter is not closed with '{}'", FENCE);
+        }
+
+        let content = lines.collect::<Vec<_>>().join("\n").trim().to_string();
+        if content.is_empty() {
+            bail!("Memory content is empty");
+        }
+
+        Ok(Self {
+            memory_type: memory_type.context("Missing 'type' in front matter")?,
+            importance: importance.context("Missing 'importance' in front matter")?,
+            tags,
+            content,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let doc = MemoryDocument {
+            memory_type: "learning".to_string(),
+            importance: 0.7,
+            tags: vec!["rust".to_string(), "async".to_string()],
+            content: "Pin is needed for self-referential futures.\n\nSecond paragraph.".to_string(),
+        };
+
+        assert_eq!(MemoryDocument::parse(&doc.render()).unwrap(), doc);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(MemoryDocument::parse("no front matter").is_err());
+        assert!(MemoryDocument::parse("---\ntype: fact\nimportance: 0.5\n\nbody").is_err());
+        assert!(MemoryDocument::parse("---\ntype: fact\nimportance: 2\n---\nbody").is_err());
+        assert!(MemoryDocument::parse("---\ntype: fact\nimportance: 0.5\n---\n\n").is_err());
+        assert!(MemoryDocument::parse("---\ncolor: red\n---\nbody").is_err());
+
+        let doc =
+            MemoryDocument::parse("---\ntype: fact\nimportance: 0.5\ntags:\n---\nbody").unwrap();
+        assert!(doc.tags.is_empty());
+    }
+}
@@ -0,0 +1,113 @@
+//! Memory import - walk a directory and split documents into memory-sized chunks
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Files under `dir` matching `pattern` (e.g. `**/*.md`), sorted
+pub fn collect_files(dir: &Path, pattern: &str) -> Result<Vec<PathBuf>> {
+    let full_pattern = dir.join(pattern);
+    let full_pattern = full_pattern
+        .to_str()
+        .context("Directory path is not valid UTF-8")?;
+
+    let mut files = Vec::new();
+    for entry in glob::glob(full_pattern).context("Invalid glob pattern")? {
+        let path = entry.context("Failed to read directory entry")?;
+        if path.is_file() {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Tags from the directory components of `file` relative to `dir`
+///
+/// `notes/rust/async.md` imported from `notes` yields `["rust"]`.
+pub fn path_tags(dir: &Path, file: &Path) -> Vec<String> {
+    file.strip_prefix(dir)
+        .ok()
+        .and_then(|rel| rel.parent())
+        .map(|parent| {
+            parent
+                .components()
+                .filter_map(|c| c.as_os_str().to_str())
+                .map(|c| c.to_lowercase())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Split text into chunks of at most `max_chars`, preferring paragraph breaks
+pub fn chunk_text(text: &str, max_chars: usize) -> Vec<String> {
+    let max_chars = max_chars.max(1);
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    for paragraph in text.split("\n\n").map(str::trim).filter(|p| !p.is_empty()) {
+        let paragraph_len = paragraph.chars().count();
+        let current_len = current.chars().count();
+
+        if current_len > 0 && current_len + 2 + paragraph_len > max_chars {
+            chunks.push(std::mem::take(&mut current));
+        }
+
+        if paragraph_len > max_chars {
+            // Hard-split paragraphs that cannot fit on their own
+            let chars: Vec<char> = paragraph.chars().collect();
+            for piece in chars.chunks(max_chars) {
+                chunks.push(piece.iter().collect::<String>().trim().to_string());
+            }
+            continue;
+        }
+
+        if !current.is_empty() {
+            current.push_str("\n\n");
+        }
+        current.push_str(paragraph);
+    }
+
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks.retain(|c| !c.is_empty());
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk_text_keeps_small_documents_whole() {
+        let text = "# Title\n\nFirst paragraph.\n\nSecond paragraph.\n";
+        assert_eq!(
+            chunk_text(text, 1000),
+            vec!["# Title\n\nFirst paragraph.\n\nSecond paragraph."]
+        );
+        assert!(chunk_text("  \n\n  ", 1000).is_empty());
+    }
+
+    #[test]
+    fn test_chunk_text_splits_on_paragraphs() {
+        let text = "aaaa\n\nbbbb\n\ncccc";
+        assert_eq!(chunk_text(text, 10), vec!["aaaa\n\nbbbb", "cccc"]);
+    }
+
+    #[test]
+    fn test_chunk_text_hard_splits_long_paragraphs() {
+        let text = "short\n\n0123456789abcdef";
+        assert_eq!(chunk_text(text, 8), vec!["short", "01234567", "89abcdef"]);
+    }
+
+    #[test]
+    fn test_path_tags() {
+        let dir = Path::new("notes");
+        assert_eq!(
+            path_tags(dir, Path::new("notes/Rust/async/tokio.md")),
+            vec!["rust", "async"]
+        );
+        assert!(path_tags(dir, Path::new("notes/index.md")).is_empty());
+        assert!(path_tags(dir, Path::new("other/file.md")).is_empty());
+    }
+}
@@ -0,0 +1,33 @@
+//! OS keychain storage for API keys (one entry per server context)
+
+use anyhow::{Context, Result};
+
+const SERVICE: &str = "kaiba";
+
+fn entry(context: &str) -> Result<keyring::Entry> {
+    keyring::Entry::new(SERVICE, context).context("Failed to open OS keychain")
+}
+
+/// Read the API key stored for a context, if any
+pub fn get(context: &str) -> Result<Option<String>> {
+    match entry(context)?.get_password() {
+        Ok(key) => Ok(Some(key)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e).context("Failed to read API key from OS keychain"),
+    }
+}
+
+/// Store the API key for a context
+pub fn set(context: &str, key: &str) -> Result<()> {
+    entry(context)?
+        .set_password(key)
+        .context("Failed to store API key in OS keychain")
+}
+
+/// Remove the API key for a context (missing entries are fine)
+pub fn delete(context: &str) -> Result<()> {
+    match entry(context)?.delete_password() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(e).context("Failed to remove API key from OS keychain"),
+    }
+}
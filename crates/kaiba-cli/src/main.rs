@@ -4,32 +4,73 @@
 
 mod api;
 mod config;
+mod doctor;
+mod exec;
+mod frontmatter;
+mod import;
+mod keychain;
+mod output;
+mod time;
 
 use anyhow::{bail, Context, Result};
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
 use colored::Colorize;
-use dialoguer::{Input, Password};
+use dialoguer::{Confirm, Input, Password};
+use std::collections::{HashMap, HashSet};
 use std::fs;
-
-use api::KaibaClient;
-use config::Config;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use uuid::Uuid;
+
+use api::{
+    CallContext, CallRequest, CreateReiRequest, CreateTeiRequest, KaibaClient, LearnRequest,
+    SearchMemoriesRequest, UpdateMemoryRequest, UpdateReiRequest, UpdateReiStateRequest,
+    UpsertScheduleRequest,
+};
+use config::{Config, ProfileChange};
+use doctor::{Check, CheckStatus};
+use frontmatter::MemoryDocument;
+use output::{print_json, OutputFormat, Table};
 
 #[derive(Parser)]
 #[command(name = "kaiba")]
 #[command(about = "Kaiba CLI - Memory upload and management", long_about = None)]
 #[command(version)]
 struct Cli {
+    /// Output format: table (colored), json, or plain (no color, tab-separated)
+    #[arg(
+        short,
+        long,
+        global = true,
+        value_enum,
+        env = "KAIBA_OUTPUT",
+        default_value_t,
+        visible_alias = "format"
+    )]
+    output: OutputFormat,
+
+    /// Request timeout in seconds (cold-starting servers can be slow)
+    #[arg(long, global = true, env = "KAIBA_TIMEOUT", default_value_t = 60)]
+    timeout: u64,
+
+    /// Retries for connection failures and 502/503/504 responses
+    #[arg(long, global = true, env = "KAIBA_RETRIES", default_value_t = 2)]
+    retries: u32,
+
     #[command(subcommand)]
     command: Commands,
 }
 
 #[derive(Subcommand)]
 enum Commands {
-    /// Login and store API key
+    /// Login and store API key (in the OS keychain when available)
     Login {
         /// API key (will prompt if not provided)
         #[arg(short, long)]
         key: Option<String>,
+        /// Store the API key in the config file instead of the OS keychain
+        #[arg(long)]
+        no_keychain: bool,
     },
 
     /// Manage profiles (Rei shortcuts)
@@ -38,12 +79,18 @@ enum Commands {
         action: ProfileAction,
     },
 
-    /// List Reis from API
+    /// Rei management
     Rei {
         #[command(subcommand)]
         action: ReiAction,
     },
 
+    /// Tei (execution interface) management
+    Tei {
+        #[command(subcommand)]
+        action: TeiAction,
+    },
+
     /// Memory operations
     Memory {
         #[command(subcommand)]
@@ -56,6 +103,105 @@ enum Commands {
         action: WebhookAction,
     },
 
+    /// Custom webhook events
+    Event {
+        #[command(subcommand)]
+        action: EventAction,
+    },
+
+    /// Call a Rei from the terminal
+    Call {
+        /// Message to send
+        message: String,
+        /// Print the response as it is generated (when the server streams)
+        #[arg(long)]
+        stream: bool,
+        /// Include relevant memories (RAG)
+        #[arg(short = 'm', long)]
+        include_memories: bool,
+        /// Max memories to include
+        #[arg(long)]
+        memory_limit: Option<usize>,
+        /// Tei IDs to choose from (defaults to all associated Teis)
+        #[arg(long, value_delimiter = ',')]
+        tei: Vec<Uuid>,
+        /// Profile to use
+        #[arg(short, long)]
+        profile: Option<String>,
+        /// Show Tei, token usage, and memories used
+        #[arg(long)]
+        verbose: bool,
+    },
+
+    /// Chat with a Rei interactively (one conversation session)
+    Chat {
+        /// Profile to use
+        #[arg(short, long)]
+        profile: Option<String>,
+        /// Don't retrieve memories for each turn
+        #[arg(long)]
+        no_memories: bool,
+        /// Max memories to retrieve per turn
+        #[arg(long)]
+        memory_limit: Option<usize>,
+        /// Tei IDs to choose from (defaults to all associated Teis)
+        #[arg(long, value_delimiter = ',')]
+        tei: Vec<Uuid>,
+    },
+
+    /// Show or change a Rei's state (energy, mood, tokens)
+    State {
+        #[command(subcommand)]
+        action: StateAction,
+    },
+
+    /// Add energy to a Rei (e.g. `kaiba recharge +30`, `kaiba recharge -10`)
+    Recharge {
+        /// Energy to add; negative drains
+        #[arg(allow_hyphen_values = true)]
+        amount: i32,
+        /// Profile to use
+        #[arg(short, long)]
+        profile: Option<String>,
+    },
+
+    /// Status overview (energy, tokens, memories, activity, webhook failures)
+    Status {
+        /// Only this profile (defaults to all profiles)
+        #[arg(short, long)]
+        profile: Option<String>,
+    },
+
+    /// Print new calls, memories and webhook deliveries as they happen
+    Watch {
+        /// Profile to use
+        #[arg(short, long)]
+        profile: Option<String>,
+        /// Seconds between polls (servers without live events) or reconnects
+        #[arg(short, long, default_value = "5")]
+        interval: u64,
+    },
+
+    /// Run a self-learning session now
+    Learn {
+        /// Profile to use
+        #[arg(short, long)]
+        profile: Option<String>,
+        /// Learn even if energy is low
+        #[arg(long)]
+        force: bool,
+        /// Max search queries for this session
+        #[arg(long)]
+        max_queries: Option<usize>,
+    },
+
+    /// Digest recent learning into an expertise memory
+    Digest {
+        /// Profile to use
+        #[arg(short, long)]
+        profile: Option<String>,
+    },
+
     /// Get prompt for external Tei (Claude Code, Casting, etc.)
     Prompt {
         /// Output format: raw, claude-code, casting
@@ -73,10 +219,64 @@ enum Commands {
         /// Show metadata (Rei info, memory count)
         #[arg(long)]
         verbose: bool,
+        /// Copy the prompt to the clipboard instead of printing it
+        #[arg(long)]
+        copy: bool,
+        /// Write the prompt to a file (UTF-8) instead of printing it
+        #[arg(long, value_name = "FILE")]
+        out: Option<std::path::PathBuf>,
+    },
+
+    /// Run a command with the persona prompt substituted in
+    ///
+    /// `{}` becomes the prompt text, `{file}` the path of a temp file with the
+    /// prompt. Without placeholders the prompt is passed as $KAIBA_PROMPT.
+    #[command(
+        after_help = "Example: kaiba exec -p mai -- claude --system-prompt {}",
+        trailing_var_arg = true
+    )]
+    Exec {
+        /// Prompt format: raw, claude-code, casting
+        #[arg(short, long, default_value = "claude-code")]
+        format: String,
+        /// Include memories in prompt
+        #[arg(short = 'm', long)]
+        include_memories: bool,
+        /// Context for memory search (defaults to Rei name)
+        #[arg(short, long)]
+        context: Option<String>,
+        /// Profile to use
+        #[arg(short, long)]
+        profile: Option<String>,
+        /// Command and arguments
+        #[arg(required = true, allow_hyphen_values = true)]
+        command: Vec<String>,
     },
 
     /// Show current configuration
-    Config,
+    Config {
+        #[command(subcommand)]
+        action: Option<ConfigAction>,
+    },
+
+    /// Manage server contexts (base URL + API key)
+    Context {
+        #[command(subcommand)]
+        action: ContextAction,
+    },
+
+    /// Generate shell completions (e.g. `kaiba completions zsh > _kaiba`)
+    Completions {
+        /// Target shell
+        shell: clap_complete::Shell,
+    },
+
+    /// Check config, server reachability, auth and server features
+    Doctor,
+
+    /// Generate the man page (roff) on stdout
+    #[command(hide = true)]
+    Man,
 }
 
 #[derive(Subcommand)]
@@ -104,23 +304,264 @@ enum ProfileAction {
         /// Profile name to remove
         name: String,
     },
+    /// Create profiles for all Reis on the server, named after the Rei
+    Sync {
+        /// Also remove profiles whose Rei no longer exists
+        #[arg(long)]
+        prune: bool,
+        /// Show what would change without saving
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 #[derive(Subcommand)]
 enum ReiAction {
     /// List all Reis
     List,
+    /// Show a Rei with its manifest
+    Show {
+        /// Profile to use (defaults to the default profile)
+        profile: Option<String>,
+    },
+    /// Create a new Rei
+    Create {
+        /// Rei name
+        #[arg(short, long)]
+        name: String,
+        /// Role description
+        #[arg(short, long)]
+        role: String,
+        /// Avatar image URL
+        #[arg(long)]
+        avatar_url: Option<String>,
+        /// JSON file with the Rei manifest
+        #[arg(short, long)]
+        manifest: Option<String>,
+        /// Also save a profile shortcut with this name
+        #[arg(long)]
+        save_profile: Option<String>,
+    },
+    /// Update a Rei
+    Update {
+        /// Profile to use (defaults to the default profile)
+        profile: Option<String>,
+        /// New name
+        #[arg(long)]
+        name: Option<String>,
+        /// New role description
+        #[arg(long)]
+        role: Option<String>,
+        /// New avatar image URL
+        #[arg(long)]
+        avatar_url: Option<String>,
+        /// JSON file with the new manifest
+        #[arg(short, long)]
+        manifest: Option<String>,
+    },
+    /// Open a Rei's manifest in $EDITOR and save the changes
+    Edit {
+        /// Profile to use (defaults to the default profile)
+        profile: Option<String>,
+    },
+    /// Delete a Rei
+    #[command(visible_alias = "rm")]
+    Delete {
+        /// Profile to use (defaults to the default profile)
+        profile: Option<String>,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Restore a deleted Rei (until it is purged)
+    Restore {
+        /// Profile to use (defaults to the default profile)
+        profile: Option<String>,
+    },
+    /// Show or set when a Rei learns on its own
+    Schedule {
+        /// Profile to use (defaults to the default profile)
+        profile: Option<String>,
+        /// 5-field cron expression (e.g. "0 7 * * *")
+        #[arg(long, conflicts_with = "clear")]
+        cron: Option<String>,
+        /// IANA timezone for the cron expression (default: UTC)
+        #[arg(long, requires = "cron")]
+        timezone: Option<String>,
+        /// Remove the schedule and follow the server's interval again
+        #[arg(long)]
+        clear: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum TeiAction {
+    /// List Teis (all, or those linked to a profile's Rei)
+    #[command(visible_alias = "ls")]
+    List {
+        /// Only show Teis linked to this profile's Rei
+        #[arg(short, long)]
+        profile: Option<String>,
+    },
+    /// Create a Tei
+    Add {
+        /// Tei name
+        #[arg(short, long)]
+        name: String,
+        /// LLM provider (anthropic, openai, google)
+        #[arg(long)]
+        provider: String,
+        /// Model ID (e.g., "claude-sonnet-4-5")
+        #[arg(short, long)]
+        model: String,
+        /// Use when the Rei is low on energy
+        #[arg(long)]
+        fallback: bool,
+        /// Selection priority (lower is preferred)
+        #[arg(long, default_value = "0")]
+        priority: i32,
+        /// JSON file with provider config (temperature, max_tokens, ...)
+        #[arg(long)]
+        config: Option<String>,
+        /// Also link the new Tei to this profile's Rei
+        #[arg(long)]
+        link: Option<String>,
+    },
+    /// Delete a Tei
+    #[command(visible_alias = "remove")]
+    Rm {
+        /// Tei ID
+        tei_id: String,
+    },
+    /// Link a Tei to a Rei
+    Link {
+        /// Tei ID
+        tei_id: String,
+        /// Profile to use
+        #[arg(short, long)]
+        profile: Option<String>,
+    },
+    /// Unlink a Tei from a Rei
+    Unlink {
+        /// Tei ID
+        tei_id: String,
+        /// Profile to use
+        #[arg(short, long)]
+        profile: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum StateAction {
+    /// Show the current state
+    Show {
+        /// Profile to use
+        #[arg(short, long)]
+        profile: Option<String>,
+    },
+    /// Set state fields
+    Set {
+        /// Mood (e.g. focused, curious, tired)
+        #[arg(long)]
+        mood: Option<String>,
+        /// Energy level (0-100)
+        #[arg(long, value_parser = clap::value_parser!(i32).range(0..=100))]
+        energy: Option<i32>,
+        /// Token budget
+        #[arg(long)]
+        token_budget: Option<i32>,
+        /// Tokens used (e.g. 0 to reset)
+        #[arg(long)]
+        tokens_used: Option<i32>,
+        /// Energy regenerated per hour
+        #[arg(long)]
+        regen: Option<i32>,
+        /// Profile to use
+        #[arg(short, long)]
+        profile: Option<String>,
+    },
+    /// Pause autonomy: the scheduler and trigger leave the Rei alone
+    Pause {
+        /// Profile to use
+        #[arg(short, long)]
+        profile: Option<String>,
+    },
+    /// Resume autonomy after a pause
+    Resume {
+        /// Profile to use
+        #[arg(short, long)]
+        profile: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Show the configuration (default)
+    Show,
+    /// Print the effective value of a setting
+    Get {
+        #[arg(value_parser = clap::builder::PossibleValuesParser::new(config::SETTINGS))]
+        key: String,
+    },
+    /// Change a setting
+    Set {
+        #[arg(value_parser = clap::builder::PossibleValuesParser::new(config::SETTINGS))]
+        key: String,
+        value: String,
+    },
+    /// Clear a setting
+    Unset {
+        #[arg(value_parser = clap::builder::PossibleValuesParser::new(config::SETTINGS))]
+        key: String,
+    },
+    /// Print the config file path
+    Path,
+}
+
+#[derive(Subcommand)]
+enum ContextAction {
+    /// Add a named server context
+    Add {
+        /// Context name
+        name: String,
+        /// Server base URL
+        #[arg(long)]
+        url: String,
+        /// API key (or run `kaiba login` after switching)
+        #[arg(long)]
+        api_key: Option<String>,
+        /// Switch to the new context
+        #[arg(long = "use")]
+        switch: bool,
+    },
+    /// List contexts
+    #[command(visible_alias = "ls")]
+    List,
+    /// Switch the current context ("default" for the top-level settings)
+    Use {
+        /// Context name
+        name: String,
+    },
+    /// Remove a context
+    #[command(visible_alias = "rm")]
+    Remove {
+        /// Context name
+        name: String,
+    },
 }
 
 #[derive(Subcommand)]
 enum MemoryAction {
     /// Add a memory
     Add {
-        /// Memory content (or use -f for file)
+        /// Memory content ("-" reads stdin; or use -f for file)
         content: Option<String>,
         /// Read content from file
         #[arg(short, long)]
         file: Option<String>,
+        /// Read content from the system clipboard
+        #[arg(long, conflicts_with_all = ["content", "file"])]
+        clipboard: bool,
         /// Memory type (learning, fact, expertise, reflection)
         #[arg(short = 't', long)]
         r#type: Option<String>,
@@ -134,6 +575,40 @@ enum MemoryAction {
         #[arg(short, long)]
         profile: Option<String>,
     },
+    /// Import a directory of files as memories
+    Import {
+        /// Directory to import from
+        dir: String,
+        /// Files to import, relative to the directory
+        #[arg(long, default_value = "**/*.md")]
+        glob: String,
+        /// Tag each memory with the directories in its path
+        #[arg(long)]
+        tag_from_path: bool,
+        /// Extra tags for every imported memory (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        tags: Vec<String>,
+        /// Memory type (learning, fact, expertise, reflection)
+        #[arg(short = 't', long)]
+        r#type: Option<String>,
+        /// Split documents into chunks of at most this many characters
+        #[arg(long, default_value = "4000")]
+        chunk_size: usize,
+        /// List what would be imported without uploading
+        #[arg(long)]
+        dry_run: bool,
+        /// Profile to use
+        #[arg(short, long)]
+        profile: Option<String>,
+    },
+    /// Edit a memory's content, type, importance and tags in $EDITOR
+    Edit {
+        /// Memory ID
+        memory_id: String,
+        /// Profile to use
+        #[arg(short, long)]
+        profile: Option<String>,
+    },
     /// Search memories
     Search {
         /// Search query
@@ -141,6 +616,24 @@ enum MemoryAction {
         /// Max results
         #[arg(short, long, default_value = "10")]
         limit: usize,
+        /// Only memories of this type (conversation, learning, fact, expertise, reflection)
+        #[arg(short = 't', long)]
+        r#type: Option<String>,
+        /// Only memories with any of these tags (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        tags: Vec<String>,
+        /// Require all of --tags instead of any
+        #[arg(long, requires = "tags")]
+        tags_all: bool,
+        /// Minimum importance (0.0-1.0)
+        #[arg(long)]
+        min_importance: Option<f32>,
+        /// Only memories created since (e.g. 12h, 7d, 2w, 2025-01-31)
+        #[arg(long)]
+        since: Option<String>,
+        /// Show the similarity score of each result
+        #[arg(long)]
+        show_score: bool,
         /// Profile to use
         #[arg(short, long)]
         profile: Option<String>,
@@ -150,12 +643,14 @@ enum MemoryAction {
 #[derive(Subcommand)]
 enum WebhookAction {
     /// List all webhooks
+    #[command(visible_alias = "ls")]
     List {
         /// Profile to use
         #[arg(short, long)]
         profile: Option<String>,
     },
     /// Create a new webhook
+    #[command(visible_alias = "add")]
     Create {
         /// Webhook name
         #[arg(short, long)]
@@ -166,9 +661,12 @@ enum WebhookAction {
         /// Event types (comma-separated: learning_completed, memory_added, etc.)
         #[arg(short, long, value_delimiter = ',')]
         events: Vec<String>,
-        /// Payload format (e.g., "github_issue")
-        #[arg(short = 'f', long)]
-        format: Option<String>,
+        /// Payload format (e.g., "github_issue", "jira_issue", "linear_issue", "discord_embed")
+        #[arg(long)]
+        payload_format: Option<String>,
+        /// Signing secret for the X-Kaiba-Signature header
+        #[arg(long)]
+        secret: Option<String>,
         /// Profile to use
         #[arg(short, long)]
         profile: Option<String>,
@@ -193,13 +691,14 @@ enum WebhookAction {
         #[arg(long, value_delimiter = ',')]
         events: Option<Vec<String>>,
         /// Payload format
-        #[arg(short = 'f', long)]
-        format: Option<String>,
+        #[arg(long)]
+        payload_format: Option<String>,
         /// Profile to use
         #[arg(short, long)]
         profile: Option<String>,
     },
     /// Delete a webhook
+    #[command(visible_alias = "rm")]
     Delete {
         /// Webhook ID
         webhook_id: String,
@@ -214,6 +713,9 @@ enum WebhookAction {
         /// Event type to simulate
         #[arg(short, long)]
         event: Option<String>,
+        /// Show the rendered request without sending it
+        #[arg(long)]
+        dry_run: bool,
         /// Profile to use
         #[arg(short, long)]
         profile: Option<String>,
@@ -222,6 +724,52 @@ enum WebhookAction {
     Deliveries {
         /// Webhook ID
         webhook_id: String,
+        /// Filter by status (pending, success, failed, retrying, dead)
+        #[arg(short, long)]
+        status: Option<String>,
+        /// Profile to use
+        #[arg(short, long)]
+        profile: Option<String>,
+    },
+    /// Replay a past delivery (re-sends the original payload)
+    Replay {
+        /// Webhook ID
+        webhook_id: String,
+        /// Delivery ID to replay
+        delivery_id: String,
+        /// Profile to use
+        #[arg(short, long)]
+        profile: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum EventAction {
+    /// List registered custom events
+    List,
+    /// Register a custom event
+    Define {
+        /// Event name (subscribe with "custom:<name>")
+        name: String,
+        /// What the event means
+        #[arg(short, long)]
+        description: Option<String>,
+        /// JSON file with the event data schema
+        #[arg(short, long)]
+        schema: Option<String>,
+    },
+    /// Unregister a custom event
+    Remove {
+        /// Event name
+        name: String,
+    },
+    /// Emit a custom event for a Rei
+    Emit {
+        /// Event name
+        name: String,
+        /// Event data as JSON (validated against the event schema)
+        #[arg(short, long)]
+        data: Option<String>,
         /// Profile to use
         #[arg(short, long)]
         profile: Option<String>,
@@ -231,21 +779,102 @@ enum WebhookAction {
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
+    let output = cli.output;
+    output.apply();
+    api::ClientOptions {
+        timeout: std::time::Duration::from_secs(cli.timeout),
+        retries: cli.retries,
+    }
+    .install();
 
     match cli.command {
-        Commands::Login { key } => cmd_login(key).await,
-        Commands::Profile { action } => cmd_profile(action).await,
-        Commands::Rei { action } => cmd_rei(action).await,
-        Commands::Memory { action } => cmd_memory(action).await,
-        Commands::Webhook { action } => cmd_webhook(action).await,
+        Commands::Login { key, no_keychain } => cmd_login(key, !no_keychain, output).await,
+        Commands::Profile { action } => cmd_profile(action, output).await,
+        Commands::Rei { action } => cmd_rei(action, output).await,
+        Commands::Tei { action } => cmd_tei(action, output).await,
+        Commands::Memory { action } => cmd_memory(action, output).await,
+        Commands::Webhook { action } => cmd_webhook(action, output).await,
+        Commands::Event { action } => cmd_event(action, output).await,
+        Commands::Call {
+            message,
+            stream,
+            include_memories,
+            memory_limit,
+            tei,
+            profile,
+            verbose,
+        } => {
+            cmd_call(
+                CallRequest {
+                    tei_ids: tei,
+                    message,
+                    context: CallContext {
+                        include_memories,
+                        memory_limit,
+                    },
+                    session_id: None,
+                },
+                stream,
+                profile,
+                verbose,
+                output,
+            )
+            .await
+        }
         Commands::Prompt {
             format,
             include_memories,
             context,
             profile,
             verbose,
-        } => cmd_prompt(format, include_memories, context, profile, verbose).await,
-        Commands::Config => cmd_config(),
+            copy,
+            out,
+        } => {
+            let target = PromptTarget { copy, out };
+            cmd_prompt(
+                format,
+                include_memories,
+                context,
+                profile,
+                verbose,
+                target,
+                output,
+            )
+            .await
+        }
+        Commands::Chat {
+            profile,
+            no_memories,
+            memory_limit,
+            tei,
+        } => cmd_chat(profile, !no_memories, memory_limit, tei).await,
+        Commands::State { action } => cmd_state(action, output).await,
+        Commands::Recharge { amount, profile } => cmd_recharge(amount, profile, output).await,
+        Commands::Status { profile } => cmd_status(profile, output).await,
+        Commands::Watch { profile, interval } => cmd_watch(profile, interval, output).await,
+        Commands::Learn {
+            profile,
+            force,
+            max_queries,
+        } => cmd_learn(profile, force, max_queries, output).await,
+        Commands::Digest { profile } => cmd_digest(profile, output).await,
+        Commands::Exec {
+            format,
+            include_memories,
+            context,
+            profile,
+            command,
+        } => cmd_exec(format, include_memories, context, profile, command).await,
+        Commands::Config { action } => cmd_config(action.unwrap_or(ConfigAction::Show), output),
+        Commands::Context { action } => cmd_context(action, output),
+        Commands::Completions { shell } => {
+            clap_complete::generate(shell, &mut Cli::command(), "kaiba", &mut io::stdout());
+            Ok(())
+        }
+        Commands::Doctor => cmd_doctor(output).await,
+        Commands::Man => clap_mangen::Man::new(Cli::command())
+            .render(&mut io::stdout())
+            .context("Failed to render man page"),
     }
 }
 
@@ -253,7 +882,7 @@ async fn main() -> Result<()> {
 // Command Implementations
 // ============================================
 
-async fn cmd_login(key: Option<String>) -> Result<()> {
+async fn cmd_login(key: Option<String>, use_keychain: bool, output: OutputFormat) -> Result<()> {
     let mut config = Config::load()?;
 
     let api_key = match key {
@@ -265,26 +894,58 @@ async fn cmd_login(key: Option<String>) -> Result<()> {
     };
 
     // Test connection
-    let client = KaibaClient::new(&config.base_url, &api_key);
-    print!("Testing connection... ");
+    let client = KaibaClient::new(config.base_url(), &api_key);
+    if !output.is_json() {
+        print!("Testing connection... ");
+    }
 
     match client.health().await {
         Ok(true) => {
-            println!("{}", "OK".green());
+            if !output.is_json() {
+                println!("{}", "OK".green());
+            }
         }
         _ => {
-            println!("{}", "Failed".red());
+            if !output.is_json() {
+                println!("{}", "Failed".red());
+            }
             bail!("Could not connect to Kaiba API. Check your API key.");
         }
     }
 
-    config.set_api_key(api_key);
-    config.save()?;
-
-    println!(
-        "{} API key saved to {:?}",
+    if use_keychain {
+        if let Err(e) = config.set_api_key(api_key.clone(), true) {
+            eprintln!(
+                "{} {:#}; storing the key in the config file",
+                "Warning:".yellow(),
+                e
+            );
+            config.set_api_key(api_key, false)?;
+        }
+    } else {
+        config.set_api_key(api_key, false)?;
+    }
+    config.save()?;
+    let stored_in = if config.uses_keychain() {
+        "OS keychain".to_string()
+    } else {
+        format!("{:?}", Config::config_path()?)
+    };
+
+    if output.is_json() {
+        return print_json(&serde_json::json!({
+            "status": "ok",
+            "context": config.current_context_name(),
+            "keychain": config.uses_keychain(),
+            "config_path": Config::config_path()?,
+        }));
+    }
+
+    println!(
+        "{} API key saved for context '{}' in {}",
         "✓".green(),
-        Config::config_path()?
+        config.current_context_name(),
+        stored_in
     );
 
     // Offer to set up a profile if none exists
@@ -301,7 +962,7 @@ async fn cmd_login(key: Option<String>) -> Result<()> {
     Ok(())
 }
 
-async fn cmd_profile(action: ProfileAction) -> Result<()> {
+async fn cmd_profile(action: ProfileAction, output: OutputFormat) -> Result<()> {
     let mut config = Config::load()?;
 
     match action {
@@ -311,13 +972,16 @@ async fn cmd_profile(action: ProfileAction) -> Result<()> {
             display_name,
         } => {
             // Verify Rei exists if we have an API key
-            if let Some(api_key) = &config.api_key {
-                let client = KaibaClient::new(&config.base_url, api_key);
+            if let Some(api_key) = config.api_key() {
+                let client = KaibaClient::new(config.base_url(), api_key);
                 match client.get_rei(&rei_id).await {
                     Ok(rei) => {
                         let display = display_name.clone().unwrap_or_else(|| rei.name.clone());
                         config.add_profile(name.clone(), rei_id, Some(display.clone()));
                         config.save()?;
+                        if output.is_json() {
+                            return print_json(&config.profiles[&name]);
+                        }
                         println!("{} Profile '{}' added ({})", "✓".green(), name, display);
                     }
                     Err(e) => {
@@ -327,6 +991,9 @@ async fn cmd_profile(action: ProfileAction) -> Result<()> {
             } else {
                 config.add_profile(name.clone(), rei_id, display_name);
                 config.save()?;
+                if output.is_json() {
+                    return print_json(&config.profiles[&name]);
+                }
                 println!(
                     "{} Profile '{}' added (unverified - no API key)",
                     "✓".yellow(),
@@ -336,6 +1003,10 @@ async fn cmd_profile(action: ProfileAction) -> Result<()> {
         }
 
         ProfileAction::List => {
+            if output.is_json() {
+                return print_json(&config.profiles);
+            }
+
             if config.profiles.is_empty() {
                 println!("No profiles configured.");
                 println!("\n{}", "Add one with:".dimmed());
@@ -343,29 +1014,25 @@ async fn cmd_profile(action: ProfileAction) -> Result<()> {
                 return Ok(());
             }
 
-            println!("{}", "Profiles:".bold());
+            let mut table = Table::new(vec!["PROFILE", "NAME", "REI ID", "DEFAULT"]);
             for (name, profile) in &config.profiles {
                 let is_default = config.default_profile.as_ref() == Some(name);
-                let default_marker = if is_default {
-                    " (default)".green().to_string()
-                } else {
-                    String::new()
-                };
-                let display_name = profile.name.as_deref().unwrap_or("-");
-
-                println!(
-                    "  {} {} ({}){}",
-                    name.cyan(),
-                    display_name.dimmed(),
-                    &profile.rei_id[..8],
-                    default_marker
-                );
+                table.add_row(vec![
+                    name.clone(),
+                    profile.name.clone().unwrap_or_else(|| "-".to_string()),
+                    profile.rei_id.clone(),
+                    if is_default { "*" } else { "" }.to_string(),
+                ]);
             }
+            table.print(output);
         }
 
         ProfileAction::Set { name } => {
             if config.set_default_profile(name.clone()) {
                 config.save()?;
+                if output.is_json() {
+                    return print_json(&serde_json::json!({ "default_profile": name }));
+                }
                 println!("{} Default profile set to '{}'", "✓".green(), name);
             } else {
                 bail!("Profile '{}' not found", name);
@@ -379,34 +1046,97 @@ async fn cmd_profile(action: ProfileAction) -> Result<()> {
                     config.default_profile = None;
                 }
                 config.save()?;
+                if output.is_json() {
+                    return print_json(&serde_json::json!({ "removed": name }));
+                }
                 println!("{} Profile '{}' removed", "✓".green(), name);
             } else {
                 bail!("Profile '{}' not found", name);
             }
         }
+
+        ProfileAction::Sync { prune, dry_run } => {
+            let api_key = config
+                .api_key()
+                .context("Not logged in. Run 'kaiba login' first.")?;
+            let client = KaibaClient::new(config.base_url(), api_key);
+
+            let reis: Vec<(String, String)> = client
+                .list_reis()
+                .await?
+                .into_iter()
+                .map(|rei| (rei.id.to_string(), rei.name))
+                .collect();
+
+            let changes = config.sync_profiles(&reis, prune);
+            if !dry_run {
+                config.save()?;
+            }
+
+            if output.is_json() {
+                return print_json(&changes);
+            }
+
+            if changes.is_empty() {
+                println!("Profiles are up to date ({} Reis).", reis.len());
+                return Ok(());
+            }
+
+            let mut table = Table::new(vec!["CHANGE", "PROFILE", "REI ID"]);
+            for change in &changes {
+                let (label, profile, rei_id) = match change {
+                    ProfileChange::Added { profile, rei_id } => ("added", profile, rei_id),
+                    ProfileChange::Updated { profile, rei_id } => ("updated", profile, rei_id),
+                    ProfileChange::Removed { profile, rei_id } => ("removed", profile, rei_id),
+                };
+                table.add_row(vec![label.to_string(), profile.clone(), rei_id.clone()]);
+            }
+            table.print(output);
+
+            if dry_run {
+                println!("\n{}", "Dry run - nothing saved.".dimmed());
+            }
+        }
     }
 
     Ok(())
 }
 
-async fn cmd_rei(action: ReiAction) -> Result<()> {
+async fn cmd_rei(action: ReiAction, output: OutputFormat) -> Result<()> {
     let config = Config::load()?;
     let api_key = config
-        .api_key
-        .as_ref()
+        .api_key()
         .context("Not logged in. Run 'kaiba login' first.")?;
 
-    let client = KaibaClient::new(&config.base_url, api_key);
+    let client = KaibaClient::new(config.base_url(), api_key);
 
     match action {
         ReiAction::List => {
             let reis = client.list_reis().await?;
 
+            if output.is_json() {
+                return print_json(&reis);
+            }
+
             if reis.is_empty() {
                 println!("No Reis found.");
                 return Ok(());
             }
 
+            if output == OutputFormat::Plain {
+                let mut table = Table::new(vec!["ID", "NAME", "ENERGY", "ROLE"]);
+                for rei in &reis {
+                    table.add_row(vec![
+                        rei.id.to_string(),
+                        rei.name.clone(),
+                        rei.state.energy_level.to_string(),
+                        rei.role.clone(),
+                    ]);
+                }
+                table.print(output);
+                return Ok(());
+            }
+
             println!("{}", "Reis:".bold());
             for rei in reis {
                 let energy_color = if rei.state.energy_level >= 50 {
@@ -429,24 +1159,377 @@ async fn cmd_rei(action: ReiAction) -> Result<()> {
             println!("\n{}", "Add a profile shortcut:".dimmed());
             println!("  kaiba profile add <name> --rei-id <ID>");
         }
+
+        ReiAction::Show { profile } => {
+            let rei_id = config.get_rei_id(profile.as_deref()).context(
+                "No profile specified and no default profile set. Use a profile name or set a default.",
+            )?;
+
+            let rei = client.get_rei(&rei_id).await?;
+
+            if output.is_json() {
+                return print_json(&rei);
+            }
+
+            println!("{} {}", rei.name.cyan().bold(), rei.role.dimmed());
+            println!("  ID: {}", rei.id);
+            println!(
+                "  Energy: {}%  Mood: {}",
+                rei.state.energy_level, rei.state.mood
+            );
+            if let Some(avatar_url) = &rei.avatar_url {
+                println!("  Avatar: {}", avatar_url.dimmed());
+            }
+            println!("\n{}", "Manifest:".bold());
+            println!("{}", serde_json::to_string_pretty(&rei.manifest)?);
+        }
+
+        ReiAction::Create {
+            name,
+            role,
+            avatar_url,
+            manifest,
+            save_profile,
+        } => {
+            let request = CreateReiRequest {
+                name,
+                role,
+                avatar_url,
+                manifest: manifest.as_deref().map(read_json_file).transpose()?,
+            };
+
+            let rei = client.create_rei(&request).await?;
+
+            if let Some(profile_name) = &save_profile {
+                let mut config = config.clone();
+                config.add_profile(
+                    profile_name.clone(),
+                    rei.id.to_string(),
+                    Some(rei.name.clone()),
+                );
+                config.save()?;
+            }
+
+            if output.is_json() {
+                return print_json(&rei);
+            }
+
+            println!("{} Rei created: {}", "✓".green(), rei.name.cyan());
+            println!("  ID: {}", rei.id);
+            if let Some(profile_name) = save_profile {
+                println!("  Profile: {}", profile_name.cyan());
+            }
+        }
+
+        ReiAction::Update {
+            profile,
+            name,
+            role,
+            avatar_url,
+            manifest,
+        } => {
+            let rei_id = config.get_rei_id(profile.as_deref()).context(
+                "No profile specified and no default profile set. Use a profile name or set a default.",
+            )?;
+
+            let request = UpdateReiRequest {
+                name,
+                role,
+                avatar_url,
+                manifest: manifest.as_deref().map(read_json_file).transpose()?,
+            };
+
+            let rei = client.update_rei(&rei_id, &request, None).await?;
+
+            if output.is_json() {
+                return print_json(&rei);
+            }
+
+            println!("{} Rei updated: {}", "✓".green(), rei.name.cyan());
+        }
+
+        ReiAction::Edit { profile } => {
+            let rei_id = config.get_rei_id(profile.as_deref()).context(
+                "No profile specified and no default profile set. Use a profile name or set a default.",
+            )?;
+
+            let rei = client.get_rei(&rei_id).await?;
+            let original = serde_json::to_string_pretty(&rei.manifest)?;
+
+            let path = std::env::temp_dir().join(format!("kaiba-rei-{}.json", rei.id));
+            fs::write(&path, &original)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+
+            let edited = open_in_editor(&path).and_then(|_| {
+                fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))
+            });
+            let _ = fs::remove_file(&path);
+            let edited = edited?;
+
+            if edited.trim() == original.trim() {
+                if output.is_json() {
+                    return print_json(&rei);
+                }
+                println!("No changes.");
+                return Ok(());
+            }
+
+            let manifest: serde_json::Value =
+                serde_json::from_str(&edited).context("Edited manifest is not valid JSON")?;
+
+            let request = UpdateReiRequest {
+                manifest: Some(manifest),
+                ..Default::default()
+            };
+            // Refuse to overwrite changes made while the editor was open
+            let rei = client.update_rei(&rei_id, &request, rei.updated_at).await?;
+
+            if output.is_json() {
+                return print_json(&rei);
+            }
+
+            println!("{} Manifest updated: {}", "✓".green(), rei.name.cyan());
+        }
+
+        ReiAction::Delete { profile, yes } => {
+            let rei_id = config.get_rei_id(profile.as_deref()).context(
+                "No profile specified and no default profile set. Use a profile name or set a default.",
+            )?;
+
+            let rei = client.get_rei(&rei_id).await?;
+
+            if !yes {
+                let confirmed = Confirm::new()
+                    .with_prompt(format!(
+                        "Delete Rei '{}'? It can be restored until it is purged.",
+                        rei.name
+                    ))
+                    .default(false)
+                    .interact()
+                    .context("Failed to read input")?;
+                if !confirmed {
+                    println!("Aborted.");
+                    return Ok(());
+                }
+            }
+
+            client.delete_rei(&rei_id).await?;
+
+            if output.is_json() {
+                return print_json(&serde_json::json!({ "deleted": rei.id }));
+            }
+
+            println!("{} Rei deleted: {}", "✓".green(), rei.name.cyan());
+        }
+
+        ReiAction::Restore { profile } => {
+            let rei_id = config.get_rei_id(profile.as_deref()).context(
+                "No profile specified and no default profile set. Use a profile name or set a default.",
+            )?;
+
+            let rei = client.restore_rei(&rei_id).await?;
+
+            if output.is_json() {
+                return print_json(&rei);
+            }
+
+            println!("{} Rei restored: {}", "✓".green(), rei.name.cyan());
+        }
+
+        ReiAction::Schedule {
+            profile,
+            cron,
+            timezone,
+            clear,
+        } => {
+            let rei_id = config.get_rei_id(profile.as_deref()).context(
+                "No profile specified and no default profile set. Use a profile name or set a default.",
+            )?;
+
+            if clear {
+                client.clear_schedule(&rei_id).await?;
+                if output.is_json() {
+                    return print_json(&serde_json::json!({ "schedule": null }));
+                }
+                println!("{} Schedule removed", "✓".green());
+                return Ok(());
+            }
+
+            let schedule = match cron {
+                Some(cron) => {
+                    let request = UpsertScheduleRequest { cron, timezone };
+                    Some(client.set_schedule(&rei_id, &request).await?)
+                }
+                None => client.get_schedule(&rei_id).await?,
+            };
+
+            if output.is_json() {
+                return print_json(&serde_json::json!({ "schedule": schedule }));
+            }
+
+            let Some(schedule) = schedule else {
+                println!("No schedule; learns on the server's interval.");
+                return Ok(());
+            };
+            println!(
+                "{} {} ({})",
+                "Schedule:".bold(),
+                schedule.cron.cyan(),
+                schedule.timezone
+            );
+            match schedule.next_run_at {
+                Some(next) => println!("{} {}", "Next run:".bold(), next.to_rfc3339()),
+                None => println!("{} never", "Next run:".bold()),
+            }
+            if let Some(last) = schedule.last_run_at {
+                println!("{} {}", "Last run:".bold(), last.to_rfc3339());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn cmd_tei(action: TeiAction, output: OutputFormat) -> Result<()> {
+    let config = Config::load()?;
+    let api_key = config
+        .api_key()
+        .context("Not logged in. Run 'kaiba login' first.")?;
+
+    let client = KaibaClient::new(config.base_url(), api_key);
+
+    match action {
+        TeiAction::List { profile } => {
+            let teis = match &profile {
+                Some(name) => {
+                    let rei_id = config
+                        .get_rei_id(Some(name))
+                        .with_context(|| format!("Profile '{}' not found", name))?;
+                    client.list_rei_teis(&rei_id).await?
+                }
+                None => client.list_teis().await?,
+            };
+
+            if output.is_json() {
+                return print_json(&teis);
+            }
+
+            if teis.is_empty() {
+                println!("No Teis found.");
+                return Ok(());
+            }
+
+            let mut table = Table::new(vec![
+                "ID", "NAME", "PROVIDER", "MODEL", "PRIORITY", "FALLBACK",
+            ]);
+            for tei in &teis {
+                table.add_row(vec![
+                    tei.id.to_string(),
+                    tei.name.clone(),
+                    tei.provider.clone(),
+                    tei.model_id.clone(),
+                    tei.priority.to_string(),
+                    if tei.is_fallback { "yes" } else { "" }.to_string(),
+                ]);
+            }
+            table.print(output);
+        }
+
+        TeiAction::Add {
+            name,
+            provider,
+            model,
+            fallback,
+            priority,
+            config: tei_config,
+            link,
+        } => {
+            let request = CreateTeiRequest {
+                name,
+                provider: provider.to_lowercase(),
+                model_id: model,
+                is_fallback: fallback,
+                priority,
+                config: tei_config.as_deref().map(read_json_file).transpose()?,
+            };
+
+            let tei = client.create_tei(&request).await?;
+
+            if let Some(profile_name) = &link {
+                let rei_id = config
+                    .get_rei_id(Some(profile_name))
+                    .with_context(|| format!("Profile '{}' not found", profile_name))?;
+                client.link_tei(&rei_id, &tei.id.to_string()).await?;
+            }
+
+            if output.is_json() {
+                return print_json(&tei);
+            }
+
+            println!("{} Tei created: {}", "✓".green(), tei.name.cyan());
+            println!("  ID: {}", tei.id);
+            println!("  Model: {} ({})", tei.model_id, tei.provider.dimmed());
+            if let Some(profile_name) = link {
+                println!("  Linked to: {}", profile_name.cyan());
+            }
+        }
+
+        TeiAction::Rm { tei_id } => {
+            client.delete_tei(&tei_id).await?;
+
+            if output.is_json() {
+                return print_json(&serde_json::json!({ "deleted": tei_id }));
+            }
+
+            println!("{} Tei deleted: {}", "✓".green(), tei_id.dimmed());
+        }
+
+        TeiAction::Link { tei_id, profile } => {
+            let rei_id = config.get_rei_id(profile.as_deref()).context(
+                "No profile specified and no default profile set. Use -p <profile> or set a default.",
+            )?;
+
+            client.link_tei(&rei_id, &tei_id).await?;
+
+            if output.is_json() {
+                return print_json(&serde_json::json!({ "rei_id": rei_id, "linked": tei_id }));
+            }
+
+            println!("{} Tei linked: {}", "✓".green(), tei_id.dimmed());
+        }
+
+        TeiAction::Unlink { tei_id, profile } => {
+            let rei_id = config.get_rei_id(profile.as_deref()).context(
+                "No profile specified and no default profile set. Use -p <profile> or set a default.",
+            )?;
+
+            client.unlink_tei(&rei_id, &tei_id).await?;
+
+            if output.is_json() {
+                return print_json(&serde_json::json!({ "rei_id": rei_id, "unlinked": tei_id }));
+            }
+
+            println!("{} Tei unlinked: {}", "✓".green(), tei_id.dimmed());
+        }
     }
 
     Ok(())
 }
 
-async fn cmd_memory(action: MemoryAction) -> Result<()> {
+async fn cmd_memory(action: MemoryAction, output: OutputFormat) -> Result<()> {
     let config = Config::load()?;
     let api_key = config
-        .api_key
-        .as_ref()
+        .api_key()
         .context("Not logged in. Run 'kaiba login' first.")?;
 
-    let client = KaibaClient::new(&config.base_url, api_key);
+    let client = KaibaClient::new(config.base_url(), api_key);
 
     match action {
         MemoryAction::Add {
             content,
             file,
+            clipboard,
             r#type,
             importance,
             tags,
@@ -455,8 +1538,16 @@ async fn cmd_memory(action: MemoryAction) -> Result<()> {
             let rei_id = config.get_rei_id(profile.as_deref())
                 .context("No profile specified and no default profile set. Use -p <profile> or set a default.")?;
 
-            // Get content from file or argument
+            // Get content from argument, stdin, file, or clipboard
             let memory_content = match (content, file) {
+                _ if clipboard => read_clipboard()?,
+                (Some(c), None) if c == "-" => {
+                    let mut buf = String::new();
+                    io::stdin()
+                        .read_to_string(&mut buf)
+                        .context("Failed to read stdin")?;
+                    buf
+                }
                 (Some(c), None) => c,
                 (None, Some(f)) => {
                     fs::read_to_string(&f).with_context(|| format!("Failed to read file: {}", f))?
@@ -473,6 +1564,10 @@ async fn cmd_memory(action: MemoryAction) -> Result<()> {
                 }
             };
 
+            if memory_content.trim().is_empty() {
+                bail!("Memory content is empty");
+            }
+
             let memory = client
                 .add_memory(
                     &rei_id,
@@ -483,6 +1578,10 @@ async fn cmd_memory(action: MemoryAction) -> Result<()> {
                 )
                 .await?;
 
+            if output.is_json() {
+                return print_json(&memory);
+            }
+
             let profile_name = profile
                 .as_deref()
                 .or(config.default_profile.as_deref())
@@ -499,106 +1598,1248 @@ async fn cmd_memory(action: MemoryAction) -> Result<()> {
             println!("  {}", truncate_string(&memory_content, 80).dimmed());
         }
 
-        MemoryAction::Search {
-            query,
-            limit,
+        MemoryAction::Import {
+            dir,
+            glob,
+            tag_from_path,
+            tags,
+            r#type,
+            chunk_size,
+            dry_run,
             profile,
         } => {
-            let rei_id = config.get_rei_id(profile.as_deref())
-                .context("No profile specified and no default profile set. Use -p <profile> or set a default.")?;
+            let rei_id = config.get_rei_id(profile.as_deref()).context(
+                "No profile specified and no default profile set. Use -p <profile> or set a default.",
+            )?;
 
-            let memories = client.search_memories(&rei_id, &query, Some(limit)).await?;
+            let dir = Path::new(&dir);
+            if !dir.is_dir() {
+                bail!("Not a directory: {}", dir.display());
+            }
 
-            if memories.is_empty() {
-                println!("No memories found for '{}'", query);
+            let files = import::collect_files(dir, &glob)?;
+            if files.is_empty() {
+                if output.is_json() {
+                    return print_json(&serde_json::json!({ "files": 0, "chunks": 0 }));
+                }
+                println!("No files matching '{}' in {}", glob, dir.display());
                 return Ok(());
             }
 
-            let profile_name = profile
-                .as_deref()
-                .or(config.default_profile.as_deref())
-                .unwrap_or("default");
+            let (mut chunks_total, mut imported, mut failed) = (0usize, 0usize, 0usize);
+            for (i, file) in files.iter().enumerate() {
+                let display = file.strip_prefix(dir).unwrap_or(file).display();
+                let text = match fs::read_to_string(file) {
+                    Ok(text) => text,
+                    Err(e) => {
+                        failed += 1;
+                        eprintln!("{} {}: {}", "Skipped".yellow(), display, e);
+                        continue;
+                    }
+                };
 
-            println!(
-                "{} results for '{}' ({}):",
-                memories.len().to_string().green(),
-                query,
-                profile_name.cyan()
-            );
+                let chunks = import::chunk_text(&text, chunk_size);
+                let mut file_tags = tags.clone();
+                if tag_from_path {
+                    file_tags.extend(import::path_tags(dir, file));
+                }
+                chunks_total += chunks.len();
+
+                if !output.is_json() {
+                    println!(
+                        "[{}/{}] {} ({} chunk{}){}",
+                        i + 1,
+                        files.len(),
+                        display,
+                        chunks.len(),
+                        if chunks.len() == 1 { "" } else { "s" },
+                        if file_tags.is_empty() {
+                            String::new()
+                        } else {
+                            format!(" [{}]", file_tags.join(", ")).dimmed().to_string()
+                        }
+                    );
+                }
+                if dry_run {
+                    continue;
+                }
 
-            for mem in memories {
-                let type_badge = format!("[{}]", mem.memory_type).dimmed();
-                let preview = truncate_string(&mem.content, 60);
-                println!("  {} {}", type_badge, preview);
+                for chunk in &chunks {
+                    match client
+                        .add_memory(&rei_id, chunk, r#type.as_deref(), None, &file_tags)
+                        .await
+                    {
+                        Ok(_) => imported += 1,
+                        Err(e) => {
+                            failed += 1;
+                            eprintln!("{} {}: {:#}", "Failed".red(), display, e);
+                        }
+                    }
+                }
             }
-        }
-    }
 
-    Ok(())
-}
+            if output.is_json() {
+                return print_json(&serde_json::json!({
+                    "files": files.len(),
+                    "chunks": chunks_total,
+                    "imported": imported,
+                    "failed": failed,
+                    "dry_run": dry_run,
+                }));
+            }
 
-async fn cmd_prompt(
-    format: String,
-    include_memories: bool,
-    context: Option<String>,
-    profile: Option<String>,
-    verbose: bool,
-) -> Result<()> {
-    let config = Config::load()?;
-    let api_key = config
-        .api_key
-        .as_ref()
-        .context("Not logged in. Run 'kaiba login' first.")?;
+            if dry_run {
+                println!(
+                    "\n{} {} files, {} chunks would be imported",
+                    "Dry run:".bold(),
+                    files.len(),
+                    chunks_total
+                );
+            } else {
+                println!(
+                    "\n{} Imported {} memories from {} files{}",
+                    "✓".green(),
+                    imported,
+                    files.len(),
+                    if failed > 0 {
+                        format!(" ({} failed)", failed).red().to_string()
+                    } else {
+                        String::new()
+                    }
+                );
+            }
+        }
+
+        MemoryAction::Edit { memory_id, profile } => {
+            let rei_id = config.get_rei_id(profile.as_deref()).context(
+                "No profile specified and no default profile set. Use -p <profile> or set a default.",
+            )?;
+
+            let memory = client.get_memory(&rei_id, &memory_id).await?;
+            let original = MemoryDocument {
+                memory_type: memory.memory_type.clone(),
+                importance: memory.importance,
+                tags: memory.tags.clone(),
+                // Trimmed like the parsed document, so an untouched file compares equal
+                content: memory.content.trim().to_string(),
+            };
+
+            let path = std::env::temp_dir().join(format!("kaiba-memory-{}.md", memory.id));
+            fs::write(&path, original.render())
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+
+            let edited = open_in_editor(&path).and_then(|_| {
+                fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))
+            });
+            let _ = fs::remove_file(&path);
+            let edited = MemoryDocument::parse(&edited?)?;
+
+            if edited == original {
+                if output.is_json() {
+                    return print_json(&memory);
+                }
+                println!("No changes.");
+                return Ok(());
+            }
+
+            // Only send what changed; content edits trigger a re-embed either way
+            let request = UpdateMemoryRequest {
+                content: (edited.content != original.content).then_some(edited.content),
+                memory_type: (edited.memory_type != original.memory_type)
+                    .then_some(edited.memory_type),
+                importance: (edited.importance != original.importance).then_some(edited.importance),
+                tags: (edited.tags != original.tags).then_some(edited.tags),
+            };
+            let memory = client.update_memory(&rei_id, &memory_id, &request).await?;
+
+            if output.is_json() {
+                return print_json(&memory);
+            }
+
+            println!("{} Memory updated: {}", "✓".green(), memory.id.cyan());
+        }
+
+        MemoryAction::Search {
+            query,
+            limit,
+            r#type,
+            tags,
+            tags_all,
+            min_importance,
+            since,
+            show_score,
+            profile,
+        } => {
+            let rei_id = config.get_rei_id(profile.as_deref())
+                .context("No profile specified and no default profile set. Use -p <profile> or set a default.")?;
+
+            let request = SearchMemoriesRequest {
+                query: query.clone(),
+                limit: Some(limit),
+                memory_type: r#type,
+                tags,
+                tags_match_mode: tags_all.then(|| "all".to_string()),
+                min_importance,
+                created_after: since
+                    .map(|s| time::parse_since(&s, chrono::Utc::now()))
+                    .transpose()?,
+            };
+            let memories = client.search_memories(&rei_id, &request).await?;
+
+            if output.is_json() {
+                return print_json(&memories);
+            }
+
+            if memories.is_empty() {
+                println!("No memories found for '{}'", query);
+                return Ok(());
+            }
+
+            let score = |mem: &api::MemoryResponse| {
+                mem.similarity
+                    .map(|s| format!("{:.3}", s))
+                    .unwrap_or_else(|| "-".to_string())
+            };
+
+            if output == OutputFormat::Plain {
+                let mut table = Table::new(if show_score {
+                    vec!["ID", "TYPE", "SCORE", "CONTENT"]
+                } else {
+                    vec!["ID", "TYPE", "CONTENT"]
+                });
+                for mem in &memories {
+                    let mut row = vec![mem.id.clone(), mem.memory_type.clone()];
+                    if show_score {
+                        row.push(score(mem));
+                    }
+                    row.push(mem.content.replace(['\t', '\n'], " "));
+                    table.add_row(row);
+                }
+                table.print(output);
+                return Ok(());
+            }
+
+            let profile_name = profile
+                .as_deref()
+                .or(config.default_profile.as_deref())
+                .unwrap_or("default");
+
+            println!(
+                "{} results for '{}' ({}):",
+                memories.len().to_string().green(),
+                query,
+                profile_name.cyan()
+            );
+
+            for mem in &memories {
+                let type_badge = format!("[{}]", mem.memory_type).dimmed();
+                let preview = truncate_string(&mem.content, 60);
+                if show_score {
+                    println!("  {} {} {}", score(mem).yellow(), type_badge, preview);
+                } else {
+                    println!("  {} {}", type_badge, preview);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Where `kaiba prompt` puts the prompt instead of stdout
+struct PromptTarget {
+    copy: bool,
+    out: Option<std::path::PathBuf>,
+}
+
+async fn cmd_prompt(
+    format: String,
+    include_memories: bool,
+    context: Option<String>,
+    profile: Option<String>,
+    verbose: bool,
+    target: PromptTarget,
+    output: OutputFormat,
+) -> Result<()> {
+    let config = Config::load()?;
+    let api_key = config
+        .api_key()
+        .context("Not logged in. Run 'kaiba login' first.")?;
+
+    let rei_id = config.get_rei_id(profile.as_deref()).context(
+        "No profile specified and no default profile set. Use -p <profile> or set a default.",
+    )?;
+
+    let client = KaibaClient::new(config.base_url(), api_key);
+
+    let prompt_resp = client
+        .get_prompt(&rei_id, Some(&format), include_memories, context.as_deref())
+        .await?;
+
+    // Written directly rather than through shell redirection, which can
+    // re-encode the text (e.g. UTF-16 in Windows PowerShell)
+    if let Some(path) = &target.out {
+        fs::write(path, &prompt_resp.system_prompt)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        eprintln!("{} Prompt written to {}", "✓".green(), path.display());
+    }
+    if target.copy {
+        write_clipboard(&prompt_resp.system_prompt)?;
+        eprintln!("{} Prompt copied to clipboard", "✓".green());
+    }
+
+    if output.is_json() {
+        return print_json(&prompt_resp);
+    }
+    if target.copy || target.out.is_some() {
+        return Ok(());
+    }
+
+    if verbose {
+        // Show metadata to stderr so stdout is clean for piping
+        eprintln!(
+            "{} {} ({}) [{}%] - {} format, {} memories",
+            "Prompt for".dimmed(),
+            prompt_resp.rei.name.cyan(),
+            prompt_resp.rei.role.dimmed(),
+            prompt_resp.rei.energy_level,
+            prompt_resp.format.green(),
+            prompt_resp.memories_included
+        );
+        eprintln!("{}", "---".dimmed());
+    }
+
+    // Output the prompt to stdout (clean for piping)
+    println!("{}", prompt_resp.system_prompt);
+
+    Ok(())
+}
+
+async fn cmd_call(
+    request: CallRequest,
+    stream: bool,
+    profile: Option<String>,
+    verbose: bool,
+    output: OutputFormat,
+) -> Result<()> {
+    let config = Config::load()?;
+    let api_key = config
+        .api_key()
+        .context("Not logged in. Run 'kaiba login' first.")?;
+
+    let rei_id = config.get_rei_id(profile.as_deref()).context(
+        "No profile specified and no default profile set. Use -p <profile> or set a default.",
+    )?;
+
+    let client = KaibaClient::new(config.base_url(), api_key);
+
+    // JSON output needs the complete response, so never stream it
+    let stream = stream && !output.is_json();
+
+    let mut streamed = false;
+    let call = client
+        .call_rei(&rei_id, &request, stream, |delta| {
+            streamed = true;
+            print!("{}", delta);
+            let _ = io::stdout().flush();
+        })
+        .await?;
+
+    if output.is_json() {
+        return print_json(&call);
+    }
+
+    if streamed {
+        println!();
+    } else {
+        println!("{}", call.response);
+    }
+
+    if verbose {
+        eprintln!();
+        eprintln!("{} {}", "Tei:".dimmed(), call.tei_used.to_string().dimmed());
+        eprintln!(
+            "{} {}",
+            "Tokens:".dimmed(),
+            call.tokens_consumed.to_string().dimmed()
+        );
+        if !call.memories_included.is_empty() {
+            eprintln!("{}", "Memories:".dimmed());
+            for memory in &call.memories_included {
+                eprintln!("  {} ({:.2})", memory.id.dimmed(), memory.similarity);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Read and parse a JSON file (e.g., a Rei manifest)
+fn read_json_file(path: &str) -> Result<serde_json::Value> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read file: {}", path))?;
+    serde_json::from_str(&content).with_context(|| format!("Invalid JSON in file: {}", path))
+}
+
+/// Open a file in $VISUAL / $EDITOR (default: vi) and wait for it to exit
+fn open_in_editor(path: &std::path::Path) -> Result<()> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    // Allow editors with arguments, e.g. "code --wait"
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().context("$EDITOR is empty")?;
+
+    let status = std::process::Command::new(program)
+        .args(parts)
+        .arg(path)
+        .status()
+        .with_context(|| format!("Failed to launch editor: {}", editor))?;
+    if !status.success() {
+        bail!("Editor exited with {}", status);
+    }
+    Ok(())
+}
+
+/// Truncate string safely for UTF-8 (by char count, not bytes)
+fn truncate_string(s: &str, max_chars: usize) -> String {
+    let chars: Vec<char> = s.chars().take(max_chars).collect();
+    if s.chars().count() > max_chars {
+        format!("{}...", chars.into_iter().collect::<String>())
+    } else {
+        s.to_string()
+    }
+}
+
+/// Read text from the system clipboard
+fn read_clipboard() -> Result<String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_text())
+        .context("Failed to read from clipboard")
+}
+
+/// Put text on the clipboard
+///
+/// On Linux the text stays available after exit only if a clipboard
+/// manager is running.
+fn write_clipboard(text: &str) -> Result<()> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text))
+        .context("Failed to write to clipboard")
+}
+
+/// First 8 characters of an ID for display
+fn short_id(id: &str) -> &str {
+    id.get(..8).unwrap_or(id)
+}
+
+async fn cmd_chat(
+    profile: Option<String>,
+    include_memories: bool,
+    memory_limit: Option<usize>,
+    tei_ids: Vec<Uuid>,
+) -> Result<()> {
+    let config = Config::load()?;
+    let api_key = config
+        .api_key()
+        .context("Not logged in. Run 'kaiba login' first.")?;
+
+    let rei_id = config.get_rei_id(profile.as_deref()).context(
+        "No profile specified and no default profile set. Use -p <profile> or set a default.",
+    )?;
+
+    let client = KaibaClient::new(config.base_url(), api_key);
+    let rei = client.get_rei(&rei_id).await?;
+
+    let mut session_id = Uuid::new_v4();
+    let mut last_turn: Option<(String, String)> = None;
+
+    println!(
+        "Chatting with {} {}",
+        rei.name.cyan().bold(),
+        format!("(session {})", &session_id.to_string()[..8]).dimmed()
+    );
+    println!("{}", "Type /help for commands, /exit to quit.".dimmed());
+
+    loop {
+        print!("\n{} ", "you>".green().bold());
+        io::stdout().flush()?;
+
+        let mut line = String::new();
+        if io::stdin().read_line(&mut line)? == 0 {
+            println!();
+            break;
+        }
+
+        match line.trim() {
+            "" => continue,
+            "/exit" | "/quit" => break,
+            "/help" => {
+                println!("  /save  Store the last turn as a memory");
+                println!("  /new   Start a new conversation session");
+                println!("  /exit  Quit");
+            }
+            "/new" => {
+                session_id = Uuid::new_v4();
+                last_turn = None;
+                println!(
+                    "{}",
+                    format!("New session {}", &session_id.to_string()[..8]).dimmed()
+                );
+            }
+            "/save" => {
+                let Some((message, response)) = &last_turn else {
+                    println!("Nothing to save yet.");
+                    continue;
+                };
+                let content = format!("User: {}\n{}: {}", message, rei.name, response);
+                match client
+                    .add_memory(&rei_id, &content, Some("conversation"), None, &[])
+                    .await
+                {
+                    Ok(memory) => {
+                        println!("{} Saved as memory [{}]", "✓".green(), short_id(&memory.id))
+                    }
+                    Err(e) => eprintln!("{} {:#}", "Error:".red(), e),
+                }
+            }
+            command if command.starts_with('/') => {
+                println!("Unknown command: {} (try /help)", command);
+            }
+            message => {
+                let request = CallRequest {
+                    tei_ids: tei_ids.clone(),
+                    message: message.to_string(),
+                    context: CallContext {
+                        include_memories,
+                        memory_limit,
+                    },
+                    session_id: Some(session_id),
+                };
+
+                let call = match client.call_rei(&rei_id, &request, false, |_| {}).await {
+                    Ok(call) => call,
+                    Err(e) => {
+                        eprintln!("{} {:#}", "Error:".red(), e);
+                        continue;
+                    }
+                };
+
+                if !call.memories_included.is_empty() {
+                    let refs: Vec<String> = call
+                        .memories_included
+                        .iter()
+                        .map(|m| format!("{} ({:.2})", short_id(&m.id), m.similarity))
+                        .collect();
+                    println!("{}", format!("  memories: {}", refs.join(", ")).dimmed());
+                }
+                println!(
+                    "{} {}",
+                    format!("{}>", rei.name).cyan().bold(),
+                    call.response
+                );
+
+                last_turn = Some((message.to_string(), call.response));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn cmd_exec(
+    format: String,
+    include_memories: bool,
+    context: Option<String>,
+    profile: Option<String>,
+    command: Vec<String>,
+) -> Result<()> {
+    let config = Config::load()?;
+    let api_key = config
+        .api_key()
+        .context("Not logged in. Run 'kaiba login' first.")?;
+
+    let rei_id = config.get_rei_id(profile.as_deref()).context(
+        "No profile specified and no default profile set. Use -p <profile> or set a default.",
+    )?;
+
+    let client = KaibaClient::new(config.base_url(), api_key);
+    let prompt = client
+        .get_prompt(&rei_id, Some(&format), include_memories, context.as_deref())
+        .await?
+        .system_prompt;
+
+    let prompt_file = if exec::needs_prompt_file(&command) {
+        let path = std::env::temp_dir().join(format!("kaiba-prompt-{}.md", Uuid::new_v4()));
+        fs::write(&path, &prompt).with_context(|| format!("Failed to write {}", path.display()))?;
+        Some(path)
+    } else {
+        None
+    };
+
+    let args = exec::render_args(
+        &command,
+        &prompt,
+        prompt_file.as_deref().and_then(|p| p.to_str()),
+    );
+    let (program, args) = args.split_first().context("No command given")?;
+
+    let mut child = std::process::Command::new(program);
+    child.args(args);
+    if !exec::has_placeholder(&command) {
+        child.env("KAIBA_PROMPT", &prompt);
+    }
+    let status = child.status();
+
+    if let Some(path) = &prompt_file {
+        let _ = fs::remove_file(path);
+    }
+
+    let status = status.with_context(|| format!("Failed to run {}", program))?;
+    if !status.success() {
+        std::process::exit(status.code().unwrap_or(1));
+    }
+
+    Ok(())
+}
+
+async fn cmd_state(action: StateAction, output: OutputFormat) -> Result<()> {
+    let config = Config::load()?;
+    let api_key = config
+        .api_key()
+        .context("Not logged in. Run 'kaiba login' first.")?;
+
+    let client = KaibaClient::new(config.base_url(), api_key);
+
+    let state = match action {
+        StateAction::Show { profile } => {
+            let rei_id = config.get_rei_id(profile.as_deref()).context(
+                "No profile specified and no default profile set. Use -p <profile> or set a default.",
+            )?;
+            client.get_rei(&rei_id).await?.state
+        }
+        StateAction::Set {
+            mood,
+            energy,
+            token_budget,
+            tokens_used,
+            regen,
+            profile,
+        } => {
+            let rei_id = config.get_rei_id(profile.as_deref()).context(
+                "No profile specified and no default profile set. Use -p <profile> or set a default.",
+            )?;
+
+            let request = UpdateReiStateRequest {
+                energy_level: energy,
+                mood,
+                token_budget,
+                tokens_used,
+                energy_regen_per_hour: regen,
+            };
+            if request.energy_level.is_none()
+                && request.mood.is_none()
+                && request.token_budget.is_none()
+                && request.tokens_used.is_none()
+                && request.energy_regen_per_hour.is_none()
+            {
+                bail!("Nothing to update. Use --mood, --energy, --token-budget, --tokens-used or --regen");
+            }
+
+            client.update_rei_state(&rei_id, &request).await?
+        }
+        StateAction::Pause { profile } => {
+            let rei_id = config.get_rei_id(profile.as_deref()).context(
+                "No profile specified and no default profile set. Use -p <profile> or set a default.",
+            )?;
+            client.set_autonomy(&rei_id, false).await?
+        }
+        StateAction::Resume { profile } => {
+            let rei_id = config.get_rei_id(profile.as_deref()).context(
+                "No profile specified and no default profile set. Use -p <profile> or set a default.",
+            )?;
+            client.set_autonomy(&rei_id, true).await?
+        }
+    };
+
+    if output.is_json() {
+        return print_json(&state);
+    }
+
+    println!(
+        "  Energy: {}% (+{}/h)",
+        state.energy_level, state.energy_regen_per_hour
+    );
+    println!("  Mood:   {}", state.mood.cyan());
+    println!("  Tokens: {} / {}", state.tokens_used, state.token_budget);
+    if !state.autonomy_enabled {
+        println!("  {}", "Autonomy paused".yellow());
+    }
+
+    Ok(())
+}
+
+async fn cmd_recharge(amount: i32, profile: Option<String>, output: OutputFormat) -> Result<()> {
+    let config = Config::load()?;
+    let api_key = config
+        .api_key()
+        .context("Not logged in. Run 'kaiba login' first.")?;
+
+    let rei_id = config.get_rei_id(profile.as_deref()).context(
+        "No profile specified and no default profile set. Use -p <profile> or set a default.",
+    )?;
+
+    let client = KaibaClient::new(config.base_url(), api_key);
+    let recharged = client.recharge(&rei_id, amount).await?;
+
+    if output.is_json() {
+        return print_json(&recharged);
+    }
+
+    println!(
+        "{} Energy {} → {}",
+        "⚡".yellow(),
+        recharged.previous_energy,
+        recharged.current_energy.to_string().green()
+    );
+
+    Ok(())
+}
+
+async fn cmd_status(profile: Option<String>, output: OutputFormat) -> Result<()> {
+    let config = Config::load()?;
+    let api_key = config
+        .api_key()
+        .context("Not logged in. Run 'kaiba login' first.")?;
+
+    let client = KaibaClient::new(config.base_url(), api_key);
+
+    let mut profiles: Vec<(String, String)> = match profile {
+        Some(name) => {
+            let rei_id = config
+                .get_rei_id(Some(&name))
+                .with_context(|| format!("Profile '{}' not found", name))?;
+            vec![(name, rei_id)]
+        }
+        None => config
+            .profiles
+            .iter()
+            .map(|(name, p)| (name.clone(), p.rei_id.clone()))
+            .collect(),
+    };
+    if profiles.is_empty() {
+        bail!("No profiles configured. Add one with 'kaiba profile add'.");
+    }
+    profiles.sort();
+
+    let mut dashboards = Vec::with_capacity(profiles.len());
+    for (name, rei_id) in profiles {
+        let dashboard = client.get_dashboard(&rei_id).await;
+        dashboards.push((name, dashboard));
+    }
+
+    if output.is_json() {
+        let statuses: serde_json::Map<String, serde_json::Value> = dashboards
+            .into_iter()
+            .map(|(name, dashboard)| -> Result<(String, serde_json::Value)> {
+                let value = match dashboard {
+                    Ok(d) => serde_json::to_value(d)?,
+                    Err(e) => serde_json::json!({ "error": format!("{:#}", e) }),
+                };
+                Ok((name, value))
+            })
+            .collect::<Result<_>>()?;
+        return print_json(&statuses);
+    }
+
+    let now = chrono::Utc::now();
+    let ago = |ts: Option<chrono::DateTime<chrono::Utc>>| {
+        ts.map(|t| time::ago(t, now))
+            .unwrap_or_else(|| "never".to_string())
+    };
+
+    if output == OutputFormat::Plain {
+        let mut table = Table::new(vec![
+            "PROFILE", "ENERGY", "MOOD", "TOKENS", "BUDGET", "MEMORIES", "LEARN", "DIGEST",
+            "FAILURES",
+        ]);
+        for (name, dashboard) in &dashboards {
+            let Ok(d) = dashboard else {
+                continue;
+            };
+            table.add_row(vec![
+                name.clone(),
+                d.state.energy_level.to_string(),
+                d.state.mood.clone(),
+                d.state.tokens_used.to_string(),
+                d.state.token_budget.to_string(),
+                d.stats.memory_count.to_string(),
+                ago(d.activity.last_learn_at),
+                ago(d.activity.last_digest_at),
+                d.webhooks.recent_failures.to_string(),
+            ]);
+        }
+        table.print(output);
+        return Ok(());
+    }
+
+    for (i, (name, dashboard)) in dashboards.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        let d = match dashboard {
+            Ok(d) => d,
+            Err(e) => {
+                println!("{} {} {:#}", name.cyan().bold(), "Error:".red(), e);
+                continue;
+            }
+        };
+
+        let energy = format!("{}%", d.state.energy_level);
+        let energy = match d.state.energy_level {
+            e if e < 20 => energy.red(),
+            e if e < 50 => energy.yellow(),
+            _ => energy.green(),
+        };
+        let budget_pct = if d.state.token_budget > 0 {
+            d.state.tokens_used as f64 * 100.0 / d.state.token_budget as f64
+        } else {
+            0.0
+        };
+
+        println!(
+            "{} {} {}",
+            name.cyan().bold(),
+            d.rei.name,
+            d.rei.role.dimmed()
+        );
+        println!(
+            "  Energy:   {} (+{}/h)  Mood: {}",
+            energy, d.state.energy_regen_per_hour, d.state.mood
+        );
+        println!(
+            "  Tokens:   {} / {} ({:.0}%)",
+            d.state.tokens_used, d.state.token_budget, budget_pct
+        );
+        println!(
+            "  Memories: {}  Teis: {}",
+            d.stats.memory_count, d.stats.tei_count
+        );
+        println!(
+            "  Activity: learned {}, digested {}, active {}",
+            ago(d.activity.last_learn_at),
+            ago(d.activity.last_digest_at),
+            ago(d.activity.last_active_at)
+        );
+
+        if d.webhooks.recent_failures == 0 {
+            println!(
+                "  Webhooks: {} (no failures in 24h)",
+                d.webhooks.webhook_count
+            );
+        } else {
+            println!(
+                "  Webhooks: {} ({})",
+                d.webhooks.webhook_count,
+                format!("{} failures in 24h", d.webhooks.recent_failures).red()
+            );
+            for webhook in d.webhooks.per_webhook.iter().filter(|w| w.failed > 0) {
+                println!(
+                    "    {} {} failed, last {}",
+                    webhook.name,
+                    webhook.failed,
+                    ago(webhook.last_failure_at)
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// What `kaiba watch` has already printed
+#[derive(Default)]
+struct WatchState {
+    calls_since: Option<chrono::DateTime<chrono::Utc>>,
+    memories_since: Option<chrono::DateTime<chrono::Utc>>,
+    seen_calls: HashSet<Uuid>,
+    seen_memories: HashSet<String>,
+    /// Last known status of each delivery, to report retries and failures
+    deliveries: HashMap<Uuid, String>,
+    /// Set after the first poll; earlier deliveries are not printed
+    primed: bool,
+}
+
+async fn cmd_watch(profile: Option<String>, interval: u64, output: OutputFormat) -> Result<()> {
+    let config = Config::load()?;
+    let api_key = config
+        .api_key()
+        .context("Not logged in. Run 'kaiba login' first.")?;
+
+    let rei_id = config.get_rei_id(profile.as_deref()).context(
+        "No profile specified and no default profile set. Use -p <profile> or set a default.",
+    )?;
+
+    let client = KaibaClient::new(config.base_url(), api_key);
+
+    if !output.is_json() {
+        eprintln!(
+            "Watching {} live (Ctrl-C to stop)",
+            short_id(&rei_id).cyan()
+        );
+    }
+    loop {
+        match client
+            .stream_events(&rei_id, |event| print_stream_event(output, &event))
+            .await
+        {
+            // Server ended the stream (e.g. a redeploy); reconnect
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => eprintln!("{} {:#}", "⚠".yellow(), e),
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(interval.max(1))).await;
+    }
+
+    // Servers without an event stream are polled instead
+    let now = chrono::Utc::now();
+    let mut state = WatchState {
+        calls_since: Some(now),
+        memories_since: Some(now),
+        ..Default::default()
+    };
+
+    if !output.is_json() {
+        eprintln!(
+            "Server has no live events, polling every {}s instead",
+            interval
+        );
+    }
+
+    loop {
+        // Keep watching through transient errors (e.g. a redeploying server)
+        if let Err(e) = poll_activity(&client, &rei_id, &mut state, output).await {
+            eprintln!("{} {:#}", "⚠".yellow(), e);
+        }
+        state.primed = true;
+        tokio::time::sleep(std::time::Duration::from_secs(interval.max(1))).await;
+    }
+}
+
+/// Fetch activity since the last poll and print what is new
+async fn poll_activity(
+    client: &KaibaClient,
+    rei_id: &str,
+    state: &mut WatchState,
+    output: OutputFormat,
+) -> Result<()> {
+    let calls = client.list_calls(rei_id, state.calls_since).await?;
+    for call in calls.into_iter().rev() {
+        if !state.seen_calls.insert(call.id) {
+            continue;
+        }
+        state.calls_since = state.calls_since.max(Some(call.created_at));
+        let detail = format!(
+            "{} ({} tokens)",
+            truncate_string(&call.message.replace('\n', " "), 60),
+            call.tokens_consumed
+        );
+        print_watch_line(output, "call", &detail, &call)?;
+    }
+
+    let memories = client.list_memories(rei_id, state.memories_since).await?;
+    for memory in memories.into_iter().rev() {
+        if !state.seen_memories.insert(memory.id.clone()) {
+            continue;
+        }
+        state.memories_since = state.memories_since.max(memory.created_at);
+        let detail = format!(
+            "[{}] {}",
+            memory.memory_type,
+            truncate_string(&memory.content.replace('\n', " "), 60)
+        );
+        print_watch_line(output, "memory", &detail, &memory)?;
+    }
+
+    for webhook in client.list_webhooks(rei_id).await? {
+        let deliveries = client
+            .list_deliveries(rei_id, &webhook.id.to_string(), None)
+            .await?;
+        for delivery in deliveries.into_iter().rev() {
+            let previous = state
+                .deliveries
+                .insert(delivery.id, delivery.status.clone());
+            if !state.primed || previous.as_deref() == Some(delivery.status.as_str()) {
+                continue;
+            }
+            let detail = format!(
+                "{} {} {}{}",
+                webhook.name,
+                delivery.event,
+                delivery.status,
+                delivery
+                    .status_code
+                    .map(|c| format!(" (HTTP {})", c))
+                    .unwrap_or_default()
+            );
+            print_watch_line(output, "webhook", &detail, &delivery)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Print one event from the live stream
+fn print_stream_event(output: OutputFormat, event: &api::StreamEvent) -> Result<()> {
+    let text = |key: &str| {
+        event
+            .data
+            .get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .replace('\n', " ")
+    };
+
+    let (kind, detail) = match event.event.as_str() {
+        "response_completed" => (
+            "call",
+            format!(
+                "{} ({} tokens)",
+                truncate_string(&text("message"), 60),
+                event.data["tokens_consumed"]
+            ),
+        ),
+        "memory_added" => (
+            "memory",
+            format!(
+                "[{}] {}",
+                text("memory_type"),
+                truncate_string(&text("content"), 60)
+            ),
+        ),
+        "webhook_delivery" => (
+            "webhook",
+            format!(
+                "{} {} {}{}",
+                text("webhook_name"),
+                text("event"),
+                text("status"),
+                event.data["status_code"]
+                    .as_i64()
+                    .map(|c| format!(" (HTTP {})", c))
+                    .unwrap_or_default()
+            ),
+        ),
+        other => (other, truncate_string(&event.data.to_string(), 80)),
+    };
+    print_watch_line(output, kind, &detail, event)
+}
+
+/// Print one watched item: a JSON line, a tab-separated row, or a colored line
+fn print_watch_line<T: serde::Serialize>(
+    output: OutputFormat,
+    kind: &str,
+    detail: &str,
+    item: &T,
+) -> Result<()> {
+    let time = chrono::Local::now().format("%H:%M:%S");
+    match output {
+        OutputFormat::Json => {
+            let line = serde_json::json!({ "kind": kind, "data": item });
+            println!("{}", serde_json::to_string(&line)?);
+        }
+        OutputFormat::Plain => println!("{}\t{}\t{}", time, kind, detail),
+        OutputFormat::Table => {
+            let kind = format!("{:<7}", kind);
+            let kind = match kind.trim_end() {
+                "call" => kind.cyan(),
+                "memory" => kind.green(),
+                _ => kind.magenta(),
+            };
+            println!("{} {} {}", time.to_string().dimmed(), kind, detail);
+        }
+    }
+    Ok(())
+}
+
+async fn cmd_learn(
+    profile: Option<String>,
+    force: bool,
+    max_queries: Option<usize>,
+    output: OutputFormat,
+) -> Result<()> {
+    let config = Config::load()?;
+    let api_key = config
+        .api_key()
+        .context("Not logged in. Run 'kaiba login' first.")?;
+
+    let rei_id = config.get_rei_id(profile.as_deref()).context(
+        "No profile specified and no default profile set. Use -p <profile> or set a default.",
+    )?;
+
+    let client = KaibaClient::new(config.base_url(), api_key);
+    let learned = client
+        .learn(&rei_id, &LearnRequest { max_queries, force })
+        .await?;
+
+    if output.is_json() {
+        return print_json(&learned);
+    }
+
+    let Some(session) = learned.session.filter(|_| learned.success) else {
+        bail!(
+            "Learning failed: {}",
+            learned.error.unwrap_or_else(|| "unknown error".to_string())
+        );
+    };
+
+    println!(
+        "{} Learning completed for {}",
+        "✓".green(),
+        session.rei_name.cyan()
+    );
+    println!(
+        "  Searches: {}/{}",
+        session.searches_completed,
+        session.queries_generated.len()
+    );
+    if session.searches_skipped > 0 {
+        println!("  Skipped (already known): {}", session.searches_skipped);
+    }
+    if session.items_learned > 0 {
+        println!("  Feed entries learned: {}", session.items_learned);
+    }
+    if !session.queries_deferred.is_empty() {
+        let resume = session
+            .resume_at
+            .map(|at| format!(" until {}", at.format("%Y-%m-%d %H:%M UTC")))
+            .unwrap_or_default();
+        println!(
+            "  {}",
+            format!(
+                "Rate limited{}: {} queries deferred to the next run",
+                resume,
+                session.queries_deferred.len()
+            )
+            .yellow()
+        );
+    }
+    println!("  Memories stored: {}", session.memories_stored);
+    for query in &session.queries_generated {
+        println!("    {} {}", "?".dimmed(), query);
+    }
+    if !session.errors.is_empty() {
+        println!("  {}", format!("Errors ({}):", session.errors.len()).red());
+        for error in &session.errors {
+            println!("    {}", error);
+        }
+    }
+
+    Ok(())
+}
+
+async fn cmd_digest(profile: Option<String>, output: OutputFormat) -> Result<()> {
+    let config = Config::load()?;
+    let api_key = config
+        .api_key()
+        .context("Not logged in. Run 'kaiba login' first.")?;
 
     let rei_id = config.get_rei_id(profile.as_deref()).context(
         "No profile specified and no default profile set. Use -p <profile> or set a default.",
     )?;
 
-    let client = KaibaClient::new(&config.base_url, api_key);
+    let client = KaibaClient::new(config.base_url(), api_key);
+    let digested = client.digest(&rei_id).await?;
 
-    let prompt_resp = client
-        .get_prompt(&rei_id, Some(&format), include_memories, context.as_deref())
-        .await?;
+    if output.is_json() {
+        return print_json(&digested);
+    }
 
-    if verbose {
-        // Show metadata to stderr so stdout is clean for piping
-        eprintln!(
-            "{} {} ({}) [{}%] - {} format, {} memories",
-            "Prompt for".dimmed(),
-            prompt_resp.rei.name.cyan(),
-            prompt_resp.rei.role.dimmed(),
-            prompt_resp.rei.energy_level,
-            prompt_resp.format.green(),
-            prompt_resp.memories_included
+    let Some(result) = digested.result.filter(|_| digested.success) else {
+        bail!(
+            "Digest failed: {}",
+            digested
+                .error
+                .unwrap_or_else(|| "unknown error".to_string())
         );
-        eprintln!("{}", "---".dimmed());
-    }
+    };
 
-    // Output the prompt to stdout (clean for piping)
-    println!("{}", prompt_resp.system_prompt);
+    println!(
+        "{} Digested {} memories",
+        "✓".green(),
+        result.memories_processed
+    );
+    if result.expertise_created {
+        println!("  Expertise memory created");
+    }
+    if !result.summary.is_empty() {
+        println!("\n{}", result.summary);
+    }
 
     Ok(())
 }
 
-/// Truncate string safely for UTF-8 (by char count, not bytes)
-fn truncate_string(s: &str, max_chars: usize) -> String {
-    let chars: Vec<char> = s.chars().take(max_chars).collect();
-    if s.chars().count() > max_chars {
-        format!("{}...", chars.into_iter().collect::<String>())
-    } else {
-        s.to_string()
+fn cmd_config(action: ConfigAction, output: OutputFormat) -> Result<()> {
+    let mut config = Config::load()?;
+
+    match action {
+        ConfigAction::Show => {}
+        ConfigAction::Get { key } => {
+            let value = config.get_setting(&key)?;
+            if output.is_json() {
+                return print_json(&serde_json::json!({ "key": key, "value": value }));
+            }
+            // Unset values print nothing, so `$(kaiba config get ...)` stays empty
+            if let Some(value) = value {
+                println!("{}", value);
+            }
+            return Ok(());
+        }
+        ConfigAction::Set { key, value } => {
+            config.set_setting(&key, value)?;
+            config.save()?;
+            if !output.is_json() {
+                println!("{} {} updated", "✓".green(), key.cyan());
+            }
+            return Ok(());
+        }
+        ConfigAction::Unset { key } => {
+            config.unset_setting(&key)?;
+            config.save()?;
+            if !output.is_json() {
+                println!("{} {} cleared", "✓".green(), key.cyan());
+            }
+            return Ok(());
+        }
+        ConfigAction::Path => {
+            println!("{}", Config::config_path()?.display());
+            return Ok(());
+        }
     }
-}
 
-fn cmd_config() -> Result<()> {
-    let config = Config::load()?;
+    if output.is_json() {
+        return print_json(&serde_json::json!({
+            "path": Config::config_path()?,
+            "context": config.current_context_name(),
+            "base_url": config.base_url(),
+            "api_key_set": config.api_key().is_some(),
+            "keychain": config.uses_keychain(),
+            "default_profile": config.default_profile,
+            "profiles": config.profiles,
+            "env_overrides": config.env_overrides(),
+        }));
+    }
 
     println!("{}", "Configuration:".bold());
     println!("  Path: {:?}", Config::config_path()?);
-    println!("  Base URL: {}", config.base_url);
+    println!("  Context: {}", config.current_context_name().cyan());
+    println!("  Base URL: {}", config.base_url());
     println!(
         "  API Key: {}",
-        if config.api_key.is_some() {
+        if config.api_key().is_some() && config.uses_keychain() {
+            "Set (keychain)".green()
+        } else if config.api_key().is_some() {
             "Set".green()
         } else {
             "Not set".red()
@@ -609,18 +2850,317 @@ fn cmd_config() -> Result<()> {
         config.default_profile.as_deref().unwrap_or("None").cyan()
     );
     println!("  Profiles: {}", config.profiles.len());
+    let overrides = config.env_overrides();
+    if !overrides.is_empty() {
+        println!("  Overridden by: {}", overrides.join(", ").yellow());
+    }
+
+    Ok(())
+}
+
+async fn cmd_doctor(output: OutputFormat) -> Result<()> {
+    let mut checks = Vec::new();
+
+    let config = match Config::load() {
+        Ok(config) => {
+            checks.push(Check::ok(
+                "config",
+                format!(
+                    "{} (context: {})",
+                    Config::config_path()?.display(),
+                    config.current_context_name()
+                ),
+            ));
+            config
+        }
+        Err(e) => {
+            checks.push(Check::fail(
+                "config",
+                format!("{:#}", e),
+                format!(
+                    "Fix or delete {}, then run 'kaiba login'",
+                    Config::config_path()?.display()
+                ),
+            ));
+            return report_checks(&checks, output);
+        }
+    };
+
+    let client = KaibaClient::new(config.base_url(), config.api_key().unwrap_or_default());
+    let started = std::time::Instant::now();
+    let info = match client.server_info().await {
+        Ok(info) => {
+            checks.push(Check::ok(
+                "server",
+                format!(
+                    "{} ({} ms)",
+                    config.base_url(),
+                    started.elapsed().as_millis()
+                ),
+            ));
+            info
+        }
+        Err(e) => {
+            checks.push(Check::fail(
+                "server",
+                format!("{} unreachable: {:#}", config.base_url(), e),
+                "Check the URL ('kaiba config get base_url') or set it with 'kaiba config set base_url <URL>'",
+            ));
+            return report_checks(&checks, output);
+        }
+    };
+
+    let cli_version = env!("CARGO_PKG_VERSION");
+    checks.push(match &info.version {
+        Some(server) if doctor::versions_compatible(cli_version, server) => {
+            Check::ok("version", format!("server {}, cli {}", server, cli_version))
+        }
+        Some(server) => Check::warn(
+            "version",
+            format!("server {} may not match cli {}", server, cli_version),
+            "Update the CLI ('cargo install kaiba-cli') or redeploy the server",
+        ),
+        None => Check::warn(
+            "version",
+            "Server did not report a version",
+            "Redeploy the server",
+        ),
+    });
+
+    let authenticated = match config.api_key() {
+        None => {
+            checks.push(Check::fail("auth", "No API key", "Run 'kaiba login'"));
+            false
+        }
+        Some(_) => match client.list_reis().await {
+            Ok(reis) => {
+                checks.push(Check::ok(
+                    "auth",
+                    format!("API key accepted ({} Reis)", reis.len()),
+                ));
+                true
+            }
+            Err(e) => {
+                checks.push(Check::fail(
+                    "auth",
+                    format!("{:#}", e),
+                    "Run 'kaiba login' with a valid key",
+                ));
+                false
+            }
+        },
+    };
+
+    checks.push(match &config.default_profile {
+        Some(name) => match config.get_rei_id(Some(name)) {
+            None => Check::warn(
+                "profile",
+                format!("Default profile '{}' does not exist", name),
+                "Run 'kaiba profile set <name>'",
+            ),
+            Some(rei_id) if authenticated => match client.get_rei(&rei_id).await {
+                Ok(rei) => Check::ok("profile", format!("{} → {}", name, rei.name)),
+                Err(_) => Check::warn(
+                    "profile",
+                    format!("Rei of profile '{}' not found on this server", name),
+                    "Run 'kaiba profile sync --prune'",
+                ),
+            },
+            Some(_) => Check::ok("profile", name.clone()),
+        },
+        None if config.profiles.is_empty() => Check::warn(
+            "profile",
+            "No profiles",
+            "Run 'kaiba profile sync' to add one per Rei",
+        ),
+        None => Check::warn(
+            "profile",
+            "No default profile",
+            "Run 'kaiba profile set <name>'",
+        ),
+    });
+
+    match &info.services {
+        Some(services) => {
+            checks.push(if services.memory && services.embedding {
+                Check::ok(
+                    "memories",
+                    "Memory search and prompts with memories enabled",
+                )
+            } else {
+                Check::warn(
+                    "memories",
+                    "Memory search and prompts with memories are disabled on the server",
+                    "Set QDRANT_URL and OPENAI_API_KEY in the server secrets",
+                )
+            });
+            checks.push(if services.web_search && services.digest {
+                Check::ok("learning", "Self-learning and digest enabled")
+            } else {
+                Check::warn(
+                    "learning",
+                    "Self-learning and digest are disabled on the server",
+                    "Set GEMINI_API_KEY in the server secrets",
+                )
+            });
+        }
+        None => checks.push(Check::warn(
+            "services",
+            "Server does not report its optional services",
+            "Redeploy the server to a newer version",
+        )),
+    }
+
+    report_checks(&checks, output)
+}
+
+/// Print doctor results; fails if any check failed
+fn report_checks(checks: &[Check], output: OutputFormat) -> Result<()> {
+    if output.is_json() {
+        print_json(&checks)?;
+    } else {
+        for check in checks {
+            check.print();
+        }
+    }
+
+    let failed = checks
+        .iter()
+        .filter(|c| c.status == CheckStatus::Fail)
+        .count();
+    if failed > 0 {
+        bail!("{} check(s) failed", failed);
+    }
+    Ok(())
+}
+
+fn cmd_context(action: ContextAction, output: OutputFormat) -> Result<()> {
+    let mut config = Config::load()?;
+
+    match action {
+        ContextAction::Add {
+            name,
+            url,
+            api_key,
+            switch,
+        } => {
+            if name == config::DEFAULT_CONTEXT {
+                bail!(
+                    "'{}' is the top-level config; edit base_url there instead",
+                    config::DEFAULT_CONTEXT
+                );
+            }
+            config.add_context(name.clone(), url.clone(), api_key);
+            if switch {
+                config.use_context(&name);
+            }
+            config.save()?;
+
+            if output.is_json() {
+                return print_json(&serde_json::json!({
+                    "name": name,
+                    "base_url": url,
+                    "current": switch,
+                }));
+            }
+            println!(
+                "{} Context '{}' added ({})",
+                "✓".green(),
+                name,
+                url.dimmed()
+            );
+            if switch {
+                println!("  Switched to '{}'", name.cyan());
+            }
+        }
+
+        ContextAction::List => {
+            let current = config.current_context_name().to_string();
+            let mut contexts: Vec<(String, String, bool)> = vec![(
+                config::DEFAULT_CONTEXT.to_string(),
+                config.base_url.clone(),
+                config.api_key.is_some() || config.keychain,
+            )];
+            let mut named: Vec<_> = config.contexts.iter().collect();
+            named.sort_by(|a, b| a.0.cmp(b.0));
+            contexts.extend(named.into_iter().map(|(name, ctx)| {
+                (
+                    name.clone(),
+                    ctx.base_url.clone(),
+                    ctx.api_key.is_some() || ctx.keychain,
+                )
+            }));
+
+            if output.is_json() {
+                let contexts: Vec<serde_json::Value> = contexts
+                    .iter()
+                    .map(|(name, url, key)| {
+                        serde_json::json!({
+                            "name": name,
+                            "base_url": url,
+                            "api_key_set": key,
+                            "current": *name == current,
+                        })
+                    })
+                    .collect();
+                return print_json(&contexts);
+            }
+
+            let mut table = Table::new(vec!["CURRENT", "NAME", "URL", "API KEY"]);
+            for (name, url, key) in contexts {
+                table.add_row(vec![
+                    if name == current { "*" } else { "" }.to_string(),
+                    name,
+                    url,
+                    if key { "set" } else { "-" }.to_string(),
+                ]);
+            }
+            table.print(output);
+        }
+
+        ContextAction::Use { name } => {
+            if !config.use_context(&name) {
+                bail!("Context '{}' not found", name);
+            }
+            config.save()?;
+
+            if output.is_json() {
+                return print_json(&serde_json::json!({
+                    "current": name,
+                    "base_url": config.base_url(),
+                }));
+            }
+            println!(
+                "{} Switched to '{}' ({})",
+                "✓".green(),
+                name.cyan(),
+                config.base_url().dimmed()
+            );
+        }
+
+        ContextAction::Remove { name } => {
+            if !config.remove_context(&name) {
+                bail!("Context '{}' not found", name);
+            }
+            config.save()?;
+
+            if output.is_json() {
+                return print_json(&serde_json::json!({ "removed": name }));
+            }
+            println!("{} Context '{}' removed", "✓".green(), name);
+        }
+    }
 
     Ok(())
 }
 
-async fn cmd_webhook(action: WebhookAction) -> Result<()> {
+async fn cmd_webhook(action: WebhookAction, output: OutputFormat) -> Result<()> {
     let config = Config::load()?;
     let api_key = config
-        .api_key
-        .as_ref()
+        .api_key()
         .context("Not logged in. Run 'kaiba login' first.")?;
 
-    let client = KaibaClient::new(&config.base_url, api_key);
+    let client = KaibaClient::new(config.base_url(), api_key);
 
     match action {
         WebhookAction::List { profile } => {
@@ -630,49 +3170,38 @@ async fn cmd_webhook(action: WebhookAction) -> Result<()> {
 
             let webhooks = client.list_webhooks(&rei_id).await?;
 
+            if output.is_json() {
+                return print_json(&webhooks);
+            }
+
             if webhooks.is_empty() {
                 println!("No webhooks configured.");
                 return Ok(());
             }
 
-            let profile_name = profile
-                .as_deref()
-                .or(config.default_profile.as_deref())
-                .unwrap_or("default");
-
-            println!("{} ({}):", "Webhooks".bold(), profile_name.cyan());
-            for webhook in webhooks {
-                let status = if webhook.enabled {
-                    "✓".green()
-                } else {
-                    "✗".red()
-                };
-                let format_badge = webhook
-                    .payload_format
-                    .as_ref()
-                    .map(|f| format!(" [{}]", f).dimmed().to_string())
-                    .unwrap_or_default();
-
-                println!(
-                    "  {} {} {}{}",
-                    status,
-                    webhook.name.cyan().bold(),
-                    webhook.events.join(", ").dimmed(),
-                    format_badge
-                );
-                println!(
-                    "    {} → {}",
-                    &webhook.id.to_string()[..8].dimmed(),
-                    webhook.url.dimmed()
-                );
+            let mut table = Table::new(vec!["ID", "NAME", "ENABLED", "EVENTS", "FORMAT", "URL"]);
+            for webhook in &webhooks {
+                table.add_row(vec![
+                    webhook.id.to_string()[..8].to_string(),
+                    webhook.name.clone(),
+                    if webhook.enabled { "✓" } else { "✗" }.to_string(),
+                    webhook.events.join(","),
+                    webhook
+                        .payload_format
+                        .clone()
+                        .unwrap_or_else(|| "-".to_string()),
+                    webhook.url.clone(),
+                ]);
             }
+            table.print(output);
         }
 
         WebhookAction::Create {
             name,
             url,
             events,
-            format,
+            payload_format,
+            secret,
             profile,
         } => {
             let rei_id = config.get_rei_id(profile.as_deref()).context(
@@ -684,15 +3213,20 @@ async fn cmd_webhook(action: WebhookAction) -> Result<()> {
                     &rei_id,
                     &name,
                     &url,
+                    secret,
                     if events.is_empty() {
                         None
                     } else {
                         Some(events)
                     },
-                    format,
+                    payload_format,
                 )
                 .await?;
 
+            if output.is_json() {
+                return print_json(&webhook);
+            }
+
             println!("{} Webhook created: {}", "✓".green(), webhook.name.cyan());
             println!("  ID: {}", webhook.id);
             println!("  URL: {}", webhook.url.dimmed());
@@ -709,7 +3243,7 @@ async fn cmd_webhook(action: WebhookAction) -> Result<()> {
             enable,
             disable,
             events,
-            format,
+            payload_format,
             profile,
         } => {
             let rei_id = config.get_rei_id(profile.as_deref()).context(
@@ -729,9 +3263,21 @@ async fn cmd_webhook(action: WebhookAction) -> Result<()> {
             };
 
             let webhook = client
-                .update_webhook(&rei_id, &webhook_id, name, url, enabled, events, format)
+                .update_webhook(
+                    &rei_id,
+                    &webhook_id,
+                    name,
+                    url,
+                    enabled,
+                    events,
+                    payload_format,
+                )
                 .await?;
 
+            if output.is_json() {
+                return print_json(&webhook);
+            }
+
             println!("{} Webhook updated: {}", "✓".green(), webhook.name.cyan());
             println!(
                 "  Status: {}",
@@ -753,75 +3299,245 @@ async fn cmd_webhook(action: WebhookAction) -> Result<()> {
 
             client.delete_webhook(&rei_id, &webhook_id).await?;
 
+            if output.is_json() {
+                return print_json(&serde_json::json!({ "deleted": webhook_id }));
+            }
+
             println!("{} Webhook deleted: {}", "✓".green(), webhook_id.dimmed());
         }
 
         WebhookAction::Trigger {
             webhook_id,
             event,
+            dry_run,
             profile,
         } => {
             let rei_id = config.get_rei_id(profile.as_deref()).context(
                 "No profile specified and no default profile set. Use -p <profile> or set a default.",
             )?;
 
+            if dry_run {
+                let rendered = client.dry_run_webhook(&rei_id, &webhook_id, event).await?;
+
+                if output.is_json() {
+                    return print_json(&rendered);
+                }
+
+                println!(
+                    "{} {} → {}",
+                    "Dry run:".bold(),
+                    rendered.event.cyan(),
+                    rendered.url
+                );
+                if !rendered.would_deliver {
+                    println!(
+                        "  {}",
+                        "Filtered out by event subscription or conditions".yellow()
+                    );
+                }
+                if let Some(error) = rendered.error {
+                    println!("  {} {}", "Error:".red(), error);
+                    return Ok(());
+                }
+                println!("\n{}", "Headers:".bold());
+                for (key, value) in &rendered.headers {
+                    println!("  {}: {}", key, value);
+                }
+                if let Some(body) = rendered.body {
+                    println!("\n{}", "Body:".bold());
+                    println!("{}", serde_json::to_string_pretty(&body)?);
+                }
+                return Ok(());
+            }
+
             let delivery = client.trigger_webhook(&rei_id, &webhook_id, event).await?;
 
+            if output.is_json() {
+                return print_json(&delivery);
+            }
+
             println!(
                 "{} Webhook triggered: {}",
                 "✓".green(),
                 delivery.event.cyan()
             );
-            println!("  Delivery ID: {}", delivery.id);
-            println!(
-                "  Status: {}",
-                match delivery.status.as_str() {
-                    "success" => "Success".green(),
-                    "failed" => "Failed".red(),
-                    _ => delivery.status.yellow(),
-                }
-            );
-            if let Some(code) = delivery.status_code {
-                println!("  HTTP Status: {}", code);
-            }
+            print_delivery_outcome(&delivery);
         }
 
         WebhookAction::Deliveries {
             webhook_id,
+            status,
             profile,
         } => {
             let rei_id = config.get_rei_id(profile.as_deref()).context(
                 "No profile specified and no default profile set. Use -p <profile> or set a default.",
             )?;
 
-            let deliveries = client.list_deliveries(&rei_id, &webhook_id).await?;
+            let deliveries = client
+                .list_deliveries(&rei_id, &webhook_id, status.as_deref())
+                .await?;
+
+            if output.is_json() {
+                return print_json(&deliveries);
+            }
 
             if deliveries.is_empty() {
                 println!("No deliveries found.");
                 return Ok(());
             }
 
-            println!("{} deliveries:", deliveries.len().to_string().green());
-            for delivery in deliveries {
-                let status_badge = match delivery.status.as_str() {
-                    "success" => "✓".green(),
-                    "failed" => "✗".red(),
-                    "pending" => "⏳".yellow(),
-                    _ => "?".dimmed(),
-                };
-
-                println!(
-                    "  {} {} [{}] (attempts: {})",
-                    status_badge,
-                    delivery.event.cyan(),
+            let mut table =
+                Table::new(vec!["ID", "EVENT", "STATUS", "HTTP", "ATTEMPTS", "CREATED"]);
+            for delivery in &deliveries {
+                table.add_row(vec![
+                    delivery.id.to_string(),
+                    delivery.event.clone(),
+                    delivery.status.clone(),
                     delivery
                         .status_code
                         .map(|c| c.to_string())
                         .unwrap_or_else(|| "-".to_string()),
-                    delivery.attempts
+                    delivery.attempts.to_string(),
+                    delivery.created_at.clone(),
+                ]);
+            }
+            table.print(output);
+        }
+
+        WebhookAction::Replay {
+            webhook_id,
+            delivery_id,
+            profile,
+        } => {
+            let rei_id = config.get_rei_id(profile.as_deref()).context(
+                "No profile specified and no default profile set. Use -p <profile> or set a default.",
+            )?;
+
+            let delivery = client
+                .replay_delivery(&rei_id, &webhook_id, &delivery_id)
+                .await?;
+
+            if output.is_json() {
+                return print_json(&delivery);
+            }
+
+            println!(
+                "{} Delivery replayed: {}",
+                "✓".green(),
+                delivery.event.cyan()
+            );
+            print_delivery_outcome(&delivery);
+        }
+    }
+
+    Ok(())
+}
+
+/// Print the ID, status, and HTTP code of a single delivery
+fn print_delivery_outcome(delivery: &api::WebhookDeliveryResponse) {
+    println!("  Delivery ID: {}", delivery.id);
+    println!(
+        "  Status: {}",
+        match delivery.status.as_str() {
+            "success" => "Success".green(),
+            "failed" => "Failed".red(),
+            "dead" => "Dead (retries exhausted)".red(),
+            _ => delivery.status.yellow(),
+        }
+    );
+    if let Some(code) = delivery.status_code {
+        println!("  HTTP Status: {}", code);
+    }
+}
+
+async fn cmd_event(action: EventAction, output: OutputFormat) -> Result<()> {
+    let config = Config::load()?;
+    let api_key = config
+        .api_key()
+        .context("Not logged in. Run 'kaiba login' first.")?;
+
+    let client = KaibaClient::new(config.base_url(), api_key);
+
+    match action {
+        EventAction::List => {
+            let events = client.list_events().await?;
+
+            if output.is_json() {
+                return print_json(&events);
+            }
+
+            if events.is_empty() {
+                println!("No custom events registered.");
+                return Ok(());
+            }
+
+            println!("{}:", "Custom events".bold());
+            for event in events {
+                println!(
+                    "  {} {}",
+                    event.event.cyan().bold(),
+                    event.description.unwrap_or_default().dimmed()
                 );
-                println!("    {}", delivery.created_at.dimmed());
+                if event.schema.as_object().is_some_and(|s| !s.is_empty()) {
+                    println!("    schema: {}", event.schema.to_string().dimmed());
+                }
+            }
+        }
+
+        EventAction::Define {
+            name,
+            description,
+            schema,
+        } => {
+            let schema = schema
+                .map(|path| -> Result<serde_json::Value> {
+                    let content = fs::read_to_string(&path)
+                        .with_context(|| format!("Failed to read schema file: {}", path))?;
+                    serde_json::from_str(&content)
+                        .with_context(|| format!("Invalid JSON in schema file: {}", path))
+                })
+                .transpose()?;
+
+            let event = client.define_event(&name, description, schema).await?;
+
+            if output.is_json() {
+                return print_json(&event);
+            }
+
+            println!("{} Event registered: {}", "✓".green(), event.event.cyan());
+        }
+
+        EventAction::Remove { name } => {
+            client.delete_event(&name).await?;
+
+            if output.is_json() {
+                return print_json(&serde_json::json!({ "deleted": name }));
+            }
+
+            println!("{} Event removed: {}", "✓".green(), name.dimmed());
+        }
+
+        EventAction::Emit {
+            name,
+            data,
+            profile,
+        } => {
+            let rei_id = config.get_rei_id(profile.as_deref()).context(
+                "No profile specified and no default profile set. Use -p <profile> or set a default.",
+            )?;
+
+            let data = data
+                .map(|d| serde_json::from_str(&d).context("--data must be valid JSON"))
+                .transpose()?;
+
+            let emitted = client.emit_event(&rei_id, &name, data).await?;
+
+            if output.is_json() {
+                return print_json(&emitted);
             }
+
+            println!("{} Event emitted: {}", "✓".green(), emitted.event.cyan());
+            println!("  Event ID: {}", emitted.event_id);
         }
     }
 
@@ -0,0 +1,91 @@
+//! Output formatting - aligned tables, JSON, or plain text for scripting
+
+use anyhow::Result;
+use clap::ValueEnum;
+use colored::Colorize;
+use serde::Serialize;
+
+/// How command results are printed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable table
+    #[default]
+    Table,
+    /// Pretty-printed JSON
+    Json,
+    /// Uncolored, tab-separated text
+    Plain,
+}
+
+impl OutputFormat {
+    /// Apply process-wide settings; colors are only used for tables
+    pub fn apply(self) {
+        if self != OutputFormat::Table {
+            colored::control::set_override(false);
+        }
+    }
+
+    pub fn is_json(self) -> bool {
+        self == OutputFormat::Json
+    }
+}
+
+/// Print any API response as pretty JSON
+pub fn print_json<T: Serialize>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// Column-aligned table (tab-separated rows without header in plain mode)
+pub struct Table {
+    headers: Vec<&'static str>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(headers: Vec<&'static str>) -> Self {
+        Self {
+            headers,
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn add_row(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+
+    pub fn print(&self, format: OutputFormat) {
+        if format == OutputFormat::Plain {
+            for row in &self.rows {
+                println!("{}", row.join("\t"));
+            }
+            return;
+        }
+
+        let mut widths: Vec<usize> = self.headers.iter().map(|h| h.chars().count()).collect();
+        for row in &self.rows {
+            for (i, cell) in row.iter().enumerate() {
+                if let Some(width) = widths.get_mut(i) {
+                    *width = (*width).max(cell.chars().count());
+                }
+            }
+        }
+
+        let header: Vec<String> = self
+            .headers
+            .iter()
+            .zip(&widths)
+            .map(|(h, w)| format!("{:<w$}", h, w = *w))
+            .collect();
+        println!("{}", header.join("  ").trim_end().bold());
+
+        for row in &self.rows {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(c, w)| format!("{:<w$}", c, w = *w))
+                .collect();
+            println!("{}", cells.join("  ").trim_end());
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! Time arguments and display - `--since` parsing and "3h ago" formatting
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+/// Parse `--since`: a relative duration (`30m`, `12h`, `7d`, `2w`),
+/// a date (`2025-01-31`), or an RFC 3339 timestamp
+pub fn parse_since(value: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    let value = value.trim();
+
+    if let Ok(ts) = DateTime::parse_from_rfc3339(value) {
+        return Ok(ts.with_timezone(&Utc));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc());
+    }
+
+    let Some(unit) = value.chars().last() else {
+        bail!("Empty --since value");
+    };
+    let amount: i64 = value[..value.len() - unit.len_utf8()]
+        .parse()
+        .with_context(|| format!("Invalid --since value '{}'", value))?;
+    let duration = match unit {
+        'm' => Duration::try_minutes(amount),
+        'h' => Duration::try_hours(amount),
+        'd' => Duration::try_days(amount),
+        'w' => Duration::try_weeks(amount),
+        _ => bail!(
+            "Invalid --since value '{}': use e.g. 12h, 7d, 2w or YYYY-MM-DD",
+            value
+        ),
+    };
+
+    duration
+        .and_then(|duration| now.checked_sub_signed(duration))
+        .with_context(|| format!("--since value '{}' is out of range", value))
+}
+
+/// Human-friendly age of a timestamp ("just now", "5m ago", "3d ago")
+pub fn ago(ts: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let secs = (now - ts).num_seconds();
+    match secs {
+        s if s < 60 => "just now".to_string(),
+        s if s < 3600 => format!("{}m ago", s / 60),
+        s if s < 86400 => format!("{}h ago", s / 3600),
+        s => format!("{}d ago", s / 86400),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_since() {
+        let now = DateTime::parse_from_rfc3339("2025-03-10T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(parse_since("7d", now).unwrap(), now - Duration::days(7));
+        assert_eq!(parse_since("12h", now).unwrap(), now - Duration::hours(12));
+        assert_eq!(parse_since("2w", now).unwrap(), now - Duration::weeks(2));
+        assert_eq!(
+            parse_since("2025-03-01", now).unwrap().to_rfc3339(),
+            "2025-03-01T00:00:00+00:00"
+        );
+        assert_eq!(
+            parse_since("2025-03-09T08:30:00+09:00", now)
+                .unwrap()
+                .to_rfc3339(),
+            "2025-03-08T23:30:00+00:00"
+        );
+
+        assert!(parse_since("", now).is_err());
+        assert!(parse_since("7y", now).is_err());
+        assert!(parse_since("d", now).is_err());
+        assert!(parse_since("yesterday", now).is_err());
+        assert!(parse_since(&format!("{}w", i64::MAX), now).is_err());
+        assert!(parse_since("-9999999999d", now).is_err());
+    }
+
+    #[test]
+    fn test_ago() {
+        let now = DateTime::parse_from_rfc3339("2025-03-10T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(ago(now, now), "just now");
+        assert_eq!(ago(now + Duration::minutes(1), now), "just now");
+        assert_eq!(ago(now - Duration::minutes(5), now), "5m ago");
+        assert_eq!(ago(now - Duration::hours(3), now), "3h ago");
+        assert_eq!(ago(now - Duration::days(2), now), "2d ago");
+    }
+}
@@ -1,12 +1,17 @@
 //! Discord API client wrapper
 
+use serenity::builder::CreateAttachment;
 use serenity::http::Http;
 use serenity::model::channel::Message as SerenityMessage;
-use serenity::model::id::ChannelId;
+use serenity::model::id::{ApplicationId, ChannelId, MessageId};
 use std::sync::Arc;
 use tracing::{debug, error};
 
 use crate::config::DiscordConfig;
+use crate::reply::{send_reply, DiscordReply, REPLY_FILENAME};
+
+/// Discord message length limit
+pub(crate) const MAX_MESSAGE_LEN: usize = 2000;
 
 /// Discord API client
 pub struct DiscordClient {
@@ -19,6 +24,10 @@ impl DiscordClient {
     /// Create a new Discord client
     pub fn new(config: DiscordConfig) -> Self {
         let http = Arc::new(Http::new(&config.token));
+        // Interaction responses are addressed by application
+        if let Some(app_id) = config.application_id {
+            http.set_application_id(ApplicationId::new(app_id));
+        }
         Self { http, config }
     }
 
@@ -41,21 +50,27 @@ impl DiscordClient {
         Ok(messages)
     }
 
-    /// Send a message to a channel
+    /// Send a message to a channel, split into several if it is long
     pub async fn send_message(
         &self,
         channel_id: u64,
         content: &str,
-    ) -> Result<SerenityMessage, serenity::Error> {
-        let channel = ChannelId::new(channel_id);
-        debug!(channel_id = %channel_id, content_len = %content.len(), "Sending message to Discord");
-
-        let message = channel
-            .say(&self.http, content)
+    ) -> Result<Vec<SerenityMessage>, serenity::Error> {
+        self.send_reply(channel_id, &DiscordReply::new(content))
             .await
-            .inspect_err(|e| error!(error = %e, "Failed to send Discord message"))?;
+    }
+
+    /// Send a reply (text, embed, attachments) to a channel
+    pub async fn send_reply(
+        &self,
+        channel_id: u64,
+        reply: &DiscordReply,
+    ) -> Result<Vec<SerenityMessage>, serenity::Error> {
+        debug!(channel_id = %channel_id, "Sending message to Discord");
 
-        Ok(message)
+        send_reply(&self.http, ChannelId::new(channel_id), reply, None)
+            .await
+            .inspect_err(|e| error!(error = %e, "Failed to send Discord message"))
     }
 
     /// Reply to a message
@@ -63,25 +78,50 @@ impl DiscordClient {
         &self,
         channel_id: u64,
         message_id: u64,
-        content: &str,
-    ) -> Result<SerenityMessage, serenity::Error> {
-        let channel = ChannelId::new(channel_id);
+        reply: &DiscordReply,
+    ) -> Result<Vec<SerenityMessage>, serenity::Error> {
         debug!(
             channel_id = %channel_id,
             message_id = %message_id,
             "Replying to Discord message"
         );
 
-        let message = channel
-            .send_message(
-                &self.http,
-                serenity::builder::CreateMessage::new()
-                    .content(content)
-                    .reference_message((channel, serenity::model::id::MessageId::new(message_id))),
-            )
-            .await?;
+        send_reply(
+            &self.http,
+            ChannelId::new(channel_id),
+            reply,
+            Some(MessageId::new(message_id)),
+        )
+        .await
+    }
+
+    /// Replace a deferred interaction response with the reply
+    ///
+    /// An interaction has a single response, so a reply over the length
+    /// limit is cut short and attached in full.
+    pub async fn edit_interaction_response(
+        &self,
+        interaction_token: &str,
+        content: &str,
+    ) -> Result<SerenityMessage, serenity::Error> {
+        debug!(content_len = %content.len(), "Editing Discord interaction response");
 
-        Ok(message)
+        let attachments = if content.chars().count() > MAX_MESSAGE_LEN {
+            vec![CreateAttachment::bytes(
+                content.as_bytes().to_vec(),
+                REPLY_FILENAME,
+            )]
+        } else {
+            vec![]
+        };
+        self.http
+            .edit_original_interaction_response(
+                interaction_token,
+                &serde_json::json!({ "content": truncate(content) }),
+                attachments,
+            )
+            .await
+            .inspect_err(|e| error!(error = %e, "Failed to edit Discord interaction response"))
     }
 
     /// Get the underlying HTTP client for advanced operations
@@ -89,3 +129,13 @@ impl DiscordClient {
         &self.http
     }
 }
+
+/// Cut text down to Discord's message length limit
+fn truncate(text: &str) -> String {
+    if text.chars().count() > MAX_MESSAGE_LEN {
+        let truncated: String = text.chars().take(MAX_MESSAGE_LEN - 3).collect();
+        format!("{}...", truncated)
+    } else {
+        text.to_string()
+    }
+}
@@ -0,0 +1,71 @@
+//! `/kaiba` slash command definition and registration
+//!
+//! - `/kaiba ask <question>` - Ask the channel's Rei
+//! - `/kaiba remember <text>` - Store a memory
+//! - `/kaiba recall <query>` - Search the Rei's memories
+
+use serenity::all::{CommandOptionType, CreateCommand, CreateCommandOption, GuildId, Http};
+use tracing::info;
+
+/// Top-level command name
+pub const COMMAND_NAME: &str = "kaiba";
+
+/// Subcommands of `/kaiba`, with their single required text option
+const SUBCOMMANDS: [(&str, &str, &str, &str); 3] = [
+    (
+        "ask",
+        "Ask the Rei of this channel",
+        "question",
+        "What to ask",
+    ),
+    (
+        "remember",
+        "Store a memory for the Rei of this channel",
+        "text",
+        "What to remember",
+    ),
+    (
+        "recall",
+        "Search the memories of the Rei of this channel",
+        "query",
+        "What to look for",
+    ),
+];
+
+/// The `/kaiba` command with its subcommands
+pub fn kaiba_command() -> CreateCommand {
+    SUBCOMMANDS.iter().fold(
+        CreateCommand::new(COMMAND_NAME).description("Talk to the Rei of this channel"),
+        |command, (name, description, option, option_description)| {
+            command.add_option(
+                CreateCommandOption::new(CommandOptionType::SubCommand, *name, *description)
+                    .add_sub_option(
+                        CreateCommandOption::new(
+                            CommandOptionType::String,
+                            *option,
+                            *option_description,
+                        )
+                        .required(true),
+                    ),
+            )
+        },
+    )
+}
+
+/// Register `/kaiba` in one guild (immediate) or globally (may take up to an
+/// hour to show up)
+pub async fn register_commands(http: &Http, guild_id: Option<u64>) -> Result<(), serenity::Error> {
+    match guild_id {
+        Some(guild_id) => {
+            GuildId::new(guild_id)
+                .set_commands(http, vec![kaiba_command()])
+                .await?;
+            info!(guild_id = %guild_id, "Registered Discord slash commands");
+        }
+        None => {
+            serenity::all::Command::create_global_command(http, kaiba_command()).await?;
+            info!("Registered global Discord slash commands");
+        }
+    }
+    Ok(())
+}
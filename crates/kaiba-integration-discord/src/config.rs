@@ -17,6 +17,27 @@ pub struct DiscordConfig {
     pub respond_to_mentions: bool,
     /// Whether to respond to DMs
     pub respond_to_dms: bool,
+    /// Whether to answer each conversation in its own thread
+    #[serde(default = "default_thread_per_conversation")]
+    pub thread_per_conversation: bool,
+    /// Whether replies carry an embed with the Rei's name, mood and energy
+    #[serde(default = "default_rich_replies")]
+    pub rich_replies: bool,
+    /// Reacting with this emoji stores the message as a memory of the Rei
+    #[serde(default = "default_bookmark_emoji")]
+    pub bookmark_emoji: String,
+}
+
+fn default_thread_per_conversation() -> bool {
+    true
+}
+
+fn default_rich_replies() -> bool {
+    true
+}
+
+fn default_bookmark_emoji() -> String {
+    "🧠".to_string()
 }
 
 impl DiscordConfig {
@@ -29,6 +50,9 @@ impl DiscordConfig {
             enable_slash_commands: false,
             respond_to_mentions: true,
             respond_to_dms: true,
+            thread_per_conversation: true,
+            rich_replies: true,
+            bookmark_emoji: default_bookmark_emoji(),
         }
     }
 
@@ -49,6 +73,24 @@ impl DiscordConfig {
         self.enable_slash_commands = enable;
         self
     }
+
+    /// Answer each conversation in its own thread
+    pub fn with_threads(mut self, enable: bool) -> Self {
+        self.thread_per_conversation = enable;
+        self
+    }
+
+    /// Show the Rei's card (an embed colored by its mood) under replies
+    pub fn with_rich_replies(mut self, enable: bool) -> Self {
+        self.rich_replies = enable;
+        self
+    }
+
+    /// Set the emoji that bookmarks a message as a memory
+    pub fn with_bookmark_emoji(mut self, emoji: impl Into<String>) -> Self {
+        self.bookmark_emoji = emoji.into();
+        self
+    }
 }
 
 impl Default for DiscordConfig {
@@ -60,6 +102,9 @@ impl Default for DiscordConfig {
             enable_slash_commands: false,
             respond_to_mentions: true,
             respond_to_dms: true,
+            thread_per_conversation: true,
+            rich_replies: true,
+            bookmark_emoji: default_bookmark_emoji(),
         }
     }
 }
@@ -0,0 +1,382 @@
+//! Discord gateway runner
+//!
+//! Keeps a gateway connection open and answers messages in the channels
+//! Reis are bound to (`discord_channel_id` in the manifest). Each message is
+//! handed to an [`IntegrationEventHandler`] (the server's call pipeline), and
+//! its reply is posted back as a reply to the message (see
+//! [`DiscordReply`](crate::DiscordReply) for long replies and the Rei's card).
+//!
+//! With `thread_per_conversation` (the default), a message in a bound channel
+//! starts a thread and the Rei answers there; later messages in the thread
+//! continue the same conversation. The event metadata carries the thread ID
+//! and a call session derived from it, so several users talking in one
+//! channel don't share (or interleave) a context.
+//!
+//! Reacting to a message in a bound channel (or its threads) with the
+//! bookmark emoji (🧠 unless configured) stores the message as a memory of
+//! the Rei; the bot marks it ✅ so later reactions don't store it again.
+//!
+//! With slash commands enabled, `/kaiba` is registered once connected; the
+//! commands themselves arrive as interaction webhooks (see
+//! [`DiscordWebhookHandler::parse_interaction`](crate::DiscordWebhookHandler::parse_interaction)).
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use kaiba::domain::entities::Rei;
+use kaiba::domain::errors::DomainError;
+use kaiba::ports::integration::{IntegrationEvent, IntegrationEventHandler};
+use kaiba::ports::ReiRepository;
+use serenity::all::{
+    ChannelId, Context, CreateThread, EventHandler, GatewayIntents, GuildChannel,
+    Message as SerenityMessage, Reaction, ReactionType, Ready, ThreadListSyncEvent,
+};
+use serenity::Client;
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::commands::register_commands;
+use crate::config::DiscordConfig;
+use crate::integration::manifest_channel_id;
+use crate::reply::{send_reply, DiscordReply, ReplyEmbed};
+
+/// How long the channel → Rei map is used before manifests are reloaded
+const CHANNEL_REFRESH: Duration = Duration::from_secs(60);
+
+/// Longest thread name taken from the opening message (Discord allows 100)
+const MAX_THREAD_NAME_CHARS: usize = 80;
+
+/// Reaction the bot leaves on a bookmarked message
+const BOOKMARKED: &str = "✅";
+
+/// Runs the Discord gateway connection for all Reis bound to a channel
+pub struct DiscordGatewayRunner {
+    config: DiscordConfig,
+    reis: Arc<dyn ReiRepository>,
+    handler: Arc<dyn IntegrationEventHandler>,
+}
+
+impl DiscordGatewayRunner {
+    /// Create a runner answering through `handler`
+    pub fn new(
+        config: DiscordConfig,
+        reis: Arc<dyn ReiRepository>,
+        handler: Arc<dyn IntegrationEventHandler>,
+    ) -> Self {
+        Self {
+            config,
+            reis,
+            handler,
+        }
+    }
+
+    /// Connect and handle events until the connection fails or `shutdown`
+    /// completes
+    pub async fn run_until(
+        &self,
+        shutdown: impl Future<Output = ()> + Send,
+    ) -> Result<(), DomainError> {
+        // GUILDS delivers thread events, to know which threads belong to a bound channel
+        let intents = GatewayIntents::GUILDS
+            | GatewayIntents::GUILD_MESSAGES
+            | GatewayIntents::GUILD_MESSAGE_REACTIONS
+            | GatewayIntents::MESSAGE_CONTENT;
+        let events = GatewayEvents {
+            reis: self.reis.clone(),
+            handler: self.handler.clone(),
+            channels: Mutex::new(ChannelMap::default()),
+            threads: Mutex::new(HashMap::new()),
+            use_threads: self.config.thread_per_conversation,
+            slash_commands: self.config.enable_slash_commands,
+            guild_id: self.config.guild_id,
+            rich_replies: self.config.rich_replies,
+            bookmark_emoji: self.config.bookmark_emoji.clone(),
+        };
+
+        let mut client = Client::builder(&self.config.token, intents)
+            .event_handler(events)
+            .await
+            .map_err(|e| DomainError::ExternalService(format!("Discord API error: {}", e)))?;
+        let shard_manager = client.shard_manager.clone();
+
+        tokio::select! {
+            result = client.start() => result.map_err(|e| {
+                DomainError::ExternalService(format!("Discord gateway error: {}", e))
+            }),
+            _ = shutdown => {
+                shard_manager.shutdown_all().await;
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Channel → Rei bindings, reloaded every [`CHANNEL_REFRESH`]
+#[derive(Default)]
+struct ChannelMap {
+    loaded_at: Option<Instant>,
+    reis: HashMap<u64, Rei>,
+}
+
+/// Serenity event handler behind the runner
+struct GatewayEvents {
+    reis: Arc<dyn ReiRepository>,
+    handler: Arc<dyn IntegrationEventHandler>,
+    channels: Mutex<ChannelMap>,
+    /// Thread → parent channel, for every thread seen
+    threads: Mutex<HashMap<u64, u64>>,
+    /// Answer each conversation in its own thread
+    use_threads: bool,
+    /// Register `/kaiba` once connected
+    slash_commands: bool,
+    guild_id: Option<u64>,
+    /// Show the Rei's card under replies
+    rich_replies: bool,
+    /// Reaction that bookmarks a message
+    bookmark_emoji: String,
+}
+
+impl GatewayEvents {
+    /// The Rei bound to a channel, if any
+    async fn rei_for(&self, channel_id: u64) -> Option<Rei> {
+        let mut channels = self.channels.lock().await;
+        let stale = channels
+            .loaded_at
+            .is_none_or(|at| at.elapsed() >= CHANNEL_REFRESH);
+        if stale {
+            match self.reis.find_all().await {
+                Ok(reis) => {
+                    channels.reis = reis
+                        .into_iter()
+                        .filter_map(|rei| manifest_channel_id(&rei).map(|id| (id, rei)))
+                        .collect();
+                    channels.loaded_at = Some(Instant::now());
+                }
+                // Keep answering with the bindings we have
+                Err(e) => warn!(error = %e, "Failed to load Discord channel bindings"),
+            }
+        }
+        channels.reis.get(&channel_id).cloned()
+    }
+
+    /// Remember which channel a thread belongs to
+    async fn track_thread(&self, thread: &GuildChannel) {
+        if let Some(parent_id) = thread.parent_id {
+            self.threads
+                .lock()
+                .await
+                .insert(thread.id.get(), parent_id.get());
+        }
+    }
+
+    /// Where a message goes: the Rei it is addressed to, and the thread its
+    /// conversation continues in (if it was posted in one)
+    async fn route(&self, channel_id: u64) -> Option<(Rei, Option<u64>)> {
+        if let Some(rei) = self.rei_for(channel_id).await {
+            return Some((rei, None));
+        }
+        let parent_id = self.threads.lock().await.get(&channel_id).copied()?;
+        let rei = self.rei_for(parent_id).await?;
+        Some((rei, Some(channel_id)))
+    }
+
+    /// Start a thread on the message that opens a conversation
+    async fn start_thread(&self, ctx: &Context, msg: &SerenityMessage) -> Option<ChannelId> {
+        let thread = msg
+            .channel_id
+            .create_thread_from_message(&ctx.http, msg.id, CreateThread::new(thread_name(msg)))
+            .await
+            .inspect_err(|e| warn!(error = %e, "Failed to start Discord thread"))
+            .ok()?;
+        self.track_thread(&thread).await;
+        Some(thread.id)
+    }
+}
+
+#[async_trait]
+impl EventHandler for GatewayEvents {
+    async fn ready(&self, ctx: Context, ready: Ready) {
+        info!(user = %ready.user.name, "Discord gateway connected");
+
+        if self.slash_commands {
+            if let Err(e) = register_commands(&ctx.http, self.guild_id).await {
+                warn!(error = %e, "Failed to register Discord slash commands");
+            }
+        }
+    }
+
+    async fn thread_create(&self, _ctx: Context, thread: GuildChannel) {
+        self.track_thread(&thread).await;
+    }
+
+    async fn thread_list_sync(&self, _ctx: Context, sync: ThreadListSyncEvent) {
+        for thread in &sync.threads {
+            self.track_thread(thread).await;
+        }
+    }
+
+    async fn message(&self, ctx: Context, msg: SerenityMessage) {
+        if msg.author.bot || msg.content.trim().is_empty() {
+            return;
+        }
+        let Some((rei, thread_id)) = self.route(msg.channel_id.get()).await else {
+            return;
+        };
+        debug!(channel_id = %msg.channel_id, rei_name = %rei.name, "Routing Discord message");
+
+        // A message in the channel itself opens a new conversation
+        let thread_id = match thread_id {
+            Some(thread_id) => Some(ChannelId::new(thread_id)),
+            None if self.use_threads && msg.guild_id.is_some() => {
+                self.start_thread(&ctx, &msg).await
+            }
+            None => None,
+        };
+
+        let event = IntegrationEvent::MessageReceived {
+            channel_id: msg.channel_id.to_string(),
+            user_id: msg.author.id.to_string(),
+            user_name: msg.author.name.clone(),
+            content: msg.content.clone(),
+            metadata: serde_json::json!({
+                "guild_id": msg.guild_id.map(|g| g.to_string()),
+                "message_id": msg.id.to_string(),
+                "thread_id": thread_id.map(|t| t.to_string()),
+                "session_id": thread_id.map(|t| conversation_session(t.get())),
+            }),
+        };
+
+        let reply_channel = thread_id.unwrap_or(msg.channel_id);
+        let _ = reply_channel.broadcast_typing(&ctx.http).await;
+        let reply = match self.handler.handle(&rei, event).await {
+            Ok(Some(reply)) if !reply.trim().is_empty() => reply,
+            Ok(_) => return,
+            Err(e) => {
+                warn!(error = %e, rei_name = %rei.name, "Failed to answer Discord message");
+                return;
+            }
+        };
+
+        let mut reply = DiscordReply::new(reply);
+        if self.rich_replies {
+            let state = self.reis.find_state(rei.id).await.ok().flatten();
+            reply = reply.with_embed(ReplyEmbed::for_rei(&rei, state.as_ref()));
+        }
+
+        // The opening message stays in the channel; its answer goes in the thread
+        let reference = (reply_channel == msg.channel_id).then_some(msg.id);
+        if let Err(e) = send_reply(&ctx.http, reply_channel, &reply, reference).await {
+            warn!(error = %e, "Failed to post reply to Discord");
+        }
+    }
+
+    async fn reaction_add(&self, ctx: Context, reaction: Reaction) {
+        if !same_emoji(&reaction.emoji, &self.bookmark_emoji) {
+            return;
+        }
+        let Some((rei, _)) = self.route(reaction.channel_id.get()).await else {
+            return;
+        };
+        let msg = match reaction.message(&ctx.http).await {
+            Ok(msg) => msg,
+            Err(e) => {
+                warn!(error = %e, "Failed to fetch bookmarked Discord message");
+                return;
+            }
+        };
+        if msg.content.trim().is_empty() || is_bookmarked(&msg) {
+            return;
+        }
+        debug!(message_id = %msg.id, rei_name = %rei.name, "Bookmarking Discord message");
+
+        let event = IntegrationEvent::ReactionAdded {
+            message_id: msg.id.to_string(),
+            channel_id: msg.channel_id.to_string(),
+            user_id: reaction.user_id.map(|u| u.to_string()).unwrap_or_default(),
+            emoji: self.bookmark_emoji.clone(),
+            metadata: serde_json::json!({
+                "content": msg.content,
+                "author_id": msg.author.id.to_string(),
+                "author_name": msg.author.name,
+                "guild_id": msg.guild_id.map(|g| g.to_string()),
+                "link": msg.link(),
+            }),
+        };
+        match self.handler.handle(&rei, event).await {
+            Ok(Some(_)) => {
+                let marked = msg
+                    .react(&ctx.http, ReactionType::Unicode(BOOKMARKED.to_string()))
+                    .await;
+                if let Err(e) = marked {
+                    warn!(error = %e, "Failed to mark bookmarked Discord message");
+                }
+            }
+            Ok(None) => {}
+            Err(e) => {
+                warn!(error = %e, rei_name = %rei.name, "Failed to bookmark Discord message");
+            }
+        }
+    }
+}
+
+/// Whether a reaction is the configured emoji (a custom emoji by its name)
+fn same_emoji(emoji: &ReactionType, configured: &str) -> bool {
+    let name = match emoji {
+        ReactionType::Unicode(name) => name.as_str(),
+        ReactionType::Custom {
+            name: Some(name), ..
+        } => name.as_str(),
+        _ => return false,
+    };
+    // Some clients add a variation selector to the same emoji
+    let strip = |e: &str| e.trim().trim_end_matches('\u{fe0f}').to_string();
+    strip(name) == strip(configured)
+}
+
+/// Whether the bot already marked the message as bookmarked
+fn is_bookmarked(msg: &SerenityMessage) -> bool {
+    msg.reactions
+        .iter()
+        .any(|r| r.me && matches!(&r.reaction_type, ReactionType::Unicode(e) if e == BOOKMARKED))
+}
+
+/// Thread name from the opening message
+fn thread_name(msg: &SerenityMessage) -> String {
+    let first_line = msg.content.lines().next().unwrap_or_default().trim();
+    let name: String = first_line.chars().take(MAX_THREAD_NAME_CHARS).collect();
+    if name.is_empty() {
+        format!("Conversation with {}", msg.author.name)
+    } else {
+        name
+    }
+}
+
+/// Call session of a thread, derived from its ID so it survives restarts
+pub fn conversation_session(thread_id: u64) -> Uuid {
+    Uuid::from_u64_pair(0, thread_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_emoji() {
+        let brain = ReactionType::Unicode("🧠".to_string());
+        assert!(same_emoji(&brain, "🧠"));
+        assert!(!same_emoji(&brain, "📌"));
+
+        // "❤" and "❤️" (with a variation selector) are the same reaction
+        assert!(same_emoji(&ReactionType::Unicode("❤\u{fe0f}".into()), "❤"));
+    }
+
+    #[test]
+    fn test_conversation_session() {
+        assert_eq!(conversation_session(42), conversation_session(42));
+        assert_ne!(conversation_session(42), conversation_session(43));
+    }
+}
@@ -5,39 +5,59 @@ use chrono::{DateTime, Utc};
 use kaiba::domain::entities::{Message, Rei};
 use kaiba::domain::errors::DomainError;
 use kaiba::ports::integration::{IntegrationEvent, TeiIntegration};
+use kaiba::ports::ReiRepository;
+use std::sync::Arc;
 use tracing::{debug, warn};
 
 use crate::client::DiscordClient;
 use crate::config::DiscordConfig;
+use crate::reply::{DiscordReply, ReplyEmbed};
+use crate::webhook::{DiscordInteraction, DiscordWebhookHandler};
 
 /// Discord integration implementing TeiIntegration trait
 pub struct DiscordIntegration {
     client: DiscordClient,
-    #[allow(dead_code)]
     config: DiscordConfig,
+    /// Where the Rei's mood comes from, for the card under posts
+    reis: Option<Arc<dyn ReiRepository>>,
 }
 
 impl DiscordIntegration {
     /// Create a new Discord integration
     pub fn new(config: DiscordConfig) -> Self {
         let client = DiscordClient::new(config.clone());
-        Self { client, config }
+        Self {
+            client,
+            config,
+            reis: None,
+        }
+    }
+
+    /// Color the Rei's card by its current mood
+    pub fn with_rei_repository(mut self, reis: Arc<dyn ReiRepository>) -> Self {
+        self.reis = Some(reis);
+        self
+    }
+
+    /// Post a reply (embed, attachments) to the Rei's channel
+    pub async fn post_reply(&self, rei: &Rei, reply: &DiscordReply) -> Result<(), DomainError> {
+        let channel_id = self.get_channel_id(rei)?;
+        self.client
+            .send_reply(channel_id, reply)
+            .await
+            .map_err(|e| DomainError::ExternalService(format!("Discord API error: {}", e)))?;
+
+        Ok(())
     }
 
     /// Extract Discord channel ID from Rei's manifest
     fn get_channel_id(&self, rei: &Rei) -> Result<u64, DomainError> {
-        rei.manifest
-            .get("discord_channel_id")
-            .and_then(|v| {
-                v.as_u64()
-                    .or_else(|| v.as_str().and_then(|s| s.parse().ok()))
-            })
-            .ok_or_else(|| {
-                DomainError::Validation(format!(
-                    "Rei '{}' does not have discord_channel_id configured in manifest",
-                    rei.name
-                ))
-            })
+        manifest_channel_id(rei).ok_or_else(|| {
+            DomainError::Validation(format!(
+                "Rei '{}' does not have discord_channel_id configured in manifest",
+                rei.name
+            ))
+        })
     }
 
     /// Convert serenity Message to domain Message
@@ -67,6 +87,14 @@ impl DiscordIntegration {
     }
 }
 
+/// The `discord_channel_id` of a Rei's manifest (a number or a string)
+pub(crate) fn manifest_channel_id(rei: &Rei) -> Option<u64> {
+    rei.manifest.get("discord_channel_id").and_then(|v| {
+        v.as_u64()
+            .or_else(|| v.as_str().and_then(|s| s.parse().ok()))
+    })
+}
+
 #[async_trait]
 impl TeiIntegration for DiscordIntegration {
     async fn read_messages(&self, rei: &Rei) -> Result<Vec<Message>, DomainError> {
@@ -91,12 +119,15 @@ impl TeiIntegration for DiscordIntegration {
             "Posting message to Discord"
         );
 
-        self.client
-            .send_message(channel_id, content)
-            .await
-            .map_err(|e| DomainError::ExternalService(format!("Discord API error: {}", e)))?;
-
-        Ok(())
+        let mut reply = DiscordReply::new(content);
+        if self.config.rich_replies {
+            let state = match &self.reis {
+                Some(reis) => reis.find_state(rei.id).await.ok().flatten(),
+                None => None,
+            };
+            reply = reply.with_embed(ReplyEmbed::for_rei(rei, state.as_ref()));
+        }
+        self.post_reply(rei, &reply).await
     }
 
     fn name(&self) -> &str {
@@ -108,64 +139,13 @@ impl TeiIntegration for DiscordIntegration {
         payload: &[u8],
     ) -> Result<Option<IntegrationEvent>, DomainError> {
         // Parse Discord interaction webhook
-        let payload_str = std::str::from_utf8(payload)
-            .map_err(|e| DomainError::Validation(format!("Invalid UTF-8 in webhook: {}", e)))?;
-
-        let json: serde_json::Value = serde_json::from_str(payload_str)
-            .map_err(|e| DomainError::Validation(format!("Invalid JSON in webhook: {}", e)))?;
-
-        // Check interaction type
-        let interaction_type = json.get("type").and_then(|t| t.as_u64()).unwrap_or(0);
-
-        match interaction_type {
-            // Ping (verification)
-            1 => {
+        match DiscordWebhookHandler::new().parse_interaction(payload)? {
+            DiscordInteraction::Command { event, .. } => Ok(Some(event)),
+            DiscordInteraction::Ping => {
                 debug!("Received Discord ping verification");
                 Ok(None)
             }
-            // Application Command (slash command)
-            2 => {
-                let data = json.get("data").ok_or_else(|| {
-                    DomainError::Validation("Missing data in slash command".into())
-                })?;
-
-                let command = data
-                    .get("name")
-                    .and_then(|n| n.as_str())
-                    .unwrap_or_default()
-                    .to_string();
-
-                let user_id = json
-                    .get("member")
-                    .and_then(|m| m.get("user"))
-                    .or_else(|| json.get("user"))
-                    .and_then(|u| u.get("id"))
-                    .and_then(|id| id.as_str())
-                    .unwrap_or_default()
-                    .to_string();
-
-                let channel_id = json
-                    .get("channel_id")
-                    .and_then(|c| c.as_str())
-                    .unwrap_or_default()
-                    .to_string();
-
-                Ok(Some(IntegrationEvent::SlashCommand {
-                    command,
-                    user_id,
-                    channel_id,
-                    args: vec![],
-                }))
-            }
-            // Message Component
-            3 => {
-                debug!("Received Discord message component interaction");
-                Ok(None)
-            }
-            _ => {
-                warn!(interaction_type = %interaction_type, "Unknown Discord interaction type");
-                Ok(None)
-            }
+            DiscordInteraction::Ignored => Ok(None),
         }
     }
 
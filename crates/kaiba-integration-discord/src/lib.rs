@@ -5,18 +5,30 @@
 //! # Usage
 //!
 //! ```rust,ignore
-//! use kaiba_integration_discord::{DiscordIntegration, DiscordConfig};
+//! use kaiba_integration_discord::{DiscordConfig, DiscordGatewayRunner, DiscordIntegration};
 //!
 //! let config = DiscordConfig::new("your-bot-token");
-//! let integration = DiscordIntegration::new(config).await?;
+//! let integration = DiscordIntegration::new(config.clone()).await?;
+//!
+//! // Answer messages in the channels Reis are bound to
+//! let runner = DiscordGatewayRunner::new(config, rei_repository, call_handler);
+//! runner.run_until(shutdown.cancelled()).await?;
 //! ```
 
 mod client;
+mod commands;
 mod config;
+mod gateway;
 mod integration;
+mod reply;
+mod subscriber;
 mod webhook;
 
 pub use client::DiscordClient;
+pub use commands::{kaiba_command, register_commands, COMMAND_NAME};
 pub use config::DiscordConfig;
+pub use gateway::{conversation_session, DiscordGatewayRunner};
 pub use integration::DiscordIntegration;
-pub use webhook::DiscordWebhookHandler;
+pub use reply::{mood_color, split_message, DiscordReply, ReplyAttachment, ReplyEmbed};
+pub use subscriber::DiscordEventSubscriber;
+pub use webhook::{DiscordInteraction, DiscordWebhookHandler};
@@ -0,0 +1,335 @@
+//! Rich replies: embeds, attachments and long text
+//!
+//! Discord caps message content at 2000 characters. A [`DiscordReply`] is
+//! split into as many messages as it needs, breaking at paragraphs, lines
+//! or words and keeping code blocks closed across the split. The embed and
+//! any attachments ride on the last message. Replies too long to post as a
+//! handful of messages are sent as a file instead.
+
+use std::sync::Arc;
+
+use kaiba::domain::entities::{Rei, ReiState};
+use serenity::all::{
+    ChannelId, CreateAttachment, CreateEmbed, CreateMessage, Http, Message as SerenityMessage,
+    MessageId,
+};
+
+use crate::client::MAX_MESSAGE_LEN;
+
+/// Most messages one reply is split into before it is sent as a file
+const MAX_CHUNKS: usize = 4;
+
+/// Room kept in each chunk to close and reopen a code block
+const FENCE_RESERVE: usize = 8;
+
+/// Code block delimiter
+const FENCE: &str = "```";
+
+/// Name of the file carrying a reply too long for messages
+pub(crate) const REPLY_FILENAME: &str = "reply.md";
+
+/// Embed color when the mood is unknown (Discord blurple)
+const DEFAULT_COLOR: u32 = 0x5865F2;
+
+/// A file attached to a reply
+#[derive(Debug, Clone)]
+pub struct ReplyAttachment {
+    pub filename: String,
+    pub data: Vec<u8>,
+}
+
+/// An embed shown under a reply
+#[derive(Debug, Clone)]
+pub struct ReplyEmbed {
+    title: String,
+    description: Option<String>,
+    thumbnail: Option<String>,
+    fields: Vec<(String, String, bool)>,
+    color: u32,
+}
+
+impl ReplyEmbed {
+    /// Create an embed with a title
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            description: None,
+            thumbnail: None,
+            fields: Vec::new(),
+            color: DEFAULT_COLOR,
+        }
+    }
+
+    /// A Rei's card: its name and avatar, with its mood and energy when known
+    pub fn for_rei(rei: &Rei, state: Option<&ReiState>) -> Self {
+        let mut embed = Self::new(&rei.name);
+        embed.thumbnail = rei.avatar_url.clone();
+        if let Some(state) = state {
+            embed = embed
+                .with_field("Mood", &state.mood, true)
+                .with_field("Energy", format!("{}%", state.energy_level), true)
+                .with_color(mood_color(&state.mood));
+        }
+        embed
+    }
+
+    /// Set the text under the title
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add a field
+    pub fn with_field(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+        inline: bool,
+    ) -> Self {
+        self.fields.push((name.into(), value.into(), inline));
+        self
+    }
+
+    /// Set the color of the embed's side bar
+    pub fn with_color(mut self, color: u32) -> Self {
+        self.color = color;
+        self
+    }
+
+    fn build(&self) -> CreateEmbed {
+        let mut embed = CreateEmbed::new()
+            .title(&self.title)
+            .color(self.color)
+            .fields(self.fields.clone());
+        if let Some(description) = &self.description {
+            embed = embed.description(description);
+        }
+        if let Some(thumbnail) = &self.thumbnail {
+            embed = embed.thumbnail(thumbnail);
+        }
+        embed
+    }
+}
+
+/// A reply to post: text, an optional embed and attachments
+#[derive(Debug, Clone, Default)]
+pub struct DiscordReply {
+    content: String,
+    embed: Option<ReplyEmbed>,
+    attachments: Vec<ReplyAttachment>,
+}
+
+impl DiscordReply {
+    /// Create a plain text reply
+    pub fn new(content: impl Into<String>) -> Self {
+        Self {
+            content: content.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Show an embed under the reply
+    pub fn with_embed(mut self, embed: ReplyEmbed) -> Self {
+        self.embed = Some(embed);
+        self
+    }
+
+    /// Attach a file
+    pub fn with_attachment(mut self, filename: impl Into<String>, data: Vec<u8>) -> Self {
+        self.attachments.push(ReplyAttachment {
+            filename: filename.into(),
+            data,
+        });
+        self
+    }
+
+    /// The messages to send, in order
+    fn messages(&self) -> Vec<CreateMessage> {
+        let mut chunks = split_message(&self.content);
+        let mut attachments = self.attachments.clone();
+        if chunks.len() > MAX_CHUNKS {
+            chunks = vec![format!(
+                "{}\n\n📎 The full reply is attached.",
+                truncate_chars(&chunks[0], MAX_MESSAGE_LEN - 40)
+            )];
+            attachments.insert(
+                0,
+                ReplyAttachment {
+                    filename: REPLY_FILENAME.to_string(),
+                    data: self.content.clone().into_bytes(),
+                },
+            );
+        }
+        if chunks.is_empty() && (self.embed.is_some() || !attachments.is_empty()) {
+            chunks.push(String::new());
+        }
+
+        let last = chunks.len().saturating_sub(1);
+        chunks
+            .into_iter()
+            .enumerate()
+            .map(|(i, chunk)| {
+                let mut message = CreateMessage::new();
+                if !chunk.is_empty() {
+                    message = message.content(chunk);
+                }
+                if i == last {
+                    if let Some(embed) = &self.embed {
+                        message = message.embed(embed.build());
+                    }
+                    for attachment in &attachments {
+                        message = message.add_file(CreateAttachment::bytes(
+                            attachment.data.clone(),
+                            &attachment.filename,
+                        ));
+                    }
+                }
+                message
+            })
+            .collect()
+    }
+}
+
+/// Post a reply to a channel; the first message answers `reference`
+pub(crate) async fn send_reply(
+    http: &Arc<Http>,
+    channel: ChannelId,
+    reply: &DiscordReply,
+    reference: Option<MessageId>,
+) -> Result<Vec<SerenityMessage>, serenity::Error> {
+    let mut sent = Vec::new();
+    for (i, mut message) in reply.messages().into_iter().enumerate() {
+        if let (0, Some(reference)) = (i, reference) {
+            message = message.reference_message((channel, reference));
+        }
+        sent.push(channel.send_message(http, message).await?);
+    }
+    Ok(sent)
+}
+
+/// Embed color for a mood (keywords, so "very happy" counts as happy)
+pub fn mood_color(mood: &str) -> u32 {
+    const COLORS: &[(&[&str], u32)] = &[
+        (&["happy", "joyful", "excited", "cheerful"], 0xF1C40F),
+        (&["curious", "inspired", "focused"], 0x3498DB),
+        (&["calm", "content", "relaxed"], 0x2ECC71),
+        (&["sad", "melancholy", "lonely"], 0x5D6D7E),
+        (&["tired", "exhausted", "sleepy"], 0x7F8C8D),
+        (&["angry", "frustrated", "annoyed"], 0xE74C3C),
+    ];
+    let mood = mood.to_lowercase();
+    COLORS
+        .iter()
+        .find(|(keywords, _)| keywords.iter().any(|k| mood.contains(k)))
+        .map_or(DEFAULT_COLOR, |(_, color)| *color)
+}
+
+/// Split text into chunks within Discord's message length limit
+///
+/// Breaks at the last paragraph, line or word boundary that fits; a code
+/// block cut by the split is closed and reopened in the next chunk.
+pub fn split_message(text: &str) -> Vec<String> {
+    let limit = MAX_MESSAGE_LEN - FENCE_RESERVE;
+    let mut chunks = Vec::new();
+    let mut in_fence = false;
+    let mut rest = text.trim();
+
+    while !rest.is_empty() {
+        let piece = match rest.char_indices().nth(limit) {
+            None => rest,
+            Some((cut, _)) => {
+                let window = &rest[..cut];
+                let at = [("\n\n", limit / 2), ("\n", limit / 2), (" ", 0)]
+                    .iter()
+                    .find_map(|(sep, min)| {
+                        window
+                            .rfind(sep)
+                            .filter(|&at| window[..at].chars().count() > *min)
+                    })
+                    .unwrap_or(cut);
+                &rest[..at]
+            }
+        };
+        rest = rest[piece.len()..].trim_start();
+
+        let mut chunk = String::new();
+        if in_fence {
+            chunk.push_str(FENCE);
+            chunk.push('\n');
+        }
+        chunk.push_str(piece.trim_end());
+        if piece.matches(FENCE).count() % 2 == 1 {
+            in_fence = !in_fence;
+        }
+        if in_fence && !rest.is_empty() {
+            chunk.push('\n');
+            chunk.push_str(FENCE);
+        }
+        chunks.push(chunk);
+    }
+    chunks
+}
+
+/// The first `max` characters of text
+fn truncate_chars(text: &str, max: usize) -> String {
+    text.chars().take(max).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_message_short() {
+        assert_eq!(split_message("  hello  "), vec!["hello"]);
+        assert!(split_message("").is_empty());
+    }
+
+    #[test]
+    fn test_split_message_at_boundaries() {
+        let paragraph = "word ".repeat(300);
+        let text = format!("{}\n\n{}", paragraph.trim(), paragraph.trim());
+
+        let chunks = split_message(&text);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0], paragraph.trim());
+        assert!(chunks.iter().all(|c| c.chars().count() <= MAX_MESSAGE_LEN));
+
+        // No boundary at all: cut hard
+        let chunks = split_message(&"x".repeat(MAX_MESSAGE_LEN * 2));
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks.iter().all(|c| c.chars().count() <= MAX_MESSAGE_LEN));
+    }
+
+    #[test]
+    fn test_split_message_keeps_code_blocks_closed() {
+        let code = "let x = 1;\n".repeat(300);
+        let text = format!("Here:\n```rust\n{}```\nDone.", code);
+
+        let chunks = split_message(&text);
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.chars().count() <= MAX_MESSAGE_LEN);
+            assert_eq!(chunk.matches(FENCE).count() % 2, 0, "{}", chunk);
+        }
+        assert!(chunks[1].starts_with("```\n"));
+    }
+
+    #[test]
+    fn test_long_reply_becomes_attachment() {
+        let reply =
+            DiscordReply::new("word ".repeat(MAX_MESSAGE_LEN)).with_embed(ReplyEmbed::new("Rei"));
+        assert_eq!(reply.messages().len(), 1);
+
+        let reply = DiscordReply::new("").with_attachment("notes.txt", b"notes".to_vec());
+        assert_eq!(reply.messages().len(), 1);
+        assert!(DiscordReply::new("").messages().is_empty());
+    }
+
+    #[test]
+    fn test_mood_color() {
+        assert_eq!(mood_color("Very Happy"), 0xF1C40F);
+        assert_eq!(mood_color("curious"), 0x3498DB);
+        assert_eq!(mood_color("neutral"), DEFAULT_COLOR);
+    }
+}
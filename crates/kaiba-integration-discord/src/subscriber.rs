@@ -0,0 +1,85 @@
+//! ReiEventSubscriber implementation for Discord
+//!
+//! Posts a short summary of selected Rei events to a Discord channel.
+
+use async_trait::async_trait;
+use kaiba::domain::entities::{ReiEvent, WebhookEventType};
+use kaiba::domain::errors::DomainError;
+use kaiba::ports::event_bus::ReiEventSubscriber;
+use tracing::debug;
+
+use crate::client::DiscordClient;
+use crate::config::DiscordConfig;
+
+/// Discord message length limit
+const MAX_MESSAGE_LEN: usize = 2000;
+
+/// Event bus subscriber that posts Rei events to a Discord channel
+pub struct DiscordEventSubscriber {
+    client: DiscordClient,
+    channel_id: u64,
+    events: Vec<WebhookEventType>,
+}
+
+impl DiscordEventSubscriber {
+    /// Create a subscriber posting all events to `channel_id`
+    pub fn new(config: DiscordConfig, channel_id: u64) -> Self {
+        Self {
+            client: DiscordClient::new(config),
+            channel_id,
+            events: vec![WebhookEventType::All],
+        }
+    }
+
+    /// Only post the given event types
+    pub fn with_events(mut self, events: Vec<WebhookEventType>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Render an event as a Discord message
+    fn format_event(event: &ReiEvent) -> String {
+        let mut text = format!("**{}** for Rei `{}`", event.event_type, event.rei_id);
+
+        if let Some(obj) = event.data.as_object() {
+            for (key, value) in obj {
+                let value = match value.as_str() {
+                    Some(s) => s.to_string(),
+                    None => value.to_string(),
+                };
+                text.push_str(&format!("\n- {}: {}", key, value));
+            }
+        }
+
+        if text.chars().count() > MAX_MESSAGE_LEN {
+            let truncated: String = text.chars().take(MAX_MESSAGE_LEN - 3).collect();
+            format!("{}...", truncated)
+        } else {
+            text
+        }
+    }
+}
+
+#[async_trait]
+impl ReiEventSubscriber for DiscordEventSubscriber {
+    fn name(&self) -> &str {
+        "discord"
+    }
+
+    fn interested_in(&self, event: &ReiEvent) -> bool {
+        self.events
+            .iter()
+            .any(|e| *e == WebhookEventType::All || *e == event.event_type)
+    }
+
+    async fn handle(&self, event: &ReiEvent) -> Result<(), DomainError> {
+        debug!(event = %event.event_type, channel_id = %self.channel_id, "Posting event to Discord");
+
+        self.client
+            .send_message(self.channel_id, &Self::format_event(event))
+            .await
+            .map_err(|e| DomainError::ExternalService(format!("Discord API error: {}", e)))?;
+
+        Ok(())
+    }
+}
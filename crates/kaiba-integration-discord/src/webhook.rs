@@ -1,14 +1,51 @@
 //! Discord webhook handling
 
+use ed25519_dalek::{Signature, VerifyingKey};
 use kaiba::domain::errors::DomainError;
-use kaiba::ports::integration::IntegrationEvent;
+use kaiba::ports::integration::{
+    IntegrationEvent, VerificationError, WebhookHeaders, WebhookVerifier,
+};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, warn};
 
+use crate::commands::COMMAND_NAME;
+
+/// Interaction response: acknowledge a ping
+const RESPONSE_PONG: u64 = 1;
+/// Interaction response: reply with a message
+const RESPONSE_MESSAGE: u64 = 4;
+/// Interaction response: "thinking...", the reply follows by editing it
+const RESPONSE_DEFERRED: u64 = 5;
+/// Message flag: only the invoking user sees the reply
+const FLAG_EPHEMERAL: u64 = 1 << 6;
+/// Oldest (or furthest in the future) accepted interaction timestamp;
+/// wide enough for clock drift and delivery delays, short enough to limit replays
+const MAX_TIMESTAMP_SKEW_SECS: u64 = 300;
+/// Header carrying the hex ed25519 signature
+const SIGNATURE_HEADER: &str = "x-signature-ed25519";
+/// Header carrying the signed timestamp
+const TIMESTAMP_HEADER: &str = "x-signature-timestamp";
+
+/// A parsed interaction webhook
+#[derive(Debug, Clone)]
+pub enum DiscordInteraction {
+    /// Endpoint verification; answer with [`DiscordWebhookHandler::pong`]
+    Ping,
+    /// A `/kaiba` subcommand; answer with
+    /// [`DiscordWebhookHandler::deferred_response`], then edit the response
+    /// through `token` once the reply is ready
+    Command {
+        event: IntegrationEvent,
+        token: String,
+    },
+    /// An interaction Kaiba does not handle
+    Ignored,
+}
+
 /// Discord webhook handler for incoming events
 pub struct DiscordWebhookHandler {
     /// Public key for signature verification (optional)
-    public_key: Option<String>,
+    public_key: Option<VerifyingKey>,
 }
 
 impl DiscordWebhookHandler {
@@ -18,10 +55,15 @@ impl DiscordWebhookHandler {
     }
 
     /// Create a webhook handler with signature verification
-    pub fn with_public_key(public_key: impl Into<String>) -> Self {
-        Self {
-            public_key: Some(public_key.into()),
-        }
+    ///
+    /// Fails if `public_key` is not the application's hex ed25519 public key.
+    pub fn with_public_key(public_key: &str) -> Result<Self, DomainError> {
+        let public_key = decode_hex::<32>(public_key)
+            .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+            .ok_or_else(|| DomainError::Validation("Invalid Discord public key".into()))?;
+        Ok(Self {
+            public_key: Some(public_key),
+        })
     }
 
     /// Parse a Discord gateway event into an IntegrationEvent
@@ -90,44 +132,159 @@ impl DiscordWebhookHandler {
             channel_id: reaction.channel_id,
             user_id: reaction.user_id,
             emoji,
+            metadata: serde_json::Value::Null,
         }))
     }
 
+    /// Parse an interaction webhook body
+    ///
+    /// `/kaiba <subcommand> <text>` becomes a `SlashCommand` event with the
+    /// subcommand as `command` and its text as the only argument.
+    pub fn parse_interaction(&self, payload: &[u8]) -> Result<DiscordInteraction, DomainError> {
+        let interaction: InteractionPayload = serde_json::from_slice(payload)
+            .map_err(|e| DomainError::Validation(format!("Invalid interaction: {}", e)))?;
+
+        match interaction.kind {
+            // Ping (verification)
+            1 => Ok(DiscordInteraction::Ping),
+            // Application Command (slash command)
+            2 => {
+                let data = interaction.data.ok_or_else(|| {
+                    DomainError::Validation("Missing data in slash command".into())
+                })?;
+                if data.name != COMMAND_NAME {
+                    debug!(command = %data.name, "Ignoring unknown Discord command");
+                    return Ok(DiscordInteraction::Ignored);
+                }
+                let Some(subcommand) = data.options.into_iter().next() else {
+                    return Err(DomainError::Validation(
+                        "Missing subcommand in slash command".into(),
+                    ));
+                };
+                let args = subcommand
+                    .options
+                    .into_iter()
+                    .filter_map(|option| match option.value {
+                        Some(serde_json::Value::String(s)) => Some(s),
+                        Some(value) => Some(value.to_string()),
+                        None => None,
+                    })
+                    .collect();
+                let user_id = interaction
+                    .member
+                    .map(|m| m.user)
+                    .or(interaction.user)
+                    .map(|u| u.id)
+                    .unwrap_or_default();
+
+                Ok(DiscordInteraction::Command {
+                    event: IntegrationEvent::SlashCommand {
+                        command: subcommand.name,
+                        user_id,
+                        channel_id: interaction.channel_id.unwrap_or_default(),
+                        args,
+                    },
+                    token: interaction.token,
+                })
+            }
+            // Message Component
+            3 => {
+                debug!("Received Discord message component interaction");
+                Ok(DiscordInteraction::Ignored)
+            }
+            kind => {
+                warn!(interaction_type = %kind, "Unknown Discord interaction type");
+                Ok(DiscordInteraction::Ignored)
+            }
+        }
+    }
+
+    /// Response body acknowledging a ping
+    pub fn pong() -> serde_json::Value {
+        serde_json::json!({ "type": RESPONSE_PONG })
+    }
+
+    /// Response body showing "thinking..." until the response is edited
+    pub fn deferred_response() -> serde_json::Value {
+        serde_json::json!({ "type": RESPONSE_DEFERRED })
+    }
+
+    /// Response body replying right away (`ephemeral`: only to the invoking user)
+    pub fn message_response(content: &str, ephemeral: bool) -> serde_json::Value {
+        serde_json::json!({
+            "type": RESPONSE_MESSAGE,
+            "data": {
+                "content": content,
+                "flags": if ephemeral { FLAG_EPHEMERAL } else { 0 },
+            },
+        })
+    }
+
     /// Verify Discord signature (for HTTP interactions)
-    #[allow(dead_code)]
-    pub fn verify_signature(
+    ///
+    /// `signature` (`X-Signature-Ed25519`) must be the hex ed25519 signature
+    /// of `timestamp` (`X-Signature-Timestamp`) followed by the raw body,
+    /// made with the key matching the application's public key. Returns
+    /// false for a bad or stale signature, or without a public key.
+    pub fn verify_signature(&self, signature: &str, timestamp: &str, body: &[u8]) -> bool {
+        self.check_signature(signature, timestamp, body).is_ok()
+    }
+
+    fn check_signature(
         &self,
         signature: &str,
         timestamp: &str,
         body: &[u8],
-    ) -> Result<bool, DomainError> {
-        let Some(ref _public_key) = self.public_key else {
+    ) -> Result<(), VerificationError> {
+        let Some(public_key) = &self.public_key else {
             warn!("Signature verification requested but no public key configured");
-            return Ok(false);
+            return Err(VerificationError::NotConfigured(
+                "no Discord public key".into(),
+            ));
         };
 
-        // Note: Full signature verification requires ed25519 crate
-        // For now, we'll trust the signature if format is valid
-        if signature.len() != 128 || !signature.chars().all(|c| c.is_ascii_hexdigit()) {
-            return Ok(false);
+        let signature = decode_hex::<64>(signature)
+            .map(|bytes| Signature::from_bytes(&bytes))
+            .ok_or(VerificationError::InvalidSignature)?;
+
+        // Verify timestamp is recent
+        let ts = timestamp
+            .parse::<i64>()
+            .map_err(|_| VerificationError::InvalidSignature)?;
+        let now = chrono::Utc::now().timestamp();
+        if now.abs_diff(ts) > MAX_TIMESTAMP_SKEW_SECS {
+            warn!(
+                timestamp = %timestamp,
+                now = %now,
+                "Discord webhook timestamp too old"
+            );
+            return Err(VerificationError::Expired);
         }
 
-        // Verify timestamp is recent (within 5 seconds)
-        if let Ok(ts) = timestamp.parse::<i64>() {
-            let now = chrono::Utc::now().timestamp();
-            if (now - ts).abs() > 5 {
-                warn!(
-                    timestamp = %timestamp,
-                    now = %now,
-                    "Discord webhook timestamp too old"
-                );
-                return Ok(false);
-            }
-        }
+        let mut message = timestamp.as_bytes().to_vec();
+        message.extend_from_slice(body);
 
-        let _ = body; // Used in real verification
+        public_key
+            .verify_strict(&message, &signature)
+            .map_err(|_| VerificationError::InvalidSignature)
+    }
+}
+
+/// Decode a hex string of exactly `N` bytes
+fn decode_hex<const N: usize>(hex: &str) -> Option<[u8; N]> {
+    hex::decode(hex.trim()).ok()?.try_into().ok()
+}
 
-        Ok(true)
+impl WebhookVerifier for DiscordWebhookHandler {
+    fn verify(&self, headers: &WebhookHeaders, body: &[u8]) -> Result<(), VerificationError> {
+        if self.public_key.is_none() {
+            return Err(VerificationError::NotConfigured(
+                "no Discord public key".into(),
+            ));
+        }
+        let signature = headers.require(SIGNATURE_HEADER)?;
+        let timestamp = headers.require(TIMESTAMP_HEADER)?;
+        self.check_signature(signature, timestamp, body)
     }
 }
 
@@ -169,6 +326,43 @@ struct DiscordEmoji {
     name: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct InteractionPayload {
+    #[serde(rename = "type")]
+    kind: u64,
+    #[serde(default)]
+    token: String,
+    channel_id: Option<String>,
+    data: Option<CommandData>,
+    member: Option<InteractionMember>,
+    user: Option<InteractionUser>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommandData {
+    name: String,
+    #[serde(default)]
+    options: Vec<CommandOption>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommandOption {
+    name: String,
+    value: Option<serde_json::Value>,
+    #[serde(default)]
+    options: Vec<CommandOption>,
+}
+
+#[derive(Debug, Deserialize)]
+struct InteractionMember {
+    user: InteractionUser,
+}
+
+#[derive(Debug, Deserialize)]
+struct InteractionUser {
+    id: String,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -225,6 +419,56 @@ mod tests {
         assert!(event.is_none());
     }
 
+    #[test]
+    fn test_parse_slash_command() {
+        let handler = DiscordWebhookHandler::new();
+        let body = serde_json::json!({
+            "type": 2,
+            "token": "interaction-token",
+            "channel_id": "456",
+            "member": { "user": { "id": "user123" } },
+            "data": {
+                "name": "kaiba",
+                "options": [{
+                    "name": "recall",
+                    "type": 1,
+                    "options": [{ "name": "query", "type": 3, "value": "rust async" }]
+                }]
+            }
+        });
+
+        let interaction = handler
+            .parse_interaction(body.to_string().as_bytes())
+            .unwrap();
+        let DiscordInteraction::Command { event, token } = interaction else {
+            panic!("Expected Command interaction");
+        };
+        assert_eq!(token, "interaction-token");
+        if let IntegrationEvent::SlashCommand {
+            command,
+            user_id,
+            channel_id,
+            args,
+        } = event
+        {
+            assert_eq!(command, "recall");
+            assert_eq!(user_id, "user123");
+            assert_eq!(channel_id, "456");
+            assert_eq!(args, vec!["rust async".to_string()]);
+        } else {
+            panic!("Expected SlashCommand event");
+        }
+
+        let ping = handler.parse_interaction(br#"{"type": 1}"#).unwrap();
+        assert!(matches!(ping, DiscordInteraction::Ping));
+
+        let other = serde_json::json!({"type": 2, "token": "t", "data": {"name": "other"}});
+        let ignored = handler
+            .parse_interaction(other.to_string().as_bytes())
+            .unwrap();
+        assert!(matches!(ignored, DiscordInteraction::Ignored));
+    }
+
     #[test]
     fn test_parse_dm() {
         let handler = DiscordWebhookHandler::new();
@@ -251,4 +495,117 @@ mod tests {
             panic!("Expected DirectMessage event");
         }
     }
+
+    /// Handler for a fixed test key, and the key to sign with
+    fn signed_handler() -> (DiscordWebhookHandler, ed25519_dalek::SigningKey) {
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+        let public_key = hex::encode(signing_key.verifying_key().to_bytes());
+        (
+            DiscordWebhookHandler::with_public_key(&public_key).unwrap(),
+            signing_key,
+        )
+    }
+
+    fn sign(key: &ed25519_dalek::SigningKey, timestamp: &str, body: &[u8]) -> String {
+        use ed25519_dalek::Signer;
+        let mut message = timestamp.as_bytes().to_vec();
+        message.extend_from_slice(body);
+        hex::encode(key.sign(&message).to_bytes())
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let (handler, key) = signed_handler();
+        let timestamp = chrono::Utc::now().timestamp().to_string();
+        let body = br#"{"type":1}"#;
+        let signature = sign(&key, &timestamp, body);
+
+        assert!(handler.verify_signature(&signature, &timestamp, body));
+    }
+
+    #[test]
+    fn test_verify_headers() {
+        let (handler, key) = signed_handler();
+        let timestamp = chrono::Utc::now().timestamp().to_string();
+        let body = br#"{"type":1}"#;
+        let headers = WebhookHeaders::new()
+            .with("X-Signature-Ed25519", sign(&key, &timestamp, body))
+            .with("X-Signature-Timestamp", timestamp.clone());
+
+        assert_eq!(handler.verify(&headers, body), Ok(()));
+        assert_eq!(
+            handler.verify(&headers, b"{}"),
+            Err(VerificationError::InvalidSignature)
+        );
+        assert_eq!(
+            handler.verify(
+                &WebhookHeaders::new().with("X-Signature-Timestamp", timestamp),
+                body
+            ),
+            Err(VerificationError::MissingHeader(
+                SIGNATURE_HEADER.to_string()
+            ))
+        );
+        assert!(matches!(
+            DiscordWebhookHandler::new().verify(&headers, body),
+            Err(VerificationError::NotConfigured(_))
+        ));
+
+        let stale = (chrono::Utc::now().timestamp() - 600).to_string();
+        let headers = WebhookHeaders::new()
+            .with("X-Signature-Ed25519", sign(&key, &stale, body))
+            .with("X-Signature-Timestamp", stale);
+        assert_eq!(
+            handler.verify(&headers, body),
+            Err(VerificationError::Expired)
+        );
+    }
+
+    #[test]
+    fn test_reject_tampered_interactions() {
+        let (handler, key) = signed_handler();
+        let timestamp = chrono::Utc::now().timestamp().to_string();
+        let body = br#"{"type":2,"data":{"name":"kaiba"}}"#;
+        let signature = sign(&key, &timestamp, body);
+
+        // Body changed after signing
+        let tampered = br#"{"type":2,"data":{"name":"kaibb"}}"#;
+        assert!(!handler.verify_signature(&signature, &timestamp, tampered));
+
+        // Timestamp changed after signing
+        let later = (timestamp.parse::<i64>().unwrap() + 1).to_string();
+        assert!(!handler.verify_signature(&signature, &later, body));
+
+        // Signed by another key
+        let other = ed25519_dalek::SigningKey::from_bytes(&[8u8; 32]);
+        let forged = sign(&other, &timestamp, body);
+        assert!(!handler.verify_signature(&forged, &timestamp, body));
+
+        // Well-formed hex that is not a signature of this body
+        assert!(!handler.verify_signature(&"ab".repeat(64), &timestamp, body));
+        assert!(!handler.verify_signature("not-hex", &timestamp, body));
+    }
+
+    #[test]
+    fn test_reject_stale_or_unconfigured() {
+        let (handler, key) = signed_handler();
+        let body = br#"{"type":1}"#;
+        let now = chrono::Utc::now().timestamp();
+        for stale in [now - 600, now + 600, i64::MIN, i64::MAX] {
+            let stale = stale.to_string();
+            let signature = sign(&key, &stale, body);
+            assert!(!handler.verify_signature(&signature, &stale, body));
+        }
+        let drifted = (now - 60).to_string();
+        let signature = sign(&key, &drifted, body);
+        assert!(handler.verify_signature(&signature, &drifted, body));
+
+        let unconfigured = DiscordWebhookHandler::new();
+        let timestamp = chrono::Utc::now().timestamp().to_string();
+        let signature = sign(&key, &timestamp, body);
+        assert!(!unconfigured.verify_signature(&signature, &timestamp, body));
+
+        assert!(DiscordWebhookHandler::with_public_key("1234").is_err());
+        assert!(DiscordWebhookHandler::with_public_key(&"zz".repeat(32)).is_err());
+    }
 }
@@ -0,0 +1,66 @@
+//! Email configuration
+
+use serde::{Deserialize, Serialize};
+
+/// Configuration for email integration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmailConfig {
+    /// IMAP server (TLS)
+    pub imap_host: String,
+    pub imap_port: u16,
+    /// SMTP server (STARTTLS)
+    pub smtp_host: String,
+    pub smtp_port: u16,
+    /// Login for both servers
+    pub username: String,
+    pub password: String,
+    /// Mailbox polled for new messages
+    pub mailbox: String,
+    /// Seconds between polls
+    pub poll_interval_secs: u64,
+}
+
+impl EmailConfig {
+    /// Create a configuration with default ports, polling `INBOX`
+    pub fn new(
+        imap_host: impl Into<String>,
+        smtp_host: impl Into<String>,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        Self {
+            imap_host: imap_host.into(),
+            imap_port: 993,
+            smtp_host: smtp_host.into(),
+            smtp_port: 587,
+            username: username.into(),
+            password: password.into(),
+            mailbox: "INBOX".to_string(),
+            poll_interval_secs: 60,
+        }
+    }
+
+    /// Set the IMAP port
+    pub fn with_imap_port(mut self, port: u16) -> Self {
+        self.imap_port = port;
+        self
+    }
+
+    /// Set the SMTP port
+    pub fn with_smtp_port(mut self, port: u16) -> Self {
+        self.smtp_port = port;
+        self
+    }
+
+    /// Poll another mailbox than `INBOX`
+    pub fn with_mailbox(mut self, mailbox: impl Into<String>) -> Self {
+        self.mailbox = mailbox.into();
+        self
+    }
+
+    /// Set the poll interval
+    pub fn with_poll_interval(mut self, secs: u64) -> Self {
+        self.poll_interval_secs = secs;
+        self
+    }
+}
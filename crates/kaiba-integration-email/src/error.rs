@@ -0,0 +1,31 @@
+//! Email errors
+
+use kaiba::domain::errors::DomainError;
+
+/// IMAP, SMTP and message errors
+#[derive(Debug, thiserror::Error)]
+pub enum EmailError {
+    #[error("IMAP error: {0}")]
+    Imap(#[from] async_imap::error::Error),
+
+    #[error("Connection error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("TLS error: {0}")]
+    Tls(String),
+
+    #[error("SMTP error: {0}")]
+    Smtp(#[from] lettre::transport::smtp::Error),
+
+    #[error("Invalid address: {0}")]
+    Address(#[from] lettre::address::AddressError),
+
+    #[error("Invalid message: {0}")]
+    Message(#[from] lettre::error::Error),
+}
+
+impl From<EmailError> for DomainError {
+    fn from(e: EmailError) -> Self {
+        DomainError::ExternalService(e.to_string())
+    }
+}
@@ -0,0 +1,151 @@
+//! TeiIntegration implementation for email
+
+use async_trait::async_trait;
+use kaiba::domain::entities::{Message, Rei};
+use kaiba::domain::errors::DomainError;
+use kaiba::ports::integration::{IntegrationEvent, TeiIntegration};
+use lettre::message::Mailbox as Sender;
+use tracing::{debug, warn};
+
+use crate::config::EmailConfig;
+use crate::error::EmailError;
+use crate::mailbox::Mailbox;
+use crate::mailer::Mailer;
+
+/// Email integration implementing TeiIntegration trait
+pub struct EmailIntegration {
+    config: EmailConfig,
+    mailer: Mailer,
+}
+
+impl EmailIntegration {
+    /// Create a new email integration
+    pub fn new(config: EmailConfig) -> Result<Self, EmailError> {
+        Ok(Self {
+            mailer: Mailer::new(&config)?,
+            config,
+        })
+    }
+
+    /// Extract the Rei's email address from its manifest
+    fn get_address(&self, rei: &Rei) -> Result<String, DomainError> {
+        manifest_address(rei).ok_or_else(|| {
+            DomainError::Validation(format!(
+                "Rei '{}' does not have email_address configured in manifest",
+                rei.name
+            ))
+        })
+    }
+}
+
+/// The `email_address` of a Rei's manifest
+pub(crate) fn manifest_address(rei: &Rei) -> Option<String> {
+    rei.manifest
+        .get("email_address")
+        .and_then(|v| v.as_str())
+        .map(|address| address.trim().to_lowercase())
+        .filter(|address| !address.is_empty())
+}
+
+/// The sender of mail from a Rei: its name and address
+pub(crate) fn rei_sender(rei: &Rei, address: &str) -> Result<Sender, EmailError> {
+    Ok(Sender::new(Some(rei.name.clone()), address.parse()?))
+}
+
+#[async_trait]
+impl TeiIntegration for EmailIntegration {
+    async fn read_messages(&self, rei: &Rei) -> Result<Vec<Message>, DomainError> {
+        let address = self.get_address(rei)?;
+        debug!(address = %address, rei_name = %rei.name, "Reading unseen email");
+
+        let mut mailbox = Mailbox::open(&self.config).await?;
+        let emails = mailbox.unseen().await;
+        if let Err(e) = mailbox.close().await {
+            debug!(error = %e, "IMAP logout failed");
+        }
+
+        Ok(emails?
+            .iter()
+            .filter(|email| email.is_addressed_to(&address))
+            .map(|email| email.to_message(&address))
+            .collect())
+    }
+
+    async fn post_message(&self, rei: &Rei, content: &str) -> Result<(), DomainError> {
+        let address = self.get_address(rei)?;
+        let recipient = rei
+            .manifest
+            .get("email_recipient")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| {
+                DomainError::Validation(format!(
+                    "Rei '{}' does not have email_recipient configured in manifest",
+                    rei.name
+                ))
+            })?;
+        debug!(
+            recipient = %recipient,
+            rei_name = %rei.name,
+            content_len = %content.len(),
+            "Sending email"
+        );
+
+        let subject = format!("Message from {}", rei.name);
+        self.mailer
+            .send(rei_sender(rei, &address)?, recipient, &subject, content)
+            .await?;
+
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "email"
+    }
+
+    async fn handle_webhook(
+        &self,
+        _payload: &[u8],
+    ) -> Result<Option<IntegrationEvent>, DomainError> {
+        // Mail is polled over IMAP; there is no webhook
+        Ok(None)
+    }
+
+    async fn health_check(&self) -> Result<bool, DomainError> {
+        match Mailbox::open(&self.config).await {
+            Ok(mailbox) => {
+                let _ = mailbox.close().await;
+                Ok(true)
+            }
+            Err(e) => {
+                warn!(error = %e, "Email health check failed");
+                Ok(false)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_manifest_address() {
+        let rei = |manifest| Rei {
+            id: Uuid::new_v4(),
+            name: "Yui".to_string(),
+            role: "Assistant".to_string(),
+            avatar_url: None,
+            manifest,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        let bound = rei(serde_json::json!({ "email_address": " Yui@Example.com " }));
+        assert_eq!(manifest_address(&bound).as_deref(), Some("yui@example.com"));
+        let blank = rei(serde_json::json!({ "email_address": "" }));
+        assert_eq!(manifest_address(&blank), None);
+        assert_eq!(manifest_address(&rei(serde_json::json!({}))), None);
+    }
+}
@@ -0,0 +1,32 @@
+//! Email Integration for Kaiba
+//!
+//! This crate provides email (IMAP/SMTP) integration for the Kaiba AI persona system.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use kaiba_integration_email::{EmailConfig, EmailIntegration, EmailPollingRunner};
+//!
+//! let config = EmailConfig::new("imap.example.com", "smtp.example.com", "kaiba", "password");
+//! let integration = EmailIntegration::new(config.clone())?;
+//!
+//! // Answer mail sent to the Reis' addresses
+//! let runner = EmailPollingRunner::new(config, rei_repository, call_handler)?;
+//! runner.run_until(shutdown.cancelled()).await?;
+//! ```
+
+mod config;
+mod error;
+mod integration;
+mod mailbox;
+mod mailer;
+mod message;
+mod runner;
+
+pub use config::EmailConfig;
+pub use error::EmailError;
+pub use integration::EmailIntegration;
+pub use mailbox::Mailbox;
+pub use mailer::Mailer;
+pub use message::InboundEmail;
+pub use runner::EmailPollingRunner;
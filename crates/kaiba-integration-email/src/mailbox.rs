@@ -0,0 +1,112 @@
+//! IMAP mailbox access over TLS
+
+use std::sync::Arc;
+
+use async_imap::Session;
+use futures_util::TryStreamExt;
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tokio_rustls::rustls::pki_types::ServerName;
+use tokio_rustls::rustls::{ClientConfig, RootCertStore};
+use tokio_rustls::TlsConnector;
+use tracing::{debug, warn};
+
+use crate::config::EmailConfig;
+use crate::error::EmailError;
+use crate::message::InboundEmail;
+
+/// A logged-in IMAP session with the configured mailbox selected
+pub struct Mailbox {
+    session: Session<TlsStream<TcpStream>>,
+}
+
+impl Mailbox {
+    /// Connect, log in and select the configured mailbox
+    pub async fn open(config: &EmailConfig) -> Result<Self, EmailError> {
+        let roots = RootCertStore {
+            roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+        };
+        let tls = ClientConfig::builder()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        let server_name = ServerName::try_from(config.imap_host.clone())
+            .map_err(|e| EmailError::Tls(e.to_string()))?;
+
+        let tcp = TcpStream::connect((config.imap_host.as_str(), config.imap_port)).await?;
+        let stream = TlsConnector::from(Arc::new(tls))
+            .connect(server_name, tcp)
+            .await?;
+
+        let mut client = async_imap::Client::new(stream);
+        // The server greets before accepting commands
+        let _greeting = client.read_response().await;
+        let mut session = client
+            .login(&config.username, &config.password)
+            .await
+            .map_err(|(e, _)| e)?;
+        session.select(&config.mailbox).await?;
+        debug!(host = %config.imap_host, mailbox = %config.mailbox, "IMAP mailbox opened");
+
+        Ok(Self { session })
+    }
+
+    /// Messages not yet marked `\Seen`
+    ///
+    /// Bodies are fetched with `BODY.PEEK[]`, so reading doesn't mark them;
+    /// see [`Mailbox::mark_seen`]. Messages that fail to parse are skipped.
+    pub async fn unseen(&mut self) -> Result<Vec<InboundEmail>, EmailError> {
+        let uids = self.session.uid_search("UNSEEN").await?;
+        if uids.is_empty() {
+            return Ok(vec![]);
+        }
+        let mut uids: Vec<u32> = uids.into_iter().collect();
+        uids.sort_unstable();
+
+        let fetches: Vec<_> = self
+            .session
+            .uid_fetch(uid_set(&uids), "(UID BODY.PEEK[])")
+            .await?
+            .try_collect()
+            .await?;
+
+        Ok(fetches
+            .iter()
+            .filter_map(|fetch| {
+                let uid = fetch.uid?;
+                let email = fetch.body().and_then(|raw| InboundEmail::parse(uid, raw));
+                if email.is_none() {
+                    warn!(uid = %uid, "Skipping unparseable email");
+                }
+                email
+            })
+            .collect())
+    }
+
+    /// Mark messages `\Seen` so they aren't picked up again
+    pub async fn mark_seen(&mut self, uids: &[u32]) -> Result<(), EmailError> {
+        if uids.is_empty() {
+            return Ok(());
+        }
+        let _: Vec<_> = self
+            .session
+            .uid_store(uid_set(uids), "+FLAGS (\\Seen)")
+            .await?
+            .try_collect()
+            .await?;
+        Ok(())
+    }
+
+    /// Log out and close the connection
+    pub async fn close(mut self) -> Result<(), EmailError> {
+        self.session.logout().await?;
+        Ok(())
+    }
+}
+
+/// IMAP sequence set for UIDs (`1,5,7`)
+fn uid_set(uids: &[u32]) -> String {
+    uids.iter()
+        .map(u32::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
@@ -0,0 +1,160 @@
+//! SMTP delivery (STARTTLS)
+
+use lettre::message::header::{ContentType, Header, HeaderName, HeaderValue};
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+
+use crate::config::EmailConfig;
+use crate::error::EmailError;
+use crate::message::InboundEmail;
+
+/// `Auto-Submitted` (RFC 3834), so auto-responders don't answer replies
+#[derive(Debug, Clone)]
+struct AutoSubmitted(String);
+
+impl Header for AutoSubmitted {
+    fn name() -> HeaderName {
+        HeaderName::new_from_ascii_str("Auto-Submitted")
+    }
+
+    fn parse(s: &str) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self(s.to_string()))
+    }
+
+    fn display(&self) -> HeaderValue {
+        HeaderValue::new(Self::name(), self.0.clone())
+    }
+}
+
+/// Sends mail as a Rei
+///
+/// Mail is sent from the Rei's own address with the configured login, so the
+/// SMTP account must be allowed to send as it (an alias of the mailbox).
+#[derive(Clone)]
+pub struct Mailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+impl Mailer {
+    /// Create a mailer for the configured SMTP server
+    pub fn new(config: &EmailConfig) -> Result<Self, EmailError> {
+        let transport = AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.smtp_host)?
+            .port(config.smtp_port)
+            .credentials(Credentials::new(
+                config.username.clone(),
+                config.password.clone(),
+            ))
+            .build();
+        Ok(Self { transport })
+    }
+
+    /// Send a new message
+    pub async fn send(
+        &self,
+        from: Mailbox,
+        to: &str,
+        subject: &str,
+        body: &str,
+    ) -> Result<(), EmailError> {
+        let message = Message::builder()
+            .from(from)
+            .to(Mailbox::new(None, to.parse()?))
+            .subject(subject)
+            .header(ContentType::TEXT_PLAIN)
+            .body(body.to_string())?;
+        self.transport.send(message).await?;
+        Ok(())
+    }
+
+    /// Reply to a received message, keeping it in the same thread
+    pub async fn reply(
+        &self,
+        from: Mailbox,
+        original: &InboundEmail,
+        body: &str,
+    ) -> Result<(), EmailError> {
+        self.transport
+            .send(reply_message(from, original, body)?)
+            .await?;
+        Ok(())
+    }
+
+    /// Whether the SMTP server accepts a connection
+    pub async fn test_connection(&self) -> Result<bool, EmailError> {
+        Ok(self.transport.test_connection().await?)
+    }
+}
+
+/// A reply in the thread of `original`, marked as automatic
+fn reply_message(
+    from: Mailbox,
+    original: &InboundEmail,
+    body: &str,
+) -> Result<Message, EmailError> {
+    let to = Mailbox::new(original.from_name.clone(), original.from_address.parse()?);
+    let mut builder = Message::builder()
+        .from(from)
+        .to(to)
+        .subject(reply_subject(&original.subject))
+        .header(ContentType::TEXT_PLAIN)
+        .header(AutoSubmitted("auto-replied".to_string()));
+
+    if let Some(id) = &original.message_id {
+        let references: Vec<String> = original
+            .references
+            .iter()
+            .chain(std::iter::once(id))
+            .map(|id| format!("<{id}>"))
+            .collect();
+        builder = builder
+            .in_reply_to(format!("<{id}>"))
+            .references(references.join(" "));
+    }
+
+    Ok(builder.body(body.to_string())?)
+}
+
+/// `Re: ` prefixed subject, without stacking prefixes
+fn reply_subject(subject: &str) -> String {
+    let subject = subject.trim();
+    if subject
+        .get(..3)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("re:"))
+    {
+        subject.to_string()
+    } else if subject.is_empty() {
+        "Re: (no subject)".to_string()
+    } else {
+        format!("Re: {subject}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reply_subject() {
+        assert_eq!(reply_subject("Plans"), "Re: Plans");
+        assert_eq!(reply_subject("RE: Plans"), "RE: Plans");
+        assert_eq!(reply_subject(""), "Re: (no subject)");
+    }
+
+    #[test]
+    fn test_reply_is_marked_auto_replied() {
+        let raw = "From: Alice <alice@example.com>\r\n\
+            To: yui@example.com\r\n\
+            Subject: Plans\r\n\
+            Message-ID: <root@example.com>\r\n\
+            \r\n\
+            Shall we meet on Friday?\r\n";
+        let original = InboundEmail::parse(1, raw.as_bytes()).unwrap();
+        let from = Mailbox::new(Some("Yui".to_string()), "yui@example.com".parse().unwrap());
+
+        let reply = reply_message(from, &original, "Friday works.").unwrap();
+        let reply = String::from_utf8(reply.formatted()).unwrap();
+        assert!(reply.contains("Auto-Submitted: auto-replied\r\n"));
+        assert!(reply.contains("In-Reply-To: <root@example.com>\r\n"));
+    }
+}
@@ -0,0 +1,273 @@
+//! Received email, parsed into what a Rei needs to answer it
+
+use chrono::{DateTime, Utc};
+use kaiba::domain::entities::Message;
+use kaiba::ports::integration::IntegrationEvent;
+use mail_parser::MessageParser;
+use uuid::Uuid;
+
+/// Sender local parts of daemons and unattended mailboxes
+const UNATTENDED_SENDERS: [&str; 6] = [
+    "mailer-daemon",
+    "postmaster",
+    "noreply",
+    "no-reply",
+    "donotreply",
+    "do-not-reply",
+];
+
+/// A message fetched from the mailbox
+#[derive(Debug, Clone)]
+pub struct InboundEmail {
+    /// IMAP UID in the polled mailbox
+    pub uid: u32,
+    /// `Message-ID`, without angle brackets
+    pub message_id: Option<String>,
+    /// `References` (oldest first), without angle brackets
+    pub references: Vec<String>,
+    pub from_address: String,
+    pub from_name: Option<String>,
+    /// `To` and `Cc` addresses, lower-cased
+    pub recipients: Vec<String>,
+    pub subject: String,
+    /// Plain text body without quoted earlier messages
+    pub text: String,
+    /// `Date` header
+    pub date: Option<DateTime<Utc>>,
+    /// Sent by an auto-responder, a mailing list or a mail daemon; answering
+    /// it could start a mail loop
+    pub automated: bool,
+}
+
+impl InboundEmail {
+    /// Parse a raw RFC 5322 message; `None` without a sender
+    pub fn parse(uid: u32, raw: &[u8]) -> Option<Self> {
+        let message = MessageParser::default().parse(raw)?;
+        let from = message.from()?.first()?;
+        let from_address = from.address()?.to_string();
+
+        let recipients = [message.to(), message.cc()]
+            .into_iter()
+            .flatten()
+            .flat_map(|address| address.iter())
+            .filter_map(|addr| addr.address())
+            .map(|addr| addr.to_lowercase())
+            .collect();
+        let automated = is_automated(
+            |name| message.header_raw(name).map(str::trim),
+            &from_address,
+        );
+        let references = message
+            .references()
+            .as_text_list()
+            .map(|ids| ids.into_iter().map(str::to_string).collect())
+            .unwrap_or_default();
+
+        Some(Self {
+            uid,
+            message_id: message.message_id().map(str::to_string),
+            references,
+            from_address,
+            from_name: from.name().map(str::to_string),
+            recipients,
+            subject: message.subject().unwrap_or_default().to_string(),
+            text: strip_quoted(&message.body_text(0).unwrap_or_default()),
+            date: message
+                .date()
+                .and_then(|date| DateTime::from_timestamp(date.to_timestamp(), 0)),
+            automated,
+        })
+    }
+
+    /// As a platform message in the mailbox of `rei_address`
+    pub fn to_message(&self, rei_address: &str) -> Message {
+        Message {
+            id: self
+                .message_id
+                .clone()
+                .unwrap_or_else(|| self.uid.to_string()),
+            channel_id: rei_address.to_string(),
+            author_id: self.from_address.clone(),
+            author_name: self
+                .from_name
+                .clone()
+                .unwrap_or_else(|| self.from_address.clone()),
+            content: self.text.clone(),
+            timestamp: self.date.unwrap_or_else(Utc::now),
+            platform: "email".to_string(),
+            metadata: serde_json::json!({ "subject": self.subject }),
+        }
+    }
+
+    /// Whether the message was sent (or copied) to `address`
+    pub fn is_addressed_to(&self, address: &str) -> bool {
+        let address = address.to_lowercase();
+        self.recipients.contains(&address)
+    }
+
+    /// Call session of the thread, derived from its first message so every
+    /// reply in the thread continues the same conversation
+    pub fn session(&self) -> Option<Uuid> {
+        let root = self.references.first().or(self.message_id.as_ref())?;
+        Some(conversation_session(root))
+    }
+
+    /// The event handed to the call pipeline; `rei_address` is the channel
+    pub fn to_event(&self, rei_address: &str) -> IntegrationEvent {
+        let content = if self.subject.trim().is_empty() {
+            self.text.clone()
+        } else {
+            format!("Subject: {}\n\n{}", self.subject.trim(), self.text)
+        };
+
+        IntegrationEvent::MessageReceived {
+            channel_id: rei_address.to_string(),
+            user_id: self.from_address.clone(),
+            user_name: self
+                .from_name
+                .clone()
+                .unwrap_or_else(|| self.from_address.clone()),
+            content,
+            metadata: serde_json::json!({
+                "message_id": self.message_id,
+                "subject": self.subject,
+                "session_id": self.session(),
+            }),
+        }
+    }
+}
+
+/// Call session for a thread root `Message-ID`
+///
+/// FNV-1a keeps the mapping stable across builds and restarts.
+fn conversation_session(root_message_id: &str) -> Uuid {
+    let hash = root_message_id
+        .bytes()
+        .fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+    Uuid::from_u64_pair(2, hash)
+}
+
+/// Whether a message is automatic mail that must not be answered
+///
+/// Follows RFC 3834: `Auto-Submitted` other than `no`, bulk or list
+/// `Precedence`, a null `Return-Path` (bounces), or a daemon or no-reply
+/// sender.
+fn is_automated<'a>(header: impl Fn(&'static str) -> Option<&'a str>, from_address: &str) -> bool {
+    let auto_submitted =
+        header("Auto-Submitted").is_some_and(|value| !value.eq_ignore_ascii_case("no"));
+    let bulk = header("Precedence").is_some_and(|value| {
+        ["bulk", "junk", "list"]
+            .iter()
+            .any(|p| value.eq_ignore_ascii_case(p))
+    });
+    let null_return_path =
+        header("Return-Path").is_some_and(|value| value.is_empty() || value == "<>");
+    let local_part = from_address
+        .split('@')
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    let unattended = UNATTENDED_SENDERS.contains(&local_part.as_str());
+
+    auto_submitted || bulk || null_return_path || unattended
+}
+
+/// Drop quoted lines (`> ...`) and the "On ... wrote:" line introducing them
+fn strip_quoted(text: &str) -> String {
+    let lines: Vec<&str> = text
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('>'))
+        .collect();
+    let end = match lines.last() {
+        Some(last) if last.trim_end().ends_with("wrote:") => lines.len() - 1,
+        _ => lines.len(),
+    };
+    lines[..end].join("\n").trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RAW: &str = "From: Alice <alice@example.com>\r\n\
+        To: yui@example.com\r\n\
+        Cc: Bob@Example.com\r\n\
+        Subject: Re: Plans\r\n\
+        Message-ID: <reply-2@example.com>\r\n\
+        In-Reply-To: <reply-1@example.com>\r\n\
+        References: <root@example.com> <reply-1@example.com>\r\n\
+        Content-Type: text/plain; charset=utf-8\r\n\
+        \r\n\
+        Sounds good, see you then.\r\n\
+        \r\n\
+        On Mon, Yui wrote:\r\n\
+        > Shall we meet on Friday?\r\n";
+
+    #[test]
+    fn test_parse_email() {
+        let email = InboundEmail::parse(7, RAW.as_bytes()).unwrap();
+
+        assert_eq!(email.uid, 7);
+        assert_eq!(email.from_address, "alice@example.com");
+        assert_eq!(email.from_name.as_deref(), Some("Alice"));
+        assert_eq!(email.subject, "Re: Plans");
+        assert_eq!(email.text, "Sounds good, see you then.");
+        assert!(email.is_addressed_to("Yui@example.com"));
+        assert!(email.is_addressed_to("bob@example.com"));
+        assert!(!email.is_addressed_to("carol@example.com"));
+        assert_eq!(
+            email.references.first().map(String::as_str),
+            Some("root@example.com")
+        );
+    }
+
+    #[test]
+    fn test_thread_shares_session() {
+        let reply = InboundEmail::parse(7, RAW.as_bytes()).unwrap();
+        let root = RAW
+            .replace(
+                "Message-ID: <reply-2@example.com>",
+                "Message-ID: <root@example.com>",
+            )
+            .replace(
+                "References: <root@example.com> <reply-1@example.com>\r\n",
+                "",
+            )
+            .replace("In-Reply-To: <reply-1@example.com>\r\n", "");
+        let root = InboundEmail::parse(1, root.as_bytes()).unwrap();
+
+        assert!(reply.session().is_some());
+        assert_eq!(reply.session(), root.session());
+    }
+
+    #[test]
+    fn test_detect_automated_mail() {
+        let email = InboundEmail::parse(7, RAW.as_bytes()).unwrap();
+        assert!(!email.automated);
+
+        let with_header =
+            |header: &str| RAW.replacen("Subject:", &format!("{}\r\nSubject:", header), 1);
+        for automated in [
+            with_header("Auto-Submitted: auto-replied"),
+            with_header("Precedence: Bulk"),
+            with_header("Precedence: list"),
+            with_header("Return-Path: <>"),
+            RAW.replace("alice@example.com", "MAILER-DAEMON@example.com"),
+            RAW.replace("alice@example.com", "no-reply@example.com"),
+        ] {
+            let email = InboundEmail::parse(7, automated.as_bytes()).unwrap();
+            assert!(email.automated, "{}", automated);
+        }
+
+        for human in [
+            with_header("Auto-Submitted: no"),
+            with_header("Precedence: first-class"),
+            with_header("Return-Path: <alice@example.com>"),
+        ] {
+            let email = InboundEmail::parse(7, human.as_bytes()).unwrap();
+            assert!(!email.automated, "{}", human);
+        }
+    }
+}
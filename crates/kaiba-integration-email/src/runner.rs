@@ -0,0 +1,147 @@
+//! Email polling runner
+//!
+//! Polls the configured IMAP mailbox for unseen mail and answers every
+//! message sent (or copied) to a Rei's address (`email_address` in the
+//! manifest). Each message is handed to an [`IntegrationEventHandler`] (the
+//! server's call pipeline) and its reply is sent back over SMTP in the same
+//! thread. A message is marked `\Seen` once answered, or when it isn't
+//! addressed to any Rei; messages whose answer failed stay unseen and are
+//! retried on the next poll. Automatic mail (auto-replies, bounces, lists)
+//! is marked seen without an answer, so two auto-responders can't loop.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use kaiba::domain::entities::Rei;
+use kaiba::domain::errors::DomainError;
+use kaiba::ports::integration::IntegrationEventHandler;
+use kaiba::ports::ReiRepository;
+use tracing::{debug, info, warn};
+
+use crate::config::EmailConfig;
+use crate::error::EmailError;
+use crate::integration::{manifest_address, rei_sender};
+use crate::mailbox::Mailbox;
+use crate::mailer::Mailer;
+use crate::message::InboundEmail;
+
+/// Answers email sent to Reis
+pub struct EmailPollingRunner {
+    config: EmailConfig,
+    mailer: Mailer,
+    reis: Arc<dyn ReiRepository>,
+    handler: Arc<dyn IntegrationEventHandler>,
+}
+
+impl EmailPollingRunner {
+    /// Create a runner answering for the Reis in `reis`
+    pub fn new(
+        config: EmailConfig,
+        reis: Arc<dyn ReiRepository>,
+        handler: Arc<dyn IntegrationEventHandler>,
+    ) -> Result<Self, EmailError> {
+        Ok(Self {
+            mailer: Mailer::new(&config)?,
+            config,
+            reis,
+            handler,
+        })
+    }
+
+    /// Poll and answer mail until `shutdown` completes
+    ///
+    /// A failed poll is logged and retried on the next interval.
+    pub async fn run_until(&self, shutdown: impl Future<Output = ()>) -> Result<(), DomainError> {
+        info!(
+            mailbox = %self.config.mailbox,
+            interval_secs = self.config.poll_interval_secs,
+            "Email polling started"
+        );
+
+        tokio::pin!(shutdown);
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(self.config.poll_interval_secs.max(1)));
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = &mut shutdown => return Ok(()),
+            }
+            if let Err(e) = self.poll().await {
+                warn!(error = %e, "Failed to poll email");
+            }
+        }
+    }
+
+    /// Answer the unseen mail once
+    async fn poll(&self) -> Result<(), EmailError> {
+        let mut mailbox = Mailbox::open(&self.config).await?;
+        let emails = mailbox.unseen().await?;
+        if emails.is_empty() {
+            return mailbox.close().await;
+        }
+        debug!(count = emails.len(), "Fetched unseen email");
+
+        let reis = match self.reis.find_all().await {
+            Ok(reis) => reis,
+            Err(e) => {
+                // Leave the mail unseen until the Reis can be loaded
+                warn!(error = %e, "Failed to load email bindings");
+                return mailbox.close().await;
+            }
+        };
+        let bound: Vec<(String, Rei)> = reis
+            .into_iter()
+            .filter_map(|rei| manifest_address(&rei).map(|address| (address, rei)))
+            .collect();
+
+        let mut done = Vec::new();
+        for email in &emails {
+            if email.automated {
+                debug!(uid = email.uid, from = %email.from_address, "Ignoring automatic email");
+                done.push(email.uid);
+                continue;
+            }
+            let Some((address, rei)) = bound
+                .iter()
+                .find(|(address, _)| email.is_addressed_to(address))
+            else {
+                debug!(uid = email.uid, "Ignoring email not addressed to a Rei");
+                done.push(email.uid);
+                continue;
+            };
+            if self.answer(rei, address, email).await {
+                done.push(email.uid);
+            }
+        }
+
+        mailbox.mark_seen(&done).await?;
+        mailbox.close().await
+    }
+
+    /// Answer one email as `rei`; false if it should be retried
+    async fn answer(&self, rei: &Rei, address: &str, email: &InboundEmail) -> bool {
+        debug!(uid = email.uid, rei_name = %rei.name, "Routing email");
+
+        let reply = match self.handler.handle(rei, email.to_event(address)).await {
+            Ok(Some(reply)) if !reply.trim().is_empty() => reply,
+            Ok(_) => return true,
+            Err(e) => {
+                warn!(error = %e, rei_name = %rei.name, "Failed to answer email");
+                return false;
+            }
+        };
+
+        let sent = match rei_sender(rei, address) {
+            Ok(from) => self.mailer.reply(from, email, &reply).await,
+            Err(e) => Err(e),
+        };
+        match sent {
+            Ok(()) => true,
+            Err(e) => {
+                warn!(error = %e, rei_name = %rei.name, "Failed to send email reply");
+                false
+            }
+        }
+    }
+}
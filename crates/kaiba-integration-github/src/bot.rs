@@ -0,0 +1,140 @@
+//! GitHub App runner
+//!
+//! Answers issues and pull requests in the repositories Reis are bound to
+//! (`github_repo` in the manifest). A newly opened issue or pull request is
+//! always answered; a comment is answered when it mentions the App
+//! (`@<app-slug>`). Each event is handed to an [`IntegrationEventHandler`]
+//! (the server's call pipeline) and its reply is posted as a comment on the
+//! thread. Events arrive by webhook; the server verifies them with
+//! [`GitHubBot::webhook_handler`] and passes them to
+//! [`GitHubBot::handle_event`].
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use kaiba::domain::entities::Rei;
+use kaiba::ports::integration::IntegrationEventHandler;
+use kaiba::ports::ReiRepository;
+use tokio::sync::{Mutex, OnceCell};
+use tracing::{debug, warn};
+
+use crate::client::GitHubClient;
+use crate::config::GitHubConfig;
+use crate::error::GitHubError;
+use crate::integration::manifest_repos;
+use crate::webhook::{GitHubEvent, GitHubWebhookHandler};
+
+/// How long the repository → Rei map is used before manifests are reloaded
+const REPO_REFRESH: Duration = Duration::from_secs(60);
+
+/// Repository → Rei bindings, reloaded every [`REPO_REFRESH`]
+#[derive(Default)]
+struct RepoMap {
+    loaded_at: Option<Instant>,
+    /// Keyed by lower-cased `owner/name`
+    reis: HashMap<String, Rei>,
+}
+
+/// Routes GitHub events to the Reis bound to their repositories
+pub struct GitHubBot {
+    client: GitHubClient,
+    config: GitHubConfig,
+    reis: Arc<dyn ReiRepository>,
+    repos: Mutex<RepoMap>,
+    /// The App's slug, looked up once
+    slug: OnceCell<String>,
+}
+
+impl GitHubBot {
+    /// Create a bot answering for the Reis in `reis`
+    pub fn new(config: GitHubConfig, reis: Arc<dyn ReiRepository>) -> Result<Self, GitHubError> {
+        Ok(Self {
+            client: GitHubClient::new(&config)?,
+            config,
+            reis,
+            repos: Mutex::new(RepoMap::default()),
+            slug: OnceCell::new(),
+        })
+    }
+
+    /// Handler checking deliveries against the configured webhook secret
+    pub fn webhook_handler(&self) -> GitHubWebhookHandler {
+        GitHubWebhookHandler::with_secret(self.config.webhook_secret.clone())
+    }
+
+    /// Answer one event, if it is in a bound repository and meant for the App
+    pub async fn handle_event(&self, event: GitHubEvent, handler: &dyn IntegrationEventHandler) {
+        let Some(rei) = self.rei_for(&event.repo).await else {
+            debug!(repo = %event.repo, "Ignoring GitHub event from unbound repository");
+            return;
+        };
+        if !event.is_opened() && !self.is_mentioned(&event).await {
+            return;
+        }
+        debug!(
+            repo = %event.repo,
+            number = %event.number,
+            rei_name = %rei.name,
+            "Routing GitHub event"
+        );
+
+        let reply = match handler.handle(&rei, event.to_integration_event()).await {
+            Ok(Some(reply)) if !reply.trim().is_empty() => reply,
+            Ok(_) => return,
+            Err(e) => {
+                warn!(error = %e, rei_name = %rei.name, "Failed to answer GitHub event");
+                return;
+            }
+        };
+
+        if let Err(e) = self
+            .client
+            .create_comment(event.installation_id, &event.repo, event.number, &reply)
+            .await
+        {
+            warn!(error = %e, "Failed to post reply to GitHub");
+        }
+    }
+
+    /// Whether a comment mentions the App
+    async fn is_mentioned(&self, event: &GitHubEvent) -> bool {
+        let slug = self
+            .slug
+            .get_or_try_init(|| async { self.client.get_app().await.map(|app| app.slug) })
+            .await;
+        match slug {
+            Ok(slug) => event.mentions(slug),
+            Err(e) => {
+                warn!(error = %e, "Failed to look up the GitHub App");
+                false
+            }
+        }
+    }
+
+    /// The Rei bound to a repository, if any
+    async fn rei_for(&self, repo: &str) -> Option<Rei> {
+        let mut repos = self.repos.lock().await;
+        let stale = repos
+            .loaded_at
+            .is_none_or(|at| at.elapsed() >= REPO_REFRESH);
+        if stale {
+            match self.reis.find_all().await {
+                Ok(reis) => {
+                    repos.reis = reis
+                        .into_iter()
+                        .flat_map(|rei| {
+                            manifest_repos(&rei)
+                                .into_iter()
+                                .map(move |repo| (repo.to_lowercase(), rei.clone()))
+                        })
+                        .collect();
+                    repos.loaded_at = Some(Instant::now());
+                }
+                // Keep answering with the bindings we have
+                Err(e) => warn!(error = %e, "Failed to load GitHub repository bindings"),
+            }
+        }
+        repos.reis.get(&repo.to_lowercase()).cloned()
+    }
+}
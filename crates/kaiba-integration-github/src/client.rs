@@ -0,0 +1,205 @@
+//! GitHub REST API client
+//!
+//! A GitHub App authenticates as itself with a short-lived JWT signed by its
+//! private key, and acts in a repository with an installation access token
+//! exchanged for that JWT. Installation tokens last an hour and are cached
+//! until shortly before they expire.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use reqwest::{Method, RequestBuilder};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tracing::{debug, error};
+
+use crate::config::GitHubConfig;
+use crate::error::GitHubError;
+use crate::types::{GitHubApp, GitHubComment};
+
+/// GitHub comment length limit
+pub(crate) const MAX_COMMENT_LEN: usize = 65536;
+
+/// REST API version requested
+const API_VERSION: &str = "2022-11-28";
+
+/// GitHub rejects requests without a User-Agent
+const USER_AGENT: &str = "kaiba";
+
+/// Lifetime of an App JWT (GitHub allows at most 10 minutes)
+const JWT_TTL_SECS: i64 = 540;
+
+/// Installation tokens this close to expiring are renewed
+const TOKEN_RENEW_SECS: i64 = 300;
+
+/// Request timeout
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Claims of an App JWT
+#[derive(Serialize)]
+struct AppClaims {
+    iat: i64,
+    exp: i64,
+    iss: String,
+}
+
+/// An installation access token
+#[derive(Debug, Clone, Deserialize)]
+struct InstallationToken {
+    token: String,
+    expires_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize)]
+struct InstallationRef {
+    id: u64,
+}
+
+/// GitHub REST API client authenticating as an App
+pub struct GitHubClient {
+    http: reqwest::Client,
+    base_url: String,
+    app_id: u64,
+    key: EncodingKey,
+    /// Installation ID → access token
+    tokens: Mutex<HashMap<u64, InstallationToken>>,
+}
+
+impl GitHubClient {
+    /// Create a new GitHub client; fails on a malformed private key
+    pub fn new(config: &GitHubConfig) -> Result<Self, GitHubError> {
+        Ok(Self {
+            http: reqwest::Client::new(),
+            base_url: config.api_base_url.trim_end_matches('/').to_string(),
+            app_id: config.app_id,
+            key: EncodingKey::from_rsa_pem(config.private_key.as_bytes())?,
+            tokens: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// A JWT authenticating as the App itself
+    fn app_jwt(&self) -> Result<String, GitHubError> {
+        // Backdated a minute against clock drift, as GitHub recommends
+        let now = Utc::now().timestamp();
+        let claims = AppClaims {
+            iat: now - 60,
+            exp: now + JWT_TTL_SECS,
+            iss: self.app_id.to_string(),
+        };
+        Ok(jsonwebtoken::encode(
+            &Header::new(Algorithm::RS256),
+            &claims,
+            &self.key,
+        )?)
+    }
+
+    /// An access token for an installation, cached until it nearly expires
+    async fn installation_token(&self, installation_id: u64) -> Result<String, GitHubError> {
+        let mut tokens = self.tokens.lock().await;
+        if let Some(token) = tokens.get(&installation_id) {
+            if (token.expires_at - Utc::now()).num_seconds() > TOKEN_RENEW_SECS {
+                return Ok(token.token.clone());
+            }
+        }
+
+        debug!(installation_id = %installation_id, "Requesting GitHub installation token");
+        let path = format!("/app/installations/{}/access_tokens", installation_id);
+        let token: InstallationToken = self
+            .send(self.request(Method::POST, &path, &self.app_jwt()?))
+            .await?;
+        tokens.insert(installation_id, token.clone());
+        Ok(token.token)
+    }
+
+    fn request(&self, method: Method, path: &str, bearer: &str) -> RequestBuilder {
+        self.http
+            .request(method, format!("{}{}", self.base_url, path))
+            .bearer_auth(bearer)
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", API_VERSION)
+            .header("User-Agent", USER_AGENT)
+            .timeout(REQUEST_TIMEOUT)
+    }
+
+    async fn send<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T, GitHubError> {
+        let response = request.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body: serde_json::Value = response.json().await.unwrap_or_default();
+            return Err(GitHubError::Api {
+                status: status.as_u16(),
+                message: body
+                    .get("message")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_else(|| status.canonical_reason().unwrap_or("request failed"))
+                    .to_string(),
+            });
+        }
+        Ok(response.json().await?)
+    }
+
+    /// The App itself
+    pub async fn get_app(&self) -> Result<GitHubApp, GitHubError> {
+        self.send(self.request(Method::GET, "/app", &self.app_jwt()?))
+            .await
+    }
+
+    /// The installation of the App on a repository (`owner/name`)
+    pub async fn repo_installation(&self, repo: &str) -> Result<u64, GitHubError> {
+        let path = format!("/repos/{}/installation", repo);
+        let installation: InstallationRef = self
+            .send(self.request(Method::GET, &path, &self.app_jwt()?))
+            .await?;
+        Ok(installation.id)
+    }
+
+    /// Comment on an issue or pull request
+    pub async fn create_comment(
+        &self,
+        installation_id: u64,
+        repo: &str,
+        number: u64,
+        body: &str,
+    ) -> Result<GitHubComment, GitHubError> {
+        debug!(repo = %repo, number = %number, content_len = %body.len(), "Commenting on GitHub");
+
+        let token = self.installation_token(installation_id).await?;
+        let path = format!("/repos/{}/issues/{}/comments", repo, number);
+        self.send(
+            self.request(Method::POST, &path, &token)
+                .json(&serde_json::json!({ "body": truncate(body) })),
+        )
+        .await
+        .inspect_err(|e| error!(error = %e, "Failed to comment on GitHub"))
+    }
+
+    /// The latest issue and pull request comments of a repository, newest first
+    pub async fn recent_comments(
+        &self,
+        installation_id: u64,
+        repo: &str,
+        limit: usize,
+    ) -> Result<Vec<GitHubComment>, GitHubError> {
+        let token = self.installation_token(installation_id).await?;
+        let path = format!("/repos/{}/issues/comments", repo);
+        self.send(self.request(Method::GET, &path, &token).query(&[
+            ("sort", "created".to_string()),
+            ("direction", "desc".to_string()),
+            ("per_page", limit.min(100).to_string()),
+        ]))
+        .await
+    }
+}
+
+/// Cut text down to GitHub's comment length limit
+pub(crate) fn truncate(text: &str) -> String {
+    if text.chars().count() > MAX_COMMENT_LEN {
+        let truncated: String = text.chars().take(MAX_COMMENT_LEN - 3).collect();
+        format!("{}...", truncated)
+    } else {
+        text.to_string()
+    }
+}
@@ -0,0 +1,38 @@
+//! GitHub configuration
+
+use serde::{Deserialize, Serialize};
+
+/// Configuration for the GitHub App integration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubConfig {
+    /// App ID from the GitHub App settings
+    pub app_id: u64,
+    /// The App's private key (PEM), signing the JWTs it authenticates with
+    pub private_key: String,
+    /// Secret GitHub signs webhook deliveries with (`X-Hub-Signature-256`)
+    pub webhook_secret: String,
+    /// REST API base URL (for GitHub Enterprise Server)
+    pub api_base_url: String,
+}
+
+impl GitHubConfig {
+    /// Create a new GitHub configuration
+    pub fn new(
+        app_id: u64,
+        private_key: impl Into<String>,
+        webhook_secret: impl Into<String>,
+    ) -> Self {
+        Self {
+            app_id,
+            private_key: private_key.into(),
+            webhook_secret: webhook_secret.into(),
+            api_base_url: "https://api.github.com".to_string(),
+        }
+    }
+
+    /// Set the REST API base URL
+    pub fn with_api_base_url(mut self, url: impl Into<String>) -> Self {
+        self.api_base_url = url.into();
+        self
+    }
+}
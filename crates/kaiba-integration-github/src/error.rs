@@ -0,0 +1,22 @@
+//! GitHub errors
+
+use kaiba::domain::errors::DomainError;
+
+/// GitHub API and authentication errors
+#[derive(Debug, thiserror::Error)]
+pub enum GitHubError {
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("GitHub API error ({status}): {message}")]
+    Api { status: u16, message: String },
+
+    #[error("App authentication failed: {0}")]
+    Jwt(#[from] jsonwebtoken::errors::Error),
+}
+
+impl From<GitHubError> for DomainError {
+    fn from(e: GitHubError) -> Self {
+        DomainError::ExternalService(e.to_string())
+    }
+}
@@ -0,0 +1,185 @@
+//! TeiIntegration implementation for GitHub
+
+use async_trait::async_trait;
+use kaiba::domain::entities::{Message, Rei};
+use kaiba::domain::errors::DomainError;
+use kaiba::ports::integration::{IntegrationEvent, TeiIntegration};
+use tracing::{debug, warn};
+
+use crate::client::GitHubClient;
+use crate::config::GitHubConfig;
+use crate::error::GitHubError;
+use crate::webhook::GitHubWebhookHandler;
+
+/// Comments read per repository by `read_messages`
+const READ_LIMIT: usize = 50;
+
+/// GitHub integration implementing TeiIntegration trait
+pub struct GitHubIntegration {
+    client: GitHubClient,
+}
+
+impl GitHubIntegration {
+    /// Create a new GitHub integration; fails on a malformed private key
+    pub fn new(config: GitHubConfig) -> Result<Self, GitHubError> {
+        Ok(Self {
+            client: GitHubClient::new(&config)?,
+        })
+    }
+
+    /// Extract the repositories from Rei's manifest
+    fn get_repos(&self, rei: &Rei) -> Result<Vec<String>, DomainError> {
+        let repos = manifest_repos(rei);
+        if repos.is_empty() {
+            return Err(DomainError::Validation(format!(
+                "Rei '{}' does not have github_repo configured in manifest",
+                rei.name
+            )));
+        }
+        Ok(repos)
+    }
+}
+
+/// The `github_repo` of a Rei's manifest: one `owner/name` or a list of them
+pub(crate) fn manifest_repos(rei: &Rei) -> Vec<String> {
+    let repos = match rei.manifest.get("github_repo") {
+        Some(serde_json::Value::String(repo)) => vec![repo.as_str()],
+        Some(serde_json::Value::Array(repos)) => repos.iter().filter_map(|v| v.as_str()).collect(),
+        _ => vec![],
+    };
+    repos
+        .into_iter()
+        .map(str::trim)
+        .filter(|repo| {
+            repo.split('/').count() == 2 && !repo.starts_with('/') && !repo.ends_with('/')
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+/// The `github_issue` of a Rei's manifest (`owner/name#12`) that
+/// `post_message` comments on
+pub(crate) fn manifest_issue(rei: &Rei) -> Option<(String, u64)> {
+    let issue = rei.manifest.get("github_issue")?.as_str()?;
+    let (repo, number) = issue.trim().split_once('#')?;
+    Some((repo.to_string(), number.parse().ok()?))
+}
+
+#[async_trait]
+impl TeiIntegration for GitHubIntegration {
+    async fn read_messages(&self, rei: &Rei) -> Result<Vec<Message>, DomainError> {
+        let mut messages = Vec::new();
+        for repo in self.get_repos(rei)? {
+            debug!(repo = %repo, rei_name = %rei.name, "Reading comments from GitHub");
+            let installation_id = self.client.repo_installation(&repo).await?;
+            let comments = self
+                .client
+                .recent_comments(installation_id, &repo, READ_LIMIT)
+                .await?;
+
+            messages.extend(comments.into_iter().filter_map(|comment| {
+                let channel_id = format!("{}#{}", repo, comment.issue_number()?);
+                Some(
+                    Message::new(
+                        comment.id.to_string(),
+                        channel_id,
+                        comment.user.id.to_string(),
+                        comment.user.login.clone(),
+                        comment.body.clone().unwrap_or_default(),
+                        "github",
+                    )
+                    .with_timestamp(comment.created_at)
+                    .with_metadata(serde_json::json!({
+                        "repo": repo,
+                        "url": comment.html_url,
+                        "is_bot": comment.user.is_bot(),
+                    })),
+                )
+            }));
+        }
+        Ok(messages)
+    }
+
+    async fn post_message(&self, rei: &Rei, content: &str) -> Result<(), DomainError> {
+        let (repo, number) = manifest_issue(rei).ok_or_else(|| {
+            DomainError::Validation(format!(
+                "Rei '{}' does not have github_issue configured in manifest",
+                rei.name
+            ))
+        })?;
+        debug!(
+            repo = %repo,
+            number = %number,
+            rei_name = %rei.name,
+            content_len = %content.len(),
+            "Posting comment to GitHub"
+        );
+
+        let installation_id = self.client.repo_installation(&repo).await?;
+        self.client
+            .create_comment(installation_id, &repo, number, content)
+            .await?;
+
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "github"
+    }
+
+    async fn handle_webhook(
+        &self,
+        payload: &[u8],
+    ) -> Result<Option<IntegrationEvent>, DomainError> {
+        let handler = GitHubWebhookHandler::new();
+        Ok(handler
+            .parse_payload(payload)?
+            .map(|event| event.to_integration_event()))
+    }
+
+    async fn health_check(&self) -> Result<bool, DomainError> {
+        match self.client.get_app().await {
+            Ok(_) => Ok(true),
+            Err(e) => {
+                warn!(error = %e, "GitHub health check failed");
+                Ok(false)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn rei(manifest: serde_json::Value) -> Rei {
+        Rei {
+            id: Uuid::new_v4(),
+            name: "Yui".to_string(),
+            role: "Assistant".to_string(),
+            avatar_url: None,
+            manifest,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_manifest_repos_and_issue() {
+        let one = rei(serde_json::json!({ "github_repo": "acme/app" }));
+        assert_eq!(manifest_repos(&one), vec!["acme/app"]);
+
+        let many = rei(serde_json::json!({
+            "github_repo": ["acme/app", " acme/docs ", "not-a-repo", "acme/"],
+            "github_issue": "acme/app#7",
+        }));
+        assert_eq!(manifest_repos(&many), vec!["acme/app", "acme/docs"]);
+        assert_eq!(manifest_issue(&many), Some(("acme/app".to_string(), 7)));
+
+        let none = rei(serde_json::json!({ "github_issue": "acme/app" }));
+        assert!(manifest_repos(&none).is_empty());
+        assert_eq!(manifest_issue(&none), None);
+    }
+}
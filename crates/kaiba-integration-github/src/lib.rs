@@ -0,0 +1,37 @@
+//! GitHub Integration for Kaiba
+//!
+//! This crate provides GitHub App integration for the Kaiba AI persona system.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use kaiba_integration_github::{GitHubBot, GitHubConfig, GitHubIntegration};
+//!
+//! let config = GitHubConfig::new(123456, private_key_pem, "webhook-secret");
+//! let integration = GitHubIntegration::new(config.clone())?;
+//!
+//! // Answer issues and comments in the repositories Reis are bound to
+//! let bot = GitHubBot::new(config, rei_repository)?;
+//! let handler = bot.webhook_handler();
+//! if handler.verify_signature(signature_header, &body) {
+//!     if let Some(event) = handler.parse_payload(&body)? {
+//!         bot.handle_event(event, &call_handler).await;
+//!     }
+//! }
+//! ```
+
+mod bot;
+mod client;
+mod config;
+mod error;
+mod integration;
+mod types;
+mod webhook;
+
+pub use bot::GitHubBot;
+pub use client::GitHubClient;
+pub use config::GitHubConfig;
+pub use error::GitHubError;
+pub use integration::GitHubIntegration;
+pub use types::{GitHubApp, GitHubComment, GitHubIssue, GitHubUser};
+pub use webhook::{conversation_session, GitHubEvent, GitHubWebhookHandler, ThreadKind};
@@ -0,0 +1,88 @@
+//! GitHub API and webhook types (only the fields Kaiba uses)
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+/// A user or bot account
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitHubUser {
+    pub id: u64,
+    pub login: String,
+    /// "User", "Bot" or "Organization"
+    #[serde(rename = "type", default)]
+    pub kind: String,
+}
+
+impl GitHubUser {
+    /// Whether the account is a bot (an App, including this one)
+    pub fn is_bot(&self) -> bool {
+        self.kind == "Bot" || self.login.ends_with("[bot]")
+    }
+}
+
+/// A GitHub App
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitHubApp {
+    pub id: u64,
+    /// Handle the App is mentioned by (`@slug`)
+    pub slug: String,
+    pub name: String,
+}
+
+/// An issue or pull request; pull requests are issues with `pull_request` set
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitHubIssue {
+    pub number: u64,
+    pub title: String,
+    pub body: Option<String>,
+    pub html_url: String,
+    pub user: GitHubUser,
+    /// Present when the issue is a pull request
+    #[serde(default)]
+    pub pull_request: Option<serde_json::Value>,
+}
+
+/// A comment on an issue, or on a pull request's diff
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitHubComment {
+    pub id: u64,
+    pub body: Option<String>,
+    pub html_url: String,
+    pub user: GitHubUser,
+    pub created_at: DateTime<Utc>,
+    /// API URL of the issue (issue comments only)
+    #[serde(default)]
+    pub issue_url: Option<String>,
+}
+
+impl GitHubComment {
+    /// Number of the issue the comment is on, from its `issue_url`
+    pub fn issue_number(&self) -> Option<u64> {
+        self.issue_url.as_deref()?.rsplit('/').next()?.parse().ok()
+    }
+}
+
+/// A repository
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitHubRepository {
+    /// `owner/name`
+    pub full_name: String,
+}
+
+/// The App installation a webhook delivery belongs to
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitHubInstallation {
+    pub id: u64,
+}
+
+/// Body of a webhook delivery
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhookPayload {
+    pub action: Option<String>,
+    pub repository: Option<GitHubRepository>,
+    pub installation: Option<GitHubInstallation>,
+    pub sender: Option<GitHubUser>,
+    pub issue: Option<GitHubIssue>,
+    pub pull_request: Option<GitHubIssue>,
+    pub comment: Option<GitHubComment>,
+}
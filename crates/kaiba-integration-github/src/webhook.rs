@@ -0,0 +1,421 @@
+//! GitHub webhook handling
+//!
+//! Deliveries are signed with the App's webhook secret. Newly opened issues
+//! and pull requests, and new comments on them (in the conversation or on
+//! the diff), become [`GitHubEvent`]s; everything else is ignored.
+
+use hmac::{Hmac, Mac};
+use kaiba::domain::errors::DomainError;
+use kaiba::ports::integration::{
+    IntegrationEvent, VerificationError, WebhookHeaders, WebhookVerifier,
+};
+use sha2::Sha256;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::types::{GitHubUser, WebhookPayload};
+
+/// Header carrying `sha256=<hex HMAC of the body>`
+const SIGNATURE_HEADER: &str = "x-hub-signature-256";
+
+/// Whether a thread is an issue or a pull request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThreadKind {
+    Issue,
+    PullRequest,
+}
+
+impl ThreadKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Issue => "issue",
+            Self::PullRequest => "pull_request",
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Issue => "issue",
+            Self::PullRequest => "pull request",
+        }
+    }
+}
+
+/// A new issue, pull request or comment on one
+#[derive(Debug, Clone)]
+pub struct GitHubEvent {
+    /// Repository, `owner/name`
+    pub repo: String,
+    /// App installation that received the event (and can answer it)
+    pub installation_id: u64,
+    /// Issue or pull request number
+    pub number: u64,
+    pub kind: ThreadKind,
+    pub title: String,
+    /// Link to the issue, pull request or comment
+    pub url: String,
+    pub author: GitHubUser,
+    pub body: String,
+    /// The comment; `None` when the issue or pull request was just opened
+    pub comment_id: Option<u64>,
+}
+
+impl GitHubEvent {
+    /// Whether the event opened the thread rather than commented on it
+    pub fn is_opened(&self) -> bool {
+        self.comment_id.is_none()
+    }
+
+    /// Whether the body mentions `@login` (case-insensitive, whole handle)
+    pub fn mentions(&self, login: &str) -> bool {
+        let body = self.body.to_lowercase();
+        let handle = format!("@{}", login.to_lowercase());
+        body.match_indices(&handle).any(|(at, _)| {
+            let before = body[..at].chars().next_back();
+            let after = body[at + handle.len()..].chars().next();
+            !before.is_some_and(is_handle_char) && !after.is_some_and(is_handle_char)
+        })
+    }
+
+    /// The event as a `MessageReceived` for the call pipeline
+    ///
+    /// The channel is `owner/name#number`; each thread is one call session.
+    /// Replies to newly opened threads are what the Rei found out about
+    /// them, so they are marked to be remembered (`remember_reply`).
+    pub fn to_integration_event(&self) -> IntegrationEvent {
+        let content = if self.is_opened() {
+            format!(
+                "New {} #{}: {}\n\n{}",
+                self.kind.label(),
+                self.number,
+                self.title,
+                self.body
+            )
+        } else {
+            format!(
+                "Comment on {} #{} \"{}\":\n\n{}",
+                self.kind.label(),
+                self.number,
+                self.title,
+                self.body
+            )
+        };
+
+        IntegrationEvent::MessageReceived {
+            channel_id: format!("{}#{}", self.repo, self.number),
+            user_id: self.author.id.to_string(),
+            user_name: self.author.login.clone(),
+            content,
+            metadata: serde_json::json!({
+                "repo": self.repo,
+                "number": self.number,
+                "kind": self.kind.as_str(),
+                "title": self.title,
+                "url": self.url,
+                "comment_id": self.comment_id,
+                "installation_id": self.installation_id,
+                "session_id": conversation_session(&self.repo, self.number),
+                "remember_reply": self.is_opened(),
+            }),
+        }
+    }
+}
+
+/// GitHub webhook handler for incoming deliveries
+pub struct GitHubWebhookHandler {
+    /// Secret deliveries are signed with (optional)
+    secret: Option<String>,
+}
+
+impl GitHubWebhookHandler {
+    /// Create a new webhook handler
+    pub fn new() -> Self {
+        Self { secret: None }
+    }
+
+    /// Create a webhook handler with signature verification
+    pub fn with_secret(secret: impl Into<String>) -> Self {
+        Self {
+            secret: Some(secret.into()),
+        }
+    }
+
+    /// Parse a delivery body into an event, if it is one Kaiba answers
+    ///
+    /// The kind of event is told by the payload itself, so the
+    /// `X-GitHub-Event` header is not needed. Events sent by bots
+    /// (including this App) are ignored.
+    pub fn parse_payload(&self, payload: &[u8]) -> Result<Option<GitHubEvent>, DomainError> {
+        let payload: WebhookPayload = serde_json::from_slice(payload)
+            .map_err(|e| DomainError::Validation(format!("Invalid GitHub webhook: {}", e)))?;
+        Ok(self.parse_delivery(payload))
+    }
+
+    fn parse_delivery(&self, payload: WebhookPayload) -> Option<GitHubEvent> {
+        let action = payload.action.as_deref().unwrap_or_default();
+        let (Some(repo), Some(installation)) = (payload.repository, payload.installation) else {
+            debug!(action = %action, "Ignoring GitHub delivery without repository or installation");
+            return None;
+        };
+        if payload.sender.as_ref().is_some_and(GitHubUser::is_bot) {
+            return None;
+        }
+
+        let (issue, kind, comment) =
+            match (action, payload.comment, payload.issue, payload.pull_request) {
+                // issue_comment (on issues and pull requests alike)
+                ("created", Some(comment), Some(issue), _) => {
+                    let kind = if issue.pull_request.is_some() {
+                        ThreadKind::PullRequest
+                    } else {
+                        ThreadKind::Issue
+                    };
+                    (issue, kind, Some(comment))
+                }
+                // pull_request_review_comment
+                ("created", Some(comment), None, Some(pr)) => {
+                    (pr, ThreadKind::PullRequest, Some(comment))
+                }
+                // issues / pull_request
+                ("opened", None, Some(issue), _) => (issue, ThreadKind::Issue, None),
+                ("opened", None, None, Some(pr)) => (pr, ThreadKind::PullRequest, None),
+                _ => {
+                    debug!(action = %action, "Ignoring GitHub delivery");
+                    return None;
+                }
+            };
+
+        let (author, body, url, comment_id) = match comment {
+            Some(comment) => (
+                comment.user,
+                comment.body.unwrap_or_default(),
+                comment.html_url,
+                Some(comment.id),
+            ),
+            None => (
+                issue.user,
+                issue.body.unwrap_or_default(),
+                issue.html_url,
+                None,
+            ),
+        };
+        let body = body.trim().to_string();
+        if author.is_bot() || (body.is_empty() && comment_id.is_some()) {
+            return None;
+        }
+
+        Some(GitHubEvent {
+            repo: repo.full_name,
+            installation_id: installation.id,
+            number: issue.number,
+            kind,
+            title: issue.title,
+            url,
+            author,
+            body,
+            comment_id,
+        })
+    }
+
+    /// Check the `X-Hub-Signature-256` header (`sha256=<hex HMAC of the body>`)
+    pub fn verify_signature(&self, header: Option<&str>, body: &[u8]) -> bool {
+        let Some(ref secret) = self.secret else {
+            warn!("Signature verification requested but no webhook secret configured");
+            return false;
+        };
+        let Some(signature) = header
+            .and_then(|h| h.strip_prefix("sha256="))
+            .and_then(|h| hex::decode(h).ok())
+        else {
+            return false;
+        };
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+            .expect("HMAC can take key of any size");
+        mac.update(body);
+        mac.verify_slice(&signature).is_ok()
+    }
+}
+
+impl WebhookVerifier for GitHubWebhookHandler {
+    fn verify(&self, headers: &WebhookHeaders, body: &[u8]) -> Result<(), VerificationError> {
+        if self.secret.is_none() {
+            return Err(VerificationError::NotConfigured(
+                "no GitHub webhook secret".into(),
+            ));
+        }
+        let signature = headers.require(SIGNATURE_HEADER)?;
+        if self.verify_signature(Some(signature), body) {
+            Ok(())
+        } else {
+            Err(VerificationError::InvalidSignature)
+        }
+    }
+}
+
+impl Default for GitHubWebhookHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Call session of an issue or pull request, stable across restarts
+///
+/// FNV-1a of `owner/name#number` keeps the mapping stable across builds.
+pub fn conversation_session(repo: &str, number: u64) -> Uuid {
+    let thread = format!("{}#{}", repo.to_lowercase(), number);
+    let hash = thread.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    Uuid::from_u64_pair(4, hash)
+}
+
+fn is_handle_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(login: &str, kind: &str) -> serde_json::Value {
+        serde_json::json!({ "id": 7, "login": login, "type": kind })
+    }
+
+    fn issue(pull_request: bool) -> serde_json::Value {
+        let mut issue = serde_json::json!({
+            "number": 12,
+            "title": "Crash on start",
+            "body": "It crashes.",
+            "html_url": "https://github.com/acme/app/issues/12",
+            "user": user("aki", "User"),
+        });
+        if pull_request {
+            issue["pull_request"] = serde_json::json!({ "url": "..." });
+        }
+        issue
+    }
+
+    fn delivery(extra: serde_json::Value) -> Vec<u8> {
+        let mut payload = serde_json::json!({
+            "repository": { "full_name": "acme/app" },
+            "installation": { "id": 99 },
+            "sender": user("aki", "User"),
+        });
+        for (key, value) in extra.as_object().unwrap() {
+            payload[key] = value.clone();
+        }
+        serde_json::to_vec(&payload).unwrap()
+    }
+
+    fn comment(body: &str, author: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({
+            "id": 555,
+            "body": body,
+            "html_url": "https://github.com/acme/app/issues/12#issuecomment-555",
+            "user": author,
+            "created_at": "2024-01-01T00:00:00Z",
+        })
+    }
+
+    #[test]
+    fn test_parse_opened_issue() {
+        let handler = GitHubWebhookHandler::new();
+        let payload = delivery(serde_json::json!({ "action": "opened", "issue": issue(false) }));
+
+        let event = handler.parse_payload(&payload).unwrap().unwrap();
+        assert_eq!(event.repo, "acme/app");
+        assert_eq!(event.installation_id, 99);
+        assert_eq!(event.kind, ThreadKind::Issue);
+        assert!(event.is_opened());
+
+        let IntegrationEvent::MessageReceived {
+            channel_id,
+            content,
+            metadata,
+            ..
+        } = event.to_integration_event()
+        else {
+            panic!("Expected MessageReceived event");
+        };
+        assert_eq!(channel_id, "acme/app#12");
+        assert!(content.starts_with("New issue #12: Crash on start"));
+        assert_eq!(metadata["remember_reply"], true);
+        assert_eq!(
+            metadata["session_id"],
+            conversation_session("acme/app", 12).to_string()
+        );
+    }
+
+    #[test]
+    fn test_parse_comments() {
+        let handler = GitHubWebhookHandler::new();
+
+        let on_pr = delivery(serde_json::json!({
+            "action": "created",
+            "issue": issue(true),
+            "comment": comment("@kaiba-bot thoughts?", user("mio", "User")),
+        }));
+        let event = handler.parse_payload(&on_pr).unwrap().unwrap();
+        assert_eq!(event.kind, ThreadKind::PullRequest);
+        assert_eq!(event.comment_id, Some(555));
+        assert_eq!(event.author.login, "mio");
+        assert!(event.mentions("Kaiba-Bot"));
+        assert!(!event.mentions("kaiba"));
+
+        let on_diff = delivery(serde_json::json!({
+            "action": "created",
+            "pull_request": issue(false),
+            "comment": comment("Why this?", user("mio", "User")),
+        }));
+        let event = handler.parse_payload(&on_diff).unwrap().unwrap();
+        assert_eq!(event.kind, ThreadKind::PullRequest);
+        assert!(!event.is_opened());
+    }
+
+    #[test]
+    fn test_ignore_bots_and_other_actions() {
+        let handler = GitHubWebhookHandler::new();
+        for extra in [
+            serde_json::json!({ "action": "edited", "issue": issue(false) }),
+            serde_json::json!({ "action": "closed", "pull_request": issue(false) }),
+            serde_json::json!({
+                "action": "created",
+                "issue": issue(false),
+                "comment": comment("Done!", user("kaiba-bot[bot]", "Bot")),
+            }),
+            serde_json::json!({ "zen": "Keep it logically awesome." }),
+        ] {
+            assert!(handler.parse_payload(&delivery(extra)).unwrap().is_none());
+        }
+        assert!(handler.parse_payload(b"not json").is_err());
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let handler = GitHubWebhookHandler::with_secret("s3cret");
+        let body = br#"{"action":"opened"}"#;
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"s3cret").unwrap();
+        mac.update(body);
+        let signature = format!("sha256={}", hex::encode(mac.finalize().into_bytes()));
+
+        assert!(handler.verify_signature(Some(&signature), body));
+        assert!(!handler.verify_signature(Some(&signature), b"{}"));
+        assert!(!handler.verify_signature(Some("sha256=zz"), body));
+        assert!(!handler.verify_signature(None, body));
+        assert!(!GitHubWebhookHandler::new().verify_signature(Some(&signature), body));
+
+        let headers = WebhookHeaders::new().with("X-Hub-Signature-256", signature);
+        assert_eq!(handler.verify(&headers, body), Ok(()));
+        assert_eq!(
+            handler.verify(&headers, b"{}"),
+            Err(VerificationError::InvalidSignature)
+        );
+        assert_eq!(
+            handler.verify(&WebhookHeaders::new(), body),
+            Err(VerificationError::MissingHeader(
+                SIGNATURE_HEADER.to_string()
+            ))
+        );
+    }
+}
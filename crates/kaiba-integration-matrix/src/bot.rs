@@ -0,0 +1,188 @@
+//! Matrix bot runner
+//!
+//! Syncs with the homeserver and answers messages in the rooms Reis are
+//! bound to (`matrix_room_id` in the manifest). Invites to a bound room are
+//! accepted; other invites are left alone. Each message is handed to an
+//! [`IntegrationEventHandler`] (the server's call pipeline) and its reply is
+//! sent back to the room. Messages in encrypted rooms are decrypted by the
+//! client and replies are encrypted (feature `e2e-encryption`).
+//!
+//! Messages sent while the bot was offline are skipped: the first sync only
+//! catches up, and handlers see events from the next one on.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use kaiba::domain::entities::Rei;
+use kaiba::domain::errors::DomainError;
+use kaiba::ports::integration::{IntegrationEvent, IntegrationEventHandler};
+use kaiba::ports::ReiRepository;
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+
+use crate::client::MatrixClient;
+use crate::config::MatrixConfig;
+use crate::integration::manifest_room_id;
+use crate::message::conversation_session;
+use crate::types::{localpart, RoomEvent, SyncResponse};
+
+/// How long the room → Rei map is used before manifests are reloaded
+const ROOM_REFRESH: Duration = Duration::from_secs(60);
+
+/// Room → Rei bindings, reloaded every [`ROOM_REFRESH`]
+#[derive(Default)]
+struct RoomMap {
+    loaded_at: Option<Instant>,
+    reis: HashMap<String, Rei>,
+}
+
+/// Routes Matrix room messages to the Reis bound to the rooms
+pub struct MatrixBot {
+    client: MatrixClient,
+    config: MatrixConfig,
+    reis: Arc<dyn ReiRepository>,
+    rooms: Mutex<RoomMap>,
+}
+
+impl MatrixBot {
+    /// Create a bot on a logged-in client (see [`connect`](crate::connect))
+    pub fn new(client: MatrixClient, config: MatrixConfig, reis: Arc<dyn ReiRepository>) -> Self {
+        Self {
+            client,
+            config,
+            reis,
+            rooms: Mutex::new(RoomMap::default()),
+        }
+    }
+
+    /// Sync and answer messages until the sync fails or `shutdown` completes
+    pub async fn sync_until(
+        self: Arc<Self>,
+        handler: Arc<dyn IntegrationEventHandler>,
+        shutdown: impl Future<Output = ()> + Send,
+    ) -> Result<(), DomainError> {
+        let timeout = Duration::from_secs(self.config.sync_timeout_secs);
+        let caught_up = self.client.sync(None, timeout).await?;
+        self.accept_invites(&caught_up).await;
+        let mut since = caught_up.next_batch;
+
+        info!("Matrix sync started");
+        tokio::pin!(shutdown);
+        loop {
+            let response = tokio::select! {
+                response = self.client.sync(Some(&since), timeout) => response?,
+                _ = &mut shutdown => return Ok(()),
+            };
+            self.accept_invites(&response).await;
+
+            for (room_id, room) in response.rooms.join {
+                for event in room.timeline.events {
+                    // Answered concurrently so a slow reply doesn't hold up the sync
+                    let bot = self.clone();
+                    let handler = handler.clone();
+                    let room_id = room_id.clone();
+                    tokio::spawn(async move {
+                        bot.handle_message(event, &room_id, handler.as_ref()).await
+                    });
+                }
+            }
+            since = response.next_batch;
+        }
+    }
+
+    /// Answer one message, if it is text in a bound room
+    async fn handle_message(
+        &self,
+        event: RoomEvent,
+        room_id: &str,
+        handler: &dyn IntegrationEventHandler,
+    ) {
+        if event.sender == self.client.user_id() {
+            return;
+        }
+        let Some(content) = event.text_body().filter(|c| !c.trim().is_empty()) else {
+            return;
+        };
+        let Some(rei) = self.rei_for(room_id).await else {
+            debug!(room_id = %room_id, "Ignoring Matrix message from unbound room");
+            return;
+        };
+        debug!(room_id = %room_id, rei_name = %rei.name, "Routing Matrix message");
+
+        let user_name = match self.client.display_name(room_id, &event.sender).await {
+            Some(name) => name,
+            None => localpart(&event.sender).to_string(),
+        };
+        let integration_event = IntegrationEvent::MessageReceived {
+            channel_id: room_id.to_string(),
+            user_id: event.sender.clone(),
+            user_name,
+            content: content.to_string(),
+            metadata: serde_json::json!({
+                "event_id": event.event_id,
+                "session_id": conversation_session(room_id),
+            }),
+        };
+
+        let _ = self.client.typing(room_id, true).await;
+        let reply = match handler.handle(&rei, integration_event).await {
+            Ok(Some(reply)) if !reply.trim().is_empty() => reply,
+            Ok(_) => {
+                let _ = self.client.typing(room_id, false).await;
+                return;
+            }
+            Err(e) => {
+                warn!(error = %e, rei_name = %rei.name, "Failed to answer Matrix message");
+                let _ = self.client.typing(room_id, false).await;
+                return;
+            }
+        };
+
+        // Sending a message ends the typing notice
+        if let Err(e) = self.client.send_text(room_id, &reply).await {
+            warn!(error = %e, "Failed to post reply to Matrix");
+        }
+    }
+
+    /// Join the rooms of a sync the bot was invited to, if a Rei is bound to them
+    async fn accept_invites(&self, response: &SyncResponse) {
+        for room_id in response.rooms.invite.keys() {
+            if self.rei_for(room_id).await.is_none() {
+                debug!(room_id = %room_id, "Ignoring Matrix invite to unbound room");
+                continue;
+            }
+            match self.client.join(room_id).await {
+                Ok(()) => info!(room_id = %room_id, "Joined Matrix room"),
+                Err(e) => warn!(error = %e, room_id = %room_id, "Failed to join Matrix room"),
+            }
+        }
+    }
+
+    /// The Rei bound to a room, if any
+    async fn rei_for(&self, room_id: &str) -> Option<Rei> {
+        let mut rooms = self.rooms.lock().await;
+        let stale = rooms
+            .loaded_at
+            .is_none_or(|at| at.elapsed() >= ROOM_REFRESH);
+        if stale {
+            match self.reis.find_all().await {
+                Ok(reis) => {
+                    rooms.reis = reis
+                        .into_iter()
+                        .filter_map(|rei| {
+                            manifest_room_id(&rei)
+                                .map(str::to_string)
+                                .map(|id| (id, rei))
+                        })
+                        .collect();
+                    rooms.loaded_at = Some(Instant::now());
+                }
+                // Keep answering with the bindings we have
+                Err(e) => warn!(error = %e, "Failed to load Matrix room bindings"),
+            }
+        }
+        rooms.reis.get(room_id).cloned()
+    }
+}
@@ -0,0 +1,377 @@
+//! Matrix client-server API client
+//!
+//! The bot logs in with a password once; requests then carry the access
+//! token the homeserver handed out. With the `e2e-encryption` feature,
+//! messages to encrypted rooms are encrypted and encrypted events are
+//! decrypted (see [`crate::crypto`]); without it, encrypted events are
+//! skipped with a warning.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use reqwest::{Method, RequestBuilder, Url};
+use serde::de::DeserializeOwned;
+use tracing::{error, info, warn};
+
+use crate::config::MatrixConfig;
+#[cfg(feature = "e2e-encryption")]
+use crate::crypto::Encryption;
+use crate::error::MatrixError;
+#[cfg(feature = "e2e-encryption")]
+use crate::types::JoinedMembersResponse;
+use crate::types::{
+    ErrorResponse, LoginResponse, MemberContent, MessagesResponse, RoomEvent, SyncResponse,
+    ENCRYPTED_EVENT,
+};
+
+/// Extra time a sync request gets beyond its own timeout
+const SYNC_GRACE: Duration = Duration::from_secs(10);
+
+/// Timeout of requests other than syncs
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Sync filter of the catch-up sync: only the latest event per room
+const CATCH_UP_FILTER: &str = r#"{"room":{"timeline":{"limit":1}}}"#;
+
+struct Inner {
+    http: reqwest::Client,
+    base_url: Url,
+    access_token: String,
+    user_id: String,
+    /// Makes transaction IDs unique within a login
+    txn_counter: AtomicU64,
+    #[cfg(feature = "e2e-encryption")]
+    encryption: Encryption,
+}
+
+/// A logged-in Matrix client; clones share the login
+#[derive(Clone)]
+pub struct MatrixClient {
+    inner: Arc<Inner>,
+}
+
+/// Log in to the homeserver
+///
+/// The returned client is shared by [`MatrixIntegration`](crate::MatrixIntegration)
+/// and [`MatrixBot`](crate::MatrixBot), so both act as the same device.
+pub async fn connect(config: &MatrixConfig) -> Result<MatrixClient, MatrixError> {
+    let base_url = Url::parse(&config.homeserver_url)
+        .map_err(|e| MatrixError::Url(format!("{}: {}", config.homeserver_url, e)))?;
+    let http = reqwest::Client::new();
+
+    let mut body = serde_json::json!({
+        "type": "m.login.password",
+        "identifier": { "type": "m.id.user", "user": config.user_id },
+        "password": config.password,
+        "initial_device_display_name": config.device_name,
+    });
+    if let Some(device_id) = &config.device_id {
+        body["device_id"] = serde_json::json!(device_id);
+    }
+    let login: LoginResponse = send(
+        http.post(endpoint(&base_url, &["login"]))
+            .json(&body)
+            .timeout(REQUEST_TIMEOUT),
+    )
+    .await?;
+    info!(user_id = %login.user_id, device_id = %login.device_id, "Logged in to Matrix");
+
+    #[cfg(feature = "e2e-encryption")]
+    if config.device_id.is_some() {
+        warn!("Encryption keys are not kept across logins; key upload fails for a reused device");
+    }
+    let client = MatrixClient {
+        inner: Arc::new(Inner {
+            http,
+            base_url,
+            #[cfg(feature = "e2e-encryption")]
+            encryption: Encryption::new(&login.user_id, &login.device_id).await?,
+            access_token: login.access_token,
+            user_id: login.user_id,
+            txn_counter: AtomicU64::new(0),
+        }),
+    };
+    // Upload the device's identity and one-time keys
+    #[cfg(feature = "e2e-encryption")]
+    client.inner.encryption.send_outgoing(&client).await;
+
+    Ok(client)
+}
+
+/// URL of a client-server API endpoint; segments are percent-encoded
+fn endpoint(base_url: &Url, segments: &[&str]) -> Url {
+    let mut url = base_url.clone();
+    if let Ok(mut path) = url.path_segments_mut() {
+        path.pop_if_empty()
+            .extend(["_matrix", "client", "v3"])
+            .extend(segments);
+    }
+    url
+}
+
+async fn send<T: DeserializeOwned>(request: RequestBuilder) -> Result<T, MatrixError> {
+    let response = request.send().await?;
+    let status = response.status();
+    if !status.is_success() {
+        let body: ErrorResponse = response.json().await.unwrap_or_default();
+        return Err(MatrixError::Api {
+            status: status.as_u16(),
+            errcode: body.errcode,
+            message: body.error,
+        });
+    }
+    Ok(response.json().await?)
+}
+
+impl MatrixClient {
+    /// The bot's user ID
+    pub fn user_id(&self) -> &str {
+        &self.inner.user_id
+    }
+
+    fn request(&self, method: Method, segments: &[&str]) -> RequestBuilder {
+        self.inner
+            .http
+            .request(method, endpoint(&self.inner.base_url, segments))
+            .bearer_auth(&self.inner.access_token)
+            .timeout(REQUEST_TIMEOUT)
+    }
+
+    /// Check the access token is still valid
+    pub async fn whoami(&self) -> Result<(), MatrixError> {
+        send::<serde_json::Value>(self.request(Method::GET, &["account", "whoami"]))
+            .await
+            .map(|_| ())
+    }
+
+    /// Sync with the homeserver
+    ///
+    /// Without `since` this is the catch-up sync, which returns only the
+    /// latest event of each room; with it, the request waits up to
+    /// `timeout` for new events.
+    pub async fn sync(
+        &self,
+        since: Option<&str>,
+        timeout: Duration,
+    ) -> Result<SyncResponse, MatrixError> {
+        let request = self
+            .request(Method::GET, &["sync"])
+            .timeout(timeout + SYNC_GRACE);
+        let request = match since {
+            Some(since) => request.query(&[
+                ("since", since.to_string()),
+                ("timeout", timeout.as_millis().to_string()),
+            ]),
+            None => request.query(&[("filter", CATCH_UP_FILTER)]),
+        };
+        let mut response: SyncResponse = send(request).await?;
+
+        // Room keys arrive in the same sync as the messages they unlock
+        #[cfg(feature = "e2e-encryption")]
+        self.inner.encryption.receive_sync(self, &response).await;
+        for (room_id, room) in &mut response.rooms.join {
+            let events = std::mem::take(&mut room.timeline.events);
+            for event in events {
+                room.timeline
+                    .events
+                    .push(self.decrypt(room_id, event).await);
+            }
+        }
+        Ok(response)
+    }
+
+    /// Join a room
+    pub async fn join(&self, room_id: &str) -> Result<(), MatrixError> {
+        send::<serde_json::Value>(
+            self.request(Method::POST, &["join", room_id])
+                .json(&serde_json::json!({})),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Send a text message to a room, encrypted if the room is
+    pub async fn send_text(&self, room_id: &str, body: &str) -> Result<(), MatrixError> {
+        let content = serde_json::json!({ "msgtype": "m.text", "body": body });
+        self.send_event(room_id, "m.room.message", content)
+            .await
+            .inspect_err(
+                |e| error!(error = %e, room_id = %room_id, "Failed to send Matrix message"),
+            )
+    }
+
+    #[cfg(feature = "e2e-encryption")]
+    async fn send_event(
+        &self,
+        room_id: &str,
+        event_type: &str,
+        content: serde_json::Value,
+    ) -> Result<(), MatrixError> {
+        let encryption = &self.inner.encryption;
+        if !encryption.is_encrypted(self, room_id).await? {
+            return self.send_event_as(room_id, event_type, &content).await;
+        }
+        let encrypted = encryption
+            .encrypt(self, room_id, event_type, &content)
+            .await?;
+        self.send_event_as(room_id, ENCRYPTED_EVENT, &encrypted)
+            .await
+    }
+
+    #[cfg(not(feature = "e2e-encryption"))]
+    async fn send_event(
+        &self,
+        room_id: &str,
+        event_type: &str,
+        content: serde_json::Value,
+    ) -> Result<(), MatrixError> {
+        self.send_event_as(room_id, event_type, &content).await
+    }
+
+    async fn send_event_as(
+        &self,
+        room_id: &str,
+        event_type: &str,
+        content: &serde_json::Value,
+    ) -> Result<(), MatrixError> {
+        let txn_id = format!(
+            "kaiba{}.{}",
+            Utc::now().timestamp_millis(),
+            self.inner.txn_counter.fetch_add(1, Ordering::Relaxed)
+        );
+        send::<serde_json::Value>(
+            self.request(
+                Method::PUT,
+                &["rooms", room_id, "send", event_type, &txn_id],
+            )
+            .json(content),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    #[cfg(feature = "e2e-encryption")]
+    async fn decrypt(&self, room_id: &str, event: RoomEvent) -> RoomEvent {
+        self.inner.encryption.decrypt(room_id, event).await
+    }
+
+    #[cfg(not(feature = "e2e-encryption"))]
+    async fn decrypt(&self, room_id: &str, event: RoomEvent) -> RoomEvent {
+        if event.kind == ENCRYPTED_EVENT {
+            warn!(
+                room_id = %room_id,
+                event_id = %event.event_id,
+                "Skipping encrypted Matrix event (built without e2e-encryption)"
+            );
+        }
+        event
+    }
+
+    /// Show or stop the typing notice in a room
+    pub async fn typing(&self, room_id: &str, typing: bool) -> Result<(), MatrixError> {
+        let mut body = serde_json::json!({ "typing": typing });
+        if typing {
+            body["timeout"] = serde_json::json!(REQUEST_TIMEOUT.as_millis() as u64);
+        }
+        send::<serde_json::Value>(
+            self.request(
+                Method::PUT,
+                &["rooms", room_id, "typing", &self.inner.user_id],
+            )
+            .json(&body),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// The latest events of a room, newest first
+    pub async fn messages(&self, room_id: &str, limit: u32) -> Result<Vec<RoomEvent>, MatrixError> {
+        let response: MessagesResponse = send(
+            self.request(Method::GET, &["rooms", room_id, "messages"])
+                .query(&[("dir", "b".to_string()), ("limit", limit.to_string())]),
+        )
+        .await?;
+        let mut events = Vec::with_capacity(response.chunk.len());
+        for event in response.chunk {
+            events.push(self.decrypt(room_id, event).await);
+        }
+        Ok(events)
+    }
+
+    /// The display name of a room member, if set
+    pub async fn display_name(&self, room_id: &str, user_id: &str) -> Option<String> {
+        let member: MemberContent = send(self.request(
+            Method::GET,
+            &["rooms", room_id, "state", "m.room.member", user_id],
+        ))
+        .await
+        .ok()?;
+        member.displayname
+    }
+}
+
+#[cfg(feature = "e2e-encryption")]
+impl MatrixClient {
+    /// Send a ruma request (the key endpoints of [`Encryption`])
+    pub(crate) async fn send_request<R: ruma::api::OutgoingRequest>(
+        &self,
+        request: R,
+    ) -> Result<R::IncomingResponse, MatrixError> {
+        use ruma::api::{IncomingResponse, MatrixVersion, SendAccessToken};
+
+        let request = request
+            .try_into_http_request::<Vec<u8>>(
+                self.inner.base_url.as_str().trim_end_matches('/'),
+                SendAccessToken::IfRequired(&self.inner.access_token),
+                &[MatrixVersion::V1_1],
+            )
+            .map_err(|e| MatrixError::Encryption(e.to_string()))?;
+        let mut request = reqwest::Request::try_from(request)?;
+        *request.timeout_mut() = Some(REQUEST_TIMEOUT);
+
+        let response = self.inner.http.execute(request).await?;
+        let mut http_response = http::Response::new(Vec::new());
+        *http_response.status_mut() = response.status();
+        *http_response.body_mut() = response.bytes().await?.to_vec();
+        R::IncomingResponse::try_from_http_response(http_response)
+            .map_err(|e| MatrixError::Encryption(e.to_string()))
+    }
+
+    /// Whether a room has encryption turned on
+    pub(crate) async fn room_encryption(&self, room_id: &str) -> Result<bool, MatrixError> {
+        let state = send::<serde_json::Value>(self.request(
+            Method::GET,
+            &["rooms", room_id, "state", "m.room.encryption", ""],
+        ))
+        .await;
+        match state {
+            Ok(_) => Ok(true),
+            Err(MatrixError::Api { status: 404, .. }) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// User IDs of a room's joined members
+    pub(crate) async fn joined_members(&self, room_id: &str) -> Result<Vec<String>, MatrixError> {
+        let members: JoinedMembersResponse =
+            send(self.request(Method::GET, &["rooms", room_id, "joined_members"])).await?;
+        Ok(members.joined.into_keys().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_encodes_segments() {
+        let base = Url::parse("https://matrix.example.org/").unwrap();
+        let url = endpoint(&base, &["rooms", "!abc/def:example.org", "messages"]);
+        assert_eq!(
+            url.as_str(),
+            "https://matrix.example.org/_matrix/client/v3/rooms/!abc%2Fdef:example.org/messages"
+        );
+    }
+}
@@ -0,0 +1,53 @@
+//! Matrix configuration
+
+use serde::{Deserialize, Serialize};
+
+/// Configuration for Matrix integration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatrixConfig {
+    /// Homeserver URL, e.g. `https://matrix.example.org`
+    pub homeserver_url: String,
+    /// Bot account, e.g. `@kaiba:example.org`
+    pub user_id: String,
+    /// Bot account password
+    pub password: String,
+    /// Device to log in as; unset = a new device on every login
+    ///
+    /// Leave unset with `e2e-encryption`: keys live in memory, and a reused
+    /// device can't upload new ones.
+    pub device_id: Option<String>,
+    /// Display name of a newly created device
+    pub device_name: String,
+    /// Seconds one sync request waits for events
+    pub sync_timeout_secs: u64,
+}
+
+impl MatrixConfig {
+    /// Create a new Matrix configuration for a bot account
+    pub fn new(
+        homeserver_url: impl Into<String>,
+        user_id: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        Self {
+            homeserver_url: homeserver_url.into(),
+            user_id: user_id.into(),
+            password: password.into(),
+            device_id: None,
+            device_name: "Kaiba".to_string(),
+            sync_timeout_secs: 30,
+        }
+    }
+
+    /// Log in as an existing device
+    pub fn with_device_id(mut self, device_id: impl Into<String>) -> Self {
+        self.device_id = Some(device_id.into());
+        self
+    }
+
+    /// Set the sync timeout
+    pub fn with_sync_timeout(mut self, secs: u64) -> Self {
+        self.sync_timeout_secs = secs;
+        self
+    }
+}
@@ -0,0 +1,349 @@
+//! End-to-end encryption (feature `e2e-encryption`)
+//!
+//! An [`OlmMachine`] holds the device's keys. Room keys arrive as to-device
+//! messages in syncs; before a message is sent to an encrypted room, the
+//! room key is shared with the devices of every member. The keys are kept in
+//! memory, so after a restart the bot is a new device and cannot decrypt
+//! messages sent to the previous one.
+
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::ops::Deref;
+use std::sync::Mutex;
+
+use matrix_sdk_crypto::{
+    DecryptionSettings, EncryptionSettings, EncryptionSyncChanges, OlmMachine, OutgoingRequest,
+    OutgoingRequests, ToDeviceRequest, TrustRequirement,
+};
+use ruma::api::client::keys::get_keys;
+use ruma::api::client::message::send_message_event;
+use ruma::api::client::to_device::send_event_to_device;
+use ruma::serde::Raw;
+use ruma::{OwnedDeviceId, OwnedUserId, RoomId, UserId};
+use tracing::{debug, warn};
+
+use crate::client::MatrixClient;
+use crate::error::MatrixError;
+use crate::types::{RoomEvent, SyncResponse, ENCRYPTED_EVENT};
+
+/// Keys and sessions of the bot's device
+pub(crate) struct Encryption {
+    machine: OlmMachine,
+    /// Rooms known to be encrypted; encryption can't be turned off again
+    encrypted_rooms: Mutex<HashSet<String>>,
+    /// Key claims and room key sharing run one at a time
+    share_lock: tokio::sync::Mutex<()>,
+}
+
+fn encryption_error(e: impl Display) -> MatrixError {
+    MatrixError::Encryption(e.to_string())
+}
+
+impl Encryption {
+    /// Create the keys of a freshly logged-in device
+    pub(crate) async fn new(user_id: &str, device_id: &str) -> Result<Self, MatrixError> {
+        let user_id = UserId::parse(user_id).map_err(encryption_error)?;
+        let device_id = OwnedDeviceId::from(device_id);
+        Ok(Self {
+            machine: OlmMachine::new(&user_id, &device_id).await,
+            encrypted_rooms: Mutex::new(HashSet::new()),
+            share_lock: tokio::sync::Mutex::new(()),
+        })
+    }
+
+    /// Take in the to-device messages and key counts of a sync
+    pub(crate) async fn receive_sync(&self, client: &MatrixClient, response: &SyncResponse) {
+        let changes = EncryptionSyncChanges {
+            to_device_events: response.to_device.events.clone(),
+            changed_devices: &response.device_lists,
+            one_time_keys_counts: &response.device_one_time_keys_count,
+            unused_fallback_keys: response.device_unused_fallback_key_types.as_deref(),
+            next_batch_token: Some(response.next_batch.clone()),
+        };
+        if let Err(e) = self.machine.receive_sync_changes(changes).await {
+            warn!(error = %e, "Failed to process Matrix encryption changes");
+        }
+        self.send_outgoing(client).await;
+    }
+
+    /// Send the requests the machine has queued (key uploads and queries, ...)
+    pub(crate) async fn send_outgoing(&self, client: &MatrixClient) {
+        let requests = match self.machine.outgoing_requests().await {
+            Ok(requests) => requests,
+            Err(e) => {
+                warn!(error = %e, "Failed to collect Matrix key requests");
+                return;
+            }
+        };
+        for request in requests {
+            if let Err(e) = self.send_request(client, &request).await {
+                warn!(error = %e, "Failed to send Matrix key request");
+            }
+        }
+    }
+
+    async fn send_request(
+        &self,
+        client: &MatrixClient,
+        request: &OutgoingRequest,
+    ) -> Result<(), MatrixError> {
+        let request_id = request.request_id();
+        let marked = match request.request() {
+            OutgoingRequests::KeysUpload(upload) => {
+                let response = client.send_request(upload.clone()).await?;
+                self.machine
+                    .mark_request_as_sent(request_id, &response)
+                    .await
+            }
+            OutgoingRequests::KeysQuery(query) => {
+                let mut keys = get_keys::v3::Request::new();
+                keys.device_keys = query.device_keys.clone();
+                keys.timeout = query.timeout;
+                let response = client.send_request(keys).await?;
+                self.machine
+                    .mark_request_as_sent(request_id, &response)
+                    .await
+            }
+            OutgoingRequests::KeysClaim(claim) => {
+                let response = client.send_request(claim.clone()).await?;
+                self.machine
+                    .mark_request_as_sent(request_id, &response)
+                    .await
+            }
+            OutgoingRequests::ToDeviceRequest(to_device) => {
+                return self.send_to_device(client, to_device).await;
+            }
+            OutgoingRequests::SignatureUpload(signatures) => {
+                let response = client.send_request(signatures.clone()).await?;
+                self.machine
+                    .mark_request_as_sent(request_id, &response)
+                    .await
+            }
+            OutgoingRequests::RoomMessage(message) => {
+                let message = send_message_event::v3::Request::new(
+                    message.room_id.clone(),
+                    message.txn_id.clone(),
+                    &message.content,
+                )
+                .map_err(encryption_error)?;
+                let response = client.send_request(message).await?;
+                self.machine
+                    .mark_request_as_sent(request_id, &response)
+                    .await
+            }
+        };
+        marked.map_err(encryption_error)
+    }
+
+    async fn send_to_device(
+        &self,
+        client: &MatrixClient,
+        request: &ToDeviceRequest,
+    ) -> Result<(), MatrixError> {
+        let response = client
+            .send_request(send_event_to_device::v3::Request::new_raw(
+                request.event_type.clone(),
+                request.txn_id.clone(),
+                request.messages.clone(),
+            ))
+            .await?;
+        self.machine
+            .mark_request_as_sent(&request.txn_id, &response)
+            .await
+            .map_err(encryption_error)
+    }
+
+    /// The plaintext of an encrypted event; other events are returned as they are
+    ///
+    /// Events that can't be decrypted (sent before this device existed, or
+    /// whose room key hasn't arrived) are returned encrypted and have no text.
+    pub(crate) async fn decrypt(&self, room_id: &str, event: RoomEvent) -> RoomEvent {
+        if event.kind != ENCRYPTED_EVENT {
+            return event;
+        }
+        let decrypted = async {
+            let room_id = RoomId::parse(room_id).map_err(encryption_error)?;
+            let raw = Raw::new(&event).map_err(encryption_error)?.cast();
+            let settings = DecryptionSettings {
+                sender_device_trust_requirement: TrustRequirement::Untrusted,
+            };
+            let decrypted = self
+                .machine
+                .decrypt_room_event(&raw, &room_id, &settings)
+                .await
+                .map_err(encryption_error)?;
+            decrypted
+                .event
+                .deserialize_as::<RoomEvent>()
+                .map_err(encryption_error)
+        };
+        match decrypted.await {
+            Ok(decrypted) => decrypted,
+            Err(e) => {
+                warn!(
+                    error = %e,
+                    room_id = %room_id,
+                    event_id = %event.event_id,
+                    "Skipping Matrix event that could not be decrypted"
+                );
+                event
+            }
+        }
+    }
+
+    /// Whether messages to a room must be encrypted
+    pub(crate) async fn is_encrypted(
+        &self,
+        client: &MatrixClient,
+        room_id: &str,
+    ) -> Result<bool, MatrixError> {
+        let known = self
+            .encrypted_rooms
+            .lock()
+            .expect("encrypted rooms lock poisoned")
+            .contains(room_id);
+        if known {
+            return Ok(true);
+        }
+        let encrypted = client.room_encryption(room_id).await?;
+        if encrypted {
+            self.encrypted_rooms
+                .lock()
+                .expect("encrypted rooms lock poisoned")
+                .insert(room_id.to_string());
+        }
+        Ok(encrypted)
+    }
+
+    /// Encrypt an event for a room, sharing the room key with its members first
+    pub(crate) async fn encrypt(
+        &self,
+        client: &MatrixClient,
+        room_id: &str,
+        event_type: &str,
+        content: &serde_json::Value,
+    ) -> Result<serde_json::Value, MatrixError> {
+        let room = RoomId::parse(room_id).map_err(encryption_error)?;
+        let members: Vec<OwnedUserId> = client
+            .joined_members(room_id)
+            .await?
+            .iter()
+            .filter_map(|user_id| UserId::parse(user_id.as_str()).ok())
+            .collect();
+        let members = || members.iter().map(Deref::deref);
+
+        let _guard = self.share_lock.lock().await;
+        // Query the devices of members seen for the first time
+        self.machine
+            .update_tracked_users(members())
+            .await
+            .map_err(encryption_error)?;
+        self.send_outgoing(client).await;
+
+        if let Some((request_id, claim)) = self
+            .machine
+            .get_missing_sessions(members())
+            .await
+            .map_err(encryption_error)?
+        {
+            let response = client.send_request(claim).await?;
+            self.machine
+                .mark_request_as_sent(&request_id, &response)
+                .await
+                .map_err(encryption_error)?;
+        }
+
+        let shares = self
+            .machine
+            .share_room_key(&room, members(), EncryptionSettings::default())
+            .await
+            .map_err(encryption_error)?;
+        debug!(room_id = %room_id, requests = shares.len(), "Sharing Matrix room key");
+        for share in shares {
+            self.send_to_device(client, &share).await?;
+        }
+
+        let content = Raw::new(content).map_err(encryption_error)?.cast();
+        let encrypted = self
+            .machine
+            .encrypt_room_event_raw(&room, event_type, &content)
+            .await
+            .map_err(encryption_error)?;
+        serde_json::to_value(encrypted).map_err(encryption_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: &str, content: serde_json::Value) -> RoomEvent {
+        serde_json::from_value(serde_json::json!({
+            "type": kind,
+            "event_id": "$abc:example.org",
+            "sender": "@aki:example.org",
+            "origin_server_ts": 1700000000000u64,
+            "content": content,
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_decrypt_passes_plain_and_skips_unknown_sessions() {
+        let encryption = Encryption::new("@kaiba:example.org", "KAIBA")
+            .await
+            .unwrap();
+
+        let plain = event(
+            "m.room.message",
+            serde_json::json!({ "msgtype": "m.text", "body": "Let's use Rust" }),
+        );
+        let plain = encryption.decrypt("!room:example.org", plain).await;
+        assert_eq!(plain.text_body(), Some("Let's use Rust"));
+
+        // No room key for this session has arrived, so the event stays encrypted
+        let encrypted = event(
+            ENCRYPTED_EVENT,
+            serde_json::json!({
+                "algorithm": "m.megolm.v1.aes-sha2",
+                "ciphertext": "AwgAEnACgAkLmt6qF84IK++J7UDH2Za1YVchHyprqTqsg",
+                "device_id": "SENDER",
+                "sender_key": "WJ6Ce7U67a6jqkHYHd8o0+5H4bqdi9hInZdk0+swuXs",
+                "session_id": "6xbv6fXpsvGrXu8/mfL3yIVIYdUWAVHi7CmAUbbcvG0",
+            }),
+        );
+        let encrypted = encryption.decrypt("!room:example.org", encrypted).await;
+        assert_eq!(encrypted.kind, ENCRYPTED_EVENT);
+        assert_eq!(encrypted.text_body(), None);
+    }
+
+    #[test]
+    fn test_sync_response_encryption_fields() {
+        let response: SyncResponse = serde_json::from_value(serde_json::json!({
+            "next_batch": "s72595_4483_1934",
+            "to_device": { "events": [{
+                "type": "m.room.encrypted",
+                "sender": "@aki:example.org",
+                "content": { "algorithm": "m.olm.v1.curve25519-aes-sha2", "ciphertext": {} }
+            }] },
+            "device_lists": { "changed": ["@aki:example.org"] },
+            "device_one_time_keys_count": { "signed_curve25519": 20 },
+            "device_unused_fallback_key_types": ["signed_curve25519"]
+        }))
+        .unwrap();
+
+        assert_eq!(response.to_device.events.len(), 1);
+        assert_eq!(
+            response.device_lists.changed[0].as_str(),
+            "@aki:example.org"
+        );
+        assert_eq!(response.device_one_time_keys_count.len(), 1);
+        assert_eq!(
+            response
+                .device_unused_fallback_key_types
+                .as_deref()
+                .map(<[_]>::len),
+            Some(1)
+        );
+    }
+}
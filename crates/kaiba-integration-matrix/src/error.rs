@@ -0,0 +1,29 @@
+//! Matrix errors
+
+use kaiba::domain::errors::DomainError;
+
+/// Matrix client and protocol errors
+#[derive(Debug, thiserror::Error)]
+pub enum MatrixError {
+    #[error("Matrix HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("Matrix API error {status} ({errcode}): {message}")]
+    Api {
+        status: u16,
+        errcode: String,
+        message: String,
+    },
+
+    #[error("Invalid homeserver URL: {0}")]
+    Url(String),
+
+    #[error("Matrix encryption error: {0}")]
+    Encryption(String),
+}
+
+impl From<MatrixError> for DomainError {
+    fn from(e: MatrixError) -> Self {
+        DomainError::ExternalService(e.to_string())
+    }
+}
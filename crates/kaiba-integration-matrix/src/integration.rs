@@ -0,0 +1,121 @@
+//! TeiIntegration implementation for Matrix
+
+use async_trait::async_trait;
+use kaiba::domain::entities::{Message, Rei};
+use kaiba::domain::errors::DomainError;
+use kaiba::ports::integration::{IntegrationEvent, TeiIntegration};
+use tracing::{debug, warn};
+
+use crate::client::MatrixClient;
+use crate::message::to_message;
+
+/// Messages read from a room's history
+const READ_LIMIT: u32 = 50;
+
+/// Matrix integration implementing TeiIntegration trait
+pub struct MatrixIntegration {
+    client: MatrixClient,
+}
+
+impl MatrixIntegration {
+    /// Create a Matrix integration on a logged-in client (see [`connect`](crate::connect))
+    pub fn new(client: MatrixClient) -> Self {
+        Self { client }
+    }
+
+    /// The room named by the Rei's manifest
+    fn get_room_id<'a>(&self, rei: &'a Rei) -> Result<&'a str, DomainError> {
+        manifest_room_id(rei).ok_or_else(|| {
+            DomainError::Validation(format!(
+                "Rei '{}' does not have matrix_room_id configured in manifest",
+                rei.name
+            ))
+        })
+    }
+}
+
+/// The `matrix_room_id` of a Rei's manifest (`!opaque:server`)
+pub(crate) fn manifest_room_id(rei: &Rei) -> Option<&str> {
+    rei.manifest
+        .get("matrix_room_id")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+}
+
+#[async_trait]
+impl TeiIntegration for MatrixIntegration {
+    async fn read_messages(&self, rei: &Rei) -> Result<Vec<Message>, DomainError> {
+        let room_id = self.get_room_id(rei)?;
+        debug!(room_id = %room_id, rei_name = %rei.name, "Reading messages from Matrix");
+
+        // Events that could not be decrypted have no text, so they are left out
+        let events = self.client.messages(room_id, READ_LIMIT).await?;
+        Ok(events
+            .iter()
+            .filter_map(|event| to_message(event, room_id))
+            .collect())
+    }
+
+    async fn post_message(&self, rei: &Rei, content: &str) -> Result<(), DomainError> {
+        let room_id = self.get_room_id(rei)?;
+        debug!(
+            room_id = %room_id,
+            rei_name = %rei.name,
+            content_len = %content.len(),
+            "Posting message to Matrix"
+        );
+
+        self.client.send_text(room_id, content).await?;
+
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "matrix"
+    }
+
+    async fn handle_webhook(
+        &self,
+        _payload: &[u8],
+    ) -> Result<Option<IntegrationEvent>, DomainError> {
+        // Events arrive by sync (see MatrixBot), not by webhook
+        Ok(None)
+    }
+
+    async fn health_check(&self) -> Result<bool, DomainError> {
+        match self.client.whoami().await {
+            Ok(_) => Ok(true),
+            Err(e) => {
+                warn!(error = %e, "Matrix health check failed");
+                Ok(false)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_manifest_room_id() {
+        let rei = |manifest| Rei {
+            id: Uuid::new_v4(),
+            name: "Yui".to_string(),
+            role: "Assistant".to_string(),
+            avatar_url: None,
+            manifest,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        let bound = rei(serde_json::json!({ "matrix_room_id": " !abc:example.org " }));
+        assert_eq!(manifest_room_id(&bound), Some("!abc:example.org"));
+        let empty = rei(serde_json::json!({ "matrix_room_id": "" }));
+        assert_eq!(manifest_room_id(&empty), None);
+        assert_eq!(manifest_room_id(&rei(serde_json::json!({}))), None);
+    }
+}
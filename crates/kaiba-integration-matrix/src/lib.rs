@@ -0,0 +1,39 @@
+//! Matrix Integration for Kaiba
+//!
+//! This crate provides Matrix platform integration for the Kaiba AI persona
+//! system, for communities running their own homeserver. It speaks the
+//! client-server API directly. End-to-end encrypted rooms are supported with
+//! the `e2e-encryption` feature (on by default), which keeps the device's
+//! keys in memory.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use kaiba_integration_matrix::{connect, MatrixBot, MatrixConfig, MatrixIntegration};
+//!
+//! let config = MatrixConfig::new("https://matrix.example.org", "@kaiba:example.org", "password");
+//! let client = connect(&config).await?;
+//! let integration = MatrixIntegration::new(client.clone());
+//!
+//! // Answer messages in the rooms Reis are bound to
+//! let bot = Arc::new(MatrixBot::new(client, config, rei_repository));
+//! bot.sync_until(call_handler, shutdown.cancelled()).await?;
+//! ```
+
+mod bot;
+mod client;
+mod config;
+#[cfg(feature = "e2e-encryption")]
+mod crypto;
+mod error;
+mod integration;
+mod message;
+mod types;
+
+pub use bot::MatrixBot;
+pub use client::{connect, MatrixClient};
+pub use config::MatrixConfig;
+pub use error::MatrixError;
+pub use integration::MatrixIntegration;
+pub use message::conversation_session;
+pub use types::{JoinedRoom, RoomEvent, SyncResponse, SyncRooms, Timeline};
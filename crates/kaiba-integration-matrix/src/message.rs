@@ -0,0 +1,86 @@
+//! Matrix events as domain messages
+
+use chrono::{DateTime, Utc};
+use kaiba::domain::entities::Message;
+use uuid::Uuid;
+
+use crate::types::{localpart, server_name, RoomEvent};
+
+/// A text message of a room's timeline as a domain message
+pub(crate) fn to_message(event: &RoomEvent, room_id: &str) -> Option<Message> {
+    let body = event.text_body()?;
+    let timestamp =
+        DateTime::<Utc>::from_timestamp_millis(event.origin_server_ts).unwrap_or_else(Utc::now);
+
+    Some(
+        Message::new(
+            event.event_id.clone(),
+            room_id,
+            event.sender.clone(),
+            localpart(&event.sender),
+            body,
+            "matrix",
+        )
+        .with_timestamp(timestamp)
+        .with_metadata(serde_json::json!({
+            "server_name": server_name(&event.sender),
+        })),
+    )
+}
+
+/// Call session of a room, derived from its ID so it survives restarts
+///
+/// FNV-1a keeps the mapping stable across builds and restarts.
+pub fn conversation_session(room_id: &str) -> Uuid {
+    let hash = room_id.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    Uuid::from_u64_pair(3, hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_message() {
+        let event: RoomEvent = serde_json::from_value(serde_json::json!({
+            "type": "m.room.message",
+            "event_id": "$abc:example.org",
+            "sender": "@aki:example.org",
+            "origin_server_ts": 1700000000000u64,
+            "content": { "msgtype": "m.text", "body": "Let's use Rust" }
+        }))
+        .unwrap();
+
+        let message = to_message(&event, "!room:example.org").unwrap();
+        assert_eq!(message.id, "$abc:example.org");
+        assert_eq!(message.channel_id, "!room:example.org");
+        assert_eq!(message.author_name, "aki");
+        assert_eq!(message.content, "Let's use Rust");
+        assert_eq!(message.timestamp.timestamp(), 1700000000);
+        assert_eq!(message.metadata["server_name"], "example.org");
+
+        let encrypted: RoomEvent = serde_json::from_value(serde_json::json!({
+            "type": "m.room.encrypted",
+            "event_id": "$def:example.org",
+            "sender": "@aki:example.org",
+            "origin_server_ts": 1700000000000u64,
+            "content": { "algorithm": "m.megolm.v1.aes-sha2", "ciphertext": "..." }
+        }))
+        .unwrap();
+        assert!(to_message(&encrypted, "!room:example.org").is_none());
+    }
+
+    #[test]
+    fn test_conversation_session() {
+        assert_eq!(
+            conversation_session("!a:example.org"),
+            conversation_session("!a:example.org")
+        );
+        assert_ne!(
+            conversation_session("!a:example.org"),
+            conversation_session("!b:example.org")
+        );
+    }
+}
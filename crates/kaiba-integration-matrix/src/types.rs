@@ -0,0 +1,149 @@
+//! Matrix client-server API types (only the fields Kaiba uses)
+
+use std::collections::HashMap;
+
+#[cfg(feature = "e2e-encryption")]
+use std::collections::BTreeMap;
+
+#[cfg(feature = "e2e-encryption")]
+use ruma::{
+    api::client::sync::sync_events::DeviceLists, events::AnyToDeviceEvent, serde::Raw,
+    OneTimeKeyAlgorithm, UInt,
+};
+use serde::{Deserialize, Serialize};
+
+/// Response of `/login`
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct LoginResponse {
+    pub access_token: String,
+    pub user_id: String,
+    pub device_id: String,
+}
+
+/// Response of `/sync`
+#[derive(Debug, Clone, Deserialize)]
+pub struct SyncResponse {
+    /// Token the next sync continues from
+    pub next_batch: String,
+    #[serde(default)]
+    pub rooms: SyncRooms,
+    /// Messages sent to this device (room keys, among others)
+    #[cfg(feature = "e2e-encryption")]
+    #[serde(default)]
+    pub(crate) to_device: ToDevice,
+    /// Users whose devices changed since the last sync
+    #[cfg(feature = "e2e-encryption")]
+    #[serde(default)]
+    pub(crate) device_lists: DeviceLists,
+    /// One-time keys the server still holds for this device
+    #[cfg(feature = "e2e-encryption")]
+    #[serde(default)]
+    pub(crate) device_one_time_keys_count: BTreeMap<OneTimeKeyAlgorithm, UInt>,
+    #[cfg(feature = "e2e-encryption")]
+    pub(crate) device_unused_fallback_key_types: Option<Vec<OneTimeKeyAlgorithm>>,
+}
+
+/// To-device messages of a sync
+#[cfg(feature = "e2e-encryption")]
+#[derive(Debug, Clone, Default, Deserialize)]
+pub(crate) struct ToDevice {
+    #[serde(default)]
+    pub events: Vec<Raw<AnyToDeviceEvent>>,
+}
+
+/// Rooms with news in a sync
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SyncRooms {
+    /// Joined rooms by room ID
+    #[serde(default)]
+    pub join: HashMap<String, JoinedRoom>,
+    /// Rooms the bot is invited to, by room ID
+    #[serde(default)]
+    pub invite: HashMap<String, serde_json::Value>,
+}
+
+/// A joined room in a sync
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct JoinedRoom {
+    #[serde(default)]
+    pub timeline: Timeline,
+}
+
+/// New timeline events of a room
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Timeline {
+    #[serde(default)]
+    pub events: Vec<RoomEvent>,
+}
+
+/// Event type of encrypted room events
+pub(crate) const ENCRYPTED_EVENT: &str = "m.room.encrypted";
+
+/// A room timeline event
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RoomEvent {
+    /// Event type, e.g. `m.room.message`
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub event_id: String,
+    pub sender: String,
+    /// Milliseconds since the epoch
+    pub origin_server_ts: i64,
+    #[serde(default)]
+    pub content: serde_json::Value,
+}
+
+impl RoomEvent {
+    /// The text of a room message (`m.text`, `m.notice` or `m.emote`)
+    pub fn text_body(&self) -> Option<&str> {
+        if self.kind != "m.room.message" {
+            return None;
+        }
+        match self.content.get("msgtype")?.as_str()? {
+            "m.text" | "m.notice" | "m.emote" => self.content.get("body")?.as_str(),
+            _ => None,
+        }
+    }
+}
+
+/// Response of `/rooms/{room_id}/messages`
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct MessagesResponse {
+    #[serde(default)]
+    pub chunk: Vec<RoomEvent>,
+}
+
+/// Response of `/rooms/{room_id}/joined_members`
+#[cfg(feature = "e2e-encryption")]
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct JoinedMembersResponse {
+    /// Members by user ID
+    #[serde(default)]
+    pub joined: HashMap<String, serde_json::Value>,
+}
+
+/// A room member's state event content
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct MemberContent {
+    pub displayname: Option<String>,
+}
+
+/// Error body of a failed request
+#[derive(Debug, Clone, Default, Deserialize)]
+pub(crate) struct ErrorResponse {
+    #[serde(default)]
+    pub errcode: String,
+    #[serde(default)]
+    pub error: String,
+}
+
+/// The localpart of a user ID (`aki` for `@aki:example.org`)
+pub(crate) fn localpart(user_id: &str) -> &str {
+    let user_id = user_id.strip_prefix('@').unwrap_or(user_id);
+    user_id.split(':').next().unwrap_or(user_id)
+}
+
+/// The server name of a user ID (`example.org` for `@aki:example.org`)
+pub(crate) fn server_name(user_id: &str) -> &str {
+    user_id.split_once(':').map_or("", |(_, server)| server)
+}
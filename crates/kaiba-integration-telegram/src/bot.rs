@@ -0,0 +1,173 @@
+//! Telegram bot runner
+//!
+//! Answers messages in the chats Reis are bound to (`telegram_chat_id` in
+//! the manifest; a private chat's ID is the user's ID). Updates arrive by
+//! long polling ([`TelegramBot::poll_until`]) or, with a webhook secret, by
+//! webhook (the server passes them to [`TelegramBot::handle_update`]). Each
+//! message is handed to an [`IntegrationEventHandler`] (the server's call
+//! pipeline) and its reply is sent back to the chat, as a reply in groups.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use kaiba::domain::entities::Rei;
+use kaiba::domain::errors::DomainError;
+use kaiba::ports::integration::IntegrationEventHandler;
+use kaiba::ports::ReiRepository;
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+
+use crate::client::TelegramClient;
+use crate::config::TelegramConfig;
+use crate::integration::manifest_chat_id;
+use crate::types::TelegramUpdate;
+use crate::webhook::TelegramWebhookHandler;
+
+/// How long the chat → Rei map is used before manifests are reloaded
+const CHAT_REFRESH: Duration = Duration::from_secs(60);
+
+/// Pause after a failed poll before trying again
+const POLL_RETRY: Duration = Duration::from_secs(5);
+
+/// Chat → Rei bindings, reloaded every [`CHAT_REFRESH`]
+#[derive(Default)]
+struct ChatMap {
+    loaded_at: Option<Instant>,
+    reis: HashMap<i64, Rei>,
+}
+
+/// Routes Telegram updates to the Reis bound to their chats
+pub struct TelegramBot {
+    client: TelegramClient,
+    config: TelegramConfig,
+    reis: Arc<dyn ReiRepository>,
+    chats: Mutex<ChatMap>,
+}
+
+impl TelegramBot {
+    /// Create a bot answering for the Reis in `reis`
+    pub fn new(config: TelegramConfig, reis: Arc<dyn ReiRepository>) -> Self {
+        Self {
+            client: TelegramClient::new(&config),
+            config,
+            reis,
+            chats: Mutex::new(ChatMap::default()),
+        }
+    }
+
+    /// Whether updates arrive by webhook rather than long polling
+    pub fn uses_webhook(&self) -> bool {
+        self.config.webhook_secret.is_some()
+    }
+
+    /// Handler checking webhook updates against the configured secret
+    pub fn webhook_handler(&self) -> TelegramWebhookHandler {
+        match &self.config.webhook_secret {
+            Some(secret) => TelegramWebhookHandler::with_secret(secret.clone()),
+            None => TelegramWebhookHandler::new(),
+        }
+    }
+
+    /// Long-poll updates and answer them until `shutdown` completes
+    ///
+    /// Each update is answered in its own task, so a slow reply doesn't hold
+    /// up the next poll.
+    pub async fn poll_until(
+        self: Arc<Self>,
+        handler: Arc<dyn IntegrationEventHandler>,
+        shutdown: impl Future<Output = ()> + Send,
+    ) -> Result<(), DomainError> {
+        // getUpdates is refused while a webhook is set
+        self.client
+            .delete_webhook()
+            .await
+            .map_err(|e| DomainError::ExternalService(e.to_string()))?;
+        info!("Telegram long polling started");
+
+        tokio::pin!(shutdown);
+        let mut offset = None;
+        loop {
+            let updates = tokio::select! {
+                updates = self.client.get_updates(offset, self.config.poll_timeout_secs) => updates,
+                _ = &mut shutdown => return Ok(()),
+            };
+            let updates = match updates {
+                Ok(updates) => updates,
+                Err(e) => {
+                    warn!(error = %e, "Failed to poll Telegram updates");
+                    tokio::select! {
+                        _ = tokio::time::sleep(POLL_RETRY) => continue,
+                        _ = &mut shutdown => return Ok(()),
+                    }
+                }
+            };
+
+            for update in updates {
+                offset = Some(update.update_id + 1);
+                let bot = self.clone();
+                let handler = handler.clone();
+                tokio::spawn(async move { bot.handle_update(update, handler.as_ref()).await });
+            }
+        }
+    }
+
+    /// Answer one update, if it is a message in a bound chat
+    pub async fn handle_update(
+        &self,
+        update: TelegramUpdate,
+        handler: &dyn IntegrationEventHandler,
+    ) {
+        let Some(event) = self.webhook_handler().parse_update(&update) else {
+            return;
+        };
+        let Some(message) = update.message else {
+            return;
+        };
+        let chat_id = message.chat.id;
+        let Some(rei) = self.rei_for(chat_id).await else {
+            debug!(chat_id = %chat_id, "Ignoring Telegram message from unbound chat");
+            return;
+        };
+        debug!(chat_id = %chat_id, rei_name = %rei.name, "Routing Telegram message");
+
+        let _ = self.client.send_typing(chat_id).await;
+        let reply = match handler.handle(&rei, event).await {
+            Ok(Some(reply)) if !reply.trim().is_empty() => reply,
+            Ok(_) => return,
+            Err(e) => {
+                warn!(error = %e, rei_name = %rei.name, "Failed to answer Telegram message");
+                return;
+            }
+        };
+
+        // In groups, reply to the message so it's clear who is answered
+        let reply_to = (!message.chat.is_private()).then_some(message.message_id);
+        if let Err(e) = self.client.send_message(chat_id, &reply, reply_to).await {
+            warn!(error = %e, "Failed to post reply to Telegram");
+        }
+    }
+
+    /// The Rei bound to a chat, if any
+    async fn rei_for(&self, chat_id: i64) -> Option<Rei> {
+        let mut chats = self.chats.lock().await;
+        let stale = chats
+            .loaded_at
+            .is_none_or(|at| at.elapsed() >= CHAT_REFRESH);
+        if stale {
+            match self.reis.find_all().await {
+                Ok(reis) => {
+                    chats.reis = reis
+                        .into_iter()
+                        .filter_map(|rei| manifest_chat_id(&rei).map(|id| (id, rei)))
+                        .collect();
+                    chats.loaded_at = Some(Instant::now());
+                }
+                // Keep answering with the bindings we have
+                Err(e) => warn!(error = %e, "Failed to load Telegram chat bindings"),
+            }
+        }
+        chats.reis.get(&chat_id).cloned()
+    }
+}
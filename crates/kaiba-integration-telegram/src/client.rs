@@ -0,0 +1,160 @@
+//! Telegram Bot API client
+
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use tracing::{debug, error};
+
+use crate::config::TelegramConfig;
+use crate::types::{TelegramMessage, TelegramUpdate, TelegramUser};
+
+/// Telegram message length limit
+pub(crate) const MAX_MESSAGE_LEN: usize = 4096;
+
+/// Extra time a long poll request gets beyond its own timeout
+const POLL_GRACE: Duration = Duration::from_secs(10);
+
+/// Timeout of requests other than long polls
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Telegram Bot API errors
+#[derive(Debug, thiserror::Error)]
+pub enum TelegramError {
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("Telegram API error: {0}")]
+    Api(String),
+}
+
+/// Bot API response envelope
+#[derive(Debug, Deserialize)]
+struct ApiResponse<T> {
+    ok: bool,
+    result: Option<T>,
+    description: Option<String>,
+}
+
+/// Telegram Bot API client
+pub struct TelegramClient {
+    http: reqwest::Client,
+    base_url: String,
+}
+
+impl TelegramClient {
+    /// Create a new Telegram client
+    pub fn new(config: &TelegramConfig) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            base_url: format!(
+                "{}/bot{}",
+                config.api_base_url.trim_end_matches('/'),
+                config.token
+            ),
+        }
+    }
+
+    async fn call<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        body: &serde_json::Value,
+        timeout: Duration,
+    ) -> Result<T, TelegramError> {
+        let response: ApiResponse<T> = self
+            .http
+            .post(format!("{}/{}", self.base_url, method))
+            .json(body)
+            .timeout(timeout)
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        match response {
+            ApiResponse {
+                ok: true,
+                result: Some(result),
+                ..
+            } => Ok(result),
+            ApiResponse { description, .. } => Err(TelegramError::Api(
+                description.unwrap_or_else(|| format!("{} failed", method)),
+            )),
+        }
+    }
+
+    /// Wait up to `timeout_secs` for updates after `offset`
+    pub async fn get_updates(
+        &self,
+        offset: Option<i64>,
+        timeout_secs: u64,
+    ) -> Result<Vec<TelegramUpdate>, TelegramError> {
+        self.call(
+            "getUpdates",
+            &serde_json::json!({
+                "offset": offset,
+                "timeout": timeout_secs,
+                "allowed_updates": ["message"],
+            }),
+            Duration::from_secs(timeout_secs) + POLL_GRACE,
+        )
+        .await
+    }
+
+    /// Send a message to a chat, optionally as a reply
+    pub async fn send_message(
+        &self,
+        chat_id: i64,
+        text: &str,
+        reply_to: Option<i64>,
+    ) -> Result<TelegramMessage, TelegramError> {
+        debug!(chat_id = %chat_id, content_len = %text.len(), "Sending message to Telegram");
+
+        self.call(
+            "sendMessage",
+            &serde_json::json!({
+                "chat_id": chat_id,
+                "text": truncate(text),
+                "reply_parameters": reply_to.map(|id| serde_json::json!({
+                    "message_id": id,
+                    "allow_sending_without_reply": true,
+                })),
+            }),
+            REQUEST_TIMEOUT,
+        )
+        .await
+        .inspect_err(|e| error!(error = %e, "Failed to send Telegram message"))
+    }
+
+    /// Show "typing..." in a chat
+    pub async fn send_typing(&self, chat_id: i64) -> Result<bool, TelegramError> {
+        self.call(
+            "sendChatAction",
+            &serde_json::json!({ "chat_id": chat_id, "action": "typing" }),
+            REQUEST_TIMEOUT,
+        )
+        .await
+    }
+
+    /// The bot's own user
+    pub async fn get_me(&self) -> Result<TelegramUser, TelegramError> {
+        self.call("getMe", &serde_json::json!({}), REQUEST_TIMEOUT)
+            .await
+    }
+
+    /// Remove the webhook, so updates can be long-polled
+    pub async fn delete_webhook(&self) -> Result<bool, TelegramError> {
+        self.call("deleteWebhook", &serde_json::json!({}), REQUEST_TIMEOUT)
+            .await
+    }
+}
+
+/// Cut text down to Telegram's message length limit
+pub(crate) fn truncate(text: &str) -> String {
+    if text.chars().count() > MAX_MESSAGE_LEN {
+        let truncated: String = text.chars().take(MAX_MESSAGE_LEN - 3).collect();
+        format!("{}...", truncated)
+    } else {
+        text.to_string()
+    }
+}
@@ -0,0 +1,56 @@
+//! Telegram configuration
+
+use serde::{Deserialize, Serialize};
+
+/// Configuration for Telegram integration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelegramConfig {
+    /// Bot token from @BotFather
+    pub token: String,
+    /// Secret Telegram sends with webhook updates
+    /// (`X-Telegram-Bot-Api-Secret-Token`); without it updates are long-polled
+    pub webhook_secret: Option<String>,
+    /// Seconds one long poll waits for updates
+    pub poll_timeout_secs: u64,
+    /// Bot API base URL (for a local Bot API server)
+    pub api_base_url: String,
+}
+
+impl TelegramConfig {
+    /// Create a new Telegram configuration with just a token
+    pub fn new(token: impl Into<String>) -> Self {
+        Self {
+            token: token.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Receive updates by webhook instead of long polling
+    pub fn with_webhook_secret(mut self, secret: impl Into<String>) -> Self {
+        self.webhook_secret = Some(secret.into());
+        self
+    }
+
+    /// Set the long poll timeout
+    pub fn with_poll_timeout(mut self, secs: u64) -> Self {
+        self.poll_timeout_secs = secs;
+        self
+    }
+
+    /// Set the Bot API base URL
+    pub fn with_api_base_url(mut self, url: impl Into<String>) -> Self {
+        self.api_base_url = url.into();
+        self
+    }
+}
+
+impl Default for TelegramConfig {
+    fn default() -> Self {
+        Self {
+            token: String::new(),
+            webhook_secret: None,
+            poll_timeout_secs: 30,
+            api_base_url: "https://api.telegram.org".to_string(),
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//! TeiIntegration implementation for Telegram
+
+use async_trait::async_trait;
+use kaiba::domain::entities::{Message, Rei};
+use kaiba::domain::errors::DomainError;
+use kaiba::ports::integration::{IntegrationEvent, TeiIntegration};
+use tracing::{debug, warn};
+
+use crate::client::TelegramClient;
+use crate::config::TelegramConfig;
+use crate::webhook::TelegramWebhookHandler;
+
+/// Telegram integration implementing TeiIntegration trait
+pub struct TelegramIntegration {
+    client: TelegramClient,
+}
+
+impl TelegramIntegration {
+    /// Create a new Telegram integration
+    pub fn new(config: TelegramConfig) -> Self {
+        Self {
+            client: TelegramClient::new(&config),
+        }
+    }
+
+    /// Extract Telegram chat ID from Rei's manifest
+    fn get_chat_id(&self, rei: &Rei) -> Result<i64, DomainError> {
+        manifest_chat_id(rei).ok_or_else(|| {
+            DomainError::Validation(format!(
+                "Rei '{}' does not have telegram_chat_id configured in manifest",
+                rei.name
+            ))
+        })
+    }
+}
+
+/// The `telegram_chat_id` of a Rei's manifest (a number or a string)
+pub(crate) fn manifest_chat_id(rei: &Rei) -> Option<i64> {
+    rei.manifest.get("telegram_chat_id").and_then(|v| {
+        v.as_i64()
+            .or_else(|| v.as_str().and_then(|s| s.parse().ok()))
+    })
+}
+
+#[async_trait]
+impl TeiIntegration for TelegramIntegration {
+    async fn read_messages(&self, rei: &Rei) -> Result<Vec<Message>, DomainError> {
+        let chat_id = self.get_chat_id(rei)?;
+        // The Bot API has no chat history; messages only arrive as updates
+        debug!(chat_id = %chat_id, rei_name = %rei.name, "Telegram has no message history to read");
+        Ok(vec![])
+    }
+
+    async fn post_message(&self, rei: &Rei, content: &str) -> Result<(), DomainError> {
+        let chat_id = self.get_chat_id(rei)?;
+        debug!(
+            chat_id = %chat_id,
+            rei_name = %rei.name,
+            content_len = %content.len(),
+            "Posting message to Telegram"
+        );
+
+        self.client
+            .send_message(chat_id, content, None)
+            .await
+            .map_err(|e| DomainError::ExternalService(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "telegram"
+    }
+
+    async fn handle_webhook(
+        &self,
+        payload: &[u8],
+    ) -> Result<Option<IntegrationEvent>, DomainError> {
+        let handler = TelegramWebhookHandler::new();
+        let update = handler.parse_payload(payload)?;
+        Ok(handler.parse_update(&update))
+    }
+
+    async fn health_check(&self) -> Result<bool, DomainError> {
+        match self.client.get_me().await {
+            Ok(_) => Ok(true),
+            Err(e) => {
+                warn!(error = %e, "Telegram health check failed");
+                Ok(false)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_manifest_chat_id() {
+        let rei = |manifest| Rei {
+            id: Uuid::new_v4(),
+            name: "Yui".to_string(),
+            role: "Assistant".to_string(),
+            avatar_url: None,
+            manifest,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        let group = rei(serde_json::json!({ "telegram_chat_id": -100123 }));
+        assert_eq!(manifest_chat_id(&group), Some(-100123));
+        let private = rei(serde_json::json!({ "telegram_chat_id": "42" }));
+        assert_eq!(manifest_chat_id(&private), Some(42));
+        assert_eq!(manifest_chat_id(&rei(serde_json::json!({}))), None);
+    }
+}
@@ -0,0 +1,30 @@
+//! Telegram Integration for Kaiba
+//!
+//! This crate provides Telegram platform integration for the Kaiba AI persona system.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use kaiba_integration_telegram::{TelegramBot, TelegramConfig, TelegramIntegration};
+//!
+//! let config = TelegramConfig::new("123456:bot-token");
+//! let integration = TelegramIntegration::new(config.clone());
+//!
+//! // Answer messages in the chats Reis are bound to
+//! let bot = Arc::new(TelegramBot::new(config, rei_repository));
+//! bot.poll_until(call_handler, shutdown.cancelled()).await?;
+//! ```
+
+mod bot;
+mod client;
+mod config;
+mod integration;
+mod types;
+mod webhook;
+
+pub use bot::TelegramBot;
+pub use client::{TelegramClient, TelegramError};
+pub use config::TelegramConfig;
+pub use integration::TelegramIntegration;
+pub use types::{TelegramChat, TelegramMessage, TelegramUpdate, TelegramUser};
+pub use webhook::{conversation_session, TelegramWebhookHandler};
@@ -0,0 +1,58 @@
+//! Bot API types (the fields Kaiba uses)
+
+use serde::{Deserialize, Serialize};
+
+/// An incoming update
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TelegramUpdate {
+    pub update_id: i64,
+    pub message: Option<TelegramMessage>,
+}
+
+/// A message
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TelegramMessage {
+    pub message_id: i64,
+    pub from: Option<TelegramUser>,
+    pub chat: TelegramChat,
+    /// Unix time
+    pub date: i64,
+    pub text: Option<String>,
+}
+
+/// A user or bot
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TelegramUser {
+    pub id: i64,
+    #[serde(default)]
+    pub is_bot: bool,
+    pub first_name: String,
+    pub username: Option<String>,
+}
+
+impl TelegramUser {
+    /// `@username` when set, the first name otherwise
+    pub fn display_name(&self) -> String {
+        match &self.username {
+            Some(username) => username.clone(),
+            None => self.first_name.clone(),
+        }
+    }
+}
+
+/// A private chat, group, supergroup or channel
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TelegramChat {
+    pub id: i64,
+    /// "private", "group", "supergroup" or "channel"
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub title: Option<String>,
+}
+
+impl TelegramChat {
+    /// A one-to-one chat with the bot
+    pub fn is_private(&self) -> bool {
+        self.kind == "private"
+    }
+}
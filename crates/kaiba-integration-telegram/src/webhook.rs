@@ -0,0 +1,202 @@
+//! Telegram update handling
+
+use kaiba::domain::errors::DomainError;
+use kaiba::ports::integration::{
+    IntegrationEvent, VerificationError, WebhookHeaders, WebhookVerifier,
+};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::types::TelegramUpdate;
+
+/// Header carrying the webhook secret
+const SECRET_HEADER: &str = "x-telegram-bot-api-secret-token";
+
+/// Telegram update handler for incoming events
+pub struct TelegramWebhookHandler {
+    /// Secret expected in `X-Telegram-Bot-Api-Secret-Token` (optional)
+    secret: Option<String>,
+}
+
+impl TelegramWebhookHandler {
+    /// Create a new update handler
+    pub fn new() -> Self {
+        Self { secret: None }
+    }
+
+    /// Create an update handler checking the webhook secret
+    pub fn with_secret(secret: impl Into<String>) -> Self {
+        Self {
+            secret: Some(secret.into()),
+        }
+    }
+
+    /// Parse a webhook body into an update
+    pub fn parse_payload(&self, payload: &[u8]) -> Result<TelegramUpdate, DomainError> {
+        serde_json::from_slice(payload)
+            .map_err(|e| DomainError::Validation(format!("Invalid Telegram update: {}", e)))
+    }
+
+    /// Turn an update into an IntegrationEvent
+    ///
+    /// Text messages from people become `MessageReceived`, in private chats
+    /// and groups alike, with the chat as channel. The metadata carries the
+    /// chat type, the message ID to reply to, and the chat's call session.
+    pub fn parse_update(&self, update: &TelegramUpdate) -> Option<IntegrationEvent> {
+        let Some(message) = &update.message else {
+            debug!(update_id = %update.update_id, "Ignoring Telegram update without message");
+            return None;
+        };
+        let text = message.text.as_deref()?.trim();
+        let from = message.from.as_ref()?;
+        // Ignore bots (including ourselves)
+        if from.is_bot || text.is_empty() {
+            return None;
+        }
+
+        Some(IntegrationEvent::MessageReceived {
+            channel_id: message.chat.id.to_string(),
+            user_id: from.id.to_string(),
+            user_name: from.display_name(),
+            content: text.to_string(),
+            metadata: serde_json::json!({
+                "chat_type": message.chat.kind,
+                "chat_title": message.chat.title,
+                "message_id": message.message_id,
+                "session_id": conversation_session(message.chat.id),
+            }),
+        })
+    }
+
+    /// Check the `X-Telegram-Bot-Api-Secret-Token` header
+    pub fn verify_secret(&self, header: Option<&str>) -> bool {
+        let Some(ref secret) = self.secret else {
+            warn!("Secret verification requested but no webhook secret configured");
+            return false;
+        };
+        header.is_some_and(|h| constant_time_eq(h.as_bytes(), secret.as_bytes()))
+    }
+}
+
+impl WebhookVerifier for TelegramWebhookHandler {
+    fn verify(&self, headers: &WebhookHeaders, _body: &[u8]) -> Result<(), VerificationError> {
+        // Without a secret every update is rejected, as by `verify_secret`
+        if self.secret.is_none() {
+            return Err(VerificationError::InvalidSignature);
+        }
+        let secret = headers.require(SECRET_HEADER)?;
+        if self.verify_secret(Some(secret)) {
+            Ok(())
+        } else {
+            Err(VerificationError::InvalidSignature)
+        }
+    }
+}
+
+impl Default for TelegramWebhookHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Call session of a chat, derived from its ID so it survives restarts
+pub fn conversation_session(chat_id: i64) -> Uuid {
+    Uuid::from_u64_pair(1, chat_id as u64)
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(chat_type: &str, chat_id: i64, is_bot: bool, text: &str) -> TelegramUpdate {
+        serde_json::from_value(serde_json::json!({
+            "update_id": 1,
+            "message": {
+                "message_id": 10,
+                "from": {
+                    "id": 123,
+                    "is_bot": is_bot,
+                    "first_name": "Test",
+                    "username": "testuser"
+                },
+                "chat": { "id": chat_id, "type": chat_type },
+                "date": 1700000000,
+                "text": text
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_private_and_group_messages() {
+        let handler = TelegramWebhookHandler::new();
+
+        for (chat_type, chat_id) in [("private", 123), ("supergroup", -100123)] {
+            let event = handler.parse_update(&update(chat_type, chat_id, false, "Hello"));
+            if let Some(IntegrationEvent::MessageReceived {
+                channel_id,
+                user_id,
+                user_name,
+                content,
+                metadata,
+            }) = event
+            {
+                assert_eq!(channel_id, chat_id.to_string());
+                assert_eq!(user_id, "123");
+                assert_eq!(user_name, "testuser");
+                assert_eq!(content, "Hello");
+                assert_eq!(metadata["chat_type"], chat_type);
+                assert_eq!(
+                    metadata["session_id"],
+                    conversation_session(chat_id).to_string()
+                );
+            } else {
+                panic!("Expected MessageReceived event");
+            }
+        }
+    }
+
+    #[test]
+    fn test_ignore_bots_and_empty_messages() {
+        let handler = TelegramWebhookHandler::new();
+        assert!(handler
+            .parse_update(&update("private", 123, true, "Bot message"))
+            .is_none());
+        assert!(handler
+            .parse_update(&update("private", 123, false, "   "))
+            .is_none());
+    }
+
+    #[test]
+    fn test_verify_secret() {
+        let handler = TelegramWebhookHandler::with_secret("s3cret");
+        assert!(handler.verify_secret(Some("s3cret")));
+        assert!(!handler.verify_secret(Some("wrong")));
+        assert!(!handler.verify_secret(None));
+        assert!(!TelegramWebhookHandler::new().verify_secret(Some("s3cret")));
+    }
+
+    #[test]
+    fn test_verify_headers() {
+        let handler = TelegramWebhookHandler::with_secret("s3cret");
+        let headers =
+            |secret: &str| WebhookHeaders::new().with("X-Telegram-Bot-Api-Secret-Token", secret);
+        assert_eq!(handler.verify(&headers("s3cret"), b"{}"), Ok(()));
+        assert_eq!(
+            handler.verify(&headers("wrong"), b"{}"),
+            Err(VerificationError::InvalidSignature)
+        );
+        assert_eq!(
+            handler.verify(&WebhookHeaders::new(), b"{}"),
+            Err(VerificationError::MissingHeader(SECRET_HEADER.to_string()))
+        );
+        assert_eq!(
+            TelegramWebhookHandler::new().verify(&headers("s3cret"), b"{}"),
+            Err(VerificationError::InvalidSignature)
+        );
+    }
+}
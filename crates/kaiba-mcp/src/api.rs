@@ -0,0 +1,161 @@
+//! Kaiba API Client (the endpoints behind the tools)
+
+use anyhow::{bail, Context, Result};
+use reqwest::{Client, RequestBuilder, Response};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Per-request timeout; calls wait on the Tei, so this is generous
+const TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Readable message from an error body: the `detail` of a problem+json
+/// response, or the body as-is from older servers
+fn error_detail(body: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|v| v.get("detail")?.as_str().map(str::to_string))
+        .unwrap_or_else(|| body.to_string())
+}
+
+/// API Client for Kaiba
+pub struct KaibaClient {
+    client: Client,
+    base_url: String,
+    api_key: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MemoryResponse {
+    pub id: String,
+    pub content: String,
+    pub memory_type: String,
+    pub importance: f32,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub similarity: Option<f32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreateMemoryRequest {
+    pub content: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub importance: Option<f32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchMemoriesRequest {
+    pub query: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PromptResponse {
+    pub system_prompt: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CallRequest {
+    pub tei_ids: Vec<Uuid>,
+    pub message: String,
+    pub context: CallContext,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CallContext {
+    pub include_memories: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CallResponse {
+    pub response: String,
+    #[serde(default)]
+    pub session_id: Option<Uuid>,
+}
+
+impl KaibaClient {
+    /// Create a new API client
+    pub fn new(base_url: &str, api_key: &str) -> Self {
+        let client = Client::builder()
+            .timeout(TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: api_key.to_string(),
+        }
+    }
+
+    /// Search a Rei's memories by meaning
+    pub async fn search_memories(
+        &self,
+        rei_id: &str,
+        request: &SearchMemoriesRequest,
+    ) -> Result<Vec<MemoryResponse>> {
+        let url = format!("{}/kaiba/rei/{}/memories/search", self.base_url, rei_id);
+        self.send(self.client.post(&url).json(request)).await
+    }
+
+    /// Add a memory
+    pub async fn add_memory(
+        &self,
+        rei_id: &str,
+        request: &CreateMemoryRequest,
+    ) -> Result<MemoryResponse> {
+        let url = format!("{}/kaiba/rei/{}/memories", self.base_url, rei_id);
+        self.send(self.client.post(&url).json(request)).await
+    }
+
+    /// Get a Rei's system prompt
+    pub async fn get_prompt(
+        &self,
+        rei_id: &str,
+        format: &str,
+        context: Option<&str>,
+    ) -> Result<PromptResponse> {
+        let mut url = format!(
+            "{}/kaiba/rei/{}/prompt?format={}&include_memories=true",
+            self.base_url,
+            rei_id,
+            urlencoding::encode(format)
+        );
+        if let Some(ctx) = context {
+            url.push_str(&format!("&context={}", urlencoding::encode(ctx)));
+        }
+        self.send(self.client.get(&url)).await
+    }
+
+    /// Call a Rei (LLM invocation with its memories)
+    pub async fn call_rei(&self, rei_id: &str, request: &CallRequest) -> Result<CallResponse> {
+        let url = format!("{}/kaiba/rei/{}/call", self.base_url, rei_id);
+        self.send(self.client.post(&url).json(request)).await
+    }
+
+    /// Send an authorized request and parse the JSON response
+    async fn send<T: DeserializeOwned>(&self, builder: RequestBuilder) -> Result<T> {
+        let resp: Response = builder
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .send()
+            .await
+            .context("Failed to connect to Kaiba API")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            bail!("API error ({}): {}", status, error_detail(&body));
+        }
+
+        resp.json().await.context("Failed to parse response")
+    }
+}
@@ -0,0 +1,47 @@
+//! Kaiba MCP server - Rei memory and calls as MCP tools
+//!
+//! Speaks the Model Context Protocol over stdio, so MCP clients (Claude
+//! Desktop, editors, agents) can search and add a Rei's memories, fetch its
+//! prompt and call it directly instead of shelling out to the REST API.
+//!
+//! Configured from the environment, like the CLI:
+//! - `KAIBA_BASE_URL` - server URL (default: the hosted server)
+//! - `KAIBA_API_KEY` - API key (required)
+//! - `KAIBA_REI_ID` - Rei the tools act on unless given a `rei_id`
+//!
+//! stdout carries the protocol; diagnostics go to stderr.
+
+mod api;
+mod server;
+mod tools;
+
+use anyhow::{Context, Result};
+
+use crate::api::KaibaClient;
+use crate::server::Server;
+use crate::tools::Tools;
+
+const DEFAULT_BASE_URL: &str = "https://kaiba-zlje.shuttle.app";
+
+/// Read a non-empty environment variable
+fn env(key: &str) -> Option<String> {
+    std::env::var(key)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let base_url = env("KAIBA_BASE_URL").unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+    let api_key = env("KAIBA_API_KEY").context("KAIBA_API_KEY is not set")?;
+    let default_rei = env("KAIBA_REI_ID");
+    if default_rei.is_none() {
+        eprintln!("kaiba-mcp: KAIBA_REI_ID is not set; tools need a rei_id argument");
+    }
+
+    let tools = Tools::new(KaibaClient::new(&base_url, &api_key), default_rei);
+    Server::new(tools)
+        .serve(tokio::io::stdin(), tokio::io::stdout())
+        .await
+}
@@ -0,0 +1,203 @@
+//! MCP over stdio: newline-delimited JSON-RPC 2.0
+//!
+//! Only the tool capability is offered. Tool failures (an API error, a
+//! missing Rei) are returned as `isError` results so the model can see and
+//! react to them; protocol mistakes get JSON-RPC errors.
+
+use anyhow::Result;
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+
+use crate::tools::Tools;
+
+/// Protocol revision answered when the client asks for another
+const PROTOCOL_VERSION: &str = "2024-11-05";
+
+// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+pub struct Server {
+    tools: Tools,
+}
+
+impl Server {
+    pub fn new(tools: Tools) -> Self {
+        Self { tools }
+    }
+
+    /// Answer messages from `input` on `output` until the input closes
+    pub async fn serve(
+        &self,
+        input: impl AsyncRead + Unpin,
+        mut output: impl AsyncWrite + Unpin,
+    ) -> Result<()> {
+        let mut lines = BufReader::new(input).lines();
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let response = match serde_json::from_str::<Value>(&line) {
+                Ok(message) => self.handle(message).await,
+                Err(e) => Some(error(Value::Null, PARSE_ERROR, &e.to_string())),
+            };
+            if let Some(response) = response {
+                let mut bytes = serde_json::to_vec(&response)?;
+                bytes.push(b'\n');
+                output.write_all(&bytes).await?;
+                output.flush().await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Answer one message; `None` for notifications
+    pub async fn handle(&self, message: Value) -> Option<Value> {
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            // Responses to requests we never send
+            return message
+                .get("id")
+                .is_none()
+                .then(|| error(Value::Null, INVALID_REQUEST, "Missing method"));
+        };
+        let id = message.get("id").cloned()?;
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+        Some(match method {
+            "initialize" => result(id, self.initialize(&params)),
+            "ping" => result(id, json!({})),
+            "tools/list" => result(id, json!({ "tools": self.tools.definitions() })),
+            "tools/call" => self.call_tool(id, params).await,
+            _ => error(id, METHOD_NOT_FOUND, &format!("Unknown method: {}", method)),
+        })
+    }
+
+    fn initialize(&self, params: &Value) -> Value {
+        let version = params
+            .get("protocolVersion")
+            .and_then(Value::as_str)
+            .unwrap_or(PROTOCOL_VERSION);
+        json!({
+            "protocolVersion": version,
+            "capabilities": { "tools": {} },
+            "serverInfo": {
+                "name": "kaiba",
+                "version": env!("CARGO_PKG_VERSION")
+            }
+        })
+    }
+
+    async fn call_tool(&self, id: Value, params: Value) -> Value {
+        let Some(name) = params.get("name").and_then(Value::as_str) else {
+            return error(id, INVALID_PARAMS, "Missing tool name");
+        };
+        if !self.tools.has(name) {
+            return error(id, INVALID_PARAMS, &format!("Unknown tool: {}", name));
+        }
+        let args = params.get("arguments").cloned().unwrap_or(Value::Null);
+
+        let (text, is_error) = match self.tools.call(name, args).await {
+            Ok(text) => (text, false),
+            Err(e) => (format!("{:#}", e), true),
+        };
+        result(
+            id,
+            json!({
+                "content": [{ "type": "text", "text": text }],
+                "isError": is_error
+            }),
+        )
+    }
+}
+
+fn result(id: Value, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+fn error(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::KaibaClient;
+
+    fn server(default_rei: Option<&str>) -> Server {
+        let client = KaibaClient::new("http://127.0.0.1:9", "test-key");
+        Server::new(Tools::new(client, default_rei.map(str::to_string)))
+    }
+
+    #[tokio::test]
+    async fn test_initialize_and_list_tools() {
+        let server = server(None);
+
+        let init = server
+            .handle(json!({
+                "jsonrpc": "2.0", "id": 1, "method": "initialize",
+                "params": { "protocolVersion": "2025-03-26", "capabilities": {} }
+            }))
+            .await
+            .unwrap();
+        assert_eq!(init["result"]["protocolVersion"], "2025-03-26");
+        assert!(init["result"]["capabilities"]["tools"].is_object());
+
+        let initialized = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+        assert!(server.handle(initialized).await.is_none());
+
+        let list = server
+            .handle(json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" }))
+            .await
+            .unwrap();
+        let names: Vec<&str> = list["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            ["search_memories", "add_memory", "get_prompt", "call_rei"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tool_errors() {
+        let server = server(None);
+
+        let unknown = server
+            .handle(json!({
+                "jsonrpc": "2.0", "id": 3, "method": "tools/call",
+                "params": { "name": "delete_everything" }
+            }))
+            .await
+            .unwrap();
+        assert_eq!(unknown["error"]["code"], INVALID_PARAMS);
+
+        // No Rei to act on: reported to the model, not as a protocol error
+        let no_rei = server
+            .handle(json!({
+                "jsonrpc": "2.0", "id": 4, "method": "tools/call",
+                "params": { "name": "search_memories", "arguments": { "query": "rust" } }
+            }))
+            .await
+            .unwrap();
+        assert_eq!(no_rei["result"]["isError"], true);
+        assert!(no_rei["result"]["content"][0]["text"]
+            .as_str()
+            .unwrap()
+            .contains("KAIBA_REI_ID"));
+
+        let method = server
+            .handle(json!({ "jsonrpc": "2.0", "id": 5, "method": "resources/list" }))
+            .await
+            .unwrap();
+        assert_eq!(method["error"]["code"], METHOD_NOT_FOUND);
+    }
+}
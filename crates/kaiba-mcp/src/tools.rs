@@ -0,0 +1,240 @@
+//! Kaiba tools offered to MCP clients
+//!
+//! Every tool takes an optional `rei_id`; without one the Rei from
+//! `KAIBA_REI_ID` is used.
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::api::{
+    CallContext, CallRequest, CreateMemoryRequest, KaibaClient, SearchMemoriesRequest,
+};
+
+/// Memory types accepted by `add_memory`
+const MEMORY_TYPES: [&str; 4] = ["learning", "fact", "expertise", "reflection"];
+
+/// Results returned by `search_memories` unless asked otherwise
+const DEFAULT_SEARCH_LIMIT: usize = 5;
+
+#[derive(Debug, Deserialize)]
+struct SearchArgs {
+    rei_id: Option<String>,
+    query: String,
+    limit: Option<usize>,
+    memory_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AddArgs {
+    rei_id: Option<String>,
+    content: String,
+    memory_type: Option<String>,
+    importance: Option<f32>,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PromptArgs {
+    rei_id: Option<String>,
+    format: Option<String>,
+    context: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CallArgs {
+    rei_id: Option<String>,
+    message: String,
+    session_id: Option<uuid::Uuid>,
+}
+
+/// The tools, bound to a Kaiba server
+pub struct Tools {
+    client: KaibaClient,
+    default_rei: Option<String>,
+}
+
+impl Tools {
+    pub fn new(client: KaibaClient, default_rei: Option<String>) -> Self {
+        Self {
+            client,
+            default_rei,
+        }
+    }
+
+    /// Tool definitions for `tools/list`
+    pub fn definitions(&self) -> Value {
+        let rei_id = json!({
+            "type": "string",
+            "description": "Rei ID (defaults to the configured Rei)"
+        });
+        json!([
+            {
+                "name": "search_memories",
+                "description": "Search the Rei's memories by meaning. Not all memories are in the prompt; use this to recall past conversations, projects and learnings.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "rei_id": rei_id,
+                        "query": { "type": "string", "description": "What to look for" },
+                        "limit": { "type": "integer", "minimum": 1, "description": "Maximum results (default 5)" },
+                        "memory_type": { "type": "string", "enum": MEMORY_TYPES }
+                    },
+                    "required": ["query"]
+                }
+            },
+            {
+                "name": "add_memory",
+                "description": "Save a new memory for the Rei.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "rei_id": rei_id,
+                        "content": { "type": "string", "description": "What to remember" },
+                        "memory_type": { "type": "string", "enum": MEMORY_TYPES },
+                        "importance": { "type": "number", "minimum": 0, "maximum": 1 },
+                        "tags": { "type": "array", "items": { "type": "string" } }
+                    },
+                    "required": ["content"]
+                }
+            },
+            {
+                "name": "get_prompt",
+                "description": "Fetch the Rei's system prompt (identity, state and relevant memories).",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "rei_id": rei_id,
+                        "format": { "type": "string", "enum": ["raw", "casting", "claude-code"], "description": "Prompt format (default raw)" },
+                        "context": { "type": "string", "description": "Query selecting the memories included" }
+                    }
+                }
+            },
+            {
+                "name": "call_rei",
+                "description": "Ask the Rei a question; it answers through its own Tei with its memories.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "rei_id": rei_id,
+                        "message": { "type": "string" },
+                        "session_id": { "type": "string", "format": "uuid", "description": "Continue an earlier conversation" }
+                    },
+                    "required": ["message"]
+                }
+            }
+        ])
+    }
+
+    /// Whether a tool of this name exists
+    pub fn has(&self, name: &str) -> bool {
+        matches!(
+            name,
+            "search_memories" | "add_memory" | "get_prompt" | "call_rei"
+        )
+    }
+
+    /// Run a tool, returning its text output
+    pub async fn call(&self, name: &str, args: Value) -> Result<String> {
+        match name {
+            "search_memories" => self.search_memories(parse(args)?).await,
+            "add_memory" => self.add_memory(parse(args)?).await,
+            "get_prompt" => self.get_prompt(parse(args)?).await,
+            "call_rei" => self.call_rei(parse(args)?).await,
+            _ => bail!("Unknown tool: {}", name),
+        }
+    }
+
+    async fn search_memories(&self, args: SearchArgs) -> Result<String> {
+        let rei_id = self.rei(args.rei_id)?;
+        let request = SearchMemoriesRequest {
+            query: args.query,
+            limit: Some(args.limit.unwrap_or(DEFAULT_SEARCH_LIMIT)),
+            memory_type: args.memory_type,
+        };
+        let memories = self.client.search_memories(&rei_id, &request).await?;
+        if memories.is_empty() {
+            return Ok("No matching memories.".to_string());
+        }
+
+        Ok(memories
+            .iter()
+            .map(|m| {
+                let mut line = format!("- [{}] {}", m.memory_type, m.content);
+                if !m.tags.is_empty() {
+                    line.push_str(&format!(" (tags: {})", m.tags.join(", ")));
+                }
+                if let Some(similarity) = m.similarity {
+                    line.push_str(&format!(" [similarity {:.2}]", similarity));
+                }
+                line
+            })
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
+    async fn add_memory(&self, args: AddArgs) -> Result<String> {
+        let rei_id = self.rei(args.rei_id)?;
+        if let Some(memory_type) = &args.memory_type {
+            if !MEMORY_TYPES.contains(&memory_type.as_str()) {
+                bail!(
+                    "Unknown memory type '{}' (expected one of: {})",
+                    memory_type,
+                    MEMORY_TYPES.join(", ")
+                );
+            }
+        }
+        let request = CreateMemoryRequest {
+            content: args.content,
+            memory_type: args.memory_type,
+            importance: args.importance,
+            tags: args.tags,
+        };
+        let memory = self.client.add_memory(&rei_id, &request).await?;
+        Ok(format!(
+            "Saved {} memory {} (importance {:.1})",
+            memory.memory_type, memory.id, memory.importance
+        ))
+    }
+
+    async fn get_prompt(&self, args: PromptArgs) -> Result<String> {
+        let rei_id = self.rei(args.rei_id)?;
+        let format = args.format.as_deref().unwrap_or("raw");
+        let prompt = self
+            .client
+            .get_prompt(&rei_id, format, args.context.as_deref())
+            .await?;
+        Ok(prompt.system_prompt)
+    }
+
+    async fn call_rei(&self, args: CallArgs) -> Result<String> {
+        let rei_id = self.rei(args.rei_id)?;
+        let request = CallRequest {
+            tei_ids: vec![],
+            message: args.message,
+            context: CallContext {
+                include_memories: true,
+            },
+            session_id: args.session_id,
+        };
+        let call = self.client.call_rei(&rei_id, &request).await?;
+        Ok(match call.session_id {
+            Some(session) => format!("{}\n\n(session_id: {})", call.response, session),
+            None => call.response,
+        })
+    }
+
+    /// The Rei a tool acts on
+    fn rei(&self, rei_id: Option<String>) -> Result<String> {
+        rei_id
+            .or_else(|| self.default_rei.clone())
+            .context("No rei_id given and KAIBA_REI_ID is not set")
+    }
+}
+
+/// Tool arguments from the `arguments` object (absent = no arguments)
+fn parse<T: serde::de::DeserializeOwned>(args: Value) -> Result<T> {
+    let args = if args.is_null() { json!({}) } else { args };
+    serde_json::from_value(args).context("Invalid arguments")
+}
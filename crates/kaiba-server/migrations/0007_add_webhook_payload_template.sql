@@ -0,0 +1,8 @@
+-- Add payload_template column to rei_webhooks
+-- Enables user-defined payload bodies (Jinja syntax) for arbitrary downstream APIs
+
+ALTER TABLE rei_webhooks
+ADD COLUMN IF NOT EXISTS payload_template TEXT;
+
+COMMENT ON COLUMN rei_webhooks.payload_template IS
+'Jinja template rendered with the webhook payload; takes precedence over payload_format when set';
@@ -0,0 +1,8 @@
+-- Add conditions column to rei_webhooks
+-- Data predicates (e.g., importance >= 0.8) evaluated before delivery
+
+ALTER TABLE rei_webhooks
+ADD COLUMN IF NOT EXISTS conditions JSONB NOT NULL DEFAULT '[]';
+
+COMMENT ON COLUMN rei_webhooks.conditions IS
+'Array of {field, op, value} predicates; all must match payload data for delivery';
@@ -0,0 +1,15 @@
+-- Dead-letter handling for webhooks
+-- Deliveries that exhaust retries move to status 'dead'.
+-- Webhooks can auto-disable after N consecutive dead-lettered deliveries.
+
+ALTER TABLE rei_webhooks
+ADD COLUMN IF NOT EXISTS consecutive_failures INTEGER NOT NULL DEFAULT 0;
+
+ALTER TABLE rei_webhooks
+ADD COLUMN IF NOT EXISTS max_consecutive_failures INTEGER;
+
+COMMENT ON COLUMN rei_webhooks.consecutive_failures IS 'Dead-lettered deliveries in a row since the last success';
+COMMENT ON COLUMN rei_webhooks.max_consecutive_failures IS 'Auto-disable threshold for consecutive failures (NULL = never)';
+
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_webhook_status
+    ON webhook_deliveries (webhook_id, status, created_at DESC);
@@ -0,0 +1,21 @@
+-- Inbound Webhook Sources
+-- Lets external services (GitHub, RSS bridges, generic JSON) poke a Rei
+
+CREATE TABLE IF NOT EXISTS rei_inbound_sources (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    rei_id UUID NOT NULL REFERENCES reis(id) ON DELETE CASCADE,
+    source TEXT NOT NULL,  -- github, rss, or any generic name
+    secret TEXT NOT NULL,  -- HMAC-SHA256 verification secret / shared token
+    action TEXT NOT NULL DEFAULT 'memory',  -- memory, learn, digest
+    memory_type TEXT NOT NULL DEFAULT 'fact',
+    enabled BOOLEAN NOT NULL DEFAULT true,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE(rei_id, source)
+);
+
+CREATE INDEX IF NOT EXISTS idx_rei_inbound_sources_rei_id ON rei_inbound_sources(rei_id);
+
+CREATE TRIGGER update_rei_inbound_sources_updated_at
+    BEFORE UPDATE ON rei_inbound_sources
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,7 @@
+-- Add format_options column to rei_webhooks
+-- Format-specific settings, e.g. GitHub issue label mapping, assignees and milestone
+
+ALTER TABLE rei_webhooks
+ADD COLUMN IF NOT EXISTS format_options JSONB NOT NULL DEFAULT '{}';
+
+COMMENT ON COLUMN rei_webhooks.format_options IS 'Format-specific options (e.g., github_issue: labels per event, assignees, milestone)';
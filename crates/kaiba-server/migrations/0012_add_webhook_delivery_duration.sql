@@ -0,0 +1,7 @@
+-- Delivery latency tracking
+-- Response time of the last attempt, used for per-webhook latency percentiles
+
+ALTER TABLE webhook_deliveries
+ADD COLUMN IF NOT EXISTS duration_ms INTEGER;
+
+COMMENT ON COLUMN webhook_deliveries.duration_ms IS 'Response time of the last delivery attempt in milliseconds';
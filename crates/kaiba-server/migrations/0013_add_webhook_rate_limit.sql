@@ -0,0 +1,8 @@
+-- Per-webhook rate limiting
+-- Deliveries beyond the limit wait for a free slot instead of hitting
+-- downstream API throttles.
+
+ALTER TABLE rei_webhooks
+ADD COLUMN IF NOT EXISTS rate_limit_per_minute INTEGER;
+
+COMMENT ON COLUMN rei_webhooks.rate_limit_per_minute IS 'Maximum deliveries per minute (NULL = unlimited)';
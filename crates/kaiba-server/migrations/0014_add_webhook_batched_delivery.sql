@@ -0,0 +1,22 @@
+-- Batched webhook delivery
+-- Webhooks in 'batched' mode queue events and deliver them together
+-- every batch_interval_minutes, reducing noise from high-frequency events.
+
+ALTER TABLE rei_webhooks
+ADD COLUMN IF NOT EXISTS delivery_mode TEXT NOT NULL DEFAULT 'immediate';
+
+ALTER TABLE rei_webhooks
+ADD COLUMN IF NOT EXISTS batch_interval_minutes INTEGER NOT NULL DEFAULT 15;
+
+COMMENT ON COLUMN rei_webhooks.delivery_mode IS 'immediate (one delivery per event) or batched';
+COMMENT ON COLUMN rei_webhooks.batch_interval_minutes IS 'Minutes between batched deliveries';
+
+CREATE TABLE IF NOT EXISTS webhook_batch_events (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    webhook_id UUID NOT NULL REFERENCES rei_webhooks(id) ON DELETE CASCADE,
+    payload JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_batch_events_webhook_created
+    ON webhook_batch_events (webhook_id, created_at);
@@ -0,0 +1,9 @@
+-- Global webhooks
+-- Webhooks without a rei_id receive events from every Rei
+-- (e.g., ops alerting), managed at /kaiba/webhooks.
+
+ALTER TABLE rei_webhooks
+ALTER COLUMN rei_id DROP NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_rei_webhooks_global
+    ON rei_webhooks(created_at DESC) WHERE rei_id IS NULL;
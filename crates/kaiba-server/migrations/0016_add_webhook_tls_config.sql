@@ -0,0 +1,8 @@
+-- Per-webhook TLS configuration
+-- Custom root CA and client certificate (mTLS) for receivers behind
+-- private CAs. Values may be ${secret:name} references.
+
+ALTER TABLE rei_webhooks
+ADD COLUMN IF NOT EXISTS tls_config JSONB;
+
+COMMENT ON COLUMN rei_webhooks.tls_config IS 'TLS settings: ca_cert_pem, client_cert_pem, client_key_pem (NULL = system defaults)';
@@ -0,0 +1,18 @@
+-- Event outbox
+-- ReiEvents are written here (in the same transaction as the domain change
+-- where there is one) and relayed to subscribers by a background worker,
+-- so events survive a process restart. Rows are deleted once dispatched.
+
+CREATE TABLE IF NOT EXISTS rei_event_outbox (
+    id UUID PRIMARY KEY,  -- ReiEvent ID (doubles as webhook delivery_id)
+    rei_id UUID NOT NULL,
+    event_type TEXT NOT NULL,
+    event JSONB NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    available_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),  -- Lease / retry backoff
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_rei_event_outbox_available
+    ON rei_event_outbox (available_at, created_at);
@@ -0,0 +1,11 @@
+-- Custom event registry
+-- Custom events (custom:<name>) are declared here before webhooks can
+-- subscribe to them; event data is validated against the schema.
+
+CREATE TABLE IF NOT EXISTS webhook_event_definitions (
+    name TEXT PRIMARY KEY,
+    description TEXT,
+    schema JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,10 @@
+-- Conversation sessions for calls
+-- Calls sharing a session_id form one conversation; earlier turns are fed back as context
+
+ALTER TABLE call_logs
+ADD COLUMN IF NOT EXISTS session_id UUID;
+
+CREATE INDEX IF NOT EXISTS idx_call_logs_session ON call_logs(session_id, created_at)
+WHERE session_id IS NOT NULL;
+
+COMMENT ON COLUMN call_logs.session_id IS 'Conversation session this call belongs to (NULL for one-off calls)';
@@ -0,0 +1,16 @@
+-- Scoped API Keys
+-- Per-caller keys with a scope and optional Rei restriction, alongside the master KAIBA_API_KEY
+
+CREATE TABLE IF NOT EXISTS api_keys (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name TEXT NOT NULL,
+    key_prefix TEXT NOT NULL,       -- first characters, for recognizing a key in listings
+    key_hash TEXT NOT NULL UNIQUE,  -- SHA-256 (hex); the key itself is never stored
+    scope TEXT NOT NULL DEFAULT 'read',  -- read, memory_write, admin
+    rei_ids UUID[] NOT NULL DEFAULT '{}',  -- empty = all Reis
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ
+);
+
+COMMENT ON COLUMN api_keys.rei_ids IS 'Reis this key may access (empty for all)';
@@ -0,0 +1,28 @@
+-- Users and Ownership
+-- Reis, Teis and global webhooks belong to a user; API keys bound to a user
+-- only see that user's resources. NULL owner = shared (operator-managed).
+
+CREATE TABLE IF NOT EXISTS users (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name TEXT NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE reis
+ADD COLUMN IF NOT EXISTS owner_id UUID REFERENCES users(id) ON DELETE SET NULL;
+
+ALTER TABLE teis
+ADD COLUMN IF NOT EXISTS owner_id UUID REFERENCES users(id) ON DELETE SET NULL;
+
+ALTER TABLE rei_webhooks
+ADD COLUMN IF NOT EXISTS owner_id UUID REFERENCES users(id) ON DELETE SET NULL;
+
+ALTER TABLE api_keys
+ADD COLUMN IF NOT EXISTS user_id UUID REFERENCES users(id) ON DELETE CASCADE;
+
+CREATE INDEX IF NOT EXISTS idx_reis_owner ON reis(owner_id);
+CREATE INDEX IF NOT EXISTS idx_teis_owner ON teis(owner_id);
+CREATE INDEX IF NOT EXISTS idx_rei_webhooks_owner ON rei_webhooks(owner_id);
+
+COMMENT ON COLUMN rei_webhooks.owner_id IS 'Owner of a global webhook; it only receives events from the owner''s Reis';
+COMMENT ON COLUMN api_keys.user_id IS 'User the key acts as (NULL for operator keys that see everything)';
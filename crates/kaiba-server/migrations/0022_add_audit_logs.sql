@@ -0,0 +1,27 @@
+-- Audit Log
+-- One row per successful create/update/delete: who (API key), what (route,
+-- entity) and when. Written by the audit middleware after the handler runs.
+
+CREATE TABLE IF NOT EXISTS audit_logs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    actor TEXT NOT NULL,
+    api_key_id UUID,
+    method TEXT NOT NULL,
+    route TEXT NOT NULL,
+    path TEXT NOT NULL,
+    entity_type TEXT NOT NULL,
+    entity_id UUID,
+    status INTEGER NOT NULL,
+    request_id TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_logs_created ON audit_logs(created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_audit_logs_entity ON audit_logs(entity_type, entity_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_audit_logs_api_key ON audit_logs(api_key_id, created_at DESC)
+WHERE api_key_id IS NOT NULL;
+
+COMMENT ON COLUMN audit_logs.actor IS 'API key name, "master key", or "anonymous" when auth is disabled';
+COMMENT ON COLUMN audit_logs.api_key_id IS 'Scoped key that made the change (NULL for the master key); kept after the key is deleted';
+COMMENT ON COLUMN audit_logs.route IS 'Route template, e.g. /kaiba/rei/:id';
+COMMENT ON COLUMN audit_logs.entity_type IS 'Last fixed route segment, e.g. rei, memories, webhooks';
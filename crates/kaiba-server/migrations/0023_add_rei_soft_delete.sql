@@ -0,0 +1,7 @@
+-- Soft delete for Reis
+-- Deleted Reis keep their rows (and everything cascading from them) until the
+-- purge job removes them after the retention window, so they can be restored.
+
+ALTER TABLE reis ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_reis_deleted_at ON reis(deleted_at) WHERE deleted_at IS NOT NULL;
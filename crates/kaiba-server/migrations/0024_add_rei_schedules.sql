@@ -0,0 +1,19 @@
+-- Per-Rei Schedules
+-- A Rei with a schedule gets its autonomous cycle at the times of a cron
+-- expression in its own timezone instead of every LEARNING_INTERVAL_SECS.
+
+CREATE TABLE IF NOT EXISTS rei_schedules (
+    rei_id UUID PRIMARY KEY REFERENCES reis(id) ON DELETE CASCADE,
+    cron TEXT NOT NULL,                    -- 5-field cron expression
+    timezone TEXT NOT NULL DEFAULT 'UTC',  -- IANA timezone name
+    next_run_at TIMESTAMPTZ,               -- NULL = no future run
+    last_run_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_rei_schedules_next_run_at ON rei_schedules(next_run_at);
+
+CREATE TRIGGER update_rei_schedules_updated_at
+    BEFORE UPDATE ON rei_schedules
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,18 @@
+-- Learning Session History
+-- One row per completed self-learning session, whether started by the
+-- scheduler, the API or an inbound webhook.
+
+CREATE TABLE IF NOT EXISTS learning_sessions (
+    id UUID PRIMARY KEY,
+    rei_id UUID NOT NULL REFERENCES reis(id) ON DELETE CASCADE,
+    queries JSONB NOT NULL DEFAULT '[]',  -- search queries generated from the manifest
+    searches_completed INTEGER NOT NULL DEFAULT 0,
+    memories_stored INTEGER NOT NULL DEFAULT 0,
+    errors JSONB NOT NULL DEFAULT '[]',   -- one message per failed query
+    energy_spent INTEGER NOT NULL DEFAULT 0,
+    started_at TIMESTAMPTZ NOT NULL,
+    finished_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_learning_sessions_rei_started
+    ON learning_sessions(rei_id, started_at DESC);
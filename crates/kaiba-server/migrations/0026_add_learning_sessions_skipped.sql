@@ -0,0 +1,6 @@
+-- Skipped Searches
+-- Queries whose topic was already well covered in MemoryKai are skipped
+-- without a web search.
+
+ALTER TABLE learning_sessions
+ADD COLUMN IF NOT EXISTS searches_skipped INTEGER NOT NULL DEFAULT 0;
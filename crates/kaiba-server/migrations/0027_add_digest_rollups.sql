@@ -0,0 +1,10 @@
+-- Hierarchical Digests
+-- Daily digests are rolled up into weekly digests, and weekly into monthly.
+-- Each column is the end of the last period rolled up for that tier.
+
+ALTER TABLE rei_states
+ADD COLUMN IF NOT EXISTS last_weekly_digest_at TIMESTAMPTZ,
+ADD COLUMN IF NOT EXISTS last_monthly_digest_at TIMESTAMPTZ;
+
+COMMENT ON COLUMN rei_states.last_weekly_digest_at IS 'End of the last week rolled up into a weekly digest';
+COMMENT ON COLUMN rei_states.last_monthly_digest_at IS 'End of the last month rolled up into a monthly digest';
@@ -0,0 +1,33 @@
+-- Per-Rei Decision Policies and Decision Log
+-- A policy overrides the thresholds of the autonomous Learn/Digest/Rest
+-- decision and picks its strategy: fixed rules or an LLM that weighs mood,
+-- energy, pending memories and recent outcomes. Every decision is logged with
+-- its reason.
+
+CREATE TABLE IF NOT EXISTS rei_decision_policies (
+    rei_id UUID PRIMARY KEY REFERENCES reis(id) ON DELETE CASCADE,
+    strategy TEXT NOT NULL DEFAULT 'rules',  -- rules, llm
+    min_energy_learn INTEGER NOT NULL DEFAULT 50,
+    min_energy_digest INTEGER NOT NULL DEFAULT 60,
+    min_tokens_action INTEGER NOT NULL DEFAULT 500,
+    memories_for_digest INTEGER NOT NULL DEFAULT 5,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TRIGGER update_rei_decision_policies_updated_at
+    BEFORE UPDATE ON rei_decision_policies
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE IF NOT EXISTS rei_decisions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    rei_id UUID NOT NULL REFERENCES reis(id) ON DELETE CASCADE,
+    action TEXT NOT NULL,    -- learn, digest, rest
+    reason TEXT NOT NULL,
+    strategy TEXT NOT NULL,  -- strategy that made the decision
+    context JSONB NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_rei_decisions_rei_created
+    ON rei_decisions(rei_id, created_at DESC);
@@ -0,0 +1,15 @@
+-- Daily Learning Budgets
+-- What self-learning consumed for a Rei on budget_day (UTC). Counters restart
+-- on the first session of a new day. The caps come from the manifest's
+-- learning_budget, or the server defaults.
+
+ALTER TABLE rei_states
+ADD COLUMN IF NOT EXISTS budget_day DATE,
+ADD COLUMN IF NOT EXISTS searches_today INTEGER NOT NULL DEFAULT 0,
+ADD COLUMN IF NOT EXISTS embeddings_today INTEGER NOT NULL DEFAULT 0,
+ADD COLUMN IF NOT EXISTS tokens_today INTEGER NOT NULL DEFAULT 0;
+
+COMMENT ON COLUMN rei_states.budget_day IS 'UTC day the *_today learning counters belong to';
+COMMENT ON COLUMN rei_states.searches_today IS 'Web searches made by self-learning on budget_day';
+COMMENT ON COLUMN rei_states.embeddings_today IS 'Embedding calls made by self-learning on budget_day';
+COMMENT ON COLUMN rei_states.tokens_today IS 'Gemini tokens reported by self-learning searches on budget_day';
@@ -0,0 +1,15 @@
+-- Learning Sources
+-- Items a Rei already learned from a source other than web search (e.g. RSS
+-- or Atom feed entries), so each is stored only once.
+
+CREATE TABLE IF NOT EXISTS learning_source_items (
+    rei_id UUID NOT NULL REFERENCES reis(id) ON DELETE CASCADE,
+    source TEXT NOT NULL,    -- rss
+    item_key TEXT NOT NULL,  -- entry ID within the source
+    url TEXT,
+    learned_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (rei_id, source, item_key)
+);
+
+ALTER TABLE learning_sessions
+ADD COLUMN IF NOT EXISTS items_learned INTEGER NOT NULL DEFAULT 0;
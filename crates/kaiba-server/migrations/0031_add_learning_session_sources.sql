@@ -0,0 +1,6 @@
+-- Learning Session Sources
+-- URLs cited by the memories a session stored, after the manifest's
+-- excluded_domains were dropped.
+
+ALTER TABLE learning_sessions
+ADD COLUMN IF NOT EXISTS sources JSONB NOT NULL DEFAULT '[]';
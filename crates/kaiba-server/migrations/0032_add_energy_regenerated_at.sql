@@ -0,0 +1,9 @@
+-- Elapsed-Time Energy Regeneration
+-- Energy is regenerated for the hours since energy_regenerated_at rather than
+-- a fixed amount per cycle. Reis that were never regenerated start from
+-- last_active_at.
+
+ALTER TABLE rei_states
+ADD COLUMN IF NOT EXISTS energy_regenerated_at TIMESTAMPTZ;
+
+COMMENT ON COLUMN rei_states.energy_regenerated_at IS 'Time up to which energy regeneration has been credited';
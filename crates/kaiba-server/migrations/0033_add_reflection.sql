@@ -0,0 +1,15 @@
+-- Reflection Action
+-- A new autonomous action: the Rei writes a short journal entry about what
+-- it has remembered since its last reflection, stored as a reflection memory.
+-- Policies set how much energy it needs and how often it comes due.
+
+ALTER TABLE rei_states
+ADD COLUMN IF NOT EXISTS last_reflection_at TIMESTAMPTZ;
+
+COMMENT ON COLUMN rei_states.last_reflection_at IS 'Start of the last reflection; later memories have not been reflected on';
+
+ALTER TABLE rei_decision_policies
+ADD COLUMN IF NOT EXISTS min_energy_reflect INTEGER NOT NULL DEFAULT 30,
+ADD COLUMN IF NOT EXISTS reflection_interval_hours INTEGER NOT NULL DEFAULT 24;
+
+COMMENT ON COLUMN rei_decisions.action IS 'learn, digest, reflect or rest';
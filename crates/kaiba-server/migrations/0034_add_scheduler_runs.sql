@@ -0,0 +1,19 @@
+-- Scheduler Run History
+-- Every autonomous cycle, due-schedule run and /kaiba/trigger call is
+-- recorded with what each Rei did, so a loop that keeps failing (or never
+-- finishes) shows up. Runs without finished_at were interrupted.
+
+CREATE TABLE IF NOT EXISTS scheduler_runs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    kind TEXT NOT NULL,        -- interval, schedule, trigger
+    dry_run BOOLEAN NOT NULL DEFAULT FALSE,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    finished_at TIMESTAMPTZ,
+    reis_processed INTEGER NOT NULL DEFAULT 0,
+    errors INTEGER NOT NULL DEFAULT 0,
+    error TEXT,                -- failure of the run as a whole
+    results JSONB NOT NULL DEFAULT '[]'
+);
+
+CREATE INDEX IF NOT EXISTS idx_scheduler_runs_started
+    ON scheduler_runs(started_at DESC);
@@ -0,0 +1,14 @@
+-- Learning Rate Limits
+-- When Gemini rate-limits a search, the remaining queries of the session are
+-- deferred instead of failed: they are kept on the Rei's state, searched first
+-- by the next session, and web search pauses until learning_resume_at.
+
+ALTER TABLE rei_states
+ADD COLUMN IF NOT EXISTS deferred_queries JSONB NOT NULL DEFAULT '[]',
+ADD COLUMN IF NOT EXISTS learning_resume_at TIMESTAMPTZ;
+
+COMMENT ON COLUMN rei_states.learning_resume_at IS 'Web search is rate limited until then';
+
+ALTER TABLE learning_sessions
+ADD COLUMN IF NOT EXISTS rate_limited INTEGER NOT NULL DEFAULT 0,
+ADD COLUMN IF NOT EXISTS queries_deferred JSONB NOT NULL DEFAULT '[]';
@@ -0,0 +1,7 @@
+-- Autonomy Pause
+-- A Rei can be frozen (vacation, debugging) without deleting its schedule,
+-- policy or webhooks: the scheduler and /kaiba/trigger skip it while
+-- autonomy_enabled is false.
+
+ALTER TABLE rei_states
+ADD COLUMN IF NOT EXISTS autonomy_enabled BOOLEAN NOT NULL DEFAULT TRUE;
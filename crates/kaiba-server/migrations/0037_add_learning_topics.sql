@@ -0,0 +1,12 @@
+-- Learning Topic Coverage
+-- When each manifest topic (search query) was last taken up by a learning
+-- session, so sessions rotate through all of a Rei's interests instead of
+-- always searching the first few.
+
+CREATE TABLE IF NOT EXISTS learning_topics (
+    rei_id UUID NOT NULL REFERENCES reis(id) ON DELETE CASCADE,
+    query TEXT NOT NULL,
+    last_queried_at TIMESTAMPTZ NOT NULL,
+    times_queried INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (rei_id, query)
+);
@@ -0,0 +1,23 @@
+-- Scheduled Tasks
+-- One-off instructions a Rei carries out at a given time ("summarize the
+-- week's news on Friday at 17:00"). The task runner sends each due task
+-- through the call pipeline and delivers the result as a task_completed
+-- event.
+
+CREATE TABLE IF NOT EXISTS rei_tasks (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    rei_id UUID NOT NULL REFERENCES reis(id) ON DELETE CASCADE,
+    instruction TEXT NOT NULL,
+    include_memories BOOLEAN NOT NULL DEFAULT TRUE,
+    due_at TIMESTAMPTZ NOT NULL,
+    -- pending, running, completed or failed
+    status TEXT NOT NULL DEFAULT 'pending',
+    result TEXT,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    started_at TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_rei_tasks_due ON rei_tasks (status, due_at);
+CREATE INDEX IF NOT EXISTS idx_rei_tasks_rei ON rei_tasks (rei_id, created_at DESC);
@@ -0,0 +1,13 @@
+-- Failure Streaks
+-- Consecutive failures per Rei and subject ('digest' or 'search:<query>').
+-- A streak reaching the threshold gets a post-mortem reflection memory and
+-- backs the Rei off the subject; a success deletes the row.
+
+CREATE TABLE IF NOT EXISTS rei_failures (
+    rei_id UUID NOT NULL REFERENCES reis(id) ON DELETE CASCADE,
+    subject TEXT NOT NULL,
+    failures INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    last_failed_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (rei_id, subject)
+);
@@ -0,0 +1,6 @@
+-- Learning Mode
+-- Whether a session learned from web search or, without a Gemini key, from
+-- the Rei's own memories.
+
+ALTER TABLE learning_sessions
+    ADD COLUMN IF NOT EXISTS mode TEXT NOT NULL DEFAULT 'web';
@@ -0,0 +1,17 @@
+-- Per-Rei Integration Switches
+-- Integrations (Discord, Telegram, email, ...) answer for every Rei bound to
+-- them in its manifest. A row here turns one integration off (or back on)
+-- for one Rei without touching the binding; no row = enabled.
+
+CREATE TABLE IF NOT EXISTS rei_integrations (
+    rei_id UUID NOT NULL REFERENCES reis(id) ON DELETE CASCADE,
+    integration TEXT NOT NULL,             -- integration name, e.g. 'discord'
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (rei_id, integration)
+);
+
+CREATE TRIGGER update_rei_integrations_updated_at
+    BEFORE UPDATE ON rei_integrations
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
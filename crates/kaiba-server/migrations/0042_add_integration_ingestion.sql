@@ -0,0 +1,7 @@
+-- Integration Ingestion
+-- Reis can opt in, per integration, to having the conversations it reads
+-- summarized into fact and learning memories. ingested_until is the newest
+-- message already summarized; later runs only take messages after it.
+
+ALTER TABLE rei_integrations ADD COLUMN IF NOT EXISTS ingest BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE rei_integrations ADD COLUMN IF NOT EXISTS ingested_until TIMESTAMPTZ;
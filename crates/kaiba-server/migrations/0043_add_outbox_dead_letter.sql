@@ -0,0 +1,11 @@
+-- Outbox Dead Letters
+-- Events that cannot be decoded, or that keep failing after the maximum
+-- number of attempts, get dead_at set. The relay no longer claims them;
+-- they stay in the table with their last_error for inspection.
+
+ALTER TABLE rei_event_outbox ADD COLUMN IF NOT EXISTS dead_at TIMESTAMPTZ;
+
+DROP INDEX IF EXISTS idx_rei_event_outbox_available;
+CREATE INDEX IF NOT EXISTS idx_rei_event_outbox_available
+    ON rei_event_outbox (available_at, created_at)
+    WHERE dead_at IS NULL;
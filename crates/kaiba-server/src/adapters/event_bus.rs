@@ -0,0 +1,163 @@
+//! In-Process Event Bus Implementation
+//!
+//! Dispatches ReiEvents to registered subscribers on the tokio runtime.
+//! Each subscriber handles each event in its own task, so a slow
+//! subscriber (e.g., webhook retries) never blocks the producer.
+//!
+//! With an outbox configured, producers `emit` events into the outbox and
+//! the outbox relay `dispatch`es them, so events survive a restart.
+
+use std::sync::{Arc, RwLock};
+
+use sqlx::{Postgres, Transaction};
+
+use kaiba::{DomainError, ReiEvent, ReiEventBus, ReiEventSubscriber};
+
+use crate::adapters::PgEventOutbox;
+use crate::supervisor::TaskSupervisor;
+
+/// In-process implementation of ReiEventBus
+#[derive(Default)]
+pub struct InProcessEventBus {
+    subscribers: RwLock<Vec<Arc<dyn ReiEventSubscriber>>>,
+    outbox: Option<Arc<PgEventOutbox>>,
+    tasks: Option<Arc<TaskSupervisor>>,
+}
+
+impl InProcessEventBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a subscriber (builder style, for startup wiring)
+    pub fn with_subscriber(self, subscriber: Arc<dyn ReiEventSubscriber>) -> Self {
+        self.subscribe(subscriber);
+        self
+    }
+
+    /// Route emitted events through a durable outbox
+    pub fn with_outbox(mut self, outbox: Arc<PgEventOutbox>) -> Self {
+        self.outbox = Some(outbox);
+        self
+    }
+
+    /// Run published deliveries under a supervisor, so shutdown waits for them
+    pub fn with_tasks(mut self, tasks: Arc<TaskSupervisor>) -> Self {
+        self.tasks = Some(tasks);
+        self
+    }
+
+    /// Emit an event: into the outbox if configured, otherwise publish directly
+    pub async fn emit(&self, event: ReiEvent) {
+        if let Some(outbox) = &self.outbox {
+            match outbox.enqueue(&event).await {
+                Ok(()) => return,
+                Err(e) => tracing::warn!(
+                    "⚠️  Outbox write failed for {}, publishing directly: {}",
+                    event.event_type,
+                    e
+                ),
+            }
+        }
+        self.publish(event);
+    }
+
+    /// Stage an event in the caller's transaction
+    ///
+    /// Without an outbox the event is handed back, to be published after commit.
+    pub async fn stage_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        event: ReiEvent,
+    ) -> Result<Option<ReiEvent>, DomainError> {
+        if self.outbox.is_none() {
+            return Ok(Some(event));
+        }
+        PgEventOutbox::enqueue_in(tx, &event).await?;
+        Ok(None)
+    }
+
+    /// Deliver an event to all interested subscribers and wait for them
+    ///
+    /// Fails if any subscriber fails, so the outbox relay can retry.
+    pub async fn dispatch(&self, event: &ReiEvent) -> Result<(), DomainError> {
+        let event = Arc::new(event.clone());
+        let handles: Vec<_> = self
+            .interested_subscribers(&event)
+            .into_iter()
+            .map(|subscriber| {
+                let event = event.clone();
+                tokio::spawn(async move {
+                    subscriber
+                        .handle(&event)
+                        .await
+                        .map_err(|e| format!("{}: {}", subscriber.name(), e))
+                })
+            })
+            .collect();
+
+        let mut errors = Vec::new();
+        for handle in handles {
+            match handle.await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => errors.push(e),
+                Err(e) => errors.push(e.to_string()),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(DomainError::ExternalService(errors.join("; ")))
+        }
+    }
+
+    fn interested_subscribers(&self, event: &ReiEvent) -> Vec<Arc<dyn ReiEventSubscriber>> {
+        self.subscribers
+            .read()
+            .expect("Event bus lock poisoned")
+            .iter()
+            .filter(|s| s.interested_in(event))
+            .cloned()
+            .collect()
+    }
+}
+
+impl ReiEventBus for InProcessEventBus {
+    fn subscribe(&self, subscriber: Arc<dyn ReiEventSubscriber>) {
+        tracing::info!("📡 Event subscriber registered: {}", subscriber.name());
+        self.subscribers
+            .write()
+            .expect("Event bus lock poisoned")
+            .push(subscriber);
+    }
+
+    fn publish(&self, event: ReiEvent) {
+        let subscribers = self.interested_subscribers(&event);
+
+        if subscribers.is_empty() {
+            return;
+        }
+
+        let event = Arc::new(event);
+        for subscriber in subscribers {
+            let event = event.clone();
+            let delivery = async move {
+                if let Err(e) = subscriber.handle(&event).await {
+                    tracing::warn!(
+                        "⚠️  Event subscriber {} failed on {}: {}",
+                        subscriber.name(),
+                        event.event_type,
+                        e
+                    );
+                }
+            };
+            match &self.tasks {
+                Some(tasks) => tasks.spawn(delivery),
+                None => {
+                    tokio::spawn(delivery);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,153 @@
+//! Discord Embed Formatter
+//!
+//! Transforms WebhookPayload into Discord Webhook API format (embeds).
+//! Used for posting Rei events to a Discord channel.
+
+use kaiba::{WebhookEventType, WebhookPayload};
+
+/// Embed colors (decimal RGB, as required by Discord)
+const COLOR_SUCCESS: u32 = 0x2ECC71;
+const COLOR_WARNING: u32 = 0xF1C40F;
+const COLOR_FAILURE: u32 = 0xE74C3C;
+const COLOR_INFO: u32 = 0x3498DB;
+
+/// Discord limits: 25 fields per embed, 1024 chars per field value
+const MAX_FIELDS: usize = 25;
+const MAX_FIELD_VALUE_LEN: usize = 1024;
+
+/// Format a webhook payload as a Discord webhook message with a single embed
+///
+/// - title: derived from the event type (prefixed with payload.data.rei_name if present)
+/// - color: derived from payload.data.status / payload.data.errors
+/// - fields: one per top-level key in payload.data (rei_name excluded)
+pub fn format_as_discord_embed(payload: &WebhookPayload) -> serde_json::Value {
+    let rei_name = payload.data.get("rei_name").and_then(|v| v.as_str());
+
+    let title = match rei_name {
+        Some(name) => format!("[{}] {}", name, event_title(&payload.event)),
+        None => event_title(&payload.event),
+    };
+
+    let fields: Vec<serde_json::Value> = payload
+        .data
+        .as_object()
+        .map(|obj| {
+            obj.iter()
+                .filter(|(key, _)| key.as_str() != "rei_name")
+                .take(MAX_FIELDS)
+                .map(|(key, value)| {
+                    let inline = !value.is_array() && !value.is_object();
+                    serde_json::json!({
+                        "name": key,
+                        "value": field_value(value),
+                        "inline": inline
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    serde_json::json!({
+        "username": "Kaiba",
+        "embeds": [{
+            "title": title,
+            "description": format!("Event `{}` for Rei `{}`", payload.event, payload.rei_id),
+            "color": status_color(&payload.data),
+            "fields": fields,
+            "timestamp": payload.timestamp.to_rfc3339(),
+            "footer": {
+                "text": format!("delivery {}", payload.delivery_id)
+            }
+        }]
+    })
+}
+
+/// Human-readable title for an event type
+fn event_title(event: &WebhookEventType) -> String {
+    match event {
+        WebhookEventType::ResponseCompleted => "Response Completed".to_string(),
+        WebhookEventType::StateChanged => "State Changed".to_string(),
+        WebhookEventType::MemoryAdded => "Memory Added".to_string(),
+        WebhookEventType::SearchCompleted => "Search Completed".to_string(),
+        WebhookEventType::LearningCompleted => "Learning Report".to_string(),
+        WebhookEventType::DigestCompleted => "Digest Report".to_string(),
+        WebhookEventType::TaskCompleted => "Task Completed".to_string(),
+        WebhookEventType::Custom(name) => format!("Custom Event: {}", name),
+        WebhookEventType::All => "Event".to_string(),
+    }
+}
+
+/// Pick an embed color from the payload data
+///
+/// An explicit `status` field wins; otherwise a non-empty `errors` array
+/// marks the event as a partial failure.
+fn status_color(data: &serde_json::Value) -> u32 {
+    match data.get("status").and_then(|v| v.as_str()) {
+        Some("success" | "ok" | "completed") => return COLOR_SUCCESS,
+        Some("failed" | "error") => return COLOR_FAILURE,
+        Some(_) => return COLOR_WARNING,
+        None => {}
+    }
+
+    match data.get("errors").and_then(|v| v.as_array()) {
+        Some(errors) if !errors.is_empty() => COLOR_WARNING,
+        Some(_) => COLOR_SUCCESS,
+        None => COLOR_INFO,
+    }
+}
+
+/// Render a JSON value as an embed field value
+fn field_value(value: &serde_json::Value) -> String {
+    let text = match value {
+        serde_json::Value::String(s) if s.is_empty() => "-".to_string(),
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Null => "-".to_string(),
+        serde_json::Value::Array(arr) if arr.is_empty() => "None".to_string(),
+        serde_json::Value::Array(arr) => arr
+            .iter()
+            .map(|v| match v.as_str() {
+                Some(s) => format!("- {}", s),
+                None => format!("- {}", v),
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        other => other.to_string(),
+    };
+
+    if text.chars().count() > MAX_FIELD_VALUE_LEN {
+        let truncated: String = text.chars().take(MAX_FIELD_VALUE_LEN - 3).collect();
+        format!("{}...", truncated)
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_format_learning_completed() {
+        let payload = WebhookPayload::new(
+            WebhookEventType::LearningCompleted,
+            Uuid::new_v4(),
+            serde_json::json!({
+                "rei_name": "TestRei",
+                "queries_generated": ["Rust async", "WebAssembly"],
+                "searches_completed": 2,
+                "errors": ["timeout"]
+            }),
+        );
+
+        let result = format_as_discord_embed(&payload);
+        let embed = &result["embeds"][0];
+
+        assert_eq!(embed["title"], "[TestRei] Learning Report");
+        assert_eq!(embed["color"], COLOR_WARNING);
+
+        let fields = embed["fields"].as_array().unwrap();
+        assert_eq!(fields.len(), 3);
+        assert!(fields.iter().all(|f| f["name"] != "rei_name"));
+    }
+}
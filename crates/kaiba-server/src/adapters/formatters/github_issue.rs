@@ -2,8 +2,59 @@
 //!
 //! Transforms WebhookPayload into GitHub Issue API format.
 //! Used for posting learning reports to a GitHub repository.
+//!
+//! Labels, assignees and milestone come from the webhook's `format_options`:
+//!
+//! ```json
+//! {
+//!   "labels": { "learning_completed": ["learning-report"], "default": ["kaiba"] },
+//!   "assignees": ["octocat"],
+//!   "milestone": 3
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use kaiba::{WebhookEventType, WebhookPayload};
+
+/// Labels applied when no mapping matches the event
+const DEFAULT_LABELS: [&str; 2] = ["learning-report", "auto-generated"];
+
+/// GitHub Issue options read from a webhook's format_options
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GitHubIssueOptions {
+    /// Labels per event type ("learning_completed", "custom:deploy", ...), with "default" as fallback
+    #[serde(default)]
+    pub labels: HashMap<String, Vec<String>>,
+    /// GitHub usernames to assign
+    #[serde(default)]
+    pub assignees: Vec<String>,
+    /// Milestone number
+    pub milestone: Option<u64>,
+}
 
-use kaiba::WebhookPayload;
+impl GitHubIssueOptions {
+    /// Parse options, rejecting unknown keys
+    pub fn parse(options: &serde_json::Value) -> Result<Self, String> {
+        if options.is_null() {
+            return Ok(Self::default());
+        }
+        serde_json::from_value(options.clone())
+            .map_err(|e| format!("Invalid github_issue format_options: {e}"))
+    }
+
+    /// Labels for an event type
+    fn labels_for(&self, event: &WebhookEventType) -> Vec<String> {
+        self.labels
+            .get(&event.to_string())
+            .or_else(|| self.labels.get("default"))
+            .cloned()
+            .unwrap_or_else(|| DEFAULT_LABELS.iter().map(|l| l.to_string()).collect())
+    }
+}
 
 /// Format a webhook payload as a GitHub Issue
 ///
@@ -13,7 +64,10 @@ use kaiba::WebhookPayload;
 /// - searches_completed: usize
 /// - memories_stored: usize
 /// - errors: Vec<String>
-pub fn format_as_github_issue(payload: &WebhookPayload) -> serde_json::Value {
+pub fn format_as_github_issue(
+    payload: &WebhookPayload,
+    options: &GitHubIssueOptions,
+) -> serde_json::Value {
     let rei_name = payload
         .data
         .get("rei_name")
@@ -105,17 +159,25 @@ pub fn format_as_github_issue(payload: &WebhookPayload) -> serde_json::Value {
     );
 
     // GitHub Issue API format
-    serde_json::json!({
+    let mut issue = serde_json::json!({
         "title": title,
         "body": body,
-        "labels": ["learning-report", "auto-generated"]
-    })
+        "labels": options.labels_for(&payload.event)
+    });
+
+    if !options.assignees.is_empty() {
+        issue["assignees"] = serde_json::json!(options.assignees);
+    }
+    if let Some(milestone) = options.milestone {
+        issue["milestone"] = serde_json::json!(milestone);
+    }
+
+    issue
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use kaiba::WebhookEventType;
     use uuid::Uuid;
 
     #[test]
@@ -132,7 +194,7 @@ mod tests {
             }),
         );
 
-        let result = format_as_github_issue(&payload);
+        let result = format_as_github_issue(&payload, &GitHubIssueOptions::default());
 
         assert!(result.get("title").is_some());
         assert!(result.get("body").is_some());
@@ -141,5 +203,40 @@ mod tests {
         let title = result["title"].as_str().unwrap();
         assert!(title.contains("TestRei"));
         assert!(title.contains("Learning Report"));
+        assert_eq!(result["labels"][0], "learning-report");
+        assert!(result.get("assignees").is_none());
+    }
+
+    #[test]
+    fn test_label_mapping_and_triage_fields() {
+        let options = GitHubIssueOptions::parse(&serde_json::json!({
+            "labels": {
+                "digest_completed": ["digest"],
+                "default": ["kaiba"]
+            },
+            "assignees": ["octocat"],
+            "milestone": 3
+        }))
+        .unwrap();
+
+        let digest = WebhookPayload::new(
+            WebhookEventType::DigestCompleted,
+            Uuid::new_v4(),
+            serde_json::json!({}),
+        );
+        let result = format_as_github_issue(&digest, &options);
+        assert_eq!(result["labels"], serde_json::json!(["digest"]));
+        assert_eq!(result["assignees"], serde_json::json!(["octocat"]));
+        assert_eq!(result["milestone"], 3);
+
+        let learning = WebhookPayload::new(
+            WebhookEventType::LearningCompleted,
+            Uuid::new_v4(),
+            serde_json::json!({}),
+        );
+        let result = format_as_github_issue(&learning, &options);
+        assert_eq!(result["labels"], serde_json::json!(["kaiba"]));
+
+        assert!(GitHubIssueOptions::parse(&serde_json::json!({ "label": [] })).is_err());
     }
 }
@@ -0,0 +1,103 @@
+//! Jira Issue Formatter
+//!
+//! Transforms WebhookPayload into a Jira create-issue body
+//! (`POST /rest/api/2/issue`, description in wiki markup).
+//!
+//! Requires `format_options`:
+//!
+//! ```json
+//! { "project_key": "KAI", "issue_type": "Task", "labels": ["kaiba"], "priority": "Medium" }
+//! ```
+
+use serde::Deserialize;
+
+use kaiba::WebhookPayload;
+
+use super::report::IssueReport;
+
+/// Jira Issue options read from a webhook's format_options
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JiraIssueOptions {
+    /// Project key (e.g., "KAI")
+    pub project_key: String,
+    /// Issue type name (default: "Task")
+    #[serde(default = "default_issue_type")]
+    pub issue_type: String,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Priority name (e.g., "High")
+    pub priority: Option<String>,
+}
+
+fn default_issue_type() -> String {
+    "Task".to_string()
+}
+
+impl JiraIssueOptions {
+    pub fn parse(options: &serde_json::Value) -> Result<Self, String> {
+        serde_json::from_value(options.clone())
+            .map_err(|e| format!("Invalid jira_issue format_options: {e}"))
+    }
+}
+
+/// Format a webhook payload as a Jira create-issue request
+pub fn format_as_jira_issue(
+    payload: &WebhookPayload,
+    options: &JiraIssueOptions,
+) -> serde_json::Value {
+    let report = IssueReport::from_payload(payload);
+
+    let mut fields = serde_json::json!({
+        "project": { "key": options.project_key },
+        "summary": report.title,
+        "description": report.to_jira_wiki(),
+        "issuetype": { "name": options.issue_type },
+        "labels": options.labels,
+    });
+
+    if let Some(priority) = &options.priority {
+        fields["priority"] = serde_json::json!({ "name": priority });
+    }
+
+    serde_json::json!({ "fields": fields })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaiba::WebhookEventType;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_format_digest_completed() {
+        let payload = WebhookPayload::new(
+            WebhookEventType::DigestCompleted,
+            Uuid::new_v4(),
+            serde_json::json!({
+                "memories_processed": 5,
+                "expertise_created": 1,
+                "summary": "Rust async patterns"
+            }),
+        );
+        let options =
+            JiraIssueOptions::parse(&serde_json::json!({ "project_key": "KAI" })).unwrap();
+
+        let result = format_as_jira_issue(&payload, &options);
+        let fields = &result["fields"];
+
+        assert_eq!(fields["project"]["key"], "KAI");
+        assert_eq!(fields["issuetype"]["name"], "Task");
+        assert!(fields["summary"]
+            .as_str()
+            .unwrap()
+            .contains("Digest Report"));
+        assert!(fields["description"]
+            .as_str()
+            .unwrap()
+            .contains("h3. Summary\nRust async patterns"));
+        assert!(fields.get("priority").is_none());
+
+        assert!(JiraIssueOptions::parse(&serde_json::json!({})).is_err());
+    }
+}
@@ -0,0 +1,123 @@
+//! Linear Issue Formatter
+//!
+//! Transforms WebhookPayload into a Linear GraphQL `issueCreate` mutation
+//! (`POST https://api.linear.app/graphql`, description in Markdown).
+//!
+//! Requires `format_options`:
+//!
+//! ```json
+//! { "team_id": "...", "label_ids": ["..."], "project_id": "...", "priority": 2 }
+//! ```
+
+use serde::Deserialize;
+
+use kaiba::WebhookPayload;
+
+use super::report::IssueReport;
+
+const ISSUE_CREATE_MUTATION: &str = "mutation IssueCreate($input: IssueCreateInput!) { issueCreate(input: $input) { success issue { id identifier url } } }";
+
+/// Linear Issue options read from a webhook's format_options
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LinearIssueOptions {
+    pub team_id: String,
+    #[serde(default)]
+    pub label_ids: Vec<String>,
+    pub project_id: Option<String>,
+    pub assignee_id: Option<String>,
+    /// 0 = none, 1 = urgent, 2 = high, 3 = medium, 4 = low
+    pub priority: Option<u8>,
+}
+
+impl LinearIssueOptions {
+    pub fn parse(options: &serde_json::Value) -> Result<Self, String> {
+        let parsed: Self = serde_json::from_value(options.clone())
+            .map_err(|e| format!("Invalid linear_issue format_options: {e}"))?;
+
+        if parsed.priority.is_some_and(|p| p > 4) {
+            return Err("Invalid linear_issue format_options: priority must be 0-4".to_string());
+        }
+        Ok(parsed)
+    }
+}
+
+/// Format a webhook payload as a Linear issueCreate request
+pub fn format_as_linear_issue(
+    payload: &WebhookPayload,
+    options: &LinearIssueOptions,
+) -> serde_json::Value {
+    let report = IssueReport::from_payload(payload);
+
+    let mut input = serde_json::json!({
+        "teamId": options.team_id,
+        "title": report.title,
+        "description": report.to_markdown(),
+    });
+
+    if !options.label_ids.is_empty() {
+        input["labelIds"] = serde_json::json!(options.label_ids);
+    }
+    if let Some(project_id) = &options.project_id {
+        input["projectId"] = serde_json::json!(project_id);
+    }
+    if let Some(assignee_id) = &options.assignee_id {
+        input["assigneeId"] = serde_json::json!(assignee_id);
+    }
+    if let Some(priority) = options.priority {
+        input["priority"] = serde_json::json!(priority);
+    }
+
+    serde_json::json!({
+        "query": ISSUE_CREATE_MUTATION,
+        "variables": { "input": input }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaiba::WebhookEventType;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_format_learning_completed() {
+        let payload = WebhookPayload::new(
+            WebhookEventType::LearningCompleted,
+            Uuid::new_v4(),
+            serde_json::json!({
+                "rei_name": "TestRei",
+                "queries_generated": ["Rust async"],
+                "searches_completed": 1,
+                "memories_stored": 1,
+                "errors": []
+            }),
+        );
+        let options = LinearIssueOptions::parse(&serde_json::json!({
+            "team_id": "team-1",
+            "priority": 3
+        }))
+        .unwrap();
+
+        let result = format_as_linear_issue(&payload, &options);
+        let input = &result["variables"]["input"];
+
+        assert!(result["query"].as_str().unwrap().contains("issueCreate"));
+        assert_eq!(input["teamId"], "team-1");
+        assert_eq!(input["priority"], 3);
+        assert!(input["title"]
+            .as_str()
+            .unwrap()
+            .starts_with("[TestRei] Learning Report"));
+        assert!(input["description"]
+            .as_str()
+            .unwrap()
+            .contains("### Queries Searched\n- Rust async"));
+        assert!(input.get("labelIds").is_none());
+
+        assert!(
+            LinearIssueOptions::parse(&serde_json::json!({ "team_id": "t", "priority": 9 }))
+                .is_err()
+        );
+    }
+}
@@ -2,16 +2,66 @@
 //!
 //! Transform WebhookPayload into integration-specific formats.
 
+mod discord_embed;
 mod github_issue;
+mod jira_issue;
+mod linear_issue;
+mod report;
+mod template;
 
-pub use github_issue::format_as_github_issue;
+pub use discord_embed::format_as_discord_embed;
+pub use github_issue::{format_as_github_issue, GitHubIssueOptions};
+pub use jira_issue::{format_as_jira_issue, JiraIssueOptions};
+pub use linear_issue::{format_as_linear_issue, LinearIssueOptions};
+pub use template::{render_template, validate_template};
 
-use kaiba::WebhookPayload;
+use kaiba::{ReiWebhook, WebhookPayload};
 
-/// Format a webhook payload based on the specified format type
-pub fn format_payload(format: Option<&str>, payload: &WebhookPayload) -> serde_json::Value {
-    match format {
-        Some("github_issue") => format_as_github_issue(payload),
+/// Format a webhook payload based on the specified format type and options
+pub fn format_payload(
+    format: Option<&str>,
+    options: &serde_json::Value,
+    payload: &WebhookPayload,
+) -> Result<serde_json::Value, String> {
+    Ok(match format {
+        Some("github_issue") => {
+            format_as_github_issue(payload, &GitHubIssueOptions::parse(options)?)
+        }
+        Some("jira_issue") => format_as_jira_issue(payload, &JiraIssueOptions::parse(options)?),
+        Some("linear_issue") => {
+            format_as_linear_issue(payload, &LinearIssueOptions::parse(options)?)
+        }
+        Some("discord_embed") => format_as_discord_embed(payload),
         _ => serde_json::to_value(payload).unwrap_or_default(),
+    })
+}
+
+/// Format a webhook payload using the webhook's configuration
+///
+/// A payload_template takes precedence over payload_format.
+pub fn format_for_webhook(
+    webhook: &ReiWebhook,
+    payload: &WebhookPayload,
+) -> Result<serde_json::Value, String> {
+    match webhook.payload_template.as_deref() {
+        Some(template) => render_template(template, payload),
+        None => format_payload(
+            webhook.payload_format.as_deref(),
+            &webhook.format_options,
+            payload,
+        ),
+    }
+}
+
+/// Check that format_options are valid for the given format
+pub fn validate_format_options(
+    format: Option<&str>,
+    options: &serde_json::Value,
+) -> Result<(), String> {
+    match format {
+        Some("github_issue") => GitHubIssueOptions::parse(options).map(|_| ()),
+        Some("jira_issue") => JiraIssueOptions::parse(options).map(|_| ()),
+        Some("linear_issue") => LinearIssueOptions::parse(options).map(|_| ()),
+        _ => Ok(()),
     }
 }
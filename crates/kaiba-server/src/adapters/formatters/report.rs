@@ -0,0 +1,177 @@
+//! Issue Report Builder
+//!
+//! Turns a WebhookPayload into a title and sections shared by the
+//! issue-tracker formatters (Jira, Linear). Each formatter renders the
+//! sections in its own markup.
+
+use kaiba::{WebhookEventType, WebhookPayload};
+
+/// A titled block of report lines
+pub struct ReportSection {
+    pub heading: String,
+    pub lines: Vec<String>,
+}
+
+/// Tracker-agnostic issue content
+pub struct IssueReport {
+    pub title: String,
+    pub sections: Vec<ReportSection>,
+}
+
+impl IssueReport {
+    /// Build a report for learning/digest events (other events list their data)
+    pub fn from_payload(payload: &WebhookPayload) -> Self {
+        let rei_name = payload
+            .data
+            .get("rei_name")
+            .and_then(|v| v.as_str())
+            .map(String::from)
+            .unwrap_or_else(|| format!("Rei {}", &payload.rei_id.to_string()[..8]));
+        let date = payload.timestamp.format("%Y-%m-%d");
+
+        let (kind, sections) = match payload.event {
+            WebhookEventType::LearningCompleted => ("Learning Report", learning_sections(payload)),
+            WebhookEventType::DigestCompleted => ("Digest Report", digest_sections(payload)),
+            _ => ("Event", data_sections(payload)),
+        };
+
+        let mut sections = sections;
+        sections.push(ReportSection {
+            heading: "Details".to_string(),
+            lines: vec![
+                format!("Event: {}", payload.event),
+                format!("Rei: {}", payload.rei_id),
+                format!(
+                    "Time: {}",
+                    payload.timestamp.format("%Y-%m-%d %H:%M:%S UTC")
+                ),
+            ],
+        });
+
+        Self {
+            title: format!("[{}] {} - {}", rei_name, kind, date),
+            sections,
+        }
+    }
+
+    /// Render as Markdown (Linear, GitHub)
+    pub fn to_markdown(&self) -> String {
+        self.render(|heading| format!("### {}", heading))
+    }
+
+    /// Render as Jira wiki markup
+    pub fn to_jira_wiki(&self) -> String {
+        self.render(|heading| format!("h3. {}", heading))
+    }
+
+    fn render(&self, heading: impl Fn(&str) -> String) -> String {
+        let mut out = self
+            .sections
+            .iter()
+            .map(|section| {
+                format!(
+                    "{}\n{}",
+                    heading(&section.heading),
+                    section.lines.join("\n")
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        out.push_str("\n\n----\n_Auto-generated by Kaiba_");
+        out
+    }
+}
+
+fn learning_sections(payload: &WebhookPayload) -> Vec<ReportSection> {
+    vec![
+        ReportSection {
+            heading: "Queries Searched".to_string(),
+            lines: bullet_list(payload.data.get("queries_generated"), "(no queries)"),
+        },
+        ReportSection {
+            heading: "Results".to_string(),
+            lines: vec![
+                format!(
+                    "- Searches Completed: {}",
+                    number(payload, "searches_completed")
+                ),
+                format!("- Memories Stored: {}", number(payload, "memories_stored")),
+            ],
+        },
+        ReportSection {
+            heading: "Errors".to_string(),
+            lines: bullet_list(payload.data.get("errors"), "None"),
+        },
+    ]
+}
+
+fn digest_sections(payload: &WebhookPayload) -> Vec<ReportSection> {
+    let summary = payload
+        .data
+        .get("summary")
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .unwrap_or("(no summary)");
+
+    vec![
+        ReportSection {
+            heading: "Summary".to_string(),
+            lines: vec![summary.to_string()],
+        },
+        ReportSection {
+            heading: "Results".to_string(),
+            lines: vec![
+                format!(
+                    "- Memories Processed: {}",
+                    number(payload, "memories_processed")
+                ),
+                format!(
+                    "- Expertise Created: {}",
+                    number(payload, "expertise_created")
+                ),
+            ],
+        },
+    ]
+}
+
+fn data_sections(payload: &WebhookPayload) -> Vec<ReportSection> {
+    let lines = payload
+        .data
+        .as_object()
+        .map(|obj| {
+            obj.iter()
+                .map(|(key, value)| match value.as_str() {
+                    Some(s) => format!("- {}: {}", key, s),
+                    None => format!("- {}: {}", key, value),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    vec![ReportSection {
+        heading: "Data".to_string(),
+        lines,
+    }]
+}
+
+fn bullet_list(value: Option<&serde_json::Value>, empty: &str) -> Vec<String> {
+    let items: Vec<String> = value
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str())
+                .map(|s| format!("- {}", s))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if items.is_empty() {
+        vec![format!("- {}", empty)]
+    } else {
+        items
+    }
+}
+
+fn number(payload: &WebhookPayload, key: &str) -> u64 {
+    payload.data.get(key).and_then(|v| v.as_u64()).unwrap_or(0)
+}
@@ -0,0 +1,78 @@
+//! Template Formatter
+//!
+//! Renders a user-defined Jinja template with the WebhookPayload.
+//! Lets a webhook target arbitrary JSON APIs (Notion, Linear, Jira, ...)
+//! without a dedicated Rust formatter.
+//!
+//! Template context:
+//! - event: String (e.g., "learning_completed", "custom:deploy")
+//! - rei_id: String
+//! - delivery_id: String
+//! - timestamp: String (RFC 3339)
+//! - data: event-specific object
+//!
+//! The rendered output must be valid JSON. Use the `tojson` filter to
+//! embed strings safely, e.g. `{"title": {{ data.rei_name | tojson }}}`.
+
+use minijinja::Environment;
+
+use kaiba::WebhookPayload;
+
+/// Check that a template compiles
+pub fn validate_template(template: &str) -> Result<(), String> {
+    let env = Environment::new();
+    env.template_from_str(template)
+        .map(|_| ())
+        .map_err(|e| format!("Invalid payload template: {e}"))
+}
+
+/// Render a template with the payload and parse the result as JSON
+pub fn render_template(
+    template: &str,
+    payload: &WebhookPayload,
+) -> Result<serde_json::Value, String> {
+    let context = serde_json::json!({
+        "event": payload.event.to_string(),
+        "rei_id": payload.rei_id.to_string(),
+        "delivery_id": payload.delivery_id.to_string(),
+        "timestamp": payload.timestamp.to_rfc3339(),
+        "data": payload.data,
+    });
+
+    let env = Environment::new();
+    let rendered = env
+        .render_str(template, context)
+        .map_err(|e| format!("Failed to render payload template: {e}"))?;
+
+    serde_json::from_str(&rendered)
+        .map_err(|e| format!("Payload template did not render valid JSON: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaiba::WebhookEventType;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_render_template() {
+        let payload = WebhookPayload::new(
+            WebhookEventType::LearningCompleted,
+            Uuid::new_v4(),
+            serde_json::json!({
+                "rei_name": "Test \"Rei\"",
+                "memories_stored": 2
+            }),
+        );
+
+        let template = r#"{"title": {{ data.rei_name | tojson }}, "count": {{ data.memories_stored }}, "kind": "{{ event }}"}"#;
+        let result = render_template(template, &payload).unwrap();
+
+        assert_eq!(result["title"], "Test \"Rei\"");
+        assert_eq!(result["count"], 2);
+        assert_eq!(result["kind"], "learning_completed");
+
+        assert!(validate_template("{{ data.rei_name").is_err());
+        assert!(render_template("not json", &payload).is_err());
+    }
+}
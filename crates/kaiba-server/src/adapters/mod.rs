@@ -2,10 +2,15 @@
 //!
 //! Implementations of domain ports for external systems.
 
+pub mod event_bus;
 pub mod formatters;
 pub mod postgres;
+pub mod rate_limiter;
+pub mod secrets;
 pub mod webhook;
 
 // Re-exports
-pub use postgres::{PgReiRepository, PgReiWebhookRepository, PgTeiRepository};
+pub use event_bus::InProcessEventBus;
+pub use postgres::{PgEventOutbox, PgReiRepository, PgReiWebhookRepository, PgTeiRepository};
+pub use secrets::WebhookSecrets;
 pub use webhook::HttpWebhook;
@@ -1,9 +1,11 @@
 //! PostgreSQL Repository Implementations
 
+mod outbox;
 mod rei_repository;
 mod tei_repository;
 mod webhook_repository;
 
+pub use outbox::PgEventOutbox;
 pub use rei_repository::PgReiRepository;
 pub use tei_repository::PgTeiRepository;
 pub use webhook_repository::PgReiWebhookRepository;
@@ -0,0 +1,163 @@
+//! PostgreSQL Event Outbox
+//!
+//! Durable queue between event producers and the event bus. Producers
+//! write events with `enqueue` (or `enqueue_in` inside their own
+//! transaction); the outbox relay claims them, dispatches to subscribers,
+//! and deletes them once handled. Delivery is at-least-once.
+//!
+//! Events that cannot be decoded, or that fail `MAX_ATTEMPTS` times, are
+//! dead-lettered: left in the table with `dead_at` set and never claimed
+//! again.
+
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use kaiba::{DomainError, ReiEvent};
+
+/// How long a claimed event stays invisible to other relays
+const LEASE_SECS: f64 = 300.0;
+
+/// Upper bound on retry backoff
+const MAX_BACKOFF_SECS: f64 = 3600.0;
+
+/// Dispatch attempts before an event is dead-lettered
+const MAX_ATTEMPTS: i32 = 10;
+
+/// PostgreSQL-backed event outbox
+pub struct PgEventOutbox {
+    pool: PgPool,
+}
+
+impl PgEventOutbox {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Write an event as part of an existing transaction
+    pub async fn enqueue_in(
+        tx: &mut Transaction<'_, Postgres>,
+        event: &ReiEvent,
+    ) -> Result<(), DomainError> {
+        let event_json =
+            serde_json::to_value(event).map_err(|e| DomainError::Repository(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO rei_event_outbox (id, rei_id, event_type, event)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (id) DO NOTHING
+            "#,
+        )
+        .bind(event.id)
+        .bind(event.rei_id)
+        .bind(event.event_type.to_string())
+        .bind(&event_json)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| DomainError::Repository(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Write an event in its own transaction
+    pub async fn enqueue(&self, event: &ReiEvent) -> Result<(), DomainError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::Repository(e.to_string()))?;
+        Self::enqueue_in(&mut tx, event).await?;
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::Repository(e.to_string()))
+    }
+
+    /// Claim up to `limit` available events (oldest first) under a lease
+    ///
+    /// Rows that no longer decode as a ReiEvent are dead-lettered.
+    pub async fn claim(&self, limit: i64) -> Result<Vec<ReiEvent>, DomainError> {
+        let rows: Vec<(Uuid, serde_json::Value)> = sqlx::query_as(
+            r#"
+            UPDATE rei_event_outbox
+            SET attempts = attempts + 1,
+                available_at = NOW() + make_interval(secs => $2)
+            WHERE id IN (
+                SELECT id FROM rei_event_outbox
+                WHERE dead_at IS NULL AND available_at <= NOW()
+                ORDER BY created_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, event
+            "#,
+        )
+        .bind(limit)
+        .bind(LEASE_SECS)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(e.to_string()))?;
+
+        let mut events = Vec::with_capacity(rows.len());
+        for (id, event) in rows {
+            match serde_json::from_value::<ReiEvent>(event) {
+                Ok(event) => events.push(event),
+                Err(e) => {
+                    tracing::error!(
+                        "💀 Outbox event {} cannot be decoded, dead-lettering: {}",
+                        id,
+                        e
+                    );
+                    self.dead_letter(id, &format!("undecodable event: {}", e))
+                        .await?;
+                }
+            }
+        }
+        events.sort_by_key(|e| e.occurred_at);
+        Ok(events)
+    }
+
+    /// Remove a dispatched event
+    pub async fn complete(&self, id: Uuid) -> Result<(), DomainError> {
+        sqlx::query("DELETE FROM rei_event_outbox WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Repository(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Record a failed dispatch and back off exponentially before the next attempt
+    ///
+    /// Returns true if this was the last attempt and the event is now dead-lettered.
+    pub async fn fail(&self, id: Uuid, error: &str) -> Result<bool, DomainError> {
+        let dead: Option<bool> = sqlx::query_scalar(
+            r#"
+            UPDATE rei_event_outbox
+            SET last_error = $2,
+                available_at = NOW() + make_interval(secs => LEAST($3, 30 * POWER(2, attempts))),
+                dead_at = CASE WHEN attempts >= $4 THEN NOW() END
+            WHERE id = $1
+            RETURNING dead_at IS NOT NULL
+            "#,
+        )
+        .bind(id)
+        .bind(error)
+        .bind(MAX_BACKOFF_SECS)
+        .bind(MAX_ATTEMPTS)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(e.to_string()))?;
+        Ok(dead.unwrap_or(false))
+    }
+
+    /// Stop relaying an event, keeping it for inspection
+    async fn dead_letter(&self, id: Uuid, error: &str) -> Result<(), DomainError> {
+        sqlx::query("UPDATE rei_event_outbox SET dead_at = NOW(), last_error = $2 WHERE id = $1")
+            .bind(id)
+            .bind(error)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Repository(e.to_string()))?;
+        Ok(())
+    }
+}
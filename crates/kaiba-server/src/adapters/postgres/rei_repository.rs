@@ -1,11 +1,14 @@
 //! PostgreSQL implementation of ReiRepository
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use sqlx::PgPool;
 use uuid::Uuid;
 
 use kaiba::{DomainError, Rei, ReiRepository, ReiState};
 
+use crate::services::ownership::InsertOwned;
+
 /// PostgreSQL implementation of ReiRepository
 pub struct PgReiRepository {
     pool: PgPool,
@@ -56,6 +59,7 @@ struct ReiStateRow {
     energy_regen_per_hour: i32,
     last_digest_at: Option<chrono::DateTime<chrono::Utc>>,
     last_learn_at: Option<chrono::DateTime<chrono::Utc>>,
+    autonomy_enabled: bool,
 }
 
 impl From<ReiStateRow> for ReiState {
@@ -72,27 +76,55 @@ impl From<ReiStateRow> for ReiState {
             energy_regen_per_hour: row.energy_regen_per_hour,
             last_digest_at: row.last_digest_at,
             last_learn_at: row.last_learn_at,
+            autonomy_enabled: row.autonomy_enabled,
         }
     }
 }
 
+#[async_trait]
+impl InsertOwned<Rei> for PgReiRepository {
+    async fn insert_owned(&self, rei: &Rei, owner_id: Option<Uuid>) -> Result<Rei, DomainError> {
+        let row = sqlx::query_as::<_, ReiRow>(
+            r#"
+            INSERT INTO reis (id, name, role, avatar_url, manifest, owner_id)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#,
+        )
+        .bind(rei.id)
+        .bind(&rei.name)
+        .bind(&rei.role)
+        .bind(&rei.avatar_url)
+        .bind(&rei.manifest)
+        .bind(owner_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(e.to_string()))?;
+
+        Ok(row.into())
+    }
+}
+
 #[async_trait]
 impl ReiRepository for PgReiRepository {
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Rei>, DomainError> {
-        let row = sqlx::query_as::<_, ReiRow>("SELECT * FROM reis WHERE id = $1")
-            .bind(id)
-            .fetch_optional(&self.pool)
-            .await
-            .map_err(|e| DomainError::Repository(e.to_string()))?;
+        let row =
+            sqlx::query_as::<_, ReiRow>("SELECT * FROM reis WHERE id = $1 AND deleted_at IS NULL")
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(|e| DomainError::Repository(e.to_string()))?;
 
         Ok(row.map(Into::into))
     }
 
     async fn find_all(&self) -> Result<Vec<Rei>, DomainError> {
-        let rows = sqlx::query_as::<_, ReiRow>("SELECT * FROM reis ORDER BY created_at DESC")
-            .fetch_all(&self.pool)
-            .await
-            .map_err(|e| DomainError::Repository(e.to_string()))?;
+        let rows = sqlx::query_as::<_, ReiRow>(
+            "SELECT * FROM reis WHERE deleted_at IS NULL ORDER BY created_at DESC",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(e.to_string()))?;
 
         Ok(rows.into_iter().map(Into::into).collect())
     }
@@ -106,61 +138,99 @@ impl ReiRepository for PgReiRepository {
                 .await
                 .map_err(|e| DomainError::Repository(e.to_string()))?;
 
-        let row = if exists {
-            // Update
-            sqlx::query_as::<_, ReiRow>(
-                r#"
-                UPDATE reis
-                SET name = $2, role = $3, avatar_url = $4, manifest = $5, updated_at = NOW()
-                WHERE id = $1
-                RETURNING *
-                "#,
-            )
-            .bind(rei.id)
-            .bind(&rei.name)
-            .bind(&rei.role)
-            .bind(&rei.avatar_url)
-            .bind(&rei.manifest)
-            .fetch_one(&self.pool)
-            .await
-        } else {
-            // Insert
-            sqlx::query_as::<_, ReiRow>(
-                r#"
-                INSERT INTO reis (id, name, role, avatar_url, manifest)
-                VALUES ($1, $2, $3, $4, $5)
-                RETURNING *
-                "#,
-            )
-            .bind(rei.id)
-            .bind(&rei.name)
-            .bind(&rei.role)
-            .bind(&rei.avatar_url)
-            .bind(&rei.manifest)
-            .fetch_one(&self.pool)
-            .await
+        if !exists {
+            return self.insert_owned(rei, None).await;
         }
+
+        let row = sqlx::query_as::<_, ReiRow>(
+            r#"
+            UPDATE reis
+            SET name = $2, role = $3, avatar_url = $4, manifest = $5, updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(rei.id)
+        .bind(&rei.name)
+        .bind(&rei.role)
+        .bind(&rei.avatar_url)
+        .bind(&rei.manifest)
+        .fetch_one(&self.pool)
+        .await
         .map_err(|e| DomainError::Repository(e.to_string()))?;
 
         Ok(row.into())
     }
 
+    async fn update_if_unchanged(
+        &self,
+        rei: &Rei,
+        expected: DateTime<Utc>,
+    ) -> Result<Option<Rei>, DomainError> {
+        let row = sqlx::query_as::<_, ReiRow>(
+            r#"
+            UPDATE reis
+            SET name = $2, role = $3, avatar_url = $4, manifest = $5, updated_at = NOW()
+            WHERE id = $1 AND updated_at = $6 AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(rei.id)
+        .bind(&rei.name)
+        .bind(&rei.role)
+        .bind(&rei.avatar_url)
+        .bind(&rei.manifest)
+        .bind(expected)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(e.to_string()))?;
+
+        Ok(row.map(Into::into))
+    }
+
     async fn delete(&self, id: Uuid) -> Result<bool, DomainError> {
-        let result = sqlx::query("DELETE FROM reis WHERE id = $1")
-            .bind(id)
-            .execute(&self.pool)
-            .await
-            .map_err(|e| DomainError::Repository(e.to_string()))?;
+        let result =
+            sqlx::query("UPDATE reis SET deleted_at = NOW() WHERE id = $1 AND deleted_at IS NULL")
+                .bind(id)
+                .execute(&self.pool)
+                .await
+                .map_err(|e| DomainError::Repository(e.to_string()))?;
 
         Ok(result.rows_affected() > 0)
     }
 
-    async fn find_state(&self, rei_id: Uuid) -> Result<Option<ReiState>, DomainError> {
-        let row = sqlx::query_as::<_, ReiStateRow>("SELECT * FROM rei_states WHERE rei_id = $1")
-            .bind(rei_id)
-            .fetch_optional(&self.pool)
+    async fn restore(&self, id: Uuid) -> Result<bool, DomainError> {
+        let result = sqlx::query(
+            "UPDATE reis SET deleted_at = NULL WHERE id = $1 AND deleted_at IS NOT NULL",
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn purge_deleted(&self, cutoff: DateTime<Utc>) -> Result<Vec<Uuid>, DomainError> {
+        sqlx::query_scalar::<_, Uuid>("DELETE FROM reis WHERE deleted_at < $1 RETURNING id")
+            .bind(cutoff)
+            .fetch_all(&self.pool)
             .await
-            .map_err(|e| DomainError::Repository(e.to_string()))?;
+            .map_err(|e| DomainError::Repository(e.to_string()))
+    }
+
+    async fn find_state(&self, rei_id: Uuid) -> Result<Option<ReiState>, DomainError> {
+        let row = sqlx::query_as::<_, ReiStateRow>(
+            r#"
+            SELECT s.* FROM rei_states s
+            JOIN reis r ON r.id = s.rei_id
+            WHERE s.rei_id = $1 AND r.deleted_at IS NULL
+            "#,
+        )
+        .bind(rei_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(e.to_string()))?;
 
         Ok(row.map(Into::into))
     }
@@ -188,6 +258,29 @@ impl ReiRepository for PgReiRepository {
         Ok(row.into())
     }
 
+    async fn set_autonomy(
+        &self,
+        rei_id: Uuid,
+        enabled: bool,
+    ) -> Result<Option<ReiState>, DomainError> {
+        let row = sqlx::query_as::<_, ReiStateRow>(
+            r#"
+            UPDATE rei_states s
+            SET autonomy_enabled = $2, updated_at = NOW()
+            FROM reis r
+            WHERE s.rei_id = $1 AND r.id = s.rei_id AND r.deleted_at IS NULL
+            RETURNING s.*
+            "#,
+        )
+        .bind(rei_id)
+        .bind(enabled)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(e.to_string()))?;
+
+        Ok(row.map(Into::into))
+    }
+
     async fn create_state(&self, rei_id: Uuid) -> Result<ReiState, DomainError> {
         let row = sqlx::query_as::<_, ReiStateRow>(
             r#"
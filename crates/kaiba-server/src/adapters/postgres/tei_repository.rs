@@ -1,11 +1,14 @@
 //! PostgreSQL implementation of TeiRepository
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use sqlx::PgPool;
 use uuid::Uuid;
 
 use kaiba::{DomainError, ReiTei, Tei, TeiRepository};
 
+use crate::services::ownership::InsertOwned;
+
 /// PostgreSQL implementation of TeiRepository
 pub struct PgTeiRepository {
     pool: PgPool,
@@ -66,6 +69,33 @@ impl From<ReiTeiRow> for ReiTei {
     }
 }
 
+#[async_trait]
+impl InsertOwned<Tei> for PgTeiRepository {
+    async fn insert_owned(&self, tei: &Tei, owner_id: Option<Uuid>) -> Result<Tei, DomainError> {
+        let row = sqlx::query_as::<_, TeiRow>(
+            r#"
+            INSERT INTO teis (id, name, provider, model_id, is_fallback, priority, config, expertise, owner_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING *
+            "#,
+        )
+        .bind(tei.id)
+        .bind(&tei.name)
+        .bind(&tei.provider)
+        .bind(&tei.model_id)
+        .bind(tei.is_fallback)
+        .bind(tei.priority)
+        .bind(&tei.config)
+        .bind(&tei.expertise)
+        .bind(owner_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(e.to_string()))?;
+
+        Ok(row.into())
+    }
+}
+
 #[async_trait]
 impl TeiRepository for PgTeiRepository {
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Tei>, DomainError> {
@@ -97,52 +127,64 @@ impl TeiRepository for PgTeiRepository {
                 .await
                 .map_err(|e| DomainError::Repository(e.to_string()))?;
 
-        let row = if exists {
-            // Update
-            sqlx::query_as::<_, TeiRow>(
-                r#"
-                UPDATE teis
-                SET name = $2, provider = $3, model_id = $4, is_fallback = $5,
-                    priority = $6, config = $7, expertise = $8, updated_at = NOW()
-                WHERE id = $1
-                RETURNING *
-                "#,
-            )
-            .bind(tei.id)
-            .bind(&tei.name)
-            .bind(&tei.provider)
-            .bind(&tei.model_id)
-            .bind(tei.is_fallback)
-            .bind(tei.priority)
-            .bind(&tei.config)
-            .bind(&tei.expertise)
-            .fetch_one(&self.pool)
-            .await
-        } else {
-            // Insert
-            sqlx::query_as::<_, TeiRow>(
-                r#"
-                INSERT INTO teis (id, name, provider, model_id, is_fallback, priority, config, expertise)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-                RETURNING *
-                "#,
-            )
-            .bind(tei.id)
-            .bind(&tei.name)
-            .bind(&tei.provider)
-            .bind(&tei.model_id)
-            .bind(tei.is_fallback)
-            .bind(tei.priority)
-            .bind(&tei.config)
-            .bind(&tei.expertise)
-            .fetch_one(&self.pool)
-            .await
+        if !exists {
+            return self.insert_owned(tei, None).await;
         }
+
+        let row = sqlx::query_as::<_, TeiRow>(
+            r#"
+            UPDATE teis
+            SET name = $2, provider = $3, model_id = $4, is_fallback = $5,
+                priority = $6, config = $7, expertise = $8, updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(tei.id)
+        .bind(&tei.name)
+        .bind(&tei.provider)
+        .bind(&tei.model_id)
+        .bind(tei.is_fallback)
+        .bind(tei.priority)
+        .bind(&tei.config)
+        .bind(&tei.expertise)
+        .fetch_one(&self.pool)
+        .await
         .map_err(|e| DomainError::Repository(e.to_string()))?;
 
         Ok(row.into())
     }
 
+    async fn update_if_unchanged(
+        &self,
+        tei: &Tei,
+        expected: DateTime<Utc>,
+    ) -> Result<Option<Tei>, DomainError> {
+        let row = sqlx::query_as::<_, TeiRow>(
+            r#"
+            UPDATE teis
+            SET name = $2, provider = $3, model_id = $4, is_fallback = $5,
+                priority = $6, config = $7, expertise = $8, updated_at = NOW()
+            WHERE id = $1 AND updated_at = $9
+            RETURNING *
+            "#,
+        )
+        .bind(tei.id)
+        .bind(&tei.name)
+        .bind(&tei.provider)
+        .bind(&tei.model_id)
+        .bind(tei.is_fallback)
+        .bind(tei.priority)
+        .bind(&tei.config)
+        .bind(&tei.expertise)
+        .bind(expected)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(e.to_string()))?;
+
+        Ok(row.map(Into::into))
+    }
+
     async fn delete(&self, id: Uuid) -> Result<bool, DomainError> {
         let result = sqlx::query("DELETE FROM teis WHERE id = $1")
             .bind(id)
@@ -200,12 +242,13 @@ impl TeiRepository for PgTeiRepository {
     }
 
     async fn rei_exists(&self, rei_id: Uuid) -> Result<bool, DomainError> {
-        let exists =
-            sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM reis WHERE id = $1)")
-                .bind(rei_id)
-                .fetch_one(&self.pool)
-                .await
-                .map_err(|e| DomainError::Repository(e.to_string()))?;
+        let exists = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM reis WHERE id = $1 AND deleted_at IS NULL)",
+        )
+        .bind(rei_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(e.to_string()))?;
 
         Ok(exists)
     }
@@ -5,10 +5,13 @@ use sqlx::PgPool;
 use uuid::Uuid;
 
 use kaiba::{
-    DeliveryStatus, DomainError, ReiWebhook, ReiWebhookRepository, WebhookDelivery,
-    WebhookEventType, WebhookPayload,
+    DeliveryRetention, DeliveryStatus, DomainError, EventDefinition, LatencyBucket, ReiWebhook,
+    ReiWebhookRepository, WebhookCondition, WebhookDelivery, WebhookDeliveryStats,
+    WebhookEventType, WebhookPayload, LATENCY_BUCKETS_MS,
 };
 
+use crate::services::ownership::InsertOwned;
+
 /// PostgreSQL implementation of ReiWebhookRepository
 pub struct PgReiWebhookRepository {
     pool: PgPool,
@@ -24,16 +27,25 @@ impl PgReiWebhookRepository {
 #[derive(sqlx::FromRow)]
 struct ReiWebhookRow {
     id: Uuid,
-    rei_id: Uuid,
+    rei_id: Option<Uuid>,
     name: String,
     url: String,
     secret: Option<String>,
     enabled: bool,
     events: serde_json::Value,
+    conditions: serde_json::Value,
     headers: serde_json::Value,
     max_retries: i32,
     timeout_ms: i32,
+    consecutive_failures: i32,
+    max_consecutive_failures: Option<i32>,
+    rate_limit_per_minute: Option<i32>,
+    delivery_mode: String,
+    batch_interval_minutes: i32,
+    tls_config: Option<serde_json::Value>,
     payload_format: Option<String>,
+    format_options: serde_json::Value,
+    payload_template: Option<String>,
     created_at: chrono::DateTime<chrono::Utc>,
     updated_at: chrono::DateTime<chrono::Utc>,
 }
@@ -42,6 +54,8 @@ impl From<ReiWebhookRow> for ReiWebhook {
     fn from(row: ReiWebhookRow) -> Self {
         let events: Vec<WebhookEventType> =
             serde_json::from_value(row.events).unwrap_or_else(|_| vec![WebhookEventType::All]);
+        let conditions: Vec<WebhookCondition> =
+            serde_json::from_value(row.conditions).unwrap_or_default();
 
         Self {
             id: row.id,
@@ -51,10 +65,19 @@ impl From<ReiWebhookRow> for ReiWebhook {
             secret: row.secret,
             enabled: row.enabled,
             events,
+            conditions,
             headers: row.headers,
             max_retries: row.max_retries,
             timeout_ms: row.timeout_ms,
+            consecutive_failures: row.consecutive_failures,
+            max_consecutive_failures: row.max_consecutive_failures,
+            rate_limit_per_minute: row.rate_limit_per_minute,
+            delivery_mode: row.delivery_mode.parse().unwrap_or_default(),
+            batch_interval_minutes: row.batch_interval_minutes,
+            tls: row.tls_config.and_then(|v| serde_json::from_value(v).ok()),
             payload_format: row.payload_format,
+            format_options: row.format_options,
+            payload_template: row.payload_template,
             created_at: row.created_at,
             updated_at: row.updated_at,
         }
@@ -70,10 +93,24 @@ struct WebhookDeliveryRow {
     status_code: Option<i32>,
     response_body: Option<String>,
     attempts: i32,
+    duration_ms: Option<i32>,
     created_at: chrono::DateTime<chrono::Utc>,
     completed_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+/// Internal row type for delivery stats aggregation
+#[derive(sqlx::FromRow)]
+struct DeliveryStatsRow {
+    total: i64,
+    successful: i64,
+    failed: i64,
+    last_success_at: Option<chrono::DateTime<chrono::Utc>>,
+    last_failure_at: Option<chrono::DateTime<chrono::Utc>>,
+    latency_p50_ms: Option<f64>,
+    latency_p95_ms: Option<f64>,
+    latency_p99_ms: Option<f64>,
+}
+
 impl From<WebhookDeliveryRow> for WebhookDelivery {
     fn from(row: WebhookDeliveryRow) -> Self {
         let payload: WebhookPayload =
@@ -83,6 +120,7 @@ impl From<WebhookDeliveryRow> for WebhookDelivery {
             "success" => DeliveryStatus::Success,
             "failed" => DeliveryStatus::Failed,
             "retrying" => DeliveryStatus::Retrying,
+            "dead" => DeliveryStatus::Dead,
             _ => DeliveryStatus::Pending,
         };
 
@@ -94,18 +132,109 @@ impl From<WebhookDeliveryRow> for WebhookDelivery {
             status_code: row.status_code,
             response_body: row.response_body,
             attempts: row.attempts,
+            duration_ms: row.duration_ms,
             created_at: row.created_at,
             completed_at: row.completed_at,
         }
     }
 }
 
+#[derive(sqlx::FromRow)]
+struct EventDefinitionRow {
+    name: String,
+    description: Option<String>,
+    schema: serde_json::Value,
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<EventDefinitionRow> for EventDefinition {
+    fn from(row: EventDefinitionRow) -> Self {
+        Self {
+            name: row.name,
+            description: row.description,
+            // Stored schemas were validated on save
+            schema: serde_json::from_value(row.schema).unwrap_or_default(),
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
 fn delivery_status_to_string(status: &DeliveryStatus) -> &'static str {
     match status {
         DeliveryStatus::Pending => "pending",
         DeliveryStatus::Success => "success",
         DeliveryStatus::Failed => "failed",
         DeliveryStatus::Retrying => "retrying",
+        DeliveryStatus::Dead => "dead",
+    }
+}
+
+/// Events, conditions and TLS settings of a webhook as JSON columns
+fn json_columns(
+    webhook: &ReiWebhook,
+) -> Result<
+    (
+        serde_json::Value,
+        serde_json::Value,
+        Option<serde_json::Value>,
+    ),
+    DomainError,
+> {
+    let events_json = serde_json::to_value(&webhook.events)
+        .map_err(|e| DomainError::Repository(e.to_string()))?;
+    let conditions_json = serde_json::to_value(&webhook.conditions)
+        .map_err(|e| DomainError::Repository(e.to_string()))?;
+    let tls_json = webhook
+        .tls
+        .as_ref()
+        .map(serde_json::to_value)
+        .transpose()
+        .map_err(|e| DomainError::Repository(e.to_string()))?;
+    Ok((events_json, conditions_json, tls_json))
+}
+
+#[async_trait]
+impl InsertOwned<ReiWebhook> for PgReiWebhookRepository {
+    async fn insert_owned(
+        &self,
+        webhook: &ReiWebhook,
+        owner_id: Option<Uuid>,
+    ) -> Result<ReiWebhook, DomainError> {
+        let (events_json, conditions_json, tls_json) = json_columns(webhook)?;
+        let row = sqlx::query_as::<_, ReiWebhookRow>(
+            r#"
+            INSERT INTO rei_webhooks (id, rei_id, name, url, secret, enabled, events, headers, max_retries, timeout_ms, payload_format, payload_template, conditions, max_consecutive_failures, format_options, rate_limit_per_minute, delivery_mode, batch_interval_minutes, tls_config, owner_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20)
+            RETURNING *
+            "#,
+        )
+        .bind(webhook.id)
+        .bind(webhook.rei_id)
+        .bind(&webhook.name)
+        .bind(&webhook.url)
+        .bind(&webhook.secret)
+        .bind(webhook.enabled)
+        .bind(&events_json)
+        .bind(&webhook.headers)
+        .bind(webhook.max_retries)
+        .bind(webhook.timeout_ms)
+        .bind(&webhook.payload_format)
+        .bind(&webhook.payload_template)
+        .bind(&conditions_json)
+        .bind(webhook.max_consecutive_failures)
+        .bind(&webhook.format_options)
+        .bind(webhook.rate_limit_per_minute)
+        .bind(webhook.delivery_mode.to_string())
+        .bind(webhook.batch_interval_minutes)
+        .bind(&tls_json)
+        .bind(owner_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(e.to_string()))?;
+
+        Ok(row.into())
     }
 }
 
@@ -133,14 +262,30 @@ impl ReiWebhookRepository for PgReiWebhookRepository {
         Ok(rows.into_iter().map(Into::into).collect())
     }
 
+    async fn find_global(&self) -> Result<Vec<ReiWebhook>, DomainError> {
+        let rows = sqlx::query_as::<_, ReiWebhookRow>(
+            "SELECT * FROM rei_webhooks WHERE rei_id IS NULL ORDER BY created_at DESC",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(e.to_string()))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
     async fn find_by_rei_and_event(
         &self,
         rei_id: Uuid,
         event: &WebhookEventType,
     ) -> Result<Vec<ReiWebhook>, DomainError> {
-        // Get all enabled webhooks for this Rei, then filter by event type
+        // Get all enabled webhooks for this Rei (plus global ones), then filter by event type.
+        // Global webhooks with an owner only see events from the owner's Reis.
         let rows = sqlx::query_as::<_, ReiWebhookRow>(
-            "SELECT * FROM rei_webhooks WHERE rei_id = $1 AND enabled = true",
+            r#"
+            SELECT * FROM rei_webhooks
+            WHERE (rei_id = $1 OR rei_id IS NULL) AND enabled = true
+              AND (owner_id IS NULL OR owner_id = (SELECT owner_id FROM reis WHERE id = $1))
+            "#,
         )
         .bind(rei_id)
         .fetch_all(&self.pool)
@@ -157,9 +302,6 @@ impl ReiWebhookRepository for PgReiWebhookRepository {
     }
 
     async fn save(&self, webhook: &ReiWebhook) -> Result<ReiWebhook, DomainError> {
-        let events_json = serde_json::to_value(&webhook.events)
-            .map_err(|e| DomainError::Repository(e.to_string()))?;
-
         // Check if exists
         let exists = sqlx::query_scalar::<_, bool>(
             "SELECT EXISTS(SELECT 1 FROM rei_webhooks WHERE id = $1)",
@@ -169,52 +311,44 @@ impl ReiWebhookRepository for PgReiWebhookRepository {
         .await
         .map_err(|e| DomainError::Repository(e.to_string()))?;
 
-        let row = if exists {
-            // Update
-            sqlx::query_as::<_, ReiWebhookRow>(
-                r#"
-                UPDATE rei_webhooks
-                SET name = $2, url = $3, secret = $4, enabled = $5, events = $6,
-                    headers = $7, max_retries = $8, timeout_ms = $9, payload_format = $10, updated_at = NOW()
-                WHERE id = $1
-                RETURNING *
-                "#,
-            )
-            .bind(webhook.id)
-            .bind(&webhook.name)
-            .bind(&webhook.url)
-            .bind(&webhook.secret)
-            .bind(webhook.enabled)
-            .bind(&events_json)
-            .bind(&webhook.headers)
-            .bind(webhook.max_retries)
-            .bind(webhook.timeout_ms)
-            .bind(&webhook.payload_format)
-            .fetch_one(&self.pool)
-            .await
-        } else {
-            // Insert
-            sqlx::query_as::<_, ReiWebhookRow>(
-                r#"
-                INSERT INTO rei_webhooks (id, rei_id, name, url, secret, enabled, events, headers, max_retries, timeout_ms, payload_format)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
-                RETURNING *
-                "#,
-            )
-            .bind(webhook.id)
-            .bind(webhook.rei_id)
-            .bind(&webhook.name)
-            .bind(&webhook.url)
-            .bind(&webhook.secret)
-            .bind(webhook.enabled)
-            .bind(&events_json)
-            .bind(&webhook.headers)
-            .bind(webhook.max_retries)
-            .bind(webhook.timeout_ms)
-            .bind(&webhook.payload_format)
-            .fetch_one(&self.pool)
-            .await
+        if !exists {
+            return self.insert_owned(webhook, None).await;
         }
+
+        let (events_json, conditions_json, tls_json) = json_columns(webhook)?;
+        let row = sqlx::query_as::<_, ReiWebhookRow>(
+            r#"
+            UPDATE rei_webhooks
+            SET name = $2, url = $3, secret = $4, enabled = $5, events = $6,
+                headers = $7, max_retries = $8, timeout_ms = $9, payload_format = $10,
+                payload_template = $11, conditions = $12,
+                max_consecutive_failures = $13, format_options = $14,
+                rate_limit_per_minute = $15, delivery_mode = $16,
+                batch_interval_minutes = $17, tls_config = $18, updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(webhook.id)
+        .bind(&webhook.name)
+        .bind(&webhook.url)
+        .bind(&webhook.secret)
+        .bind(webhook.enabled)
+        .bind(&events_json)
+        .bind(&webhook.headers)
+        .bind(webhook.max_retries)
+        .bind(webhook.timeout_ms)
+        .bind(&webhook.payload_format)
+        .bind(&webhook.payload_template)
+        .bind(&conditions_json)
+        .bind(webhook.max_consecutive_failures)
+        .bind(&webhook.format_options)
+        .bind(webhook.rate_limit_per_minute)
+        .bind(webhook.delivery_mode.to_string())
+        .bind(webhook.batch_interval_minutes)
+        .bind(&tls_json)
+        .fetch_one(&self.pool)
+        .await
         .map_err(|e| DomainError::Repository(e.to_string()))?;
 
         Ok(row.into())
@@ -231,17 +365,43 @@ impl ReiWebhookRepository for PgReiWebhookRepository {
     }
 
     async fn set_enabled(&self, id: Uuid, enabled: bool) -> Result<bool, DomainError> {
-        let result =
-            sqlx::query("UPDATE rei_webhooks SET enabled = $2, updated_at = NOW() WHERE id = $1")
-                .bind(id)
-                .bind(enabled)
-                .execute(&self.pool)
-                .await
-                .map_err(|e| DomainError::Repository(e.to_string()))?;
+        // Re-enabling starts a fresh failure streak
+        let result = sqlx::query(
+            r#"
+            UPDATE rei_webhooks
+            SET enabled = $2,
+                consecutive_failures = CASE WHEN $2 THEN 0 ELSE consecutive_failures END,
+                updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(enabled)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(e.to_string()))?;
 
         Ok(result.rows_affected() > 0)
     }
 
+    async fn record_delivery_outcome(&self, id: Uuid, success: bool) -> Result<i32, DomainError> {
+        let count = sqlx::query_scalar::<_, i32>(
+            r#"
+            UPDATE rei_webhooks
+            SET consecutive_failures = CASE WHEN $2 THEN 0 ELSE consecutive_failures + 1 END
+            WHERE id = $1
+            RETURNING consecutive_failures
+            "#,
+        )
+        .bind(id)
+        .bind(success)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(e.to_string()))?;
+
+        Ok(count)
+    }
+
     async fn save_delivery(
         &self,
         delivery: &WebhookDelivery,
@@ -264,7 +424,8 @@ impl ReiWebhookRepository for PgReiWebhookRepository {
             sqlx::query_as::<_, WebhookDeliveryRow>(
                 r#"
                 UPDATE webhook_deliveries
-                SET status = $2, status_code = $3, response_body = $4, attempts = $5, completed_at = $6
+                SET status = $2, status_code = $3, response_body = $4, attempts = $5, completed_at = $6,
+                    duration_ms = $7
                 WHERE id = $1
                 RETURNING *
                 "#,
@@ -275,14 +436,15 @@ impl ReiWebhookRepository for PgReiWebhookRepository {
             .bind(&delivery.response_body)
             .bind(delivery.attempts)
             .bind(delivery.completed_at)
+            .bind(delivery.duration_ms)
             .fetch_one(&self.pool)
             .await
         } else {
             // Insert
             sqlx::query_as::<_, WebhookDeliveryRow>(
                 r#"
-                INSERT INTO webhook_deliveries (id, webhook_id, payload, status, status_code, response_body, attempts, completed_at)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                INSERT INTO webhook_deliveries (id, webhook_id, payload, status, status_code, response_body, attempts, completed_at, duration_ms)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
                 RETURNING *
                 "#,
             )
@@ -294,6 +456,7 @@ impl ReiWebhookRepository for PgReiWebhookRepository {
             .bind(&delivery.response_body)
             .bind(delivery.attempts)
             .bind(delivery.completed_at)
+            .bind(delivery.duration_ms)
             .fetch_one(&self.pool)
             .await
         }
@@ -302,6 +465,18 @@ impl ReiWebhookRepository for PgReiWebhookRepository {
         Ok(row.into())
     }
 
+    async fn find_delivery_by_id(&self, id: Uuid) -> Result<Option<WebhookDelivery>, DomainError> {
+        let row = sqlx::query_as::<_, WebhookDeliveryRow>(
+            "SELECT * FROM webhook_deliveries WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(e.to_string()))?;
+
+        Ok(row.map(Into::into))
+    }
+
     async fn find_deliveries(
         &self,
         webhook_id: Uuid,
@@ -319,6 +494,25 @@ impl ReiWebhookRepository for PgReiWebhookRepository {
         Ok(rows.into_iter().map(Into::into).collect())
     }
 
+    async fn find_deliveries_by_status(
+        &self,
+        webhook_id: Uuid,
+        status: &DeliveryStatus,
+        limit: i32,
+    ) -> Result<Vec<WebhookDelivery>, DomainError> {
+        let rows = sqlx::query_as::<_, WebhookDeliveryRow>(
+            "SELECT * FROM webhook_deliveries WHERE webhook_id = $1 AND status = $2 ORDER BY created_at DESC LIMIT $3",
+        )
+        .bind(webhook_id)
+        .bind(delivery_status_to_string(status))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(e.to_string()))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
     async fn find_pending_deliveries(&self) -> Result<Vec<WebhookDelivery>, DomainError> {
         let rows = sqlx::query_as::<_, WebhookDeliveryRow>(
             "SELECT * FROM webhook_deliveries WHERE status IN ('pending', 'retrying') ORDER BY created_at ASC",
@@ -329,4 +523,228 @@ impl ReiWebhookRepository for PgReiWebhookRepository {
 
         Ok(rows.into_iter().map(Into::into).collect())
     }
+
+    async fn delivery_stats(
+        &self,
+        webhook_id: Uuid,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<WebhookDeliveryStats, DomainError> {
+        let row = sqlx::query_as::<_, DeliveryStatsRow>(
+            r#"
+            SELECT
+                COUNT(*) AS total,
+                COUNT(*) FILTER (WHERE status = 'success') AS successful,
+                COUNT(*) FILTER (WHERE status IN ('failed', 'dead')) AS failed,
+                MAX(completed_at) FILTER (WHERE status = 'success') AS last_success_at,
+                MAX(completed_at) FILTER (WHERE status IN ('failed', 'dead')) AS last_failure_at,
+                percentile_cont(0.5) WITHIN GROUP (ORDER BY duration_ms) AS latency_p50_ms,
+                percentile_cont(0.95) WITHIN GROUP (ORDER BY duration_ms) AS latency_p95_ms,
+                percentile_cont(0.99) WITHIN GROUP (ORDER BY duration_ms) AS latency_p99_ms
+            FROM webhook_deliveries
+            WHERE webhook_id = $1 AND ($2::timestamptz IS NULL OR created_at >= $2)
+            "#,
+        )
+        .bind(webhook_id)
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(e.to_string()))?;
+
+        // width_bucket returns 0 below the first bound, N at or above the last
+        let buckets: Vec<(i32, i64)> = sqlx::query_as(
+            r#"
+            SELECT width_bucket(duration_ms, $3::int[]) AS bucket, COUNT(*)
+            FROM webhook_deliveries
+            WHERE webhook_id = $1 AND duration_ms IS NOT NULL
+              AND ($2::timestamptz IS NULL OR created_at >= $2)
+            GROUP BY bucket
+            "#,
+        )
+        .bind(webhook_id)
+        .bind(since)
+        .bind(&LATENCY_BUCKETS_MS[..])
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(e.to_string()))?;
+
+        let latency_histogram = (0..=LATENCY_BUCKETS_MS.len())
+            .map(|i| LatencyBucket {
+                lt_ms: LATENCY_BUCKETS_MS.get(i).copied(),
+                count: buckets
+                    .iter()
+                    .find(|(bucket, _)| *bucket as usize == i)
+                    .map(|(_, count)| *count)
+                    .unwrap_or(0),
+            })
+            .collect();
+
+        Ok(WebhookDeliveryStats {
+            total: row.total,
+            successful: row.successful,
+            failed: row.failed,
+            last_success_at: row.last_success_at,
+            last_failure_at: row.last_failure_at,
+            latency_p50_ms: row.latency_p50_ms,
+            latency_p95_ms: row.latency_p95_ms,
+            latency_p99_ms: row.latency_p99_ms,
+            latency_histogram,
+        })
+    }
+
+    async fn prune_deliveries(&self, retention: &DeliveryRetention) -> Result<u64, DomainError> {
+        let mut deleted = 0;
+
+        if let Some(days) = retention.max_age_days {
+            let result = sqlx::query(
+                r#"
+                DELETE FROM webhook_deliveries
+                WHERE created_at < NOW() - make_interval(days => $1)
+                  AND status NOT IN ('pending', 'retrying')
+                "#,
+            )
+            .bind(days)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Repository(e.to_string()))?;
+            deleted += result.rows_affected();
+        }
+
+        if let Some(max) = retention.max_per_webhook {
+            let result = sqlx::query(
+                r#"
+                DELETE FROM webhook_deliveries d
+                USING (
+                    SELECT id, ROW_NUMBER() OVER (PARTITION BY webhook_id ORDER BY created_at DESC) AS rn
+                    FROM webhook_deliveries
+                ) ranked
+                WHERE d.id = ranked.id AND ranked.rn > $1
+                  AND d.status NOT IN ('pending', 'retrying')
+                "#,
+            )
+            .bind(max)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Repository(e.to_string()))?;
+            deleted += result.rows_affected();
+        }
+
+        Ok(deleted)
+    }
+
+    async fn enqueue_batch_event(
+        &self,
+        webhook_id: Uuid,
+        payload: &WebhookPayload,
+    ) -> Result<(), DomainError> {
+        let payload_json =
+            serde_json::to_value(payload).map_err(|e| DomainError::Repository(e.to_string()))?;
+
+        sqlx::query("INSERT INTO webhook_batch_events (webhook_id, payload) VALUES ($1, $2)")
+            .bind(webhook_id)
+            .bind(&payload_json)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Repository(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn find_due_batches(&self) -> Result<Vec<ReiWebhook>, DomainError> {
+        let rows = sqlx::query_as::<_, ReiWebhookRow>(
+            r#"
+            SELECT w.* FROM rei_webhooks w
+            WHERE w.enabled = true AND w.delivery_mode = 'batched'
+              AND EXISTS (
+                SELECT 1 FROM webhook_batch_events b
+                WHERE b.webhook_id = w.id
+                  AND b.created_at <= NOW() - make_interval(mins => w.batch_interval_minutes)
+              )
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(e.to_string()))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn take_batch_events(
+        &self,
+        webhook_id: Uuid,
+    ) -> Result<Vec<WebhookPayload>, DomainError> {
+        let payloads: Vec<serde_json::Value> = sqlx::query_scalar(
+            "DELETE FROM webhook_batch_events WHERE webhook_id = $1 RETURNING payload",
+        )
+        .bind(webhook_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(e.to_string()))?;
+
+        Ok(payloads
+            .into_iter()
+            .filter_map(|p| serde_json::from_value(p).ok())
+            .collect())
+    }
+
+    async fn find_event_definitions(&self) -> Result<Vec<EventDefinition>, DomainError> {
+        let rows = sqlx::query_as::<_, EventDefinitionRow>(
+            "SELECT * FROM webhook_event_definitions ORDER BY name",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(e.to_string()))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn find_event_definition(
+        &self,
+        name: &str,
+    ) -> Result<Option<EventDefinition>, DomainError> {
+        let row = sqlx::query_as::<_, EventDefinitionRow>(
+            "SELECT * FROM webhook_event_definitions WHERE name = $1",
+        )
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(e.to_string()))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn save_event_definition(
+        &self,
+        definition: &EventDefinition,
+    ) -> Result<EventDefinition, DomainError> {
+        let row = sqlx::query_as::<_, EventDefinitionRow>(
+            r#"
+            INSERT INTO webhook_event_definitions (name, description, schema, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, NOW())
+            ON CONFLICT (name) DO UPDATE
+            SET description = EXCLUDED.description,
+                schema = EXCLUDED.schema,
+                updated_at = NOW()
+            RETURNING *
+            "#,
+        )
+        .bind(&definition.name)
+        .bind(&definition.description)
+        .bind(definition.schema.as_value())
+        .bind(definition.created_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| DomainError::Repository(e.to_string()))?;
+
+        Ok(row.into())
+    }
+
+    async fn delete_event_definition(&self, name: &str) -> Result<bool, DomainError> {
+        let result = sqlx::query("DELETE FROM webhook_event_definitions WHERE name = $1")
+            .bind(name)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::Repository(e.to_string()))?;
+
+        Ok(result.rows_affected() > 0)
+    }
 }
@@ -0,0 +1,137 @@
+//! Sliding-Window Rate Limiter - Requests per minute per key
+//!
+//! Each key keeps a sliding one-minute window of send slots. Callers either
+//! take a slot right away or are turned down with the time until one frees
+//! up (API routes, see `rate_limit`), or reserve the earliest slot that keeps
+//! the limit and wait for it, so bursts are queued in arrival order rather
+//! than dropped (webhook deliveries).
+
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// Sliding-window limiter keyed by `K` (a webhook ID, a caller and route class, ...)
+#[derive(Debug)]
+pub struct SlidingWindowLimiter<K> {
+    slots: Mutex<HashMap<K, VecDeque<Instant>>>,
+}
+
+impl<K> Default for SlidingWindowLimiter<K> {
+    fn default() -> Self {
+        Self {
+            slots: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<K: Eq + Hash> SlidingWindowLimiter<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take a slot now, or return how long until one frees up
+    ///
+    /// Turned-down requests take no slot.
+    pub fn try_acquire(&self, key: K, per_minute: u32, now: Instant) -> Result<(), Duration> {
+        let limit = per_minute.max(1) as usize;
+        let mut slots = self.slots.lock().expect("rate limiter lock poisoned");
+        let window = Self::window(&mut slots, key, now);
+
+        if window.len() >= limit {
+            return Err((window[window.len() - limit] + WINDOW).saturating_duration_since(now));
+        }
+        window.push_back(now);
+        Ok(())
+    }
+
+    /// Wait until another request may be sent under `key`
+    ///
+    /// Returns how long the caller was queued.
+    pub async fn acquire(&self, key: K, per_minute: u32) -> Duration {
+        let wait = self.reserve(key, per_minute, Instant::now());
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+        wait
+    }
+
+    /// Reserve the next free slot and return the delay until it
+    fn reserve(&self, key: K, per_minute: u32, now: Instant) -> Duration {
+        let limit = per_minute.max(1) as usize;
+        let mut slots = self.slots.lock().expect("rate limiter lock poisoned");
+        let window = Self::window(&mut slots, key, now);
+
+        // Slots are reserved in order, so the one `limit` places back
+        // bounds when the next may start
+        let slot = if window.len() < limit {
+            now
+        } else {
+            (window[window.len() - limit] + WINDOW).max(now)
+        };
+        window.push_back(slot);
+
+        slot - now
+    }
+
+    /// The key's window, without slots that have left it
+    fn window(
+        slots: &mut HashMap<K, VecDeque<Instant>>,
+        key: K,
+        now: Instant,
+    ) -> &mut VecDeque<Instant> {
+        let window = slots.entry(key).or_default();
+        while window
+            .front()
+            .is_some_and(|slot| now.saturating_duration_since(*slot) >= WINDOW)
+        {
+            window.pop_front();
+        }
+        window
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_reserve_queues_beyond_limit() {
+        let limiter = SlidingWindowLimiter::new();
+        let webhook_id = Uuid::new_v4();
+        let now = Instant::now();
+
+        assert!(limiter.reserve(webhook_id, 2, now).is_zero());
+        assert!(limiter.reserve(webhook_id, 2, now).is_zero());
+        assert_eq!(limiter.reserve(webhook_id, 2, now), WINDOW);
+        assert_eq!(limiter.reserve(webhook_id, 2, now), WINDOW);
+        assert_eq!(limiter.reserve(webhook_id, 2, now), WINDOW * 2);
+
+        // Other webhooks are unaffected
+        assert!(limiter.reserve(Uuid::new_v4(), 2, now).is_zero());
+
+        // Once the first slots leave the window, queued ones still count
+        let later = now + WINDOW;
+        assert_eq!(limiter.reserve(webhook_id, 2, later), WINDOW);
+    }
+
+    #[test]
+    fn test_try_acquire_rejects_beyond_limit() {
+        let limiter = SlidingWindowLimiter::new();
+        let now = Instant::now();
+
+        assert!(limiter.try_acquire("a", 2, now).is_ok());
+        assert!(limiter
+            .try_acquire("a", 2, now + Duration::from_secs(10))
+            .is_ok());
+        assert_eq!(
+            limiter.try_acquire("a", 2, now + Duration::from_secs(20)),
+            Err(Duration::from_secs(40))
+        );
+        // The rejection took no slot
+        assert!(limiter.try_acquire("a", 2, now + WINDOW).is_ok());
+    }
+}
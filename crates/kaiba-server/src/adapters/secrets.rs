@@ -0,0 +1,134 @@
+//! Webhook Secrets - Resolve `${secret:name}` references in webhook headers
+//!
+//! Tokens live in the deployment secret store as `WEBHOOK_SECRET_<NAME>`
+//! and are substituted at delivery time, so webhook rows only hold the
+//! reference, e.g. `{"Authorization": "Bearer ${secret:linear_token}"}`.
+
+use std::collections::HashMap;
+
+/// Secret store key prefix for webhook secrets
+const SECRET_KEY_PREFIX: &str = "WEBHOOK_SECRET_";
+
+/// Placeholder prefix inside header values
+const PLACEHOLDER_PREFIX: &str = "${secret:";
+
+/// Rendered in place of secret values when masking
+const SECRET_MASK: &str = "********";
+
+/// Named secrets available to webhook headers
+#[derive(Debug, Clone, Default)]
+pub struct WebhookSecrets {
+    values: HashMap<String, String>,
+}
+
+impl WebhookSecrets {
+    /// Collect `WEBHOOK_SECRET_*` entries (names are lowercased, prefix stripped)
+    pub fn from_secret_store<I>(entries: I) -> Self
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let values = entries
+            .into_iter()
+            .filter_map(|(key, value)| {
+                key.strip_prefix(SECRET_KEY_PREFIX)
+                    .map(|name| (name.to_lowercase(), value))
+            })
+            .collect();
+        Self { values }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Replace every `${secret:name}` in a header value
+    pub fn interpolate(&self, value: &str) -> Result<String, String> {
+        self.resolve(value, false)
+    }
+
+    /// Like `interpolate`, but renders resolved secrets as a mask (for dry runs)
+    pub fn interpolate_masked(&self, value: &str) -> Result<String, String> {
+        self.resolve(value, true)
+    }
+
+    fn resolve(&self, value: &str, mask: bool) -> Result<String, String> {
+        let mut result = String::with_capacity(value.len());
+        let mut rest = value;
+
+        while let Some(start) = rest.find(PLACEHOLDER_PREFIX) {
+            result.push_str(&rest[..start]);
+            let after = &rest[start + PLACEHOLDER_PREFIX.len()..];
+            let end = after
+                .find('}')
+                .ok_or_else(|| format!("Unterminated secret reference in: {}", value))?;
+            let name = after[..end].trim().to_lowercase();
+
+            let secret = self
+                .values
+                .get(&name)
+                .ok_or_else(|| format!("Unknown secret: {}", name))?;
+            result.push_str(if mask { SECRET_MASK } else { secret });
+            rest = &after[end + 1..];
+        }
+
+        result.push_str(rest);
+        Ok(result)
+    }
+
+    /// Check that all secret references in a headers object resolve
+    pub fn validate_headers(&self, headers: &serde_json::Value) -> Result<(), String> {
+        let Some(headers) = headers.as_object() else {
+            return Err("headers must be a JSON object".to_string());
+        };
+
+        for (key, value) in headers {
+            let value = value
+                .as_str()
+                .ok_or_else(|| format!("Header '{}' must be a string", key))?;
+            self.interpolate(value)
+                .map_err(|e| format!("Header '{}': {}", key, e))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interpolate() {
+        let secrets = WebhookSecrets::from_secret_store(vec![
+            (
+                "WEBHOOK_SECRET_LINEAR_TOKEN".to_string(),
+                "lin_123".to_string(),
+            ),
+            ("OPENAI_API_KEY".to_string(), "sk-ignored".to_string()),
+        ]);
+        assert_eq!(secrets.len(), 1);
+
+        assert_eq!(
+            secrets
+                .interpolate("Bearer ${secret:linear_token}")
+                .unwrap(),
+            "Bearer lin_123"
+        );
+        assert_eq!(
+            secrets
+                .interpolate_masked("Bearer ${secret:linear_token}")
+                .unwrap(),
+            "Bearer ********"
+        );
+        assert_eq!(secrets.interpolate("plain").unwrap(), "plain");
+        assert!(secrets.interpolate("${secret:openai_api_key}").is_err());
+        assert!(secrets.interpolate("${secret:linear_token").is_err());
+
+        let headers = serde_json::json!({ "Authorization": "Bearer ${secret:missing}" });
+        assert!(secrets.validate_headers(&headers).is_err());
+    }
+}
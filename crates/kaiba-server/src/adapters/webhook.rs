@@ -1,22 +1,33 @@
 //! HTTP Webhook Implementation
 //!
 //! Delivers webhooks to external endpoints using reqwest.
+//! Webhooks with custom TLS settings (private CA, client certificate) get
+//! their own client, cached until the settings change.
 
 use async_trait::async_trait;
-use reqwest::Client;
-use std::time::Duration;
+use reqwest::{Certificate, Client, Identity};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
 
 use kaiba::{
     DeliveryStatus, DomainError, ReiWebhook, TeiWebhook, WebhookDelivery, WebhookDeliveryConfig,
-    WebhookPayload,
+    WebhookPayload, WebhookTlsConfig,
 };
 
 use crate::adapters::formatters;
+use crate::adapters::rate_limiter::SlidingWindowLimiter;
+use crate::adapters::secrets::WebhookSecrets;
 
 /// HTTP implementation of TeiWebhook
 pub struct HttpWebhook {
     client: Client,
     config: WebhookDeliveryConfig,
+    secrets: WebhookSecrets,
+    rate_limiter: SlidingWindowLimiter<Uuid>,
+    /// Clients for webhooks with custom TLS, keyed by webhook ID
+    tls_clients: Mutex<HashMap<Uuid, (WebhookTlsConfig, Client)>>,
 }
 
 impl HttpWebhook {
@@ -31,10 +42,134 @@ impl HttpWebhook {
             .build()
             .expect("Failed to build HTTP client");
 
-        Self { client, config }
+        Self {
+            client,
+            config,
+            secrets: WebhookSecrets::default(),
+            rate_limiter: SlidingWindowLimiter::new(),
+            tls_clients: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Set secrets available to `${secret:name}` header references
+    pub fn with_secrets(mut self, secrets: WebhookSecrets) -> Self {
+        self.secrets = secrets;
+        self
+    }
+
+    /// Secrets available to header references
+    pub fn secrets(&self) -> &WebhookSecrets {
+        &self.secrets
+    }
+
+    /// Build a client that trusts the configured CA and presents the client certificate
+    pub fn build_tls_client(&self, tls: &WebhookTlsConfig) -> Result<Client, String> {
+        let mut builder = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .user_agent(&self.config.user_agent);
+
+        if let Some(ca) = &tls.ca_cert_pem {
+            let pem = self
+                .secrets
+                .interpolate(ca)
+                .map_err(|e| format!("ca_cert_pem: {e}"))?;
+            let cert = Certificate::from_pem(pem.as_bytes())
+                .map_err(|e| format!("Invalid CA certificate: {e}"))?;
+            builder = builder.add_root_certificate(cert);
+        }
+
+        match (&tls.client_cert_pem, &tls.client_key_pem) {
+            (Some(cert), Some(key)) => {
+                let cert = self
+                    .secrets
+                    .interpolate(cert)
+                    .map_err(|e| format!("client_cert_pem: {e}"))?;
+                let key = self
+                    .secrets
+                    .interpolate(key)
+                    .map_err(|e| format!("client_key_pem: {e}"))?;
+                let identity = Identity::from_pkcs8_pem(cert.as_bytes(), key.as_bytes())
+                    .map_err(|e| format!("Invalid client certificate or key: {e}"))?;
+                builder = builder.identity(identity);
+            }
+            (None, None) => {}
+            _ => return Err("client_cert_pem and client_key_pem must be set together".to_string()),
+        }
+
+        builder
+            .build()
+            .map_err(|e| format!("Failed to build TLS client: {e}"))
+    }
+
+    /// HTTP client for a webhook (shared client unless custom TLS is configured)
+    fn client_for(&self, webhook: &ReiWebhook) -> Result<Client, String> {
+        let Some(tls) = webhook.tls.as_ref().filter(|tls| !tls.is_empty()) else {
+            return Ok(self.client.clone());
+        };
+
+        let mut clients = self.tls_clients.lock().expect("TLS client cache poisoned");
+        if let Some((cached, client)) = clients.get(&webhook.id) {
+            if cached == tls {
+                return Ok(client.clone());
+            }
+        }
+
+        let client = self.build_tls_client(tls)?;
+        clients.insert(webhook.id, (tls.clone(), client.clone()));
+        Ok(client)
+    }
+
+    /// Format the payload and build request headers without sending
+    ///
+    /// With `mask_secrets`, resolved `${secret:name}` values are masked so
+    /// dry runs never echo tokens back.
+    pub fn prepare(
+        &self,
+        webhook: &ReiWebhook,
+        payload: &WebhookPayload,
+        mask_secrets: bool,
+    ) -> Result<PreparedRequest, String> {
+        // Format payload based on webhook configuration
+        let body = formatters::format_for_webhook(webhook, payload)?;
+
+        let mut headers = vec![("Content-Type".to_string(), "application/json".to_string())];
+
+        // Add signature if secret is configured
+        if let Some(secret) = &webhook.secret {
+            let bytes = serde_json::to_vec(&body)
+                .map_err(|e| format!("Failed to serialize payload: {e}"))?;
+            headers.push((
+                "X-Kaiba-Signature".to_string(),
+                self.sign_payload(secret, &bytes),
+            ));
+        }
+
+        // Add custom headers (resolving ${secret:name} references)
+        if let Some(custom) = webhook.headers.as_object() {
+            for (key, value) in custom {
+                if let Some(v) = value.as_str() {
+                    let resolved = if mask_secrets {
+                        self.secrets.interpolate_masked(v)
+                    } else {
+                        self.secrets.interpolate(v)
+                    }
+                    .map_err(|e| format!("Header '{}': {}", key, e))?;
+                    headers.push((key.clone(), resolved));
+                }
+            }
+        }
+
+        Ok(PreparedRequest { body, headers })
     }
 }
 
+/// A formatted webhook request, ready to send
+#[derive(Debug, Clone)]
+pub struct PreparedRequest {
+    pub body: serde_json::Value,
+    pub headers: Vec<(String, String)>,
+}
+
 impl Default for HttpWebhook {
     fn default() -> Self {
         Self::new()
@@ -43,6 +178,7 @@ impl Default for HttpWebhook {
 
 #[async_trait]
 impl TeiWebhook for HttpWebhook {
+    #[tracing::instrument(name = "webhook.deliver", skip_all, fields(webhook_id = %webhook.id))]
     async fn deliver(
         &self,
         webhook: &ReiWebhook,
@@ -50,43 +186,63 @@ impl TeiWebhook for HttpWebhook {
     ) -> Result<WebhookDelivery, DomainError> {
         let mut delivery = WebhookDelivery::new(webhook.id, payload.clone());
 
-        // Format payload based on webhook configuration
-        let formatted = formatters::format_payload(webhook.payload_format.as_deref(), payload);
+        let prepared = match self.prepare(webhook, payload, false) {
+            Ok(prepared) => prepared,
+            Err(e) => {
+                tracing::warn!(
+                    "⚠️  Webhook {} request preparation failed: {}",
+                    webhook.id,
+                    e
+                );
+                return Ok(delivery.failed(None, e));
+            }
+        };
 
         // Serialize payload
-        let body = serde_json::to_vec(&formatted).map_err(|e| {
+        let body = serde_json::to_vec(&prepared.body).map_err(|e| {
             DomainError::ExternalService(format!("Failed to serialize payload: {e}"))
         })?;
 
+        let client = match self.client_for(webhook) {
+            Ok(client) => client,
+            Err(e) => {
+                tracing::warn!("⚠️  Webhook {} TLS setup failed: {}", webhook.id, e);
+                return Ok(delivery.failed(None, e));
+            }
+        };
+
         // Build request
-        let mut request = self
-            .client
+        let mut request = client
             .post(&webhook.url)
-            .header("Content-Type", "application/json")
             .timeout(Duration::from_millis(webhook.timeout_ms as u64));
-
-        // Add signature if secret is configured
-        if let Some(secret) = &webhook.secret {
-            let signature = self.sign_payload(secret, &body);
-            request = request.header("X-Kaiba-Signature", signature);
+        for (key, value) in &prepared.headers {
+            request = request.header(key, value);
         }
 
-        // Add custom headers
-        if let Some(headers) = webhook.headers.as_object() {
-            for (key, value) in headers {
-                if let Some(v) = value.as_str() {
-                    request = request.header(key, v);
-                }
+        // Queue behind earlier deliveries if the webhook is rate limited
+        if let Some(per_minute) = webhook.rate_limit_per_minute.filter(|n| *n > 0) {
+            let waited = self
+                .rate_limiter
+                .acquire(webhook.id, per_minute as u32)
+                .await;
+            if !waited.is_zero() {
+                tracing::debug!(
+                    "⏳ Webhook {} rate limited, waited {}ms",
+                    webhook.id,
+                    waited.as_millis()
+                );
             }
         }
 
         // Send request
+        let started = Instant::now();
         let response = request.body(body).send().await;
 
         match response {
             Ok(resp) => {
                 let status_code = resp.status().as_u16() as i32;
                 let response_body = resp.text().await.ok();
+                delivery = delivery.with_duration(started.elapsed());
 
                 if (200..300).contains(&status_code) {
                     delivery = delivery.success(status_code, response_body);
@@ -98,7 +254,9 @@ impl TeiWebhook for HttpWebhook {
                 }
             }
             Err(e) => {
-                delivery = delivery.failed(None, e.to_string());
+                delivery = delivery
+                    .with_duration(started.elapsed())
+                    .failed(None, e.to_string());
             }
         }
 
@@ -131,12 +289,11 @@ impl TeiWebhook for HttpWebhook {
             delivery.status_code = result.status_code;
             delivery.response_body = result.response_body;
             delivery.attempts = result.attempts;
+            delivery.duration_ms = result.duration_ms;
         }
 
-        // All retries exhausted
-        delivery.status = DeliveryStatus::Failed;
-        delivery.completed_at = Some(chrono::Utc::now());
-        Ok(delivery)
+        // All retries exhausted - move to dead-letter
+        Ok(delivery.dead_letter())
     }
 
     async fn verify_endpoint(&self, url: &str) -> Result<bool, DomainError> {
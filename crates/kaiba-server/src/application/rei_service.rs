@@ -2,11 +2,14 @@
 //!
 //! Orchestrates domain operations for Rei management.
 
+use chrono::{DateTime, Utc};
 use std::sync::Arc;
 use uuid::Uuid;
 
 use kaiba::{DomainError, Rei, ReiRepository, ReiState};
 
+use crate::services::ownership::InsertOwned;
+
 /// Application service for Rei operations
 pub struct ReiService<R: ReiRepository> {
     repo: Arc<R>,
@@ -50,24 +53,10 @@ impl<R: ReiRepository> ReiService<R> {
         Ok(Some((rei, state)))
     }
 
-    /// Create a new Rei with initial state
-    pub async fn create(
-        &self,
-        name: String,
-        role: String,
-        avatar_url: Option<String>,
-        manifest: Option<serde_json::Value>,
-    ) -> Result<(Rei, ReiState), DomainError> {
-        let rei = Rei::new(name, role, avatar_url, manifest);
-        let saved_rei = self.repo.save(&rei).await?;
-        let state = self.repo.create_state(saved_rei.id).await?;
-
-        tracing::info!("Created Rei: {} ({})", saved_rei.name, saved_rei.id);
-
-        Ok((saved_rei, state))
-    }
-
     /// Update a Rei
+    ///
+    /// With `expected_updated_at`, the update fails with `Conflict` if the
+    /// Rei was changed since the caller read it.
     pub async fn update(
         &self,
         id: Uuid,
@@ -75,6 +64,7 @@ impl<R: ReiRepository> ReiService<R> {
         role: Option<String>,
         avatar_url: Option<String>,
         manifest: Option<serde_json::Value>,
+        expected_updated_at: Option<DateTime<Utc>>,
     ) -> Result<(Rei, ReiState), DomainError> {
         let current = self
             .repo
@@ -92,7 +82,19 @@ impl<R: ReiRepository> ReiService<R> {
             updated_at: chrono::Utc::now(),
         };
 
-        let saved = self.repo.save(&updated).await?;
+        let saved = match expected_updated_at {
+            Some(expected) => self
+                .repo
+                .update_if_unchanged(&updated, expected)
+                .await?
+                .ok_or_else(|| {
+                    DomainError::Conflict(format!(
+                        "Rei {} was modified since it was read; reload and retry",
+                        id
+                    ))
+                })?,
+            None => self.repo.save(&updated).await?,
+        };
         let state = self
             .repo
             .find_state(saved.id)
@@ -102,7 +104,7 @@ impl<R: ReiRepository> ReiService<R> {
         Ok((saved, state))
     }
 
-    /// Delete a Rei
+    /// Soft-delete a Rei (restorable until purged)
     pub async fn delete(&self, id: Uuid) -> Result<bool, DomainError> {
         let deleted = self.repo.delete(id).await?;
         if deleted {
@@ -111,6 +113,20 @@ impl<R: ReiRepository> ReiService<R> {
         Ok(deleted)
     }
 
+    /// Restore a soft-deleted Rei
+    pub async fn restore(&self, id: Uuid) -> Result<Option<(Rei, ReiState)>, DomainError> {
+        if !self.repo.restore(id).await? {
+            return Ok(None);
+        }
+        tracing::info!("Restored Rei: {}", id);
+        self.get_by_id(id).await
+    }
+
+    /// Permanently delete Reis soft-deleted before `cutoff`
+    pub async fn purge_deleted(&self, cutoff: DateTime<Utc>) -> Result<Vec<Uuid>, DomainError> {
+        self.repo.purge_deleted(cutoff).await
+    }
+
     /// Get Rei state
     pub async fn get_state(&self, rei_id: Uuid) -> Result<Option<ReiState>, DomainError> {
         self.repo.find_state(rei_id).await
@@ -144,8 +160,46 @@ impl<R: ReiRepository> ReiService<R> {
             energy_regen_per_hour: energy_regen_per_hour.unwrap_or(current.energy_regen_per_hour),
             last_digest_at: current.last_digest_at,
             last_learn_at: current.last_learn_at,
+            autonomy_enabled: current.autonomy_enabled,
         };
 
         self.repo.save_state(&updated).await
     }
+
+    /// Pause (`false`) or resume (`true`) the Rei's autonomous actions
+    pub async fn set_autonomy(
+        &self,
+        rei_id: Uuid,
+        enabled: bool,
+    ) -> Result<Option<ReiState>, DomainError> {
+        let state = self.repo.set_autonomy(rei_id, enabled).await?;
+        if state.is_some() {
+            tracing::info!(
+                "{} autonomy of Rei: {}",
+                if enabled { "Resumed" } else { "Paused" },
+                rei_id
+            );
+        }
+        Ok(state)
+    }
+}
+
+impl<R: ReiRepository + InsertOwned<Rei>> ReiService<R> {
+    /// Create a new Rei with initial state, owned by `owner_id` if set
+    pub async fn create(
+        &self,
+        name: String,
+        role: String,
+        avatar_url: Option<String>,
+        manifest: Option<serde_json::Value>,
+        owner_id: Option<Uuid>,
+    ) -> Result<(Rei, ReiState), DomainError> {
+        let rei = Rei::new(name, role, avatar_url, manifest);
+        let saved_rei = self.repo.insert_owned(&rei, owner_id).await?;
+        let state = self.repo.create_state(saved_rei.id).await?;
+
+        tracing::info!("Created Rei: {} ({})", saved_rei.name, saved_rei.id);
+
+        Ok((saved_rei, state))
+    }
 }
@@ -2,11 +2,14 @@
 //!
 //! Orchestrates domain operations for Tei management.
 
+use chrono::{DateTime, Utc};
 use std::sync::Arc;
 use uuid::Uuid;
 
 use kaiba::{DomainError, Provider, ReiTei, Tei, TeiRepository};
 
+use crate::services::ownership::InsertOwned;
+
 /// Application service for Tei operations
 pub struct TeiService<R: TeiRepository> {
     repo: Arc<R>,
@@ -27,40 +30,10 @@ impl<R: TeiRepository> TeiService<R> {
         self.repo.find_by_id(id).await
     }
 
-    /// Create a new Tei
-    #[allow(clippy::too_many_arguments)]
-    pub async fn create(
-        &self,
-        name: String,
-        provider: Provider,
-        model_id: String,
-        is_fallback: bool,
-        priority: i32,
-        config: Option<serde_json::Value>,
-        expertise: Option<serde_json::Value>,
-    ) -> Result<Tei, DomainError> {
-        let tei = Tei::new(
-            name,
-            provider,
-            model_id,
-            is_fallback,
-            priority,
-            config,
-            expertise,
-        );
-        let saved = self.repo.save(&tei).await?;
-
-        tracing::info!(
-            "Created Tei: {} ({}) - {}",
-            saved.name,
-            saved.id,
-            saved.model_id
-        );
-
-        Ok(saved)
-    }
-
     /// Update a Tei
+    ///
+    /// With `expected_updated_at`, the update fails with `Conflict` if the
+    /// Tei was changed since the caller read it.
     #[allow(clippy::too_many_arguments)]
     pub async fn update(
         &self,
@@ -72,6 +45,7 @@ impl<R: TeiRepository> TeiService<R> {
         priority: Option<i32>,
         config: Option<serde_json::Value>,
         expertise: Option<serde_json::Value>,
+        expected_updated_at: Option<DateTime<Utc>>,
     ) -> Result<Tei, DomainError> {
         let current = self
             .repo
@@ -92,7 +66,19 @@ impl<R: TeiRepository> TeiService<R> {
             updated_at: chrono::Utc::now(),
         };
 
-        self.repo.save(&updated).await
+        match expected_updated_at {
+            Some(expected) => self
+                .repo
+                .update_if_unchanged(&updated, expected)
+                .await?
+                .ok_or_else(|| {
+                    DomainError::Conflict(format!(
+                        "Tei {} was modified since it was read; reload and retry",
+                        id
+                    ))
+                }),
+            None => self.repo.save(&updated).await,
+        }
     }
 
     /// Delete a Tei
@@ -164,3 +150,39 @@ impl<R: TeiRepository> TeiService<R> {
         Ok(removed)
     }
 }
+
+impl<R: TeiRepository + InsertOwned<Tei>> TeiService<R> {
+    /// Create a new Tei, owned by `owner_id` if set
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create(
+        &self,
+        name: String,
+        provider: Provider,
+        model_id: String,
+        is_fallback: bool,
+        priority: i32,
+        config: Option<serde_json::Value>,
+        expertise: Option<serde_json::Value>,
+        owner_id: Option<Uuid>,
+    ) -> Result<Tei, DomainError> {
+        let tei = Tei::new(
+            name,
+            provider,
+            model_id,
+            is_fallback,
+            priority,
+            config,
+            expertise,
+        );
+        let saved = self.repo.insert_owned(&tei, owner_id).await?;
+
+        tracing::info!(
+            "Created Tei: {} ({}) - {}",
+            saved.name,
+            saved.id,
+            saved.model_id
+        );
+
+        Ok(saved)
+    }
+}
@@ -0,0 +1,192 @@
+//! Audit Log - Who changed what, and when
+//!
+//! Every successful request that isn't a read is recorded in `audit_logs`
+//! with the caller, the route and the entity it touched. The entity is the
+//! last fixed segment of the route (`/kaiba/rei/:id/memories/:memory_id` →
+//! `memories`); its id comes from the path, or from the `id` of the created
+//! resource for POSTs to a collection. Rows are written in the background so
+//! auditing never delays or fails the request itself.
+
+use axum::{
+    body::{Body, HttpBody},
+    extract::{MatchedPath, Request, State},
+    http::{header, Method},
+    middleware::Next,
+    response::Response,
+};
+use uuid::Uuid;
+
+use crate::auth::Principal;
+use crate::telemetry::REQUEST_ID_HEADER;
+use crate::AppState;
+
+/// Largest response body searched for the id of a created resource
+const MAX_ID_BODY_BYTES: u64 = 64 * 1024;
+
+/// Entity a request touched, from its route template and actual path
+///
+/// Returns the last fixed route segment and, if the route ends in a
+/// parameter, that parameter parsed as a UUID.
+pub fn entity_of(route: &str, path: &str) -> (String, Option<Uuid>) {
+    let route_segments: Vec<&str> = route.trim_matches('/').split('/').collect();
+    let path_segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+    let entity_type = route_segments
+        .iter()
+        .rev()
+        .find(|s| !s.starts_with(':') && **s != "kaiba")
+        .copied()
+        .unwrap_or("kaiba")
+        .to_string();
+
+    let entity_id = match route_segments.last() {
+        Some(last) if last.starts_with(':') => path_segments.last().and_then(|s| s.parse().ok()),
+        _ => None,
+    };
+
+    (entity_type, entity_id)
+}
+
+/// Last UUID in a path (`/kaiba/rei/{id}/state` → the Rei)
+fn parent_id(path: &str) -> Option<Uuid> {
+    path.split('/').rev().find_map(|s| s.parse().ok())
+}
+
+pub async fn audit_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let method = request.method().clone();
+    if matches!(method, Method::GET | Method::HEAD | Method::OPTIONS) {
+        return next.run(request).await;
+    }
+
+    let path = request.uri().path().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|m| m.as_str().to_string())
+        .unwrap_or_else(|| path.clone());
+    let (actor, api_key_id) = match request.extensions().get::<Principal>() {
+        Some(principal @ Principal::Key(key)) => (principal.label().to_string(), Some(key.id)),
+        Some(principal) => (principal.label().to_string(), None),
+        None => ("anonymous".to_string(), None),
+    };
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+
+    let mut response = next.run(request).await;
+    if !response.status().is_success() {
+        return response;
+    }
+
+    let (entity_type, mut entity_id) = entity_of(&route, &path);
+    if entity_id.is_none() && method == Method::POST {
+        let (id, body) = created_id(response).await;
+        response = body;
+        entity_id = id;
+    }
+    let entity_id = entity_id.or_else(|| parent_id(&path));
+    let status = i32::from(response.status().as_u16());
+
+    let pool = state.pool.clone();
+    state.tasks.spawn(async move {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO audit_logs
+                (actor, api_key_id, method, route, path, entity_type, entity_id, status, request_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+        )
+        .bind(&actor)
+        .bind(api_key_id)
+        .bind(method.as_str())
+        .bind(&route)
+        .bind(&path)
+        .bind(&entity_type)
+        .bind(entity_id)
+        .bind(status)
+        .bind(&request_id)
+        .execute(&pool)
+        .await;
+
+        if let Err(e) = result {
+            tracing::warn!("Failed to write audit log for {} {}: {}", method, path, e);
+        }
+    });
+
+    response
+}
+
+/// `id` of a created resource, read from a small JSON response body
+async fn created_id(response: Response) -> (Option<Uuid>, Response) {
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"));
+    let small = response
+        .body()
+        .size_hint()
+        .exact()
+        .is_some_and(|len| len <= MAX_ID_BODY_BYTES);
+    if !is_json || !small {
+        return (None, response);
+    }
+
+    let (parts, body) = response.into_parts();
+    let Ok(bytes) = axum::body::to_bytes(body, MAX_ID_BODY_BYTES as usize).await else {
+        // Only reachable if the size hint lied; the body is gone either way
+        return (None, Response::from_parts(parts, Body::empty()));
+    };
+    let id = serde_json::from_slice::<serde_json::Value>(&bytes)
+        .ok()
+        .and_then(|v| v.get("id")?.as_str()?.parse().ok());
+    (id, Response::from_parts(parts, Body::from(bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REI: &str = "6f1c1a9e-3f5e-4a8e-9a43-2b1c5d7e8f90";
+    const MEMORY: &str = "0b6d7a52-5c0e-4d4b-8f53-1e2a3b4c5d6e";
+
+    #[test]
+    fn test_entity_from_trailing_param() {
+        let (entity, id) = entity_of("/kaiba/rei/:id", &format!("/kaiba/rei/{}", REI));
+        assert_eq!(entity, "rei");
+        assert_eq!(id, REI.parse().ok());
+
+        let (entity, id) = entity_of(
+            "/kaiba/rei/:rei_id/memories/:memory_id",
+            &format!("/kaiba/rei/{}/memories/{}", REI, MEMORY),
+        );
+        assert_eq!(entity, "memories");
+        assert_eq!(id, MEMORY.parse().ok());
+    }
+
+    #[test]
+    fn test_entity_without_trailing_id() {
+        let path = format!("/kaiba/rei/{}/state", REI);
+        let (entity, id) = entity_of("/kaiba/rei/:id/state", &path);
+        assert_eq!(entity, "state");
+        assert_eq!(id, None);
+        assert_eq!(parent_id(&path), REI.parse().ok());
+
+        // Non-UUID parameters (event names, inbound sources) are not ids
+        let path = format!("/kaiba/rei/{}/events/deploy_done", REI);
+        let (entity, id) = entity_of("/kaiba/rei/:rei_id/events/:name", &path);
+        assert_eq!(entity, "events");
+        assert_eq!(id, None);
+
+        let (entity, id) = entity_of("/kaiba/rei", "/kaiba/rei");
+        assert_eq!(entity, "rei");
+        assert_eq!(id, None);
+        assert_eq!(parent_id("/kaiba/rei"), None);
+    }
+}
@@ -1,11 +1,35 @@
-//! Simple API Key Authentication (Bearer Token)
+//! API Key Authentication (Bearer Token)
+//!
+//! The master key (`KAIBA_API_KEY`) has full access. Keys created through
+//! `/kaiba/api-keys` carry a scope and may be restricted to some Reis; the
+//! scope a request needs is derived from its method and path. Keys bound to
+//! a user are further limited to that user's resources (see
+//! [`crate::services::ownership`]).
+//!
+//! Inbound webhooks and chat platforms skip API keys; their routes check
+//! the platform's signature with [`verify_webhook`] instead.
 
 use axum::{
-    extract::Request,
-    http::{header, StatusCode},
+    extract::{Request, State},
+    http::{header, HeaderMap, Method, StatusCode},
     middleware::Next,
     response::Response,
 };
+use kaiba::{VerificationError, WebhookHeaders, WebhookVerifier};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::error::ApiError;
+use crate::models::{ApiKey, ApiKeyScope};
+use crate::services::inbound::verify_token;
+use crate::services::ownership::{self, TenantAccess};
+use crate::AppState;
+
+/// Prefix of generated keys, so they are recognizable in configs and logs
+const KEY_PREFIX: &str = "kb_";
+
+/// Characters of a key kept for display
+const DISPLAY_PREFIX_LEN: usize = 11;
 
 /// API Key from environment/secrets
 static API_KEY: std::sync::OnceLock<String> = std::sync::OnceLock::new();
@@ -20,9 +44,137 @@ fn get_api_key() -> Option<&'static str> {
     API_KEY.get().map(|s| s.as_str())
 }
 
+/// Who made the request, available to handlers as an extension
+#[derive(Debug, Clone)]
+pub enum Principal {
+    /// The master key
+    Master,
+    /// A scoped key
+    Key(ApiKey),
+}
+
+impl Principal {
+    /// Name for logs
+    pub fn label(&self) -> &str {
+        match self {
+            Principal::Master => "master key",
+            Principal::Key(key) => &key.name,
+        }
+    }
+
+    /// User whose resources the caller is limited to (`None` sees everything)
+    pub fn owner(&self) -> Option<Uuid> {
+        match self {
+            Principal::Master => None,
+            Principal::Key(key) => key.user_id,
+        }
+    }
+}
+
+/// Generate a new key: `kb_` followed by 64 random hex characters
+pub fn generate_key() -> String {
+    format!(
+        "{}{}{}",
+        KEY_PREFIX,
+        Uuid::new_v4().simple(),
+        Uuid::new_v4().simple()
+    )
+}
+
+/// Leading characters of a key shown in listings
+pub fn display_prefix(key: &str) -> String {
+    key.chars().take(DISPLAY_PREFIX_LEN).collect()
+}
+
+/// SHA-256 of a key (hex), as stored in `api_keys.key_hash`
+pub fn hash_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+/// Scope needed for a request
+///
+/// Reads (and memory search, which is a POST) need `read`; adding or
+/// editing memories and calling a Rei need `memory_write`; anything else
+/// changes configuration and needs `admin`.
+pub fn required_scope(method: &Method, path: &str) -> ApiKeyScope {
+    if path.starts_with("/kaiba/api-keys")
+        || path.starts_with("/kaiba/users")
+        || path.starts_with("/kaiba/admin")
+    {
+        return ApiKeyScope::Admin;
+    }
+    if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+        return ApiKeyScope::Read;
+    }
+
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    match segments.as_slice() {
+        ["kaiba", "rei", _, "memories", "search"] => ApiKeyScope::Read,
+        ["kaiba", "rei", _, "memories", ..] | ["kaiba", "rei", _, "call"] => {
+            ApiKeyScope::MemoryWrite
+        }
+        _ => ApiKeyScope::Admin,
+    }
+}
+
+/// Rei a request targets (`/kaiba/rei/{rei_id}/...`)
+pub fn rei_id_from_path(path: &str) -> Option<Uuid> {
+    path.strip_prefix("/kaiba/rei/")?
+        .split('/')
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// Check a scoped key against the request; returns the reason when denied
+pub fn authorize(key: &ApiKey, method: &Method, path: &str) -> Result<(), &'static str> {
+    if !key.scope().allows(required_scope(method, path)) {
+        return Err("insufficient scope");
+    }
+    if !key.rei_ids.is_empty() {
+        // Restricted keys only reach their own Reis, never cross-Rei endpoints
+        match rei_id_from_path(path) {
+            Some(rei_id) if key.allows_rei(rei_id) => {}
+            _ => return Err("Rei not allowed for this key"),
+        }
+    }
+    Ok(())
+}
+
+/// Check an inbound webhook with its platform's verifier
+///
+/// Any bad, missing or stale signature is `401 invalid_signature`; a
+/// verifier without its secret or key is `503`.
+pub fn verify_webhook(
+    source: &str,
+    verifier: &dyn WebhookVerifier,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Result<(), ApiError> {
+    let headers: WebhookHeaders = headers
+        .iter()
+        .filter_map(|(name, value)| Some((name.as_str(), value.to_str().ok()?)))
+        .collect();
+
+    verifier.verify(&headers, body).map_err(|e| {
+        tracing::warn!("🚫 Rejected {} webhook: {}", source, e);
+        match e {
+            VerificationError::NotConfigured(_) => {
+                ApiError::unavailable(format!("{} webhook verification not configured", source))
+            }
+            _ => ApiError::new(StatusCode::UNAUTHORIZED, "Invalid signature")
+                .with_code("invalid_signature"),
+        }
+    })
+}
+
 /// Authentication middleware
-/// Validates Bearer token against the API key
-pub async fn auth_middleware(request: Request, next: Next) -> Result<Response, StatusCode> {
+/// Validates the Bearer token against the master key, then scoped keys
+pub async fn auth_middleware(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
     // Get API key
     let api_key = match get_api_key() {
         Some(key) if !key.is_empty() => key,
@@ -34,28 +186,176 @@ pub async fn auth_middleware(request: Request, next: Next) -> Result<Response, S
     };
 
     // Extract Authorization header
-    let auth_header = request
+    let token = match request
         .headers()
         .get(header::AUTHORIZATION)
-        .and_then(|h| h.to_str().ok());
-
-    match auth_header {
-        Some(header) if header.starts_with("Bearer ") => {
-            let token = &header[7..]; // Remove "Bearer " prefix
-            if token == api_key {
-                Ok(next.run(request).await)
-            } else {
-                tracing::warn!("Invalid API key attempted");
-                Err(StatusCode::UNAUTHORIZED)
-            }
-        }
+        .and_then(|h| h.to_str().ok())
+    {
+        // Remove "Bearer " prefix
+        Some(header) if header.starts_with("Bearer ") => header[7..].to_string(),
         Some(_) => {
             tracing::warn!("Invalid Authorization header format");
-            Err(StatusCode::UNAUTHORIZED)
+            return Err(ApiError::new(
+                StatusCode::UNAUTHORIZED,
+                "Expected 'Authorization: Bearer <key>'",
+            ));
         }
         None => {
             tracing::warn!("Missing Authorization header");
-            Err(StatusCode::UNAUTHORIZED)
+            return Err(ApiError::new(
+                StatusCode::UNAUTHORIZED,
+                "Missing Authorization header",
+            ));
         }
+    };
+
+    if verify_token(api_key, &token) {
+        request.extensions_mut().insert(Principal::Master);
+        return Ok(next.run(request).await);
+    }
+
+    // Lookup also records use, in one round trip
+    let key = sqlx::query_as::<_, ApiKey>(
+        r#"
+        UPDATE api_keys SET last_used_at = NOW()
+        WHERE key_hash = $1 AND revoked_at IS NULL
+        RETURNING *
+        "#,
+    )
+    .bind(hash_key(&token))
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let Some(key) = key else {
+        tracing::warn!("Invalid API key attempted");
+        return Err(ApiError::new(StatusCode::UNAUTHORIZED, "Invalid API key"));
+    };
+
+    if let Err(reason) = authorize(&key, request.method(), request.uri().path()) {
+        tracing::warn!(
+            "API key '{}' denied {} {}: {}",
+            key.name,
+            request.method(),
+            request.uri().path(),
+            reason
+        );
+        return Err(ApiError::new(StatusCode::FORBIDDEN, reason));
+    }
+
+    if let Some(user_id) = key.user_id {
+        match ownership::tenant_access(request.uri().path()) {
+            TenantAccess::Collection => {}
+            TenantAccess::OperatorOnly => {
+                tracing::warn!(
+                    "API key '{}' denied {}: operator-only endpoint",
+                    key.name,
+                    request.uri().path()
+                );
+                return Err(ApiError::new(
+                    StatusCode::FORBIDDEN,
+                    "Only operator keys can use this endpoint",
+                ));
+            }
+            TenantAccess::Resource(owned, id) => {
+                let owns = ownership::is_owner(&state.pool, owned, id, user_id)
+                    .await
+                    .map_err(ApiError::internal)?;
+                // Other users' resources look like they don't exist
+                if !owns {
+                    return Err(ApiError::not_found("Not found"));
+                }
+            }
+        }
+    }
+
+    request.extensions_mut().insert(Principal::Key(key));
+    Ok(next.run(request).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(scope: ApiKeyScope, rei_ids: Vec<Uuid>) -> ApiKey {
+        ApiKey {
+            id: Uuid::new_v4(),
+            name: "test".to_string(),
+            key_prefix: "kb_0000".to_string(),
+            scope: scope.to_string(),
+            rei_ids,
+            created_at: chrono::Utc::now(),
+            last_used_at: None,
+            revoked_at: None,
+            user_id: None,
+        }
+    }
+
+    #[test]
+    fn test_required_scope() {
+        let rei = "/kaiba/rei/6f1c0d1e-8a5b-4a7e-9c1d-2b3e4f5a6b7c";
+        assert_eq!(
+            required_scope(&Method::GET, &format!("{}/memories", rei)),
+            ApiKeyScope::Read
+        );
+        assert_eq!(
+            required_scope(&Method::POST, &format!("{}/memories/search", rei)),
+            ApiKeyScope::Read
+        );
+        assert_eq!(
+            required_scope(&Method::POST, &format!("{}/memories", rei)),
+            ApiKeyScope::MemoryWrite
+        );
+        assert_eq!(
+            required_scope(&Method::POST, &format!("{}/call", rei)),
+            ApiKeyScope::MemoryWrite
+        );
+        assert_eq!(
+            required_scope(&Method::PUT, &format!("{}/state", rei)),
+            ApiKeyScope::Admin
+        );
+        assert_eq!(required_scope(&Method::DELETE, rei), ApiKeyScope::Admin);
+        assert_eq!(
+            required_scope(&Method::GET, "/kaiba/api-keys"),
+            ApiKeyScope::Admin
+        );
+        assert_eq!(
+            required_scope(&Method::GET, "/kaiba/users"),
+            ApiKeyScope::Admin
+        );
+        assert_eq!(
+            required_scope(&Method::GET, "/kaiba/admin/config"),
+            ApiKeyScope::Admin
+        );
+    }
+
+    #[test]
+    fn test_authorize() {
+        let allowed = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let path = |id: Uuid| format!("/kaiba/rei/{}/memories", id);
+
+        let writer = key(ApiKeyScope::MemoryWrite, vec![allowed]);
+        assert!(authorize(&writer, &Method::POST, &path(allowed)).is_ok());
+        assert!(authorize(&writer, &Method::POST, &path(other)).is_err());
+        assert!(authorize(&writer, &Method::GET, "/kaiba/rei").is_err());
+        assert!(authorize(&writer, &Method::DELETE, &format!("/kaiba/rei/{}", allowed)).is_err());
+
+        let reader = key(ApiKeyScope::Read, vec![]);
+        assert!(authorize(&reader, &Method::GET, "/kaiba/rei").is_ok());
+        assert!(authorize(&reader, &Method::POST, &path(other)).is_err());
+
+        let admin = key(ApiKeyScope::Admin, vec![]);
+        assert!(authorize(&admin, &Method::POST, "/kaiba/api-keys").is_ok());
+    }
+
+    #[test]
+    fn test_generated_key() {
+        let key = generate_key();
+        assert!(key.starts_with(KEY_PREFIX));
+        assert_eq!(key.len(), KEY_PREFIX.len() + 64);
+        assert_eq!(display_prefix(&key).len(), DISPLAY_PREFIX_LEN);
+        assert_eq!(hash_key(&key), hash_key(&key));
+        assert_ne!(hash_key(&key), hash_key(&generate_key()));
     }
 }
@@ -0,0 +1,165 @@
+//! Bulk Operations - Many creates in one request
+//!
+//! Bulk endpoints take a JSON array of the payloads the single-item endpoint
+//! accepts and answer `200` with one result per item, in request order. Items
+//! are created one by one and independently: an invalid or failing item is
+//! reported with its problem details and does not stop the others, and items
+//! created before a failure are kept.
+
+use async_trait::async_trait;
+use axum::{
+    extract::{FromRequest, Request},
+    http::StatusCode,
+    Json,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use utoipa::ToSchema;
+
+use crate::error::{ApiError, FieldError, Problem};
+use crate::validation::Validate;
+
+/// Most items accepted in one bulk request
+pub const MAX_BULK_ITEMS: usize = 100;
+
+/// Array body whose items are deserialized and validated one by one
+pub struct BulkJson<T>(pub Vec<Result<T, ApiError>>);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for BulkJson<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(items) = Json::<Vec<serde_json::Value>>::from_request(req, state)
+            .await
+            .map_err(|rejection| ApiError::new(rejection.status(), rejection.body_text()))?;
+
+        if items.is_empty() {
+            return Err(ApiError::bad_request("Bulk request has no items"));
+        }
+        if items.len() > MAX_BULK_ITEMS {
+            return Err(ApiError::bad_request(format!(
+                "Bulk request has {} items; at most {} are accepted",
+                items.len(),
+                MAX_BULK_ITEMS
+            )));
+        }
+
+        Ok(Self(items.into_iter().map(parse_item).collect()))
+    }
+}
+
+fn parse_item<T: DeserializeOwned + Validate>(item: serde_json::Value) -> Result<T, ApiError> {
+    let payload: T = serde_json::from_value(item).map_err(|e| {
+        ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            format!("Invalid item: {}", e),
+        )
+    })?;
+
+    let mut errors: Vec<FieldError> = Vec::new();
+    payload.validate(&mut errors);
+    if !errors.is_empty() {
+        return Err(ApiError::validation(errors));
+    }
+    Ok(payload)
+}
+
+/// Outcome of one item of a bulk request
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BulkItemResult {
+    /// Position of the item in the request
+    pub index: usize,
+    /// Status the single-item endpoint would have answered
+    pub status: u16,
+    /// URL of the created resource
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    /// Created resource (successes only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub data: Option<serde_json::Value>,
+    /// Problem details (failures only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<Problem>,
+}
+
+/// Results of a bulk request
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub struct BulkResponse {
+    pub succeeded: usize,
+    pub failed: usize,
+    pub results: Vec<BulkItemResult>,
+}
+
+impl BulkResponse {
+    /// Record an item created at `location`
+    pub fn created<T: Serialize>(&mut self, location: String, data: T) {
+        self.succeeded += 1;
+        self.results.push(BulkItemResult {
+            index: self.results.len(),
+            status: StatusCode::CREATED.as_u16(),
+            location: Some(location),
+            data: serde_json::to_value(data).ok(),
+            error: None,
+        });
+    }
+
+    /// Record an item that failed
+    pub fn failed(&mut self, err: ApiError) {
+        let problem = Problem::from(&err);
+        self.failed += 1;
+        self.results.push(BulkItemResult {
+            index: self.results.len(),
+            status: problem.status,
+            location: None,
+            data: None,
+            error: Some(problem),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::CreateTeiRequest;
+    use serde_json::json;
+
+    #[test]
+    fn test_items_fail_independently() {
+        let ok = parse_item::<CreateTeiRequest>(json!({
+            "name": "claude",
+            "provider": "anthropic",
+            "model_id": "claude-sonnet-4",
+        }));
+        let invalid = parse_item::<CreateTeiRequest>(json!({
+            "name": "",
+            "provider": "anthropic",
+            "model_id": "claude-sonnet-4",
+        }));
+        let malformed = parse_item::<CreateTeiRequest>(json!({"name": "no provider"}));
+
+        let mut response = BulkResponse::default();
+        for item in [ok, invalid, malformed] {
+            match item {
+                Ok(payload) => {
+                    response.created(format!("/kaiba/tei/{}", payload.name), payload.name)
+                }
+                Err(err) => response.failed(err),
+            }
+        }
+
+        assert_eq!((response.succeeded, response.failed), (1, 2));
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["results"][0]["status"], 201);
+        assert_eq!(json["results"][0]["location"], "/kaiba/tei/claude");
+        assert!(json["results"][0].get("error").is_none());
+        assert_eq!(json["results"][1]["index"], 1);
+        assert_eq!(json["results"][1]["error"]["code"], "validation_failed");
+        assert_eq!(json["results"][1]["error"]["errors"][0]["field"], "name");
+        assert_eq!(json["results"][2]["status"], 422);
+    }
+}
@@ -0,0 +1,576 @@
+//! Server Configuration - Typed settings from secrets and the environment
+//!
+//! Settings are read once at startup from the Shuttle secret store, with
+//! plain environment variables as a fallback so the same binary runs in a
+//! container (`docker run -e QDRANT_URL=...`). Secret-store values win when
+//! a key is set in both. Malformed values fail startup instead of silently
+//! falling back to a default.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use kaiba::{DeliveryRetention, ReiEventSubscriber};
+use kaiba_integration_discord::{DiscordConfig, DiscordEventSubscriber, DiscordWebhookHandler};
+use kaiba_integration_email::EmailConfig;
+use kaiba_integration_github::GitHubConfig;
+use kaiba_integration_matrix::MatrixConfig;
+use kaiba_integration_telegram::TelegramConfig;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::adapters::WebhookSecrets;
+use crate::body_limit::BodyLimits;
+use crate::rate_limit::RateLimits;
+use crate::services::digest::DigestThreshold;
+use crate::services::parallel::Parallelism;
+
+/// Shortest accepted scheduler interval
+const MIN_LEARNING_INTERVAL_SECS: u64 = 60;
+
+/// Shown instead of secret values
+const REDACTED: &str = "********";
+
+fn default_learning_interval() -> u64 {
+    3600
+}
+
+fn default_scheduler_max_concurrency() -> usize {
+    Parallelism::default().max_concurrent
+}
+
+fn default_scheduler_rei_timeout_secs() -> u64 {
+    Parallelism::default().rei_timeout.as_secs()
+}
+
+fn default_digest_min_memories() -> usize {
+    DigestThreshold::default().min_memories
+}
+
+fn default_digest_min_chars() -> usize {
+    DigestThreshold::default().min_chars
+}
+
+fn default_rei_retention_days() -> u32 {
+    30
+}
+
+fn default_max_body_bytes() -> usize {
+    BodyLimits::default().default
+}
+
+fn default_max_bulk_body_bytes() -> usize {
+    BodyLimits::default().bulk
+}
+
+fn default_rate_call() -> u32 {
+    RateLimits::default().call
+}
+
+fn default_rate_learn() -> u32 {
+    RateLimits::default().learn
+}
+
+fn default_rate_search() -> u32 {
+    RateLimits::default().search
+}
+
+/// All server settings; keys are the upper-cased field names
+#[derive(Debug, Clone, Deserialize)]
+pub struct ServerConfig {
+    /// Master API key (unset = authentication disabled)
+    pub kaiba_api_key: Option<String>,
+    pub qdrant_url: Option<String>,
+    pub qdrant_api_key: Option<String>,
+    /// Embeddings
+    pub openai_api_key: Option<String>,
+    /// Web search and digests
+    pub gemini_api_key: Option<String>,
+    /// Bot token for the Discord gateway (unset = Discord disabled)
+    pub discord_bot_token: Option<String>,
+    /// Registers `/kaiba` slash commands when set
+    pub discord_application_id: Option<u64>,
+    /// Verifies interaction webhooks (unset = interactions rejected)
+    pub discord_public_key: Option<String>,
+    /// Register slash commands in this guild only (instant) instead of globally
+    pub discord_guild_id: Option<u64>,
+    /// Reaction that bookmarks a Discord message as a memory (default 🧠)
+    pub discord_bookmark_emoji: Option<String>,
+    /// Channel Rei events are posted to (unset = no event posts)
+    pub discord_events_channel_id: Option<u64>,
+    /// Bot token for Telegram (unset = Telegram disabled)
+    pub telegram_bot_token: Option<String>,
+    /// Receive Telegram updates by webhook with this secret instead of polling
+    pub telegram_webhook_secret: Option<String>,
+    /// IMAP server polled for mail to Reis (unset = email disabled)
+    pub email_imap_host: Option<String>,
+    /// IMAP port (default 993, TLS)
+    pub email_imap_port: Option<u16>,
+    /// SMTP server replies are sent through
+    pub email_smtp_host: Option<String>,
+    /// SMTP port (default 587, STARTTLS)
+    pub email_smtp_port: Option<u16>,
+    /// Login for both mail servers
+    pub email_username: Option<String>,
+    pub email_password: Option<String>,
+    /// Mailbox polled for new mail (default INBOX)
+    pub email_mailbox: Option<String>,
+    /// Seconds between mailbox polls (default 60)
+    pub email_poll_interval_secs: Option<u64>,
+    /// Matrix homeserver the bot logs in to (unset = Matrix disabled)
+    pub matrix_homeserver_url: Option<String>,
+    /// Bot account, e.g. `@kaiba:example.org`
+    pub matrix_user_id: Option<String>,
+    pub matrix_password: Option<String>,
+    /// Device to log in as, so restarts don't add new devices
+    pub matrix_device_id: Option<String>,
+    /// GitHub App answering issues and pull requests (unset = GitHub disabled)
+    pub github_app_id: Option<u64>,
+    /// The App's private key (PEM; `\n` escapes are accepted)
+    pub github_private_key: Option<String>,
+    /// Secret webhook deliveries are signed with
+    pub github_webhook_secret: Option<String>,
+    /// REST API base URL, for GitHub Enterprise Server
+    pub github_api_url: Option<String>,
+    pub otel_exporter_otlp_endpoint: Option<String>,
+    /// Seconds between autonomous scheduler cycles
+    #[serde(default = "default_learning_interval")]
+    pub learning_interval_secs: u64,
+    /// Reis processed at once by scheduler cycles and triggers
+    #[serde(default = "default_scheduler_max_concurrency")]
+    pub scheduler_max_concurrency: usize,
+    /// Seconds one Rei may take before its run is abandoned
+    #[serde(default = "default_scheduler_rei_timeout_secs")]
+    pub scheduler_rei_timeout_secs: u64,
+    /// Learning memories a digest needs (fewer are left for later)
+    #[serde(default = "default_digest_min_memories")]
+    pub digest_min_memories: usize,
+    /// Characters of learning content a digest needs
+    #[serde(default = "default_digest_min_chars")]
+    pub digest_min_chars: usize,
+    /// Per-key requests per minute (0 = unlimited)
+    #[serde(default = "default_rate_call")]
+    pub rate_limit_call_per_minute: u32,
+    #[serde(default = "default_rate_learn")]
+    pub rate_limit_learn_per_minute: u32,
+    #[serde(default = "default_rate_search")]
+    pub rate_limit_search_per_minute: u32,
+    /// Largest accepted request body, in bytes
+    #[serde(default = "default_max_body_bytes")]
+    pub max_body_bytes: usize,
+    /// Largest accepted body for bulk endpoints, in bytes
+    #[serde(default = "default_max_bulk_body_bytes")]
+    pub max_bulk_body_bytes: usize,
+    /// Webhook delivery retention (0 = keep forever)
+    pub webhook_retention_days: Option<i32>,
+    pub webhook_retention_max_per_webhook: Option<i64>,
+    /// Days a deleted Rei stays restorable before it is purged (0 = forever)
+    #[serde(default = "default_rei_retention_days")]
+    pub rei_retention_days: u32,
+    /// Reject writes with 403 and pause the scheduler
+    #[serde(default)]
+    pub read_only: bool,
+    /// Answer creates and deletes with `200` instead of `201`/`204`
+    #[serde(default)]
+    pub legacy_status_codes: bool,
+    /// `WEBHOOK_SECRET_*` entries for webhook headers
+    #[serde(skip)]
+    pub webhook_secrets: WebhookSecrets,
+}
+
+impl ServerConfig {
+    /// Load from the environment overlaid with secret-store entries
+    pub fn load<I>(secrets: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        Self::from_entries(std::env::vars().chain(secrets))
+    }
+
+    /// Build from key/value pairs; later entries override earlier ones
+    fn from_entries<I>(entries: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        // Empty values count as unset, as in `KEY=` lines of an env file
+        let entries: HashMap<String, String> = entries
+            .into_iter()
+            .filter(|(_, value)| !value.trim().is_empty())
+            .collect();
+
+        let mut config: ServerConfig = envy::from_iter(entries.clone())
+            .map_err(|e| format!("Invalid configuration: {}", e))?;
+        config.webhook_secrets = WebhookSecrets::from_secret_store(entries);
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.learning_interval_secs < MIN_LEARNING_INTERVAL_SECS {
+            return Err(format!(
+                "LEARNING_INTERVAL_SECS must be at least {}",
+                MIN_LEARNING_INTERVAL_SECS
+            ));
+        }
+        for (key, url) in [
+            ("QDRANT_URL", &self.qdrant_url),
+            ("GITHUB_API_URL", &self.github_api_url),
+            (
+                "OTEL_EXPORTER_OTLP_ENDPOINT",
+                &self.otel_exporter_otlp_endpoint,
+            ),
+        ] {
+            if let Some(url) = url {
+                reqwest::Url::parse(url).map_err(|e| format!("{} is not a URL: {}", key, e))?;
+            }
+        }
+        if let Some(key) = &self.discord_public_key {
+            DiscordWebhookHandler::with_public_key(key)
+                .map_err(|_| "DISCORD_PUBLIC_KEY is not a hex ed25519 public key".to_string())?;
+        }
+        if self.qdrant_api_key.is_some() && self.qdrant_url.is_none() {
+            return Err("QDRANT_API_KEY is set but QDRANT_URL is not".to_string());
+        }
+        if self.max_body_bytes == 0 || self.max_bulk_body_bytes == 0 {
+            return Err("MAX_BODY_BYTES and MAX_BULK_BODY_BYTES must be positive".to_string());
+        }
+        if self.scheduler_max_concurrency == 0 || self.scheduler_rei_timeout_secs == 0 {
+            return Err(
+                "SCHEDULER_MAX_CONCURRENCY and SCHEDULER_REI_TIMEOUT_SECS must be positive"
+                    .to_string(),
+            );
+        }
+        Ok(())
+    }
+
+    pub fn rate_limits(&self) -> RateLimits {
+        RateLimits {
+            call: self.rate_limit_call_per_minute,
+            learn: self.rate_limit_learn_per_minute,
+            search: self.rate_limit_search_per_minute,
+        }
+    }
+
+    pub fn body_limits(&self) -> BodyLimits {
+        BodyLimits {
+            default: self.max_body_bytes,
+            bulk: self.max_bulk_body_bytes,
+        }
+    }
+
+    pub fn parallelism(&self) -> Parallelism {
+        Parallelism {
+            max_concurrent: self.scheduler_max_concurrency,
+            rei_timeout: std::time::Duration::from_secs(self.scheduler_rei_timeout_secs),
+        }
+    }
+
+    pub fn digest_threshold(&self) -> DigestThreshold {
+        DigestThreshold {
+            min_memories: self.digest_min_memories,
+            min_chars: self.digest_min_chars,
+        }
+    }
+
+    /// Discord settings; `None` without a bot token
+    pub fn discord(&self) -> Option<DiscordConfig> {
+        let token = self.discord_bot_token.as_ref()?;
+        let mut config = DiscordConfig::new(token.clone());
+        if let Some(app_id) = self.discord_application_id {
+            config = config.with_application_id(app_id).with_slash_commands(true);
+        }
+        if let Some(guild_id) = self.discord_guild_id {
+            config = config.with_guild_id(guild_id);
+        }
+        if let Some(emoji) = &self.discord_bookmark_emoji {
+            config = config.with_bookmark_emoji(emoji.trim());
+        }
+        Some(config)
+    }
+
+    /// Event bus subscribers of the configured integrations
+    ///
+    /// Discord posts events once both the bot token and the events channel are set.
+    pub fn event_subscribers(&self) -> Vec<Arc<dyn ReiEventSubscriber>> {
+        let mut subscribers: Vec<Arc<dyn ReiEventSubscriber>> = Vec::new();
+        if let (Some(discord), Some(channel_id)) = (self.discord(), self.discord_events_channel_id)
+        {
+            subscribers.push(Arc::new(DiscordEventSubscriber::new(discord, channel_id)));
+        }
+        subscribers
+    }
+
+    /// Telegram settings; `None` without a bot token
+    pub fn telegram(&self) -> Option<TelegramConfig> {
+        let token = self.telegram_bot_token.as_ref()?;
+        let mut config = TelegramConfig::new(token.clone());
+        if let Some(secret) = &self.telegram_webhook_secret {
+            config = config.with_webhook_secret(secret.clone());
+        }
+        Some(config)
+    }
+
+    /// Email settings; `None` unless both servers and the login are set
+    pub fn email(&self) -> Option<EmailConfig> {
+        let mut config = EmailConfig::new(
+            self.email_imap_host.clone()?,
+            self.email_smtp_host.clone()?,
+            self.email_username.clone()?,
+            self.email_password.clone()?,
+        );
+        if let Some(port) = self.email_imap_port {
+            config = config.with_imap_port(port);
+        }
+        if let Some(port) = self.email_smtp_port {
+            config = config.with_smtp_port(port);
+        }
+        if let Some(mailbox) = &self.email_mailbox {
+            config = config.with_mailbox(mailbox.clone());
+        }
+        if let Some(secs) = self.email_poll_interval_secs {
+            config = config.with_poll_interval(secs);
+        }
+        Some(config)
+    }
+
+    /// Matrix settings; `None` without a homeserver and login
+    pub fn matrix(&self) -> Option<MatrixConfig> {
+        let mut config = MatrixConfig::new(
+            self.matrix_homeserver_url.clone()?,
+            self.matrix_user_id.clone()?,
+            self.matrix_password.clone()?,
+        );
+        if let Some(device_id) = &self.matrix_device_id {
+            config = config.with_device_id(device_id.clone());
+        }
+        Some(config)
+    }
+
+    /// GitHub settings; `None` without an App ID, private key and webhook secret
+    pub fn github(&self) -> Option<GitHubConfig> {
+        // Keys set as one-line env values carry their newlines escaped
+        let private_key = self.github_private_key.as_ref()?.replace("\\n", "\n");
+        let mut config = GitHubConfig::new(
+            self.github_app_id?,
+            private_key,
+            self.github_webhook_secret.clone()?,
+        );
+        if let Some(url) = &self.github_api_url {
+            config = config.with_api_base_url(url.clone());
+        }
+        Some(config)
+    }
+
+    /// Retention policy; an explicit 0 disables a limit
+    pub fn delivery_retention(&self) -> DeliveryRetention {
+        let defaults = DeliveryRetention::default();
+        DeliveryRetention {
+            max_age_days: self
+                .webhook_retention_days
+                .map_or(defaults.max_age_days, |days| Some(days).filter(|d| *d > 0)),
+            max_per_webhook: self
+                .webhook_retention_max_per_webhook
+                .map_or(defaults.max_per_webhook, |max| Some(max).filter(|m| *m > 0)),
+        }
+    }
+
+    /// The effective configuration with secrets masked
+    pub fn redacted(&self) -> RedactedConfig {
+        let mask = |secret: &Option<String>| secret.as_ref().map(|_| REDACTED.to_string());
+        RedactedConfig {
+            kaiba_api_key: mask(&self.kaiba_api_key),
+            qdrant_url: self.qdrant_url.clone(),
+            qdrant_api_key: mask(&self.qdrant_api_key),
+            openai_api_key: mask(&self.openai_api_key),
+            gemini_api_key: mask(&self.gemini_api_key),
+            discord_bot_token: mask(&self.discord_bot_token),
+            discord_application_id: self.discord_application_id,
+            discord_public_key: self.discord_public_key.clone(),
+            discord_guild_id: self.discord_guild_id,
+            discord_bookmark_emoji: self.discord_bookmark_emoji.clone(),
+            discord_events_channel_id: self.discord_events_channel_id,
+            telegram_bot_token: mask(&self.telegram_bot_token),
+            telegram_webhook_secret: mask(&self.telegram_webhook_secret),
+            email_imap_host: self.email_imap_host.clone(),
+            email_imap_port: self.email_imap_port,
+            email_smtp_host: self.email_smtp_host.clone(),
+            email_smtp_port: self.email_smtp_port,
+            email_username: self.email_username.clone(),
+            email_password: mask(&self.email_password),
+            email_mailbox: self.email_mailbox.clone(),
+            email_poll_interval_secs: self.email_poll_interval_secs,
+            matrix_homeserver_url: self.matrix_homeserver_url.clone(),
+            matrix_user_id: self.matrix_user_id.clone(),
+            matrix_password: mask(&self.matrix_password),
+            matrix_device_id: self.matrix_device_id.clone(),
+            github_app_id: self.github_app_id,
+            github_private_key: mask(&self.github_private_key),
+            github_webhook_secret: mask(&self.github_webhook_secret),
+            github_api_url: self.github_api_url.clone(),
+            otel_exporter_otlp_endpoint: self.otel_exporter_otlp_endpoint.clone(),
+            learning_interval_secs: self.learning_interval_secs,
+            scheduler_max_concurrency: self.scheduler_max_concurrency,
+            scheduler_rei_timeout_secs: self.scheduler_rei_timeout_secs,
+            digest_min_memories: self.digest_min_memories,
+            digest_min_chars: self.digest_min_chars,
+            rate_limit_call_per_minute: self.rate_limit_call_per_minute,
+            rate_limit_learn_per_minute: self.rate_limit_learn_per_minute,
+            rate_limit_search_per_minute: self.rate_limit_search_per_minute,
+            max_body_bytes: self.max_body_bytes,
+            max_bulk_body_bytes: self.max_bulk_body_bytes,
+            webhook_retention_days: self.delivery_retention().max_age_days,
+            webhook_retention_max_per_webhook: self.delivery_retention().max_per_webhook,
+            rei_retention_days: self.rei_retention_days,
+            read_only: self.read_only,
+            legacy_status_codes: self.legacy_status_codes,
+            webhook_secrets: self.webhook_secrets.len(),
+        }
+    }
+}
+
+/// Effective configuration as returned by `/kaiba/admin/config`
+///
+/// Secrets are `"********"` when set and `null` when not.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RedactedConfig {
+    pub kaiba_api_key: Option<String>,
+    pub qdrant_url: Option<String>,
+    pub qdrant_api_key: Option<String>,
+    pub openai_api_key: Option<String>,
+    pub gemini_api_key: Option<String>,
+    pub discord_bot_token: Option<String>,
+    pub discord_application_id: Option<u64>,
+    pub discord_public_key: Option<String>,
+    pub discord_guild_id: Option<u64>,
+    pub discord_bookmark_emoji: Option<String>,
+    pub discord_events_channel_id: Option<u64>,
+    pub telegram_bot_token: Option<String>,
+    pub telegram_webhook_secret: Option<String>,
+    pub email_imap_host: Option<String>,
+    pub email_imap_port: Option<u16>,
+    pub email_smtp_host: Option<String>,
+    pub email_smtp_port: Option<u16>,
+    pub email_username: Option<String>,
+    pub email_password: Option<String>,
+    pub email_mailbox: Option<String>,
+    pub email_poll_interval_secs: Option<u64>,
+    pub matrix_homeserver_url: Option<String>,
+    pub matrix_user_id: Option<String>,
+    pub matrix_password: Option<String>,
+    pub matrix_device_id: Option<String>,
+    pub github_app_id: Option<u64>,
+    pub github_private_key: Option<String>,
+    pub github_webhook_secret: Option<String>,
+    pub github_api_url: Option<String>,
+    pub otel_exporter_otlp_endpoint: Option<String>,
+    pub learning_interval_secs: u64,
+    pub scheduler_max_concurrency: usize,
+    pub scheduler_rei_timeout_secs: u64,
+    pub digest_min_memories: usize,
+    pub digest_min_chars: usize,
+    pub rate_limit_call_per_minute: u32,
+    pub rate_limit_learn_per_minute: u32,
+    pub rate_limit_search_per_minute: u32,
+    pub max_body_bytes: usize,
+    pub max_bulk_body_bytes: usize,
+    /// Effective retention (null = unlimited)
+    pub webhook_retention_days: Option<i32>,
+    pub webhook_retention_max_per_webhook: Option<i64>,
+    pub rei_retention_days: u32,
+    pub read_only: bool,
+    pub legacy_status_codes: bool,
+    /// Number of `WEBHOOK_SECRET_*` entries
+    pub webhook_secrets: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_defaults() {
+        let config = ServerConfig::from_entries(entries(&[])).unwrap();
+        assert_eq!(config.kaiba_api_key, None);
+        assert_eq!(config.learning_interval_secs, 3600);
+        assert!(!config.read_only);
+        assert!(!config.legacy_status_codes);
+        assert_eq!(config.rei_retention_days, 30);
+        assert_eq!(config.rate_limits(), RateLimits::default());
+        assert_eq!(config.body_limits(), BodyLimits::default());
+        assert_eq!(config.parallelism(), Parallelism::default());
+        assert_eq!(config.digest_threshold(), DigestThreshold::default());
+        assert_eq!(
+            config.delivery_retention().max_age_days,
+            DeliveryRetention::default().max_age_days
+        );
+    }
+
+    #[test]
+    fn test_overrides_and_secrets() {
+        let config = ServerConfig::from_entries(entries(&[
+            ("OPENAI_API_KEY", "from-env"),
+            ("OPENAI_API_KEY", "from-secrets"),
+            ("GEMINI_API_KEY", ""),
+            ("RATE_LIMIT_LEARN_PER_MINUTE", "0"),
+            ("WEBHOOK_RETENTION_DAYS", "0"),
+            ("READ_ONLY", "true"),
+            ("WEBHOOK_SECRET_LINEAR_TOKEN", "lin_123"),
+        ]))
+        .unwrap();
+        assert_eq!(config.openai_api_key.as_deref(), Some("from-secrets"));
+        assert_eq!(config.gemini_api_key, None);
+        assert_eq!(config.rate_limits().learn, 0);
+        assert_eq!(config.delivery_retention().max_age_days, None);
+        assert!(config.read_only);
+        assert_eq!(config.webhook_secrets.len(), 1);
+
+        let redacted = serde_json::to_value(config.redacted()).unwrap();
+        assert_eq!(redacted["openai_api_key"], REDACTED);
+        assert!(redacted["gemini_api_key"].is_null());
+        assert!(!redacted.to_string().contains("from-secrets"));
+        assert!(!redacted.to_string().contains("lin_123"));
+    }
+
+    #[test]
+    fn test_rejects_invalid_values() {
+        for pairs in [
+            &[("RATE_LIMIT_CALL_PER_MINUTE", "lots")][..],
+            &[("LEARNING_INTERVAL_SECS", "5")],
+            &[("QDRANT_URL", "not a url")],
+            &[("QDRANT_API_KEY", "key")],
+            &[("DISCORD_PUBLIC_KEY", "1234")],
+            &[("MAX_BODY_BYTES", "0")],
+            &[("SCHEDULER_MAX_CONCURRENCY", "0")],
+        ] {
+            assert!(ServerConfig::from_entries(entries(pairs)).is_err());
+        }
+    }
+
+    #[test]
+    fn test_discord_event_subscriber_wiring() {
+        let names = |pairs: &[(&str, &str)]| {
+            ServerConfig::from_entries(entries(pairs))
+                .unwrap()
+                .event_subscribers()
+                .iter()
+                .map(|s| s.name().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            names(&[
+                ("DISCORD_BOT_TOKEN", "token"),
+                ("DISCORD_EVENTS_CHANNEL_ID", "123456789012345678"),
+            ]),
+            ["discord"]
+        );
+        assert!(names(&[("DISCORD_BOT_TOKEN", "token")]).is_empty());
+        assert!(names(&[("DISCORD_EVENTS_CHANNEL_ID", "123456789012345678")]).is_empty());
+    }
+}
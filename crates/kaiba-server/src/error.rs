@@ -0,0 +1,213 @@
+//! API Errors - RFC 7807 `application/problem+json` responses
+//!
+//! Handlers return [`ApiError`]. Client errors keep their message as the
+//! `detail`; internal errors are logged and replaced with a generic detail so
+//! database and upstream error text never reaches clients.
+
+use axum::{
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+const PROBLEM_CONTENT_TYPE: &str = "application/problem+json";
+
+/// Error returned by API handlers
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    code: &'static str,
+    detail: String,
+    errors: Vec<FieldError>,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, detail: impl Into<String>) -> Self {
+        Self {
+            status,
+            code: default_code(status),
+            detail: detail.into(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// 400 Bad Request
+    pub fn bad_request(detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, detail)
+    }
+
+    /// 404 Not Found
+    pub fn not_found(detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::NOT_FOUND, detail)
+    }
+
+    /// 422 Unprocessable Entity with the fields that failed validation
+    pub fn validation(errors: Vec<FieldError>) -> Self {
+        let mut err = Self::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "Request validation failed",
+        );
+        err.errors = errors;
+        err
+    }
+
+    /// 409 Conflict
+    pub fn conflict(detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::CONFLICT, detail)
+    }
+
+    /// 503 Service Unavailable (an optional service is not configured)
+    pub fn unavailable(detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::SERVICE_UNAVAILABLE, detail)
+    }
+
+    /// 500 Internal Server Error; the cause is logged, not returned
+    pub fn internal(cause: impl std::fmt::Display) -> Self {
+        tracing::error!("Internal error: {}", cause);
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+    }
+
+    /// Override the machine-readable error code
+    pub fn with_code(mut self, code: &'static str) -> Self {
+        self.code = code;
+        self
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({}): {}", self.status, self.code, self.detail)
+    }
+}
+
+impl From<StatusCode> for ApiError {
+    fn from(status: StatusCode) -> Self {
+        Self::new(
+            status,
+            status.canonical_reason().unwrap_or("Request failed"),
+        )
+    }
+}
+
+/// Machine-readable code for a status
+fn default_code(status: StatusCode) -> &'static str {
+    match status {
+        StatusCode::BAD_REQUEST => "bad_request",
+        StatusCode::UNAUTHORIZED => "unauthorized",
+        StatusCode::FORBIDDEN => "forbidden",
+        StatusCode::NOT_FOUND => "not_found",
+        StatusCode::CONFLICT => "conflict",
+        StatusCode::PAYLOAD_TOO_LARGE => "payload_too_large",
+        StatusCode::UNPROCESSABLE_ENTITY => "validation_failed",
+        StatusCode::TOO_MANY_REQUESTS => "rate_limited",
+        StatusCode::SERVICE_UNAVAILABLE => "service_unavailable",
+        s if s.is_server_error() => "internal",
+        _ => "error",
+    }
+}
+
+/// A field that failed validation
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct FieldError {
+    /// Path of the field (e.g. `manifest.interests`)
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Problem details body (RFC 7807)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Problem {
+    /// Always `about:blank`; `code` identifies the problem
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    /// HTTP status reason phrase
+    pub title: String,
+    pub status: u16,
+    /// Human-readable explanation
+    pub detail: String,
+    /// Stable machine-readable error code (e.g. `not_found`)
+    pub code: String,
+    /// Field-level errors (validation failures only)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FieldError>,
+}
+
+impl From<&ApiError> for Problem {
+    fn from(err: &ApiError) -> Self {
+        Self {
+            problem_type: "about:blank".to_string(),
+            title: err.status.canonical_reason().unwrap_or("Error").to_string(),
+            status: err.status.as_u16(),
+            detail: err.detail.clone(),
+            code: err.code.to_string(),
+            errors: err.errors.clone(),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (
+            self.status,
+            [(header::CONTENT_TYPE, PROBLEM_CONTENT_TYPE)],
+            Json(Problem::from(&self)),
+        )
+            .into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_problem_body() {
+        let problem = Problem::from(&ApiError::not_found("Rei not found"));
+        let json = serde_json::to_value(&problem).unwrap();
+        assert_eq!(json["type"], "about:blank");
+        assert_eq!(json["title"], "Not Found");
+        assert_eq!(json["status"], 404);
+        assert_eq!(json["detail"], "Rei not found");
+        assert_eq!(json["code"], "not_found");
+        assert!(json.get("errors").is_none());
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        let err = ApiError::validation(vec![FieldError::new("name", "must not be empty")]);
+        let json = serde_json::to_value(Problem::from(&err)).unwrap();
+        assert_eq!(json["status"], 422);
+        assert_eq!(json["code"], "validation_failed");
+        assert_eq!(json["errors"][0]["field"], "name");
+        assert_eq!(json["errors"][0]["message"], "must not be empty");
+    }
+
+    #[test]
+    fn test_internal_hides_cause() {
+        let problem = Problem::from(&ApiError::internal("relation \"reis\" does not exist"));
+        assert_eq!(problem.status, 500);
+        assert_eq!(problem.detail, "Internal server error");
+        assert_eq!(problem.code, "internal");
+    }
+
+    #[test]
+    fn test_with_code() {
+        let err = ApiError::bad_request("Invalid signature").with_code("invalid_signature");
+        assert_eq!(Problem::from(&err).code, "invalid_signature");
+        assert_eq!(
+            Problem::from(&ApiError::from(StatusCode::FORBIDDEN)).detail,
+            "Forbidden"
+        );
+    }
+}
@@ -0,0 +1,265 @@
+//! ETags - Conditional GETs for frequently polled resources
+//!
+//! IDE integrations fetch the same Rei and prompt over and over. Responses
+//! built with [`json_with_etag`] carry an `ETag` hashed from the body, so it
+//! changes whenever the Rei, its state or the memories in a prompt change.
+//! A request whose `If-None-Match` still matches gets `304 Not Modified`
+//! with no body.
+//!
+//! Updates take the opposite precondition: `If-Match` carries the ETag the
+//! client last read (or, from older clients, its `updated_at`), and the
+//! update is refused with `409` if the resource has changed since (see
+//! [`if_match_version`]). A Rei's body includes runtime state that calls and
+//! background jobs change all the time, so its ETag also names the version
+//! of the Rei row alone, and only that part is checked
+//! ([`json_with_version_etag`]).
+
+use axum::{
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::error::ApiError;
+
+/// Hex digest of a serialized value
+fn digest_of(body: &[u8]) -> String {
+    hex::encode(&Sha256::digest(body)[..16])
+}
+
+/// Strong ETag of a serialized body
+fn etag_of(body: &[u8]) -> String {
+    format!("\"{}\"", digest_of(body))
+}
+
+/// Whether an `If-None-Match` header matches `etag`
+///
+/// Comparison is weak, as RFC 9110 requires for `If-None-Match`: a `W/`
+/// prefix is ignored.
+fn matches(if_none_match: &str, etag: &str) -> bool {
+    if_none_match.trim() == "*"
+        || if_none_match
+            .split(',')
+            .map(|tag| tag.trim().trim_start_matches("W/"))
+            .any(|tag| tag == etag)
+}
+
+/// JSON response with an ETag, or `304` if the client's copy is current
+pub fn json_with_etag<T: Serialize>(headers: &HeaderMap, body: &T) -> Result<Response, ApiError> {
+    let bytes = serde_json::to_vec(body).map_err(ApiError::internal)?;
+    let etag = etag_of(&bytes);
+    respond(headers, etag, bytes)
+}
+
+/// [`json_with_etag`] for a body that holds more than the edited resource
+///
+/// The ETag is `"<version>.<body>"`. `If-None-Match` compares all of it, so
+/// any change to the body refreshes caches; [`if_match_version`] compares
+/// only the version part, hashed from `version`.
+pub fn json_with_version_etag<V: Serialize, T: Serialize>(
+    headers: &HeaderMap,
+    version: &V,
+    body: &T,
+) -> Result<Response, ApiError> {
+    let version = digest_of(&serde_json::to_vec(version).map_err(ApiError::internal)?);
+    let bytes = serde_json::to_vec(body).map_err(ApiError::internal)?;
+    let etag = format!("\"{}.{}\"", version, digest_of(&bytes));
+    respond(headers, etag, bytes)
+}
+
+fn respond(headers: &HeaderMap, etag: String, bytes: Vec<u8>) -> Result<Response, ApiError> {
+    let not_modified = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| matches(v, &etag));
+
+    let mut response = if not_modified {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        // Already serialized, so send the bytes rather than serializing twice
+        (
+            [(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("application/json"),
+            )],
+            bytes,
+        )
+            .into_response()
+    };
+
+    let headers = response.headers_mut();
+    if let Ok(value) = HeaderValue::from_str(&etag) {
+        headers.insert(header::ETAG, value);
+    }
+    // Cache, but revalidate before every use
+    headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+    Ok(response)
+}
+
+/// `updated_at` an update must still find, per its `If-Match` header
+///
+/// An ETag is checked against `version`, what the GET's ETag was hashed
+/// from: the whole body for [`json_with_etag`], the version part for
+/// [`json_with_version_etag`]. A stale one is `409 edit_conflict`. A
+/// matching ETag yields `updated_at`, so a concurrent edit landing before
+/// the write is refused too. An RFC 3339 `updated_at` (older clients) is
+/// passed through as is. `*` (any version) and a missing header impose no
+/// precondition.
+pub fn if_match_version<T: Serialize>(
+    headers: &HeaderMap,
+    version: &T,
+    updated_at: DateTime<Utc>,
+) -> Result<Option<DateTime<Utc>>, ApiError> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(None);
+    };
+    let value = value
+        .to_str()
+        .map_err(|_| ApiError::bad_request("Invalid If-Match header"))?
+        .trim();
+    if value == "*" {
+        return Ok(None);
+    }
+
+    let version = digest_of(&serde_json::to_vec(version).map_err(ApiError::internal)?);
+    for tag in value.split(',').map(str::trim) {
+        // Strong comparison, as RFC 9110 requires for `If-Match`: weak tags never match
+        let opaque = tag.strip_prefix('"').and_then(|t| t.strip_suffix('"'));
+        if opaque.and_then(|t| t.split('.').next()) == Some(version.as_str()) {
+            return Ok(Some(updated_at));
+        }
+        let version = tag.trim_start_matches("W/").trim_matches('"');
+        if let Ok(version) = DateTime::parse_from_rfc3339(version) {
+            return Ok(Some(version.with_timezone(&Utc)));
+        }
+    }
+
+    Err(
+        ApiError::conflict("Modified since the If-Match version was read; reload and retry")
+            .with_code("edit_conflict"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_etag_changes_with_body() {
+        let a = etag_of(br#"{"mood":"happy"}"#);
+        let b = etag_of(br#"{"mood":"tired"}"#);
+        assert_ne!(a, b);
+        assert_eq!(a, etag_of(br#"{"mood":"happy"}"#));
+        assert!(a.starts_with('"') && a.ends_with('"'));
+    }
+
+    #[test]
+    fn test_if_none_match() {
+        let etag = "\"abc\"";
+        assert!(matches("\"abc\"", etag));
+        assert!(matches("W/\"abc\"", etag));
+        assert!(matches("\"old\", \"abc\"", etag));
+        assert!(matches("*", etag));
+        assert!(!matches("\"old\"", etag));
+    }
+
+    #[test]
+    fn test_if_match_version() {
+        let current = serde_json::json!({"name": "shii"});
+        let updated_at: DateTime<Utc> = "2025-03-02T10:00:00Z".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        assert_eq!(
+            if_match_version(&headers, &current, updated_at).unwrap(),
+            None
+        );
+
+        headers.insert(
+            header::IF_MATCH,
+            HeaderValue::from_static("\"2025-03-01T09:30:00.123456Z\""),
+        );
+        let expected: DateTime<Utc> = "2025-03-01T09:30:00.123456Z".parse().unwrap();
+        assert_eq!(
+            if_match_version(&headers, &current, updated_at).unwrap(),
+            Some(expected)
+        );
+
+        headers.insert(header::IF_MATCH, HeaderValue::from_static("*"));
+        assert_eq!(
+            if_match_version(&headers, &current, updated_at).unwrap(),
+            None
+        );
+
+        headers.insert(header::IF_MATCH, HeaderValue::from_static("\"abc\""));
+        let stale = if_match_version(&headers, &current, updated_at).unwrap_err();
+        assert_eq!(stale.into_response().status(), StatusCode::CONFLICT);
+    }
+
+    #[test]
+    fn test_get_etag_round_trips_to_if_match() {
+        let updated_at: DateTime<Utc> = "2025-03-02T10:00:00Z".parse().unwrap();
+        let read = serde_json::json!({"name": "shii", "updated_at": updated_at});
+        let get = json_with_etag(&HeaderMap::new(), &read).unwrap();
+
+        let mut put = HeaderMap::new();
+        put.insert(header::IF_MATCH, get.headers()[header::ETAG].clone());
+        assert_eq!(
+            if_match_version(&put, &read, updated_at).unwrap(),
+            Some(updated_at)
+        );
+
+        // Someone else edited it in between
+        let changed = serde_json::json!({"name": "yui", "updated_at": updated_at});
+        let conflict = if_match_version(&put, &changed, updated_at).unwrap_err();
+        assert_eq!(conflict.into_response().status(), StatusCode::CONFLICT);
+
+        // Weak tags never match for If-Match
+        let weak = format!("W/{}", get.headers()[header::ETAG].to_str().unwrap());
+        put.insert(header::IF_MATCH, HeaderValue::from_str(&weak).unwrap());
+        assert!(if_match_version(&put, &read, updated_at).is_err());
+    }
+
+    #[test]
+    fn test_version_etag_ignores_the_rest_of_the_body() {
+        let updated_at: DateTime<Utc> = "2025-03-02T10:00:00Z".parse().unwrap();
+        let rei = serde_json::json!({"name": "shii", "updated_at": updated_at});
+        let read = serde_json::json!({"rei": rei, "energy_level": 80});
+        let get = json_with_version_etag(&HeaderMap::new(), &rei, &read).unwrap();
+        let etag = get.headers()[header::ETAG].clone();
+
+        // A call drained energy between the GET and the PUT
+        let drained = serde_json::json!({"rei": rei, "energy_level": 60});
+        let mut poll = HeaderMap::new();
+        poll.insert(header::IF_NONE_MATCH, etag.clone());
+        let refreshed = json_with_version_etag(&poll, &rei, &drained).unwrap();
+        assert_eq!(refreshed.status(), StatusCode::OK);
+        assert_ne!(refreshed.headers()[header::ETAG], etag);
+
+        let mut put = HeaderMap::new();
+        put.insert(header::IF_MATCH, etag);
+        assert_eq!(
+            if_match_version(&put, &rei, updated_at).unwrap(),
+            Some(updated_at)
+        );
+
+        // Someone else edited the Rei itself
+        let edited = serde_json::json!({"name": "yui", "updated_at": updated_at});
+        let conflict = if_match_version(&put, &edited, updated_at).unwrap_err();
+        assert_eq!(conflict.into_response().status(), StatusCode::CONFLICT);
+    }
+
+    #[test]
+    fn test_not_modified_response() {
+        let body = serde_json::json!({"name": "shii"});
+        let fresh = json_with_etag(&HeaderMap::new(), &body).unwrap();
+        assert_eq!(fresh.status(), StatusCode::OK);
+        let etag = fresh.headers()[header::ETAG].clone();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, etag.clone());
+        let cached = json_with_etag(&headers, &body).unwrap();
+        assert_eq!(cached.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(cached.headers()[header::ETAG], etag);
+    }
+}
@@ -1,24 +1,70 @@
-use axum::{extract::FromRef, middleware, routing::get, Json, Router};
+use axum::{
+    extract::{DefaultBodyLimit, FromRef, State},
+    middleware,
+    routing::get,
+    Json, Router,
+};
+use kaiba_integration_discord::{DiscordGatewayRunner, DiscordIntegration};
+use kaiba_integration_email::{EmailIntegration, EmailPollingRunner};
+use kaiba_integration_github::{GitHubBot, GitHubIntegration};
+use kaiba_integration_matrix::{MatrixBot, MatrixIntegration};
+use kaiba_integration_telegram::{TelegramBot, TelegramIntegration};
 use serde::Serialize;
 use sqlx::PgPool;
+use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 use tower_http::cors::CorsLayer;
+use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer};
+use tower_http::trace::TraceLayer;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
 mod adapters;
 mod application;
+mod audit;
 mod auth;
+mod body_limit;
+mod bulk;
+mod config;
+mod error;
+mod etag;
 mod models;
+mod pagination;
+mod rate_limit;
+mod read_only;
+mod rest;
 mod routes;
 mod services;
+mod supervisor;
+mod telemetry;
+mod validation;
 
-use adapters::{HttpWebhook, PgReiRepository, PgReiWebhookRepository, PgTeiRepository};
+use adapters::{
+    HttpWebhook, InProcessEventBus, PgEventOutbox, PgReiRepository, PgReiWebhookRepository,
+    PgTeiRepository,
+};
 use application::{ReiService, TeiService};
+use config::ServerConfig;
+use rate_limit::ApiRateLimiter;
 use services::embedding::EmbeddingService;
+use services::event_stats::EventStats;
+use services::event_stream::EventStream;
+use services::ingestion::MessageIngester;
+use services::integration_call::IntegrationCallHandler;
+use services::integration_registry::IntegrationRegistry;
+use services::outbox_relay::OutboxRelay;
 use services::qdrant::MemoryKai;
+use services::rei_purge::ReiPurger;
 use services::scheduler;
+use services::task_runner::TaskRunner;
 use services::web_search::WebSearchAgent;
+use services::webhook_batcher::WebhookBatcher;
+use services::webhook_publisher::WebhookPublisher;
+use supervisor::TaskSupervisor;
+
+/// How long shutdown waits for background work before giving up
+const SHUTDOWN_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// Type aliases for application services with concrete repository implementations
 pub type AppReiService = ReiService<PgReiRepository>;
@@ -35,6 +81,24 @@ pub struct AppState {
     pub web_search: Option<WebSearchAgent>,
     pub webhook_repo: Arc<PgReiWebhookRepository>,
     pub http_webhook: Arc<HttpWebhook>,
+    pub event_bus: Arc<InProcessEventBus>,
+    pub event_stats: Arc<EventStats>,
+    /// Live events for SSE clients
+    pub event_stream: Arc<EventStream>,
+    /// LLM key for digest runs outside the scheduler
+    pub gemini_api_key: Option<String>,
+    /// Per-key limits on call, learn and search routes
+    pub rate_limiter: Arc<ApiRateLimiter>,
+    /// Background work that shutdown waits for
+    pub tasks: Arc<TaskSupervisor>,
+    /// Integrations configured on this deployment, by name
+    pub integrations: Arc<IntegrationRegistry>,
+    /// Telegram bot, when TELEGRAM_BOT_TOKEN is set (webhook updates go here)
+    pub telegram: Option<Arc<TelegramBot>>,
+    /// GitHub App, when GITHUB_APP_ID is set (webhook deliveries go here)
+    pub github: Option<Arc<GitHubBot>>,
+    /// Settings loaded at startup
+    pub config: Arc<ServerConfig>,
 }
 
 // Allow extracting PgPool directly from AppState (for backward compatibility)
@@ -44,18 +108,50 @@ impl FromRef<AppState> for PgPool {
     }
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, utoipa::ToSchema)]
 struct HealthCheck {
     status: String,
     message: String,
     version: String,
+    /// Writes are rejected (READ_ONLY)
+    read_only: bool,
+    services: HealthServices,
+}
+
+/// Optional services configured on this deployment (lets clients diagnose setup)
+#[derive(Serialize, utoipa::ToSchema)]
+struct HealthServices {
+    /// MemoryKai (Qdrant) connected
+    memory: bool,
+    /// Embeddings available (memory add/search, prompts with memories)
+    embedding: bool,
+    /// Web search for self-learning
+    web_search: bool,
+    /// LLM key for digests
+    digest: bool,
 }
 
-async fn health_check() -> Json<HealthCheck> {
+/// Health check
+#[utoipa::path(
+    get,
+    path = "/health",
+    responses(
+        (status = 200, description = "Server is running", body = HealthCheck)
+    ),
+    tag = "Health"
+)]
+async fn health_check(State(state): State<AppState>) -> Json<HealthCheck> {
     Json(HealthCheck {
         status: "ok".to_string(),
         message: "Kaiba API is running - memories flow through the hippocampus".to_string(),
         version: env!("CARGO_PKG_VERSION").to_string(),
+        read_only: state.config.read_only,
+        services: HealthServices {
+            memory: state.memory_kai.is_some(),
+            embedding: state.embedding.is_some(),
+            web_search: state.web_search.is_some(),
+            digest: state.gemini_api_key.is_some(),
+        },
     })
 }
 
@@ -63,11 +159,16 @@ async fn health_check() -> Json<HealthCheck> {
 async fn main(
     #[shuttle_shared_db::Postgres] pool: PgPool,
     #[shuttle_runtime::Secrets] secrets: shuttle_runtime::SecretStore,
-) -> shuttle_axum::ShuttleAxum {
+) -> Result<KaibaService, shuttle_runtime::Error> {
+    // Secrets, with environment variables as fallback (see config.rs)
+    let config = ServerConfig::load(secrets).map_err(shuttle_runtime::CustomError::msg)?;
+
+    telemetry::init(config.otel_exporter_otlp_endpoint.as_deref());
+
     tracing::info!("🧠 Kaiba API initializing...");
 
     // Initialize API key from secrets
-    if let Some(api_key) = secrets.get("KAIBA_API_KEY") {
+    if let Some(api_key) = config.kaiba_api_key.clone() {
         auth::init_api_key(api_key);
         tracing::info!("🔐 API key authentication enabled");
     } else {
@@ -83,8 +184,8 @@ async fn main(
     tracing::info!("✅ Database migrations completed");
 
     // Initialize MemoryKai (Qdrant) if configured
-    let memory_kai = match (secrets.get("QDRANT_URL"), secrets.get("QDRANT_API_KEY")) {
-        (Some(url), api_key) => match MemoryKai::new(&url, api_key).await {
+    let memory_kai = match (&config.qdrant_url, &config.qdrant_api_key) {
+        (Some(url), api_key) => match MemoryKai::new(url, api_key.clone()).await {
             Ok(kai) => {
                 tracing::info!("🌊 MemoryKai (記憶海) connected");
                 Some(Arc::new(kai))
@@ -101,7 +202,7 @@ async fn main(
     };
 
     // Initialize Embedding service if configured
-    let embedding = secrets.get("OPENAI_API_KEY").map(|key| {
+    let embedding = config.openai_api_key.clone().map(|key| {
         tracing::info!("🧬 Embedding service initialized");
         EmbeddingService::new(key)
     });
@@ -111,7 +212,7 @@ async fn main(
     }
 
     // Initialize WebSearch agent if configured
-    let web_search = secrets.get("GEMINI_API_KEY").map(|key| {
+    let web_search = config.gemini_api_key.clone().map(|key| {
         tracing::info!("🔍 WebSearch agent initialized (Gemini)");
         WebSearchAgent::new(key)
     });
@@ -124,12 +225,122 @@ async fn main(
     let rei_repo = Arc::new(PgReiRepository::new(pool.clone()));
     let tei_repo = Arc::new(PgTeiRepository::new(pool.clone()));
     let webhook_repo = Arc::new(PgReiWebhookRepository::new(pool.clone()));
-    let rei_service = Arc::new(ReiService::new(rei_repo));
+    let rei_service = Arc::new(ReiService::new(rei_repo.clone()));
     let tei_service = Arc::new(TeiService::new(tei_repo));
-    let http_webhook = Arc::new(HttpWebhook::new());
+
+    // Webhook header secrets (WEBHOOK_SECRET_* → ${secret:name})
+    if !config.webhook_secrets.is_empty() {
+        tracing::info!("🔑 {} webhook secrets loaded", config.webhook_secrets.len());
+    }
+    let http_webhook = Arc::new(HttpWebhook::new().with_secrets(config.webhook_secrets.clone()));
+
+    // Background loops are restarted if they crash; shutdown drains them
+    let tasks = Arc::new(TaskSupervisor::new());
+
+    // Event bus: webhooks and stats counters subscribe to the same ReiEvent stream.
+    // Emitted events go through the outbox and are relayed at-least-once.
+    let event_stats = Arc::new(EventStats::new());
+    let event_stream = Arc::new(EventStream::new());
+    let outbox = Arc::new(PgEventOutbox::new(pool.clone()));
+    let mut event_bus = InProcessEventBus::new()
+        .with_outbox(outbox.clone())
+        .with_tasks(tasks.clone())
+        .with_subscriber(Arc::new(
+            WebhookPublisher::new(webhook_repo.clone(), http_webhook.clone())
+                .with_event_stream(event_stream.clone())
+                .with_tasks(tasks.clone()),
+        ))
+        .with_subscriber(event_stats.clone())
+        .with_subscriber(event_stream.clone());
+    // Integrations posting events to a chat channel (Discord)
+    for subscriber in config.event_subscribers() {
+        tracing::info!("📣 {} posts Rei events", subscriber.name());
+        event_bus = event_bus.with_subscriber(subscriber);
+    }
+    let event_bus = Arc::new(event_bus);
+    let relay = Arc::new(OutboxRelay::new(outbox, event_bus.clone()));
+    tasks.supervise("outbox relay", move |shutdown| relay.clone().run(shutdown));
+
+    // Batched webhooks: flush queued events once their interval has elapsed
+    let batcher = Arc::new(WebhookBatcher::new(
+        webhook_repo.clone(),
+        http_webhook.clone(),
+    ));
+    tasks.supervise("webhook batcher", move |shutdown| {
+        batcher.clone().run(shutdown)
+    });
 
     tracing::info!("🔔 Webhook service initialized");
 
+    let gemini_api_key = config.gemini_api_key.clone();
+
+    // Per-key requests per minute on expensive routes (0 disables a limit)
+    let rate_limits = config.rate_limits();
+    tracing::info!(
+        "🚦 Rate limits per key: call {}/min, learn {}/min, search {}/min",
+        rate_limits.call,
+        rate_limits.learn,
+        rate_limits.search
+    );
+
+    // Integrations configured on this deployment (switched per Rei in rei_integrations)
+    let mut integrations = IntegrationRegistry::new();
+    if let Some(discord) = config.discord() {
+        integrations = integrations.with(Arc::new(
+            DiscordIntegration::new(discord).with_rei_repository(rei_repo.clone()),
+        ));
+    }
+    if let Some(telegram) = config.telegram() {
+        integrations = integrations.with(Arc::new(TelegramIntegration::new(telegram)));
+    }
+    if let Some(github) = config.github() {
+        match GitHubIntegration::new(github) {
+            Ok(github) => integrations = integrations.with(Arc::new(github)),
+            Err(e) => tracing::warn!("⚠️  GitHub integration not loaded: {}", e),
+        }
+    }
+    if let Some(email) = config.email() {
+        match EmailIntegration::new(email) {
+            Ok(email) => integrations = integrations.with(Arc::new(email)),
+            Err(e) => tracing::warn!("⚠️  Email integration not loaded: {}", e),
+        }
+    }
+    // Matrix logs in once; the integration and the sync loop share the device
+    let matrix = match config.matrix() {
+        Some(matrix) => match kaiba_integration_matrix::connect(&matrix).await {
+            Ok(client) => Some((matrix, client)),
+            Err(e) => {
+                tracing::warn!("⚠️  Matrix integration not loaded: {}", e);
+                None
+            }
+        },
+        None => None,
+    };
+    if let Some((_, client)) = &matrix {
+        integrations = integrations.with(Arc::new(MatrixIntegration::new(client.clone())));
+    }
+    let names: Vec<&str> = integrations.names().collect();
+    if !names.is_empty() {
+        tracing::info!("🔌 Integrations: {}", names.join(", "));
+    }
+
+    // Telegram bot (updates by long polling, or by webhook with a secret)
+    let telegram = config
+        .telegram()
+        .map(|telegram| Arc::new(TelegramBot::new(telegram, rei_repo.clone())));
+
+    // GitHub App (deliveries arrive by webhook)
+    let github = match config.github() {
+        Some(github) => match GitHubBot::new(github, rei_repo.clone()) {
+            Ok(bot) => Some(Arc::new(bot)),
+            Err(e) => {
+                tracing::warn!("⚠️  GitHub App not started: {}", e);
+                None
+            }
+        },
+        None => None,
+    };
+
     // Create application state
     let state = AppState {
         pool: pool.clone(),
@@ -140,23 +351,157 @@ async fn main(
         web_search: web_search.clone(),
         webhook_repo,
         http_webhook,
+        event_bus,
+        event_stats,
+        event_stream: event_stream.clone(),
+        gemini_api_key: gemini_api_key.clone(),
+        rate_limiter: Arc::new(ApiRateLimiter::new(rate_limits)),
+        tasks: tasks.clone(),
+        integrations: Arc::new(integrations),
+        telegram: telegram.clone(),
+        github,
+        config: Arc::new(config),
     };
 
-    // Start autonomous scheduler (1 hour interval)
-    let scheduler_interval = secrets
-        .get("LEARNING_INTERVAL_SECS")
-        .and_then(|s| s.parse().ok());
-    let gemini_api_key = secrets.get("GEMINI_API_KEY");
+    // Purge deleted Reis once they are past restoring (not when read-only)
+    if !state.config.read_only && state.config.rei_retention_days > 0 {
+        let purger = Arc::new(ReiPurger::new(
+            state.rei_service.clone(),
+            memory_kai.clone(),
+            state.config.rei_retention_days,
+        ));
+        tasks.supervise("rei purge", move |shutdown| purger.clone().run(shutdown));
+    }
+
+    // Run scheduled tasks as they come due (not when read-only)
+    if !state.config.read_only {
+        let runner = Arc::new(TaskRunner::new(state.clone()));
+        tasks.supervise("task runner", move |shutdown| runner.clone().run(shutdown));
+    }
+
+    // Summarize conversations of Reis that opted in to ingestion (not when read-only)
+    if !state.config.read_only {
+        match (&memory_kai, &embedding, &gemini_api_key) {
+            (Some(memory_kai), Some(embedding), Some(gemini_api_key)) => {
+                let ingester = Arc::new(MessageIngester::new(
+                    state.clone(),
+                    memory_kai.clone(),
+                    embedding.clone(),
+                    gemini_api_key.clone(),
+                ));
+                tasks.supervise("integration ingester", move |shutdown| {
+                    ingester.clone().run(shutdown)
+                });
+            }
+            _ => tracing::warn!("⚠️  Integration ingestion disabled (missing services)"),
+        }
+    }
+
+    // Answer Discord messages in channels bound to a Rei (calls write, so not when read-only)
+    if let Some(discord) = state.config.discord() {
+        if state.config.read_only {
+            tracing::warn!("🔒 Read-only mode - Discord gateway not started");
+        } else {
+            let runner = Arc::new(DiscordGatewayRunner::new(
+                discord,
+                rei_repo.clone(),
+                Arc::new(IntegrationCallHandler::new(state.clone(), "discord")),
+            ));
+            tasks.supervise("discord gateway", move |shutdown| {
+                let runner = runner.clone();
+                async move {
+                    if let Err(e) = runner.run_until(shutdown.cancelled()).await {
+                        tracing::warn!("⚠️  Discord gateway disconnected: {}", e);
+                    }
+                }
+            });
+            tracing::info!("💬 Discord gateway started");
+        }
+    }
+
+    // Long-poll Telegram updates unless they come by webhook (not when read-only)
+    if let Some(bot) = telegram.filter(|bot| !bot.uses_webhook()) {
+        if state.config.read_only {
+            tracing::warn!("🔒 Read-only mode - Telegram polling not started");
+        } else {
+            let handler: Arc<dyn kaiba::IntegrationEventHandler> =
+                Arc::new(IntegrationCallHandler::new(state.clone(), "telegram"));
+            tasks.supervise("telegram poller", move |shutdown| {
+                let bot = bot.clone();
+                let handler = handler.clone();
+                async move {
+                    if let Err(e) = bot.poll_until(handler, shutdown.cancelled()).await {
+                        tracing::warn!("⚠️  Telegram polling stopped: {}", e);
+                    }
+                }
+            });
+            tracing::info!("✈️  Telegram polling started");
+        }
+    }
+
+    // Answer messages in Matrix rooms bound to a Rei (calls write, so not when read-only)
+    if let Some((matrix, client)) = matrix {
+        if state.config.read_only {
+            tracing::warn!("🔒 Read-only mode - Matrix sync not started");
+        } else {
+            let bot = Arc::new(MatrixBot::new(client, matrix, rei_repo.clone()));
+            let handler: Arc<dyn kaiba::IntegrationEventHandler> =
+                Arc::new(IntegrationCallHandler::new(state.clone(), "matrix"));
+            tasks.supervise("matrix sync", move |shutdown| {
+                let bot = bot.clone();
+                let handler = handler.clone();
+                async move {
+                    if let Err(e) = bot.sync_until(handler, shutdown.cancelled()).await {
+                        tracing::warn!("⚠️  Matrix sync stopped: {}", e);
+                    }
+                }
+            });
+            tracing::info!("🟩 Matrix sync started");
+        }
+    }
+
+    // Answer mail sent to Reis' addresses (calls write, so not when read-only)
+    if let Some(email) = state.config.email() {
+        if state.config.read_only {
+            tracing::warn!("🔒 Read-only mode - email polling not started");
+        } else {
+            match EmailPollingRunner::new(
+                email,
+                rei_repo,
+                Arc::new(IntegrationCallHandler::new(state.clone(), "email")),
+            ) {
+                Ok(runner) => {
+                    let runner = Arc::new(runner);
+                    tasks.supervise("email poller", move |shutdown| {
+                        let runner = runner.clone();
+                        async move {
+                            if let Err(e) = runner.run_until(shutdown.cancelled()).await {
+                                tracing::warn!("⚠️  Email polling stopped: {}", e);
+                            }
+                        }
+                    });
+                    tracing::info!("📧 Email polling started");
+                }
+                Err(e) => tracing::warn!("⚠️  Email polling not started: {}", e),
+            }
+        }
+    }
 
-    if let Some(_handle) = scheduler::maybe_start_scheduler(
+    // Start autonomous scheduler (learning runs write, so not when read-only)
+    if state.config.read_only {
+        tracing::warn!("🔒 Read-only mode - writes rejected, autonomous scheduler paused");
+    } else if scheduler::maybe_start_scheduler(
+        &tasks,
         pool,
         memory_kai,
         embedding,
         web_search,
         gemini_api_key,
-        scheduler_interval,
-        Some(state.webhook_repo.clone()),
-        Some(state.http_webhook.clone()),
+        Some(state.config.learning_interval_secs),
+        state.config.parallelism(),
+        state.config.delivery_retention(),
+        state.config.digest_threshold(),
+        Some(state.event_bus.clone()),
     ) {
         tracing::info!("📅 Autonomous scheduler started");
     } else {
@@ -173,9 +518,42 @@ async fn main(
         .merge(routes::learning::router())
         .merge(routes::prompt::router())
         .merge(routes::webhook::router())
+        .merge(routes::global_webhook::router())
+        .merge(routes::event_registry::router())
+        .merge(routes::event_stream::router())
         .merge(routes::dashboard::router())
         .merge(routes::trigger::router())
-        .layer(middleware::from_fn(auth::auth_middleware));
+        .merge(routes::inbound::router())
+        .merge(routes::integration::router())
+        .merge(routes::schedule::router())
+        .merge(routes::task::router())
+        .merge(routes::decision::router())
+        .merge(routes::api_key::router())
+        .merge(routes::user::router())
+        .merge(routes::admin::router())
+        // Layers run bottom-up: authenticate first, then limit per key,
+        // then refuse writes in read-only mode, then audit what got through
+        // (with the status code the client will see)
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            rest::legacy_status_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            audit::audit_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            read_only::read_only_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit::rate_limit_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth::auth_middleware,
+        ));
 
     // OpenAPI documentation
     let openapi = routes::swagger::ApiDoc::openapi();
@@ -184,12 +562,71 @@ async fn main(
     let router = Router::new()
         .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", openapi))
         .route("/health", get(health_check))
+        // Inbound webhooks and chat platforms authenticate by signature, not by API key
+        .merge(
+            routes::inbound::public_router()
+                .merge(routes::discord::public_router())
+                .merge(routes::github::public_router())
+                .merge(routes::telegram::public_router())
+                .layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    read_only::read_only_middleware,
+                )),
+        )
         .merge(protected_routes)
+        // Our own per-route limit replaces axum's fixed 2 MB one
+        .layer(DefaultBodyLimit::disable())
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            body_limit::body_limit_middleware,
+        ))
         .layer(CorsLayer::permissive())
+        .layer(PropagateRequestIdLayer::x_request_id())
+        .layer(TraceLayer::new_for_http().make_span_with(telemetry::request_span))
+        // Outermost, so the request span already sees the ID
+        .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
         .with_state(state);
 
     tracing::info!("📚 Swagger UI: /swagger-ui");
     tracing::info!("✅ Kaiba API ready - Rei awakens in Tei");
 
-    Ok(router.into())
+    Ok(KaibaService {
+        router,
+        tasks,
+        event_stream,
+    })
+}
+
+/// The API server plus the background work it owns
+pub struct KaibaService {
+    router: Router,
+    tasks: Arc<TaskSupervisor>,
+    event_stream: Arc<EventStream>,
+}
+
+#[async_trait::async_trait]
+impl shuttle_runtime::Service for KaibaService {
+    /// Serve until Ctrl+C/SIGTERM, then let in-flight requests, deliveries
+    /// and learning runs finish before exiting
+    async fn bind(self, addr: SocketAddr) -> Result<(), shuttle_runtime::Error> {
+        let listener = tokio::net::TcpListener::bind(addr)
+            .await
+            .map_err(shuttle_runtime::CustomError::new)?;
+
+        let event_stream = self.event_stream;
+        axum::serve(listener, self.router)
+            .with_graceful_shutdown(async move {
+                supervisor::shutdown_signal().await;
+                // Open event streams would otherwise hold shutdown forever
+                event_stream.close();
+            })
+            .await
+            .map_err(shuttle_runtime::CustomError::new)?;
+
+        tracing::info!("🛑 HTTP server stopped, draining background tasks...");
+        self.tasks.shutdown(SHUTDOWN_DRAIN_TIMEOUT).await;
+        telemetry::shutdown().await;
+        tracing::info!("👋 Kaiba API shut down");
+        Ok(())
+    }
 }
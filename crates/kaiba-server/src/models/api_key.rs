@@ -0,0 +1,133 @@
+//! API Key - Scoped credentials for callers other than the master key
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// What an API key may do; each scope includes the ones before it
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq, PartialOrd, Ord, ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyScope {
+    /// Read Reis, memories, prompts and history
+    #[default]
+    Read,
+    /// Also add and edit memories and call Reis
+    MemoryWrite,
+    /// Everything, including Rei/Tei/webhook and key management
+    Admin,
+}
+
+impl ApiKeyScope {
+    /// Whether this scope covers an action requiring `required`
+    pub fn allows(self, required: ApiKeyScope) -> bool {
+        self >= required
+    }
+}
+
+impl std::fmt::Display for ApiKeyScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApiKeyScope::Read => write!(f, "read"),
+            ApiKeyScope::MemoryWrite => write!(f, "memory_write"),
+            ApiKeyScope::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+impl std::str::FromStr for ApiKeyScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "read" => Ok(ApiKeyScope::Read),
+            "memory_write" => Ok(ApiKeyScope::MemoryWrite),
+            "admin" => Ok(ApiKeyScope::Admin),
+            _ => Err(format!("Unknown API key scope: {}", s)),
+        }
+    }
+}
+
+/// Stored API key (only the hash of the key is kept)
+#[derive(Debug, Clone, FromRow)]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub name: String,
+    pub key_prefix: String,
+    pub scope: String,
+    pub rei_ids: Vec<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub user_id: Option<Uuid>,
+}
+
+impl ApiKey {
+    /// Parsed scope; unknown values get the least access
+    pub fn scope(&self) -> ApiKeyScope {
+        self.scope.parse().unwrap_or(ApiKeyScope::Read)
+    }
+
+    /// Whether the key may access this Rei
+    pub fn allows_rei(&self, rei_id: Uuid) -> bool {
+        self.rei_ids.is_empty() || self.rei_ids.contains(&rei_id)
+    }
+}
+
+// ============================================
+// Request/Response DTOs
+// ============================================
+
+/// Create an API key
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateApiKeyRequest {
+    /// Who or what uses the key (e.g. "ci", "discord-bot")
+    pub name: String,
+    #[serde(default)]
+    pub scope: ApiKeyScope,
+    /// Restrict the key to these Reis (default: all)
+    #[serde(default)]
+    pub rei_ids: Vec<Uuid>,
+    /// Act as this user, seeing only their resources (default: operator key)
+    pub user_id: Option<Uuid>,
+}
+
+/// API key response (key hash omitted)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApiKeyResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub key_prefix: String,
+    pub scope: ApiKeyScope,
+    pub rei_ids: Vec<Uuid>,
+    pub user_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl From<ApiKey> for ApiKeyResponse {
+    fn from(k: ApiKey) -> Self {
+        Self {
+            scope: k.scope(),
+            id: k.id,
+            name: k.name,
+            key_prefix: k.key_prefix,
+            rei_ids: k.rei_ids,
+            user_id: k.user_id,
+            created_at: k.created_at,
+            last_used_at: k.last_used_at,
+            revoked_at: k.revoked_at,
+        }
+    }
+}
+
+/// Newly created API key; `key` is only shown once
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CreateApiKeyResponse {
+    pub key: String,
+    pub api_key: ApiKeyResponse,
+}
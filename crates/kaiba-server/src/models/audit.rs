@@ -0,0 +1,41 @@
+//! Audit Log - Record of changes made through the API
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// One create, update or delete
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct AuditLog {
+    pub id: Uuid,
+    /// API key name, `master key`, or `anonymous` when auth is disabled
+    pub actor: String,
+    pub api_key_id: Option<Uuid>,
+    pub method: String,
+    /// Route template (e.g. `/kaiba/rei/:id`)
+    pub route: String,
+    pub path: String,
+    /// Last fixed route segment (e.g. `rei`, `memories`, `webhooks`)
+    pub entity_type: String,
+    pub entity_id: Option<Uuid>,
+    pub status: i32,
+    pub request_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Query parameters for the audit log
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct AuditLogQuery {
+    /// Only changes to this kind of entity (e.g. `rei`)
+    pub entity_type: Option<String>,
+    /// Only changes to this entity
+    pub entity_id: Option<Uuid>,
+    /// Only changes made with this API key
+    pub api_key_id: Option<Uuid>,
+    /// Only changes at or after this time
+    pub since: Option<DateTime<Utc>>,
+    /// Only changes before this time
+    pub until: Option<DateTime<Utc>>,
+}
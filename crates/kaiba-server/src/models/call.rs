@@ -3,7 +3,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 /// Task health status (from llm-toolkit)
@@ -26,6 +26,7 @@ pub struct CallLog {
     pub response: String,
     pub tokens_consumed: i32,
     pub context: Option<serde_json::Value>,
+    pub session_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -33,6 +34,17 @@ pub struct CallLog {
 // Request/Response DTOs
 // ============================================
 
+/// Query parameters for call history
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct CallHistoryQuery {
+    /// Only calls made after this time
+    pub since: Option<DateTime<Utc>>,
+    /// Only calls answered by this Tei
+    pub tei_id: Option<Uuid>,
+    /// Only calls from this chat session
+    pub session_id: Option<Uuid>,
+}
+
 /// Call context for LLM invocation
 #[derive(Debug, Serialize, Deserialize, Default, ToSchema)]
 pub struct CallContext {
@@ -46,9 +58,13 @@ pub struct CallContext {
 /// Call request
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct CallRequest {
+    #[serde(default)]
     pub tei_ids: Vec<Uuid>,
     pub message: String,
     pub context: Option<CallContext>,
+    /// Conversation session; earlier turns in the session are used as context
+    #[serde(default)]
+    pub session_id: Option<Uuid>,
 }
 
 /// Memory reference in response
@@ -65,4 +81,5 @@ pub struct CallResponse {
     pub tei_used: Uuid,
     pub tokens_consumed: i32,
     pub memories_included: Vec<MemoryReference>,
+    pub session_id: Option<Uuid>,
 }
@@ -2,9 +2,12 @@
 
 use chrono::{DateTime, Utc};
 use serde::Serialize;
+use std::collections::HashMap;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+use super::WebhookStatsResponse;
+
 /// Dashboard response - comprehensive Rei status overview
 #[derive(Debug, Serialize, ToSchema)]
 pub struct DashboardResponse {
@@ -32,6 +35,8 @@ pub struct DashboardState {
     pub tokens_used: i32,
     pub token_budget: i32,
     pub energy_regen_per_hour: i32,
+    /// False while autonomy is paused
+    pub autonomy_enabled: bool,
 }
 
 /// Activity timestamps
@@ -47,6 +52,8 @@ pub struct DashboardActivity {
 pub struct DashboardStats {
     pub memory_count: u64,
     pub tei_count: i64,
+    /// Events published since server start, by event type
+    pub event_counts: HashMap<String, u64>,
 }
 
 /// Webhook delivery status
@@ -54,5 +61,8 @@ pub struct DashboardStats {
 pub struct DashboardWebhooks {
     pub webhook_count: i64,
     pub last_delivery_at: Option<DateTime<Utc>>,
+    /// Failed or dead-lettered deliveries in the last 24 hours
     pub recent_failures: i64,
+    /// Delivery metrics per webhook (last 24 hours)
+    pub per_webhook: Vec<WebhookStatsResponse>,
 }
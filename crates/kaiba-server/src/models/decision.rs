@@ -0,0 +1,142 @@
+//! Decision Policy - How a Rei picks its autonomous action
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// Strategy that picks Learn, Digest, Reflect or Rest
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DecisionStrategyKind {
+    /// Fixed thresholds on tokens, energy and pending memories
+    #[default]
+    Rules,
+    /// An LLM weighs mood, energy, pending memories and recent outcomes,
+    /// within the same thresholds
+    Llm,
+}
+
+impl std::fmt::Display for DecisionStrategyKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecisionStrategyKind::Rules => write!(f, "rules"),
+            DecisionStrategyKind::Llm => write!(f, "llm"),
+        }
+    }
+}
+
+impl std::str::FromStr for DecisionStrategyKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rules" => Ok(DecisionStrategyKind::Rules),
+            "llm" => Ok(DecisionStrategyKind::Llm),
+            _ => Err(format!("Unknown decision strategy: {}", s)),
+        }
+    }
+}
+
+/// Decision policy of one Rei (Reis without one use the defaults)
+#[derive(Debug, Clone, FromRow)]
+pub struct ReiDecisionPolicy {
+    pub rei_id: Uuid,
+    pub strategy: String,
+    pub min_energy_learn: i32,
+    pub min_energy_digest: i32,
+    pub min_tokens_action: i32,
+    pub memories_for_digest: i32,
+    pub min_energy_reflect: i32,
+    pub reflection_interval_hours: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ReiDecisionPolicy {
+    /// Stored strategy (unknown values fall back to rules)
+    pub fn strategy(&self) -> DecisionStrategyKind {
+        self.strategy.parse().unwrap_or_default()
+    }
+}
+
+/// Logged autonomous decision
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct DecisionLog {
+    pub id: Uuid,
+    pub rei_id: Uuid,
+    /// learn, digest, reflect or rest
+    pub action: String,
+    pub reason: String,
+    /// Strategy that made the decision
+    pub strategy: String,
+    /// Energy, tokens, mood, pending memories and recent outcomes considered
+    #[schema(value_type = Object)]
+    pub context: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+// ============================================
+// Request/Response DTOs
+// ============================================
+
+/// Set a Rei's decision policy (omitted fields use the defaults)
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct UpsertDecisionPolicyRequest {
+    /// Strategy (default: rules)
+    pub strategy: Option<DecisionStrategyKind>,
+    /// Minimum energy to learn (default: 50)
+    pub min_energy_learn: Option<i32>,
+    /// Minimum energy to digest (default: 60)
+    pub min_energy_digest: Option<i32>,
+    /// Minimum remaining tokens to act at all (default: 500)
+    pub min_tokens_action: Option<i32>,
+    /// Undigested memories before a digest is considered (default: 5)
+    pub memories_for_digest: Option<i32>,
+    /// Minimum energy to reflect (default: 30)
+    pub min_energy_reflect: Option<i32>,
+    /// Hours between reflections (default: 24)
+    pub reflection_interval_hours: Option<i32>,
+}
+
+/// Rei decision policy response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DecisionPolicyResponse {
+    pub rei_id: Uuid,
+    pub strategy: DecisionStrategyKind,
+    pub min_energy_learn: i32,
+    pub min_energy_digest: i32,
+    pub min_tokens_action: i32,
+    pub memories_for_digest: i32,
+    pub min_energy_reflect: i32,
+    pub reflection_interval_hours: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<ReiDecisionPolicy> for DecisionPolicyResponse {
+    fn from(p: ReiDecisionPolicy) -> Self {
+        Self {
+            rei_id: p.rei_id,
+            strategy: p.strategy(),
+            min_energy_learn: p.min_energy_learn,
+            min_energy_digest: p.min_energy_digest,
+            min_tokens_action: p.min_tokens_action,
+            memories_for_digest: p.memories_for_digest,
+            min_energy_reflect: p.min_energy_reflect,
+            reflection_interval_hours: p.reflection_interval_hours,
+            created_at: p.created_at,
+            updated_at: p.updated_at,
+        }
+    }
+}
+
+/// Query parameters for decision history
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct DecisionHistoryQuery {
+    /// Only decisions made after this time
+    pub since: Option<DateTime<Utc>>,
+    /// Only this action (learn, digest, reflect or rest)
+    pub action: Option<String>,
+}
@@ -0,0 +1,117 @@
+//! Inbound Webhook Source - External services poking a Rei
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::MemoryType;
+
+/// What an inbound payload does to the Rei
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum InboundAction {
+    /// Store parsed items as memories
+    #[default]
+    Memory,
+    /// Kick a self-learning session
+    Learn,
+    /// Kick a digest
+    Digest,
+}
+
+impl std::fmt::Display for InboundAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InboundAction::Memory => write!(f, "memory"),
+            InboundAction::Learn => write!(f, "learn"),
+            InboundAction::Digest => write!(f, "digest"),
+        }
+    }
+}
+
+impl std::str::FromStr for InboundAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "memory" => Ok(InboundAction::Memory),
+            "learn" => Ok(InboundAction::Learn),
+            "digest" => Ok(InboundAction::Digest),
+            _ => Err(format!("Unknown inbound action: {}", s)),
+        }
+    }
+}
+
+/// Inbound source configuration (one per Rei and source name)
+#[derive(Debug, Clone, FromRow)]
+pub struct InboundSource {
+    pub id: Uuid,
+    pub rei_id: Uuid,
+    pub source: String,
+    pub secret: String,
+    pub action: String,
+    pub memory_type: String,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ============================================
+// Request/Response DTOs
+// ============================================
+
+/// Create or replace an inbound source
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpsertInboundSourceRequest {
+    /// Source name: "github", "rss", or any name for generic JSON
+    pub source: String,
+    /// Secret for HMAC-SHA256 signature (or X-Kaiba-Token) verification
+    pub secret: String,
+    /// What to do with payloads (default: memory)
+    #[serde(default)]
+    pub action: InboundAction,
+    /// Memory type for stored items (default: fact)
+    pub memory_type: Option<MemoryType>,
+    pub enabled: Option<bool>,
+}
+
+/// Inbound source response (secret omitted)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct InboundSourceResponse {
+    pub id: Uuid,
+    pub rei_id: Uuid,
+    pub source: String,
+    pub action: String,
+    pub memory_type: String,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<InboundSource> for InboundSourceResponse {
+    fn from(s: InboundSource) -> Self {
+        Self {
+            id: s.id,
+            rei_id: s.rei_id,
+            source: s.source,
+            action: s.action,
+            memory_type: s.memory_type,
+            enabled: s.enabled,
+            created_at: s.created_at,
+            updated_at: s.updated_at,
+        }
+    }
+}
+
+/// Result of receiving an inbound payload
+#[derive(Debug, Serialize, ToSchema)]
+pub struct InboundResponse {
+    pub source: String,
+    pub action: String,
+    /// Memories stored (memory action)
+    pub memories_created: usize,
+    /// Whether a learn/digest run was started
+    pub triggered: bool,
+}
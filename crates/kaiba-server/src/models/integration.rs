@@ -0,0 +1,83 @@
+//! Integrations - Platform integrations and their per-Rei switches
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Stored switch of one integration for one Rei (no row = enabled)
+#[derive(Debug, Clone, FromRow)]
+pub struct ReiIntegration {
+    pub rei_id: Uuid,
+    pub integration: String,
+    pub enabled: bool,
+    /// Summarize read conversations into memories
+    pub ingest: bool,
+    /// Newest message already summarized
+    pub ingested_until: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ============================================
+// Request/Response DTOs
+// ============================================
+
+/// Change an integration's switches for a Rei (omitted fields are kept)
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateReiIntegrationRequest {
+    /// Whether the integration answers for the Rei
+    pub enabled: Option<bool>,
+    /// Summarize conversations the integration reads into memories
+    pub ingest: Option<bool>,
+}
+
+/// An integration as it applies to one Rei
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReiIntegrationResponse {
+    /// Integration name, e.g. "discord"
+    pub integration: String,
+    /// Whether the integration answers for this Rei
+    pub enabled: bool,
+    /// Whether read conversations become memories (off by default)
+    pub ingest: bool,
+    /// Newest message already turned into memories
+    pub ingested_until: Option<DateTime<Utc>>,
+    /// Last changed (null = never switched, the defaults apply)
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl ReiIntegrationResponse {
+    /// The default of an integration never switched for the Rei
+    pub fn default_for(integration: &str) -> Self {
+        Self {
+            integration: integration.to_string(),
+            enabled: true,
+            ingest: false,
+            ingested_until: None,
+            updated_at: None,
+        }
+    }
+}
+
+impl From<ReiIntegration> for ReiIntegrationResponse {
+    fn from(i: ReiIntegration) -> Self {
+        Self {
+            integration: i.integration,
+            enabled: i.enabled,
+            ingest: i.ingest,
+            ingested_until: i.ingested_until,
+            updated_at: Some(i.updated_at),
+        }
+    }
+}
+
+/// Health of a registered integration
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct IntegrationHealth {
+    pub name: String,
+    pub healthy: bool,
+    /// Why the check failed, if it did
+    pub error: Option<String>,
+    pub checked_at: DateTime<Utc>,
+}
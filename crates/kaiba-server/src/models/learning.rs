@@ -0,0 +1,53 @@
+//! Learning Session - History of self-learning runs
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// Completed learning session
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct LearningSessionLog {
+    pub id: Uuid,
+    pub rei_id: Uuid,
+    /// web, or memory when learning from existing memories only
+    pub mode: String,
+    /// Search queries generated from the manifest
+    #[schema(value_type = Vec<String>)]
+    pub queries: serde_json::Value,
+    pub searches_completed: i32,
+    /// Queries skipped because MemoryKai already covered the topic
+    pub searches_skipped: i32,
+    pub memories_stored: i32,
+    /// Items learned from sources other than web search (e.g. feed entries)
+    pub items_learned: i32,
+    /// URLs of the sources the stored memories cite
+    #[schema(value_type = Vec<String>)]
+    pub sources: serde_json::Value,
+    /// One message per failed query
+    #[schema(value_type = Vec<String>)]
+    pub errors: serde_json::Value,
+    /// Searches Gemini rejected with a rate limit
+    pub rate_limited: i32,
+    /// Queries put off to the next session by a rate limit
+    #[schema(value_type = Vec<String>)]
+    pub queries_deferred: serde_json::Value,
+    pub energy_spent: i32,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+}
+
+// ============================================
+// Request/Response DTOs
+// ============================================
+
+/// Query parameters for learning history
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct LearningHistoryQuery {
+    /// Only sessions started after this time
+    pub since: Option<DateTime<Utc>>,
+    /// Only sessions with at least one failed query
+    #[serde(default)]
+    pub failed_only: bool,
+}
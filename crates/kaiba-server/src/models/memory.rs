@@ -1,11 +1,13 @@
 //! Memory - Long-term storage in Qdrant
 
 use chrono::{DateTime, Utc};
+use kaiba::{ReiEvent, WebhookEventType};
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
 
 /// Memory type
-#[derive(Debug, Clone, Serialize, Deserialize, Default, ToSchema)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum MemoryType {
     #[default]
@@ -39,6 +41,21 @@ impl std::fmt::Display for MemoryType {
     }
 }
 
+impl std::str::FromStr for MemoryType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "conversation" => Ok(MemoryType::Conversation),
+            "learning" => Ok(MemoryType::Learning),
+            "fact" => Ok(MemoryType::Fact),
+            "expertise" => Ok(MemoryType::Expertise),
+            "reflection" => Ok(MemoryType::Reflection),
+            _ => Err(format!("Unknown memory type: {}", s)),
+        }
+    }
+}
+
 /// Memory entry (stored in Qdrant)
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Memory {
@@ -56,6 +73,23 @@ pub struct Memory {
     pub created_at: DateTime<Utc>,
 }
 
+impl Memory {
+    /// Build a MemoryAdded event for the event bus
+    pub fn added_event(&self, rei_id: Uuid) -> ReiEvent {
+        ReiEvent::new(
+            rei_id,
+            WebhookEventType::MemoryAdded,
+            serde_json::json!({
+                "memory_id": self.id,
+                "memory_type": self.memory_type.to_string(),
+                "content": self.content,
+                "importance": self.importance,
+                "tags": self.tags,
+            }),
+        )
+    }
+}
+
 // ============================================
 // Request/Response DTOs
 // ============================================
@@ -75,6 +109,16 @@ pub struct CreateMemoryRequest {
     pub metadata: Option<serde_json::Value>,
 }
 
+/// Update memory request (omitted fields are kept)
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateMemoryRequest {
+    pub content: Option<String>,
+    pub memory_type: Option<MemoryType>,
+    pub importance: Option<f32>,
+    pub tags: Option<Vec<String>>,
+    pub metadata: Option<serde_json::Value>,
+}
+
 /// Search memories request
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct SearchMemoriesRequest {
@@ -92,6 +136,19 @@ pub struct SearchMemoriesRequest {
     pub tags_match_mode: TagMatchMode,
     /// Minimum importance score (0.0 - 1.0)
     pub min_importance: Option<f32>,
+    /// Only memories created after this time
+    pub created_after: Option<DateTime<Utc>>,
+    /// Only memories created at or before this time
+    pub created_before: Option<DateTime<Utc>>,
+}
+
+/// Query parameters for listing recent memories
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ListMemoriesQuery {
+    /// Only memories created after this time
+    pub since: Option<DateTime<Utc>>,
+    /// Maximum number of results (default: 50)
+    pub limit: Option<usize>,
 }
 
 /// Memory response
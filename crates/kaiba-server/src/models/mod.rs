@@ -4,20 +4,50 @@
 //! - Tei (体): Execution interface with expertise
 //! - Memory: Long-term storage
 //! - Call: LLM invocation
+//! - Learning: Self-learning session history
 //! - Webhook: Outbound webhook configuration
+//! - Inbound: Inbound webhook sources
+//! - Schedule: Per-Rei cron schedules
+//! - Decision: Per-Rei decision policies and the decision log
+//! - ApiKey: Scoped API keys
+//! - User: Owner of Reis, Teis and webhooks
+//! - Audit: Record of changes made through the API
+//! - SchedulerRun: History of autonomous cycles and triggers
+//! - Task: Scheduled one-off tasks
+//! - Integration: Per-Rei integration switches and health
 
+mod api_key;
+mod audit;
 mod call;
 mod dashboard;
+mod decision;
+mod inbound;
+mod integration;
+mod learning;
 mod memory;
 mod prompt;
 mod rei;
+mod schedule;
+mod scheduler_run;
+mod task;
 mod tei;
+mod user;
 mod webhook;
 
+pub use api_key::*;
+pub use audit::*;
 pub use call::*;
 pub use dashboard::*;
+pub use decision::*;
+pub use inbound::*;
+pub use integration::*;
+pub use learning::*;
 pub use memory::*;
 pub use prompt::*;
 pub use rei::*;
+pub use schedule::*;
+pub use scheduler_run::*;
+pub use task::*;
 pub use tei::*;
+pub use user::*;
 pub use webhook::*;
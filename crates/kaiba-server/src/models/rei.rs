@@ -1,9 +1,10 @@
 //! Rei (霊) - Persistent Persona Identity
 
 use chrono::{DateTime, Utc};
+use kaiba::{ReiEvent, WebhookEventType};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 /// Rei - Core persona identity
@@ -35,6 +36,8 @@ pub struct ReiState {
     pub last_digest_at: Option<DateTime<Utc>>,
     /// Last time Learn was completed (for dashboard)
     pub last_learn_at: Option<DateTime<Utc>>,
+    /// Whether the scheduler and trigger act for this Rei (false = paused)
+    pub autonomy_enabled: bool,
 }
 
 // ============================================
@@ -59,6 +62,15 @@ pub struct UpdateReiRequest {
     pub manifest: Option<serde_json::Value>,
 }
 
+/// Filters for listing Reis
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ListReisQuery {
+    /// Case-insensitive substring of the name
+    pub name: Option<String>,
+    /// Exact mood (e.g. "focused")
+    pub mood: Option<String>,
+}
+
 /// Rei response with state
 #[derive(Debug, Serialize, ToSchema)]
 pub struct ReiResponse {
@@ -83,6 +95,27 @@ pub struct ReiStateResponse {
     pub energy_regen_per_hour: i32,
     pub last_digest_at: Option<DateTime<Utc>>,
     pub last_learn_at: Option<DateTime<Utc>>,
+    /// False while autonomy is paused
+    pub autonomy_enabled: bool,
+}
+
+impl ReiStateResponse {
+    /// Build a StateChanged event for the event bus
+    pub fn changed_event(&self, rei_id: Uuid, reason: &str) -> ReiEvent {
+        ReiEvent::new(
+            rei_id,
+            WebhookEventType::StateChanged,
+            serde_json::json!({
+                "reason": reason,
+                "energy_level": self.energy_level,
+                "mood": self.mood,
+                "token_budget": self.token_budget,
+                "tokens_used": self.tokens_used,
+                "energy_regen_per_hour": self.energy_regen_per_hour,
+                "autonomy_enabled": self.autonomy_enabled,
+            }),
+        )
+    }
 }
 
 /// Update Rei state request
@@ -106,6 +139,7 @@ impl From<ReiState> for ReiStateResponse {
             energy_regen_per_hour: state.energy_regen_per_hour,
             last_digest_at: state.last_digest_at,
             last_learn_at: state.last_learn_at,
+            autonomy_enabled: state.autonomy_enabled,
         }
     }
 }
@@ -0,0 +1,59 @@
+//! Rei Schedule - Cron-style timing of a Rei's autonomous cycle
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Schedule of one Rei (Reis without one follow the global interval)
+#[derive(Debug, Clone, FromRow)]
+pub struct ReiSchedule {
+    pub rei_id: Uuid,
+    pub cron: String,
+    pub timezone: String,
+    pub next_run_at: Option<DateTime<Utc>>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ============================================
+// Request/Response DTOs
+// ============================================
+
+/// Set a Rei's schedule
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpsertScheduleRequest {
+    /// 5-field cron expression, e.g. "0 7 * * *" (every day at 07:00)
+    pub cron: String,
+    /// IANA timezone the expression is evaluated in (default: UTC)
+    pub timezone: Option<String>,
+}
+
+/// Rei schedule response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ScheduleResponse {
+    pub rei_id: Uuid,
+    pub cron: String,
+    pub timezone: String,
+    /// Next autonomous cycle (null = the expression never fires again)
+    pub next_run_at: Option<DateTime<Utc>>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<ReiSchedule> for ScheduleResponse {
+    fn from(s: ReiSchedule) -> Self {
+        Self {
+            rei_id: s.rei_id,
+            cron: s.cron,
+            timezone: s.timezone,
+            next_run_at: s.next_run_at,
+            last_run_at: s.last_run_at,
+            created_at: s.created_at,
+            updated_at: s.updated_at,
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Scheduler Run - History of autonomous cycles and triggers
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use super::Rei;
+
+/// What one Rei did in a run
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReiRunResult {
+    pub rei_id: Uuid,
+    pub rei_name: String,
+    /// Learn, Digest, Reflect, Rest or Skip
+    pub action: String,
+    pub success: bool,
+    pub details: Option<String>,
+}
+
+impl ReiRunResult {
+    pub fn new(rei: &Rei, action: &str, success: bool, details: impl Into<String>) -> Self {
+        Self {
+            rei_id: rei.id,
+            rei_name: rei.name.clone(),
+            action: action.to_string(),
+            success,
+            details: Some(details.into()),
+        }
+    }
+}
+
+/// One scheduler cycle, due-schedule run or trigger call
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct SchedulerRun {
+    pub id: Uuid,
+    /// interval, schedule or trigger
+    pub kind: String,
+    pub dry_run: bool,
+    pub started_at: DateTime<Utc>,
+    /// Unset while running, or when the run was interrupted
+    pub finished_at: Option<DateTime<Utc>>,
+    pub reis_processed: i32,
+    /// Reis whose action failed
+    pub errors: i32,
+    /// Why the run as a whole failed, if it did
+    pub error: Option<String>,
+    #[schema(value_type = Vec<ReiRunResult>)]
+    pub results: serde_json::Value,
+}
+
+/// Query parameters for scheduler run history
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SchedulerRunQuery {
+    /// Only runs of this kind (interval, schedule or trigger)
+    pub kind: Option<String>,
+    /// Only runs started at or after this time
+    pub since: Option<DateTime<Utc>>,
+    /// Only runs with (`true`) or without (`false`) failures; unfinished
+    /// runs count as failed
+    pub failed: Option<bool>,
+}
@@ -0,0 +1,50 @@
+//! Rei Task - One-off instructions carried out at a given time
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// A scheduled one-off task
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct ReiTask {
+    pub id: Uuid,
+    pub rei_id: Uuid,
+    /// Message sent through the call pipeline when the task comes due
+    pub instruction: String,
+    /// Whether the Rei's memories are searched for context
+    pub include_memories: bool,
+    pub due_at: DateTime<Utc>,
+    /// pending, running, completed or failed
+    pub status: String,
+    /// The Rei's response (completed tasks)
+    pub result: Option<String>,
+    /// Why the task failed (failed tasks)
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+// ============================================
+// Request/Response DTOs
+// ============================================
+
+/// Schedule a task
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateTaskRequest {
+    /// What the Rei should do, e.g. "Summarize this week's notes"
+    pub instruction: String,
+    /// When to run it (a time in the past runs it on the next check)
+    pub due_at: DateTime<Utc>,
+    /// Search the Rei's memories for context (default: true)
+    pub include_memories: Option<bool>,
+}
+
+/// Query parameters for listing tasks
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct TaskQuery {
+    /// Only tasks with this status (pending, running, completed or failed)
+    pub status: Option<String>,
+}
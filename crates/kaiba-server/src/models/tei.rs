@@ -3,7 +3,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 /// LLM Provider
@@ -100,6 +100,15 @@ pub struct UpdateTeiRequest {
     pub expertise: Option<serde_json::Value>,
 }
 
+/// Filters for listing Teis
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ListTeisQuery {
+    /// Case-insensitive substring of the name
+    pub name: Option<String>,
+    /// Provider (e.g. "anthropic")
+    pub provider: Option<String>,
+}
+
 /// Tei response
 #[derive(Debug, Serialize, ToSchema)]
 pub struct TeiResponse {
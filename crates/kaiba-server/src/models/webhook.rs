@@ -1,11 +1,16 @@
 //! Webhook DTOs
 
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
-use kaiba::{DeliveryStatus, WebhookEventType};
+use kaiba::{
+    DeliveryStatus, EventDefinition, WebhookCondition, WebhookDeliveryStats, WebhookEventType,
+    WebhookPayload, WebhookTlsConfig,
+};
 
 /// Request to create a new webhook
 #[derive(Debug, Deserialize, ToSchema)]
@@ -19,15 +24,33 @@ pub struct CreateWebhookRequest {
     /// Event types to subscribe to (defaults to "all")
     #[serde(default)]
     pub events: Option<Vec<String>>,
-    /// Custom headers to include
+    /// Data conditions that must all match (e.g., "importance >= 0.8")
+    #[serde(default)]
+    pub conditions: Option<Vec<String>>,
+    /// Custom headers to include; values may reference deployment secrets
+    /// as `${secret:name}` (resolved from WEBHOOK_SECRET_<NAME> at delivery time)
     #[serde(default)]
     pub headers: Option<serde_json::Value>,
     /// Maximum retry attempts (default: 3)
     pub max_retries: Option<i32>,
     /// Timeout in milliseconds (default: 30000)
     pub timeout_ms: Option<i32>,
-    /// Payload format transformation (e.g., "github_issue")
+    /// Auto-disable after this many consecutive failed deliveries (default: never)
+    pub max_consecutive_failures: Option<i32>,
+    /// Maximum deliveries per minute; excess deliveries are queued (default: unlimited)
+    pub rate_limit_per_minute: Option<i32>,
+    /// "immediate" (default) or "batched" (events delivered together every N minutes)
+    pub delivery_mode: Option<String>,
+    /// Minutes between batched deliveries (default: 15)
+    pub batch_interval_minutes: Option<i32>,
+    /// Custom CA / client certificate for receivers behind private CAs
+    pub tls: Option<WebhookTlsRequest>,
+    /// Payload format transformation (e.g., "github_issue", "jira_issue", "linear_issue", "discord_embed")
     pub payload_format: Option<String>,
+    /// Format-specific options (e.g., {"labels": {...}, "assignees": [...], "milestone": 1} for github_issue)
+    pub format_options: Option<serde_json::Value>,
+    /// Jinja payload template (rendered with event, rei_id, delivery_id, timestamp, data)
+    pub payload_template: Option<String>,
 }
 
 /// Request to update a webhook
@@ -38,24 +61,79 @@ pub struct UpdateWebhookRequest {
     pub secret: Option<String>,
     pub enabled: Option<bool>,
     pub events: Option<Vec<String>>,
+    pub conditions: Option<Vec<String>>,
     pub headers: Option<serde_json::Value>,
     pub max_retries: Option<i32>,
     pub timeout_ms: Option<i32>,
+    /// Auto-disable threshold (0 = never)
+    pub max_consecutive_failures: Option<i32>,
+    /// Deliveries per minute (0 = unlimited)
+    pub rate_limit_per_minute: Option<i32>,
+    /// "immediate" or "batched"
+    pub delivery_mode: Option<String>,
+    pub batch_interval_minutes: Option<i32>,
+    /// TLS settings (all fields null = clear)
+    pub tls: Option<WebhookTlsRequest>,
     pub payload_format: Option<String>,
+    pub format_options: Option<serde_json::Value>,
+    pub payload_template: Option<String>,
+}
+
+/// TLS settings for a webhook
+///
+/// Values are PEM text or `${secret:name}` references (recommended for the key).
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct WebhookTlsRequest {
+    /// Additional root CA certificate to trust
+    pub ca_cert_pem: Option<String>,
+    /// Client certificate chain for mutual TLS
+    pub client_cert_pem: Option<String>,
+    /// Client private key (PKCS#8) for mutual TLS
+    pub client_key_pem: Option<String>,
+}
+
+impl WebhookTlsRequest {
+    /// Convert to the domain config (None if nothing is set)
+    pub fn into_domain(self) -> Option<WebhookTlsConfig> {
+        let tls = WebhookTlsConfig {
+            ca_cert_pem: self.ca_cert_pem,
+            client_cert_pem: self.client_cert_pem,
+            client_key_pem: self.client_key_pem,
+        };
+        (!tls.is_empty()).then_some(tls)
+    }
+}
+
+/// Which TLS settings a webhook has
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookTlsSummary {
+    pub custom_ca: bool,
+    pub client_certificate: bool,
 }
 
 /// Webhook response
 #[derive(Debug, Serialize, ToSchema)]
 pub struct WebhookResponse {
     pub id: Uuid,
-    pub rei_id: Uuid,
+    /// Owning Rei (null for global webhooks)
+    pub rei_id: Option<Uuid>,
     pub name: String,
     pub url: String,
     pub enabled: bool,
     pub events: Vec<String>,
+    pub conditions: Vec<String>,
     pub max_retries: i32,
     pub timeout_ms: i32,
+    pub consecutive_failures: i32,
+    pub max_consecutive_failures: Option<i32>,
+    pub rate_limit_per_minute: Option<i32>,
+    pub delivery_mode: String,
+    pub batch_interval_minutes: i32,
+    /// Configured TLS settings (PEM contents are not returned)
+    pub tls: Option<WebhookTlsSummary>,
     pub payload_format: Option<String>,
+    pub format_options: serde_json::Value,
+    pub payload_template: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -69,10 +147,53 @@ pub struct WebhookDeliveryResponse {
     pub status: String,
     pub status_code: Option<i32>,
     pub attempts: i32,
+    /// Response time of the last attempt in milliseconds
+    pub duration_ms: Option<i32>,
     pub created_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
 }
 
+/// Query parameters for listing deliveries
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct DeliveriesQuery {
+    /// Filter by status (pending, success, failed, retrying, dead)
+    pub status: Option<String>,
+}
+
+/// Query parameters for webhook delivery stats
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct WebhookStatsQuery {
+    /// Only include deliveries from the last N hours (default: all time)
+    pub hours: Option<i64>,
+}
+
+/// Delivery count for one latency bucket
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LatencyBucketResponse {
+    /// Exclusive upper bound in ms (null = overflow bucket)
+    pub lt_ms: Option<i32>,
+    pub count: i64,
+}
+
+/// Webhook delivery metrics
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookStatsResponse {
+    pub webhook_id: Uuid,
+    pub name: String,
+    pub total: i64,
+    pub successful: i64,
+    /// Failed or dead-lettered deliveries
+    pub failed: i64,
+    /// Successful / finished deliveries (null if none finished)
+    pub success_rate: Option<f64>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub last_failure_at: Option<DateTime<Utc>>,
+    pub latency_p50_ms: Option<f64>,
+    pub latency_p95_ms: Option<f64>,
+    pub latency_p99_ms: Option<f64>,
+    pub latency_histogram: Vec<LatencyBucketResponse>,
+}
+
 /// Request to trigger a test webhook
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct TriggerWebhookRequest {
@@ -80,6 +201,25 @@ pub struct TriggerWebhookRequest {
     pub event: Option<String>,
     /// Custom data to include in payload
     pub data: Option<serde_json::Value>,
+    /// Render the request without sending it (no delivery is recorded)
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Rendered webhook request returned by a dry run
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookDryRunResponse {
+    pub webhook_id: Uuid,
+    pub url: String,
+    pub event: String,
+    /// Whether the webhook's event filter and conditions accept this payload
+    pub would_deliver: bool,
+    /// Request headers (secret references are masked)
+    pub headers: BTreeMap<String, String>,
+    /// Formatted request body (null if rendering failed)
+    pub body: Option<serde_json::Value>,
+    /// Formatter, template, or header error
+    pub error: Option<String>,
 }
 
 impl WebhookResponse {
@@ -91,9 +231,21 @@ impl WebhookResponse {
             url: webhook.url,
             enabled: webhook.enabled,
             events: webhook.events.iter().map(|e| e.to_string()).collect(),
+            conditions: webhook.conditions.iter().map(|c| c.to_string()).collect(),
             max_retries: webhook.max_retries,
             timeout_ms: webhook.timeout_ms,
+            consecutive_failures: webhook.consecutive_failures,
+            max_consecutive_failures: webhook.max_consecutive_failures,
+            rate_limit_per_minute: webhook.rate_limit_per_minute,
+            delivery_mode: webhook.delivery_mode.to_string(),
+            batch_interval_minutes: webhook.batch_interval_minutes,
+            tls: webhook.tls.as_ref().map(|tls| WebhookTlsSummary {
+                custom_ca: tls.ca_cert_pem.is_some(),
+                client_certificate: tls.has_client_identity(),
+            }),
             payload_format: webhook.payload_format,
+            format_options: webhook.format_options,
+            payload_template: webhook.payload_template,
             created_at: webhook.created_at,
             updated_at: webhook.updated_at,
         }
@@ -111,16 +263,125 @@ impl WebhookDeliveryResponse {
                 DeliveryStatus::Success => "success",
                 DeliveryStatus::Failed => "failed",
                 DeliveryStatus::Retrying => "retrying",
+                DeliveryStatus::Dead => "dead",
             }
             .to_string(),
             status_code: delivery.status_code,
             attempts: delivery.attempts,
+            duration_ms: delivery.duration_ms,
             created_at: delivery.created_at,
             completed_at: delivery.completed_at,
         }
     }
 }
 
+impl WebhookDryRunResponse {
+    /// Build from the rendered `(headers, body)` or the rendering error
+    pub fn new(
+        webhook: &kaiba::ReiWebhook,
+        payload: &WebhookPayload,
+        rendered: Result<(Vec<(String, String)>, serde_json::Value), String>,
+    ) -> Self {
+        let (headers, body, error) = match rendered {
+            Ok((headers, body)) => (headers.into_iter().collect(), Some(body), None),
+            Err(e) => (BTreeMap::new(), None, Some(e)),
+        };
+
+        Self {
+            webhook_id: webhook.id,
+            url: webhook.url.clone(),
+            event: payload.event.to_string(),
+            would_deliver: webhook.should_deliver(payload),
+            headers,
+            body,
+            error,
+        }
+    }
+}
+
+/// Request to register a custom event
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateEventDefinitionRequest {
+    /// Event name without the `custom:` prefix (e.g., "deploy_finished")
+    pub name: String,
+    pub description: Option<String>,
+    /// JSON Schema subset for the event data (default: accept anything)
+    pub schema: Option<serde_json::Value>,
+}
+
+/// Request to update a custom event
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateEventDefinitionRequest {
+    pub description: Option<String>,
+    pub schema: Option<serde_json::Value>,
+}
+
+/// Registered custom event
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EventDefinitionResponse {
+    pub name: String,
+    /// Event type to use in webhook subscriptions (e.g., "custom:deploy_finished")
+    pub event: String,
+    pub description: Option<String>,
+    pub schema: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request to emit a custom event for a Rei
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct EmitEventRequest {
+    /// Event data, validated against the event's schema (default: `{}`)
+    pub data: Option<serde_json::Value>,
+}
+
+/// Emitted custom event
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EmitEventResponse {
+    pub event_id: Uuid,
+    pub rei_id: Uuid,
+    pub event: String,
+}
+
+impl EventDefinitionResponse {
+    pub fn from_domain(definition: EventDefinition) -> Self {
+        Self {
+            event: definition.event_type().to_string(),
+            name: definition.name,
+            description: definition.description,
+            schema: definition.schema.as_value().clone(),
+            created_at: definition.created_at,
+            updated_at: definition.updated_at,
+        }
+    }
+}
+
+impl WebhookStatsResponse {
+    pub fn from_domain(webhook: &kaiba::ReiWebhook, stats: WebhookDeliveryStats) -> Self {
+        Self {
+            webhook_id: webhook.id,
+            name: webhook.name.clone(),
+            total: stats.total,
+            successful: stats.successful,
+            failed: stats.failed,
+            success_rate: stats.success_rate(),
+            last_success_at: stats.last_success_at,
+            last_failure_at: stats.last_failure_at,
+            latency_p50_ms: stats.latency_p50_ms,
+            latency_p95_ms: stats.latency_p95_ms,
+            latency_p99_ms: stats.latency_p99_ms,
+            latency_histogram: stats
+                .latency_histogram
+                .into_iter()
+                .map(|b| LatencyBucketResponse {
+                    lt_ms: b.lt_ms,
+                    count: b.count,
+                })
+                .collect(),
+        }
+    }
+}
+
 /// Parse event type strings to domain types
 pub fn parse_event_types(events: Option<Vec<String>>) -> Vec<WebhookEventType> {
     events
@@ -132,6 +393,8 @@ pub fn parse_event_types(events: Option<Vec<String>>) -> Vec<WebhookEventType> {
                     "memory_added" => WebhookEventType::MemoryAdded,
                     "search_completed" => WebhookEventType::SearchCompleted,
                     "learning_completed" => WebhookEventType::LearningCompleted,
+                    "digest_completed" => WebhookEventType::DigestCompleted,
+                    "task_completed" => WebhookEventType::TaskCompleted,
                     "all" => WebhookEventType::All,
                     s if s.starts_with("custom:") => {
                         WebhookEventType::Custom(s.strip_prefix("custom:").unwrap().to_string())
@@ -142,3 +405,8 @@ pub fn parse_event_types(events: Option<Vec<String>>) -> Vec<WebhookEventType> {
         })
         .unwrap_or_else(|| vec![WebhookEventType::All])
 }
+
+/// Parse condition expressions to domain types
+pub fn parse_conditions(conditions: Vec<String>) -> Result<Vec<WebhookCondition>, String> {
+    conditions.iter().map(|c| c.parse()).collect()
+}
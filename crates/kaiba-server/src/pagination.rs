@@ -0,0 +1,230 @@
+//! Pagination - Shared `limit`/`offset`/`sort` parameters for list endpoints
+//!
+//! List handlers take a [`Pagination`] extractor next to their own filter
+//! query and return a [`Page`], which carries the unpaged total in the
+//! `X-Total-Count` header so the body stays a plain JSON array.
+
+use async_trait::async_trait;
+use axum::{
+    extract::{FromRequestParts, Query},
+    http::{request::Parts, HeaderValue},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::IntoParams;
+
+use crate::error::{ApiError, FieldError};
+
+/// Header carrying the number of items before paging
+pub const TOTAL_COUNT_HEADER: &str = "x-total-count";
+
+/// Page size when `limit` is omitted
+pub const DEFAULT_LIMIT: i64 = 50;
+
+/// Largest accepted `limit`
+pub const MAX_LIMIT: i64 = 200;
+
+/// Query parameters shared by list endpoints
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PageQuery {
+    /// Maximum items to return (default 50, max 200)
+    pub limit: Option<i64>,
+    /// Items to skip (default 0)
+    pub offset: Option<i64>,
+    /// Sort field; prefix with `-` for descending (e.g. `-created_at`)
+    pub sort: Option<String>,
+}
+
+/// Sort order requested by the client
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sort {
+    pub field: String,
+    pub descending: bool,
+}
+
+impl Sort {
+    pub fn asc(field: &str) -> Self {
+        Self {
+            field: field.to_string(),
+            descending: false,
+        }
+    }
+
+    pub fn desc(field: &str) -> Self {
+        Self {
+            field: field.to_string(),
+            descending: true,
+        }
+    }
+
+    fn parse(sort: &str) -> Self {
+        match sort.strip_prefix('-') {
+            Some(field) => Self::desc(field),
+            None => Self::asc(sort.trim_start_matches('+')),
+        }
+    }
+}
+
+/// Validated paging parameters
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pagination {
+    pub limit: i64,
+    pub offset: i64,
+    sort: Option<Sort>,
+}
+
+impl Pagination {
+    fn from_query(query: PageQuery) -> Result<Self, Vec<FieldError>> {
+        let mut errors = Vec::new();
+        let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+        if !(1..=MAX_LIMIT).contains(&limit) {
+            errors.push(FieldError::new(
+                "limit",
+                format!("must be between 1 and {}", MAX_LIMIT),
+            ));
+        }
+        let offset = query.offset.unwrap_or(0);
+        if offset < 0 {
+            errors.push(FieldError::new("offset", "must not be negative"));
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(Self {
+            limit,
+            offset,
+            sort: query
+                .sort
+                .filter(|s| !s.trim().is_empty())
+                .map(|s| Sort::parse(s.trim())),
+        })
+    }
+
+    /// Requested sort if its field is one of `allowed`, else `default`
+    ///
+    /// Unknown fields are rejected rather than ignored, so a typo doesn't
+    /// silently return a differently ordered page.
+    pub fn sort_or(&self, allowed: &[&str], default: Sort) -> Result<Sort, ApiError> {
+        match &self.sort {
+            None => Ok(default),
+            Some(sort) if allowed.contains(&sort.field.as_str()) => Ok(sort.clone()),
+            Some(sort) => Err(ApiError::validation(vec![FieldError::new(
+                "sort",
+                format!(
+                    "unknown field '{}' (expected one of: {})",
+                    sort.field,
+                    allowed.join(", ")
+                ),
+            )])),
+        }
+    }
+
+    /// Page an already filtered and sorted list
+    pub fn page<T>(&self, items: Vec<T>) -> Page<T> {
+        let total = items.len() as i64;
+        let items = items
+            .into_iter()
+            .skip(self.offset as usize)
+            .take(self.limit as usize)
+            .collect();
+        Page { items, total }
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Pagination
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(query) = Query::<PageQuery>::from_request_parts(parts, state)
+            .await
+            .map_err(|rejection| ApiError::bad_request(rejection.body_text()))?;
+        Self::from_query(query).map_err(ApiError::validation)
+    }
+}
+
+/// One page of a list, with the total before paging
+#[derive(Debug)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: i64,
+}
+
+impl<T: Serialize> IntoResponse for Page<T> {
+    fn into_response(self) -> Response {
+        let mut response = Json(self.items).into_response();
+        response
+            .headers_mut()
+            .insert(TOTAL_COUNT_HEADER, HeaderValue::from(self.total));
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(limit: Option<i64>, offset: Option<i64>, sort: Option<&str>) -> PageQuery {
+        PageQuery {
+            limit,
+            offset,
+            sort: sort.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_defaults_and_bounds() {
+        let pagination = Pagination::from_query(query(None, None, None)).unwrap();
+        assert_eq!(pagination.limit, DEFAULT_LIMIT);
+        assert_eq!(pagination.offset, 0);
+
+        let errors = Pagination::from_query(query(Some(MAX_LIMIT + 1), Some(-1), None))
+            .unwrap_err()
+            .into_iter()
+            .map(|e| e.field)
+            .collect::<Vec<_>>();
+        assert_eq!(errors, vec!["limit", "offset"]);
+        assert!(Pagination::from_query(query(Some(0), None, None)).is_err());
+    }
+
+    #[test]
+    fn test_sort() {
+        let default = Sort::desc("created_at");
+        let allowed = ["name", "created_at"];
+
+        let pagination = Pagination::from_query(query(None, None, Some("name"))).unwrap();
+        assert_eq!(
+            pagination.sort_or(&allowed, default.clone()).unwrap(),
+            Sort::asc("name")
+        );
+
+        let pagination = Pagination::from_query(query(None, None, None)).unwrap();
+        assert!(
+            pagination
+                .sort_or(&allowed, default.clone())
+                .unwrap()
+                .descending
+        );
+
+        let pagination = Pagination::from_query(query(None, None, Some("-bogus"))).unwrap();
+        assert!(pagination.sort_or(&allowed, default).is_err());
+    }
+
+    #[test]
+    fn test_page() {
+        let pagination = Pagination::from_query(query(Some(2), Some(3), None)).unwrap();
+        let page = pagination.page((0..6).collect());
+        assert_eq!(page.items, vec![3, 4]);
+        assert_eq!(page.total, 6);
+
+        let page = pagination.page(vec![1, 2]);
+        assert!(page.items.is_empty());
+        assert_eq!(page.total, 2);
+    }
+}
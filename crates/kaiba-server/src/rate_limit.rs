@@ -0,0 +1,213 @@
+//! API Rate Limiting - Per-key limits on expensive routes
+//!
+//! Calls, learning runs and searches cost LLM/embedding tokens, so each API
+//! key gets a sliding one-minute window per route class. Requests over the
+//! limit are rejected with `429 Too Many Requests` and a `Retry-After`
+//! header instead of being queued.
+
+use std::time::{Duration, Instant};
+
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::adapters::rate_limiter::SlidingWindowLimiter;
+use crate::auth::Principal;
+use crate::error::ApiError;
+use crate::AppState;
+
+/// Expensive route classes with their own limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RouteClass {
+    /// LLM calls (`/kaiba/rei/:id/call`)
+    Call,
+    /// Self-learning and digests (`learn`, `digest`, `learn/all`, `trigger`)
+    Learn,
+    /// Memory and web search
+    Search,
+}
+
+impl RouteClass {
+    /// Class of a request, or `None` for routes that are not limited
+    pub fn of(method: &Method, path: &str) -> Option<Self> {
+        if *method != Method::POST {
+            return None;
+        }
+
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+        match segments.as_slice() {
+            ["kaiba", "rei", _, "call"] => Some(RouteClass::Call),
+            ["kaiba", "rei", _, "learn"]
+            | ["kaiba", "rei", _, "digest"]
+            | ["kaiba", "learn", "all"]
+            | ["kaiba", "trigger"] => Some(RouteClass::Learn),
+            ["kaiba", "rei", _, "memories", "search"] | ["kaiba", "search"] => {
+                Some(RouteClass::Search)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Requests per minute per key for each class (0 = unlimited)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimits {
+    pub call: u32,
+    pub learn: u32,
+    pub search: u32,
+}
+
+impl Default for RateLimits {
+    fn default() -> Self {
+        Self {
+            call: 30,
+            learn: 5,
+            search: 60,
+        }
+    }
+}
+
+impl RateLimits {
+    fn for_class(&self, class: RouteClass) -> u32 {
+        match class {
+            RouteClass::Call => self.call,
+            RouteClass::Learn => self.learn,
+            RouteClass::Search => self.search,
+        }
+    }
+}
+
+/// Sliding-window limiter keyed by caller and route class
+#[derive(Debug, Default)]
+pub struct ApiRateLimiter {
+    limits: RateLimits,
+    windows: SlidingWindowLimiter<(String, RouteClass)>,
+}
+
+impl ApiRateLimiter {
+    pub fn new(limits: RateLimits) -> Self {
+        Self {
+            limits,
+            windows: SlidingWindowLimiter::new(),
+        }
+    }
+
+    /// Record a request; returns how long to wait when over the limit
+    fn check(&self, caller: &str, class: RouteClass, now: Instant) -> Result<(), Duration> {
+        let limit = self.limits.for_class(class);
+        if limit == 0 {
+            return Ok(());
+        }
+        // Rejected requests don't count; the oldest one frees the next slot
+        self.windows
+            .try_acquire((caller.to_string(), class), limit, now)
+    }
+}
+
+/// Rate limiting middleware
+/// Runs after authentication so each key is limited on its own
+pub async fn rate_limit_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(class) = RouteClass::of(request.method(), request.uri().path()) else {
+        return next.run(request).await;
+    };
+
+    let caller = match request.extensions().get::<Principal>() {
+        Some(Principal::Key(key)) => key.id.to_string(),
+        Some(Principal::Master) => "master".to_string(),
+        None => "anonymous".to_string(),
+    };
+
+    if let Err(retry_after) = state.rate_limiter.check(&caller, class, Instant::now()) {
+        // Round up so clients never retry a moment too early
+        let secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+        tracing::warn!(
+            "Rate limit exceeded for {} on {:?} ({})",
+            caller,
+            class,
+            request.uri().path()
+        );
+        let mut response = ApiError::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            format!("Rate limit exceeded, retry in {}s", secs),
+        )
+        .into_response();
+        response
+            .headers_mut()
+            .insert(header::RETRY_AFTER, HeaderValue::from(secs));
+        return response;
+    }
+
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_class() {
+        let rei = "/kaiba/rei/6f1c0d1e-8a5b-4a7e-9c1d-2b3e4f5a6b7c";
+        assert_eq!(
+            RouteClass::of(&Method::POST, &format!("{}/call", rei)),
+            Some(RouteClass::Call)
+        );
+        assert_eq!(
+            RouteClass::of(&Method::POST, &format!("{}/digest", rei)),
+            Some(RouteClass::Learn)
+        );
+        assert_eq!(
+            RouteClass::of(&Method::POST, &format!("{}/memories/search", rei)),
+            Some(RouteClass::Search)
+        );
+        assert_eq!(
+            RouteClass::of(&Method::POST, "/kaiba/search"),
+            Some(RouteClass::Search)
+        );
+        assert_eq!(
+            RouteClass::of(&Method::POST, &format!("{}/memories", rei)),
+            None
+        );
+        assert_eq!(
+            RouteClass::of(&Method::GET, &format!("{}/calls", rei)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_check_rejects_beyond_limit() {
+        let limiter = ApiRateLimiter::new(RateLimits {
+            call: 2,
+            learn: 0,
+            search: 1,
+        });
+        let now = Instant::now();
+
+        assert!(limiter.check("a", RouteClass::Call, now).is_ok());
+        assert!(limiter
+            .check("a", RouteClass::Call, now + Duration::from_secs(10))
+            .is_ok());
+        assert_eq!(
+            limiter.check("a", RouteClass::Call, now + Duration::from_secs(20)),
+            Err(Duration::from_secs(40))
+        );
+
+        // Other keys and classes have their own windows; 0 is unlimited
+        assert!(limiter.check("b", RouteClass::Call, now).is_ok());
+        assert!(limiter.check("a", RouteClass::Search, now).is_ok());
+        for _ in 0..100 {
+            assert!(limiter.check("a", RouteClass::Learn, now).is_ok());
+        }
+
+        // The oldest request leaving the window frees a slot
+        assert!(limiter
+            .check("a", RouteClass::Call, now + Duration::from_secs(60))
+            .is_ok());
+    }
+}
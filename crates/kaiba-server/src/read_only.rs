@@ -0,0 +1,71 @@
+//! Read-Only Mode - Reject writes on demo instances and during migrations
+//!
+//! With `READ_ONLY=true`, every request that would change state gets
+//! `403 Forbidden` (problem code `read_only`). Reads, prompt generation and
+//! the POST-based searches keep working, so a demo persona can still be
+//! browsed and used as a Tei prompt.
+
+use axum::{
+    extract::{Request, State},
+    http::{Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::error::ApiError;
+use crate::AppState;
+
+/// Whether a request is allowed while the server is read-only
+pub fn allows(method: &Method, path: &str) -> bool {
+    if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+        return true;
+    }
+
+    // Searches are POST for their request body but don't write anything
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    *method == Method::POST
+        && matches!(
+            segments.as_slice(),
+            ["kaiba", "rei", _, "memories", "search"] | ["kaiba", "search"]
+        )
+}
+
+pub async fn read_only_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if state.config.read_only && !allows(request.method(), request.uri().path()) {
+        return ApiError::new(
+            StatusCode::FORBIDDEN,
+            "Server is in read-only mode; changes are disabled",
+        )
+        .with_code("read_only")
+        .into_response();
+    }
+
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allows_reads_and_searches() {
+        assert!(allows(&Method::GET, "/kaiba/rei"));
+        assert!(allows(&Method::GET, "/kaiba/rei/abc/prompt"));
+        assert!(allows(&Method::POST, "/kaiba/rei/abc/memories/search"));
+        assert!(allows(&Method::POST, "/kaiba/search"));
+    }
+
+    #[test]
+    fn test_rejects_writes() {
+        assert!(!allows(&Method::POST, "/kaiba/rei"));
+        assert!(!allows(&Method::PATCH, "/kaiba/rei/abc/state"));
+        assert!(!allows(&Method::DELETE, "/kaiba/tei/abc"));
+        assert!(!allows(&Method::POST, "/kaiba/rei/abc/call"));
+        assert!(!allows(&Method::POST, "/kaiba/rei/abc/memories"));
+        assert!(!allows(&Method::POST, "/kaiba/rei/abc/inbound/github"));
+    }
+}
@@ -0,0 +1,82 @@
+//! REST Status Codes - 201 Created and 204 No Content
+//!
+//! Creates answer `201 Created` with a `Location` header pointing at the new
+//! resource, and deletes answer `204 No Content`. Clients written against
+//! the old `200` responses can keep working with `LEGACY_STATUS_CODES=true`:
+//! 201 becomes 200 (body and `Location` unchanged) and 204 becomes 200 with
+//! `{"status": "ok"}`.
+
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+use crate::AppState;
+
+/// `201 Created` with the new resource's URL and representation
+pub fn created<T: Serialize>(location: String, body: T) -> Response {
+    let mut response = (StatusCode::CREATED, Json(body)).into_response();
+    if let Ok(value) = HeaderValue::from_str(&location) {
+        response.headers_mut().insert(header::LOCATION, value);
+    }
+    response
+}
+
+/// Pre-201/204 status codes, for old clients
+fn to_legacy(response: Response) -> Response {
+    match response.status() {
+        StatusCode::CREATED => {
+            let (mut parts, body) = response.into_parts();
+            parts.status = StatusCode::OK;
+            Response::from_parts(parts, body)
+        }
+        StatusCode::NO_CONTENT => Json(serde_json::json!({ "status": "ok" })).into_response(),
+        _ => response,
+    }
+}
+
+pub async fn legacy_status_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let response = next.run(request).await;
+    if state.config.legacy_status_codes {
+        to_legacy(response)
+    } else {
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_created_sets_location() {
+        let response = created(
+            "/kaiba/rei/abc".to_string(),
+            serde_json::json!({"id": "abc"}),
+        );
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(response.headers()[header::LOCATION], "/kaiba/rei/abc");
+    }
+
+    #[test]
+    fn test_legacy_status_codes() {
+        let response = to_legacy(created("/kaiba/tei/abc".to_string(), serde_json::json!({})));
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::LOCATION], "/kaiba/tei/abc");
+
+        let response = to_legacy(StatusCode::NO_CONTENT.into_response());
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+
+        let response = to_legacy(StatusCode::NOT_FOUND.into_response());
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
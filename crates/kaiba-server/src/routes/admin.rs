@@ -0,0 +1,193 @@
+//! Admin Routes - Operator inspection of the running server
+//!
+//! Only the master key and admin-scoped operator keys reach these routes.
+
+use axum::{
+    extract::{Query, State},
+    routing::{get, post},
+    Json, Router,
+};
+
+use crate::config::RedactedConfig;
+use crate::error::ApiError;
+use crate::models::{AuditLog, AuditLogQuery, SchedulerRun, SchedulerRunQuery};
+use crate::pagination::{Page, PageQuery, Pagination, Sort};
+use crate::services::seed::{self, SeedResult};
+use crate::AppState;
+
+/// Show the effective server configuration (secrets redacted)
+#[utoipa::path(
+    get,
+    path = "/kaiba/admin/config",
+    responses(
+        (status = 200, description = "Effective configuration", body = RedactedConfig),
+        (status = 403, description = "Admin scope required")
+    ),
+    tag = "Admin"
+)]
+pub async fn get_config(State(state): State<AppState>) -> Json<RedactedConfig> {
+    Json(state.config.redacted())
+}
+
+/// List recorded changes, newest first
+#[utoipa::path(
+    get,
+    path = "/kaiba/admin/audit",
+    params(PageQuery, AuditLogQuery),
+    responses(
+        (status = 200, description = "Audit log entries; total in X-Total-Count", body = Vec<AuditLog>),
+        (status = 403, description = "Admin scope required"),
+        (status = 422, description = "Invalid paging parameters"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Admin"
+)]
+pub async fn list_audit_logs(
+    State(state): State<AppState>,
+    pagination: Pagination,
+    Query(query): Query<AuditLogQuery>,
+) -> Result<Page<AuditLog>, ApiError> {
+    let sort = pagination.sort_or(&["created_at"], Sort::desc("created_at"))?;
+    let order_by = if sort.descending {
+        "created_at DESC"
+    } else {
+        "created_at ASC"
+    };
+
+    let filter = r#"
+        WHERE ($1::text IS NULL OR entity_type = $1)
+          AND ($2::uuid IS NULL OR entity_id = $2)
+          AND ($3::uuid IS NULL OR api_key_id = $3)
+          AND ($4::timestamptz IS NULL OR created_at >= $4)
+          AND ($5::timestamptz IS NULL OR created_at < $5)
+    "#;
+
+    let total: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM audit_logs {}", filter))
+        .bind(&query.entity_type)
+        .bind(query.entity_id)
+        .bind(query.api_key_id)
+        .bind(query.since)
+        .bind(query.until)
+        .fetch_one(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+
+    let items = sqlx::query_as::<_, AuditLog>(&format!(
+        "SELECT * FROM audit_logs {} ORDER BY {} LIMIT $6 OFFSET $7",
+        filter, order_by
+    ))
+    .bind(&query.entity_type)
+    .bind(query.entity_id)
+    .bind(query.api_key_id)
+    .bind(query.since)
+    .bind(query.until)
+    .bind(pagination.limit)
+    .bind(pagination.offset)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    Ok(Page { items, total })
+}
+
+/// List scheduler cycles and trigger calls with each Rei's result, newest
+/// first
+#[utoipa::path(
+    get,
+    path = "/kaiba/admin/scheduler/runs",
+    params(PageQuery, SchedulerRunQuery),
+    responses(
+        (status = 200, description = "Scheduler runs; total in X-Total-Count", body = Vec<SchedulerRun>),
+        (status = 400, description = "Unknown run kind"),
+        (status = 403, description = "Admin scope required"),
+        (status = 422, description = "Invalid paging parameters"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Admin"
+)]
+pub async fn list_scheduler_runs(
+    State(state): State<AppState>,
+    pagination: Pagination,
+    Query(query): Query<SchedulerRunQuery>,
+) -> Result<Page<SchedulerRun>, ApiError> {
+    let sort = pagination.sort_or(&["started_at"], Sort::desc("started_at"))?;
+    let order_by = if sort.descending {
+        "started_at DESC"
+    } else {
+        "started_at ASC"
+    };
+
+    if let Some(kind) = query.kind.as_deref() {
+        if !["interval", "schedule", "trigger"].contains(&kind) {
+            return Err(ApiError::bad_request(format!(
+                "Unknown run kind: {} (expected interval, schedule or trigger)",
+                kind
+            )));
+        }
+    }
+
+    let filter = r#"
+        WHERE ($1::text IS NULL OR kind = $1)
+          AND ($2::timestamptz IS NULL OR started_at >= $2)
+          AND ($3::boolean IS NULL
+               OR $3 = (errors > 0 OR error IS NOT NULL OR finished_at IS NULL))
+    "#;
+
+    let total: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM scheduler_runs {}", filter))
+        .bind(&query.kind)
+        .bind(query.since)
+        .bind(query.failed)
+        .fetch_one(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+
+    let items = sqlx::query_as::<_, SchedulerRun>(&format!(
+        "SELECT * FROM scheduler_runs {} ORDER BY {} LIMIT $4 OFFSET $5",
+        filter, order_by
+    ))
+    .bind(&query.kind)
+    .bind(query.since)
+    .bind(query.failed)
+    .bind(pagination.limit)
+    .bind(pagination.offset)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    Ok(Page { items, total })
+}
+
+/// Create demo data (a Rei, two Teis and some memories)
+///
+/// Idempotent: existing demo data is reused and only missing parts are
+/// created. Memories need MemoryKai and embeddings to be configured.
+#[utoipa::path(
+    post,
+    path = "/kaiba/admin/seed",
+    responses(
+        (status = 200, description = "Demo data present", body = SeedResult),
+        (status = 403, description = "Admin scope required or read-only mode"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Admin"
+)]
+pub async fn seed_demo(State(state): State<AppState>) -> Result<Json<SeedResult>, ApiError> {
+    let result = seed::seed_demo(
+        &state.rei_service,
+        &state.tei_service,
+        state.memory_kai.as_deref(),
+        state.embedding.as_ref(),
+    )
+    .await
+    .map_err(ApiError::internal)?;
+
+    Ok(Json(result))
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/kaiba/admin/config", get(get_config))
+        .route("/kaiba/admin/audit", get(list_audit_logs))
+        .route("/kaiba/admin/scheduler/runs", get(list_scheduler_runs))
+        .route("/kaiba/admin/seed", post(seed_demo))
+}
@@ -0,0 +1,154 @@
+//! API Key Routes - Scoped keys for integrations and teammates
+//!
+//! Keys are shown once on creation; only a hash is stored. Revoked keys
+//! stay listed so their last use can still be audited.
+
+use axum::{
+    extract::{Path, State},
+    routing::{delete, get},
+    Extension, Json, Router,
+};
+use uuid::Uuid;
+
+use crate::auth::{self, Principal};
+use crate::error::ApiError;
+use crate::models::{ApiKey, ApiKeyResponse, CreateApiKeyRequest, CreateApiKeyResponse};
+use crate::validation::ValidJson;
+use crate::AppState;
+
+/// List API keys
+#[utoipa::path(
+    get,
+    path = "/kaiba/api-keys",
+    responses(
+        (status = 200, description = "API keys (newest first)", body = Vec<ApiKeyResponse>),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Auth"
+)]
+pub async fn list_api_keys(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<ApiKeyResponse>>, ApiError> {
+    let keys = sqlx::query_as::<_, ApiKey>("SELECT * FROM api_keys ORDER BY created_at DESC")
+        .fetch_all(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(keys.into_iter().map(ApiKeyResponse::from).collect()))
+}
+
+/// Create an API key
+///
+/// The returned `key` is not stored and cannot be shown again.
+#[utoipa::path(
+    post,
+    path = "/kaiba/api-keys",
+    request_body = CreateApiKeyRequest,
+    responses(
+        (status = 200, description = "API key created", body = CreateApiKeyResponse),
+        (status = 400, description = "Missing name, unknown Rei or unknown user"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Auth"
+)]
+pub async fn create_api_key(
+    State(state): State<AppState>,
+    principal: Option<Extension<Principal>>,
+    ValidJson(payload): ValidJson<CreateApiKeyRequest>,
+) -> Result<Json<CreateApiKeyResponse>, ApiError> {
+    for rei_id in &payload.rei_ids {
+        state
+            .rei_service
+            .get_by_id(*rei_id)
+            .await
+            .map_err(ApiError::internal)?
+            .ok_or(ApiError::bad_request(format!("Rei {} not found", rei_id)))?;
+    }
+
+    if let Some(user_id) = payload.user_id {
+        let exists =
+            sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM users WHERE id = $1)")
+                .bind(user_id)
+                .fetch_one(&state.pool)
+                .await
+                .map_err(ApiError::internal)?;
+        if !exists {
+            return Err(ApiError::bad_request(format!("User {} not found", user_id)));
+        }
+    }
+
+    let key = auth::generate_key();
+    let api_key = sqlx::query_as::<_, ApiKey>(
+        r#"
+        INSERT INTO api_keys (name, key_prefix, key_hash, scope, rei_ids, user_id)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING *
+        "#,
+    )
+    .bind(payload.name.trim())
+    .bind(auth::display_prefix(&key))
+    .bind(auth::hash_key(&key))
+    .bind(payload.scope.to_string())
+    .bind(&payload.rei_ids)
+    .bind(payload.user_id)
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    tracing::info!(
+        "🔑 API key created: {} ({}) by {}",
+        api_key.name,
+        payload.scope,
+        principal.as_ref().map_or("anonymous", |p| p.label())
+    );
+
+    Ok(Json(CreateApiKeyResponse {
+        key,
+        api_key: api_key.into(),
+    }))
+}
+
+/// Revoke an API key
+#[utoipa::path(
+    delete,
+    path = "/kaiba/api-keys/{id}",
+    params(("id" = Uuid, Path, description = "API key ID")),
+    responses(
+        (status = 200, description = "API key revoked", body = ApiKeyResponse),
+        (status = 404, description = "API key not found or already revoked"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Auth"
+)]
+pub async fn revoke_api_key(
+    State(state): State<AppState>,
+    principal: Option<Extension<Principal>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiKeyResponse>, ApiError> {
+    let api_key = sqlx::query_as::<_, ApiKey>(
+        r#"
+        UPDATE api_keys SET revoked_at = NOW()
+        WHERE id = $1 AND revoked_at IS NULL
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?
+    .ok_or(ApiError::not_found("API key not found or already revoked"))?;
+
+    tracing::info!(
+        "🔑 API key revoked: {} by {}",
+        api_key.name,
+        principal.as_ref().map_or("anonymous", |p| p.label())
+    );
+
+    Ok(Json(api_key.into()))
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/kaiba/api-keys", get(list_api_keys).post(create_api_key))
+        .route("/kaiba/api-keys/:id", delete(revoke_api_key))
+}
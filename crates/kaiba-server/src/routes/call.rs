@@ -1,17 +1,23 @@
 //! Call Routes - LLM Invocation with RAG
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     routing::post,
     Json, Router,
 };
+use kaiba::{ReiEvent, ReiEventBus, WebhookEventType};
 use llm_toolkit::ToPrompt;
+use tracing::Instrument;
 use uuid::Uuid;
 
+use crate::error::ApiError;
 use crate::models::{
-    CallLog, CallRequest, CallResponse, Memory, MemoryReference, Rei, ReiState, Tei,
+    CallHistoryQuery, CallLog, CallRequest, CallResponse, Memory, MemoryReference, Rei, ReiState,
+    Tei,
 };
+use crate::pagination::{Page, PageQuery, Pagination, Sort};
 use crate::routes::prompt::CallPromptDto;
+use crate::services::tei_expertise;
 use crate::AppState;
 
 /// Select Tei based on Rei's energy level
@@ -55,30 +61,36 @@ pub async fn call_llm(
     State(state): State<AppState>,
     Path(rei_id): Path<Uuid>,
     Json(payload): Json<CallRequest>,
-) -> Result<Json<CallResponse>, (axum::http::StatusCode, String)> {
+) -> Result<Json<CallResponse>, ApiError> {
+    execute_call(&state, rei_id, payload).await.map(Json)
+}
+
+/// Run the call pipeline for a Rei (also used by scheduled tasks)
+#[tracing::instrument(skip_all, fields(rei_id = %rei_id))]
+pub async fn execute_call(
+    state: &AppState,
+    rei_id: Uuid,
+    payload: CallRequest,
+) -> Result<CallResponse, ApiError> {
     let pool = &state.pool;
 
     // 1. Load Rei
-    let rei = sqlx::query_as::<_, Rei>("SELECT * FROM reis WHERE id = $1")
+    let rei = sqlx::query_as::<_, Rei>("SELECT * FROM reis WHERE id = $1 AND deleted_at IS NULL")
         .bind(rei_id)
         .fetch_optional(pool)
+        .instrument(tracing::info_span!("postgres.load_rei"))
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
-        .ok_or((
-            axum::http::StatusCode::NOT_FOUND,
-            "Rei not found".to_string(),
-        ))?;
+        .map_err(ApiError::internal)?
+        .ok_or(ApiError::not_found("Rei not found"))?;
 
     // 2. Load Rei state
     let rei_state = sqlx::query_as::<_, ReiState>("SELECT * FROM rei_states WHERE rei_id = $1")
         .bind(rei_id)
         .fetch_optional(pool)
+        .instrument(tracing::info_span!("postgres.load_rei_state"))
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
-        .ok_or((
-            axum::http::StatusCode::NOT_FOUND,
-            "Rei state not found".to_string(),
-        ))?;
+        .map_err(ApiError::internal)?
+        .ok_or(ApiError::not_found("Rei state not found"))?;
 
     // 3. Load requested Teis
     let teis = if payload.tei_ids.is_empty() {
@@ -94,7 +106,7 @@ pub async fn call_llm(
         .bind(rei_id)
         .fetch_all(pool)
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .map_err(ApiError::internal)?
     } else {
         // Load specific Teis
         let mut teis = Vec::new();
@@ -103,7 +115,7 @@ pub async fn call_llm(
                 .bind(tei_id)
                 .fetch_optional(pool)
                 .await
-                .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+                .map_err(ApiError::internal)?
             {
                 teis.push(tei);
             }
@@ -112,17 +124,12 @@ pub async fn call_llm(
     };
 
     if teis.is_empty() {
-        return Err((
-            axum::http::StatusCode::BAD_REQUEST,
-            "No Teis available for this Rei".to_string(),
-        ));
+        return Err(ApiError::bad_request("No Teis available for this Rei"));
     }
 
     // 4. Select Tei based on energy
-    let selected_tei = select_tei(rei_state.energy_level, &teis).ok_or((
-        axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-        "Failed to select Tei".to_string(),
-    ))?;
+    let selected_tei = select_tei(rei_state.energy_level, &teis)
+        .ok_or_else(|| ApiError::internal("Failed to select Tei"))?;
 
     tracing::info!(
         "Call for Rei {} using Tei {} ({}) - Energy: {}",
@@ -135,15 +142,22 @@ pub async fn call_llm(
     // 5. RAG: Search relevant memories if requested
     let context = payload.context.unwrap_or_default();
     let (memories, memories_included) = if context.include_memories {
-        search_memories_for_rag(&state, &rei_id, &payload.message, context.memory_limit).await?
+        search_memories_for_rag(state, &rei_id, &payload.message, context.memory_limit).await?
     } else {
         (vec![], vec![])
     };
 
-    // 6. Build system prompt with Rei identity and memories
-    let system_prompt = build_system_prompt(&rei, &memories);
+    // 6. Load earlier turns of the conversation session
+    let history = match payload.session_id {
+        Some(session_id) => load_session_history(pool, rei_id, session_id).await?,
+        None => vec![],
+    };
+
+    // 7. Build system prompt with Rei identity, Tei expertise and memories
+    let expertise = tei_expertise::latest_digest(selected_tei.expertise.as_ref());
+    let system_prompt = build_system_prompt(&rei, &memories, expertise);
 
-    // 7. TODO: Call LLM via llm-toolkit
+    // 8. TODO: Call LLM via llm-toolkit
     // For now, return mock response showing RAG context
     let memory_context = if memories.is_empty() {
         String::new()
@@ -159,17 +173,34 @@ pub async fn call_llm(
         )
     };
 
+    let history_context = if history.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "\n\n[Conversation - {} previous turns]\n{}",
+            history.len(),
+            history
+                .iter()
+                .map(|(message, response)| format!("User: {}\nRei: {}", message, response))
+                .collect::<Vec<_>>()
+                .join("\n")
+        )
+    };
+
     let response_text = format!(
-        "[Mock Response from {} via {}]{}\n\nReceived: {}\n\nSystem Prompt:\n{}\n\nThis is a placeholder response. LLM integration pending.",
+        "[Mock Response from {} via {}]{}{}\n\nReceived: {}\n\nSystem Prompt:\n{}\n\nThis is a placeholder response. LLM integration pending.",
         rei.name,
         selected_tei.model_id,
         memory_context,
+        history_context,
         payload.message,
         system_prompt
     );
     let tokens_consumed = 100; // Mock
 
-    // 8. Update Rei state (consume tokens, update last_active)
+    // 9. Update Rei state (consume tokens, update last_active); the state,
+    // the call log and the event are written together
+    let mut tx = pool.begin().await.map_err(ApiError::internal)?;
     sqlx::query(
         r#"
         UPDATE rei_states
@@ -179,15 +210,15 @@ pub async fn call_llm(
     )
     .bind(rei_id)
     .bind(tokens_consumed)
-    .execute(pool)
+    .execute(&mut *tx)
     .await
-    .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    .map_err(ApiError::internal)?;
 
-    // 9. Log the call
+    // 10. Log the call
     sqlx::query(
         r#"
-        INSERT INTO call_logs (rei_id, tei_id, message, response, tokens_consumed, context)
-        VALUES ($1, $2, $3, $4, $5, $6)
+        INSERT INTO call_logs (rei_id, tei_id, message, response, tokens_consumed, context, session_id)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
         "#,
     )
     .bind(rei_id)
@@ -196,25 +227,54 @@ pub async fn call_llm(
     .bind(&response_text)
     .bind(tokens_consumed)
     .bind(serde_json::to_value(&context).ok())
-    .execute(pool)
+    .bind(payload.session_id)
+    .execute(&mut *tx)
     .await
-    .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    .map_err(ApiError::internal)?;
 
-    Ok(Json(CallResponse {
+    let event = ReiEvent::new(
+        rei_id,
+        WebhookEventType::ResponseCompleted,
+        serde_json::json!({
+            "tei_id": selected_tei.id,
+            "tei_name": selected_tei.name,
+            "message": payload.message,
+            "tokens_consumed": tokens_consumed,
+            "session_id": payload.session_id,
+        }),
+    );
+    let unstaged = state
+        .event_bus
+        .stage_in(&mut tx, event)
+        .await
+        .map_err(ApiError::internal)?;
+    tx.commit().await.map_err(ApiError::internal)?;
+    if let Some(event) = unstaged {
+        state.event_bus.publish(event);
+    }
+
+    Ok(CallResponse {
         response: response_text,
         tei_used: selected_tei.id,
         tokens_consumed,
         memories_included,
-    }))
+        session_id: payload.session_id,
+    })
 }
 
 /// Get call history for a Rei
 #[utoipa::path(
     get,
     path = "/kaiba/rei/{rei_id}/calls",
-    params(("rei_id" = Uuid, Path, description = "Rei ID")),
+    params(
+        ("rei_id" = Uuid, Path, description = "Rei ID"),
+        CallHistoryQuery,
+        PageQuery
+    ),
     responses(
-        (status = 200, description = "Call history", body = Vec<CallLog>),
+        (status = 200, description = "Page of calls (newest first by default)", body = Vec<CallLog>,
+            headers(("x-total-count" = i64, description = "Calls matching the filters"))),
+        (status = 422, description = "Invalid paging or sort parameters"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Call"
@@ -222,16 +282,49 @@ pub async fn call_llm(
 pub async fn get_call_history(
     State(state): State<AppState>,
     Path(rei_id): Path<Uuid>,
-) -> Result<Json<Vec<CallLog>>, (axum::http::StatusCode, String)> {
-    let logs = sqlx::query_as::<_, CallLog>(
-        "SELECT * FROM call_logs WHERE rei_id = $1 ORDER BY created_at DESC LIMIT 100",
-    )
+    pagination: Pagination,
+    Query(query): Query<CallHistoryQuery>,
+) -> Result<Page<CallLog>, ApiError> {
+    let sort = pagination.sort_or(&["created_at", "tokens_consumed"], Sort::desc("created_at"))?;
+    // Only whitelisted columns reach the query
+    let order_by = match (sort.field.as_str(), sort.descending) {
+        ("tokens_consumed", true) => "tokens_consumed DESC, created_at DESC",
+        ("tokens_consumed", false) => "tokens_consumed ASC, created_at DESC",
+        (_, true) => "created_at DESC",
+        (_, false) => "created_at ASC",
+    };
+
+    let filter = r#"
+        WHERE rei_id = $1
+          AND ($2::timestamptz IS NULL OR created_at > $2)
+          AND ($3::uuid IS NULL OR tei_id = $3)
+          AND ($4::uuid IS NULL OR session_id = $4)
+    "#;
+
+    let total: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM call_logs {}", filter))
+        .bind(rei_id)
+        .bind(query.since)
+        .bind(query.tei_id)
+        .bind(query.session_id)
+        .fetch_one(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+
+    let items = sqlx::query_as::<_, CallLog>(&format!(
+        "SELECT * FROM call_logs {} ORDER BY {} LIMIT $5 OFFSET $6",
+        filter, order_by
+    ))
     .bind(rei_id)
+    .bind(query.since)
+    .bind(query.tei_id)
+    .bind(query.session_id)
+    .bind(pagination.limit)
+    .bind(pagination.offset)
     .fetch_all(&state.pool)
     .await
-    .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    .map_err(ApiError::internal)?;
 
-    Ok(Json(logs))
+    Ok(Page { items, total })
 }
 
 // ============================================
@@ -244,7 +337,7 @@ async fn search_memories_for_rag(
     rei_id: &Uuid,
     query: &str,
     limit: Option<usize>,
-) -> Result<(Vec<Memory>, Vec<MemoryReference>), (axum::http::StatusCode, String)> {
+) -> Result<(Vec<Memory>, Vec<MemoryReference>), ApiError> {
     // Check if services are available
     let memory_kai = match &state.memory_kai {
         Some(kai) => kai,
@@ -259,7 +352,7 @@ async fn search_memories_for_rag(
     // Generate query embedding
     let query_vector = embedding_service.embed(query).await.map_err(|e| {
         tracing::warn!("Failed to generate embedding for RAG: {}", e);
-        (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+        ApiError::internal(e)
     })?;
 
     // Search memories
@@ -269,7 +362,7 @@ async fn search_memories_for_rag(
         .await
         .map_err(|e| {
             tracing::warn!("Failed to search memories for RAG: {}", e);
-            (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+            ApiError::internal(e)
         })?;
 
     // Build memory references (similarity scores would come from Qdrant)
@@ -286,9 +379,37 @@ async fn search_memories_for_rag(
     Ok((memories, refs))
 }
 
+/// Maximum earlier turns fed back into a session call
+const SESSION_HISTORY_LIMIT: i64 = 10;
+
+/// Load the most recent turns of a session, oldest first
+async fn load_session_history(
+    pool: &sqlx::PgPool,
+    rei_id: Uuid,
+    session_id: Uuid,
+) -> Result<Vec<(String, String)>, ApiError> {
+    let mut turns = sqlx::query_as::<_, (String, String)>(
+        r#"
+        SELECT message, response FROM call_logs
+        WHERE rei_id = $1 AND session_id = $2
+        ORDER BY created_at DESC
+        LIMIT $3
+        "#,
+    )
+    .bind(rei_id)
+    .bind(session_id)
+    .bind(SESSION_HISTORY_LIMIT)
+    .fetch_all(pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    turns.reverse();
+    Ok(turns)
+}
+
 /// Build system prompt with Rei identity and memories using ToPrompt DTO
-fn build_system_prompt(rei: &Rei, memories: &[Memory]) -> String {
-    let dto = CallPromptDto::new(rei, memories);
+fn build_system_prompt(rei: &Rei, memories: &[Memory], expertise: Option<&str>) -> String {
+    let dto = CallPromptDto::new(rei, memories, expertise);
     dto.to_prompt()
 }
 
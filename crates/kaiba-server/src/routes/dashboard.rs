@@ -7,11 +7,13 @@ use axum::{
     routing::get,
     Json, Router,
 };
+use kaiba::ReiWebhookRepository;
 use uuid::Uuid;
 
+use crate::error::ApiError;
 use crate::models::{
     DashboardActivity, DashboardReiInfo, DashboardResponse, DashboardState, DashboardStats,
-    DashboardWebhooks,
+    DashboardWebhooks, WebhookStatsResponse,
 };
 use crate::AppState;
 
@@ -32,17 +34,14 @@ use crate::AppState;
 pub async fn get_dashboard(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
-) -> Result<Json<DashboardResponse>, (axum::http::StatusCode, String)> {
+) -> Result<Json<DashboardResponse>, ApiError> {
     // Get Rei and state
     let (rei, rei_state) = state
         .rei_service
         .get_by_id(id)
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
-        .ok_or((
-            axum::http::StatusCode::NOT_FOUND,
-            "Rei not found".to_string(),
-        ))?;
+        .map_err(ApiError::internal)?
+        .ok_or(ApiError::not_found("Rei not found"))?;
 
     // Get memory count from Qdrant
     let memory_count = match &state.memory_kai {
@@ -58,17 +57,17 @@ pub async fn get_dashboard(
         .unwrap_or(0);
 
     // Get webhook stats
-    let webhook_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM webhooks WHERE rei_id = $1")
-        .bind(id)
-        .fetch_one(&state.pool)
+    let webhooks = state
+        .webhook_repo
+        .find_by_rei(id)
         .await
-        .unwrap_or(0);
+        .map_err(ApiError::internal)?;
 
     let last_delivery: Option<chrono::DateTime<chrono::Utc>> = sqlx::query_scalar(
         r#"
         SELECT MAX(wd.created_at)
         FROM webhook_deliveries wd
-        JOIN webhooks w ON w.id = wd.webhook_id
+        JOIN rei_webhooks w ON w.id = wd.webhook_id
         WHERE w.rei_id = $1
         "#,
     )
@@ -78,20 +77,19 @@ pub async fn get_dashboard(
     .ok()
     .flatten();
 
-    let recent_failures: i64 = sqlx::query_scalar(
-        r#"
-        SELECT COUNT(*)
-        FROM webhook_deliveries wd
-        JOIN webhooks w ON w.id = wd.webhook_id
-        WHERE w.rei_id = $1
-          AND wd.success = false
-          AND wd.created_at > NOW() - INTERVAL '24 hours'
-        "#,
-    )
-    .bind(id)
-    .fetch_one(&state.pool)
-    .await
-    .unwrap_or(0);
+    let since = chrono::Utc::now() - chrono::Duration::hours(24);
+    let mut per_webhook = Vec::with_capacity(webhooks.len());
+    for webhook in &webhooks {
+        match state
+            .webhook_repo
+            .delivery_stats(webhook.id, Some(since))
+            .await
+        {
+            Ok(stats) => per_webhook.push(WebhookStatsResponse::from_domain(webhook, stats)),
+            Err(e) => tracing::warn!("⚠️  Failed to load stats for {}: {}", webhook.name, e),
+        }
+    }
+    let recent_failures = per_webhook.iter().map(|s| s.failed).sum();
 
     let response = DashboardResponse {
         rei: DashboardReiInfo {
@@ -106,6 +104,7 @@ pub async fn get_dashboard(
             tokens_used: rei_state.tokens_used,
             token_budget: rei_state.token_budget,
             energy_regen_per_hour: rei_state.energy_regen_per_hour,
+            autonomy_enabled: rei_state.autonomy_enabled,
         },
         activity: DashboardActivity {
             last_active_at: rei_state.last_active_at,
@@ -115,11 +114,13 @@ pub async fn get_dashboard(
         stats: DashboardStats {
             memory_count,
             tei_count,
+            event_counts: state.event_stats.counts_for(id),
         },
         webhooks: DashboardWebhooks {
-            webhook_count,
+            webhook_count: webhooks.len() as i64,
             last_delivery_at: last_delivery,
             recent_failures,
+            per_webhook,
         },
     };
 
@@ -0,0 +1,252 @@
+//! Decision Routes - How a Rei chooses its autonomous action
+//!
+//! GET/PUT/DELETE /kaiba/rei/:rei_id/decision-policy
+//! GET /kaiba/rei/:rei_id/decisions - Logged decisions with their reasons
+//!
+//! Without a policy a Rei uses the rule-based strategy with the default
+//! thresholds.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::get,
+    Json, Router,
+};
+use uuid::Uuid;
+
+use crate::error::ApiError;
+use crate::models::{
+    DecisionHistoryQuery, DecisionLog, DecisionPolicyResponse, ReiDecisionPolicy,
+    UpsertDecisionPolicyRequest,
+};
+use crate::pagination::{Page, PageQuery, Pagination, Sort};
+use crate::services::decision::DecisionConfig;
+use crate::validation::ValidJson;
+use crate::AppState;
+
+/// Get a Rei's decision policy
+#[utoipa::path(
+    get,
+    path = "/kaiba/rei/{rei_id}/decision-policy",
+    params(("rei_id" = Uuid, Path, description = "Rei ID")),
+    responses(
+        (status = 200, description = "Rei decision policy", body = DecisionPolicyResponse),
+        (status = 404, description = "Rei has no decision policy (defaults apply)"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Rei"
+)]
+pub async fn get_decision_policy(
+    State(state): State<AppState>,
+    Path(rei_id): Path<Uuid>,
+) -> Result<Json<DecisionPolicyResponse>, ApiError> {
+    let policy: ReiDecisionPolicy =
+        sqlx::query_as("SELECT * FROM rei_decision_policies WHERE rei_id = $1")
+            .bind(rei_id)
+            .fetch_optional(&state.pool)
+            .await
+            .map_err(ApiError::internal)?
+            .ok_or(ApiError::not_found("Rei has no decision policy"))?;
+
+    Ok(Json(policy.into()))
+}
+
+/// Set (create or replace) a Rei's decision policy
+#[utoipa::path(
+    put,
+    path = "/kaiba/rei/{rei_id}/decision-policy",
+    params(("rei_id" = Uuid, Path, description = "Rei ID")),
+    request_body = UpsertDecisionPolicyRequest,
+    responses(
+        (status = 200, description = "Decision policy saved", body = DecisionPolicyResponse),
+        (status = 404, description = "Rei not found"),
+        (status = 422, description = "Invalid thresholds"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Rei"
+)]
+pub async fn put_decision_policy(
+    State(state): State<AppState>,
+    Path(rei_id): Path<Uuid>,
+    ValidJson(payload): ValidJson<UpsertDecisionPolicyRequest>,
+) -> Result<Json<DecisionPolicyResponse>, ApiError> {
+    state
+        .rei_service
+        .get_by_id(rei_id)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or(ApiError::not_found("Rei not found"))?;
+
+    let defaults = DecisionConfig::default();
+    let saved: ReiDecisionPolicy = sqlx::query_as(
+        r#"
+        INSERT INTO rei_decision_policies
+            (rei_id, strategy, min_energy_learn, min_energy_digest, min_tokens_action,
+             memories_for_digest, min_energy_reflect, reflection_interval_hours)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        ON CONFLICT (rei_id) DO UPDATE
+        SET strategy = EXCLUDED.strategy,
+            min_energy_learn = EXCLUDED.min_energy_learn,
+            min_energy_digest = EXCLUDED.min_energy_digest,
+            min_tokens_action = EXCLUDED.min_tokens_action,
+            memories_for_digest = EXCLUDED.memories_for_digest,
+            min_energy_reflect = EXCLUDED.min_energy_reflect,
+            reflection_interval_hours = EXCLUDED.reflection_interval_hours
+        RETURNING *
+        "#,
+    )
+    .bind(rei_id)
+    .bind(payload.strategy.unwrap_or_default().to_string())
+    .bind(
+        payload
+            .min_energy_learn
+            .unwrap_or(defaults.min_energy_learn),
+    )
+    .bind(
+        payload
+            .min_energy_digest
+            .unwrap_or(defaults.min_energy_digest),
+    )
+    .bind(
+        payload
+            .min_tokens_action
+            .unwrap_or(defaults.min_tokens_action),
+    )
+    .bind(
+        payload
+            .memories_for_digest
+            .unwrap_or(defaults.memories_for_digest as i32),
+    )
+    .bind(
+        payload
+            .min_energy_reflect
+            .unwrap_or(defaults.min_energy_reflect),
+    )
+    .bind(
+        payload
+            .reflection_interval_hours
+            .unwrap_or(defaults.reflection_interval_hours as i32),
+    )
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    tracing::info!(
+        "🧭 Decision policy saved for Rei {}: {}",
+        rei_id,
+        saved.strategy
+    );
+
+    Ok(Json(saved.into()))
+}
+
+/// Remove a Rei's decision policy (it returns to the defaults)
+#[utoipa::path(
+    delete,
+    path = "/kaiba/rei/{rei_id}/decision-policy",
+    params(("rei_id" = Uuid, Path, description = "Rei ID")),
+    responses(
+        (status = 204, description = "Decision policy removed"),
+        (status = 404, description = "Rei has no decision policy"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Rei"
+)]
+pub async fn delete_decision_policy(
+    State(state): State<AppState>,
+    Path(rei_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let result = sqlx::query("DELETE FROM rei_decision_policies WHERE rei_id = $1")
+        .bind(rei_id)
+        .execute(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::not_found("Rei has no decision policy"));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Past autonomous decisions of a Rei
+#[utoipa::path(
+    get,
+    path = "/kaiba/rei/{rei_id}/decisions",
+    params(
+        ("rei_id" = Uuid, Path, description = "Rei ID"),
+        DecisionHistoryQuery,
+        PageQuery
+    ),
+    responses(
+        (status = 200, description = "Page of decisions (newest first)", body = Vec<DecisionLog>,
+            headers(("x-total-count" = i64, description = "Decisions matching the filters"))),
+        (status = 400, description = "Unknown action"),
+        (status = 422, description = "Invalid paging or sort parameters"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Rei"
+)]
+pub async fn get_decisions(
+    State(state): State<AppState>,
+    Path(rei_id): Path<Uuid>,
+    pagination: Pagination,
+    Query(query): Query<DecisionHistoryQuery>,
+) -> Result<Page<DecisionLog>, ApiError> {
+    let sort = pagination.sort_or(&["created_at"], Sort::desc("created_at"))?;
+    // Only whitelisted columns reach the query
+    let order_by = if sort.descending {
+        "created_at DESC"
+    } else {
+        "created_at ASC"
+    };
+
+    if let Some(action) = query.action.as_deref() {
+        if !["learn", "digest", "reflect", "rest"].contains(&action) {
+            return Err(ApiError::bad_request(format!(
+                "Unknown action: {} (expected learn, digest, reflect or rest)",
+                action
+            )));
+        }
+    }
+
+    let filter = r#"
+        WHERE rei_id = $1
+          AND ($2::timestamptz IS NULL OR created_at > $2)
+          AND ($3::text IS NULL OR action = $3)
+    "#;
+
+    let total: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM rei_decisions {}", filter))
+        .bind(rei_id)
+        .bind(query.since)
+        .bind(&query.action)
+        .fetch_one(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+
+    let items = sqlx::query_as::<_, DecisionLog>(&format!(
+        "SELECT * FROM rei_decisions {} ORDER BY {} LIMIT $4 OFFSET $5",
+        filter, order_by
+    ))
+    .bind(rei_id)
+    .bind(query.since)
+    .bind(&query.action)
+    .bind(pagination.limit)
+    .bind(pagination.offset)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    Ok(Page { items, total })
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/kaiba/rei/:rei_id/decision-policy",
+            get(get_decision_policy)
+                .put(put_decision_policy)
+                .delete(delete_decision_policy),
+        )
+        .route("/kaiba/rei/:rei_id/decisions", get(get_decisions))
+}
@@ -0,0 +1,138 @@
+//! Discord Routes - Slash command interactions
+//!
+//! POST /kaiba/integrations/discord/interactions - Interaction webhook (public, verified by signature)
+//!
+//! Set this URL as the application's Interactions Endpoint URL. `/kaiba`
+//! commands are answered for the Rei bound to the channel
+//! (`discord_channel_id` in its manifest). Discord wants an answer within
+//! three seconds, so commands are acknowledged with a deferred response and
+//! the reply replaces it once the Rei has answered.
+
+use axum::{body::Bytes, extract::State, http::HeaderMap, routing::post, Json, Router};
+use kaiba::{IntegrationEvent, IntegrationEventHandler};
+use kaiba_integration_discord::{DiscordClient, DiscordInteraction, DiscordWebhookHandler};
+use uuid::Uuid;
+
+use crate::auth;
+use crate::error::ApiError;
+use crate::services::integration_call::IntegrationCallHandler;
+use crate::AppState;
+
+/// Receive a Discord interaction
+#[utoipa::path(
+    post,
+    path = "/kaiba/integrations/discord/interactions",
+    request_body = serde_json::Value,
+    responses(
+        (status = 200, description = "Interaction response (pong, deferred, or message)", body = serde_json::Value),
+        (status = 400, description = "Invalid interaction"),
+        (status = 401, description = "Missing or invalid signature"),
+        (status = 503, description = "Discord interactions not configured"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Inbound"
+)]
+pub async fn receive_interaction(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let (Some(discord), Some(public_key)) = (
+        state.config.discord(),
+        state.config.discord_public_key.clone(),
+    ) else {
+        return Err(ApiError::unavailable("Discord interactions not configured"));
+    };
+    // The key is validated at startup
+    let handler =
+        DiscordWebhookHandler::with_public_key(&public_key).map_err(ApiError::internal)?;
+
+    auth::verify_webhook("Discord", &handler, &headers, &body)?;
+
+    let (event, token) = match handler
+        .parse_interaction(&body)
+        .map_err(|e| ApiError::bad_request(e.to_string()))?
+    {
+        DiscordInteraction::Ping => return Ok(Json(DiscordWebhookHandler::pong())),
+        DiscordInteraction::Ignored => {
+            return Ok(Json(DiscordWebhookHandler::message_response(
+                "Nothing to do here.",
+                true,
+            )))
+        }
+        DiscordInteraction::Command { event, token } => (event, token),
+    };
+
+    let channel_id = event.channel_id().unwrap_or_default().to_string();
+    let Some(rei) = bound_rei(&state, &channel_id).await? else {
+        return Ok(Json(DiscordWebhookHandler::message_response(
+            "No Rei is bound to this channel (set `discord_channel_id` in its manifest).",
+            true,
+        )));
+    };
+
+    let command = match &event {
+        IntegrationEvent::SlashCommand { command, .. } => command.clone(),
+        _ => String::new(),
+    };
+    tracing::info!("💬 Discord /kaiba {} for Rei {}", command, rei.id);
+
+    // Answer in the background; the deferred response shows "thinking..."
+    let calls = IntegrationCallHandler::new(state.clone(), "discord");
+    if !calls.is_enabled(&rei).await.map_err(ApiError::internal)? {
+        return Ok(Json(DiscordWebhookHandler::message_response(
+            &format!("Discord is switched off for {}.", rei.name),
+            true,
+        )));
+    }
+
+    let client = DiscordClient::new(discord);
+    state.tasks.spawn(async move {
+        let reply = match calls.handle(&rei, event).await {
+            Ok(Some(reply)) => reply,
+            Ok(None) => "Nothing to say.".to_string(),
+            Err(e) => {
+                tracing::warn!("⚠️  Discord /kaiba {} failed: {}", command, e);
+                format!("⚠️ {}", e)
+            }
+        };
+        if let Err(e) = client.edit_interaction_response(&token, &reply).await {
+            tracing::warn!("⚠️  Failed to deliver Discord reply: {}", e);
+        }
+    });
+
+    Ok(Json(DiscordWebhookHandler::deferred_response()))
+}
+
+/// The Rei whose manifest names the channel
+async fn bound_rei(state: &AppState, channel_id: &str) -> Result<Option<kaiba::Rei>, ApiError> {
+    let rei_id: Option<Uuid> = sqlx::query_scalar(
+        r#"
+        SELECT id FROM reis
+        WHERE deleted_at IS NULL AND manifest->>'discord_channel_id' = $1
+        ORDER BY created_at
+        LIMIT 1
+        "#,
+    )
+    .bind(channel_id)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let Some(rei_id) = rei_id else {
+        return Ok(None);
+    };
+    Ok(state
+        .rei_service
+        .get_by_id(rei_id)
+        .await
+        .map_err(ApiError::internal)?
+        .map(|(rei, _)| rei))
+}
+
+pub fn public_router() -> Router<AppState> {
+    Router::new().route(
+        "/kaiba/integrations/discord/interactions",
+        post(receive_interaction),
+    )
+}
@@ -0,0 +1,282 @@
+//! Event Registry Routes - Declared custom webhook events
+//!
+//! Custom events are registered here with a description and a data schema.
+//! Webhooks can then subscribe to `custom:<name>`, and clients emit the event
+//! for a Rei through `/kaiba/rei/:rei_id/events/:name`.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Response,
+    routing::{get, post},
+    Json, Router,
+};
+use uuid::Uuid;
+
+use kaiba::{EventDefinition, EventSchema, ReiEvent, ReiWebhookRepository};
+
+use crate::error::ApiError;
+use crate::models::{
+    CreateEventDefinitionRequest, EmitEventRequest, EmitEventResponse, EventDefinitionResponse,
+    UpdateEventDefinitionRequest,
+};
+use crate::rest;
+use crate::AppState;
+
+/// List registered custom events
+#[utoipa::path(
+    get,
+    path = "/kaiba/events",
+    responses(
+        (status = 200, description = "Registered custom events", body = Vec<EventDefinitionResponse>),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Webhook"
+)]
+pub async fn list_event_definitions(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<EventDefinitionResponse>>, ApiError> {
+    let definitions = state
+        .webhook_repo
+        .find_event_definitions()
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(
+        definitions
+            .into_iter()
+            .map(EventDefinitionResponse::from_domain)
+            .collect(),
+    ))
+}
+
+/// Register a custom event
+#[utoipa::path(
+    post,
+    path = "/kaiba/events",
+    request_body = CreateEventDefinitionRequest,
+    responses(
+        (status = 201, description = "Event registered", body = EventDefinitionResponse,
+            headers(("location" = String, description = "URL of the new event"))),
+        (status = 400, description = "Invalid name or schema"),
+        (status = 409, description = "Event already registered"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Webhook"
+)]
+pub async fn create_event_definition(
+    State(state): State<AppState>,
+    Json(payload): Json<CreateEventDefinitionRequest>,
+) -> Result<Response, ApiError> {
+    EventDefinition::validate_name(&payload.name).map_err(ApiError::bad_request)?;
+
+    let existing = state
+        .webhook_repo
+        .find_event_definition(&payload.name)
+        .await
+        .map_err(ApiError::internal)?;
+    if existing.is_some() {
+        return Err(ApiError::conflict(format!(
+            "Event '{}' is already registered",
+            payload.name
+        )));
+    }
+
+    let mut definition = EventDefinition::new(payload.name);
+    if let Some(description) = payload.description {
+        definition = definition.with_description(description);
+    }
+    if let Some(schema) = payload.schema {
+        let schema = EventSchema::parse(schema).map_err(ApiError::bad_request)?;
+        definition = definition.with_schema(schema);
+    }
+
+    let saved = state
+        .webhook_repo
+        .save_event_definition(&definition)
+        .await
+        .map_err(ApiError::internal)?;
+
+    tracing::info!("📣 Custom event registered: {}", saved.name);
+
+    Ok(rest::created(
+        format!("/kaiba/events/{}", saved.name),
+        EventDefinitionResponse::from_domain(saved),
+    ))
+}
+
+/// Get a custom event
+#[utoipa::path(
+    get,
+    path = "/kaiba/events/{name}",
+    params(
+        ("name" = String, Path, description = "Event name")
+    ),
+    responses(
+        (status = 200, description = "Event found", body = EventDefinitionResponse),
+        (status = 404, description = "Event not registered"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Webhook"
+)]
+pub async fn get_event_definition(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<Json<EventDefinitionResponse>, ApiError> {
+    let definition = find_definition(&state, &name).await?;
+
+    Ok(Json(EventDefinitionResponse::from_domain(definition)))
+}
+
+/// Update a custom event's description or schema
+#[utoipa::path(
+    put,
+    path = "/kaiba/events/{name}",
+    params(
+        ("name" = String, Path, description = "Event name")
+    ),
+    request_body = UpdateEventDefinitionRequest,
+    responses(
+        (status = 200, description = "Event updated", body = EventDefinitionResponse),
+        (status = 400, description = "Invalid schema"),
+        (status = 404, description = "Event not registered"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Webhook"
+)]
+pub async fn update_event_definition(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    Json(payload): Json<UpdateEventDefinitionRequest>,
+) -> Result<Json<EventDefinitionResponse>, ApiError> {
+    let mut definition = find_definition(&state, &name).await?;
+
+    if let Some(description) = payload.description {
+        definition.description = Some(description);
+    }
+    if let Some(schema) = payload.schema {
+        definition.schema = EventSchema::parse(schema).map_err(ApiError::bad_request)?;
+    }
+
+    let saved = state
+        .webhook_repo
+        .save_event_definition(&definition)
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(EventDefinitionResponse::from_domain(saved)))
+}
+
+/// Unregister a custom event
+///
+/// Existing subscriptions are kept, but the event can no longer be emitted.
+#[utoipa::path(
+    delete,
+    path = "/kaiba/events/{name}",
+    params(
+        ("name" = String, Path, description = "Event name")
+    ),
+    responses(
+        (status = 204, description = "Event deleted"),
+        (status = 404, description = "Event not registered"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Webhook"
+)]
+pub async fn delete_event_definition(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let deleted = state
+        .webhook_repo
+        .delete_event_definition(&name)
+        .await
+        .map_err(ApiError::internal)?;
+
+    if !deleted {
+        return Err(ApiError::not_found(format!(
+            "Event '{}' is not registered",
+            name
+        )));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Emit a custom event for a Rei
+///
+/// The data is validated against the event's schema, then published to
+/// subscribed webhooks like any built-in event.
+#[utoipa::path(
+    post,
+    path = "/kaiba/rei/{rei_id}/events/{name}",
+    params(
+        ("rei_id" = Uuid, Path, description = "Rei ID"),
+        ("name" = String, Path, description = "Event name")
+    ),
+    request_body = EmitEventRequest,
+    responses(
+        (status = 200, description = "Event emitted", body = EmitEventResponse),
+        (status = 400, description = "Data does not match the event schema"),
+        (status = 404, description = "Rei or event not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Webhook"
+)]
+pub async fn emit_event(
+    State(state): State<AppState>,
+    Path((rei_id, name)): Path<(Uuid, String)>,
+    Json(payload): Json<EmitEventRequest>,
+) -> Result<Json<EmitEventResponse>, ApiError> {
+    state
+        .rei_service
+        .get_by_id(rei_id)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or(ApiError::not_found("Rei not found"))?;
+
+    let definition = find_definition(&state, &name).await?;
+    let data = payload.data.unwrap_or_else(|| serde_json::json!({}));
+    definition
+        .schema
+        .validate(&data)
+        .map_err(ApiError::bad_request)?;
+
+    let event = ReiEvent::new(rei_id, definition.event_type(), data);
+    let response = EmitEventResponse {
+        event_id: event.id,
+        rei_id,
+        event: event.event_type.to_string(),
+    };
+    state.event_bus.emit(event).await;
+
+    Ok(Json(response))
+}
+
+/// Load a custom event definition or 404
+async fn find_definition(state: &AppState, name: &str) -> Result<EventDefinition, ApiError> {
+    state
+        .webhook_repo
+        .find_event_definition(name)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or(ApiError::not_found(format!(
+            "Event '{}' is not registered",
+            name
+        )))
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/kaiba/events",
+            get(list_event_definitions).post(create_event_definition),
+        )
+        .route(
+            "/kaiba/events/:name",
+            get(get_event_definition)
+                .put(update_event_definition)
+                .delete(delete_event_definition),
+        )
+        .route("/kaiba/rei/:rei_id/events/:name", post(emit_event))
+}
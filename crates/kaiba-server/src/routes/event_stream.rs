@@ -0,0 +1,75 @@
+//! Event Stream Routes - Live Rei events over Server-Sent Events
+//!
+//! `GET /kaiba/rei/:rei_id/events` keeps the connection open and sends each
+//! event as it happens: SSE `event:` is the event type and `data:` the JSON
+//! event. Only events from after the client connected are sent.
+
+use axum::{
+    extract::{Path, Query, State},
+    response::sse::{KeepAlive, Sse},
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::error::ApiError;
+use crate::services::event_stream::KEEP_ALIVE;
+use crate::AppState;
+
+/// Query parameters for the event stream
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct EventStreamQuery {
+    /// Comma-separated event types to send (e.g. `memory_added,webhook_delivery`; default all)
+    pub types: Option<String>,
+}
+
+/// Stream a Rei's events as they happen (Server-Sent Events)
+///
+/// Event types: `response_completed`, `state_changed`, `memory_added`,
+/// `search_completed`, `learning_completed`, `digest_completed`,
+/// `task_completed`, `custom:<name>` and `webhook_delivery`. A `lagged` event carries the
+/// number of events a slow client missed.
+#[utoipa::path(
+    get,
+    path = "/kaiba/rei/{rei_id}/events",
+    params(
+        ("rei_id" = Uuid, Path, description = "Rei ID"),
+        EventStreamQuery
+    ),
+    responses(
+        (status = 200, description = "Event stream", content_type = "text/event-stream", body = String),
+        (status = 404, description = "Rei not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Rei"
+)]
+pub async fn stream_events(
+    State(state): State<AppState>,
+    Path(rei_id): Path<Uuid>,
+    Query(query): Query<EventStreamQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    state
+        .rei_service
+        .get_by_id(rei_id)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or_else(|| ApiError::not_found("Rei not found"))?;
+
+    let types = query.types.map(|types| {
+        types
+            .split(',')
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect()
+    });
+
+    let events = state.event_stream.subscribe(rei_id, types);
+    Ok(Sse::new(events).keep_alive(KeepAlive::new().interval(KEEP_ALIVE)))
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new().route("/kaiba/rei/:rei_id/events", get(stream_events))
+}
@@ -0,0 +1,67 @@
+//! GitHub Routes - App webhook deliveries
+//!
+//! POST /kaiba/integrations/github/webhook - Webhook delivery (public, verified by signature)
+//!
+//! Set this URL as the GitHub App's webhook URL, with `GITHUB_WEBHOOK_SECRET`
+//! as its secret, and subscribe to Issues, Issue comments, Pull requests and
+//! Pull request review comments. Deliveries are acknowledged at once and
+//! answered in the background, so GitHub's 10 second timeout is never hit.
+
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    routing::post,
+    Router,
+};
+
+use crate::auth;
+use crate::error::ApiError;
+use crate::services::integration_call::IntegrationCallHandler;
+use crate::AppState;
+
+/// Receive a GitHub App webhook delivery
+#[utoipa::path(
+    post,
+    path = "/kaiba/integrations/github/webhook",
+    request_body = serde_json::Value,
+    responses(
+        (status = 200, description = "Delivery accepted"),
+        (status = 400, description = "Invalid delivery"),
+        (status = 401, description = "Missing or invalid signature"),
+        (status = 503, description = "GitHub App not configured"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Inbound"
+)]
+pub async fn receive_delivery(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<StatusCode, ApiError> {
+    let Some(bot) = state.github.clone() else {
+        return Err(ApiError::unavailable("GitHub App not configured"));
+    };
+    let handler = bot.webhook_handler();
+
+    auth::verify_webhook("GitHub", &handler, &headers, &body)?;
+
+    // Pings and events Kaiba doesn't answer are acknowledged all the same
+    let Some(event) = handler
+        .parse_payload(&body)
+        .map_err(|e| ApiError::bad_request(e.to_string()))?
+    else {
+        return Ok(StatusCode::OK);
+    };
+
+    let calls = IntegrationCallHandler::new(state.clone(), "github");
+    state
+        .tasks
+        .spawn(async move { bot.handle_event(event, &calls).await });
+
+    Ok(StatusCode::OK)
+}
+
+pub fn public_router() -> Router<AppState> {
+    Router::new().route("/kaiba/integrations/github/webhook", post(receive_delivery))
+}
@@ -0,0 +1,201 @@
+//! Global Webhook Routes - Administrator-level webhooks
+//!
+//! Webhooks managed here are not scoped to a Rei: they receive matching
+//! events from every Rei (e.g., ops alerting, daily digests). A webhook
+//! created with a user-bound key belongs to that user and only receives
+//! events from their Reis.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Response,
+    routing::get,
+    Extension, Json, Router,
+};
+use uuid::Uuid;
+
+use kaiba::{ReiWebhook, ReiWebhookRepository};
+
+use crate::auth::Principal;
+use crate::error::ApiError;
+use crate::models::{CreateWebhookRequest, UpdateWebhookRequest, WebhookResponse};
+use crate::rest;
+use crate::routes::webhook::{apply_update, build_webhook};
+use crate::services::ownership::{self, InsertOwned, Owned};
+use crate::validation::ValidJson;
+use crate::AppState;
+
+/// List global webhooks
+#[utoipa::path(
+    get,
+    path = "/kaiba/webhooks",
+    responses(
+        (status = 200, description = "List of global webhooks", body = Vec<WebhookResponse>),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Webhook"
+)]
+pub async fn list_global_webhooks(
+    State(state): State<AppState>,
+    principal: Option<Extension<Principal>>,
+) -> Result<Json<Vec<WebhookResponse>>, ApiError> {
+    let visible = ownership::visible(
+        &state.pool,
+        Owned::Webhook,
+        principal.and_then(|Extension(p)| p.owner()),
+    )
+    .await
+    .map_err(ApiError::internal)?;
+
+    let webhooks = state
+        .webhook_repo
+        .find_global()
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(
+        webhooks
+            .into_iter()
+            .filter(|w| visible.contains(w.id))
+            .map(WebhookResponse::from_domain)
+            .collect(),
+    ))
+}
+
+/// Create a global webhook
+#[utoipa::path(
+    post,
+    path = "/kaiba/webhooks",
+    request_body = CreateWebhookRequest,
+    responses(
+        (status = 201, description = "Webhook created", body = WebhookResponse,
+            headers(("location" = String, description = "URL of the new webhook"))),
+        (status = 400, description = "Invalid payload template, condition or event"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Webhook"
+)]
+pub async fn create_global_webhook(
+    State(state): State<AppState>,
+    principal: Option<Extension<Principal>>,
+    ValidJson(payload): ValidJson<CreateWebhookRequest>,
+) -> Result<Response, ApiError> {
+    let webhook = build_webhook(&state, None, payload).await?;
+
+    let saved = state
+        .webhook_repo
+        .insert_owned(&webhook, principal.and_then(|Extension(p)| p.owner()))
+        .await
+        .map_err(ApiError::internal)?;
+
+    tracing::info!("🌐 Global webhook created: {}", saved.name);
+
+    Ok(rest::created(
+        format!("/kaiba/webhooks/{}", saved.id),
+        WebhookResponse::from_domain(saved),
+    ))
+}
+
+/// Get a global webhook by ID
+#[utoipa::path(
+    get,
+    path = "/kaiba/webhooks/{webhook_id}",
+    params(
+        ("webhook_id" = Uuid, Path, description = "Webhook ID")
+    ),
+    responses(
+        (status = 200, description = "Webhook found", body = WebhookResponse),
+        (status = 404, description = "Webhook not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Webhook"
+)]
+pub async fn get_global_webhook(
+    State(state): State<AppState>,
+    Path(webhook_id): Path<Uuid>,
+) -> Result<Json<WebhookResponse>, ApiError> {
+    let webhook = find_global_webhook(&state, webhook_id).await?;
+
+    Ok(Json(WebhookResponse::from_domain(webhook)))
+}
+
+/// Update a global webhook
+#[utoipa::path(
+    put,
+    path = "/kaiba/webhooks/{webhook_id}",
+    params(
+        ("webhook_id" = Uuid, Path, description = "Webhook ID")
+    ),
+    request_body = UpdateWebhookRequest,
+    responses(
+        (status = 200, description = "Webhook updated", body = WebhookResponse),
+        (status = 400, description = "Invalid payload template, condition or event"),
+        (status = 404, description = "Webhook not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Webhook"
+)]
+pub async fn update_global_webhook(
+    State(state): State<AppState>,
+    Path(webhook_id): Path<Uuid>,
+    ValidJson(payload): ValidJson<UpdateWebhookRequest>,
+) -> Result<Json<WebhookResponse>, ApiError> {
+    let webhook = find_global_webhook(&state, webhook_id).await?;
+    let saved = apply_update(&state, webhook, payload).await?;
+
+    Ok(Json(WebhookResponse::from_domain(saved)))
+}
+
+/// Delete a global webhook
+#[utoipa::path(
+    delete,
+    path = "/kaiba/webhooks/{webhook_id}",
+    params(
+        ("webhook_id" = Uuid, Path, description = "Webhook ID")
+    ),
+    responses(
+        (status = 204, description = "Webhook deleted"),
+        (status = 404, description = "Webhook not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Webhook"
+)]
+pub async fn delete_global_webhook(
+    State(state): State<AppState>,
+    Path(webhook_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    find_global_webhook(&state, webhook_id).await?;
+
+    state
+        .webhook_repo
+        .delete(webhook_id)
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Load a webhook, treating Rei-scoped webhooks as not found
+async fn find_global_webhook(state: &AppState, webhook_id: Uuid) -> Result<ReiWebhook, ApiError> {
+    state
+        .webhook_repo
+        .find_by_id(webhook_id)
+        .await
+        .map_err(ApiError::internal)?
+        .filter(ReiWebhook::is_global)
+        .ok_or(ApiError::not_found("Webhook not found"))
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/kaiba/webhooks",
+            get(list_global_webhooks).post(create_global_webhook),
+        )
+        .route(
+            "/kaiba/webhooks/:webhook_id",
+            get(get_global_webhook)
+                .put(update_global_webhook)
+                .delete(delete_global_webhook),
+        )
+}
@@ -0,0 +1,349 @@
+//! Inbound Routes - Let external services poke a Rei
+//!
+//! POST /kaiba/rei/:rei_id/inbound/:source - Receive a payload (public, verified by secret)
+//! GET/POST /kaiba/rei/:rei_id/inbound-sources - Manage inbound sources
+//! DELETE /kaiba/rei/:rei_id/inbound-sources/:source - Remove an inbound source
+//!
+//! Payloads are authenticated with the source's secret, either as an HMAC
+//! signature (`X-Hub-Signature-256` / `X-Kaiba-Signature`) or as a shared
+//! token (`X-Kaiba-Token`) for services that cannot sign.
+
+use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    http::HeaderMap,
+    routing::{delete, get, post},
+    Json, Router,
+};
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::auth;
+use crate::error::ApiError;
+use crate::models::{
+    InboundAction, InboundResponse, InboundSource, InboundSourceResponse, Memory, MemoryType,
+    UpsertInboundSourceRequest,
+};
+use crate::services::digest::DigestService;
+use crate::services::inbound::{self, InboundItem};
+use crate::services::learning_source;
+use crate::services::self_learning::SelfLearningService;
+use crate::AppState;
+
+/// List inbound sources for a Rei
+#[utoipa::path(
+    get,
+    path = "/kaiba/rei/{rei_id}/inbound-sources",
+    params(("rei_id" = Uuid, Path, description = "Rei ID")),
+    responses(
+        (status = 200, description = "List of inbound sources", body = Vec<InboundSourceResponse>),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Inbound"
+)]
+pub async fn list_sources(
+    State(state): State<AppState>,
+    Path(rei_id): Path<Uuid>,
+) -> Result<Json<Vec<InboundSourceResponse>>, ApiError> {
+    let sources: Vec<InboundSource> =
+        sqlx::query_as("SELECT * FROM rei_inbound_sources WHERE rei_id = $1 ORDER BY source")
+            .bind(rei_id)
+            .fetch_all(&state.pool)
+            .await
+            .map_err(ApiError::internal)?;
+
+    Ok(Json(sources.into_iter().map(Into::into).collect()))
+}
+
+/// Create or replace an inbound source
+#[utoipa::path(
+    post,
+    path = "/kaiba/rei/{rei_id}/inbound-sources",
+    params(("rei_id" = Uuid, Path, description = "Rei ID")),
+    request_body = UpsertInboundSourceRequest,
+    responses(
+        (status = 200, description = "Inbound source saved", body = InboundSourceResponse),
+        (status = 400, description = "Invalid source or secret"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Inbound"
+)]
+pub async fn upsert_source(
+    State(state): State<AppState>,
+    Path(rei_id): Path<Uuid>,
+    Json(payload): Json<UpsertInboundSourceRequest>,
+) -> Result<Json<InboundSourceResponse>, ApiError> {
+    let source = payload.source.trim().to_lowercase();
+    if source.is_empty() || payload.secret.is_empty() {
+        return Err(ApiError::bad_request("source and secret are required"));
+    }
+
+    let memory_type = payload.memory_type.unwrap_or(MemoryType::Fact);
+
+    let saved: InboundSource = sqlx::query_as(
+        r#"
+        INSERT INTO rei_inbound_sources (rei_id, source, secret, action, memory_type, enabled)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        ON CONFLICT (rei_id, source) DO UPDATE
+        SET secret = EXCLUDED.secret,
+            action = EXCLUDED.action,
+            memory_type = EXCLUDED.memory_type,
+            enabled = EXCLUDED.enabled
+        RETURNING *
+        "#,
+    )
+    .bind(rei_id)
+    .bind(&source)
+    .bind(&payload.secret)
+    .bind(payload.action.to_string())
+    .bind(memory_type.to_string())
+    .bind(payload.enabled.unwrap_or(true))
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    tracing::info!(
+        "📥 Inbound source saved: {} ({})",
+        saved.source,
+        saved.action
+    );
+
+    Ok(Json(saved.into()))
+}
+
+/// Delete an inbound source
+#[utoipa::path(
+    delete,
+    path = "/kaiba/rei/{rei_id}/inbound-sources/{source}",
+    params(
+        ("rei_id" = Uuid, Path, description = "Rei ID"),
+        ("source" = String, Path, description = "Source name")
+    ),
+    responses(
+        (status = 200, description = "Inbound source deleted"),
+        (status = 404, description = "Inbound source not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Inbound"
+)]
+pub async fn delete_source(
+    State(state): State<AppState>,
+    Path((rei_id, source)): Path<(Uuid, String)>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let source = source.to_lowercase();
+    let result = sqlx::query("DELETE FROM rei_inbound_sources WHERE rei_id = $1 AND source = $2")
+        .bind(rei_id)
+        .bind(&source)
+        .execute(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::not_found(format!(
+            "Inbound source '{}' not found",
+            source
+        )));
+    }
+
+    Ok(Json(serde_json::json!({ "deleted": true })))
+}
+
+/// Receive a payload from an external service
+#[utoipa::path(
+    post,
+    path = "/kaiba/rei/{rei_id}/inbound/{source}",
+    params(
+        ("rei_id" = Uuid, Path, description = "Rei ID"),
+        ("source" = String, Path, description = "Source name (github, rss, or generic)")
+    ),
+    request_body = serde_json::Value,
+    responses(
+        (status = 200, description = "Payload accepted", body = InboundResponse),
+        (status = 400, description = "Invalid JSON payload"),
+        (status = 401, description = "Missing or invalid signature"),
+        (status = 404, description = "Inbound source not configured"),
+        (status = 503, description = "Required services unavailable"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Inbound"
+)]
+pub async fn receive_inbound(
+    State(state): State<AppState>,
+    Path((rei_id, source)): Path<(Uuid, String)>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<InboundResponse>, ApiError> {
+    let source = source.to_lowercase();
+    let config: InboundSource = sqlx::query_as(
+        "SELECT * FROM rei_inbound_sources WHERE rei_id = $1 AND source = $2 AND enabled = true",
+    )
+    .bind(rei_id)
+    .bind(&source)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?
+    .ok_or(ApiError::not_found(format!(
+        "Inbound source '{}' not configured",
+        source
+    )))?;
+
+    auth::verify_webhook(
+        &source,
+        &inbound::SourceVerifier::new(&config.secret),
+        &headers,
+        &body,
+    )?;
+
+    let action: InboundAction = config.action.parse().map_err(ApiError::internal)?;
+
+    let mut response = InboundResponse {
+        source: source.clone(),
+        action: action.to_string(),
+        memories_created: 0,
+        triggered: false,
+    };
+
+    match action {
+        InboundAction::Memory => {
+            let body: serde_json::Value = serde_json::from_slice(&body)
+                .map_err(|e| ApiError::bad_request(format!("Invalid JSON: {}", e)))?;
+            let event = headers.get("x-github-event").and_then(|v| v.to_str().ok());
+            let memory_type = config.memory_type.parse().unwrap_or(MemoryType::Fact);
+
+            let items = inbound::parse_payload(&source, event, &body);
+            response.memories_created = store_items(&state, rei_id, items, memory_type).await?;
+        }
+        InboundAction::Learn | InboundAction::Digest => {
+            spawn_run(&state, rei_id, action)?;
+            response.triggered = true;
+        }
+    }
+
+    tracing::info!(
+        "📥 Inbound {} for {}: {} memories, triggered={}",
+        source,
+        rei_id,
+        response.memories_created,
+        response.triggered
+    );
+
+    Ok(Json(response))
+}
+
+/// Embed and store parsed items as memories
+async fn store_items(
+    state: &AppState,
+    rei_id: Uuid,
+    items: Vec<InboundItem>,
+    memory_type: MemoryType,
+) -> Result<usize, ApiError> {
+    let memory_kai = state
+        .memory_kai
+        .as_ref()
+        .ok_or(ApiError::unavailable("MemoryKai not available"))?;
+
+    let embedding_service = state
+        .embedding
+        .as_ref()
+        .ok_or(ApiError::unavailable("Embedding service not available"))?;
+
+    let mut stored = 0;
+    for item in items {
+        let memory = Memory {
+            id: Uuid::new_v4().to_string(),
+            rei_id: rei_id.to_string(),
+            content: item.content,
+            memory_type: memory_type.clone(),
+            importance: item.importance.unwrap_or(0.5),
+            tags: item.tags,
+            metadata: (!item.metadata.is_null()).then_some(item.metadata),
+            created_at: Utc::now(),
+        };
+
+        let embedding = match embedding_service.embed(&memory.content).await {
+            Ok(e) => e,
+            Err(e) => {
+                tracing::warn!("  ⚠️  Failed to embed inbound item: {}", e);
+                continue;
+            }
+        };
+
+        if let Err(e) = memory_kai
+            .add_memory(&rei_id.to_string(), memory.clone(), embedding)
+            .await
+        {
+            tracing::warn!("  ⚠️  Failed to store inbound item: {}", e);
+            continue;
+        }
+
+        state.event_bus.emit(memory.added_event(rei_id)).await;
+        stored += 1;
+    }
+
+    Ok(stored)
+}
+
+/// Start a learn or digest run in the background
+fn spawn_run(state: &AppState, rei_id: Uuid, action: InboundAction) -> Result<(), ApiError> {
+    let (Some(memory_kai), Some(embedding)) = (&state.memory_kai, &state.embedding) else {
+        return Err(ApiError::unavailable("Required services not available"));
+    };
+
+    if action == InboundAction::Digest {
+        let service = DigestService::new(
+            state.pool.clone(),
+            memory_kai.clone(),
+            embedding.clone(),
+            state.gemini_api_key.clone(),
+        )
+        .with_event_bus(Some(state.event_bus.clone()))
+        .with_threshold(state.config.digest_threshold());
+
+        state.tasks.spawn(async move {
+            if let Err(e) = service.digest(rei_id).await {
+                tracing::warn!("⚠️  Inbound digest failed for {}: {}", rei_id, e);
+            }
+        });
+        return Ok(());
+    }
+
+    // Without web search, learning studies existing memories
+    let service = SelfLearningService::new(
+        state.pool.clone(),
+        memory_kai.clone(),
+        embedding.clone(),
+        state.web_search.clone(),
+        None,
+    )
+    .with_event_bus(Some(state.event_bus.clone()))
+    .with_sources(learning_source::default_sources(
+        &state.pool,
+        state.gemini_api_key.as_deref(),
+    ));
+
+    state.tasks.spawn(async move {
+        if let Err(e) = service.learn(rei_id).await {
+            tracing::warn!("⚠️  Inbound learning failed for {}: {}", rei_id, e);
+        }
+    });
+
+    Ok(())
+}
+
+/// Source management (requires authentication)
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/kaiba/rei/:rei_id/inbound-sources",
+            get(list_sources).post(upsert_source),
+        )
+        .route(
+            "/kaiba/rei/:rei_id/inbound-sources/:source",
+            delete(delete_source),
+        )
+}
+
+/// Payload receiver (authenticated per source by signature)
+pub fn public_router() -> Router<AppState> {
+    Router::new().route("/kaiba/rei/:rei_id/inbound/:source", post(receive_inbound))
+}
@@ -0,0 +1,210 @@
+//! Integration Routes - Platform integrations and per-Rei switches
+//!
+//! GET  /kaiba/integrations                              - Registered integrations and their health
+//! GET  /kaiba/rei/:rei_id/integrations                  - Which integrations answer for a Rei
+//! POST /kaiba/rei/:rei_id/integrations/:name/enable     - Switch one on for a Rei
+//! POST /kaiba/rei/:rei_id/integrations/:name/disable    - Switch one off for a Rei
+//! PUT  /kaiba/rei/:rei_id/integrations/:name            - Change switches (incl. ingestion)
+//!
+//! Integrations are registered at startup from the configuration. Every
+//! integration is enabled for a Rei until switched off; switching off keeps
+//! the manifest binding, so switching back on needs no other change.
+//! Ingestion of what an integration reads into memories is opt-in.
+
+use axum::{
+    extract::{Path, State},
+    routing::{get, post, put},
+    Json, Router,
+};
+use uuid::Uuid;
+
+use crate::error::ApiError;
+use crate::models::{IntegrationHealth, ReiIntegrationResponse, UpdateReiIntegrationRequest};
+use crate::services::integration_registry;
+use crate::AppState;
+
+/// List registered integrations with a fresh health check
+#[utoipa::path(
+    get,
+    path = "/kaiba/integrations",
+    responses(
+        (status = 200, description = "Registered integrations", body = Vec<IntegrationHealth>),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Integration"
+)]
+pub async fn list_integrations(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<IntegrationHealth>>, ApiError> {
+    Ok(Json(state.integrations.health().await))
+}
+
+/// List the registered integrations as they apply to a Rei
+#[utoipa::path(
+    get,
+    path = "/kaiba/rei/{rei_id}/integrations",
+    params(("rei_id" = Uuid, Path, description = "Rei ID")),
+    responses(
+        (status = 200, description = "Integrations for the Rei", body = Vec<ReiIntegrationResponse>),
+        (status = 404, description = "Rei not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Integration"
+)]
+pub async fn list_rei_integrations(
+    State(state): State<AppState>,
+    Path(rei_id): Path<Uuid>,
+) -> Result<Json<Vec<ReiIntegrationResponse>>, ApiError> {
+    ensure_rei(&state, rei_id).await?;
+
+    let mut switches = integration_registry::switches(&state.pool, rei_id)
+        .await
+        .map_err(ApiError::internal)?;
+
+    let integrations = state
+        .integrations
+        .names()
+        .map(
+            |name| match switches.iter().position(|s| s.integration == name) {
+                Some(i) => switches.swap_remove(i).into(),
+                None => ReiIntegrationResponse::default_for(name),
+            },
+        )
+        .collect();
+
+    Ok(Json(integrations))
+}
+
+/// Switch an integration on for a Rei
+#[utoipa::path(
+    post,
+    path = "/kaiba/rei/{rei_id}/integrations/{name}/enable",
+    params(
+        ("rei_id" = Uuid, Path, description = "Rei ID"),
+        ("name" = String, Path, description = "Integration name")
+    ),
+    responses(
+        (status = 200, description = "Integration enabled", body = ReiIntegrationResponse),
+        (status = 404, description = "Rei or integration not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Integration"
+)]
+pub async fn enable_integration(
+    State(state): State<AppState>,
+    Path((rei_id, name)): Path<(Uuid, String)>,
+) -> Result<Json<ReiIntegrationResponse>, ApiError> {
+    update(&state, rei_id, &name, Some(true), None).await
+}
+
+/// Switch an integration off for a Rei
+///
+/// The integration ignores the Rei's messages until it is enabled again.
+#[utoipa::path(
+    post,
+    path = "/kaiba/rei/{rei_id}/integrations/{name}/disable",
+    params(
+        ("rei_id" = Uuid, Path, description = "Rei ID"),
+        ("name" = String, Path, description = "Integration name")
+    ),
+    responses(
+        (status = 200, description = "Integration disabled", body = ReiIntegrationResponse),
+        (status = 404, description = "Rei or integration not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Integration"
+)]
+pub async fn disable_integration(
+    State(state): State<AppState>,
+    Path((rei_id, name)): Path<(Uuid, String)>,
+) -> Result<Json<ReiIntegrationResponse>, ApiError> {
+    update(&state, rei_id, &name, Some(false), None).await
+}
+
+/// Change an integration's switches for a Rei
+///
+/// With `ingest`, conversations the integration reads for the Rei are
+/// summarized into fact and learning memories every few minutes.
+#[utoipa::path(
+    put,
+    path = "/kaiba/rei/{rei_id}/integrations/{name}",
+    params(
+        ("rei_id" = Uuid, Path, description = "Rei ID"),
+        ("name" = String, Path, description = "Integration name")
+    ),
+    request_body = UpdateReiIntegrationRequest,
+    responses(
+        (status = 200, description = "Integration updated", body = ReiIntegrationResponse),
+        (status = 404, description = "Rei or integration not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Integration"
+)]
+pub async fn update_integration(
+    State(state): State<AppState>,
+    Path((rei_id, name)): Path<(Uuid, String)>,
+    Json(payload): Json<UpdateReiIntegrationRequest>,
+) -> Result<Json<ReiIntegrationResponse>, ApiError> {
+    update(&state, rei_id, &name, payload.enabled, payload.ingest).await
+}
+
+async fn update(
+    state: &AppState,
+    rei_id: Uuid,
+    name: &str,
+    enabled: Option<bool>,
+    ingest: Option<bool>,
+) -> Result<Json<ReiIntegrationResponse>, ApiError> {
+    ensure_rei(state, rei_id).await?;
+    if !state.integrations.contains(name) {
+        return Err(ApiError::not_found(format!(
+            "Integration '{}' is not configured",
+            name
+        )));
+    }
+
+    let saved = integration_registry::update(&state.pool, rei_id, name, enabled, ingest)
+        .await
+        .map_err(ApiError::internal)?;
+
+    tracing::info!(
+        "🔌 Integration {} for Rei {}: enabled={}, ingest={}",
+        name,
+        rei_id,
+        saved.enabled,
+        saved.ingest
+    );
+
+    Ok(Json(saved.into()))
+}
+
+async fn ensure_rei(state: &AppState, rei_id: Uuid) -> Result<(), ApiError> {
+    state
+        .rei_service
+        .get_by_id(rei_id)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or(ApiError::not_found("Rei not found"))?;
+    Ok(())
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/kaiba/integrations", get(list_integrations))
+        .route(
+            "/kaiba/rei/:rei_id/integrations",
+            get(list_rei_integrations),
+        )
+        .route(
+            "/kaiba/rei/:rei_id/integrations/:name",
+            put(update_integration),
+        )
+        .route(
+            "/kaiba/rei/:rei_id/integrations/:name/enable",
+            post(enable_integration),
+        )
+        .route(
+            "/kaiba/rei/:rei_id/integrations/:name/disable",
+            post(disable_integration),
+        )
+}
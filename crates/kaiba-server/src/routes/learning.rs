@@ -2,19 +2,33 @@
 //!
 //! POST /kaiba/rei/:rei_id/learn - Trigger learning for a specific Rei
 //! POST /kaiba/learn/all - Trigger learning for all Reis
+//! POST /kaiba/rei/:rei_id/digest - Digest recent learning into expertise
 //! POST /kaiba/rei/:rei_id/recharge - Manually recharge Rei's energy
+//! GET /kaiba/rei/:rei_id/learning/history - Past learning sessions
+//! GET /kaiba/rei/:rei_id/learning/budget - Daily learning budget and usage
+//! GET /kaiba/rei/:rei_id/learning/topics - Manifest topics in rotation order
 
 use axum::{
-    extract::{Path, State},
-    routing::post,
+    extract::{Path, Query, State},
+    routing::{get, post},
     Json, Router,
 };
+use chrono::{DateTime, Days, NaiveTime, Utc};
+use kaiba::{ReiEvent, ReiEventBus, WebhookEventType};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
-use crate::services::self_learning::{LearningConfig, LearningSession, SelfLearningService};
+use crate::error::ApiError;
+use crate::models::{LearningHistoryQuery, LearningSessionLog};
+use crate::pagination::{Page, PageQuery, Pagination, Sort};
+use crate::services::digest::{DigestResult, DigestService};
+use crate::services::learning_source;
+use crate::services::self_learning::{
+    self, LearningBudget, LearningConfig, LearningSession, LearningTopic, LearningUsage,
+    SelfLearningService,
+};
 use crate::AppState;
 
 /// Learning request (optional config override)
@@ -56,26 +70,22 @@ pub struct BatchLearnResponse {
     ),
     tag = "Learning"
 )]
+#[tracing::instrument(skip_all, fields(rei_id = %rei_id))]
 pub async fn learn_rei(
     State(state): State<AppState>,
     Path(rei_id): Path<Uuid>,
     Json(payload): Json<Option<LearnRequest>>,
-) -> Result<Json<LearnResponse>, (axum::http::StatusCode, String)> {
+) -> Result<Json<LearnResponse>, ApiError> {
     // Check required services
-    let memory_kai = state.memory_kai.as_ref().ok_or((
-        axum::http::StatusCode::SERVICE_UNAVAILABLE,
-        "MemoryKai not available".to_string(),
-    ))?;
-
-    let embedding = state.embedding.as_ref().ok_or((
-        axum::http::StatusCode::SERVICE_UNAVAILABLE,
-        "Embedding service not available".to_string(),
-    ))?;
+    let memory_kai = state
+        .memory_kai
+        .as_ref()
+        .ok_or(ApiError::unavailable("MemoryKai not available"))?;
 
-    let web_search = state.web_search.as_ref().ok_or((
-        axum::http::StatusCode::SERVICE_UNAVAILABLE,
-        "WebSearch not available".to_string(),
-    ))?;
+    let embedding = state
+        .embedding
+        .as_ref()
+        .ok_or(ApiError::unavailable("Embedding service not available"))?;
 
     // Build config from request
     let config = payload.map(|p| LearningConfig {
@@ -89,9 +99,14 @@ pub async fn learn_rei(
         state.pool.clone(),
         memory_kai.clone(),
         embedding.clone(),
-        web_search.clone(),
+        state.web_search.clone(),
         config,
-    );
+    )
+    .with_event_bus(Some(state.event_bus.clone()))
+    .with_sources(learning_source::default_sources(
+        &state.pool,
+        state.gemini_api_key.as_deref(),
+    ));
 
     match service.learn(rei_id).await {
         Ok(session) => {
@@ -130,30 +145,30 @@ pub async fn learn_rei(
 )]
 pub async fn learn_all(
     State(state): State<AppState>,
-) -> Result<Json<BatchLearnResponse>, (axum::http::StatusCode, String)> {
+) -> Result<Json<BatchLearnResponse>, ApiError> {
     // Check required services
-    let memory_kai = state.memory_kai.as_ref().ok_or((
-        axum::http::StatusCode::SERVICE_UNAVAILABLE,
-        "MemoryKai not available".to_string(),
-    ))?;
+    let memory_kai = state
+        .memory_kai
+        .as_ref()
+        .ok_or(ApiError::unavailable("MemoryKai not available"))?;
 
-    let embedding = state.embedding.as_ref().ok_or((
-        axum::http::StatusCode::SERVICE_UNAVAILABLE,
-        "Embedding service not available".to_string(),
-    ))?;
-
-    let web_search = state.web_search.as_ref().ok_or((
-        axum::http::StatusCode::SERVICE_UNAVAILABLE,
-        "WebSearch not available".to_string(),
-    ))?;
+    let embedding = state
+        .embedding
+        .as_ref()
+        .ok_or(ApiError::unavailable("Embedding service not available"))?;
 
     let service = SelfLearningService::new(
         state.pool.clone(),
         memory_kai.clone(),
         embedding.clone(),
-        web_search.clone(),
+        state.web_search.clone(),
         None,
-    );
+    )
+    .with_event_bus(Some(state.event_bus.clone()))
+    .with_sources(learning_source::default_sources(
+        &state.pool,
+        state.gemini_api_key.as_deref(),
+    ));
 
     let results = service.learn_all().await;
 
@@ -196,6 +211,74 @@ pub async fn learn_all(
     }))
 }
 
+/// Digest response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DigestResponse {
+    pub success: bool,
+    pub result: Option<DigestResult>,
+    pub error: Option<String>,
+}
+
+/// Digest recent learning memories for a specific Rei
+#[utoipa::path(
+    post,
+    path = "/kaiba/rei/{rei_id}/digest",
+    params(("rei_id" = Uuid, Path, description = "Rei ID")),
+    responses(
+        (status = 200, description = "Digest result", body = DigestResponse),
+        (status = 503, description = "Required services unavailable"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Learning"
+)]
+#[tracing::instrument(skip_all, fields(rei_id = %rei_id))]
+pub async fn digest_rei(
+    State(state): State<AppState>,
+    Path(rei_id): Path<Uuid>,
+) -> Result<Json<DigestResponse>, ApiError> {
+    let memory_kai = state
+        .memory_kai
+        .as_ref()
+        .ok_or(ApiError::unavailable("MemoryKai not available"))?;
+
+    let embedding = state
+        .embedding
+        .as_ref()
+        .ok_or(ApiError::unavailable("Embedding service not available"))?;
+
+    let service = DigestService::new(
+        state.pool.clone(),
+        memory_kai.clone(),
+        embedding.clone(),
+        state.gemini_api_key.clone(),
+    )
+    .with_event_bus(Some(state.event_bus.clone()))
+    .with_threshold(state.config.digest_threshold());
+
+    match service.digest(rei_id).await {
+        Ok(result) => {
+            tracing::info!(
+                "📚 Digest completed for {}: {} memories processed",
+                rei_id,
+                result.memories_processed
+            );
+            Ok(Json(DigestResponse {
+                success: true,
+                result: Some(result),
+                error: None,
+            }))
+        }
+        Err(e) => {
+            tracing::warn!("⚠️  Digest failed for {}: {}", rei_id, e);
+            Ok(Json(DigestResponse {
+                success: false,
+                result: None,
+                error: Some(e.to_string()),
+            }))
+        }
+    }
+}
+
 /// Recharge request
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct RechargeRequest {
@@ -236,19 +319,18 @@ pub async fn recharge_rei(
     State(state): State<AppState>,
     Path(rei_id): Path<Uuid>,
     Json(payload): Json<RechargeRequest>,
-) -> Result<Json<RechargeResponse>, (axum::http::StatusCode, String)> {
+) -> Result<Json<RechargeResponse>, ApiError> {
+    let mut tx = state.pool.begin().await.map_err(ApiError::internal)?;
+
     // Get current energy
     let current: EnergyUpdate = sqlx::query_as(
-        "SELECT energy_level, energy_regen_per_hour FROM rei_states WHERE rei_id = $1",
+        "SELECT energy_level, energy_regen_per_hour FROM rei_states WHERE rei_id = $1 FOR UPDATE",
     )
     .bind(rei_id)
-    .fetch_optional(&state.pool)
+    .fetch_optional(&mut *tx)
     .await
-    .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
-    .ok_or((
-        axum::http::StatusCode::NOT_FOUND,
-        "Rei not found".to_string(),
-    ))?;
+    .map_err(ApiError::internal)?
+    .ok_or(ApiError::not_found("Rei not found"))?;
 
     let previous_energy = current.energy_level;
 
@@ -259,9 +341,29 @@ pub async fn recharge_rei(
     sqlx::query("UPDATE rei_states SET energy_level = $1 WHERE rei_id = $2")
         .bind(new_energy)
         .bind(rei_id)
-        .execute(&state.pool)
+        .execute(&mut *tx)
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .map_err(ApiError::internal)?;
+
+    let event = ReiEvent::new(
+        rei_id,
+        WebhookEventType::StateChanged,
+        serde_json::json!({
+            "reason": "recharged",
+            "previous_energy": previous_energy,
+            "energy_level": new_energy,
+            "energy_regen_per_hour": current.energy_regen_per_hour,
+        }),
+    );
+    let unstaged = state
+        .event_bus
+        .stage_in(&mut tx, event)
+        .await
+        .map_err(ApiError::internal)?;
+    tx.commit().await.map_err(ApiError::internal)?;
+    if let Some(event) = unstaged {
+        state.event_bus.publish(event);
+    }
 
     tracing::info!(
         "⚡ Recharged Rei {}: {} -> {} (+{})",
@@ -279,9 +381,173 @@ pub async fn recharge_rei(
     }))
 }
 
+/// Past learning sessions of a Rei
+#[utoipa::path(
+    get,
+    path = "/kaiba/rei/{rei_id}/learning/history",
+    params(
+        ("rei_id" = Uuid, Path, description = "Rei ID"),
+        LearningHistoryQuery,
+        PageQuery
+    ),
+    responses(
+        (status = 200, description = "Page of sessions (newest first by default)", body = Vec<LearningSessionLog>,
+            headers(("x-total-count" = i64, description = "Sessions matching the filters"))),
+        (status = 422, description = "Invalid paging or sort parameters"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Learning"
+)]
+pub async fn get_learning_history(
+    State(state): State<AppState>,
+    Path(rei_id): Path<Uuid>,
+    pagination: Pagination,
+    Query(query): Query<LearningHistoryQuery>,
+) -> Result<Page<LearningSessionLog>, ApiError> {
+    let sort = pagination.sort_or(
+        &["started_at", "memories_stored", "energy_spent"],
+        Sort::desc("started_at"),
+    )?;
+    // Only whitelisted columns reach the query
+    let order_by = match (sort.field.as_str(), sort.descending) {
+        ("memories_stored", true) => "memories_stored DESC, started_at DESC",
+        ("memories_stored", false) => "memories_stored ASC, started_at DESC",
+        ("energy_spent", true) => "energy_spent DESC, started_at DESC",
+        ("energy_spent", false) => "energy_spent ASC, started_at DESC",
+        (_, true) => "started_at DESC",
+        (_, false) => "started_at ASC",
+    };
+
+    let filter = r#"
+        WHERE rei_id = $1
+          AND ($2::timestamptz IS NULL OR started_at > $2)
+          AND (NOT $3 OR jsonb_array_length(errors) > 0)
+    "#;
+
+    let total: i64 = sqlx::query_scalar(&format!(
+        "SELECT COUNT(*) FROM learning_sessions {}",
+        filter
+    ))
+    .bind(rei_id)
+    .bind(query.since)
+    .bind(query.failed_only)
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let items = sqlx::query_as::<_, LearningSessionLog>(&format!(
+        "SELECT * FROM learning_sessions {} ORDER BY {} LIMIT $4 OFFSET $5",
+        filter, order_by
+    ))
+    .bind(rei_id)
+    .bind(query.since)
+    .bind(query.failed_only)
+    .bind(pagination.limit)
+    .bind(pagination.offset)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    Ok(Page { items, total })
+}
+
+/// Daily learning budget response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LearningBudgetResponse {
+    pub rei_id: Uuid,
+    /// Caps in effect (manifest `learning_budget` over the server defaults)
+    pub budget: LearningBudget,
+    /// Consumed so far today (UTC)
+    pub used_today: LearningUsage,
+    /// When the counters restart
+    pub resets_at: DateTime<Utc>,
+}
+
+/// Get a Rei's daily learning budget and today's usage
+#[utoipa::path(
+    get,
+    path = "/kaiba/rei/{rei_id}/learning/budget",
+    params(("rei_id" = Uuid, Path, description = "Rei ID")),
+    responses(
+        (status = 200, description = "Budget and usage", body = LearningBudgetResponse),
+        (status = 404, description = "Rei not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Learning"
+)]
+pub async fn get_learning_budget(
+    State(state): State<AppState>,
+    Path(rei_id): Path<Uuid>,
+) -> Result<Json<LearningBudgetResponse>, ApiError> {
+    let (rei, _) = state
+        .rei_service
+        .get_by_id(rei_id)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or(ApiError::not_found("Rei not found"))?;
+
+    let used_today = self_learning::usage_today(&state.pool, rei_id)
+        .await
+        .map_err(ApiError::internal)?;
+    let tomorrow = Utc::now().date_naive() + Days::new(1);
+
+    Ok(Json(LearningBudgetResponse {
+        rei_id,
+        budget: LearningBudget::for_manifest(&rei.manifest, &LearningConfig::default().budget),
+        used_today,
+        resets_at: tomorrow.and_time(NaiveTime::MIN).and_utc(),
+    }))
+}
+
+/// List a Rei's learning topics, next to be searched first
+///
+/// Topics come from the manifest; each shows when a session last took it up.
+#[utoipa::path(
+    get,
+    path = "/kaiba/rei/{rei_id}/learning/topics",
+    params(("rei_id" = Uuid, Path, description = "Rei ID")),
+    responses(
+        (status = 200, description = "Topics in rotation order", body = Vec<LearningTopic>),
+        (status = 404, description = "Rei not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Learning"
+)]
+pub async fn get_learning_topics(
+    State(state): State<AppState>,
+    Path(rei_id): Path<Uuid>,
+) -> Result<Json<Vec<LearningTopic>>, ApiError> {
+    let (rei, _) = state
+        .rei_service
+        .get_by_id(rei_id)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or(ApiError::not_found("Rei not found"))?;
+
+    let queries = self_learning::manifest_queries(&rei.manifest, &rei.role);
+    let topics = self_learning::learning_topics(&state.pool, rei_id, &queries)
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(topics))
+}
+
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/kaiba/rei/:rei_id/learn", post(learn_rei))
+        .route("/kaiba/rei/:rei_id/digest", post(digest_rei))
         .route("/kaiba/rei/:rei_id/recharge", post(recharge_rei))
+        .route(
+            "/kaiba/rei/:rei_id/learning/history",
+            get(get_learning_history),
+        )
+        .route(
+            "/kaiba/rei/:rei_id/learning/budget",
+            get(get_learning_budget),
+        )
+        .route(
+            "/kaiba/rei/:rei_id/learning/topics",
+            get(get_learning_topics),
+        )
         .route("/kaiba/learn/all", post(learn_all))
 }
@@ -1,15 +1,22 @@
 //! Memory Routes - Long-term memory storage in MemoryKai (Qdrant)
 
 use axum::{
-    extract::{Path, State},
-    routing::post,
+    extract::{Path, Query, State},
+    response::Response,
+    routing::{get, post},
     Json, Router,
 };
 use chrono::Utc;
 use uuid::Uuid;
 
-use crate::models::{CreateMemoryRequest, Memory, MemoryResponse, SearchMemoriesRequest};
+use crate::error::ApiError;
+use crate::models::{
+    CreateMemoryRequest, ListMemoriesQuery, Memory, MemoryResponse, SearchMemoriesRequest,
+    UpdateMemoryRequest,
+};
+use crate::rest;
 use crate::services::SearchFilter;
+use crate::validation::ValidJson;
 use crate::AppState;
 
 /// Add a memory to MemoryKai
@@ -19,26 +26,28 @@ use crate::AppState;
     params(("rei_id" = Uuid, Path, description = "Rei ID")),
     request_body = CreateMemoryRequest,
     responses(
-        (status = 200, description = "Memory added", body = MemoryResponse),
+        (status = 201, description = "Memory added", body = MemoryResponse,
+            headers(("location" = String, description = "URL of the new memory"))),
         (status = 503, description = "MemoryKai or Embedding service unavailable"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Memory"
 )]
+#[tracing::instrument(skip_all, fields(rei_id = %rei_id))]
 pub async fn add_memory(
     State(state): State<AppState>,
     Path(rei_id): Path<Uuid>,
-    Json(payload): Json<CreateMemoryRequest>,
-) -> Result<Json<MemoryResponse>, (axum::http::StatusCode, String)> {
-    let memory_kai = state.memory_kai.as_ref().ok_or((
-        axum::http::StatusCode::SERVICE_UNAVAILABLE,
-        "MemoryKai not available".to_string(),
-    ))?;
-
-    let embedding_service = state.embedding.as_ref().ok_or((
-        axum::http::StatusCode::SERVICE_UNAVAILABLE,
-        "Embedding service not available".to_string(),
-    ))?;
+    ValidJson(payload): ValidJson<CreateMemoryRequest>,
+) -> Result<Response, ApiError> {
+    let memory_kai = state
+        .memory_kai
+        .as_ref()
+        .ok_or(ApiError::unavailable("MemoryKai not available"))?;
+
+    let embedding_service = state
+        .embedding
+        .as_ref()
+        .ok_or(ApiError::unavailable("Embedding service not available"))?;
 
     let memory = Memory {
         id: Uuid::new_v4().to_string(),
@@ -55,12 +64,162 @@ pub async fn add_memory(
     let embedding = embedding_service
         .embed(&payload.content)
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .map_err(ApiError::internal)?;
 
     memory_kai
         .add_memory(&rei_id.to_string(), memory.clone(), embedding)
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .map_err(ApiError::internal)?;
+
+    state.event_bus.emit(memory.added_event(rei_id)).await;
+
+    let location = format!("/kaiba/rei/{}/memories/{}", rei_id, memory.id);
+    Ok(rest::created(location, MemoryResponse::from(memory)))
+}
+
+/// List recent memories, newest first
+///
+/// Unlike search this needs no query, so it can be polled for new memories.
+#[utoipa::path(
+    get,
+    path = "/kaiba/rei/{rei_id}/memories",
+    params(
+        ("rei_id" = Uuid, Path, description = "Rei ID"),
+        ListMemoriesQuery
+    ),
+    responses(
+        (status = 200, description = "Recent memories", body = Vec<MemoryResponse>),
+        (status = 503, description = "MemoryKai unavailable"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Memory"
+)]
+pub async fn list_memories(
+    State(state): State<AppState>,
+    Path(rei_id): Path<Uuid>,
+    Query(query): Query<ListMemoriesQuery>,
+) -> Result<Json<Vec<MemoryResponse>>, ApiError> {
+    let memory_kai = state
+        .memory_kai
+        .as_ref()
+        .ok_or(ApiError::unavailable("MemoryKai not available"))?;
+
+    let filter = SearchFilter {
+        created_after: query.since,
+        ..Default::default()
+    };
+
+    let memories = memory_kai
+        .list_memories(&rei_id.to_string(), query.limit.unwrap_or(50), filter)
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(
+        memories.into_iter().map(MemoryResponse::from).collect(),
+    ))
+}
+
+/// Get a single memory
+#[utoipa::path(
+    get,
+    path = "/kaiba/rei/{rei_id}/memories/{memory_id}",
+    params(
+        ("rei_id" = Uuid, Path, description = "Rei ID"),
+        ("memory_id" = String, Path, description = "Memory ID")
+    ),
+    responses(
+        (status = 200, description = "Memory found", body = MemoryResponse),
+        (status = 404, description = "Memory not found"),
+        (status = 503, description = "MemoryKai unavailable"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Memory"
+)]
+pub async fn get_memory(
+    State(state): State<AppState>,
+    Path((rei_id, memory_id)): Path<(Uuid, String)>,
+) -> Result<Json<MemoryResponse>, ApiError> {
+    let memory_kai = state
+        .memory_kai
+        .as_ref()
+        .ok_or(ApiError::unavailable("MemoryKai not available"))?;
+
+    let memory = memory_kai
+        .get_memory(&rei_id.to_string(), &memory_id)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or(ApiError::not_found("Memory not found"))?;
+
+    Ok(Json(memory.into()))
+}
+
+/// Update a memory
+///
+/// The memory is re-embedded so search reflects the edited content.
+#[utoipa::path(
+    put,
+    path = "/kaiba/rei/{rei_id}/memories/{memory_id}",
+    params(
+        ("rei_id" = Uuid, Path, description = "Rei ID"),
+        ("memory_id" = String, Path, description = "Memory ID")
+    ),
+    request_body = UpdateMemoryRequest,
+    responses(
+        (status = 200, description = "Memory updated", body = MemoryResponse),
+        (status = 400, description = "Invalid importance"),
+        (status = 404, description = "Memory not found"),
+        (status = 503, description = "MemoryKai or Embedding service unavailable"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Memory"
+)]
+pub async fn update_memory(
+    State(state): State<AppState>,
+    Path((rei_id, memory_id)): Path<(Uuid, String)>,
+    ValidJson(payload): ValidJson<UpdateMemoryRequest>,
+) -> Result<Json<MemoryResponse>, ApiError> {
+    let memory_kai = state
+        .memory_kai
+        .as_ref()
+        .ok_or(ApiError::unavailable("MemoryKai not available"))?;
+
+    let embedding_service = state
+        .embedding
+        .as_ref()
+        .ok_or(ApiError::unavailable("Embedding service not available"))?;
+
+    let mut memory = memory_kai
+        .get_memory(&rei_id.to_string(), &memory_id)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or(ApiError::not_found("Memory not found"))?;
+
+    if let Some(importance) = payload.importance {
+        memory.importance = importance;
+    }
+    if let Some(content) = payload.content {
+        memory.content = content;
+    }
+    if let Some(memory_type) = payload.memory_type {
+        memory.memory_type = memory_type;
+    }
+    if let Some(tags) = payload.tags {
+        memory.tags = tags;
+    }
+    if let Some(metadata) = payload.metadata {
+        memory.metadata = Some(metadata);
+    }
+
+    let embedding = embedding_service
+        .embed(&memory.content)
+        .await
+        .map_err(ApiError::internal)?;
+
+    // Same point ID, so this overwrites the stored memory
+    memory_kai
+        .add_memory(&rei_id.to_string(), memory.clone(), embedding)
+        .await
+        .map_err(ApiError::internal)?;
 
     Ok(Json(memory.into()))
 }
@@ -78,26 +237,27 @@ pub async fn add_memory(
     ),
     tag = "Memory"
 )]
+#[tracing::instrument(skip_all, fields(rei_id = %rei_id))]
 pub async fn search_memories(
     State(state): State<AppState>,
     Path(rei_id): Path<Uuid>,
     Json(payload): Json<SearchMemoriesRequest>,
-) -> Result<Json<Vec<MemoryResponse>>, (axum::http::StatusCode, String)> {
-    let memory_kai = state.memory_kai.as_ref().ok_or((
-        axum::http::StatusCode::SERVICE_UNAVAILABLE,
-        "MemoryKai not available".to_string(),
-    ))?;
+) -> Result<Json<Vec<MemoryResponse>>, ApiError> {
+    let memory_kai = state
+        .memory_kai
+        .as_ref()
+        .ok_or(ApiError::unavailable("MemoryKai not available"))?;
 
-    let embedding_service = state.embedding.as_ref().ok_or((
-        axum::http::StatusCode::SERVICE_UNAVAILABLE,
-        "Embedding service not available".to_string(),
-    ))?;
+    let embedding_service = state
+        .embedding
+        .as_ref()
+        .ok_or(ApiError::unavailable("Embedding service not available"))?;
 
     // Generate query embedding using OpenAI API
     let query_vector = embedding_service
         .embed(&payload.query)
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .map_err(ApiError::internal)?;
 
     let limit = payload.limit.unwrap_or(10);
 
@@ -107,21 +267,35 @@ pub async fn search_memories(
         tags: payload.tags,
         tags_match_mode: payload.tags_match_mode,
         min_importance: payload.min_importance,
-        ..Default::default()
+        created_after: payload.created_after,
+        created_before: payload.created_before,
     };
 
     let memories = memory_kai
-        .search_memories_with_filter(&rei_id.to_string(), query_vector, limit, filter)
+        .search_memories_scored(&rei_id.to_string(), query_vector, limit, filter)
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .map_err(ApiError::internal)?;
 
     Ok(Json(
-        memories.into_iter().map(MemoryResponse::from).collect(),
+        memories
+            .into_iter()
+            .map(|(memory, score)| MemoryResponse {
+                similarity: Some(score),
+                ..MemoryResponse::from(memory)
+            })
+            .collect(),
     ))
 }
 
 pub fn router() -> Router<AppState> {
     Router::new()
-        .route("/kaiba/rei/:rei_id/memories", post(add_memory))
+        .route(
+            "/kaiba/rei/:rei_id/memories",
+            post(add_memory).get(list_memories),
+        )
         .route("/kaiba/rei/:rei_id/memories/search", post(search_memories))
+        .route(
+            "/kaiba/rei/:rei_id/memories/:memory_id",
+            get(get_memory).put(update_memory),
+        )
 }
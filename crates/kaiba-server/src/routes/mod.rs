@@ -5,18 +5,50 @@
 //! - /kaiba/rei/:id/call - LLM invocation
 //! - /kaiba/rei/:id/memories - Memory storage (MemoryKai/Qdrant)
 //! - /kaiba/rei/:id/webhooks - Webhook management (外界へのアクション)
+//! - /kaiba/webhooks - Global webhooks (all Reis)
+//! - /kaiba/events - Custom event registry
+//! - /kaiba/rei/:id/events - Live event stream (SSE)
+//! - /kaiba/rei/:id/inbound/:source - Inbound webhooks (外界からの刺激)
+//! - /kaiba/integrations - Registered integrations and their health
+//! - /kaiba/rei/:id/integrations - Per-Rei integration switches
+//! - /kaiba/integrations/discord/interactions - Discord slash commands
+//! - /kaiba/integrations/telegram/webhook - Telegram bot updates
+//! - /kaiba/integrations/github/webhook - GitHub App deliveries
+//! - /kaiba/rei/:id/schedule - Per-Rei cron schedule
+//! - /kaiba/rei/:id/tasks - Scheduled one-off tasks
+//! - /kaiba/rei/:id/decision-policy - Per-Rei decision policy and log
 //! - /kaiba/rei/:id/dashboard - Dashboard (状況一覧)
 //! - /kaiba/search - Web search (Gemini)
 //! - /kaiba/rei/:id/learn - Self-learning (自己活動)
+//! - /kaiba/api-keys - Scoped API keys
+//! - /kaiba/users - Users (resource owners)
+//! - /kaiba/admin/config - Effective configuration (secrets redacted)
+//! - /kaiba/admin/audit - Audit log of changes
+//! - /kaiba/admin/scheduler/runs - Scheduler and trigger run history
+//! - /kaiba/admin/seed - Demo data
 
+pub mod admin;
+pub mod api_key;
 pub mod call;
 pub mod dashboard;
+pub mod decision;
+pub mod discord;
+pub mod event_registry;
+pub mod event_stream;
+pub mod github;
+pub mod global_webhook;
+pub mod inbound;
+pub mod integration;
 pub mod learning;
 pub mod memory;
 pub mod prompt;
 pub mod rei;
+pub mod schedule;
 pub mod search;
 pub mod swagger;
+pub mod task;
 pub mod tei;
+pub mod telegram;
 pub mod trigger;
+pub mod user;
 pub mod webhook;
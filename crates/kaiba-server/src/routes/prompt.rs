@@ -5,13 +5,18 @@
 
 use axum::{
     extract::{Path, Query, State},
+    http::HeaderMap,
+    response::Response,
     routing::get,
-    Json, Router,
+    Router,
 };
 use llm_toolkit::ToPrompt;
 use serde::Serialize;
+use tracing::Instrument;
 use uuid::Uuid;
 
+use crate::error::ApiError;
+use crate::etag;
 use crate::models::{
     Memory, PromptFormat, PromptQuery, PromptResponse, Rei, ReiState, ReiSummary, TagMatchMode,
 };
@@ -29,50 +34,50 @@ use crate::AppState;
         PromptQuery
     ),
     responses(
-        (status = 200, description = "Generated prompt", body = PromptResponse),
+        (status = 200, description = "Generated prompt", body = PromptResponse,
+            headers(("etag" = String, description = "Changes whenever the Rei, its state or the included memories do"))),
+        (status = 304, description = "Unchanged since the `If-None-Match` ETag"),
         (status = 404, description = "Rei not found"),
         (status = 400, description = "Invalid format"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Prompt"
 )]
+#[tracing::instrument(skip_all, fields(rei_id = %rei_id))]
 pub async fn generate_prompt(
     State(state): State<AppState>,
     Path(rei_id): Path<Uuid>,
     Query(query): Query<PromptQuery>,
-) -> Result<Json<PromptResponse>, (axum::http::StatusCode, String)> {
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
     let pool = &state.pool;
 
     // 1. Parse format
     let format: PromptFormat = query
         .format
         .as_deref()
-        .map(|s| s.parse())
+        .map(|s| s.parse::<PromptFormat>())
         .transpose()
-        .map_err(|e: String| (axum::http::StatusCode::BAD_REQUEST, e))?
+        .map_err(ApiError::bad_request)?
         .unwrap_or_default();
 
     // 2. Load Rei
-    let rei = sqlx::query_as::<_, Rei>("SELECT * FROM reis WHERE id = $1")
+    let rei = sqlx::query_as::<_, Rei>("SELECT * FROM reis WHERE id = $1 AND deleted_at IS NULL")
         .bind(rei_id)
         .fetch_optional(pool)
+        .instrument(tracing::info_span!("postgres.load_rei"))
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
-        .ok_or((
-            axum::http::StatusCode::NOT_FOUND,
-            "Rei not found".to_string(),
-        ))?;
+        .map_err(ApiError::internal)?
+        .ok_or(ApiError::not_found("Rei not found"))?;
 
     // 3. Load Rei state
     let rei_state = sqlx::query_as::<_, ReiState>("SELECT * FROM rei_states WHERE rei_id = $1")
         .bind(rei_id)
         .fetch_optional(pool)
+        .instrument(tracing::info_span!("postgres.load_rei_state"))
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
-        .ok_or((
-            axum::http::StatusCode::NOT_FOUND,
-            "Rei state not found".to_string(),
-        ))?;
+        .map_err(ApiError::internal)?
+        .ok_or(ApiError::not_found("Rei state not found"))?;
 
     // 4. RAG: Search relevant memories if requested
     let memories = if query.include_memories {
@@ -105,18 +110,21 @@ pub async fn generate_prompt(
         memories.len()
     );
 
-    Ok(Json(PromptResponse {
-        system_prompt,
-        format: format_name(format).to_string(),
-        rei: ReiSummary {
-            id: rei.id,
-            name: rei.name,
-            role: rei.role,
-            energy_level: rei_state.energy_level,
-            mood: rei_state.mood,
+    etag::json_with_etag(
+        &headers,
+        &PromptResponse {
+            system_prompt,
+            format: format_name(format).to_string(),
+            rei: ReiSummary {
+                id: rei.id,
+                name: rei.name,
+                role: rei.role,
+                energy_level: rei_state.energy_level,
+                mood: rei_state.mood,
+            },
+            memories_included: memories.len(),
         },
-        memories_included: memories.len(),
-    }))
+    )
 }
 
 // ============================================
@@ -129,6 +137,7 @@ pub async fn generate_prompt(
 Role: {{ role }}
 Mood: {{ mood }}
 Energy: {{ energy_level }}%"#)]
+#[allow(dead_code)]
 struct ReiIdentityDto {
     name: String,
     role: String,
@@ -137,6 +146,7 @@ struct ReiIdentityDto {
 }
 
 impl ReiIdentityDto {
+    #[allow(dead_code)]
     fn from_rei(rei: &Rei, state: &ReiState) -> Self {
         Self {
             name: rei.name.clone(),
@@ -248,6 +258,8 @@ kaiba memory add -t <type> "<content>"
 ```
 Types: learning, fact, expertise, reflection
 
+With the Kaiba MCP server connected, the `search_memories` and `add_memory` tools do the same.
+
 Use search to recall past conversations, projects, or learnings that aren't in the initial context."#)]
 struct CastingPromptDto {
     rei_name: String,
@@ -282,6 +294,7 @@ Personality: {{ personality }}{% endif %}
 ## Memory
 - Search: `kaiba memory search "<query>"` (not all memories are in this prompt)
 - Save: `kaiba memory add -t <type> "<content>"`
+- MCP: `search_memories` / `add_memory` tools, when the Kaiba MCP server is connected
 Types: learning, fact, expertise, reflection"#)]
 struct ClaudeCodePromptDto {
     rei_name: String,
@@ -329,6 +342,10 @@ Personality: {{ personality }}{% endif %}
 {% if instructions %}
 
 Instructions: {{ instructions }}{% endif %}
+{% if expertise %}
+
+## Expertise
+{{ expertise }}{% endif %}
 {% if has_memories %}
 
 ## Relevant Memories
@@ -342,12 +359,14 @@ pub(crate) struct CallPromptDto {
     rei_role: String,
     personality: Option<String>,
     instructions: Option<String>,
+    /// Latest digest merged into the Tei's expertise
+    expertise: Option<String>,
     memories: Vec<String>,
     has_memories: bool,
 }
 
 impl CallPromptDto {
-    pub(crate) fn new(rei: &Rei, memories: &[Memory]) -> Self {
+    pub(crate) fn new(rei: &Rei, memories: &[Memory], expertise: Option<&str>) -> Self {
         let manifest = ReiManifestDto::from_rei(rei);
         let memory_strs: Vec<String> = memories.iter().map(|m| MemoryDto::from(m).to_prompt()).collect();
         let has_memories = !memories.is_empty();
@@ -357,6 +376,7 @@ impl CallPromptDto {
             rei_role: rei.role.clone(),
             personality: manifest.personality,
             instructions: manifest.instructions,
+            expertise: expertise.map(str::to_string),
             memories: memory_strs,
             has_memories,
         }
@@ -598,7 +618,7 @@ async fn search_memories_for_prompt(
     limit: Option<usize>,
     focus_tags: Vec<String>,
     min_importance: Option<f32>,
-) -> Result<Vec<Memory>, (axum::http::StatusCode, String)> {
+) -> Result<Vec<Memory>, ApiError> {
     let memory_kai = match &state.memory_kai {
         Some(kai) => kai,
         None => return Ok(vec![]),
@@ -612,7 +632,7 @@ async fn search_memories_for_prompt(
     // Generate query embedding
     let query_vector = embedding_service.embed(query).await.map_err(|e| {
         tracing::warn!("Failed to generate embedding for prompt RAG: {}", e);
-        (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+        ApiError::internal(e)
     })?;
 
     // Build search filter
@@ -631,7 +651,7 @@ async fn search_memories_for_prompt(
         .await
         .map_err(|e| {
             tracing::warn!("Failed to search memories for prompt: {}", e);
-            (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+            ApiError::internal(e)
         })?;
 
     Ok(memories)
@@ -677,6 +697,7 @@ mod tests {
             energy_regen_per_hour: 10,
             last_digest_at: None,
             last_learn_at: None,
+            autonomy_enabled: true,
         }
     }
 
@@ -723,7 +744,7 @@ mod tests {
     fn test_casting_prompt_dto() {
         let rei = sample_rei();
         let state = sample_rei_state();
-        let memories = vec![sample_memory()];
+        let memories = [sample_memory()];
 
         let manifest = ReiManifestDto::from_rei(&rei);
         let memory_strs: Vec<String> = memories.iter().map(|m| MemoryDto::from(m).to_prompt()).collect();
@@ -770,7 +791,7 @@ mod tests {
     fn test_claude_code_prompt_dto() {
         let rei = sample_rei();
         let state = sample_rei_state();
-        let memories = vec![sample_memory()];
+        let memories = [sample_memory()];
 
         let manifest = ReiManifestDto::from_rei(&rei);
         let memory_strs: Vec<String> = memories.iter().map(|m| MemoryDto::from(m).to_prompt()).collect();
@@ -811,7 +832,7 @@ mod tests {
     fn test_raw_prompt_dto() {
         let rei = sample_rei();
         let state = sample_rei_state();
-        let memories = vec![sample_memory()];
+        let memories = [sample_memory()];
 
         let manifest_json = serde_json::to_string_pretty(&rei.manifest).unwrap();
         let memory_strs: Vec<String> = memories.iter().map(|m| MemoryDto::from(m).to_prompt()).collect();
@@ -850,7 +871,7 @@ mod tests {
         let rei = sample_rei();
         let memories = vec![sample_memory()];
 
-        let dto = CallPromptDto::new(&rei, &memories);
+        let dto = CallPromptDto::new(&rei, &memories, None);
         let prompt = dto.to_prompt();
 
         // Check core structure
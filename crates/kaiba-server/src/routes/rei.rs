@@ -3,38 +3,77 @@
 //! HTTP handlers that delegate to ReiService for business logic.
 
 use axum::{
-    extract::{Path, State},
-    routing::get,
-    Json, Router,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::Response,
+    routing::{get, post},
+    Extension, Json, Router,
 };
 use uuid::Uuid;
 
+use crate::auth::Principal;
+use crate::error::ApiError;
+use crate::etag;
 use crate::models::{
-    CreateReiRequest, ReiResponse, ReiStateResponse, UpdateReiRequest, UpdateReiStateRequest,
+    CreateReiRequest, ListReisQuery, ReiResponse, ReiStateResponse, UpdateReiRequest,
+    UpdateReiStateRequest,
 };
+use crate::pagination::{Page, PageQuery, Pagination, Sort};
+use crate::rest;
+use crate::services::ownership::{self, Owned};
+use crate::validation::ValidJson;
 use crate::AppState;
 
-/// List all Reis
+/// List Reis (only the caller's own for user-bound keys)
 #[utoipa::path(
     get,
     path = "/kaiba/rei",
+    params(ListReisQuery, PageQuery),
     responses(
-        (status = 200, description = "List of all Reis", body = Vec<ReiResponse>),
+        (status = 200, description = "Page of Reis", body = Vec<ReiResponse>,
+            headers(("x-total-count" = i64, description = "Reis matching the filters"))),
+        (status = 422, description = "Invalid paging or sort parameters"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Rei"
 )]
 pub async fn list_reis(
     State(state): State<AppState>,
-) -> Result<Json<Vec<ReiResponse>>, (axum::http::StatusCode, String)> {
+    principal: Option<Extension<Principal>>,
+    pagination: Pagination,
+    Query(filter): Query<ListReisQuery>,
+) -> Result<Page<ReiResponse>, ApiError> {
+    let sort = pagination.sort_or(
+        &["name", "created_at", "updated_at", "energy_level"],
+        Sort::desc("created_at"),
+    )?;
+
+    let visible = ownership::visible(
+        &state.pool,
+        Owned::Rei,
+        principal.and_then(|Extension(p)| p.owner()),
+    )
+    .await
+    .map_err(ApiError::internal)?;
+
     let results = state
         .rei_service
         .list_all()
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .map_err(ApiError::internal)?;
 
-    let responses: Vec<ReiResponse> = results
+    let name = filter.name.map(|n| n.to_lowercase());
+    let mut responses: Vec<ReiResponse> = results
         .into_iter()
+        .filter(|(rei, _)| visible.contains(rei.id))
+        .filter(|(rei, _)| match &name {
+            Some(name) => rei.name.to_lowercase().contains(name),
+            None => true,
+        })
+        .filter(|(_, rei_state)| match &filter.mood {
+            Some(mood) => rei_state.mood == *mood,
+            None => true,
+        })
         .map(|(rei, rei_state)| ReiResponse {
             id: rei.id,
             name: rei.name,
@@ -50,30 +89,47 @@ pub async fn list_reis(
                 energy_regen_per_hour: rei_state.energy_regen_per_hour,
                 last_digest_at: rei_state.last_digest_at,
                 last_learn_at: rei_state.last_learn_at,
+                autonomy_enabled: rei_state.autonomy_enabled,
             },
             created_at: rei.created_at,
             updated_at: rei.updated_at,
         })
         .collect();
 
-    Ok(Json(responses))
+    responses.sort_by(|a, b| {
+        let ordering = match sort.field.as_str() {
+            "name" => a.name.cmp(&b.name),
+            "updated_at" => a.updated_at.cmp(&b.updated_at),
+            "energy_level" => a.state.energy_level.cmp(&b.state.energy_level),
+            _ => a.created_at.cmp(&b.created_at),
+        };
+        if sort.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+
+    Ok(pagination.page(responses))
 }
 
-/// Create new Rei
+/// Create new Rei (owned by the caller's user, if any)
 #[utoipa::path(
     post,
     path = "/kaiba/rei",
     request_body = CreateReiRequest,
     responses(
-        (status = 200, description = "Rei created successfully", body = ReiResponse),
+        (status = 201, description = "Rei created successfully", body = ReiResponse,
+            headers(("location" = String, description = "URL of the new Rei"))),
         (status = 500, description = "Internal server error")
     ),
     tag = "Rei"
 )]
 pub async fn create_rei(
     State(state): State<AppState>,
-    Json(payload): Json<CreateReiRequest>,
-) -> Result<Json<ReiResponse>, (axum::http::StatusCode, String)> {
+    principal: Option<Extension<Principal>>,
+    ValidJson(payload): ValidJson<CreateReiRequest>,
+) -> Result<Response, ApiError> {
     let (rei, rei_state) = state
         .rei_service
         .create(
@@ -81,11 +137,39 @@ pub async fn create_rei(
             payload.role,
             payload.avatar_url,
             payload.manifest,
+            principal.and_then(|Extension(p)| p.owner()),
         )
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .map_err(ApiError::internal)?;
 
-    Ok(Json(ReiResponse {
+    Ok(rest::created(
+        format!("/kaiba/rei/{}", rei.id),
+        ReiResponse {
+            id: rei.id,
+            name: rei.name,
+            role: rei.role,
+            avatar_url: rei.avatar_url,
+            manifest: rei.manifest,
+            state: ReiStateResponse {
+                energy_level: rei_state.energy_level,
+                mood: rei_state.mood,
+                token_budget: rei_state.token_budget,
+                tokens_used: rei_state.tokens_used,
+                last_active_at: rei_state.last_active_at,
+                energy_regen_per_hour: rei_state.energy_regen_per_hour,
+                last_digest_at: rei_state.last_digest_at,
+                last_learn_at: rei_state.last_learn_at,
+                autonomy_enabled: rei_state.autonomy_enabled,
+            },
+            created_at: rei.created_at,
+            updated_at: rei.updated_at,
+        },
+    ))
+}
+
+/// A Rei as GET renders it
+fn rei_response(rei: kaiba::Rei, rei_state: kaiba::ReiState) -> ReiResponse {
+    ReiResponse {
         id: rei.id,
         name: rei.name,
         role: rei.role,
@@ -100,10 +184,11 @@ pub async fn create_rei(
             energy_regen_per_hour: rei_state.energy_regen_per_hour,
             last_digest_at: rei_state.last_digest_at,
             last_learn_at: rei_state.last_learn_at,
+            autonomy_enabled: rei_state.autonomy_enabled,
         },
         created_at: rei.created_at,
         updated_at: rei.updated_at,
-    }))
+    }
 }
 
 /// Get Rei by ID
@@ -114,7 +199,9 @@ pub async fn create_rei(
         ("id" = Uuid, Path, description = "Rei ID")
     ),
     responses(
-        (status = 200, description = "Rei found", body = ReiResponse),
+        (status = 200, description = "Rei found", body = ReiResponse,
+            headers(("etag" = String, description = "Changes whenever the Rei or its state does"))),
+        (status = 304, description = "Unchanged since the `If-None-Match` ETag"),
         (status = 404, description = "Rei not found"),
         (status = 500, description = "Internal server error")
     ),
@@ -123,36 +210,17 @@ pub async fn create_rei(
 pub async fn get_rei(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
-) -> Result<Json<ReiResponse>, (axum::http::StatusCode, String)> {
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
     let (rei, rei_state) = state
         .rei_service
         .get_by_id(id)
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
-        .ok_or((
-            axum::http::StatusCode::NOT_FOUND,
-            "Rei not found".to_string(),
-        ))?;
+        .map_err(ApiError::internal)?
+        .ok_or(ApiError::not_found("Rei not found"))?;
 
-    Ok(Json(ReiResponse {
-        id: rei.id,
-        name: rei.name,
-        role: rei.role,
-        avatar_url: rei.avatar_url,
-        manifest: rei.manifest,
-        state: ReiStateResponse {
-            energy_level: rei_state.energy_level,
-            mood: rei_state.mood,
-            token_budget: rei_state.token_budget,
-            tokens_used: rei_state.tokens_used,
-            last_active_at: rei_state.last_active_at,
-            energy_regen_per_hour: rei_state.energy_regen_per_hour,
-            last_digest_at: rei_state.last_digest_at,
-            last_learn_at: rei_state.last_learn_at,
-        },
-        created_at: rei.created_at,
-        updated_at: rei.updated_at,
-    }))
+    let body = rei_response(rei.clone(), rei_state);
+    etag::json_with_version_etag(&headers, &rei, &body)
 }
 
 /// Update Rei
@@ -160,12 +228,14 @@ pub async fn get_rei(
     put,
     path = "/kaiba/rei/{id}",
     params(
-        ("id" = Uuid, Path, description = "Rei ID")
+        ("id" = Uuid, Path, description = "Rei ID"),
+        ("If-Match" = Option<String>, Header, description = "ETag (or updated_at) of the version being edited; 409 if the Rei has been edited since (state changes don't count)")
     ),
     request_body = UpdateReiRequest,
     responses(
         (status = 200, description = "Rei updated successfully", body = ReiResponse),
         (status = 404, description = "Rei not found"),
+        (status = 409, description = "Changed since the `If-Match` version"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Rei"
@@ -173,8 +243,18 @@ pub async fn get_rei(
 pub async fn update_rei(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
-    Json(payload): Json<UpdateReiRequest>,
-) -> Result<Json<ReiResponse>, (axum::http::StatusCode, String)> {
+    headers: HeaderMap,
+    ValidJson(payload): ValidJson<UpdateReiRequest>,
+) -> Result<Json<ReiResponse>, ApiError> {
+    let (current, _) = state
+        .rei_service
+        .get_by_id(id)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or(ApiError::not_found("Rei not found"))?;
+    // Only the Rei row counts: calls and background jobs change its state
+    let expected = etag::if_match_version(&headers, &current, current.updated_at)?;
+
     let (rei, rei_state) = state
         .rei_service
         .update(
@@ -183,38 +263,24 @@ pub async fn update_rei(
             payload.role,
             payload.avatar_url,
             payload.manifest,
+            expected,
         )
         .await
         .map_err(|e| match e {
-            kaiba::DomainError::NotFound { .. } => (
-                axum::http::StatusCode::NOT_FOUND,
-                "Rei not found".to_string(),
-            ),
-            _ => (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+            kaiba::DomainError::NotFound { .. } => ApiError::not_found("Rei not found"),
+            kaiba::DomainError::Conflict(detail) => {
+                ApiError::conflict(detail).with_code("edit_conflict")
+            }
+            _ => ApiError::internal(e),
         })?;
 
-    Ok(Json(ReiResponse {
-        id: rei.id,
-        name: rei.name,
-        role: rei.role,
-        avatar_url: rei.avatar_url,
-        manifest: rei.manifest,
-        state: ReiStateResponse {
-            energy_level: rei_state.energy_level,
-            mood: rei_state.mood,
-            token_budget: rei_state.token_budget,
-            tokens_used: rei_state.tokens_used,
-            last_active_at: rei_state.last_active_at,
-            energy_regen_per_hour: rei_state.energy_regen_per_hour,
-            last_digest_at: rei_state.last_digest_at,
-            last_learn_at: rei_state.last_learn_at,
-        },
-        created_at: rei.created_at,
-        updated_at: rei.updated_at,
-    }))
+    Ok(Json(rei_response(rei, rei_state)))
 }
 
 /// Delete Rei
+///
+/// The Rei is soft-deleted: it disappears from the API but can be restored
+/// until the purge job removes it after `REI_RETENTION_DAYS`.
 #[utoipa::path(
     delete,
     path = "/kaiba/rei/{id}",
@@ -222,7 +288,7 @@ pub async fn update_rei(
         ("id" = Uuid, Path, description = "Rei ID")
     ),
     responses(
-        (status = 200, description = "Rei deleted successfully"),
+        (status = 204, description = "Rei deleted successfully"),
         (status = 404, description = "Rei not found"),
         (status = 500, description = "Internal server error")
     ),
@@ -231,24 +297,65 @@ pub async fn update_rei(
 pub async fn delete_rei(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
-) -> Result<Json<serde_json::Value>, (axum::http::StatusCode, String)> {
+) -> Result<StatusCode, ApiError> {
     let deleted = state
         .rei_service
         .delete(id)
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .map_err(ApiError::internal)?;
 
     if !deleted {
-        return Err((
-            axum::http::StatusCode::NOT_FOUND,
-            "Rei not found".to_string(),
-        ));
+        return Err(ApiError::not_found("Rei not found"));
     }
 
-    Ok(Json(serde_json::json!({
-        "status": "ok",
-        "message": "Rei deleted"
-    })))
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Restore a deleted Rei
+#[utoipa::path(
+    post,
+    path = "/kaiba/rei/{id}/restore",
+    params(
+        ("id" = Uuid, Path, description = "Rei ID")
+    ),
+    responses(
+        (status = 200, description = "Rei restored", body = ReiResponse),
+        (status = 404, description = "No deleted Rei with this ID"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Rei"
+)]
+pub async fn restore_rei(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ReiResponse>, ApiError> {
+    let (rei, rei_state) = state
+        .rei_service
+        .restore(id)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or(ApiError::not_found("No deleted Rei with this ID"))?;
+
+    Ok(Json(ReiResponse {
+        id: rei.id,
+        name: rei.name,
+        role: rei.role,
+        avatar_url: rei.avatar_url,
+        manifest: rei.manifest,
+        state: ReiStateResponse {
+            energy_level: rei_state.energy_level,
+            mood: rei_state.mood,
+            token_budget: rei_state.token_budget,
+            tokens_used: rei_state.tokens_used,
+            last_active_at: rei_state.last_active_at,
+            energy_regen_per_hour: rei_state.energy_regen_per_hour,
+            last_digest_at: rei_state.last_digest_at,
+            last_learn_at: rei_state.last_learn_at,
+            autonomy_enabled: rei_state.autonomy_enabled,
+        },
+        created_at: rei.created_at,
+        updated_at: rei.updated_at,
+    }))
 }
 
 /// Get Rei state
@@ -268,16 +375,13 @@ pub async fn delete_rei(
 pub async fn get_rei_state(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
-) -> Result<Json<ReiStateResponse>, (axum::http::StatusCode, String)> {
+) -> Result<Json<ReiStateResponse>, ApiError> {
     let rei_state = state
         .rei_service
         .get_state(id)
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
-        .ok_or((
-            axum::http::StatusCode::NOT_FOUND,
-            "Rei state not found".to_string(),
-        ))?;
+        .map_err(ApiError::internal)?
+        .ok_or(ApiError::not_found("Rei state not found"))?;
 
     Ok(Json(ReiStateResponse {
         energy_level: rei_state.energy_level,
@@ -288,6 +392,7 @@ pub async fn get_rei_state(
         energy_regen_per_hour: rei_state.energy_regen_per_hour,
         last_digest_at: rei_state.last_digest_at,
         last_learn_at: rei_state.last_learn_at,
+        autonomy_enabled: rei_state.autonomy_enabled,
     }))
 }
 
@@ -309,8 +414,8 @@ pub async fn get_rei_state(
 pub async fn update_rei_state(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
-    Json(payload): Json<UpdateReiStateRequest>,
-) -> Result<Json<ReiStateResponse>, (axum::http::StatusCode, String)> {
+    ValidJson(payload): ValidJson<UpdateReiStateRequest>,
+) -> Result<Json<ReiStateResponse>, ApiError> {
     let rei_state = state
         .rei_service
         .update_state(
@@ -323,14 +428,11 @@ pub async fn update_rei_state(
         )
         .await
         .map_err(|e| match e {
-            kaiba::DomainError::NotFound { .. } => (
-                axum::http::StatusCode::NOT_FOUND,
-                "Rei state not found".to_string(),
-            ),
-            _ => (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+            kaiba::DomainError::NotFound { .. } => ApiError::not_found("Rei state not found"),
+            _ => ApiError::internal(e),
         })?;
 
-    Ok(Json(ReiStateResponse {
+    let rei_state = ReiStateResponse {
         energy_level: rei_state.energy_level,
         mood: rei_state.mood,
         token_budget: rei_state.token_budget,
@@ -339,7 +441,93 @@ pub async fn update_rei_state(
         energy_regen_per_hour: rei_state.energy_regen_per_hour,
         last_digest_at: rei_state.last_digest_at,
         last_learn_at: rei_state.last_learn_at,
-    }))
+        autonomy_enabled: rei_state.autonomy_enabled,
+    };
+    state
+        .event_bus
+        .emit(rei_state.changed_event(id, "state_updated"))
+        .await;
+    Ok(Json(rei_state))
+}
+
+/// Pause a Rei's autonomy
+///
+/// The scheduler and `/kaiba/trigger` leave a paused Rei alone; its
+/// schedule, policy and webhooks are kept for when it resumes.
+#[utoipa::path(
+    post,
+    path = "/kaiba/rei/{id}/autonomy/pause",
+    params(
+        ("id" = Uuid, Path, description = "Rei ID")
+    ),
+    responses(
+        (status = 200, description = "Autonomy paused", body = ReiStateResponse),
+        (status = 404, description = "Rei not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Rei"
+)]
+pub async fn pause_autonomy(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ReiStateResponse>, ApiError> {
+    set_autonomy(&state, id, false).await
+}
+
+/// Resume a Rei's autonomy
+#[utoipa::path(
+    post,
+    path = "/kaiba/rei/{id}/autonomy/resume",
+    params(
+        ("id" = Uuid, Path, description = "Rei ID")
+    ),
+    responses(
+        (status = 200, description = "Autonomy resumed", body = ReiStateResponse),
+        (status = 404, description = "Rei not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Rei"
+)]
+pub async fn resume_autonomy(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ReiStateResponse>, ApiError> {
+    set_autonomy(&state, id, true).await
+}
+
+async fn set_autonomy(
+    state: &AppState,
+    id: Uuid,
+    enabled: bool,
+) -> Result<Json<ReiStateResponse>, ApiError> {
+    let rei_state = state
+        .rei_service
+        .set_autonomy(id, enabled)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or(ApiError::not_found("Rei not found"))?;
+
+    let rei_state = ReiStateResponse {
+        energy_level: rei_state.energy_level,
+        mood: rei_state.mood,
+        token_budget: rei_state.token_budget,
+        tokens_used: rei_state.tokens_used,
+        last_active_at: rei_state.last_active_at,
+        energy_regen_per_hour: rei_state.energy_regen_per_hour,
+        last_digest_at: rei_state.last_digest_at,
+        last_learn_at: rei_state.last_learn_at,
+        autonomy_enabled: rei_state.autonomy_enabled,
+    };
+    let reason = if enabled {
+        "autonomy_resumed"
+    } else {
+        "autonomy_paused"
+    };
+    state
+        .event_bus
+        .emit(rei_state.changed_event(id, reason))
+        .await;
+    Ok(Json(rei_state))
 }
 
 pub fn router() -> Router<AppState> {
@@ -353,4 +541,7 @@ pub fn router() -> Router<AppState> {
             "/kaiba/rei/:id/state",
             get(get_rei_state).put(update_rei_state),
         )
+        .route("/kaiba/rei/:id/restore", post(restore_rei))
+        .route("/kaiba/rei/:id/autonomy/pause", post(pause_autonomy))
+        .route("/kaiba/rei/:id/autonomy/resume", post(resume_autonomy))
 }
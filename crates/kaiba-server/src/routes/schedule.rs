@@ -0,0 +1,149 @@
+//! Schedule Routes - When a Rei runs its autonomous cycle
+//!
+//! GET/PUT/DELETE /kaiba/rei/:rei_id/schedule
+//!
+//! A Rei with a schedule learns and digests at the times of its cron
+//! expression; without one it follows the global `LEARNING_INTERVAL_SECS`.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::get,
+    Json, Router,
+};
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::error::ApiError;
+use crate::models::{ReiSchedule, ScheduleResponse, UpsertScheduleRequest};
+use crate::services::schedule::CronSchedule;
+use crate::validation::ValidJson;
+use crate::AppState;
+
+/// Get a Rei's schedule
+#[utoipa::path(
+    get,
+    path = "/kaiba/rei/{rei_id}/schedule",
+    params(("rei_id" = Uuid, Path, description = "Rei ID")),
+    responses(
+        (status = 200, description = "Rei schedule", body = ScheduleResponse),
+        (status = 404, description = "Rei has no schedule"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Rei"
+)]
+pub async fn get_schedule(
+    State(state): State<AppState>,
+    Path(rei_id): Path<Uuid>,
+) -> Result<Json<ScheduleResponse>, ApiError> {
+    let schedule: ReiSchedule = sqlx::query_as("SELECT * FROM rei_schedules WHERE rei_id = $1")
+        .bind(rei_id)
+        .fetch_optional(&state.pool)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or(ApiError::not_found("Rei has no schedule"))?;
+
+    Ok(Json(schedule.into()))
+}
+
+/// Set (create or replace) a Rei's schedule
+#[utoipa::path(
+    put,
+    path = "/kaiba/rei/{rei_id}/schedule",
+    params(("rei_id" = Uuid, Path, description = "Rei ID")),
+    request_body = UpsertScheduleRequest,
+    responses(
+        (status = 200, description = "Schedule saved", body = ScheduleResponse),
+        (status = 404, description = "Rei not found"),
+        (status = 422, description = "Invalid cron expression or timezone"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Rei"
+)]
+pub async fn put_schedule(
+    State(state): State<AppState>,
+    Path(rei_id): Path<Uuid>,
+    ValidJson(payload): ValidJson<UpsertScheduleRequest>,
+) -> Result<Json<ScheduleResponse>, ApiError> {
+    state
+        .rei_service
+        .get_by_id(rei_id)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or(ApiError::not_found("Rei not found"))?;
+
+    let cron = payload
+        .cron
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let timezone = payload.timezone.unwrap_or_else(|| "UTC".to_string());
+    // Already checked by ValidJson
+    let next_run_at = CronSchedule::parse(&cron, &timezone)
+        .map_err(ApiError::bad_request)?
+        .next_after(Utc::now());
+
+    let saved: ReiSchedule = sqlx::query_as(
+        r#"
+        INSERT INTO rei_schedules (rei_id, cron, timezone, next_run_at)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (rei_id) DO UPDATE
+        SET cron = EXCLUDED.cron,
+            timezone = EXCLUDED.timezone,
+            next_run_at = EXCLUDED.next_run_at
+        RETURNING *
+        "#,
+    )
+    .bind(rei_id)
+    .bind(&cron)
+    .bind(&timezone)
+    .bind(next_run_at)
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    tracing::info!(
+        "📅 Schedule saved for Rei {}: {} ({})",
+        rei_id,
+        saved.cron,
+        saved.timezone
+    );
+
+    Ok(Json(saved.into()))
+}
+
+/// Remove a Rei's schedule (it returns to the global interval)
+#[utoipa::path(
+    delete,
+    path = "/kaiba/rei/{rei_id}/schedule",
+    params(("rei_id" = Uuid, Path, description = "Rei ID")),
+    responses(
+        (status = 204, description = "Schedule removed"),
+        (status = 404, description = "Rei has no schedule"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Rei"
+)]
+pub async fn delete_schedule(
+    State(state): State<AppState>,
+    Path(rei_id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let result = sqlx::query("DELETE FROM rei_schedules WHERE rei_id = $1")
+        .bind(rei_id)
+        .execute(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::not_found("Rei has no schedule"));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new().route(
+        "/kaiba/rei/:rei_id/schedule",
+        get(get_schedule).put(put_schedule).delete(delete_schedule),
+    )
+}
@@ -4,6 +4,7 @@ use axum::{extract::State, routing::post, Json, Router};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+use crate::error::ApiError;
 use crate::services::web_search::{WebSearchReference, WebSearchResponse};
 use crate::AppState;
 
@@ -46,16 +47,16 @@ impl From<WebSearchResponse> for SearchResult {
 pub async fn web_search(
     State(state): State<AppState>,
     Json(payload): Json<SearchRequest>,
-) -> Result<Json<SearchResult>, (axum::http::StatusCode, String)> {
-    let agent = state.web_search.as_ref().ok_or((
-        axum::http::StatusCode::SERVICE_UNAVAILABLE,
-        "WebSearch not available".to_string(),
-    ))?;
+) -> Result<Json<SearchResult>, ApiError> {
+    let agent = state
+        .web_search
+        .as_ref()
+        .ok_or(ApiError::unavailable("WebSearch not available"))?;
 
     let result = agent
         .search(&payload.query)
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .map_err(ApiError::internal)?;
 
     tracing::info!(
         "🔍 WebSearch: {} -> {} references",
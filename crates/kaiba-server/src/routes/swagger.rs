@@ -5,14 +5,48 @@
 use utoipa::OpenApi;
 
 use crate::models::{
+    // Auth models
+    ApiKeyResponse,
+    ApiKeyScope,
     AssociateTeiRequest,
+    // Audit models
+    AuditLog,
     CallContext,
     CallLog,
     CallRequest,
     CallResponse,
+    CreateApiKeyRequest,
+    CreateApiKeyResponse,
+    // Event registry models
+    CreateEventDefinitionRequest,
     CreateMemoryRequest,
     CreateReiRequest,
+    CreateTaskRequest,
     CreateTeiRequest,
+    CreateUserRequest,
+    // Webhook models
+    CreateWebhookRequest,
+    // Dashboard models
+    DashboardActivity,
+    DashboardReiInfo,
+    DashboardResponse,
+    DashboardState,
+    DashboardStats,
+    DashboardWebhooks,
+    DecisionLog,
+    DecisionPolicyResponse,
+    DecisionStrategyKind,
+    EmitEventRequest,
+    EmitEventResponse,
+    EventDefinitionResponse,
+    // Inbound models
+    InboundAction,
+    InboundResponse,
+    InboundSourceResponse,
+    // Integration models
+    IntegrationHealth,
+    LatencyBucketResponse,
+    LearningSessionLog,
     Memory,
     MemoryReference,
     MemoryResponse,
@@ -25,43 +59,82 @@ use crate::models::{
     Provider,
     // Rei models
     Rei,
+    ReiIntegrationResponse,
     ReiResponse,
+    ReiRunResult,
     ReiState,
     ReiStateResponse,
     ReiSummary,
+    ReiTask,
+    ScheduleResponse,
+    SchedulerRun,
     SearchMemoriesRequest,
     // Call models
+    TagMatchMode,
     TaskHealth,
     Tei,
     TeiResponse,
+    TriggerWebhookRequest,
+    UpdateEventDefinitionRequest,
+    UpdateMemoryRequest,
+    UpdateReiIntegrationRequest,
     UpdateReiRequest,
     UpdateReiStateRequest,
     UpdateTeiRequest,
+    UpdateWebhookRequest,
+    UpsertDecisionPolicyRequest,
+    UpsertInboundSourceRequest,
+    UpsertScheduleRequest,
+    User,
+    WebhookDeliveryResponse,
+    WebhookDryRunResponse,
+    WebhookResponse,
+    WebhookStatsResponse,
+    WebhookTlsRequest,
+    WebhookTlsSummary,
 };
 
-use crate::services::self_learning::LearningSession;
+use crate::bulk::{BulkItemResult, BulkResponse};
+use crate::config::RedactedConfig;
+use crate::error::{FieldError, Problem};
+use crate::services::digest::{DigestResult, DigestTier};
+use crate::services::seed::SeedResult;
+use crate::services::self_learning::{
+    LearningBudget, LearningMode, LearningSession, LearningTopic, LearningUsage,
+};
 use crate::services::web_search::WebSearchReference;
 
 // Local route types
 use super::learning::{
-    BatchLearnResponse, LearnRequest, LearnResponse, RechargeRequest, RechargeResponse,
+    BatchLearnResponse, DigestResponse, LearnRequest, LearnResponse, LearningBudgetResponse,
+    RechargeRequest, RechargeResponse,
 };
 use super::search::{SearchRequest, SearchResult};
+use super::trigger::{TriggerRequest, TriggerResponse, TriggerSummary};
+use crate::{HealthCheck, HealthServices};
 
 #[derive(OpenApi)]
 #[openapi(
     paths(
+        // Health
+        crate::health_check,
         // Rei endpoints
         super::rei::list_reis,
         super::rei::create_rei,
         super::rei::get_rei,
         super::rei::update_rei,
         super::rei::delete_rei,
+        super::rei::restore_rei,
         super::rei::get_rei_state,
         super::rei::update_rei_state,
+        super::rei::pause_autonomy,
+        super::rei::resume_autonomy,
+        super::event_stream::stream_events,
+        super::dashboard::get_dashboard,
         // Tei endpoints
         super::tei::list_teis,
         super::tei::create_tei,
+        super::tei::bulk_create_teis,
         super::tei::get_tei,
         super::tei::update_tei,
         super::tei::delete_tei,
@@ -72,10 +145,62 @@ use super::search::{SearchRequest, SearchResult};
         super::tei::disassociate_tei,
         // Memory endpoints
         super::memory::add_memory,
+        super::memory::list_memories,
+        super::memory::get_memory,
+        super::memory::update_memory,
         super::memory::search_memories,
         // Call endpoints
         super::call::call_llm,
         super::call::get_call_history,
+        // Webhook endpoints
+        super::webhook::list_webhooks,
+        super::webhook::create_webhook,
+        super::webhook::bulk_create_webhooks,
+        super::webhook::get_webhook,
+        super::webhook::update_webhook,
+        super::webhook::delete_webhook,
+        super::webhook::trigger_webhook,
+        super::webhook::list_deliveries,
+        super::webhook::get_webhook_stats,
+        super::webhook::replay_delivery,
+        super::global_webhook::list_global_webhooks,
+        super::global_webhook::create_global_webhook,
+        super::global_webhook::get_global_webhook,
+        super::global_webhook::update_global_webhook,
+        super::global_webhook::delete_global_webhook,
+        // Event registry endpoints
+        super::event_registry::list_event_definitions,
+        super::event_registry::create_event_definition,
+        super::event_registry::get_event_definition,
+        super::event_registry::update_event_definition,
+        super::event_registry::delete_event_definition,
+        super::event_registry::emit_event,
+        // Inbound endpoints
+        super::inbound::list_sources,
+        super::inbound::upsert_source,
+        super::inbound::delete_source,
+        super::inbound::receive_inbound,
+        super::discord::receive_interaction,
+        super::github::receive_delivery,
+        super::telegram::receive_update,
+        // Integration endpoints
+        super::integration::list_integrations,
+        super::integration::list_rei_integrations,
+        super::integration::enable_integration,
+        super::integration::disable_integration,
+        super::integration::update_integration,
+        // Schedule endpoints
+        super::schedule::get_schedule,
+        super::schedule::put_schedule,
+        super::schedule::delete_schedule,
+        super::task::create_task,
+        super::task::list_tasks,
+        super::task::get_task,
+        super::task::delete_task,
+        super::decision::get_decision_policy,
+        super::decision::put_decision_policy,
+        super::decision::delete_decision_policy,
+        super::decision::get_decisions,
         // Prompt endpoints
         super::prompt::generate_prompt,
         // Search endpoints
@@ -83,7 +208,24 @@ use super::search::{SearchRequest, SearchResult};
         // Learning endpoints
         super::learning::learn_rei,
         super::learning::learn_all,
+        super::learning::digest_rei,
+        super::learning::get_learning_history,
+        super::learning::get_learning_budget,
+        super::learning::get_learning_topics,
         super::learning::recharge_rei,
+        super::trigger::trigger_jobs,
+        // Auth endpoints
+        super::api_key::list_api_keys,
+        super::api_key::create_api_key,
+        super::api_key::revoke_api_key,
+        super::user::list_users,
+        super::user::create_user,
+        super::user::delete_user,
+        // Admin endpoints
+        super::admin::get_config,
+        super::admin::list_audit_logs,
+        super::admin::list_scheduler_runs,
+        super::admin::seed_demo,
     ),
     info(
         title = "Kaiba API",
@@ -103,9 +245,30 @@ use super::search::{SearchRequest, SearchResult};
         (name = "Prompt", description = "Prompt - Generate prompts for external Teis"),
         (name = "Search", description = "Search - Web search via Gemini"),
         (name = "Learning", description = "Learning - Autonomous self-learning"),
+        (name = "Trigger", description = "Trigger - Run pending learn/digest/reflect jobs"),
+        (name = "Dashboard", description = "Dashboard - Rei overview for UIs"),
+        (name = "Webhook", description = "Webhook - Outbound event delivery and custom events"),
+        (name = "Inbound", description = "Inbound - Signed payloads from external services"),
+        (name = "Integration", description = "Integration - Chat platforms answering for Reis"),
+        (name = "Auth", description = "Auth - Scoped API keys and users"),
+        (name = "Admin", description = "Admin - Operator inspection"),
     ),
     components(
         schemas(
+            // Errors (application/problem+json)
+            Problem,
+            FieldError,
+            // Bulk operations
+            BulkResponse,
+            BulkItemResult,
+            // Health
+            HealthCheck,
+            HealthServices,
+            // Admin
+            RedactedConfig,
+            AuditLog,
+            SchedulerRun,
+            SeedResult,
             // Rei
             Rei,
             ReiState,
@@ -114,6 +277,13 @@ use super::search::{SearchRequest, SearchResult};
             ReiResponse,
             ReiStateResponse,
             UpdateReiStateRequest,
+            // Dashboard
+            DashboardResponse,
+            DashboardReiInfo,
+            DashboardState,
+            DashboardActivity,
+            DashboardStats,
+            DashboardWebhooks,
             // Tei
             Provider,
             Tei,
@@ -126,6 +296,8 @@ use super::search::{SearchRequest, SearchResult};
             Memory,
             CreateMemoryRequest,
             SearchMemoriesRequest,
+            TagMatchMode,
+            UpdateMemoryRequest,
             MemoryResponse,
             // Call
             TaskHealth,
@@ -146,10 +318,199 @@ use super::search::{SearchRequest, SearchResult};
             LearnRequest,
             LearnResponse,
             BatchLearnResponse,
+            DigestResponse,
+            DigestResult,
+            DigestTier,
             RechargeRequest,
             RechargeResponse,
             LearningSession,
+            LearningMode,
+            LearningSessionLog,
+            LearningBudget,
+            LearningUsage,
+            LearningBudgetResponse,
+            LearningTopic,
+            // Trigger
+            TriggerRequest,
+            TriggerResponse,
+            ReiRunResult,
+            TriggerSummary,
+            // Webhook
+            CreateWebhookRequest,
+            UpdateWebhookRequest,
+            WebhookTlsRequest,
+            WebhookTlsSummary,
+            WebhookResponse,
+            TriggerWebhookRequest,
+            WebhookDryRunResponse,
+            WebhookDeliveryResponse,
+            WebhookStatsResponse,
+            LatencyBucketResponse,
+            // Event registry
+            CreateEventDefinitionRequest,
+            UpdateEventDefinitionRequest,
+            EventDefinitionResponse,
+            EmitEventRequest,
+            EmitEventResponse,
+            // Inbound
+            InboundAction,
+            UpsertInboundSourceRequest,
+            InboundSourceResponse,
+            InboundResponse,
+            // Integrations
+            IntegrationHealth,
+            ReiIntegrationResponse,
+            UpdateReiIntegrationRequest,
+            // Schedules
+            UpsertScheduleRequest,
+            ScheduleResponse,
+            // Tasks
+            CreateTaskRequest,
+            ReiTask,
+            // Decisions
+            DecisionStrategyKind,
+            UpsertDecisionPolicyRequest,
+            DecisionPolicyResponse,
+            DecisionLog,
+            // Auth
+            ApiKeyScope,
+            CreateApiKeyRequest,
+            ApiKeyResponse,
+            CreateApiKeyResponse,
+            User,
+            CreateUserRequest,
         )
     ),
 )]
 pub struct ApiDoc;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sources of every router, to find the routes they serve
+    const ROUTER_SOURCES: [&str; 25] = [
+        include_str!("../main.rs"),
+        include_str!("admin.rs"),
+        include_str!("api_key.rs"),
+        include_str!("call.rs"),
+        include_str!("dashboard.rs"),
+        include_str!("decision.rs"),
+        include_str!("discord.rs"),
+        include_str!("event_registry.rs"),
+        include_str!("event_stream.rs"),
+        include_str!("github.rs"),
+        include_str!("global_webhook.rs"),
+        include_str!("inbound.rs"),
+        include_str!("integration.rs"),
+        include_str!("learning.rs"),
+        include_str!("memory.rs"),
+        include_str!("prompt.rs"),
+        include_str!("rei.rs"),
+        include_str!("schedule.rs"),
+        include_str!("search.rs"),
+        include_str!("task.rs"),
+        include_str!("telegram.rs"),
+        include_str!("tei.rs"),
+        include_str!("trigger.rs"),
+        include_str!("user.rs"),
+        include_str!("webhook.rs"),
+    ];
+
+    /// Committed spec for client generation; `UPDATE_OPENAPI=1` rewrites it
+    const SNAPSHOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/openapi.json");
+
+    /// `.route("/kaiba/rei/:id", ...)` paths, in OpenAPI form (`/kaiba/rei/{id}`)
+    fn served_paths() -> Vec<String> {
+        let mut paths = Vec::new();
+        for source in ROUTER_SOURCES {
+            for (i, _) in source.match_indices(".route(") {
+                let rest = &source[i..];
+                let Some(start) = rest.find('"') else {
+                    continue;
+                };
+                let Some(len) = rest[start + 1..].find('"') else {
+                    continue;
+                };
+                let path = &rest[start + 1..start + 1 + len];
+                let path = path
+                    .split('/')
+                    .map(|s| match s.strip_prefix(':') {
+                        Some(param) => format!("{{{}}}", param),
+                        None => s.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("/");
+                paths.push(path);
+            }
+        }
+        paths
+    }
+
+    fn collect_refs(value: &serde_json::Value, refs: &mut Vec<String>) {
+        match value {
+            serde_json::Value::Object(map) => {
+                for (key, value) in map {
+                    match (key.as_str(), value.as_str()) {
+                        ("$ref", Some(reference)) => refs.push(reference.to_string()),
+                        _ => collect_refs(value, refs),
+                    }
+                }
+            }
+            serde_json::Value::Array(items) => {
+                items.iter().for_each(|v| collect_refs(v, refs));
+            }
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn test_every_route_is_documented() {
+        let doc = ApiDoc::openapi();
+        let served = served_paths();
+        assert!(served.len() > 30, "route scan found too few routes");
+
+        let missing: Vec<_> = served
+            .iter()
+            .filter(|path| !doc.paths.paths.contains_key(path.as_str()))
+            .collect();
+        assert!(
+            missing.is_empty(),
+            "routes missing from ApiDoc: {:?}",
+            missing
+        );
+    }
+
+    #[test]
+    fn test_every_schema_reference_is_registered() {
+        let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let mut refs = Vec::new();
+        collect_refs(&spec, &mut refs);
+
+        let schemas = &spec["components"]["schemas"];
+        let missing: Vec<_> = refs
+            .iter()
+            .filter_map(|r| r.strip_prefix("#/components/schemas/"))
+            .filter(|name| schemas.get(name).is_none())
+            .collect();
+        assert!(
+            missing.is_empty(),
+            "schemas missing from ApiDoc: {:?}",
+            missing
+        );
+    }
+
+    #[test]
+    fn test_openapi_snapshot() {
+        let spec = ApiDoc::openapi().to_pretty_json().unwrap() + "\n";
+        let update = std::env::var("UPDATE_OPENAPI").is_ok_and(|v| v == "1");
+
+        match std::fs::read_to_string(SNAPSHOT) {
+            Ok(committed) if !update => assert!(
+                committed == spec,
+                "openapi.json is out of date; run `UPDATE_OPENAPI=1 cargo test -p kaiba-server openapi` and commit it"
+            ),
+            _ => std::fs::write(SNAPSHOT, spec).unwrap(),
+        }
+    }
+}
@@ -0,0 +1,235 @@
+//! Task Routes - One-off instructions carried out at a given time
+//!
+//! POST/GET /kaiba/rei/:rei_id/tasks
+//! GET/DELETE /kaiba/rei/:rei_id/tasks/:task_id
+//!
+//! When a task comes due, the task runner sends its instruction through the
+//! call pipeline and delivers the response as a `task_completed` event.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Response,
+    routing::get,
+    Json, Router,
+};
+use uuid::Uuid;
+
+use crate::error::ApiError;
+use crate::models::{CreateTaskRequest, ReiTask, TaskQuery};
+use crate::pagination::{Page, PageQuery, Pagination, Sort};
+use crate::rest;
+use crate::services::task_runner::TASK_STATUSES;
+use crate::validation::ValidJson;
+use crate::AppState;
+
+/// Schedule a task for a Rei
+#[utoipa::path(
+    post,
+    path = "/kaiba/rei/{rei_id}/tasks",
+    params(("rei_id" = Uuid, Path, description = "Rei ID")),
+    request_body = CreateTaskRequest,
+    responses(
+        (status = 201, description = "Task scheduled", body = ReiTask),
+        (status = 404, description = "Rei not found"),
+        (status = 422, description = "Empty instruction"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Rei"
+)]
+pub async fn create_task(
+    State(state): State<AppState>,
+    Path(rei_id): Path<Uuid>,
+    ValidJson(payload): ValidJson<CreateTaskRequest>,
+) -> Result<Response, ApiError> {
+    state
+        .rei_service
+        .get_by_id(rei_id)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or(ApiError::not_found("Rei not found"))?;
+
+    let task: ReiTask = sqlx::query_as(
+        r#"
+        INSERT INTO rei_tasks (rei_id, instruction, include_memories, due_at)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *
+        "#,
+    )
+    .bind(rei_id)
+    .bind(payload.instruction.trim())
+    .bind(payload.include_memories.unwrap_or(true))
+    .bind(payload.due_at)
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    tracing::info!(
+        "⏰ Task {} scheduled for Rei {} at {}",
+        task.id,
+        rei_id,
+        task.due_at
+    );
+
+    let location = format!("/kaiba/rei/{}/tasks/{}", rei_id, task.id);
+    Ok(rest::created(location, task))
+}
+
+/// List a Rei's tasks
+#[utoipa::path(
+    get,
+    path = "/kaiba/rei/{rei_id}/tasks",
+    params(
+        ("rei_id" = Uuid, Path, description = "Rei ID"),
+        TaskQuery,
+        PageQuery
+    ),
+    responses(
+        (status = 200, description = "Page of tasks (soonest due first by default)", body = Vec<ReiTask>,
+            headers(("x-total-count" = i64, description = "Tasks matching the filters"))),
+        (status = 400, description = "Unknown task status"),
+        (status = 422, description = "Invalid paging or sort parameters"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Rei"
+)]
+pub async fn list_tasks(
+    State(state): State<AppState>,
+    Path(rei_id): Path<Uuid>,
+    pagination: Pagination,
+    Query(query): Query<TaskQuery>,
+) -> Result<Page<ReiTask>, ApiError> {
+    let sort = pagination.sort_or(&["due_at", "created_at"], Sort::asc("due_at"))?;
+    // Only whitelisted columns reach the query
+    let order_by = match (sort.field.as_str(), sort.descending) {
+        ("created_at", true) => "created_at DESC",
+        ("created_at", false) => "created_at ASC",
+        (_, true) => "due_at DESC, created_at DESC",
+        (_, false) => "due_at ASC, created_at ASC",
+    };
+
+    if let Some(status) = query.status.as_deref() {
+        if !TASK_STATUSES.contains(&status) {
+            return Err(ApiError::bad_request(format!(
+                "Unknown task status: {} (expected {})",
+                status,
+                TASK_STATUSES.join(", ")
+            )));
+        }
+    }
+
+    let filter = r#"
+        WHERE rei_id = $1
+          AND ($2::text IS NULL OR status = $2)
+    "#;
+
+    let total: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM rei_tasks {}", filter))
+        .bind(rei_id)
+        .bind(&query.status)
+        .fetch_one(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+
+    let items = sqlx::query_as::<_, ReiTask>(&format!(
+        "SELECT * FROM rei_tasks {} ORDER BY {} LIMIT $3 OFFSET $4",
+        filter, order_by
+    ))
+    .bind(rei_id)
+    .bind(&query.status)
+    .bind(pagination.limit)
+    .bind(pagination.offset)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    Ok(Page { items, total })
+}
+
+/// Get a task, with its result once it has run
+#[utoipa::path(
+    get,
+    path = "/kaiba/rei/{rei_id}/tasks/{task_id}",
+    params(
+        ("rei_id" = Uuid, Path, description = "Rei ID"),
+        ("task_id" = Uuid, Path, description = "Task ID")
+    ),
+    responses(
+        (status = 200, description = "Task", body = ReiTask),
+        (status = 404, description = "Task not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Rei"
+)]
+pub async fn get_task(
+    State(state): State<AppState>,
+    Path((rei_id, task_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ReiTask>, ApiError> {
+    let task: ReiTask = sqlx::query_as("SELECT * FROM rei_tasks WHERE id = $1 AND rei_id = $2")
+        .bind(task_id)
+        .bind(rei_id)
+        .fetch_optional(&state.pool)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or(ApiError::not_found("Task not found"))?;
+
+    Ok(Json(task))
+}
+
+/// Cancel a pending task, or remove a finished one from the history
+#[utoipa::path(
+    delete,
+    path = "/kaiba/rei/{rei_id}/tasks/{task_id}",
+    params(
+        ("rei_id" = Uuid, Path, description = "Rei ID"),
+        ("task_id" = Uuid, Path, description = "Task ID")
+    ),
+    responses(
+        (status = 204, description = "Task deleted"),
+        (status = 404, description = "Task not found"),
+        (status = 409, description = "Task is running"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Rei"
+)]
+pub async fn delete_task(
+    State(state): State<AppState>,
+    Path((rei_id, task_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, ApiError> {
+    let status: String =
+        sqlx::query_scalar("SELECT status FROM rei_tasks WHERE id = $1 AND rei_id = $2")
+            .bind(task_id)
+            .bind(rei_id)
+            .fetch_optional(&state.pool)
+            .await
+            .map_err(ApiError::internal)?
+            .ok_or(ApiError::not_found("Task not found"))?;
+
+    if status == "running" {
+        return Err(ApiError::conflict("Task is running"));
+    }
+
+    // A task picked up in the meantime is left alone
+    let result = sqlx::query("DELETE FROM rei_tasks WHERE id = $1 AND status <> 'running'")
+        .bind(task_id)
+        .execute(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::conflict("Task is running"));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/kaiba/rei/:rei_id/tasks",
+            get(list_tasks).post(create_task),
+        )
+        .route(
+            "/kaiba/rei/:rei_id/tasks/:task_id",
+            get(get_task).delete(delete_task),
+        )
+}
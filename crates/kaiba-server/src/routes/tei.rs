@@ -3,15 +3,25 @@
 //! HTTP handlers that delegate to TeiService for business logic.
 
 use axum::{
-    extract::{Path, State},
-    routing::{delete, get},
-    Json, Router,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::Response,
+    routing::{delete, get, post},
+    Extension, Json, Router,
 };
 use uuid::Uuid;
 
+use crate::auth::Principal;
+use crate::bulk::{BulkJson, BulkResponse};
+use crate::error::ApiError;
+use crate::etag;
 use crate::models::{
-    AssociateTeiRequest, CreateTeiRequest, Provider, TeiResponse, UpdateTeiRequest,
+    AssociateTeiRequest, CreateTeiRequest, ListTeisQuery, Provider, TeiResponse, UpdateTeiRequest,
 };
+use crate::pagination::{Page, PageQuery, Pagination, Sort};
+use crate::rest;
+use crate::services::ownership::{self, Owned};
+use crate::validation::ValidJson;
 use crate::AppState;
 
 /// Convert DTO Provider to domain Provider
@@ -23,27 +33,48 @@ fn to_domain_provider(p: Provider) -> kaiba::Provider {
     }
 }
 
-/// List all Teis
+/// List Teis (only the caller's own for user-bound keys)
 #[utoipa::path(
     get,
     path = "/kaiba/tei",
+    params(ListTeisQuery, PageQuery),
     responses(
-        (status = 200, description = "List of all Teis", body = Vec<TeiResponse>),
+        (status = 200, description = "Page of Teis", body = Vec<TeiResponse>,
+            headers(("x-total-count" = i64, description = "Teis matching the filters"))),
+        (status = 422, description = "Invalid paging or sort parameters"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Tei"
 )]
 pub async fn list_teis(
     State(state): State<AppState>,
-) -> Result<Json<Vec<TeiResponse>>, (axum::http::StatusCode, String)> {
+    principal: Option<Extension<Principal>>,
+    pagination: Pagination,
+    Query(filter): Query<ListTeisQuery>,
+) -> Result<Page<TeiResponse>, ApiError> {
+    let sort = pagination.sort_or(
+        &["name", "priority", "created_at", "updated_at"],
+        Sort::asc("priority"),
+    )?;
+
+    let visible = ownership::visible(
+        &state.pool,
+        Owned::Tei,
+        principal.and_then(|Extension(p)| p.owner()),
+    )
+    .await
+    .map_err(ApiError::internal)?;
+
     let teis = state
         .tei_service
         .list_all()
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .map_err(ApiError::internal)?;
 
-    let responses: Vec<TeiResponse> = teis
+    let name = filter.name.map(|n| n.to_lowercase());
+    let mut responses: Vec<TeiResponse> = teis
         .into_iter()
+        .filter(|tei| visible.contains(tei.id))
         .map(|tei| TeiResponse {
             id: tei.id,
             name: tei.name,
@@ -56,26 +87,90 @@ pub async fn list_teis(
             created_at: tei.created_at,
             updated_at: tei.updated_at,
         })
+        .filter(|tei| match &name {
+            Some(name) => tei.name.to_lowercase().contains(name),
+            None => true,
+        })
+        .filter(|tei| match &filter.provider {
+            Some(provider) => tei.provider.eq_ignore_ascii_case(provider),
+            None => true,
+        })
         .collect();
 
-    Ok(Json(responses))
+    // Stable sort, so equal priorities keep the newest-first order
+    responses.sort_by(|a, b| {
+        let ordering = match sort.field.as_str() {
+            "name" => a.name.cmp(&b.name),
+            "created_at" => a.created_at.cmp(&b.created_at),
+            "updated_at" => a.updated_at.cmp(&b.updated_at),
+            _ => a.priority.cmp(&b.priority),
+        };
+        if sort.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+
+    Ok(pagination.page(responses))
 }
 
-/// Create new Tei
+/// Create new Tei (owned by the caller's user, if any)
 #[utoipa::path(
     post,
     path = "/kaiba/tei",
     request_body = CreateTeiRequest,
     responses(
-        (status = 200, description = "Tei created", body = TeiResponse),
+        (status = 201, description = "Tei created", body = TeiResponse,
+            headers(("location" = String, description = "URL of the new Tei"))),
         (status = 500, description = "Internal server error")
     ),
     tag = "Tei"
 )]
 pub async fn create_tei(
     State(state): State<AppState>,
-    Json(payload): Json<CreateTeiRequest>,
-) -> Result<Json<TeiResponse>, (axum::http::StatusCode, String)> {
+    principal: Option<Extension<Principal>>,
+    ValidJson(payload): ValidJson<CreateTeiRequest>,
+) -> Result<Response, ApiError> {
+    let tei = create_one(&state, principal.as_ref(), payload).await?;
+    Ok(rest::created(format!("/kaiba/tei/{}", tei.id), tei))
+}
+
+/// Create many Teis in one request
+#[utoipa::path(
+    post,
+    path = "/kaiba/tei/bulk",
+    request_body = Vec<CreateTeiRequest>,
+    responses(
+        (status = 200, description = "One result per item, in request order", body = BulkResponse),
+        (status = 400, description = "Empty request or too many items")
+    ),
+    tag = "Tei"
+)]
+pub async fn bulk_create_teis(
+    State(state): State<AppState>,
+    principal: Option<Extension<Principal>>,
+    BulkJson(items): BulkJson<CreateTeiRequest>,
+) -> Json<BulkResponse> {
+    let mut response = BulkResponse::default();
+    for item in items {
+        let created = match item {
+            Ok(payload) => create_one(&state, principal.as_ref(), payload).await,
+            Err(err) => Err(err),
+        };
+        match created {
+            Ok(tei) => response.created(format!("/kaiba/tei/{}", tei.id), tei),
+            Err(err) => response.failed(err),
+        }
+    }
+    Json(response)
+}
+
+async fn create_one(
+    state: &AppState,
+    principal: Option<&Extension<Principal>>,
+    payload: CreateTeiRequest,
+) -> Result<TeiResponse, ApiError> {
     let tei = state
         .tei_service
         .create(
@@ -86,11 +181,12 @@ pub async fn create_tei(
             payload.priority,
             payload.config,
             payload.expertise,
+            principal.and_then(|Extension(p)| p.owner()),
         )
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .map_err(ApiError::internal)?;
 
-    Ok(Json(TeiResponse {
+    Ok(TeiResponse {
         id: tei.id,
         name: tei.name,
         provider: tei.provider,
@@ -101,7 +197,23 @@ pub async fn create_tei(
         expertise: tei.expertise,
         created_at: tei.created_at,
         updated_at: tei.updated_at,
-    }))
+    })
+}
+
+/// A Tei as GET renders it; `If-Match` ETags are checked against this
+fn tei_response(tei: kaiba::Tei) -> TeiResponse {
+    TeiResponse {
+        id: tei.id,
+        name: tei.name,
+        provider: tei.provider,
+        model_id: tei.model_id,
+        is_fallback: tei.is_fallback,
+        priority: tei.priority,
+        config: tei.config,
+        expertise: tei.expertise,
+        created_at: tei.created_at,
+        updated_at: tei.updated_at,
+    }
 }
 
 /// Get Tei by ID
@@ -110,7 +222,9 @@ pub async fn create_tei(
     path = "/kaiba/tei/{id}",
     params(("id" = Uuid, Path, description = "Tei ID")),
     responses(
-        (status = 200, description = "Tei found", body = TeiResponse),
+        (status = 200, description = "Tei found", body = TeiResponse,
+            headers(("etag" = String, description = "Changes whenever the Tei does"))),
+        (status = 304, description = "Unchanged since the `If-None-Match` ETag"),
         (status = 404, description = "Tei not found"),
         (status = 500, description = "Internal server error")
     ),
@@ -119,40 +233,31 @@ pub async fn create_tei(
 pub async fn get_tei(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
-) -> Result<Json<TeiResponse>, (axum::http::StatusCode, String)> {
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
     let tei = state
         .tei_service
         .get_by_id(id)
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
-        .ok_or((
-            axum::http::StatusCode::NOT_FOUND,
-            "Tei not found".to_string(),
-        ))?;
+        .map_err(ApiError::internal)?
+        .ok_or(ApiError::not_found("Tei not found"))?;
 
-    Ok(Json(TeiResponse {
-        id: tei.id,
-        name: tei.name,
-        provider: tei.provider,
-        model_id: tei.model_id,
-        is_fallback: tei.is_fallback,
-        priority: tei.priority,
-        config: tei.config,
-        expertise: tei.expertise,
-        created_at: tei.created_at,
-        updated_at: tei.updated_at,
-    }))
+    etag::json_with_etag(&headers, &tei_response(tei))
 }
 
 /// Update Tei
 #[utoipa::path(
     put,
     path = "/kaiba/tei/{id}",
-    params(("id" = Uuid, Path, description = "Tei ID")),
+    params(
+        ("id" = Uuid, Path, description = "Tei ID"),
+        ("If-Match" = Option<String>, Header, description = "ETag (or updated_at) of the version being edited; 409 if it has changed")
+    ),
     request_body = UpdateTeiRequest,
     responses(
         (status = 200, description = "Tei updated", body = TeiResponse),
         (status = 404, description = "Tei not found"),
+        (status = 409, description = "Changed since the `If-Match` version"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Tei"
@@ -160,8 +265,18 @@ pub async fn get_tei(
 pub async fn update_tei(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
-    Json(payload): Json<UpdateTeiRequest>,
-) -> Result<Json<TeiResponse>, (axum::http::StatusCode, String)> {
+    headers: HeaderMap,
+    ValidJson(payload): ValidJson<UpdateTeiRequest>,
+) -> Result<Json<TeiResponse>, ApiError> {
+    let current = state
+        .tei_service
+        .get_by_id(id)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or(ApiError::not_found("Tei not found"))?;
+    let updated_at = current.updated_at;
+    let expected = etag::if_match_version(&headers, &tei_response(current), updated_at)?;
+
     let tei = state
         .tei_service
         .update(
@@ -173,28 +288,18 @@ pub async fn update_tei(
             payload.priority,
             payload.config,
             payload.expertise,
+            expected,
         )
         .await
         .map_err(|e| match e {
-            kaiba::DomainError::NotFound { .. } => (
-                axum::http::StatusCode::NOT_FOUND,
-                "Tei not found".to_string(),
-            ),
-            _ => (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+            kaiba::DomainError::NotFound { .. } => ApiError::not_found("Tei not found"),
+            kaiba::DomainError::Conflict(detail) => {
+                ApiError::conflict(detail).with_code("edit_conflict")
+            }
+            _ => ApiError::internal(e),
         })?;
 
-    Ok(Json(TeiResponse {
-        id: tei.id,
-        name: tei.name,
-        provider: tei.provider,
-        model_id: tei.model_id,
-        is_fallback: tei.is_fallback,
-        priority: tei.priority,
-        config: tei.config,
-        expertise: tei.expertise,
-        created_at: tei.created_at,
-        updated_at: tei.updated_at,
-    }))
+    Ok(Json(tei_response(tei)))
 }
 
 /// Delete Tei
@@ -203,7 +308,7 @@ pub async fn update_tei(
     path = "/kaiba/tei/{id}",
     params(("id" = Uuid, Path, description = "Tei ID")),
     responses(
-        (status = 200, description = "Tei deleted"),
+        (status = 204, description = "Tei deleted"),
         (status = 404, description = "Tei not found"),
         (status = 500, description = "Internal server error")
     ),
@@ -212,24 +317,18 @@ pub async fn update_tei(
 pub async fn delete_tei(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
-) -> Result<Json<serde_json::Value>, (axum::http::StatusCode, String)> {
+) -> Result<StatusCode, ApiError> {
     let deleted = state
         .tei_service
         .delete(id)
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .map_err(ApiError::internal)?;
 
     if !deleted {
-        return Err((
-            axum::http::StatusCode::NOT_FOUND,
-            "Tei not found".to_string(),
-        ));
+        return Err(ApiError::not_found("Tei not found"));
     }
 
-    Ok(Json(serde_json::json!({
-        "status": "ok",
-        "message": "Tei deleted"
-    })))
+    Ok(StatusCode::NO_CONTENT)
 }
 
 /// Get Tei expertise
@@ -247,12 +346,12 @@ pub async fn delete_tei(
 pub async fn get_tei_expertise(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
-) -> Result<Json<serde_json::Value>, (axum::http::StatusCode, String)> {
+) -> Result<Json<serde_json::Value>, ApiError> {
     let expertise = state
         .tei_service
         .get_expertise(id)
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .map_err(ApiError::internal)?;
 
     Ok(Json(expertise.unwrap_or(serde_json::json!(null))))
 }
@@ -273,17 +372,14 @@ pub async fn update_tei_expertise(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
     Json(expertise): Json<serde_json::Value>,
-) -> Result<Json<serde_json::Value>, (axum::http::StatusCode, String)> {
+) -> Result<Json<serde_json::Value>, ApiError> {
     let result = state
         .tei_service
         .update_expertise(id, expertise)
         .await
         .map_err(|e| match e {
-            kaiba::DomainError::NotFound { .. } => (
-                axum::http::StatusCode::NOT_FOUND,
-                "Tei not found".to_string(),
-            ),
-            _ => (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+            kaiba::DomainError::NotFound { .. } => ApiError::not_found("Tei not found"),
+            _ => ApiError::internal(e),
         })?;
 
     Ok(Json(result))
@@ -307,12 +403,12 @@ pub async fn update_tei_expertise(
 pub async fn list_rei_teis(
     State(state): State<AppState>,
     Path(rei_id): Path<Uuid>,
-) -> Result<Json<Vec<TeiResponse>>, (axum::http::StatusCode, String)> {
+) -> Result<Json<Vec<TeiResponse>>, ApiError> {
     let teis = state
         .tei_service
         .list_by_rei(rei_id)
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .map_err(ApiError::internal)?;
 
     let responses: Vec<TeiResponse> = teis
         .into_iter()
@@ -348,19 +444,29 @@ pub async fn list_rei_teis(
 )]
 pub async fn associate_tei(
     State(state): State<AppState>,
+    principal: Option<Extension<Principal>>,
     Path(rei_id): Path<Uuid>,
     Json(payload): Json<AssociateTeiRequest>,
-) -> Result<Json<serde_json::Value>, (axum::http::StatusCode, String)> {
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // The Rei was checked by the auth middleware; the Tei comes from the body
+    if let Some(user_id) = principal.and_then(|Extension(p)| p.owner()) {
+        let owns = ownership::is_owner(&state.pool, Owned::Tei, payload.tei_id, user_id)
+            .await
+            .map_err(ApiError::internal)?;
+        if !owns {
+            return Err(ApiError::not_found("Tei not found"));
+        }
+    }
+
     state
         .tei_service
         .associate(rei_id, payload.tei_id)
         .await
         .map_err(|e| match e {
-            kaiba::DomainError::NotFound { entity_type, .. } => (
-                axum::http::StatusCode::NOT_FOUND,
-                format!("{} not found", entity_type),
-            ),
-            _ => (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+            kaiba::DomainError::NotFound { entity_type, .. } => {
+                ApiError::not_found(format!("{} not found", entity_type))
+            }
+            _ => ApiError::internal(e),
         })?;
 
     Ok(Json(serde_json::json!({
@@ -378,7 +484,7 @@ pub async fn associate_tei(
         ("tei_id" = Uuid, Path, description = "Tei ID")
     ),
     responses(
-        (status = 200, description = "Tei disassociated"),
+        (status = 204, description = "Tei disassociated"),
         (status = 404, description = "Association not found"),
         (status = 500, description = "Internal server error")
     ),
@@ -387,30 +493,25 @@ pub async fn associate_tei(
 pub async fn disassociate_tei(
     State(state): State<AppState>,
     Path((rei_id, tei_id)): Path<(Uuid, Uuid)>,
-) -> Result<Json<serde_json::Value>, (axum::http::StatusCode, String)> {
+) -> Result<StatusCode, ApiError> {
     let removed = state
         .tei_service
         .disassociate(rei_id, tei_id)
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .map_err(ApiError::internal)?;
 
     if !removed {
-        return Err((
-            axum::http::StatusCode::NOT_FOUND,
-            "Association not found".to_string(),
-        ));
+        return Err(ApiError::not_found("Association not found"));
     }
 
-    Ok(Json(serde_json::json!({
-        "status": "ok",
-        "message": "Tei disassociated from Rei"
-    })))
+    Ok(StatusCode::NO_CONTENT)
 }
 
 pub fn router() -> Router<AppState> {
     Router::new()
         // Tei CRUD
         .route("/kaiba/tei", get(list_teis).post(create_tei))
+        .route("/kaiba/tei/bulk", post(bulk_create_teis))
         .route(
             "/kaiba/tei/:id",
             get(get_tei).put(update_tei).delete(delete_tei),
@@ -0,0 +1,63 @@
+//! Telegram Routes - Bot updates by webhook
+//!
+//! POST /kaiba/integrations/telegram/webhook - Update webhook (public, verified by secret)
+//!
+//! Used when `TELEGRAM_WEBHOOK_SECRET` is set; register this URL with
+//! `setWebhook` and the same `secret_token`. Without a secret the server
+//! long-polls updates instead. Updates are acknowledged at once and answered
+//! in the background, so Telegram does not retry slow replies.
+
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    routing::post,
+    Router,
+};
+
+use crate::auth;
+use crate::error::ApiError;
+use crate::services::integration_call::IntegrationCallHandler;
+use crate::AppState;
+
+/// Receive a Telegram update
+#[utoipa::path(
+    post,
+    path = "/kaiba/integrations/telegram/webhook",
+    request_body = serde_json::Value,
+    responses(
+        (status = 200, description = "Update accepted"),
+        (status = 400, description = "Invalid update"),
+        (status = 401, description = "Missing or invalid secret"),
+        (status = 503, description = "Telegram webhook not configured"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Inbound"
+)]
+pub async fn receive_update(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<StatusCode, ApiError> {
+    let Some(bot) = state.telegram.clone().filter(|bot| bot.uses_webhook()) else {
+        return Err(ApiError::unavailable("Telegram webhook not configured"));
+    };
+    let handler = bot.webhook_handler();
+
+    auth::verify_webhook("Telegram", &handler, &headers, &body)?;
+
+    let update = handler
+        .parse_payload(&body)
+        .map_err(|e| ApiError::bad_request(e.to_string()))?;
+
+    let calls = IntegrationCallHandler::new(state.clone(), "telegram");
+    state
+        .tasks
+        .spawn(async move { bot.handle_update(update, &calls).await });
+
+    Ok(StatusCode::OK)
+}
+
+pub fn public_router() -> Router<AppState> {
+    Router::new().route("/kaiba/integrations/telegram/webhook", post(receive_update))
+}
@@ -5,252 +5,358 @@
 //! on elapsed time since last execution.
 //!
 //! Features:
-//! - JITTER: Random delay between Rei processing to avoid thundering herd
-//! - Batch processing: Handles all Reis in one request
+//! - JITTER: Random delay before each Rei's API calls to avoid thundering herd
+//! - Batch processing: Handles all Reis in one request, several at a time
+//!   (`SCHEDULER_MAX_CONCURRENCY`), each within `SCHEDULER_REI_TIMEOUT_SECS`
+//! - Targeting: an optional body limits the run to some Reis or actions
+//! - Dry run: reports what would run without spending energy
+//! - Paused Reis (`/kaiba/rei/{id}/autonomy/pause`) are skipped
+//! - History: each call is recorded in `/kaiba/admin/scheduler/runs`
 
-use axum::{extract::State, routing::post, Json, Router};
+use axum::{body::Bytes, extract::State, routing::post, Json, Router};
 use chrono::Utc;
-use serde::Serialize;
-use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use utoipa::ToSchema;
+use uuid::Uuid;
 
-use crate::models::Rei;
-use crate::services::decision::{Action, DecisionMaker};
+use crate::error::ApiError;
+use crate::models::{Rei, ReiRunResult};
+use crate::services::decision::{self, Action};
 use crate::services::digest::DigestService;
+use crate::services::embedding::EmbeddingService;
+use crate::services::energy;
+use crate::services::learning_source;
+use crate::services::qdrant::MemoryKai;
+use crate::services::reflection::ReflectionService;
+use crate::services::run_history::{RunKind, RunRecord};
 use crate::services::self_learning::{LearningConfig, SelfLearningService};
+use crate::services::web_search::WebSearchAgent;
 use crate::AppState;
 
-/// Jitter range in milliseconds (0-3000ms = 0-3sec)
-const JITTER_MAX_MS: u64 = 3000;
-
-/// Simple jitter using timestamp nanos (no external crate needed)
-fn jitter_ms(seed: usize) -> u64 {
-    let nanos = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .subsec_nanos() as u64;
-    (nanos ^ (seed as u64 * 7919)) % JITTER_MAX_MS
+/// Trigger request (the body is optional; an empty one processes every Rei)
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct TriggerRequest {
+    /// Only these Reis (default: all)
+    pub rei_ids: Option<Vec<Uuid>>,
+    /// Only run these actions (learn, digest, reflect, rest); a Rei that
+    /// decides on
+    /// another action is skipped (default: all)
+    #[schema(value_type = Option<Vec<String>>, example = json!(["learn"]))]
+    pub actions: Option<Vec<Action>>,
+    /// Decide without acting: no energy is regenerated or spent, and the
+    /// decisions are not logged
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 /// Trigger response
 #[derive(Debug, Serialize, ToSchema)]
 pub struct TriggerResponse {
     pub triggered_at: chrono::DateTime<Utc>,
-    pub results: Vec<ReiTriggerResult>,
+    /// Whether this was a dry run
+    pub dry_run: bool,
+    pub results: Vec<ReiRunResult>,
     pub summary: TriggerSummary,
 }
 
-/// Result for each Rei
-#[derive(Debug, Serialize, ToSchema)]
-pub struct ReiTriggerResult {
-    pub rei_name: String,
-    pub action: String,
-    pub success: bool,
-    pub details: Option<String>,
-}
-
-/// Summary of trigger execution
+/// Summary of trigger execution (in a dry run, what would have run)
 #[derive(Debug, Serialize, ToSchema)]
 pub struct TriggerSummary {
     pub reis_processed: usize,
     pub learns_executed: usize,
     pub digests_executed: usize,
+    pub reflections_executed: usize,
     pub rests_skipped: usize,
     pub errors: usize,
 }
 
+impl TriggerSummary {
+    /// Tally results; a skip counts as an error only when it failed
+    fn of(results: &[ReiRunResult]) -> Self {
+        let mut summary = Self {
+            reis_processed: results.len(),
+            learns_executed: 0,
+            digests_executed: 0,
+            reflections_executed: 0,
+            rests_skipped: 0,
+            errors: 0,
+        };
+        for result in results {
+            match (result.action.as_str(), result.success) {
+                (_, false) => summary.errors += 1,
+                ("Learn", true) => summary.learns_executed += 1,
+                ("Digest", true) => summary.digests_executed += 1,
+                ("Reflect", true) => summary.reflections_executed += 1,
+                ("Rest", true) => summary.rests_skipped += 1,
+                _ => {}
+            }
+        }
+        summary
+    }
+}
+
 /// Trigger all pending jobs
 #[utoipa::path(
     post,
     path = "/kaiba/trigger",
+    request_body = Option<TriggerRequest>,
     responses(
         (status = 200, description = "Trigger completed", body = TriggerResponse),
+        (status = 400, description = "Malformed request body"),
+        (status = 404, description = "A requested Rei does not exist"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Trigger"
 )]
 pub async fn trigger_jobs(
     State(state): State<AppState>,
-) -> Result<Json<TriggerResponse>, (axum::http::StatusCode, String)> {
+    body: Bytes,
+) -> Result<Json<TriggerResponse>, ApiError> {
+    // Cron jobs typically POST without a body
+    let request: TriggerRequest = if body.iter().all(u8::is_ascii_whitespace) {
+        TriggerRequest::default()
+    } else {
+        serde_json::from_slice(&body)
+            .map_err(|e| ApiError::bad_request(format!("Invalid trigger request: {}", e)))?
+    };
+    let dry_run = request.dry_run;
+
     let triggered_at = Utc::now();
-    let mut results = Vec::new();
-    let mut summary = TriggerSummary {
-        reis_processed: 0,
-        learns_executed: 0,
-        digests_executed: 0,
-        rests_skipped: 0,
-        errors: 0,
+
+    // Get the targeted Reis
+    let reis: Vec<Rei> = sqlx::query_as(
+        "SELECT * FROM reis WHERE deleted_at IS NULL AND ($1::uuid[] IS NULL OR id = ANY($1))",
+    )
+    .bind(&request.rei_ids)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    if let Some(rei_ids) = &request.rei_ids {
+        let missing: Vec<String> = rei_ids
+            .iter()
+            .filter(|id| !reis.iter().any(|rei| rei.id == **id))
+            .map(Uuid::to_string)
+            .collect();
+        if !missing.is_empty() {
+            return Err(ApiError::not_found(format!(
+                "Rei not found: {}",
+                missing.join(", ")
+            )));
+        }
+    }
+
+    // Check required services (without web search, learning studies
+    // existing memories)
+    let (Some(memory_kai), Some(embedding)) = (&state.memory_kai, &state.embedding) else {
+        return Err(ApiError::unavailable("Required services not available"));
     };
 
-    // Get all Reis
-    let reis: Vec<Rei> = sqlx::query_as("SELECT * FROM reis")
-        .fetch_all(&state.pool)
-        .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
-
-    // Check required services
-    let (Some(memory_kai), Some(embedding), Some(web_search)) =
-        (&state.memory_kai, &state.embedding, &state.web_search)
-    else {
-        return Err((
-            axum::http::StatusCode::SERVICE_UNAVAILABLE,
-            "Required services not available".to_string(),
-        ));
+    let record = RunRecord::start(&state.pool, RunKind::Trigger, dry_run).await;
+
+    // First, regenerate energy for the targeted Reis, for however long
+    // the deployment slept
+    if !dry_run {
+        if let Err(e) = energy::regenerate(&state.pool, request.rei_ids.as_deref()).await {
+            tracing::warn!("⚠️  Energy regeneration failed: {}", e);
+        }
+    }
+
+    let services = Services {
+        memory_kai,
+        embedding,
+        web_search: state.web_search.as_ref(),
     };
+    let parallelism = state.config.parallelism();
+    let outcomes = parallelism
+        .run(&reis, !dry_run, |rei| {
+            trigger_rei(&state, &services, rei, &request)
+        })
+        .await;
+
+    let results: Vec<ReiRunResult> = reis
+        .iter()
+        .zip(outcomes)
+        .map(|(rei, outcome)| {
+            outcome.unwrap_or_else(|_| {
+                ReiRunResult::new(
+                    rei,
+                    "Skip",
+                    false,
+                    format!("Timed out after {:?}", parallelism.rei_timeout),
+                )
+            })
+        })
+        .collect();
+    let summary = TriggerSummary::of(&results);
+    record.finish(&results, None).await;
+
+    Ok(Json(TriggerResponse {
+        triggered_at,
+        dry_run,
+        results,
+        summary,
+    }))
+}
+
+/// Services a triggered action needs
+struct Services<'a> {
+    memory_kai: &'a Arc<MemoryKai>,
+    embedding: &'a EmbeddingService,
+    web_search: Option<&'a WebSearchAgent>,
+}
+
+/// Decide for one Rei and run the decided action (unless a dry run)
+async fn trigger_rei(
+    state: &AppState,
+    services: &Services<'_>,
+    rei: &Rei,
+    request: &TriggerRequest,
+) -> ReiRunResult {
+    let dry_run = request.dry_run;
 
-    // First, regenerate energy for all Reis
-    let _ = sqlx::query(
-        r#"
-        UPDATE rei_states
-        SET energy_level = LEAST(100, energy_level + energy_regen_per_hour)
-        WHERE energy_regen_per_hour > 0
-        "#,
+    // Get Rei state
+    let rei_state = match sqlx::query_as::<_, crate::models::ReiState>(
+        "SELECT * FROM rei_states WHERE rei_id = $1",
     )
-    .execute(&state.pool)
-    .await;
+    .bind(rei.id)
+    .fetch_optional(&state.pool)
+    .await
+    {
+        Ok(Some(s)) => s,
+        Ok(None) => return ReiRunResult::new(rei, "Skip", false, "No state found"),
+        Err(e) => return ReiRunResult::new(rei, "Skip", false, e.to_string()),
+    };
+
+    if !rei_state.autonomy_enabled {
+        return ReiRunResult::new(rei, "Skip", true, "Autonomy paused");
+    }
+
+    // Count learning memories for decision
+    let memories_count = count_learning_memories(state, rei.id, &rei_state).await;
 
-    for (idx, rei) in reis.iter().enumerate() {
-        summary.reis_processed += 1;
+    // Make and log the decision under the Rei's policy (a dry run only
+    // previews it)
+    let gemini_api_key = state.gemini_api_key.as_deref();
+    let decided = if dry_run {
+        decision::preview_for_rei(&state.pool, gemini_api_key, &rei_state, memories_count).await
+    } else {
+        decision::decide_for_rei(&state.pool, gemini_api_key, &rei_state, memories_count).await
+    };
+    let decision = match decided {
+        Ok(decision) => decision,
+        Err(e) => return ReiRunResult::new(rei, "Skip", false, e.to_string()),
+    };
 
-        // Add jitter between Rei processing (skip first one)
-        if idx > 0 {
-            let delay = jitter_ms(idx);
-            tokio::time::sleep(Duration::from_millis(delay)).await;
+    if let Some(actions) = &request.actions {
+        if !actions.contains(&decision.action) {
+            return ReiRunResult::new(
+                rei,
+                "Skip",
+                true,
+                format!(
+                    "Decided to {}, which was not requested: {}",
+                    decision.action.as_str(),
+                    decision.reason
+                ),
+            );
         }
+    }
 
-        // Get Rei state
-        let rei_state = match sqlx::query_as::<_, crate::models::ReiState>(
-            "SELECT * FROM rei_states WHERE rei_id = $1",
-        )
-        .bind(rei.id)
-        .fetch_optional(&state.pool)
-        .await
-        {
-            Ok(Some(s)) => s,
-            Ok(None) => {
-                results.push(ReiTriggerResult {
-                    rei_name: rei.name.clone(),
-                    action: "Skip".to_string(),
-                    success: false,
-                    details: Some("No state found".to_string()),
-                });
-                summary.errors += 1;
-                continue;
-            }
-            Err(e) => {
-                results.push(ReiTriggerResult {
-                    rei_name: rei.name.clone(),
-                    action: "Skip".to_string(),
-                    success: false,
-                    details: Some(e.to_string()),
-                });
-                summary.errors += 1;
-                continue;
-            }
-        };
+    if dry_run {
+        return ReiRunResult::new(
+            rei,
+            decision.action.label(),
+            true,
+            format!("Would {}: {}", decision.action.as_str(), decision.reason),
+        );
+    }
 
-        // Count learning memories for decision
-        let memories_count = count_learning_memories(&state, rei.id, &rei_state).await;
-
-        // Make decision
-        let decision_maker = DecisionMaker::new(None);
-        let decision = decision_maker.decide(&rei_state, memories_count);
-
-        match decision.action {
-            Action::Learn => {
-                // Execute learn
-                let service = SelfLearningService::new(
-                    state.pool.clone(),
-                    memory_kai.clone(),
-                    embedding.clone(),
-                    web_search.clone(),
-                    Some(LearningConfig {
-                        force: true, // Force even if energy is low
-                        ..Default::default()
-                    }),
-                );
-
-                match service.learn(rei.id).await {
-                    Ok(session) => {
-                        results.push(ReiTriggerResult {
-                            rei_name: rei.name.clone(),
-                            action: "Learn".to_string(),
-                            success: true,
-                            details: Some(format!(
-                                "{} queries, {} memories stored",
-                                session.queries_generated.len(),
-                                session.memories_stored
-                            )),
-                        });
-                        summary.learns_executed += 1;
-                    }
-                    Err(e) => {
-                        results.push(ReiTriggerResult {
-                            rei_name: rei.name.clone(),
-                            action: "Learn".to_string(),
-                            success: false,
-                            details: Some(e.to_string()),
-                        });
-                        summary.errors += 1;
-                    }
-                }
+    match decision.action {
+        Action::Learn => {
+            // Execute learn
+            let service = SelfLearningService::new(
+                state.pool.clone(),
+                services.memory_kai.clone(),
+                services.embedding.clone(),
+                services.web_search.cloned(),
+                Some(LearningConfig {
+                    force: true, // Force even if energy is low
+                    ..Default::default()
+                }),
+            )
+            .with_event_bus(Some(state.event_bus.clone()))
+            .with_sources(learning_source::default_sources(
+                &state.pool,
+                state.gemini_api_key.as_deref(),
+            ));
+
+            match service.learn(rei.id).await {
+                Ok(session) => ReiRunResult::new(
+                    rei,
+                    "Learn",
+                    true,
+                    format!(
+                        "{} queries, {} memories stored",
+                        session.queries_generated.len(),
+                        session.memories_stored
+                    ),
+                ),
+                Err(e) => ReiRunResult::new(rei, "Learn", false, e.to_string()),
             }
-            Action::Digest => {
-                // Execute digest
-                let service = DigestService::new(
-                    state.pool.clone(),
-                    memory_kai.clone(),
-                    embedding.clone(),
-                    None, // Gemini API key from secrets if needed
-                );
-
-                match service.digest(rei.id).await {
-                    Ok(result) => {
-                        results.push(ReiTriggerResult {
-                            rei_name: rei.name.clone(),
-                            action: "Digest".to_string(),
-                            success: true,
-                            details: Some(format!(
-                                "{} memories processed",
-                                result.memories_processed
-                            )),
-                        });
-                        summary.digests_executed += 1;
-                    }
-                    Err(e) => {
-                        results.push(ReiTriggerResult {
-                            rei_name: rei.name.clone(),
-                            action: "Digest".to_string(),
-                            success: false,
-                            details: Some(e.to_string()),
-                        });
-                        summary.errors += 1;
-                    }
+        }
+        Action::Digest => {
+            // Execute digest
+            let service = DigestService::new(
+                state.pool.clone(),
+                services.memory_kai.clone(),
+                services.embedding.clone(),
+                None, // Gemini API key from secrets if needed
+            )
+            .with_event_bus(Some(state.event_bus.clone()))
+            .with_threshold(state.config.digest_threshold());
+
+            match service.digest(rei.id).await {
+                Ok(digest) if !digest.expertise_created => {
+                    ReiRunResult::new(rei, "Digest", true, digest.summary)
                 }
+                Ok(digest) => ReiRunResult::new(
+                    rei,
+                    "Digest",
+                    true,
+                    format!("{} memories processed", digest.memories_processed),
+                ),
+                Err(e) => ReiRunResult::new(rei, "Digest", false, e.to_string()),
             }
-            Action::Rest => {
-                results.push(ReiTriggerResult {
-                    rei_name: rei.name.clone(),
-                    action: "Rest".to_string(),
-                    success: true,
-                    details: Some(decision.reason),
-                });
-                summary.rests_skipped += 1;
+        }
+        Action::Reflect => {
+            let service = ReflectionService::new(
+                state.pool.clone(),
+                services.memory_kai.clone(),
+                services.embedding.clone(),
+                state.gemini_api_key.clone(),
+            )
+            .with_event_bus(Some(state.event_bus.clone()));
+
+            match service.reflect(rei.id).await {
+                Ok(reflection) => ReiRunResult::new(
+                    rei,
+                    "Reflect",
+                    true,
+                    format!("{} memories considered", reflection.memories_considered),
+                ),
+                Err(e) => ReiRunResult::new(rei, "Reflect", false, e.to_string()),
             }
         }
+        Action::Rest => ReiRunResult::new(rei, "Rest", true, decision.reason),
     }
-
-    Ok(Json(TriggerResponse {
-        triggered_at,
-        results,
-        summary,
-    }))
 }
 
 /// Count learning memories for a Rei since last digest
 async fn count_learning_memories(
     state: &AppState,
-    rei_id: uuid::Uuid,
+    rei_id: Uuid,
     rei_state: &crate::models::ReiState,
 ) -> usize {
     let (Some(memory_kai), Some(embedding)) = (&state.memory_kai, &state.embedding) else {
@@ -0,0 +1,116 @@
+//! User Routes - Accounts that own Reis, Teis and webhooks
+//!
+//! Users are managed by the operator (master or operator keys); a teammate
+//! then gets an API key with `user_id` set and only sees their own resources.
+
+use axum::{
+    extract::{Path, State},
+    routing::{delete, get},
+    Json, Router,
+};
+use uuid::Uuid;
+
+use crate::error::ApiError;
+use crate::models::{CreateUserRequest, User};
+use crate::validation::ValidJson;
+use crate::AppState;
+
+/// List users
+#[utoipa::path(
+    get,
+    path = "/kaiba/users",
+    responses(
+        (status = 200, description = "Users (by name)", body = Vec<User>),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Auth"
+)]
+pub async fn list_users(State(state): State<AppState>) -> Result<Json<Vec<User>>, ApiError> {
+    let users = sqlx::query_as::<_, User>("SELECT * FROM users ORDER BY name")
+        .fetch_all(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(users))
+}
+
+/// Create a user
+#[utoipa::path(
+    post,
+    path = "/kaiba/users",
+    request_body = CreateUserRequest,
+    responses(
+        (status = 200, description = "User created", body = User),
+        (status = 400, description = "Missing name"),
+        (status = 409, description = "Name already taken"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Auth"
+)]
+pub async fn create_user(
+    State(state): State<AppState>,
+    ValidJson(payload): ValidJson<CreateUserRequest>,
+) -> Result<Json<User>, ApiError> {
+    let name = payload.name.trim();
+
+    let user = sqlx::query_as::<_, User>(
+        r#"
+        INSERT INTO users (name) VALUES ($1)
+        ON CONFLICT (name) DO NOTHING
+        RETURNING *
+        "#,
+    )
+    .bind(name)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?
+    .ok_or(ApiError::conflict(format!(
+        "User '{}' already exists",
+        name
+    )))?;
+
+    tracing::info!("👤 User created: {}", user.name);
+
+    Ok(Json(user))
+}
+
+/// Delete a user
+///
+/// Their API keys are deleted; their Reis, Teis and webhooks become shared
+/// (visible to operator keys only).
+#[utoipa::path(
+    delete,
+    path = "/kaiba/users/{id}",
+    params(("id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "User deleted"),
+        (status = 404, description = "User not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Auth"
+)]
+pub async fn delete_user(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let result = sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(id)
+        .execute(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::not_found("User not found"));
+    }
+
+    Ok(Json(serde_json::json!({
+        "status": "ok",
+        "message": "User deleted"
+    })))
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/kaiba/users", get(list_users).post(create_user))
+        .route("/kaiba/users/:id", delete(delete_user))
+}
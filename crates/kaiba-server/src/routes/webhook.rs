@@ -4,18 +4,30 @@
 //! with the external world.
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
     routing::get,
     Json, Router,
 };
 use uuid::Uuid;
 
-use kaiba::{ReiWebhook, ReiWebhookRepository, TeiWebhook, WebhookEventType, WebhookPayload};
+use kaiba::{
+    DeliveryStatus, ReiWebhook, ReiWebhookRepository, TeiWebhook, WebhookDeliveryMode,
+    WebhookEventType, WebhookPayload,
+};
 
+use crate::adapters::formatters;
+use crate::bulk::{BulkJson, BulkResponse};
+use crate::error::ApiError;
 use crate::models::{
-    parse_event_types, CreateWebhookRequest, TriggerWebhookRequest, UpdateWebhookRequest,
-    WebhookDeliveryResponse, WebhookResponse,
+    parse_conditions, parse_event_types, CreateWebhookRequest, DeliveriesQuery,
+    TriggerWebhookRequest, UpdateWebhookRequest, WebhookDeliveryResponse, WebhookDryRunResponse,
+    WebhookResponse, WebhookStatsQuery, WebhookStatsResponse,
 };
+use crate::rest;
+use crate::services::webhook_health;
+use crate::validation::ValidJson;
 use crate::AppState;
 
 /// List all webhooks for a Rei
@@ -34,12 +46,12 @@ use crate::AppState;
 pub async fn list_webhooks(
     State(state): State<AppState>,
     Path(rei_id): Path<Uuid>,
-) -> Result<Json<Vec<WebhookResponse>>, (axum::http::StatusCode, String)> {
+) -> Result<Json<Vec<WebhookResponse>>, ApiError> {
     let webhooks = state
         .webhook_repo
         .find_by_rei(rei_id)
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .map_err(ApiError::internal)?;
 
     let responses: Vec<WebhookResponse> = webhooks
         .into_iter()
@@ -58,7 +70,9 @@ pub async fn list_webhooks(
     ),
     request_body = CreateWebhookRequest,
     responses(
-        (status = 200, description = "Webhook created", body = WebhookResponse),
+        (status = 201, description = "Webhook created", body = WebhookResponse,
+            headers(("location" = String, description = "URL of the new webhook"))),
+        (status = 400, description = "Invalid payload template, condition or event"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Webhook"
@@ -66,35 +80,66 @@ pub async fn list_webhooks(
 pub async fn create_webhook(
     State(state): State<AppState>,
     Path(rei_id): Path<Uuid>,
-    Json(payload): Json<CreateWebhookRequest>,
-) -> Result<Json<WebhookResponse>, (axum::http::StatusCode, String)> {
-    let events = parse_event_types(payload.events);
-
-    let mut webhook = ReiWebhook::new(rei_id, payload.name, payload.url).with_events(events);
+    ValidJson(payload): ValidJson<CreateWebhookRequest>,
+) -> Result<Response, ApiError> {
+    let saved = create_one(&state, rei_id, payload).await?;
+
+    Ok(rest::created(
+        format!("/kaiba/rei/{}/webhooks/{}", rei_id, saved.id),
+        saved,
+    ))
+}
 
-    if let Some(secret) = payload.secret {
-        webhook = webhook.with_secret(secret);
-    }
-    if let Some(headers) = payload.headers {
-        webhook = webhook.with_headers(headers);
-    }
-    if let Some(max_retries) = payload.max_retries {
-        webhook.max_retries = max_retries;
-    }
-    if let Some(timeout_ms) = payload.timeout_ms {
-        webhook.timeout_ms = timeout_ms;
-    }
-    if let Some(payload_format) = payload.payload_format {
-        webhook.payload_format = Some(payload_format);
+/// Create many webhooks for a Rei in one request
+#[utoipa::path(
+    post,
+    path = "/kaiba/rei/{rei_id}/webhooks/bulk",
+    params(
+        ("rei_id" = Uuid, Path, description = "Rei ID")
+    ),
+    request_body = Vec<CreateWebhookRequest>,
+    responses(
+        (status = 200, description = "One result per item, in request order", body = BulkResponse),
+        (status = 400, description = "Empty request or too many items")
+    ),
+    tag = "Webhook"
+)]
+pub async fn bulk_create_webhooks(
+    State(state): State<AppState>,
+    Path(rei_id): Path<Uuid>,
+    BulkJson(items): BulkJson<CreateWebhookRequest>,
+) -> Json<BulkResponse> {
+    let mut response = BulkResponse::default();
+    for item in items {
+        let created = match item {
+            Ok(payload) => create_one(&state, rei_id, payload).await,
+            Err(err) => Err(err),
+        };
+        match created {
+            Ok(saved) => response.created(
+                format!("/kaiba/rei/{}/webhooks/{}", rei_id, saved.id),
+                saved,
+            ),
+            Err(err) => response.failed(err),
+        }
     }
+    Json(response)
+}
+
+async fn create_one(
+    state: &AppState,
+    rei_id: Uuid,
+    payload: CreateWebhookRequest,
+) -> Result<WebhookResponse, ApiError> {
+    let webhook = build_webhook(state, Some(rei_id), payload).await?;
 
     let saved = state
         .webhook_repo
         .save(&webhook)
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .map_err(ApiError::internal)?;
 
-    Ok(Json(WebhookResponse::from_domain(saved)))
+    Ok(WebhookResponse::from_domain(saved))
 }
 
 /// Get webhook by ID
@@ -115,23 +160,17 @@ pub async fn create_webhook(
 pub async fn get_webhook(
     State(state): State<AppState>,
     Path((rei_id, webhook_id)): Path<(Uuid, Uuid)>,
-) -> Result<Json<WebhookResponse>, (axum::http::StatusCode, String)> {
+) -> Result<Json<WebhookResponse>, ApiError> {
     let webhook = state
         .webhook_repo
         .find_by_id(webhook_id)
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
-        .ok_or((
-            axum::http::StatusCode::NOT_FOUND,
-            "Webhook not found".to_string(),
-        ))?;
+        .map_err(ApiError::internal)?
+        .ok_or(ApiError::not_found("Webhook not found"))?;
 
     // Verify webhook belongs to this Rei
-    if webhook.rei_id != rei_id {
-        return Err((
-            axum::http::StatusCode::NOT_FOUND,
-            "Webhook not found".to_string(),
-        ));
+    if webhook.rei_id != Some(rei_id) {
+        return Err(ApiError::not_found("Webhook not found"));
     }
 
     Ok(Json(WebhookResponse::from_domain(webhook)))
@@ -148,6 +187,7 @@ pub async fn get_webhook(
     request_body = UpdateWebhookRequest,
     responses(
         (status = 200, description = "Webhook updated", body = WebhookResponse),
+        (status = 400, description = "Invalid payload template, condition or event"),
         (status = 404, description = "Webhook not found"),
         (status = 500, description = "Internal server error")
     ),
@@ -156,60 +196,21 @@ pub async fn get_webhook(
 pub async fn update_webhook(
     State(state): State<AppState>,
     Path((rei_id, webhook_id)): Path<(Uuid, Uuid)>,
-    Json(payload): Json<UpdateWebhookRequest>,
-) -> Result<Json<WebhookResponse>, (axum::http::StatusCode, String)> {
-    let mut webhook = state
+    ValidJson(payload): ValidJson<UpdateWebhookRequest>,
+) -> Result<Json<WebhookResponse>, ApiError> {
+    let webhook = state
         .webhook_repo
         .find_by_id(webhook_id)
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
-        .ok_or((
-            axum::http::StatusCode::NOT_FOUND,
-            "Webhook not found".to_string(),
-        ))?;
+        .map_err(ApiError::internal)?
+        .ok_or(ApiError::not_found("Webhook not found"))?;
 
     // Verify webhook belongs to this Rei
-    if webhook.rei_id != rei_id {
-        return Err((
-            axum::http::StatusCode::NOT_FOUND,
-            "Webhook not found".to_string(),
-        ));
-    }
-
-    // Apply updates
-    if let Some(name) = payload.name {
-        webhook.name = name;
-    }
-    if let Some(url) = payload.url {
-        webhook.url = url;
-    }
-    if let Some(secret) = payload.secret {
-        webhook.secret = Some(secret);
-    }
-    if let Some(enabled) = payload.enabled {
-        webhook.enabled = enabled;
-    }
-    if let Some(events) = payload.events {
-        webhook.events = parse_event_types(Some(events));
-    }
-    if let Some(headers) = payload.headers {
-        webhook.headers = headers;
-    }
-    if let Some(max_retries) = payload.max_retries {
-        webhook.max_retries = max_retries;
-    }
-    if let Some(timeout_ms) = payload.timeout_ms {
-        webhook.timeout_ms = timeout_ms;
-    }
-    if let Some(payload_format) = payload.payload_format {
-        webhook.payload_format = Some(payload_format);
+    if webhook.rei_id != Some(rei_id) {
+        return Err(ApiError::not_found("Webhook not found"));
     }
 
-    let saved = state
-        .webhook_repo
-        .save(&webhook)
-        .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let saved = apply_update(&state, webhook, payload).await?;
 
     Ok(Json(WebhookResponse::from_domain(saved)))
 }
@@ -223,7 +224,7 @@ pub async fn update_webhook(
         ("webhook_id" = Uuid, Path, description = "Webhook ID")
     ),
     responses(
-        (status = 200, description = "Webhook deleted"),
+        (status = 204, description = "Webhook deleted"),
         (status = 404, description = "Webhook not found"),
         (status = 500, description = "Internal server error")
     ),
@@ -232,27 +233,24 @@ pub async fn update_webhook(
 pub async fn delete_webhook(
     State(state): State<AppState>,
     Path((_rei_id, webhook_id)): Path<(Uuid, Uuid)>,
-) -> Result<Json<serde_json::Value>, (axum::http::StatusCode, String)> {
+) -> Result<StatusCode, ApiError> {
     let deleted = state
         .webhook_repo
         .delete(webhook_id)
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .map_err(ApiError::internal)?;
 
     if !deleted {
-        return Err((
-            axum::http::StatusCode::NOT_FOUND,
-            "Webhook not found".to_string(),
-        ));
+        return Err(ApiError::not_found("Webhook not found"));
     }
 
-    Ok(Json(serde_json::json!({
-        "status": "ok",
-        "message": "Webhook deleted"
-    })))
+    Ok(StatusCode::NO_CONTENT)
 }
 
 /// Trigger a test webhook delivery
+///
+/// With `dry_run: true`, the formatted body and headers are returned
+/// instead of being sent, and no delivery is recorded.
 #[utoipa::path(
     post,
     path = "/kaiba/rei/{rei_id}/webhooks/{webhook_id}/trigger",
@@ -263,6 +261,7 @@ pub async fn delete_webhook(
     request_body = TriggerWebhookRequest,
     responses(
         (status = 200, description = "Webhook triggered", body = WebhookDeliveryResponse),
+        (status = 200, description = "Dry run: rendered request", body = WebhookDryRunResponse),
         (status = 404, description = "Webhook not found"),
         (status = 500, description = "Internal server error")
     ),
@@ -272,23 +271,17 @@ pub async fn trigger_webhook(
     State(state): State<AppState>,
     Path((rei_id, webhook_id)): Path<(Uuid, Uuid)>,
     Json(payload): Json<TriggerWebhookRequest>,
-) -> Result<Json<WebhookDeliveryResponse>, (axum::http::StatusCode, String)> {
+) -> Result<Response, ApiError> {
     let webhook = state
         .webhook_repo
         .find_by_id(webhook_id)
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
-        .ok_or((
-            axum::http::StatusCode::NOT_FOUND,
-            "Webhook not found".to_string(),
-        ))?;
+        .map_err(ApiError::internal)?
+        .ok_or(ApiError::not_found("Webhook not found"))?;
 
     // Verify webhook belongs to this Rei
-    if webhook.rei_id != rei_id {
-        return Err((
-            axum::http::StatusCode::NOT_FOUND,
-            "Webhook not found".to_string(),
-        ));
+    if webhook.rei_id != Some(rei_id) {
+        return Err(ApiError::not_found("Webhook not found"));
     }
 
     // Create test payload
@@ -300,6 +293,8 @@ pub async fn trigger_webhook(
             "memory_added" => WebhookEventType::MemoryAdded,
             "search_completed" => WebhookEventType::SearchCompleted,
             "learning_completed" => WebhookEventType::LearningCompleted,
+            "digest_completed" => WebhookEventType::DigestCompleted,
+            "task_completed" => WebhookEventType::TaskCompleted,
             s => WebhookEventType::Custom(s.to_string()),
         })
         .unwrap_or(WebhookEventType::Custom("test".to_string()));
@@ -310,33 +305,54 @@ pub async fn trigger_webhook(
 
     let webhook_payload = WebhookPayload::new(event, rei_id, data);
 
+    if payload.dry_run {
+        let rendered = state
+            .http_webhook
+            .prepare(&webhook, &webhook_payload, true)
+            .map(|prepared| (prepared.headers, prepared.body));
+        let response = WebhookDryRunResponse::new(&webhook, &webhook_payload, rendered);
+        return Ok(Json(response).into_response());
+    }
+
     // Deliver webhook
     let delivery = state
         .http_webhook
         .deliver_with_retry(&webhook, &webhook_payload)
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .map_err(ApiError::internal)?;
 
     // Save delivery record
     let saved_delivery = state
         .webhook_repo
         .save_delivery(&delivery)
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .map_err(ApiError::internal)?;
 
-    Ok(Json(WebhookDeliveryResponse::from_domain(saved_delivery)))
+    webhook_health::track_delivery_outcome(
+        state.webhook_repo.as_ref(),
+        state.http_webhook.as_ref(),
+        &webhook,
+        &saved_delivery,
+    )
+    .await;
+
+    Ok(Json(WebhookDeliveryResponse::from_domain(saved_delivery)).into_response())
 }
 
 /// Get recent deliveries for a webhook
+///
+/// Use `?status=dead` to list dead-lettered deliveries (retries exhausted).
 #[utoipa::path(
     get,
     path = "/kaiba/rei/{rei_id}/webhooks/{webhook_id}/deliveries",
     params(
         ("rei_id" = Uuid, Path, description = "Rei ID"),
-        ("webhook_id" = Uuid, Path, description = "Webhook ID")
+        ("webhook_id" = Uuid, Path, description = "Webhook ID"),
+        DeliveriesQuery
     ),
     responses(
         (status = 200, description = "List of deliveries", body = Vec<WebhookDeliveryResponse>),
+        (status = 400, description = "Invalid status filter"),
         (status = 404, description = "Webhook not found"),
         (status = 500, description = "Internal server error")
     ),
@@ -345,30 +361,36 @@ pub async fn trigger_webhook(
 pub async fn list_deliveries(
     State(state): State<AppState>,
     Path((rei_id, webhook_id)): Path<(Uuid, Uuid)>,
-) -> Result<Json<Vec<WebhookDeliveryResponse>>, (axum::http::StatusCode, String)> {
+    Query(query): Query<DeliveriesQuery>,
+) -> Result<Json<Vec<WebhookDeliveryResponse>>, ApiError> {
     // Verify webhook exists and belongs to this Rei
     let webhook = state
         .webhook_repo
         .find_by_id(webhook_id)
         .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
-        .ok_or((
-            axum::http::StatusCode::NOT_FOUND,
-            "Webhook not found".to_string(),
-        ))?;
+        .map_err(ApiError::internal)?
+        .ok_or(ApiError::not_found("Webhook not found"))?;
 
-    if webhook.rei_id != rei_id {
-        return Err((
-            axum::http::StatusCode::NOT_FOUND,
-            "Webhook not found".to_string(),
-        ));
+    if webhook.rei_id != Some(rei_id) {
+        return Err(ApiError::not_found("Webhook not found"));
     }
 
-    let deliveries = state
-        .webhook_repo
-        .find_deliveries(webhook_id, 50)
-        .await
-        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let status = query
+        .status
+        .map(|s| s.parse::<DeliveryStatus>())
+        .transpose()
+        .map_err(ApiError::bad_request)?;
+
+    let deliveries = match status {
+        Some(status) => {
+            state
+                .webhook_repo
+                .find_deliveries_by_status(webhook_id, &status, 50)
+                .await
+        }
+        None => state.webhook_repo.find_deliveries(webhook_id, 50).await,
+    }
+    .map_err(ApiError::internal)?;
 
     let responses: Vec<WebhookDeliveryResponse> = deliveries
         .into_iter()
@@ -378,12 +400,353 @@ pub async fn list_deliveries(
     Ok(Json(responses))
 }
 
+/// Get delivery metrics for a webhook
+///
+/// Success rate, last success/failure timestamps, and response time
+/// percentiles plus a latency histogram.
+#[utoipa::path(
+    get,
+    path = "/kaiba/rei/{rei_id}/webhooks/{webhook_id}/stats",
+    params(
+        ("rei_id" = Uuid, Path, description = "Rei ID"),
+        ("webhook_id" = Uuid, Path, description = "Webhook ID"),
+        WebhookStatsQuery
+    ),
+    responses(
+        (status = 200, description = "Delivery metrics", body = WebhookStatsResponse),
+        (status = 400, description = "hours is out of range"),
+        (status = 404, description = "Webhook not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Webhook"
+)]
+pub async fn get_webhook_stats(
+    State(state): State<AppState>,
+    Path((rei_id, webhook_id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<WebhookStatsQuery>,
+) -> Result<Json<WebhookStatsResponse>, ApiError> {
+    let webhook = state
+        .webhook_repo
+        .find_by_id(webhook_id)
+        .await
+        .map_err(ApiError::internal)?
+        .filter(|w| w.rei_id == Some(rei_id))
+        .ok_or(ApiError::not_found("Webhook not found"))?;
+
+    let since = query
+        .hours
+        .map(|h| {
+            chrono::Duration::try_hours(h)
+                .and_then(|window| chrono::Utc::now().checked_sub_signed(window))
+                .ok_or(ApiError::bad_request("hours is out of range"))
+        })
+        .transpose()?;
+
+    let stats = state
+        .webhook_repo
+        .delivery_stats(webhook_id, since)
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(WebhookStatsResponse::from_domain(&webhook, stats)))
+}
+
+/// Replay a past delivery
+///
+/// Re-sends the original payload (same delivery_id) with an
+/// `X-Kaiba-Replay` header referencing the replayed delivery record.
+#[utoipa::path(
+    post,
+    path = "/kaiba/rei/{rei_id}/webhooks/{webhook_id}/deliveries/{delivery_id}/replay",
+    params(
+        ("rei_id" = Uuid, Path, description = "Rei ID"),
+        ("webhook_id" = Uuid, Path, description = "Webhook ID"),
+        ("delivery_id" = Uuid, Path, description = "Delivery ID to replay")
+    ),
+    responses(
+        (status = 200, description = "Delivery replayed", body = WebhookDeliveryResponse),
+        (status = 404, description = "Webhook or delivery not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Webhook"
+)]
+pub async fn replay_delivery(
+    State(state): State<AppState>,
+    Path((rei_id, webhook_id, delivery_id)): Path<(Uuid, Uuid, Uuid)>,
+) -> Result<Json<WebhookDeliveryResponse>, ApiError> {
+    let webhook = state
+        .webhook_repo
+        .find_by_id(webhook_id)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or(ApiError::not_found("Webhook not found"))?;
+
+    if webhook.rei_id != Some(rei_id) {
+        return Err(ApiError::not_found("Webhook not found"));
+    }
+
+    let original = state
+        .webhook_repo
+        .find_delivery_by_id(delivery_id)
+        .await
+        .map_err(ApiError::internal)?
+        .filter(|d| d.webhook_id == webhook_id)
+        .ok_or(ApiError::not_found("Delivery not found"))?;
+
+    // Mark the request as a replay so receivers can tell it apart
+    let mut replay_webhook = webhook.clone();
+    if !replay_webhook.headers.is_object() {
+        replay_webhook.headers = serde_json::json!({});
+    }
+    if let Some(headers) = replay_webhook.headers.as_object_mut() {
+        headers.insert(
+            "X-Kaiba-Replay".to_string(),
+            serde_json::Value::String(original.id.to_string()),
+        );
+    }
+
+    tracing::info!(
+        "🔁 Replaying delivery {} to webhook: {}",
+        original.id,
+        webhook.name
+    );
+
+    let delivery = state
+        .http_webhook
+        .deliver_with_retry(&replay_webhook, &original.payload)
+        .await
+        .map_err(ApiError::internal)?;
+
+    let saved_delivery = state
+        .webhook_repo
+        .save_delivery(&delivery)
+        .await
+        .map_err(ApiError::internal)?;
+
+    webhook_health::track_delivery_outcome(
+        state.webhook_repo.as_ref(),
+        state.http_webhook.as_ref(),
+        &webhook,
+        &saved_delivery,
+    )
+    .await;
+
+    Ok(Json(WebhookDeliveryResponse::from_domain(saved_delivery)))
+}
+
+/// Build a new webhook from a create request (rei_id None = global)
+pub async fn build_webhook(
+    state: &AppState,
+    rei_id: Option<Uuid>,
+    payload: CreateWebhookRequest,
+) -> Result<ReiWebhook, ApiError> {
+    let events = parse_event_types(payload.events);
+    ensure_registered_events(state, &events).await?;
+
+    let mut webhook = match rei_id {
+        Some(rei_id) => ReiWebhook::new(rei_id, payload.name, payload.url),
+        None => ReiWebhook::global(payload.name, payload.url),
+    }
+    .with_events(events);
+
+    if let Some(conditions) = payload.conditions {
+        let conditions = parse_conditions(conditions).map_err(ApiError::bad_request)?;
+        webhook = webhook.with_conditions(conditions);
+    }
+
+    if let Some(secret) = payload.secret {
+        webhook = webhook.with_secret(secret);
+    }
+    if let Some(headers) = payload.headers {
+        webhook = webhook.with_headers(headers);
+    }
+    if let Some(max_retries) = payload.max_retries {
+        webhook.max_retries = max_retries;
+    }
+    if let Some(timeout_ms) = payload.timeout_ms {
+        webhook.timeout_ms = timeout_ms;
+    }
+    if let Some(max_failures) = payload.max_consecutive_failures {
+        webhook.max_consecutive_failures = Some(max_failures).filter(|n| *n > 0);
+    }
+    if let Some(rate_limit) = payload.rate_limit_per_minute {
+        webhook.rate_limit_per_minute = Some(rate_limit).filter(|n| *n > 0);
+    }
+    if let Some(mode) = payload.delivery_mode {
+        webhook.delivery_mode = mode
+            .parse::<WebhookDeliveryMode>()
+            .map_err(ApiError::bad_request)?;
+    }
+    if let Some(minutes) = payload.batch_interval_minutes {
+        if minutes < 1 {
+            return Err(ApiError::bad_request(
+                "batch_interval_minutes must be at least 1",
+            ));
+        }
+        webhook.batch_interval_minutes = minutes;
+    }
+    if let Some(payload_format) = payload.payload_format {
+        webhook.payload_format = Some(payload_format);
+    }
+    if let Some(format_options) = payload.format_options {
+        webhook.format_options = format_options;
+    }
+    if let Some(tls) = payload.tls {
+        webhook.tls = tls.into_domain();
+    }
+    if let Some(tls) = &webhook.tls {
+        state
+            .http_webhook
+            .build_tls_client(tls)
+            .map_err(ApiError::bad_request)?;
+    }
+    formatters::validate_format_options(webhook.payload_format.as_deref(), &webhook.format_options)
+        .map_err(ApiError::bad_request)?;
+    state
+        .http_webhook
+        .secrets()
+        .validate_headers(&webhook.headers)
+        .map_err(ApiError::bad_request)?;
+    if let Some(payload_template) = payload.payload_template {
+        formatters::validate_template(&payload_template).map_err(ApiError::bad_request)?;
+        webhook.payload_template = Some(payload_template);
+    }
+
+    Ok(webhook)
+}
+
+/// Apply an update request to a webhook and save it
+pub async fn apply_update(
+    state: &AppState,
+    mut webhook: ReiWebhook,
+    payload: UpdateWebhookRequest,
+) -> Result<ReiWebhook, ApiError> {
+    // Apply updates
+    if let Some(name) = payload.name {
+        webhook.name = name;
+    }
+    if let Some(url) = payload.url {
+        webhook.url = url;
+    }
+    if let Some(secret) = payload.secret {
+        webhook.secret = Some(secret);
+    }
+    if let Some(enabled) = payload.enabled {
+        if enabled && !webhook.enabled {
+            // set_enabled resets the consecutive failure counter
+            state
+                .webhook_repo
+                .set_enabled(webhook.id, true)
+                .await
+                .map_err(ApiError::internal)?;
+            webhook.consecutive_failures = 0;
+        }
+        webhook.enabled = enabled;
+    }
+    if let Some(events) = payload.events {
+        let events = parse_event_types(Some(events));
+        ensure_registered_events(state, &events).await?;
+        webhook.events = events;
+    }
+    if let Some(conditions) = payload.conditions {
+        webhook.conditions = parse_conditions(conditions).map_err(ApiError::bad_request)?;
+    }
+    if let Some(headers) = payload.headers {
+        webhook.headers = headers;
+    }
+    if let Some(max_retries) = payload.max_retries {
+        webhook.max_retries = max_retries;
+    }
+    if let Some(timeout_ms) = payload.timeout_ms {
+        webhook.timeout_ms = timeout_ms;
+    }
+    if let Some(max_failures) = payload.max_consecutive_failures {
+        webhook.max_consecutive_failures = Some(max_failures).filter(|n| *n > 0);
+    }
+    if let Some(rate_limit) = payload.rate_limit_per_minute {
+        webhook.rate_limit_per_minute = Some(rate_limit).filter(|n| *n > 0);
+    }
+    if let Some(mode) = payload.delivery_mode {
+        webhook.delivery_mode = mode
+            .parse::<WebhookDeliveryMode>()
+            .map_err(ApiError::bad_request)?;
+    }
+    if let Some(minutes) = payload.batch_interval_minutes {
+        if minutes < 1 {
+            return Err(ApiError::bad_request(
+                "batch_interval_minutes must be at least 1",
+            ));
+        }
+        webhook.batch_interval_minutes = minutes;
+    }
+    if let Some(payload_format) = payload.payload_format {
+        webhook.payload_format = Some(payload_format);
+    }
+    if let Some(format_options) = payload.format_options {
+        webhook.format_options = format_options;
+    }
+    if let Some(tls) = payload.tls {
+        webhook.tls = tls.into_domain();
+    }
+    if let Some(tls) = &webhook.tls {
+        state
+            .http_webhook
+            .build_tls_client(tls)
+            .map_err(ApiError::bad_request)?;
+    }
+    formatters::validate_format_options(webhook.payload_format.as_deref(), &webhook.format_options)
+        .map_err(ApiError::bad_request)?;
+    state
+        .http_webhook
+        .secrets()
+        .validate_headers(&webhook.headers)
+        .map_err(ApiError::bad_request)?;
+    if let Some(payload_template) = payload.payload_template {
+        formatters::validate_template(&payload_template).map_err(ApiError::bad_request)?;
+        webhook.payload_template = Some(payload_template);
+    }
+
+    state
+        .webhook_repo
+        .save(&webhook)
+        .await
+        .map_err(ApiError::internal)
+}
+
+/// Reject subscriptions to custom events missing from the event registry
+async fn ensure_registered_events(
+    state: &AppState,
+    events: &[WebhookEventType],
+) -> Result<(), ApiError> {
+    for event in events {
+        let WebhookEventType::Custom(name) = event else {
+            continue;
+        };
+        let registered = state
+            .webhook_repo
+            .find_event_definition(name)
+            .await
+            .map_err(ApiError::internal)?;
+        if registered.is_none() {
+            return Err(ApiError::bad_request(format!(
+                "Unknown event '{}': register custom events at /kaiba/events first",
+                name
+            )));
+        }
+    }
+    Ok(())
+}
+
 pub fn router() -> Router<AppState> {
     Router::new()
         .route(
             "/kaiba/rei/:rei_id/webhooks",
             get(list_webhooks).post(create_webhook),
         )
+        .route(
+            "/kaiba/rei/:rei_id/webhooks/bulk",
+            axum::routing::post(bulk_create_webhooks),
+        )
         .route(
             "/kaiba/rei/:rei_id/webhooks/:webhook_id",
             get(get_webhook).put(update_webhook).delete(delete_webhook),
@@ -396,4 +759,12 @@ pub fn router() -> Router<AppState> {
             "/kaiba/rei/:rei_id/webhooks/:webhook_id/deliveries",
             get(list_deliveries),
         )
+        .route(
+            "/kaiba/rei/:rei_id/webhooks/:webhook_id/stats",
+            get(get_webhook_stats),
+        )
+        .route(
+            "/kaiba/rei/:rei_id/webhooks/:webhook_id/deliveries/:delivery_id/replay",
+            axum::routing::post(replay_delivery),
+        )
 }
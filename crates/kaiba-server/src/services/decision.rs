@@ -1,10 +1,23 @@
 //! Decision Service - Autonomous action selection
 //!
 //! Decides what action a Rei should take based on their state.
-//! Currently rule-based, designed to be swappable with LLM-based decision.
+//! Each Rei's policy (`rei_decision_policies`) sets the thresholds and the
+//! strategy: the rule-based [`DecisionMaker`] or the LLM-assisted
+//! [`LlmDecisionMaker`](crate::services::llm_decision::LlmDecisionMaker).
+//! Every decision is logged in `rei_decisions` with its reason.
+//!
+//! Actions that keep failing (see [`post_mortem`]) are backed off: the rules
+//! do not choose them, and the LLM strategy reads their post-mortems.
 
-use crate::models::ReiState;
+use crate::models::{DecisionStrategyKind, ReiDecisionPolicy, ReiState};
+use crate::services::llm_decision::LlmDecisionMaker;
+use crate::services::post_mortem::{self, FailureStreak, Subject};
+use crate::services::self_learning;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
 
 /// Actions a Rei can take during autonomous cycle
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -14,6 +27,8 @@ pub enum Action {
     Learn,
     /// Consolidate and summarize recent memories
     Digest,
+    /// Write a journal entry about recent memories
+    Reflect,
     /// Do nothing, recover energy
     Rest,
 }
@@ -23,11 +38,34 @@ impl std::fmt::Display for Action {
         match self {
             Action::Learn => write!(f, "🔍 Learn"),
             Action::Digest => write!(f, "📝 Digest"),
+            Action::Reflect => write!(f, "💭 Reflect"),
             Action::Rest => write!(f, "😴 Rest"),
         }
     }
 }
 
+impl Action {
+    /// Name used in the decision log and by the LLM strategy
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Action::Learn => "learn",
+            Action::Digest => "digest",
+            Action::Reflect => "reflect",
+            Action::Rest => "rest",
+        }
+    }
+
+    /// Name reported in run results ("Learn", "Digest", "Reflect", "Rest")
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::Learn => "Learn",
+            Action::Digest => "Digest",
+            Action::Reflect => "Reflect",
+            Action::Rest => "Rest",
+        }
+    }
+}
+
 /// Decision context - all factors considered in decision
 #[derive(Debug, Clone, Serialize)]
 pub struct DecisionContext {
@@ -35,6 +73,38 @@ pub struct DecisionContext {
     pub tokens_remaining: i32,
     pub mood: String,
     pub memories_since_digest: usize,
+    /// Hours since the last reflection (or since the Rei was created)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hours_since_reflection: Option<i64>,
+    /// Recent decisions and learning results, newest first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub recent_outcomes: Vec<String>,
+    /// Actions backed off after failing repeatedly
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub backed_off: Vec<Action>,
+    /// What keeps failing and why, newest first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub post_mortems: Vec<String>,
+    /// No Gemini key: learning works from memories, digests and reflections
+    /// cannot run
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub offline: bool,
+}
+
+impl DecisionContext {
+    pub fn new(state: &ReiState, memories_since_digest: usize) -> Self {
+        Self {
+            energy_level: state.energy_level,
+            tokens_remaining: state.token_budget - state.tokens_used,
+            mood: state.mood.clone(),
+            memories_since_digest,
+            hours_since_reflection: None,
+            recent_outcomes: Vec::new(),
+            backed_off: Vec::new(),
+            post_mortems: Vec::new(),
+            offline: false,
+        }
+    }
 }
 
 /// Decision result with reasoning
@@ -42,9 +112,16 @@ pub struct DecisionContext {
 pub struct Decision {
     pub action: Action,
     pub reason: String,
+    pub strategy: DecisionStrategyKind,
     pub context: DecisionContext,
 }
 
+/// A way of choosing the next action
+#[async_trait]
+pub trait DecisionStrategy: Send + Sync {
+    async fn next_action(&self, context: DecisionContext) -> Decision;
+}
+
 /// Thresholds for decision making (configurable)
 #[derive(Debug, Clone)]
 pub struct DecisionConfig {
@@ -56,6 +133,10 @@ pub struct DecisionConfig {
     pub min_tokens_action: i32,
     /// Memories needed before digest is considered
     pub memories_for_digest: usize,
+    /// Minimum energy to reflect
+    pub min_energy_reflect: i32,
+    /// Hours between reflections
+    pub reflection_interval_hours: i64,
 }
 
 impl Default for DecisionConfig {
@@ -65,12 +146,26 @@ impl Default for DecisionConfig {
             min_energy_digest: 60,
             min_tokens_action: 500,
             memories_for_digest: 5,
+            min_energy_reflect: 30,
+            reflection_interval_hours: 24,
+        }
+    }
+}
+
+impl From<&ReiDecisionPolicy> for DecisionConfig {
+    fn from(policy: &ReiDecisionPolicy) -> Self {
+        Self {
+            min_energy_learn: policy.min_energy_learn,
+            min_energy_digest: policy.min_energy_digest,
+            min_tokens_action: policy.min_tokens_action,
+            memories_for_digest: policy.memories_for_digest.max(0) as usize,
+            min_energy_reflect: policy.min_energy_reflect,
+            reflection_interval_hours: policy.reflection_interval_hours.into(),
         }
     }
 }
 
 /// Rule-based decision maker
-/// TODO: Replace with LLM-based decision (like orcs agent pattern)
 pub struct DecisionMaker {
     config: DecisionConfig,
 }
@@ -82,73 +177,343 @@ impl DecisionMaker {
         }
     }
 
-    /// Decide what action to take
+    /// Decide what action to take from a Rei's state alone
+    #[cfg(test)]
     pub fn decide(&self, state: &ReiState, memories_since_digest: usize) -> Decision {
-        let tokens_remaining = state.token_budget - state.tokens_used;
+        self.decide_in(DecisionContext::new(state, memories_since_digest))
+    }
 
-        let context = DecisionContext {
-            energy_level: state.energy_level,
-            tokens_remaining,
-            mood: state.mood.clone(),
-            memories_since_digest,
-        };
+    /// Decide from a prepared context
+    pub fn decide_in(&self, context: DecisionContext) -> Decision {
+        let (action, reason) = self.choose(&context);
+        Decision {
+            action,
+            reason,
+            strategy: DecisionStrategyKind::Rules,
+            context,
+        }
+    }
 
+    fn choose(&self, context: &DecisionContext) -> (Action, String) {
         // Priority 1: Token exhaustion -> Rest
-        if tokens_remaining < self.config.min_tokens_action {
-            return Decision {
-                action: Action::Rest,
-                reason: format!(
+        if context.tokens_remaining < self.config.min_tokens_action {
+            return (
+                Action::Rest,
+                format!(
                     "Token budget low ({} remaining, need {})",
-                    tokens_remaining, self.config.min_tokens_action
+                    context.tokens_remaining, self.config.min_tokens_action
                 ),
-                context,
-            };
+            );
         }
 
-        // Priority 2: Low energy -> Rest
-        if state.energy_level < self.config.min_energy_learn {
-            return Decision {
-                action: Action::Rest,
-                reason: format!(
+        // Priority 2: Low energy -> Reflect if due (it is cheap), else Rest
+        if context.energy_level < self.config.min_energy_learn {
+            if self.reflection_due(context) {
+                return (
+                    Action::Reflect,
+                    format!(
+                        "Reflection due, energy too low ({}) to learn",
+                        context.energy_level
+                    ),
+                );
+            }
+            return (
+                Action::Rest,
+                format!(
                     "Energy low ({}, need {} to learn)",
-                    state.energy_level, self.config.min_energy_learn
+                    context.energy_level, self.config.min_energy_learn
                 ),
-                context,
-            };
+            );
         }
 
         // Priority 3: Many undigested memories + enough energy -> Digest
-        if memories_since_digest >= self.config.memories_for_digest
-            && state.energy_level >= self.config.min_energy_digest
+        if context.memories_since_digest >= self.config.memories_for_digest
+            && context.energy_level >= self.config.min_energy_digest
+            && !context.backed_off.contains(&Action::Digest)
+            && !context.offline
         {
-            return Decision {
-                action: Action::Digest,
-                reason: format!(
+            return (
+                Action::Digest,
+                format!(
                     "{} memories to consolidate, energy sufficient ({})",
-                    memories_since_digest, state.energy_level
+                    context.memories_since_digest, context.energy_level
+                ),
+            );
+        }
+
+        // Priority 4: Reflection due -> Reflect
+        if self.reflection_due(context) {
+            return (
+                Action::Reflect,
+                format!(
+                    "{} hours since last reflection",
+                    context.hours_since_reflection.unwrap_or_default()
                 ),
-                context,
-            };
+            );
         }
 
-        // Priority 4: Enough energy -> Learn
-        if state.energy_level >= self.config.min_energy_learn {
-            return Decision {
-                action: Action::Learn,
-                reason: format!("Energy sufficient ({}) for learning", state.energy_level),
-                context,
-            };
+        // Priority 5: Enough energy -> Learn, unless every topic keeps failing
+        if context.energy_level >= self.config.min_energy_learn {
+            if context.backed_off.contains(&Action::Learn) {
+                return (
+                    Action::Rest,
+                    "Every learning topic keeps failing; backing off".to_string(),
+                );
+            }
+            return (
+                Action::Learn,
+                format!("Energy sufficient ({}) for learning", context.energy_level),
+            );
         }
 
         // Default: Rest
-        Decision {
-            action: Action::Rest,
-            reason: "Default to rest".to_string(),
-            context,
+        (Action::Rest, "Default to rest".to_string())
+    }
+
+    /// Whether enough time has passed, and energy is left, to reflect
+    fn reflection_due(&self, context: &DecisionContext) -> bool {
+        context
+            .hours_since_reflection
+            .is_some_and(|hours| hours >= self.config.reflection_interval_hours)
+            && context.energy_level >= self.config.min_energy_reflect
+            && !context.offline
+    }
+
+    /// Why the thresholds rule out `action`, if they do
+    pub fn forbids(&self, action: Action, context: &DecisionContext) -> Option<String> {
+        let (min_energy, need) = match action {
+            Action::Rest => return None,
+            Action::Learn => (self.config.min_energy_learn, "learn"),
+            Action::Digest => (self.config.min_energy_digest, "digest"),
+            Action::Reflect => (self.config.min_energy_reflect, "reflect"),
+        };
+        if context.tokens_remaining < self.config.min_tokens_action {
+            return Some(format!(
+                "token budget low ({} remaining)",
+                context.tokens_remaining
+            ));
+        }
+        if context.energy_level < min_energy {
+            return Some(format!(
+                "energy {} is below {} needed to {}",
+                context.energy_level, min_energy, need
+            ));
+        }
+        if context.offline && matches!(action, Action::Digest | Action::Reflect) {
+            return Some(format!("{} needs a Gemini API key", need));
+        }
+        if context.backed_off.contains(&action) {
+            return Some(match action {
+                Action::Learn => "every learning topic keeps failing".to_string(),
+                _ => format!("{} keeps failing", need),
+            });
+        }
+        if action == Action::Digest
+            && context.memories_since_digest < self.config.memories_for_digest
+        {
+            return Some(format!(
+                "only {} memories to digest (need {})",
+                context.memories_since_digest, self.config.memories_for_digest
+            ));
         }
+        if action == Action::Reflect {
+            if let Some(hours) = context
+                .hours_since_reflection
+                .filter(|hours| *hours < self.config.reflection_interval_hours)
+            {
+                return Some(format!("last reflection was {} hours ago", hours));
+            }
+        }
+        None
+    }
+}
+
+#[async_trait]
+impl DecisionStrategy for DecisionMaker {
+    async fn next_action(&self, context: DecisionContext) -> Decision {
+        self.decide_in(context)
     }
 }
 
+/// Decide a Rei's next action under its policy and log the decision
+///
+/// The LLM strategy needs `gemini_api_key`; without one the rules decide,
+/// and only between learning (from memories) and resting.
+pub async fn decide_for_rei(
+    pool: &PgPool,
+    gemini_api_key: Option<&str>,
+    state: &ReiState,
+    memories_since_digest: usize,
+) -> Result<Decision, sqlx::Error> {
+    let decision = preview_for_rei(pool, gemini_api_key, state, memories_since_digest).await?;
+    record_decision(pool, state.rei_id, &decision).await?;
+    Ok(decision)
+}
+
+/// Decide like [`decide_for_rei`] without logging the decision (dry runs)
+pub async fn preview_for_rei(
+    pool: &PgPool,
+    gemini_api_key: Option<&str>,
+    state: &ReiState,
+    memories_since_digest: usize,
+) -> Result<Decision, sqlx::Error> {
+    let policy: Option<ReiDecisionPolicy> =
+        sqlx::query_as("SELECT * FROM rei_decision_policies WHERE rei_id = $1")
+            .bind(state.rei_id)
+            .fetch_optional(pool)
+            .await?;
+    let config = policy.as_ref().map(DecisionConfig::from);
+    let strategy = policy.as_ref().map(|p| p.strategy()).unwrap_or_default();
+
+    let mut context = DecisionContext::new(state, memories_since_digest);
+    context.hours_since_reflection = hours_since_reflection(pool, state.rei_id).await?;
+    context.offline = gemini_api_key.is_none();
+    let streaks = post_mortem::backed_off(pool, state.rei_id).await?;
+    context.backed_off = backed_off_actions(pool, state.rei_id, &streaks).await?;
+    context.post_mortems = streaks
+        .iter()
+        .filter_map(FailureStreak::post_mortem)
+        .collect();
+    let decision = match (strategy, gemini_api_key) {
+        (DecisionStrategyKind::Llm, Some(api_key)) => {
+            context.recent_outcomes = recent_outcomes(pool, state.rei_id).await?;
+            LlmDecisionMaker::new(api_key, config)
+                .next_action(context)
+                .await
+        }
+        _ => DecisionMaker::new(config).next_action(context).await,
+    };
+
+    Ok(decision)
+}
+
+/// Actions to avoid: digesting when digests keep failing, learning when
+/// every manifest topic does
+async fn backed_off_actions(
+    pool: &PgPool,
+    rei_id: Uuid,
+    streaks: &[FailureStreak],
+) -> Result<Vec<Action>, sqlx::Error> {
+    let subjects: Vec<Subject> = streaks.iter().filter_map(FailureStreak::subject).collect();
+    let mut actions = Vec::new();
+    if subjects.contains(&Subject::Digest) {
+        actions.push(Action::Digest);
+    }
+
+    if subjects
+        .iter()
+        .any(|subject| matches!(subject, Subject::Search(_)))
+    {
+        let rei: Option<(serde_json::Value, String)> =
+            sqlx::query_as("SELECT manifest, role FROM reis WHERE id = $1")
+                .bind(rei_id)
+                .fetch_optional(pool)
+                .await?;
+        if let Some((manifest, role)) = rei {
+            let queries = self_learning::manifest_queries(&manifest, &role);
+            if !queries.is_empty()
+                && queries
+                    .into_iter()
+                    .all(|query| subjects.contains(&Subject::Search(query)))
+            {
+                actions.push(Action::Learn);
+            }
+        }
+    }
+
+    Ok(actions)
+}
+
+/// Hours since the Rei last reflected, or since it was created
+async fn hours_since_reflection(pool: &PgPool, rei_id: Uuid) -> Result<Option<i64>, sqlx::Error> {
+    sqlx::query_scalar(
+        r#"
+        SELECT (EXTRACT(EPOCH FROM NOW() - COALESCE(s.last_reflection_at, r.created_at)) / 3600)::BIGINT
+        FROM rei_states s
+        JOIN reis r ON r.id = s.rei_id
+        WHERE s.rei_id = $1
+        "#,
+    )
+    .bind(rei_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Recent decisions and learning sessions, newest first
+async fn recent_outcomes(pool: &PgPool, rei_id: Uuid) -> Result<Vec<String>, sqlx::Error> {
+    let decisions: Vec<(String, String, DateTime<Utc>)> = sqlx::query_as(
+        r#"
+        SELECT action, reason, created_at FROM rei_decisions
+        WHERE rei_id = $1
+        ORDER BY created_at DESC
+        LIMIT 5
+        "#,
+    )
+    .bind(rei_id)
+    .fetch_all(pool)
+    .await?;
+
+    let sessions: Vec<(i32, i32, i32, i32, DateTime<Utc>)> = sqlx::query_as(
+        r#"
+        SELECT searches_completed, searches_skipped, memories_stored,
+               jsonb_array_length(errors), started_at
+        FROM learning_sessions
+        WHERE rei_id = $1
+        ORDER BY started_at DESC
+        LIMIT 3
+        "#,
+    )
+    .bind(rei_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut outcomes: Vec<(DateTime<Utc>, String)> = decisions
+        .into_iter()
+        .map(|(action, reason, at)| (at, format!("decided to {}: {}", action, reason)))
+        .chain(
+            sessions
+                .into_iter()
+                .map(|(completed, skipped, stored, errors, at)| {
+                    (
+                        at,
+                        format!(
+                            "learned {} memories from {} searches ({} skipped, {} failed)",
+                            stored, completed, skipped, errors
+                        ),
+                    )
+                }),
+        )
+        .collect();
+    outcomes.sort_by_key(|(at, _)| std::cmp::Reverse(*at));
+
+    Ok(outcomes
+        .into_iter()
+        .map(|(at, outcome)| format!("{}: {}", at.format("%Y-%m-%d %H:%M UTC"), outcome))
+        .collect())
+}
+
+async fn record_decision(
+    pool: &PgPool,
+    rei_id: Uuid,
+    decision: &Decision,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO rei_decisions (rei_id, action, reason, strategy, context)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+    )
+    .bind(rei_id)
+    .bind(decision.action.as_str())
+    .bind(&decision.reason)
+    .bind(decision.strategy.to_string())
+    .bind(serde_json::json!(decision.context))
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -168,6 +533,7 @@ mod tests {
             energy_regen_per_hour: 10,
             last_digest_at: None,
             last_learn_at: None,
+            autonomy_enabled: true,
         }
     }
 
@@ -195,6 +561,66 @@ mod tests {
         assert_eq!(decision.action, Action::Digest);
     }
 
+    #[test]
+    fn test_forbids_outside_thresholds() {
+        let maker = DecisionMaker::new(None);
+        let context = DecisionContext::new(&mock_state(55, 0), 0);
+        assert_eq!(maker.forbids(Action::Rest, &context), None);
+        assert_eq!(maker.forbids(Action::Learn, &context), None);
+        assert!(maker.forbids(Action::Digest, &context).is_some());
+        let few = DecisionContext::new(&mock_state(80, 0), 2);
+        assert!(maker.forbids(Action::Digest, &few).is_some());
+
+        let exhausted = DecisionContext::new(&mock_state(100, 99900), 10);
+        assert!(maker.forbids(Action::Learn, &exhausted).is_some());
+    }
+
+    #[test]
+    fn test_reflects_when_due() {
+        let maker = DecisionMaker::new(None);
+        let mut context = DecisionContext::new(&mock_state(80, 0), 0);
+        context.hours_since_reflection = Some(30);
+        assert_eq!(maker.decide_in(context.clone()).action, Action::Reflect);
+
+        // Too tired to learn, but reflecting is cheap
+        context.energy_level = 40;
+        assert_eq!(maker.decide_in(context.clone()).action, Action::Reflect);
+
+        context.energy_level = 20;
+        assert_eq!(maker.decide_in(context.clone()).action, Action::Rest);
+
+        context.energy_level = 80;
+        context.hours_since_reflection = Some(3);
+        assert_eq!(maker.decide_in(context.clone()).action, Action::Learn);
+        assert!(maker.forbids(Action::Reflect, &context).is_some());
+    }
+
+    #[test]
+    fn test_backs_off_failing_actions() {
+        let maker = DecisionMaker::new(None);
+        let mut context = DecisionContext::new(&mock_state(80, 0), 10);
+        context.backed_off = vec![Action::Digest];
+        assert_eq!(maker.decide_in(context.clone()).action, Action::Learn);
+        assert!(maker.forbids(Action::Digest, &context).is_some());
+
+        context.backed_off.push(Action::Learn);
+        assert_eq!(maker.decide_in(context.clone()).action, Action::Rest);
+        assert!(maker.forbids(Action::Learn, &context).is_some());
+        assert_eq!(maker.forbids(Action::Reflect, &context), None);
+    }
+
+    #[test]
+    fn test_offline_only_learns() {
+        let maker = DecisionMaker::new(None);
+        let mut context = DecisionContext::new(&mock_state(80, 0), 10);
+        context.hours_since_reflection = Some(30);
+        context.offline = true;
+        assert_eq!(maker.decide_in(context.clone()).action, Action::Learn);
+        assert!(maker.forbids(Action::Digest, &context).is_some());
+        assert!(maker.forbids(Action::Reflect, &context).is_some());
+        assert_eq!(maker.forbids(Action::Learn, &context), None);
+    }
+
     #[test]
     fn test_token_exhausted_rests() {
         let maker = DecisionMaker::new(None);
@@ -1,21 +1,149 @@
 //! Digest Service - Consolidate and summarize memories
 //!
-//! Takes recent learning memories and creates a consolidated expertise.
+//! Takes the learning memories created since the last digest and creates a
+//! consolidated expertise. Each digest covers exactly the memories between
+//! the previous `last_digest_at` and the start of the run, oldest first; when
+//! there are more than `MAX_DIGEST_MEMORIES`, the rest wait for the next one.
+//! Too little input (see [`DigestThreshold`]) is not digested at all: the
+//! memories wait until enough have gathered for a worthwhile expertise.
+//!
+//! Digests form tiers: these daily digests are rolled up into a weekly digest
+//! once a week (Monday, UTC) is over, and weekly digests into a monthly one
+//! once a month is over. Every digest is tagged with its tier and lists the
+//! memories it consolidates in `metadata.sources`.
+//!
+//! Reis that opt in also get each digest merged into their Teis' expertise
+//! (see [`tei_expertise`](crate::services::tei_expertise)).
+//!
+//! Digests that keep failing get a post-mortem memory, and the decision
+//! maker backs off from digesting for a while (see
+//! [`post_mortem`](crate::services::post_mortem)).
 
+use crate::adapters::InProcessEventBus;
 use crate::models::{Memory, MemoryType};
 use crate::services::embedding::EmbeddingService;
+use crate::services::post_mortem::{FailureLog, Subject};
 use crate::services::qdrant::{MemoryKai, SearchFilter};
-use chrono::{DateTime, Utc};
+use crate::services::tei_expertise;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use kaiba::{ReiEvent, ReiEventBus, WebhookEventType};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use std::sync::Arc;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+/// Most learning memories summarized in one digest
+const MAX_DIGEST_MEMORIES: usize = 50;
+
+/// Least input a daily digest needs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DigestThreshold {
+    /// Learning memories
+    pub min_memories: usize,
+    /// Characters of memory content, all memories together
+    pub min_chars: usize,
+}
+
+impl Default for DigestThreshold {
+    fn default() -> Self {
+        Self {
+            min_memories: 3,
+            min_chars: 1000,
+        }
+    }
+}
+
+impl DigestThreshold {
+    /// Why `memories` are too little to digest, if they are
+    fn shortfall(&self, memories: &[Memory]) -> Option<String> {
+        let chars: usize = memories.iter().map(|m| m.content.chars().count()).sum();
+        if memories.len() >= self.min_memories && chars >= self.min_chars {
+            return None;
+        }
+        Some(format!(
+            "Only {} memories ({} characters) to digest; waiting for at least {} memories and {} characters",
+            memories.len(),
+            chars,
+            self.min_memories,
+            self.min_chars
+        ))
+    }
+}
+
+/// Level of a digest in the hierarchy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DigestTier {
+    /// Consolidates learning memories
+    Daily,
+    /// Consolidates the daily digests of a week
+    Weekly,
+    /// Consolidates the weekly digests of a month
+    Monthly,
+}
+
+impl DigestTier {
+    /// Memory tag of digests in this tier
+    pub fn tag(self) -> &'static str {
+        match self {
+            DigestTier::Daily => "daily_digest",
+            DigestTier::Weekly => "weekly_digest",
+            DigestTier::Monthly => "monthly_digest",
+        }
+    }
+
+    /// Tier whose digests this one rolls up
+    fn source(self) -> Option<DigestTier> {
+        match self {
+            DigestTier::Daily => None,
+            DigestTier::Weekly => Some(DigestTier::Daily),
+            DigestTier::Monthly => Some(DigestTier::Weekly),
+        }
+    }
+
+    /// `rei_states` column holding how far this tier has digested
+    fn watermark_column(self) -> &'static str {
+        match self {
+            DigestTier::Daily => "last_digest_at",
+            DigestTier::Weekly => "last_weekly_digest_at",
+            DigestTier::Monthly => "last_monthly_digest_at",
+        }
+    }
+
+    /// Start of the period containing `now` (UTC); roll-ups cover only
+    /// periods that have ended
+    fn period_start(self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let today = now.date_naive();
+        let start = match self {
+            DigestTier::Daily => today,
+            DigestTier::Weekly => {
+                today - Duration::days(today.weekday().num_days_from_monday() as i64)
+            }
+            DigestTier::Monthly => {
+                NaiveDate::from_ymd_opt(today.year(), today.month(), 1).unwrap_or(today)
+            }
+        };
+        start.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc()
+    }
+}
+
+impl std::fmt::Display for DigestTier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DigestTier::Daily => write!(f, "daily"),
+            DigestTier::Weekly => write!(f, "weekly"),
+            DigestTier::Monthly => write!(f, "monthly"),
+        }
+    }
+}
+
 /// Digest result
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct DigestResult {
     pub rei_id: Uuid,
+    pub tier: DigestTier,
     pub memories_processed: usize,
     pub expertise_created: bool,
     pub summary: String,
@@ -28,6 +156,8 @@ pub struct DigestService {
     embedding: EmbeddingService,
     client: Client,
     gemini_api_key: Option<String>,
+    event_bus: Option<Arc<InProcessEventBus>>,
+    threshold: DigestThreshold,
 }
 
 impl DigestService {
@@ -43,106 +173,278 @@ impl DigestService {
             embedding,
             client: Client::new(),
             gemini_api_key,
+            event_bus: None,
+            threshold: DigestThreshold::default(),
         }
     }
 
+    /// Publish MemoryAdded / DigestCompleted events
+    pub fn with_event_bus(mut self, event_bus: Option<Arc<InProcessEventBus>>) -> Self {
+        self.event_bus = event_bus;
+        self
+    }
+
+    /// Skip daily digests with less input than `threshold`
+    pub fn with_threshold(mut self, threshold: DigestThreshold) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
     /// Digest recent learning memories for a Rei
+    #[tracing::instrument(name = "digest", skip(self))]
     pub async fn digest(&self, rei_id: Uuid) -> Result<DigestResult, DigestError> {
+        let result = self.run_digest(rei_id).await;
+
+        let failures = FailureLog {
+            pool: &self.pool,
+            memory_kai: &self.memory_kai,
+            embedding: &self.embedding,
+            event_bus: self.event_bus.as_ref(),
+        };
+        match &result {
+            Ok(_) => failures.succeeded(rei_id, &Subject::Digest).await,
+            Err(e) => {
+                failures
+                    .failed(rei_id, &Subject::Digest, &e.to_string())
+                    .await
+            }
+        }
+        result
+    }
+
+    async fn run_digest(&self, rei_id: Uuid) -> Result<DigestResult, DigestError> {
         // 0. Get last_digest_at to filter already-digested memories
         let last_digest_at = self.get_last_digest_at(rei_id).await?;
+        let started_at = Utc::now();
 
-        // 1. Get recent learning memories (not yet digested)
-        let memories = self.get_learning_memories(rei_id, last_digest_at).await?;
+        // 1. Get learning memories not yet digested; memories learned while
+        //    this digest runs are left for the next one
+        let mut memories = self
+            .get_learning_memories(rei_id, last_digest_at, started_at)
+            .await?;
 
         if memories.is_empty() {
             return Ok(DigestResult {
                 rei_id,
+                tier: DigestTier::Daily,
                 memories_processed: 0,
                 expertise_created: false,
                 summary: "No memories to digest".to_string(),
             });
         }
 
+        // Too little to be worth an expertise: leave it for a later digest
+        if let Some(reason) = self.threshold.shortfall(&memories) {
+            tracing::info!("⏭️  Digest skipped for Rei {}: {}", rei_id, reason);
+            return Ok(DigestResult {
+                rei_id,
+                tier: DigestTier::Daily,
+                memories_processed: 0,
+                expertise_created: false,
+                summary: reason,
+            });
+        }
+
+        let digested_through = if memories.len() > MAX_DIGEST_MEMORIES {
+            memories.truncate(MAX_DIGEST_MEMORIES);
+            memories[MAX_DIGEST_MEMORIES - 1].created_at
+        } else {
+            started_at
+        };
+
         // 2. Generate digest summary
         let summary = self.generate_summary(&memories).await?;
 
         // 3. Store as Expertise memory
-        let memory_id = Uuid::new_v4();
+        // High importance for digested knowledge
+        self.store_expertise(rei_id, DigestTier::Daily, &summary, &memories, 0.9)
+            .await?;
+
+        let result = DigestResult {
+            rei_id,
+            tier: DigestTier::Daily,
+            memories_processed: memories.len(),
+            expertise_created: true,
+            summary,
+        };
+
+        // 4. Update last_digest_at in state; the completion event is written
+        //    to the outbox in the same transaction
+        self.update_digest_timestamp(&result, digested_through)
+            .await?;
+        self.sync_tei_expertise(&result).await;
+
+        tracing::info!(
+            "📝 Digest completed for Rei {}: {} memories -> 1 expertise",
+            rei_id,
+            memories.len()
+        );
+
+        Ok(result)
+    }
+
+    /// Roll up the digests of the previous tier from ended periods
+    ///
+    /// Returns `None` when no period has ended since the last roll-up.
+    #[tracing::instrument(name = "digest.roll_up", skip(self))]
+    pub async fn roll_up(
+        &self,
+        rei_id: Uuid,
+        tier: DigestTier,
+    ) -> Result<Option<DigestResult>, DigestError> {
+        let Some(source) = tier.source() else {
+            return Ok(None);
+        };
+
+        let rolled_up_through = self.get_watermark(rei_id, tier).await?;
+        let period_end = tier.period_start(Utc::now());
+        if rolled_up_through.is_some_and(|through| through >= period_end) {
+            return Ok(None);
+        }
+
+        let filter = SearchFilter {
+            memory_type: Some(MemoryType::Expertise),
+            tags: vec![source.tag().to_string()],
+            created_after: rolled_up_through,
+            created_before: Some(period_end),
+            ..Default::default()
+        };
+        let digests = self
+            .memory_kai
+            .scroll_memories(&rei_id.to_string(), filter)
+            .await
+            .map_err(|e| DigestError::SearchFailed(e.to_string()))?;
+
+        let mut result = DigestResult {
+            rei_id,
+            tier,
+            memories_processed: digests.len(),
+            expertise_created: false,
+            summary: format!("No {} digests to roll up", source),
+        };
+
+        if !digests.is_empty() {
+            result.summary = self.generate_roll_up(tier, &digests).await?;
+            self.store_expertise(rei_id, tier, &result.summary, &digests, 0.95)
+                .await?;
+            result.expertise_created = true;
+            self.sync_tei_expertise(&result).await;
+
+            tracing::info!(
+                "📚 {} digest for Rei {}: {} {} digests -> 1",
+                tier,
+                rei_id,
+                digests.len(),
+                source
+            );
+        }
+
+        self.update_digest_timestamp(&result, period_end).await?;
+
+        Ok(Some(result))
+    }
+
+    /// Store a digest as an Expertise memory linked to its sources
+    async fn store_expertise(
+        &self,
+        rei_id: Uuid,
+        tier: DigestTier,
+        summary: &str,
+        sources: &[Memory],
+        importance: f32,
+    ) -> Result<(), DigestError> {
         let expertise = Memory {
-            id: memory_id.to_string(),
+            id: Uuid::new_v4().to_string(),
             rei_id: rei_id.to_string(),
-            content: summary.clone(),
+            content: summary.to_string(),
             memory_type: MemoryType::Expertise,
-            importance: 0.9, // High importance for digested knowledge
-            tags: vec!["digest".to_string(), "auto_generated".to_string()],
-            metadata: None,
+            importance,
+            tags: vec![
+                "digest".to_string(),
+                tier.tag().to_string(),
+                "auto_generated".to_string(),
+            ],
+            metadata: Some(serde_json::json!({
+                "tier": tier,
+                "sources": sources.iter().map(|m| &m.id).collect::<Vec<_>>(),
+            })),
             created_at: chrono::Utc::now(),
         };
 
         let vector = self
             .embedding
-            .embed(&summary)
+            .embed(summary)
             .await
             .map_err(|e| DigestError::EmbeddingFailed(e.to_string()))?;
 
         self.memory_kai
-            .add_memory(&rei_id.to_string(), expertise, vector)
+            .add_memory(&rei_id.to_string(), expertise.clone(), vector)
             .await
             .map_err(|e| DigestError::StorageFailed(e.to_string()))?;
 
-        // 4. Update last_digest_at in state
-        self.update_digest_timestamp(rei_id).await?;
+        if let Some(event_bus) = &self.event_bus {
+            event_bus.emit(expertise.added_event(rei_id)).await;
+        }
 
-        tracing::info!(
-            "📝 Digest completed for Rei {}: {} memories -> 1 expertise",
-            rei_id,
-            memories.len()
-        );
+        Ok(())
+    }
 
-        Ok(DigestResult {
-            rei_id,
-            memories_processed: memories.len(),
-            expertise_created: true,
-            summary,
-        })
+    /// Merge a new digest into the Rei's Teis, if it opted in
+    ///
+    /// The digest is already stored, so a failure here is only logged.
+    async fn sync_tei_expertise(&self, result: &DigestResult) {
+        match tei_expertise::sync_digest(&self.pool, result).await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!(
+                "🧩 Merged {} digest into {} Tei expertise",
+                result.tier,
+                count
+            ),
+            Err(e) => tracing::warn!("⚠️  Failed to update Tei expertise: {}", e),
+        }
     }
 
     /// Get last_digest_at from rei_states
     async fn get_last_digest_at(&self, rei_id: Uuid) -> Result<Option<DateTime<Utc>>, DigestError> {
-        let result: Option<(Option<DateTime<Utc>>,)> =
-            sqlx::query_as("SELECT last_digest_at FROM rei_states WHERE rei_id = $1")
-                .bind(rei_id)
-                .fetch_optional(&self.pool)
-                .await
-                .map_err(|e| DigestError::DatabaseError(e.to_string()))?;
+        self.get_watermark(rei_id, DigestTier::Daily).await
+    }
+
+    /// How far a tier has digested
+    async fn get_watermark(
+        &self,
+        rei_id: Uuid,
+        tier: DigestTier,
+    ) -> Result<Option<DateTime<Utc>>, DigestError> {
+        let result: Option<(Option<DateTime<Utc>>,)> = sqlx::query_as(&format!(
+            "SELECT {} FROM rei_states WHERE rei_id = $1",
+            tier.watermark_column()
+        ))
+        .bind(rei_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DigestError::DatabaseError(e.to_string()))?;
 
         Ok(result.and_then(|(ts,)| ts))
     }
 
-    /// Get recent learning memories (created after last_digest_at)
+    /// Get learning memories created after last_digest_at, up to
+    /// `created_before`, oldest first
     async fn get_learning_memories(
         &self,
         rei_id: Uuid,
         last_digest_at: Option<DateTime<Utc>>,
+        created_before: DateTime<Utc>,
     ) -> Result<Vec<Memory>, DigestError> {
-        // Search for learning type memories
-        // We use a generic query to get recent learnings
-        let query_vector = self
-            .embedding
-            .embed("recent learnings and discoveries")
-            .await
-            .map_err(|e| DigestError::EmbeddingFailed(e.to_string()))?;
-
-        // Build filter: Learning type + created after last_digest_at
         let filter = SearchFilter {
             memory_type: Some(MemoryType::Learning),
             created_after: last_digest_at,
+            created_before: Some(created_before),
             ..Default::default()
         };
 
         let memories = self
             .memory_kai
-            .search_memories_with_filter(&rei_id.to_string(), query_vector, 20, filter)
+            .scroll_memories(&rei_id.to_string(), filter)
             .await
             .map_err(|e| DigestError::SearchFailed(e.to_string()))?;
 
@@ -150,9 +452,8 @@ impl DigestService {
     }
 
     /// Generate summary using Gemini
+    #[tracing::instrument(name = "llm.digest_summary", skip_all, fields(memories = memories.len()))]
     async fn generate_summary(&self, memories: &[Memory]) -> Result<String, DigestError> {
-        let api_key = self.gemini_api_key.as_ref().ok_or(DigestError::NoApiKey)?;
-
         // Build content from memories
         let memory_content: String = memories
             .iter()
@@ -176,6 +477,44 @@ Create a well-structured summary (in the same language as the memories) that con
             memory_content
         );
 
+        self.complete(prompt).await
+    }
+
+    /// Generate a roll-up of lower-tier digests using Gemini
+    #[tracing::instrument(name = "llm.digest_roll_up", skip_all, fields(digests = digests.len()))]
+    async fn generate_roll_up(
+        &self,
+        tier: DigestTier,
+        digests: &[Memory],
+    ) -> Result<String, DigestError> {
+        let digest_content: String = digests
+            .iter()
+            .map(|m| format!("### {}\n{}\n", m.created_at.format("%Y-%m-%d"), m.content))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let prompt = format!(
+            r#"You are a knowledge synthesizer. The following are consecutive knowledge digests, oldest first. Merge them into a single {} digest that:
+1. Keeps the insights that still matter and drops ones later digests superseded
+2. Shows how the topics developed over the period
+3. Removes repetition between digests
+4. Stays shorter than the digests combined
+
+## Digests:
+{}
+
+## Your Task:
+Write the {} digest (in the same language as the digests). Focus on lasting expertise rather than day-to-day detail."#,
+            tier, digest_content, tier
+        );
+
+        self.complete(prompt).await
+    }
+
+    /// Send a prompt to Gemini and return the generated text
+    async fn complete(&self, prompt: String) -> Result<String, DigestError> {
+        let api_key = self.gemini_api_key.as_ref().ok_or(DigestError::NoApiKey)?;
+
         let url = format!(
             "https://generativelanguage.googleapis.com/v1beta/models/gemini-2.0-flash:generateContent?key={}",
             api_key
@@ -217,16 +556,55 @@ Create a well-structured summary (in the same language as the memories) that con
         Ok(summary)
     }
 
-    /// Update last digest timestamp
-    async fn update_digest_timestamp(&self, rei_id: Uuid) -> Result<(), DigestError> {
-        sqlx::query(
-            "UPDATE rei_states SET last_digest_at = NOW(), last_active_at = NOW() WHERE rei_id = $1",
-        )
-        .bind(rei_id)
-        .execute(&self.pool)
+    /// Record that the tier's sources up to `digested_through` are digested
+    async fn update_digest_timestamp(
+        &self,
+        result: &DigestResult,
+        digested_through: DateTime<Utc>,
+    ) -> Result<(), DigestError> {
+        let event = ReiEvent::new(
+            result.rei_id,
+            WebhookEventType::DigestCompleted,
+            serde_json::json!({
+                "tier": result.tier,
+                "memories_processed": result.memories_processed,
+                "expertise_created": result.expertise_created,
+                "summary": result.summary,
+            }),
+        );
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DigestError::DatabaseError(e.to_string()))?;
+
+        sqlx::query(&format!(
+            "UPDATE rei_states SET {} = $1, last_active_at = NOW() WHERE rei_id = $2",
+            result.tier.watermark_column()
+        ))
+        .bind(digested_through)
+        .bind(result.rei_id)
+        .execute(&mut *tx)
         .await
         .map_err(|e| DigestError::DatabaseError(e.to_string()))?;
 
+        let unstaged = match &self.event_bus {
+            Some(event_bus) => event_bus
+                .stage_in(&mut tx, event)
+                .await
+                .map_err(|e| DigestError::DatabaseError(e.to_string()))?,
+            None => None,
+        };
+
+        tx.commit()
+            .await
+            .map_err(|e| DigestError::DatabaseError(e.to_string()))?;
+
+        if let (Some(event_bus), Some(event)) = (&self.event_bus, unstaged) {
+            event_bus.publish(event);
+        }
+
         Ok(())
     }
 }
@@ -289,3 +667,61 @@ impl std::fmt::Display for DigestError {
 }
 
 impl std::error::Error for DigestError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn learning(content: &str) -> Memory {
+        Memory {
+            id: Uuid::new_v4().to_string(),
+            rei_id: Uuid::nil().to_string(),
+            content: content.to_string(),
+            memory_type: MemoryType::Learning,
+            importance: 0.7,
+            tags: vec![],
+            metadata: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_threshold_shortfall() {
+        let threshold = DigestThreshold {
+            min_memories: 2,
+            min_chars: 10,
+        };
+        assert!(threshold
+            .shortfall(&[learning("a long enough memory")])
+            .is_some());
+        assert!(threshold
+            .shortfall(&[learning("tiny"), learning("tiny")])
+            .is_some());
+        assert_eq!(
+            threshold.shortfall(&[learning("tiny"), learning("still tiny")]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_period_start() {
+        // Thursday
+        let now = Utc.with_ymd_and_hms(2026, 3, 12, 15, 30, 0).unwrap();
+        assert_eq!(
+            DigestTier::Weekly.period_start(now),
+            Utc.with_ymd_and_hms(2026, 3, 9, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            DigestTier::Monthly.period_start(now),
+            Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_tier_hierarchy() {
+        assert_eq!(DigestTier::Daily.source(), None);
+        assert_eq!(DigestTier::Weekly.source(), Some(DigestTier::Daily));
+        assert_eq!(DigestTier::Monthly.source(), Some(DigestTier::Weekly));
+    }
+}
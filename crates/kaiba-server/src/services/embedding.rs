@@ -40,6 +40,7 @@ impl EmbeddingService {
     }
 
     /// Generate embedding for text
+    #[tracing::instrument(name = "embedding.embed", skip_all, fields(model = %self.model, chars = text.len()))]
     pub async fn embed(
         &self,
         text: &str,
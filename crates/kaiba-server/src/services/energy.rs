@@ -0,0 +1,110 @@
+//! Energy Regeneration - Reis recover energy with the time that passed
+//!
+//! Each Rei regains `energy_regen_per_hour` for every hour since energy was
+//! last regenerated, however long ago that was, so a deployment that slept
+//! for six hours credits six hours on its next cycle or trigger. Only whole
+//! points are credited and the leftover time carries over; time spent at
+//! full energy is not banked.
+
+use chrono::{DateTime, Duration, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Energy cap of every Rei
+const MAX_ENERGY: i32 = 100;
+
+/// Regenerate energy for the given Reis (all when `None`)
+///
+/// Returns how many Reis gained energy.
+pub async fn regenerate(pool: &PgPool, rei_ids: Option<&[Uuid]>) -> Result<u64, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    // Reis never regenerated before start from their last activity
+    let states: Vec<(Uuid, i32, i32, DateTime<Utc>)> = sqlx::query_as(
+        r#"
+        SELECT rei_id, energy_level, energy_regen_per_hour,
+               COALESCE(energy_regenerated_at, last_active_at, updated_at)
+        FROM rei_states
+        WHERE energy_regen_per_hour > 0
+          AND ($1::uuid[] IS NULL OR rei_id = ANY($1))
+        FOR UPDATE
+        "#,
+    )
+    .bind(rei_ids)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let now = Utc::now();
+    let mut regenerated = 0;
+    for (rei_id, energy, per_hour, since) in states {
+        let (gained, used) = regen_for(now - since, per_hour);
+        let (energy_level, regenerated_at) = if energy + gained >= MAX_ENERGY {
+            (MAX_ENERGY, now)
+        } else {
+            (energy + gained, since + used)
+        };
+        if regenerated_at == since {
+            continue;
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE rei_states
+            SET energy_level = $2, energy_regenerated_at = $3, updated_at = NOW()
+            WHERE rei_id = $1
+            "#,
+        )
+        .bind(rei_id)
+        .bind(energy_level)
+        .bind(regenerated_at)
+        .execute(&mut *tx)
+        .await?;
+        if energy_level > energy {
+            regenerated += 1;
+        }
+    }
+    tx.commit().await?;
+
+    Ok(regenerated)
+}
+
+/// Whole energy points earned over `elapsed` at `per_hour`, and the part of
+/// `elapsed` they account for
+fn regen_for(elapsed: Duration, per_hour: i32) -> (i32, Duration) {
+    if per_hour <= 0 || elapsed <= Duration::zero() {
+        return (0, Duration::zero());
+    }
+    let gained = elapsed.num_seconds() * per_hour as i64 / 3600;
+    let used = Duration::seconds(gained * 3600 / per_hour as i64);
+    (gained.min(i32::MAX as i64) as i32, used)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regen_scales_with_elapsed_time() {
+        assert_eq!(regen_for(Duration::hours(6), 10), (60, Duration::hours(6)));
+        assert_eq!(
+            regen_for(Duration::minutes(30), 10),
+            (5, Duration::minutes(30))
+        );
+    }
+
+    #[test]
+    fn test_regen_carries_over_partial_points() {
+        // 10 minutes at 10/h is 1.67 points: 1 point, 6 minutes used
+        assert_eq!(
+            regen_for(Duration::minutes(10), 10),
+            (1, Duration::minutes(6))
+        );
+        assert_eq!(regen_for(Duration::minutes(5), 10), (0, Duration::zero()));
+    }
+
+    #[test]
+    fn test_regen_ignores_disabled_and_clock_skew() {
+        assert_eq!(regen_for(Duration::hours(3), 0), (0, Duration::zero()));
+        assert_eq!(regen_for(Duration::hours(-1), 10), (0, Duration::zero()));
+    }
+}
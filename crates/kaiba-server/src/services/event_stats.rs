@@ -0,0 +1,51 @@
+//! Event Stats - In-memory event counters per Rei
+//!
+//! Subscribes to the event bus and counts events by type.
+//! Counters reset on server restart.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use kaiba::{DomainError, ReiEvent, ReiEventSubscriber};
+
+/// Event counter subscriber
+#[derive(Default)]
+pub struct EventStats {
+    counts: Mutex<HashMap<Uuid, HashMap<String, u64>>>,
+}
+
+impl EventStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Event counts by type for a Rei (since server start)
+    pub fn counts_for(&self, rei_id: Uuid) -> HashMap<String, u64> {
+        self.counts
+            .lock()
+            .expect("Event stats lock poisoned")
+            .get(&rei_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl ReiEventSubscriber for EventStats {
+    fn name(&self) -> &str {
+        "event_stats"
+    }
+
+    async fn handle(&self, event: &ReiEvent) -> Result<(), DomainError> {
+        let mut counts = self.counts.lock().expect("Event stats lock poisoned");
+        *counts
+            .entry(event.rei_id)
+            .or_default()
+            .entry(event.event_type.to_string())
+            .or_insert(0) += 1;
+        Ok(())
+    }
+}
@@ -0,0 +1,254 @@
+//! Event Stream - Live Rei events for Server-Sent Events clients
+//!
+//! Subscribes to the event bus and fans events out over a broadcast channel
+//! to every open `GET /kaiba/rei/{id}/events` stream. Webhook delivery
+//! results are added by the webhook publisher. Nothing is buffered for
+//! clients that connect later; a client that falls behind is told how many
+//! events it missed.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use axum::response::sse::Event;
+use chrono::{DateTime, Utc};
+use futures_util::Stream;
+use serde::Serialize;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use kaiba::{DomainError, ReiEvent, ReiEventSubscriber, WebhookDelivery};
+
+/// Events buffered per slow client before it starts missing some
+const CHANNEL_CAPACITY: usize = 256;
+
+/// Interval of SSE keep-alive comments, so proxies keep idle streams open
+pub const KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+/// SSE event name for webhook delivery results
+pub const DELIVERY_EVENT: &str = "webhook_delivery";
+
+/// One event as sent to stream clients
+#[derive(Debug, Clone, Serialize)]
+pub struct StreamEvent {
+    pub id: Uuid,
+    pub rei_id: Uuid,
+    /// Event type (`memory_added`, `state_changed`, ..., `webhook_delivery`)
+    pub event: String,
+    pub data: serde_json::Value,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl StreamEvent {
+    fn to_sse(&self) -> Event {
+        Event::default()
+            .id(self.id.to_string())
+            .event(&self.event)
+            .json_data(self)
+            .unwrap_or_else(|_| Event::default().comment("unserializable event"))
+    }
+}
+
+/// Broadcasts events to open streams
+pub struct EventStream {
+    sender: broadcast::Sender<StreamEvent>,
+    closed: CancellationToken,
+}
+
+impl Default for EventStream {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventStream {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self {
+            sender,
+            closed: CancellationToken::new(),
+        }
+    }
+
+    /// Publish the result of a webhook delivery
+    pub fn delivery(&self, rei_id: Uuid, webhook_name: &str, delivery: &WebhookDelivery) {
+        self.send(StreamEvent {
+            id: delivery.id,
+            rei_id,
+            event: DELIVERY_EVENT.to_string(),
+            data: serde_json::json!({
+                "webhook_id": delivery.webhook_id,
+                "webhook_name": webhook_name,
+                "event": delivery.payload.event.to_string(),
+                "status": delivery.status,
+                "status_code": delivery.status_code,
+                "attempts": delivery.attempts,
+            }),
+            occurred_at: delivery.completed_at.unwrap_or(delivery.created_at),
+        });
+    }
+
+    fn send(&self, event: StreamEvent) {
+        // Err only means nobody is listening
+        let _ = self.sender.send(event);
+    }
+
+    /// End all open streams, so graceful shutdown doesn't wait on them
+    pub fn close(&self) {
+        self.closed.cancel();
+    }
+
+    /// Live SSE events for one Rei, optionally limited to some event types
+    pub fn subscribe(
+        &self,
+        rei_id: Uuid,
+        types: Option<Vec<String>>,
+    ) -> impl Stream<Item = Result<Event, std::convert::Infallible>> {
+        let receiver = self.sender.subscribe();
+        let closed = self.closed.clone();
+
+        futures_util::stream::unfold((receiver, closed), move |(mut receiver, closed)| {
+            let types = types.clone();
+            async move {
+                loop {
+                    let received = tokio::select! {
+                        biased;
+                        _ = closed.cancelled() => return None,
+                        received = receiver.recv() => received,
+                    };
+                    let sse = match received {
+                        Ok(event) if event.rei_id != rei_id => continue,
+                        Ok(event) => {
+                            let wanted = match &types {
+                                Some(types) => types.contains(&event.event),
+                                None => true,
+                            };
+                            if !wanted {
+                                continue;
+                            }
+                            event.to_sse()
+                        }
+                        Err(RecvError::Lagged(missed)) => {
+                            Event::default().event("lagged").data(missed.to_string())
+                        }
+                        Err(RecvError::Closed) => return None,
+                    };
+                    return Some((Ok(sse), (receiver, closed)));
+                }
+            }
+        })
+    }
+}
+
+#[async_trait]
+impl ReiEventSubscriber for EventStream {
+    fn name(&self) -> &str {
+        "event_stream"
+    }
+
+    fn interested_in(&self, _event: &ReiEvent) -> bool {
+        self.sender.receiver_count() > 0
+    }
+
+    async fn handle(&self, event: &ReiEvent) -> Result<(), DomainError> {
+        self.send(StreamEvent {
+            id: event.id,
+            rei_id: event.rei_id,
+            event: event.event_type.to_string(),
+            data: event.data.clone(),
+            occurred_at: event.occurred_at,
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+    use kaiba::WebhookEventType;
+
+    #[tokio::test]
+    async fn test_streams_only_own_rei_and_types() {
+        let stream = EventStream::new();
+        let rei_id = Uuid::new_v4();
+        let events = stream.subscribe(rei_id, Some(vec!["memory_added".to_string()]));
+        tokio::pin!(events);
+
+        for event in [
+            ReiEvent::new(
+                Uuid::new_v4(),
+                WebhookEventType::MemoryAdded,
+                serde_json::json!({}),
+            ),
+            ReiEvent::new(
+                rei_id,
+                WebhookEventType::StateChanged,
+                serde_json::json!({}),
+            ),
+            ReiEvent::new(
+                rei_id,
+                WebhookEventType::MemoryAdded,
+                serde_json::json!({"n": 1}),
+            ),
+        ] {
+            stream.handle(&event).await.unwrap();
+        }
+
+        assert!(events.next().await.is_some());
+        stream.close();
+        assert!(events.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_state_changed_reaches_stream() {
+        use std::sync::Arc;
+
+        use crate::adapters::InProcessEventBus;
+        use crate::models::ReiStateResponse;
+
+        let stream = Arc::new(EventStream::new());
+        let mut received = stream.sender.subscribe();
+        let bus = InProcessEventBus::new().with_subscriber(stream.clone());
+
+        let rei_id = Uuid::new_v4();
+        let state = ReiStateResponse {
+            energy_level: 42,
+            mood: "calm".to_string(),
+            token_budget: 1000,
+            tokens_used: 10,
+            last_active_at: None,
+            energy_regen_per_hour: 5,
+            last_digest_at: None,
+            last_learn_at: None,
+            autonomy_enabled: false,
+        };
+        bus.emit(state.changed_event(rei_id, "autonomy_paused"))
+            .await;
+
+        let event = tokio::time::timeout(Duration::from_secs(1), received.recv())
+            .await
+            .expect("no event streamed")
+            .unwrap();
+        assert_eq!(event.rei_id, rei_id);
+        assert_eq!(event.event, "state_changed");
+        assert_eq!(event.data["reason"], "autonomy_paused");
+        assert_eq!(event.data["energy_level"], 42);
+        assert_eq!(event.data["mood"], "calm");
+        assert_eq!(event.data["autonomy_enabled"], false);
+    }
+
+    #[test]
+    fn test_no_dispatch_without_listeners() {
+        let stream = EventStream::new();
+        let event = ReiEvent::new(
+            Uuid::new_v4(),
+            WebhookEventType::MemoryAdded,
+            serde_json::json!({}),
+        );
+        assert!(!stream.interested_in(&event));
+
+        let _events = stream.subscribe(event.rei_id, None);
+        assert!(stream.interested_in(&event));
+    }
+}
@@ -0,0 +1,354 @@
+//! Feed Source - RSS and Atom feeds as a learning source
+//!
+//! A Rei lists feed URLs under `feeds` in its manifest. Each learning session
+//! reads the feeds, takes the newest entries it has not learned from yet,
+//! and has Gemini summarize them. Learned entries are remembered in
+//! `learning_source_items`.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::models::Rei;
+use crate::services::learning_source::{LearningSource, SourceItem, SourcePull};
+
+/// Manifest key listing a Rei's feed URLs
+pub const FEEDS_MANIFEST_KEY: &str = "feeds";
+
+const SOURCE_NAME: &str = "rss";
+
+const GEMINI_URL: &str =
+    "https://generativelanguage.googleapis.com/v1beta/models/gemini-2.0-flash:generateContent";
+
+/// How long to wait for a feed
+const FETCH_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Entry text passed to the summarizer, in characters
+const MAX_ENTRY_CHARS: usize = 8_000;
+
+/// RSS/Atom adapter
+pub struct FeedSource {
+    pool: PgPool,
+    client: Client,
+    api_key: String,
+}
+
+/// Feed entry not yet summarized
+struct FeedEntry {
+    key: String,
+    title: String,
+    text: String,
+    url: Option<String>,
+    feed_url: String,
+    published_at: Option<DateTime<Utc>>,
+}
+
+impl FeedSource {
+    pub fn new(pool: PgPool, api_key: impl Into<String>) -> Self {
+        Self {
+            pool,
+            client: Client::builder()
+                .timeout(FETCH_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+            api_key: api_key.into(),
+        }
+    }
+
+    /// Entries of one feed
+    async fn fetch(&self, feed_url: &str) -> Result<Vec<FeedEntry>, String> {
+        let response = self
+            .client
+            .get(feed_url)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("returned {}", response.status()));
+        }
+        let body = response.bytes().await.map_err(|e| e.to_string())?;
+        let feed = feed_rs::parser::parse(&body[..]).map_err(|e| e.to_string())?;
+
+        Ok(feed
+            .entries
+            .into_iter()
+            .map(|entry| {
+                let text = entry
+                    .content
+                    .and_then(|c| c.body)
+                    .or(entry.summary.map(|s| s.content))
+                    .unwrap_or_default();
+                FeedEntry {
+                    key: entry.id,
+                    title: entry
+                        .title
+                        .map(|t| t.content)
+                        .unwrap_or_else(|| "(untitled)".to_string()),
+                    text: plain_text(&text),
+                    url: entry.links.into_iter().next().map(|l| l.href),
+                    feed_url: feed_url.to_string(),
+                    published_at: entry.published.or(entry.updated),
+                }
+            })
+            .collect())
+    }
+
+    /// Keys among `keys` the Rei already learned from
+    async fn learned_keys(
+        &self,
+        rei_id: Uuid,
+        keys: &[String],
+    ) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT item_key FROM learning_source_items
+            WHERE rei_id = $1 AND source = $2 AND item_key = ANY($3)
+            "#,
+        )
+        .bind(rei_id)
+        .bind(SOURCE_NAME)
+        .bind(keys)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Summarize an entry with Gemini, returning the summary and tokens used
+    #[tracing::instrument(name = "llm.summarize_entry", skip_all)]
+    async fn summarize(&self, entry: &FeedEntry) -> Result<(String, i32), String> {
+        let text: String = entry.text.chars().take(MAX_ENTRY_CHARS).collect();
+        let prompt = format!(
+            r#"Summarize this article for a knowledge base in 3-5 sentences.
+Keep concrete facts, names and numbers; leave out promotion and navigation text.
+
+Title: {}
+
+{}"#,
+            entry.title, text
+        );
+
+        let request = GeminiRequest {
+            contents: vec![GeminiContent {
+                parts: vec![GeminiPart { text: prompt }],
+            }],
+        };
+        let response = self
+            .client
+            .post(format!("{}?key={}", GEMINI_URL, self.api_key))
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("Gemini returned {}", response.status()));
+        }
+
+        let result: GeminiResponse = response.json().await.map_err(|e| e.to_string())?;
+        let summary = result
+            .candidates
+            .first()
+            .and_then(|c| c.content.parts.first())
+            .map(|p| p.text.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .ok_or("empty summary")?;
+        let tokens = result.usage_metadata.map_or(0, |u| u.total_token_count);
+
+        Ok((summary, tokens))
+    }
+}
+
+#[async_trait]
+impl LearningSource for FeedSource {
+    fn name(&self) -> &'static str {
+        SOURCE_NAME
+    }
+
+    async fn pull(&self, rei: &Rei, limit: usize) -> SourcePull {
+        let mut pull = SourcePull::default();
+        let feed_urls: Vec<&str> = rei
+            .manifest
+            .get(FEEDS_MANIFEST_KEY)
+            .and_then(|v| v.as_array())
+            .map(|urls| urls.iter().filter_map(|u| u.as_str()).collect())
+            .unwrap_or_default();
+        if feed_urls.is_empty() || limit == 0 {
+            return pull;
+        }
+
+        let mut entries = Vec::new();
+        for feed_url in feed_urls {
+            match self.fetch(feed_url).await {
+                Ok(feed_entries) => entries.extend(feed_entries),
+                Err(e) => pull.errors.push(format!("Feed '{}': {}", feed_url, e)),
+            }
+        }
+
+        let keys: Vec<String> = entries.iter().map(|e| e.key.clone()).collect();
+        let learned = match self.learned_keys(rei.id, &keys).await {
+            Ok(learned) => learned,
+            Err(e) => {
+                pull.errors.push(format!("Feeds: {}", e));
+                return pull;
+            }
+        };
+        let entries = newest_unlearned(entries, &learned, limit);
+
+        for entry in entries {
+            match self.summarize(&entry).await {
+                Ok((summary, tokens_used)) => pull.items.push(SourceItem {
+                    key: entry.key,
+                    title: entry.title,
+                    summary,
+                    url: entry.url,
+                    origin: entry.feed_url,
+                    published_at: entry.published_at,
+                    tokens_used,
+                }),
+                Err(e) => pull
+                    .errors
+                    .push(format!("Feed entry '{}': {}", entry.title, e)),
+            }
+        }
+
+        pull
+    }
+
+    async fn mark_learned(&self, rei_id: Uuid, item: &SourceItem) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO learning_source_items (rei_id, source, item_key, url)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (rei_id, source, item_key) DO NOTHING
+            "#,
+        )
+        .bind(rei_id)
+        .bind(SOURCE_NAME)
+        .bind(&item.key)
+        .bind(&item.url)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
+
+/// Up to `limit` entries not in `learned`, newest first, each key once
+fn newest_unlearned(
+    mut entries: Vec<FeedEntry>,
+    learned: &[String],
+    limit: usize,
+) -> Vec<FeedEntry> {
+    entries.retain(|e| !learned.contains(&e.key));
+    // Undated entries go last
+    entries.sort_by_key(|e| std::cmp::Reverse(e.published_at));
+    let mut seen = std::collections::HashSet::new();
+    entries.retain(|e| seen.insert(e.key.clone()));
+    entries.truncate(limit);
+    entries
+}
+
+/// Strip HTML tags and collapse whitespace
+fn plain_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => {
+                in_tag = true;
+                text.push(' ');
+            }
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// Gemini API types
+#[derive(Serialize)]
+struct GeminiRequest {
+    contents: Vec<GeminiContent>,
+}
+
+#[derive(Serialize)]
+struct GeminiContent {
+    parts: Vec<GeminiPart>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct GeminiPart {
+    text: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiResponse {
+    candidates: Vec<GeminiCandidate>,
+    usage_metadata: Option<GeminiUsage>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiUsage {
+    #[serde(default)]
+    total_token_count: i32,
+}
+
+#[derive(Deserialize)]
+struct GeminiCandidate {
+    content: GeminiContentResponse,
+}
+
+#[derive(Deserialize)]
+struct GeminiContentResponse {
+    parts: Vec<GeminiPart>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn entry(key: &str, day: Option<u32>) -> FeedEntry {
+        FeedEntry {
+            key: key.to_string(),
+            title: key.to_string(),
+            text: String::new(),
+            url: None,
+            feed_url: "https://example.com/feed.xml".to_string(),
+            published_at: day.map(|d| Utc.with_ymd_and_hms(2026, 3, d, 0, 0, 0).unwrap()),
+        }
+    }
+
+    #[test]
+    fn test_newest_unlearned() {
+        let entries = vec![
+            entry("a", Some(1)),
+            entry("b", None),
+            entry("c", Some(3)),
+            entry("d", Some(2)),
+            entry("c", Some(3)),
+        ];
+        let picked = newest_unlearned(entries, &["d".to_string()], 2);
+        let keys: Vec<&str> = picked.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, vec!["c", "a"]);
+    }
+
+    #[test]
+    fn test_plain_text() {
+        assert_eq!(
+            plain_text("<p>Rust&nbsp;1.80 is <b>out</b> &amp; stable</p>\n<br/>"),
+            "Rust 1.80 is out & stable"
+        );
+    }
+}
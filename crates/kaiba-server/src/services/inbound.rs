@@ -0,0 +1,316 @@
+//! Inbound - Convert external webhook payloads into memory items
+//!
+//! Supported sources:
+//! - github: push events become one item per commit; other events are summarized
+//! - rss: JSON Feed (`items[].title`, `url`, `content_text` / `summary`)
+//! - anything else: generic JSON `{"content": ..., "tags": [...], "importance": ...}`,
+//!   or an array of those; other bodies are stored verbatim
+
+use kaiba::{VerificationError, WebhookHeaders, WebhookVerifier};
+
+/// Maximum items taken from a single payload
+const MAX_ITEMS: usize = 20;
+
+/// Headers carrying an `sha256=` HMAC signature of the raw body
+const SIGNATURE_HEADERS: [&str; 2] = ["x-hub-signature-256", "x-kaiba-signature"];
+
+/// Header carrying the secret itself, for services that cannot sign
+const TOKEN_HEADER: &str = "x-kaiba-token";
+
+/// A piece of external content to be stored as a memory
+#[derive(Debug, Clone)]
+pub struct InboundItem {
+    pub content: String,
+    pub tags: Vec<String>,
+    pub importance: Option<f32>,
+    pub metadata: serde_json::Value,
+}
+
+/// Parse an inbound payload according to its source
+///
+/// `event` is the source-specific event header (e.g., `X-GitHub-Event`).
+pub fn parse_payload(
+    source: &str,
+    event: Option<&str>,
+    body: &serde_json::Value,
+) -> Vec<InboundItem> {
+    let mut items = match source {
+        "github" => parse_github(event.unwrap_or("unknown"), body),
+        "rss" => parse_json_feed(body),
+        _ => parse_generic(body),
+    };
+
+    for item in items.iter_mut() {
+        item.tags.insert(0, format!("inbound:{}", source));
+    }
+    items.truncate(MAX_ITEMS);
+    items
+}
+
+/// Verify an `sha256=<hex>` HMAC signature of the raw body
+pub fn verify_signature(secret: &str, body: &[u8], signature: &str) -> bool {
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+
+    type HmacSha256 = Hmac<Sha256>;
+
+    let Some(Ok(expected)) = signature.strip_prefix("sha256=").map(hex::decode) else {
+        return false;
+    };
+
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC can take key of any size");
+    mac.update(body);
+    mac.verify_slice(&expected).is_ok()
+}
+
+/// Compare a shared token without short-circuiting on the first mismatch
+pub fn verify_token(secret: &str, token: &str) -> bool {
+    secret.len() == token.len()
+        && secret
+            .bytes()
+            .zip(token.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+/// Checks payloads against an inbound source's secret
+///
+/// A signature header wins over the token header when both are sent.
+pub struct SourceVerifier<'a> {
+    secret: &'a str,
+}
+
+impl<'a> SourceVerifier<'a> {
+    pub fn new(secret: &'a str) -> Self {
+        Self { secret }
+    }
+}
+
+impl WebhookVerifier for SourceVerifier<'_> {
+    fn verify(&self, headers: &WebhookHeaders, body: &[u8]) -> Result<(), VerificationError> {
+        let verified = match SIGNATURE_HEADERS.iter().find_map(|name| headers.get(name)) {
+            Some(signature) => verify_signature(self.secret, body, signature),
+            None => verify_token(self.secret, headers.require(TOKEN_HEADER)?),
+        };
+        if verified {
+            Ok(())
+        } else {
+            Err(VerificationError::InvalidSignature)
+        }
+    }
+}
+
+fn parse_github(event: &str, body: &serde_json::Value) -> Vec<InboundItem> {
+    let repo = body["repository"]["full_name"]
+        .as_str()
+        .unwrap_or("unknown")
+        .to_string();
+
+    if event == "push" {
+        let branch = body["ref"]
+            .as_str()
+            .map(|r| r.trim_start_matches("refs/heads/"))
+            .unwrap_or("unknown");
+
+        return body["commits"]
+            .as_array()
+            .map(|commits| {
+                commits
+                    .iter()
+                    .map(|commit| InboundItem {
+                        content: format!(
+                            "[{}@{}] {}: {}",
+                            repo,
+                            branch,
+                            commit["author"]["name"].as_str().unwrap_or("unknown"),
+                            commit["message"].as_str().unwrap_or_default()
+                        ),
+                        tags: vec!["github".to_string(), "push".to_string()],
+                        importance: None,
+                        metadata: serde_json::json!({
+                            "repository": repo,
+                            "branch": branch,
+                            "commit": commit["id"],
+                            "url": commit["url"],
+                        }),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+    }
+
+    // Issues, pull requests, releases, ...: one summary item
+    let subject = ["issue", "pull_request", "release", "discussion"]
+        .iter()
+        .find_map(|key| body.get(*key))
+        .map(|obj| {
+            (
+                obj["title"].as_str().or(obj["name"].as_str()),
+                obj["html_url"].clone(),
+            )
+        });
+
+    let action = body["action"].as_str();
+    let mut content = format!("GitHub {} on {}", event, repo);
+    if let Some(action) = action {
+        content.push_str(&format!(" ({})", action));
+    }
+    let url = match subject {
+        Some((title, url)) => {
+            if let Some(title) = title {
+                content.push_str(&format!(": {}", title));
+            }
+            url
+        }
+        None => serde_json::Value::Null,
+    };
+
+    vec![InboundItem {
+        content,
+        tags: vec!["github".to_string(), event.to_string()],
+        importance: None,
+        metadata: serde_json::json!({
+            "repository": repo,
+            "event": event,
+            "action": action,
+            "url": url,
+        }),
+    }]
+}
+
+fn parse_json_feed(body: &serde_json::Value) -> Vec<InboundItem> {
+    let feed_title = body["title"].as_str();
+
+    body["items"]
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| {
+                    let title = item["title"].as_str();
+                    let text = item["content_text"].as_str().or(item["summary"].as_str());
+                    let content = match (title, text) {
+                        (Some(t), Some(s)) => format!("{}\n\n{}", t, s),
+                        (Some(t), None) => t.to_string(),
+                        (None, Some(s)) => s.to_string(),
+                        (None, None) => return None,
+                    };
+
+                    Some(InboundItem {
+                        content,
+                        tags: vec!["rss".to_string()],
+                        importance: None,
+                        metadata: serde_json::json!({
+                            "feed": feed_title,
+                            "url": item["url"],
+                            "published": item["date_published"],
+                        }),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn parse_generic(body: &serde_json::Value) -> Vec<InboundItem> {
+    match body {
+        serde_json::Value::Array(entries) => entries.iter().filter_map(generic_item).collect(),
+        other => match generic_item(other) {
+            Some(item) => vec![item],
+            // Unknown shape: keep the whole body
+            None => vec![InboundItem {
+                content: other.to_string(),
+                tags: Vec::new(),
+                importance: None,
+                metadata: serde_json::Value::Null,
+            }],
+        },
+    }
+}
+
+fn generic_item(entry: &serde_json::Value) -> Option<InboundItem> {
+    let content = entry["content"].as_str()?.to_string();
+
+    Some(InboundItem {
+        content,
+        tags: entry["tags"]
+            .as_array()
+            .map(|tags| {
+                tags.iter()
+                    .filter_map(|t| t.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        importance: entry["importance"].as_f64().map(|v| v as f32),
+        metadata: entry
+            .get("metadata")
+            .cloned()
+            .unwrap_or(serde_json::Value::Null),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_payloads() {
+        let push = serde_json::json!({
+            "ref": "refs/heads/main",
+            "repository": { "full_name": "ynishi/kaiba" },
+            "commits": [
+                { "id": "abc", "message": "Fix bug", "author": { "name": "yn" } },
+                { "id": "def", "message": "Add test", "author": { "name": "yn" } }
+            ]
+        });
+        let items = parse_payload("github", Some("push"), &push);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].content, "[ynishi/kaiba@main] yn: Fix bug");
+        assert_eq!(items[0].tags[0], "inbound:github");
+
+        let feed = serde_json::json!({
+            "title": "Blog",
+            "items": [{ "title": "Post", "content_text": "Body", "url": "https://x" }, {}]
+        });
+        let items = parse_payload("rss", None, &feed);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].content, "Post\n\nBody");
+
+        let generic = serde_json::json!({ "content": "Deployed v2", "tags": ["deploy"] });
+        let items = parse_payload("ci", None, &generic);
+        assert_eq!(items[0].content, "Deployed v2");
+        assert_eq!(items[0].tags, vec!["inbound:ci", "deploy"]);
+    }
+
+    #[test]
+    fn test_verify() {
+        use hmac::{Hmac, Mac};
+        use sha2::Sha256;
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"secret").unwrap();
+        mac.update(b"body");
+        let signature = format!("sha256={}", hex::encode(mac.finalize().into_bytes()));
+
+        assert!(verify_signature("secret", b"body", &signature));
+        assert!(!verify_signature("other", b"body", &signature));
+        assert!(!verify_signature("secret", b"body", "sha256=zz"));
+
+        assert!(verify_token("secret", "secret"));
+        assert!(!verify_token("secret", "secrex"));
+
+        let verifier = SourceVerifier::new("secret");
+        let signed = WebhookHeaders::new().with("X-Kaiba-Signature", signature);
+        assert_eq!(verifier.verify(&signed, b"body"), Ok(()));
+        assert_eq!(
+            verifier.verify(&signed.with("X-Kaiba-Token", "secret"), b"tampered"),
+            Err(VerificationError::InvalidSignature)
+        );
+        let token = WebhookHeaders::new().with("X-Kaiba-Token", "secret");
+        assert_eq!(verifier.verify(&token, b"anything"), Ok(()));
+        assert!(matches!(
+            verifier.verify(&WebhookHeaders::new(), b"body"),
+            Err(VerificationError::MissingHeader(_))
+        ));
+    }
+}
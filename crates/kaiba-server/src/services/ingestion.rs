@@ -0,0 +1,428 @@
+//! Message Ingestion - Integration conversations become memories
+//!
+//! For every Rei that opted in (`ingest` on its `rei_integrations` row),
+//! the ingester reads what the integration sees for the Rei every few
+//! minutes, has Gemini summarize the messages newer than the last run into
+//! short facts and learnings, and stores them as memories carrying their
+//! platform provenance. Conversations too short to summarize wait for the
+//! next run.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use kaiba::Message;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::models::{Memory, MemoryType, ReiIntegration};
+use crate::services::embedding::EmbeddingService;
+use crate::services::integration_registry;
+use crate::services::qdrant::MemoryKai;
+use crate::AppState;
+
+const GEMINI_URL: &str =
+    "https://generativelanguage.googleapis.com/v1beta/models/gemini-2.0-flash:generateContent";
+
+/// How often to read opted-in conversations
+const INGEST_INTERVAL: Duration = Duration::from_secs(900);
+
+/// Fewest new messages worth summarizing
+const MIN_MESSAGES: usize = 3;
+
+/// Most messages summarized in one run (the oldest are taken first)
+const MAX_MESSAGES: usize = 50;
+
+/// Longest message excerpt shown to Gemini
+const MESSAGE_EXCERPT_CHARS: usize = 500;
+
+/// Most memories stored from one summary
+const MAX_NOTES: usize = 8;
+
+/// A memory-worthy line of a summary
+#[derive(Debug, Clone, PartialEq)]
+struct Note {
+    memory_type: MemoryType,
+    content: String,
+}
+
+/// Summarizes opted-in integration conversations into memories
+pub struct MessageIngester {
+    state: AppState,
+    memory_kai: Arc<MemoryKai>,
+    embedding: EmbeddingService,
+    client: Client,
+    gemini_api_key: String,
+}
+
+impl MessageIngester {
+    pub fn new(
+        state: AppState,
+        memory_kai: Arc<MemoryKai>,
+        embedding: EmbeddingService,
+        gemini_api_key: String,
+    ) -> Self {
+        Self {
+            state,
+            memory_kai,
+            embedding,
+            client: Client::new(),
+            gemini_api_key,
+        }
+    }
+
+    /// Run the ingester until shutdown
+    pub async fn run(self: Arc<Self>, shutdown: CancellationToken) {
+        let mut ticker = tokio::time::interval(INGEST_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {}
+                _ = shutdown.cancelled() => return,
+            }
+            self.ingest_all().await;
+        }
+    }
+
+    async fn ingest_all(&self) {
+        let switches = match integration_registry::ingesting(&self.state.pool).await {
+            Ok(switches) => switches,
+            Err(e) => {
+                tracing::warn!("⚠️  Failed to load ingestion switches: {}", e);
+                return;
+            }
+        };
+
+        for switch in switches {
+            match self.ingest(&switch).await {
+                Ok(0) => {}
+                Ok(stored) => tracing::info!(
+                    "📥 Ingested {} memories from {} for Rei {}",
+                    stored,
+                    switch.integration,
+                    switch.rei_id
+                ),
+                Err(e) => tracing::warn!(
+                    "⚠️  Ingestion from {} failed for Rei {}: {}",
+                    switch.integration,
+                    switch.rei_id,
+                    e
+                ),
+            }
+        }
+    }
+
+    /// Summarize one Rei's new messages; returns the memories stored
+    #[tracing::instrument(name = "ingest", skip_all, fields(rei_id = %switch.rei_id, integration = %switch.integration))]
+    async fn ingest(&self, switch: &ReiIntegration) -> Result<usize, IngestionError> {
+        // Integrations configured on an earlier deployment are skipped
+        let Some(integration) = self.state.integrations.get(&switch.integration) else {
+            return Ok(0);
+        };
+        let Some((rei, _)) = self
+            .state
+            .rei_service
+            .get_by_id(switch.rei_id)
+            .await
+            .map_err(|e| IngestionError::DatabaseError(e.to_string()))?
+        else {
+            return Ok(0);
+        };
+
+        let messages = integration
+            .read_messages(&rei)
+            .await
+            .map_err(|e| IngestionError::ReadFailed(e.to_string()))?;
+        let messages = new_messages(messages, switch.ingested_until);
+        if messages.len() < MIN_MESSAGES {
+            return Ok(0);
+        }
+
+        let summary = self.summarize(&rei.name, &messages).await?;
+        let notes = parse_notes(&summary);
+        for note in &notes {
+            self.store_note(rei.id, note, &switch.integration, &messages)
+                .await?;
+        }
+
+        // Only advance past messages whose notes are stored
+        let until = messages.last().map_or(Utc::now(), |m| m.timestamp);
+        integration_registry::set_ingested_until(
+            &self.state.pool,
+            rei.id,
+            &switch.integration,
+            until,
+        )
+        .await
+        .map_err(|e| IngestionError::DatabaseError(e.to_string()))?;
+
+        Ok(notes.len())
+    }
+
+    /// Summarize the messages into FACT/LEARNING lines using Gemini
+    #[tracing::instrument(name = "llm.ingestion", skip_all, fields(messages = messages.len()))]
+    async fn summarize(
+        &self,
+        rei_name: &str,
+        messages: &[Message],
+    ) -> Result<String, IngestionError> {
+        let transcript: String = messages
+            .iter()
+            .map(|m| {
+                let excerpt: String = m.content.chars().take(MESSAGE_EXCERPT_CHARS).collect();
+                format!(
+                    "[{}] {}: {}",
+                    m.timestamp.format("%Y-%m-%d %H:%M"),
+                    m.author_name,
+                    excerpt
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let prompt = format!(
+            r#"You are helping {} remember a conversation it took part in or read.
+
+## Conversation (oldest first):
+{}
+
+## Your Task:
+Extract at most {} things worth remembering long-term, in the same language as the conversation. Write one per line, each starting with either:
+- FACT: a concrete fact (who prefers what, decisions made, dates, names)
+- LEARNING: an insight or lesson that came out of the discussion
+
+Skip greetings, small talk and anything only relevant in the moment. If nothing is worth remembering, answer NONE."#,
+            rei_name, transcript, MAX_NOTES
+        );
+
+        let request = GeminiRequest {
+            contents: vec![GeminiContent {
+                parts: vec![GeminiPart { text: prompt }],
+            }],
+        };
+
+        let response = self
+            .client
+            .post(format!("{}?key={}", GEMINI_URL, self.gemini_api_key))
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| IngestionError::ApiError(e.to_string()))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(IngestionError::ApiError(format!("{}: {}", status, body)));
+        }
+
+        let result: GeminiResponse = response
+            .json()
+            .await
+            .map_err(|e| IngestionError::ParseError(e.to_string()))?;
+
+        result
+            .candidates
+            .first()
+            .and_then(|c| c.content.parts.first())
+            .map(|p| p.text.clone())
+            .ok_or_else(|| IngestionError::ParseError("empty summary".to_string()))
+    }
+
+    /// Store a note as a memory pointing back at its messages
+    async fn store_note(
+        &self,
+        rei_id: Uuid,
+        note: &Note,
+        platform: &str,
+        messages: &[Message],
+    ) -> Result<(), IngestionError> {
+        let channels: Vec<&str> = {
+            let mut channels: Vec<&str> = messages.iter().map(|m| m.channel_id.as_str()).collect();
+            channels.sort_unstable();
+            channels.dedup();
+            channels
+        };
+        let memory = Memory {
+            id: Uuid::new_v4().to_string(),
+            rei_id: rei_id.to_string(),
+            content: note.content.clone(),
+            memory_type: note.memory_type.clone(),
+            importance: match note.memory_type {
+                MemoryType::Learning => 0.6,
+                _ => 0.5,
+            },
+            tags: vec![
+                "integration".to_string(),
+                platform.to_string(),
+                "auto_generated".to_string(),
+            ],
+            metadata: Some(serde_json::json!({
+                "source": "integration",
+                "platform": platform,
+                "channel_ids": channels,
+                "message_ids": messages.iter().map(|m| &m.id).collect::<Vec<_>>(),
+                "from": messages.first().map(|m| m.timestamp),
+                "to": messages.last().map(|m| m.timestamp),
+            })),
+            created_at: Utc::now(),
+        };
+
+        let vector = self
+            .embedding
+            .embed(&memory.content)
+            .await
+            .map_err(|e| IngestionError::EmbeddingFailed(e.to_string()))?;
+
+        self.memory_kai
+            .add_memory(&rei_id.to_string(), memory.clone(), vector)
+            .await
+            .map_err(|e| IngestionError::StorageFailed(e.to_string()))?;
+
+        self.state.event_bus.emit(memory.added_event(rei_id)).await;
+
+        Ok(())
+    }
+}
+
+/// Messages with text newer than `since`, oldest first (at most `MAX_MESSAGES`)
+fn new_messages(messages: Vec<Message>, since: Option<DateTime<Utc>>) -> Vec<Message> {
+    let mut messages: Vec<Message> = messages
+        .into_iter()
+        .filter(|m| since.is_none_or(|since| m.timestamp > since))
+        .filter(|m| !m.content.trim().is_empty())
+        .collect();
+    messages.sort_by_key(|m| m.timestamp);
+    messages.truncate(MAX_MESSAGES);
+    messages
+}
+
+/// Parse the FACT/LEARNING lines of a summary, ignoring anything else
+fn parse_notes(summary: &str) -> Vec<Note> {
+    summary
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim().trim_start_matches(['-', '*', ' ']);
+            let (memory_type, rest) = if let Some(rest) = line.strip_prefix("FACT:") {
+                (MemoryType::Fact, rest)
+            } else if let Some(rest) = line.strip_prefix("LEARNING:") {
+                (MemoryType::Learning, rest)
+            } else {
+                return None;
+            };
+            let content = rest.trim();
+            (!content.is_empty()).then(|| Note {
+                memory_type,
+                content: content.to_string(),
+            })
+        })
+        .take(MAX_NOTES)
+        .collect()
+}
+
+// Gemini API types
+#[derive(Serialize)]
+struct GeminiRequest {
+    contents: Vec<GeminiContent>,
+}
+
+#[derive(Serialize)]
+struct GeminiContent {
+    parts: Vec<GeminiPart>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct GeminiPart {
+    text: String,
+}
+
+#[derive(Deserialize)]
+struct GeminiResponse {
+    candidates: Vec<GeminiCandidate>,
+}
+
+#[derive(Deserialize)]
+struct GeminiCandidate {
+    content: GeminiContentResponse,
+}
+
+#[derive(Deserialize)]
+struct GeminiContentResponse {
+    parts: Vec<GeminiPart>,
+}
+
+/// Ingestion error types
+#[derive(Debug, Clone)]
+pub enum IngestionError {
+    ReadFailed(String),
+    EmbeddingFailed(String),
+    StorageFailed(String),
+    ApiError(String),
+    ParseError(String),
+    DatabaseError(String),
+}
+
+impl std::fmt::Display for IngestionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IngestionError::ReadFailed(msg) => write!(f, "Reading messages failed: {}", msg),
+            IngestionError::EmbeddingFailed(msg) => write!(f, "Embedding failed: {}", msg),
+            IngestionError::StorageFailed(msg) => write!(f, "Storage failed: {}", msg),
+            IngestionError::ApiError(msg) => write!(f, "API error: {}", msg),
+            IngestionError::ParseError(msg) => write!(f, "Parse error: {}", msg),
+            IngestionError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for IngestionError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_notes() {
+        let summary = "Here is what to remember:\n\
+            - FACT: Aki prefers Rust for backend work\n\
+            LEARNING:  Small PRs get reviewed faster \n\
+            FACT:\n\
+            NONE";
+
+        let notes = parse_notes(summary);
+        assert_eq!(
+            notes,
+            vec![
+                Note {
+                    memory_type: MemoryType::Fact,
+                    content: "Aki prefers Rust for backend work".to_string(),
+                },
+                Note {
+                    memory_type: MemoryType::Learning,
+                    content: "Small PRs get reviewed faster".to_string(),
+                },
+            ]
+        );
+        assert!(parse_notes("NONE").is_empty());
+    }
+
+    #[test]
+    fn test_new_messages() {
+        let now = Utc::now();
+        let message = |id: &str, content: &str, minutes_ago: i64| {
+            let mut m = Message::new(id, "c1", "u1", "Aki", content, "discord");
+            m.timestamp = now - chrono::Duration::minutes(minutes_ago);
+            m
+        };
+        let messages = vec![
+            message("3", "newest", 1),
+            message("1", "already ingested", 30),
+            message("2", "new", 5),
+            message("4", "   ", 2),
+        ];
+
+        let fresh = new_messages(messages, Some(now - chrono::Duration::minutes(10)));
+        let ids: Vec<&str> = fresh.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, ["2", "3"]);
+    }
+}
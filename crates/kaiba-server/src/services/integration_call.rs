@@ -0,0 +1,322 @@
+//! Integration Calls - Answering platform messages through the call pipeline
+//!
+//! Integrations (the Discord gateway and interaction webhooks) hand events
+//! addressed to a Rei to [`IntegrationCallHandler`]. Messages and `ask`
+//! commands run through the same pipeline as `POST /kaiba/rei/:rei_id/call`;
+//! `remember` stores a memory and `recall` searches them. A bookmark
+//! reaction stores the reacted message as a memory, and replies an
+//! integration marks as findings (`remember_reply`, e.g. a first look at a
+//! new GitHub issue) are stored too. The returned text is posted back by
+//! the integration. Events for a Rei that switched the
+//! integration off (`/kaiba/rei/:id/integrations`) are dropped unanswered.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use kaiba::{DomainError, IntegrationEvent, IntegrationEventHandler, Rei};
+use uuid::Uuid;
+
+use crate::models::{CallContext, CallRequest, Memory, MemoryType};
+use crate::routes::call::execute_call;
+use crate::services::embedding::EmbeddingService;
+use crate::services::integration_registry;
+use crate::services::qdrant::MemoryKai;
+use crate::services::SearchFilter;
+use crate::AppState;
+
+/// Memories listed by `recall`
+const RECALL_LIMIT: usize = 5;
+
+/// Longest memory excerpt in a `recall` answer
+const RECALL_EXCERPT_CHARS: usize = 300;
+
+/// Longest reply kept as a finding
+const FINDING_MAX_CHARS: usize = 2000;
+
+/// Answers integration messages with the Rei's call pipeline
+pub struct IntegrationCallHandler {
+    state: AppState,
+    /// Name of the integration the events come from
+    integration: &'static str,
+}
+
+impl IntegrationCallHandler {
+    pub fn new(state: AppState, integration: &'static str) -> Self {
+        Self { state, integration }
+    }
+
+    /// Whether the integration answers for the Rei
+    pub async fn is_enabled(&self, rei: &Rei) -> Result<bool, DomainError> {
+        integration_registry::is_enabled(&self.state.pool, rei.id, self.integration)
+            .await
+            .map_err(|e| DomainError::Repository(e.to_string()))
+    }
+
+    async fn ask(
+        &self,
+        rei: &Rei,
+        message: String,
+        session_id: Option<Uuid>,
+    ) -> Result<String, DomainError> {
+        let request = CallRequest {
+            tei_ids: vec![],
+            message,
+            context: Some(CallContext {
+                task_type: Some("integration_message".to_string()),
+                include_memories: true,
+                ..Default::default()
+            }),
+            session_id,
+        };
+        let response = execute_call(&self.state, rei.id, request)
+            .await
+            .map_err(|e| DomainError::ExternalService(e.to_string()))?;
+
+        Ok(response.response)
+    }
+
+    async fn remember(
+        &self,
+        rei: &Rei,
+        content: String,
+        user_id: &str,
+        channel_id: &str,
+    ) -> Result<String, DomainError> {
+        let memory = Memory {
+            id: Uuid::new_v4().to_string(),
+            rei_id: rei.id.to_string(),
+            content,
+            memory_type: MemoryType::Fact,
+            importance: 0.5,
+            tags: vec!["slash_command".to_string()],
+            metadata: Some(serde_json::json!({
+                "source": "slash_command",
+                "user_id": user_id,
+                "channel_id": channel_id,
+            })),
+            created_at: Utc::now(),
+        };
+        self.store(rei, memory).await?;
+
+        Ok(format!("🧠 {} will remember that.", rei.name))
+    }
+
+    /// Store a message someone bookmarked with a reaction
+    async fn bookmark(
+        &self,
+        rei: &Rei,
+        content: &str,
+        reaction: serde_json::Value,
+        message: &serde_json::Value,
+    ) -> Result<String, DomainError> {
+        let content = match message.get("author_name").and_then(|v| v.as_str()) {
+            Some(author) => format!("{}: {}", author, content),
+            None => content.to_string(),
+        };
+        let mut metadata = reaction;
+        metadata["source"] = "reaction".into();
+        metadata["platform"] = self.integration.into();
+        for key in ["author_id", "author_name", "link"] {
+            if let Some(value) = message.get(key) {
+                metadata[key] = value.clone();
+            }
+        }
+        let memory = Memory {
+            id: Uuid::new_v4().to_string(),
+            rei_id: rei.id.to_string(),
+            content,
+            memory_type: MemoryType::Fact,
+            importance: 0.7,
+            tags: vec!["bookmark".to_string(), self.integration.to_string()],
+            metadata: Some(metadata),
+            created_at: Utc::now(),
+        };
+        self.store(rei, memory).await?;
+
+        Ok(format!("🧠 {} bookmarked that message.", rei.name))
+    }
+
+    /// Store a reply the integration marked as worth keeping
+    async fn remember_reply(
+        &self,
+        rei: &Rei,
+        reply: &str,
+        message: &serde_json::Value,
+    ) -> Result<(), DomainError> {
+        let reply: String = reply.chars().take(FINDING_MAX_CHARS).collect();
+        let content = match message.get("title").and_then(|v| v.as_str()) {
+            Some(title) => format!("{}: {}", title, reply),
+            None => reply,
+        };
+        let mut metadata = serde_json::json!({
+            "source": "integration_reply",
+            "platform": self.integration,
+        });
+        for key in ["repo", "number", "kind", "title", "url"] {
+            if let Some(value) = message.get(key) {
+                metadata[key] = value.clone();
+            }
+        }
+        let memory = Memory {
+            id: Uuid::new_v4().to_string(),
+            rei_id: rei.id.to_string(),
+            content,
+            memory_type: MemoryType::Learning,
+            importance: 0.6,
+            tags: vec!["finding".to_string(), self.integration.to_string()],
+            metadata: Some(metadata),
+            created_at: Utc::now(),
+        };
+        self.store(rei, memory).await
+    }
+
+    async fn store(&self, rei: &Rei, memory: Memory) -> Result<(), DomainError> {
+        let (memory_kai, embedding) = self.memory_services()?;
+
+        let vector = embedding
+            .embed(&memory.content)
+            .await
+            .map_err(|e| DomainError::ExternalService(e.to_string()))?;
+        memory_kai
+            .add_memory(&rei.id.to_string(), memory.clone(), vector)
+            .await
+            .map_err(|e| DomainError::ExternalService(e.to_string()))?;
+
+        self.state.event_bus.emit(memory.added_event(rei.id)).await;
+
+        Ok(())
+    }
+
+    async fn recall(&self, rei: &Rei, query: String) -> Result<String, DomainError> {
+        let (memory_kai, embedding) = self.memory_services()?;
+
+        let vector = embedding
+            .embed(&query)
+            .await
+            .map_err(|e| DomainError::ExternalService(e.to_string()))?;
+        let memories = memory_kai
+            .search_memories_scored(
+                &rei.id.to_string(),
+                vector,
+                RECALL_LIMIT,
+                SearchFilter::default(),
+            )
+            .await
+            .map_err(|e| DomainError::ExternalService(e.to_string()))?;
+
+        if memories.is_empty() {
+            return Ok(format!(
+                "{} remembers nothing about \"{}\".",
+                rei.name, query
+            ));
+        }
+        let mut text = format!("What {} remembers about \"{}\":", rei.name, query);
+        for (memory, score) in memories {
+            let excerpt: String = memory.content.chars().take(RECALL_EXCERPT_CHARS).collect();
+            text.push_str(&format!(
+                "\n- ({}, {:.2}) {}",
+                memory.memory_type, score, excerpt
+            ));
+        }
+        Ok(text)
+    }
+
+    fn memory_services(&self) -> Result<(&MemoryKai, &EmbeddingService), DomainError> {
+        let memory_kai = self
+            .state
+            .memory_kai
+            .as_deref()
+            .ok_or_else(|| DomainError::ExternalService("MemoryKai not available".into()))?;
+        let embedding = self.state.embedding.as_ref().ok_or_else(|| {
+            DomainError::ExternalService("Embedding service not available".into())
+        })?;
+        Ok((memory_kai, embedding))
+    }
+}
+
+#[async_trait]
+impl IntegrationEventHandler for IntegrationCallHandler {
+    async fn handle(
+        &self,
+        rei: &Rei,
+        event: IntegrationEvent,
+    ) -> Result<Option<String>, DomainError> {
+        if !self.is_enabled(rei).await? {
+            tracing::debug!("🔌 {} is disabled for Rei {}", self.integration, rei.id);
+            return Ok(None);
+        }
+
+        let reply = match event {
+            // Integrations keep a conversation's session in the metadata
+            IntegrationEvent::MessageReceived {
+                content, metadata, ..
+            } => {
+                let session_id = metadata
+                    .get("session_id")
+                    .and_then(|v| v.as_str())
+                    .and_then(|s| s.parse().ok());
+                let reply = self.ask(rei, content, session_id).await?;
+                if metadata.get("remember_reply").and_then(|v| v.as_bool()) == Some(true) {
+                    // The reply is sent either way
+                    if let Err(e) = self.remember_reply(rei, &reply, &metadata).await {
+                        tracing::warn!("⚠️  Failed to remember {} reply: {}", self.integration, e);
+                    }
+                }
+                reply
+            }
+            IntegrationEvent::MentionReceived { content, .. }
+            | IntegrationEvent::DirectMessage { content, .. } => {
+                self.ask(rei, content, None).await?
+            }
+            IntegrationEvent::SlashCommand {
+                command,
+                user_id,
+                channel_id,
+                args,
+            } => {
+                let text = args.join(" ");
+                if text.trim().is_empty() {
+                    return Err(DomainError::Validation(format!(
+                        "/{} needs some text",
+                        command
+                    )));
+                }
+                match command.as_str() {
+                    "ask" => self.ask(rei, text, None).await?,
+                    "remember" => self.remember(rei, text, &user_id, &channel_id).await?,
+                    "recall" => self.recall(rei, text).await?,
+                    _ => {
+                        return Err(DomainError::Validation(format!(
+                            "Unknown command: {}",
+                            command
+                        )))
+                    }
+                }
+            }
+            // Only reactions the integration resolved to a message's text
+            IntegrationEvent::ReactionAdded {
+                message_id,
+                channel_id,
+                user_id,
+                emoji,
+                metadata,
+            } => {
+                let Some(content) = metadata
+                    .get("content")
+                    .and_then(|v| v.as_str())
+                    .filter(|c| !c.trim().is_empty())
+                else {
+                    return Ok(None);
+                };
+                let reaction = serde_json::json!({
+                    "message_id": message_id,
+                    "channel_id": channel_id,
+                    "user_id": user_id,
+                    "emoji": emoji,
+                });
+                self.bookmark(rei, content, reaction, &metadata).await?
+            }
+        };
+
+        Ok(Some(reply))
+    }
+}
@@ -0,0 +1,166 @@
+//! Integration Registry - Platform integrations configured at startup
+//!
+//! Every integration configured on this deployment (Discord, Telegram,
+//! email, Matrix) is registered by name when the server starts. An integration
+//! answers for all Reis bound to it in their manifests; a `rei_integrations`
+//! row switches it off (or back on) for one Rei, and no row means enabled.
+//! The same row opts a Rei in to ingestion of what the integration reads
+//! (see [`ingestion`](crate::services::ingestion)).
+//! Health is checked on demand, all integrations at once.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use futures_util::future::join_all;
+use kaiba::TeiIntegration;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::{IntegrationHealth, ReiIntegration};
+
+/// Longest a single health check may take before it counts as failed
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Integrations by name
+#[derive(Default)]
+pub struct IntegrationRegistry {
+    integrations: BTreeMap<String, Arc<dyn TeiIntegration>>,
+}
+
+impl IntegrationRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an integration under its own name
+    pub fn with(mut self, integration: Arc<dyn TeiIntegration>) -> Self {
+        self.integrations
+            .insert(integration.name().to_string(), integration);
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<dyn TeiIntegration>> {
+        self.integrations.get(name).cloned()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.integrations.contains_key(name)
+    }
+
+    /// Registered names, alphabetically
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.integrations.keys().map(String::as_str)
+    }
+
+    /// Check every integration concurrently
+    pub async fn health(&self) -> Vec<IntegrationHealth> {
+        join_all(
+            self.integrations
+                .iter()
+                .map(|(name, integration)| async move {
+                    let result =
+                        tokio::time::timeout(HEALTH_CHECK_TIMEOUT, integration.health_check())
+                            .await;
+                    let (healthy, error) = match result {
+                        Ok(Ok(true)) => (true, None),
+                        Ok(Ok(false)) => (false, Some("Health check failed".to_string())),
+                        Ok(Err(e)) => (false, Some(e.to_string())),
+                        Err(_) => (false, Some("Health check timed out".to_string())),
+                    };
+                    IntegrationHealth {
+                        name: name.clone(),
+                        healthy,
+                        error,
+                        checked_at: Utc::now(),
+                    }
+                }),
+        )
+        .await
+    }
+}
+
+/// Whether an integration answers for a Rei
+pub async fn is_enabled(
+    pool: &PgPool,
+    rei_id: Uuid,
+    integration: &str,
+) -> Result<bool, sqlx::Error> {
+    let enabled: Option<bool> = sqlx::query_scalar(
+        "SELECT enabled FROM rei_integrations WHERE rei_id = $1 AND integration = $2",
+    )
+    .bind(rei_id)
+    .bind(integration)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(enabled.unwrap_or(true))
+}
+
+/// Change an integration's switches for a Rei (`None` keeps a switch)
+pub async fn update(
+    pool: &PgPool,
+    rei_id: Uuid,
+    integration: &str,
+    enabled: Option<bool>,
+    ingest: Option<bool>,
+) -> Result<ReiIntegration, sqlx::Error> {
+    sqlx::query_as(
+        r#"
+        INSERT INTO rei_integrations (rei_id, integration, enabled, ingest)
+        VALUES ($1, $2, COALESCE($3, TRUE), COALESCE($4, FALSE))
+        ON CONFLICT (rei_id, integration) DO UPDATE
+        SET enabled = COALESCE($3, rei_integrations.enabled),
+            ingest = COALESCE($4, rei_integrations.ingest)
+        RETURNING *
+        "#,
+    )
+    .bind(rei_id)
+    .bind(integration)
+    .bind(enabled)
+    .bind(ingest)
+    .fetch_one(pool)
+    .await
+}
+
+/// Stored switches of a Rei (integrations never switched are absent)
+pub async fn switches(pool: &PgPool, rei_id: Uuid) -> Result<Vec<ReiIntegration>, sqlx::Error> {
+    sqlx::query_as("SELECT * FROM rei_integrations WHERE rei_id = $1 ORDER BY integration")
+        .bind(rei_id)
+        .fetch_all(pool)
+        .await
+}
+
+/// Switches opted in to ingestion, of live Reis with the integration enabled
+pub async fn ingesting(pool: &PgPool) -> Result<Vec<ReiIntegration>, sqlx::Error> {
+    sqlx::query_as(
+        r#"
+        SELECT ri.*
+        FROM rei_integrations ri
+        JOIN reis r ON r.id = ri.rei_id
+        WHERE ri.enabled AND ri.ingest AND r.deleted_at IS NULL
+        ORDER BY ri.rei_id, ri.integration
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Record the newest message ingested for a Rei
+pub async fn set_ingested_until(
+    pool: &PgPool,
+    rei_id: Uuid,
+    integration: &str,
+    until: DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE rei_integrations SET ingested_until = $3 WHERE rei_id = $1 AND integration = $2",
+    )
+    .bind(rei_id)
+    .bind(integration)
+    .bind(until)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
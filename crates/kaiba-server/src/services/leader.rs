@@ -0,0 +1,103 @@
+//! Leader Election - One replica runs the scheduler
+//!
+//! With several server instances on one database, only the leader runs
+//! scheduler cycles; the others stand by. Leadership is a Postgres
+//! session-level advisory lock held on a dedicated pooled connection. Standby
+//! instances try to take it on every tick, so when the leader stops or its
+//! connection drops, the lock is released and a standby takes over on its
+//! next tick.
+
+use sqlx::pool::PoolConnection;
+use sqlx::{PgPool, Postgres};
+
+/// Advisory lock key of the scheduler; the high bits spell "kaiba"
+pub const SCHEDULER_LOCK_KEY: i64 = 0x6b61_6962_6100_0001;
+
+/// This instance's claim on an advisory lock
+pub struct Leadership {
+    pool: PgPool,
+    key: i64,
+    /// Connection holding the lock while this instance leads
+    conn: Option<PoolConnection<Postgres>>,
+}
+
+impl Leadership {
+    pub fn new(pool: PgPool, key: i64) -> Self {
+        Self {
+            pool,
+            key,
+            conn: None,
+        }
+    }
+
+    /// Whether this instance leads, taking over if the lock is free
+    ///
+    /// A database error counts as not leading, so a lost connection skips a
+    /// cycle rather than risking a duplicate run.
+    pub async fn check(&mut self) -> bool {
+        if let Some(conn) = self.conn.as_mut() {
+            if sqlx::query("SELECT 1").execute(&mut **conn).await.is_ok() {
+                return true;
+            }
+            // The lock went with the connection
+            tracing::warn!("⚠️  Lost scheduler leadership (connection dropped)");
+            self.abandon();
+        }
+
+        match self.try_acquire().await {
+            Ok(true) => {
+                tracing::info!("👑 This instance now runs the scheduler");
+                true
+            }
+            Ok(false) => {
+                tracing::debug!("Scheduler runs on another instance; standing by");
+                false
+            }
+            Err(e) => {
+                tracing::warn!("⚠️  Failed to take scheduler leadership: {}", e);
+                false
+            }
+        }
+    }
+
+    async fn try_acquire(&mut self) -> Result<bool, sqlx::Error> {
+        let mut conn = self.pool.acquire().await?;
+        let acquired: bool = sqlx::query_scalar("SELECT pg_try_advisory_lock($1)")
+            .bind(self.key)
+            .fetch_one(&mut *conn)
+            .await?;
+        if acquired {
+            self.conn = Some(conn);
+        }
+        Ok(acquired)
+    }
+
+    /// Give up leadership so a standby can take over right away
+    pub async fn resign(&mut self) {
+        let Some(mut conn) = self.conn.take() else {
+            return;
+        };
+        let unlocked = sqlx::query("SELECT pg_advisory_unlock($1)")
+            .bind(self.key)
+            .execute(&mut *conn)
+            .await;
+        if unlocked.is_err() {
+            // Closing the connection releases the lock
+            drop(conn.detach());
+        }
+    }
+
+    /// Close the lock connection instead of returning it to the pool, where
+    /// it would keep holding the lock
+    fn abandon(&mut self) {
+        if let Some(conn) = self.conn.take() {
+            drop(conn.detach());
+        }
+    }
+}
+
+impl Drop for Leadership {
+    fn drop(&mut self) {
+        self.abandon();
+    }
+}
@@ -0,0 +1,66 @@
+//! Learning Sources - Where self-learning reads besides web search
+//!
+//! A source hands a Rei new items to learn from, already summarized; the
+//! learning session stores each one as a Learning memory and then marks it
+//! learned so it is not pulled again. What counts as new is up to the source.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::models::Rei;
+use crate::services::feed_source::FeedSource;
+
+/// One item a source offers to learn from
+#[derive(Debug, Clone)]
+pub struct SourceItem {
+    /// Identifies the item within its source (e.g. a feed entry ID)
+    pub key: String,
+    pub title: String,
+    /// Summary to store as the memory
+    pub summary: String,
+    /// Where the item can be read
+    pub url: Option<String>,
+    /// Where the item came from (e.g. the feed URL)
+    pub origin: String,
+    pub published_at: Option<DateTime<Utc>>,
+    /// Gemini tokens spent summarizing it
+    pub tokens_used: i32,
+}
+
+/// Items pulled from a source, and what went wrong along the way
+#[derive(Debug, Default)]
+pub struct SourcePull {
+    pub items: Vec<SourceItem>,
+    /// One message per origin or item that could not be read
+    pub errors: Vec<String>,
+}
+
+/// A place self-learning reads from
+#[async_trait]
+pub trait LearningSource: Send + Sync {
+    /// Short name used in memory tags and metadata (e.g. "rss")
+    fn name(&self) -> &'static str;
+
+    /// Up to `limit` items the Rei has not learned from yet, newest first
+    async fn pull(&self, rei: &Rei, limit: usize) -> SourcePull;
+
+    /// Remember that `item` was stored, so it is not pulled again
+    async fn mark_learned(&self, rei_id: Uuid, item: &SourceItem) -> Result<(), sqlx::Error>;
+}
+
+/// Sources available with the given configuration
+///
+/// Feeds need Gemini to summarize entries.
+pub fn default_sources(
+    pool: &PgPool,
+    gemini_api_key: Option<&str>,
+) -> Vec<Arc<dyn LearningSource>> {
+    let mut sources: Vec<Arc<dyn LearningSource>> = Vec::new();
+    if let Some(key) = gemini_api_key {
+        sources.push(Arc::new(FeedSource::new(pool.clone(), key)));
+    }
+    sources
+}
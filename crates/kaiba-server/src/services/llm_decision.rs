@@ -0,0 +1,240 @@
+//! LLM Decision - Gemini weighs what a Rei should do next
+//!
+//! The model sees the Rei's mood, energy, token budget, pending memories, time
+//! since its last reflection, recent outcomes and post-mortems of what keeps
+//! failing, and picks Learn, Digest, Reflect or Rest with a reason. The rule
+//! thresholds still bound it: a choice they forbid, or a failed or
+//! unreadable answer, falls back to the rule-based decision.
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::models::DecisionStrategyKind;
+use crate::services::decision::{
+    Action, Decision, DecisionConfig, DecisionContext, DecisionMaker, DecisionStrategy,
+};
+
+const GEMINI_URL: &str =
+    "https://generativelanguage.googleapis.com/v1beta/models/gemini-2.0-flash:generateContent";
+
+/// LLM-assisted decision maker
+pub struct LlmDecisionMaker {
+    rules: DecisionMaker,
+    client: Client,
+    api_key: String,
+}
+
+impl LlmDecisionMaker {
+    pub fn new(api_key: impl Into<String>, config: Option<DecisionConfig>) -> Self {
+        Self {
+            rules: DecisionMaker::new(config),
+            client: Client::new(),
+            api_key: api_key.into(),
+        }
+    }
+
+    #[tracing::instrument(name = "llm.decide", skip_all)]
+    async fn ask(&self, context: &DecisionContext) -> Result<(Action, String), String> {
+        let outcomes = if context.recent_outcomes.is_empty() {
+            "- (none yet)".to_string()
+        } else {
+            context
+                .recent_outcomes
+                .iter()
+                .map(|outcome| format!("- {}", outcome))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        let post_mortems = if context.post_mortems.is_empty() {
+            "- (none)".to_string()
+        } else {
+            context
+                .post_mortems
+                .iter()
+                .map(|post_mortem| format!("- {}", post_mortem))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        let prompt = format!(
+            r#"You decide what an autonomous persona does next. Choose one action:
+- learn: search the web for new information on its interests (costs energy)
+- digest: consolidate memories learned since the last digest into expertise (costs energy)
+- reflect: write a journal entry with opinions and open questions about recent memories (costs a little energy)
+- rest: do nothing and recover energy
+
+## Current State
+Mood: {}
+Energy: {}/100
+Tokens remaining: {}
+Memories since last digest: {}
+Hours since last reflection: {}
+
+## Recent Outcomes (newest first)
+{}
+
+## Post-Mortems (what keeps failing)
+{}
+
+Weigh all of these: e.g. repeated failed or empty learning suggests resting or digesting, an action with a post-mortem is best avoided for now, and a tired or low mood suggests rest.
+Answer with JSON only: {{"action": "learn" | "digest" | "reflect" | "rest", "reason": "<one sentence>"}}"#,
+            context.mood,
+            context.energy_level,
+            context.tokens_remaining,
+            context.memories_since_digest,
+            context
+                .hours_since_reflection
+                .map_or("unknown".to_string(), |hours| hours.to_string()),
+            outcomes,
+            post_mortems
+        );
+
+        let request = GeminiRequest {
+            contents: vec![GeminiContent {
+                parts: vec![GeminiPart { text: prompt }],
+            }],
+            generation_config: GenerationConfig {
+                response_mime_type: "application/json",
+            },
+        };
+
+        let response = self
+            .client
+            .post(format!("{}?key={}", GEMINI_URL, self.api_key))
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        if !response.status().is_success() {
+            return Err(format!("Gemini returned {}", response.status()));
+        }
+
+        let result: GeminiResponse = response.json().await.map_err(|e| e.to_string())?;
+        let text = result
+            .candidates
+            .first()
+            .and_then(|c| c.content.parts.first())
+            .map(|p| p.text.as_str())
+            .ok_or("empty answer")?;
+
+        parse_choice(text).ok_or_else(|| format!("unreadable answer: {}", text))
+    }
+}
+
+#[async_trait]
+impl DecisionStrategy for LlmDecisionMaker {
+    async fn next_action(&self, context: DecisionContext) -> Decision {
+        let fallback = match self.ask(&context).await {
+            Ok((action, reason)) => match self.rules.forbids(action, &context) {
+                None => {
+                    return Decision {
+                        action,
+                        reason,
+                        strategy: DecisionStrategyKind::Llm,
+                        context,
+                    }
+                }
+                Some(why) => format!("LLM chose {}, but {}", action.as_str(), why),
+            },
+            Err(e) => {
+                tracing::warn!("⚠️  LLM decision failed: {}", e);
+                format!("LLM unavailable ({})", e)
+            }
+        };
+
+        let mut decision = self.rules.decide_in(context);
+        decision.reason = format!("{}; {}", fallback, decision.reason);
+        decision
+    }
+}
+
+/// Read `{"action": ..., "reason": ...}`, tolerating a Markdown code fence
+fn parse_choice(text: &str) -> Option<(Action, String)> {
+    let json = text
+        .trim()
+        .trim_start_matches("```json")
+        .trim_start_matches("```")
+        .trim_end_matches("```")
+        .trim();
+    let choice: Choice = serde_json::from_str(json).ok()?;
+    let reason = choice.reason.trim();
+    if reason.is_empty() {
+        return None;
+    }
+    Some((choice.action, reason.to_string()))
+}
+
+#[derive(Deserialize)]
+struct Choice {
+    action: Action,
+    reason: String,
+}
+
+// Gemini API types
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiRequest {
+    contents: Vec<GeminiContent>,
+    generation_config: GenerationConfig,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GenerationConfig {
+    response_mime_type: &'static str,
+}
+
+#[derive(Serialize)]
+struct GeminiContent {
+    parts: Vec<GeminiPart>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct GeminiPart {
+    text: String,
+}
+
+#[derive(Deserialize)]
+struct GeminiResponse {
+    candidates: Vec<GeminiCandidate>,
+}
+
+#[derive(Deserialize)]
+struct GeminiCandidate {
+    content: GeminiContentResponse,
+}
+
+#[derive(Deserialize)]
+struct GeminiContentResponse {
+    parts: Vec<GeminiPart>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_choice() {
+        assert_eq!(
+            parse_choice(r#"{"action": "digest", "reason": "Lots to consolidate"}"#),
+            Some((Action::Digest, "Lots to consolidate".to_string()))
+        );
+        assert_eq!(
+            parse_choice("```json\n{\"action\": \"rest\", \"reason\": \"Tired\"}\n```"),
+            Some((Action::Rest, "Tired".to_string()))
+        );
+        assert_eq!(
+            parse_choice(r#"{"action": "dance", "reason": "Fun"}"#),
+            None
+        );
+        assert_eq!(
+            parse_choice(r#"{"action": "reflect", "reason": "Time to take stock"}"#),
+            Some((Action::Reflect, "Time to take stock".to_string()))
+        );
+        assert_eq!(parse_choice(r#"{"action": "learn", "reason": " "}"#), None);
+        assert_eq!(parse_choice("I think it should learn"), None);
+    }
+}
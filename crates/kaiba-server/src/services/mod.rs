@@ -1,10 +1,36 @@
 pub mod decision;
 pub mod digest;
 pub mod embedding;
+pub mod energy;
+pub mod event_stats;
+pub mod event_stream;
+pub mod feed_source;
+pub mod inbound;
+pub mod ingestion;
+pub mod integration_call;
+pub mod integration_registry;
+pub mod leader;
+pub mod learning_source;
+pub mod llm_decision;
+pub mod outbox_relay;
+pub mod ownership;
+pub mod parallel;
+pub mod post_mortem;
 pub mod qdrant;
+pub mod reflection;
+pub mod rei_purge;
+pub mod run_history;
+pub mod schedule;
 pub mod scheduler;
+pub mod seed;
 pub mod self_learning;
+pub mod source_policy;
+pub mod task_runner;
+pub mod tei_expertise;
 pub mod web_search;
+pub mod webhook_batcher;
+pub mod webhook_health;
+pub mod webhook_publisher;
 
 // Re-exports
 pub use qdrant::SearchFilter;
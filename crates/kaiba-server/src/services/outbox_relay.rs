@@ -0,0 +1,91 @@
+//! Outbox Relay - Dispatch persisted events to the event bus
+//!
+//! Polls the event outbox, dispatches each claimed event to the bus
+//! subscribers and waits for them. Handled events are removed; failures
+//! are retried with backoff until the outbox dead-letters them. A crash
+//! mid-dispatch leaves the event leased, and it is picked up again once the
+//! lease expires (at-least-once). Webhook deliveries are handed off to
+//! background tasks by their subscriber, so they never hold up a batch.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio_util::sync::CancellationToken;
+
+use crate::adapters::{InProcessEventBus, PgEventOutbox};
+
+/// Poll interval when the outbox is drained
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Events claimed per poll
+const BATCH_SIZE: i64 = 50;
+
+/// Relays outbox events to subscribers
+pub struct OutboxRelay {
+    outbox: Arc<PgEventOutbox>,
+    event_bus: Arc<InProcessEventBus>,
+}
+
+impl OutboxRelay {
+    pub fn new(outbox: Arc<PgEventOutbox>, event_bus: Arc<InProcessEventBus>) -> Self {
+        Self { outbox, event_bus }
+    }
+
+    /// Run the relay until shutdown; the batch in hand is dispatched first
+    pub async fn run(self: Arc<Self>, shutdown: CancellationToken) {
+        tracing::info!("📮 Outbox relay started");
+        while !shutdown.is_cancelled() {
+            let relayed = self.relay_batch().await;
+            // Keep draining while there is a backlog
+            if relayed < BATCH_SIZE as usize {
+                tokio::select! {
+                    _ = tokio::time::sleep(POLL_INTERVAL) => {}
+                    _ = shutdown.cancelled() => {}
+                }
+            }
+        }
+    }
+
+    /// Claim and dispatch one batch; returns the number of events claimed
+    async fn relay_batch(&self) -> usize {
+        let events = match self.outbox.claim(BATCH_SIZE).await {
+            Ok(events) => events,
+            Err(e) => {
+                tracing::warn!("⚠️  Failed to claim outbox events: {}", e);
+                return 0;
+            }
+        };
+
+        for event in &events {
+            let result = match self.event_bus.dispatch(event).await {
+                Ok(()) => self.outbox.complete(event.id).await,
+                Err(e) => self
+                    .outbox
+                    .fail(event.id, &e.to_string())
+                    .await
+                    .map(|dead| {
+                        if dead {
+                            tracing::error!(
+                            "💀 Dispatch of {} ({}) failed for the last time, dead-lettered: {}",
+                            event.event_type,
+                            event.id,
+                            e
+                        );
+                        } else {
+                            tracing::warn!(
+                                "⚠️  Dispatch of {} ({}) failed, will retry: {}",
+                                event.event_type,
+                                event.id,
+                                e
+                            );
+                        }
+                    }),
+            };
+            if let Err(e) = result {
+                tracing::warn!("⚠️  Failed to update outbox event {}: {}", event.id, e);
+            }
+        }
+
+        events.len()
+    }
+}
@@ -0,0 +1,163 @@
+//! Ownership - Which user a Rei, Tei or global webhook belongs to
+//!
+//! Keys bound to a user only reach that user's resources. Resources without
+//! an owner are shared and only visible to the master key and operator keys.
+//! Rei webhooks, memories, calls etc. follow the ownership of their Rei.
+
+use async_trait::async_trait;
+use kaiba::DomainError;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Resource type with an `owner_id` column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Owned {
+    Rei,
+    Tei,
+    Webhook,
+}
+
+impl Owned {
+    fn table(self) -> &'static str {
+        match self {
+            Owned::Rei => "reis",
+            Owned::Tei => "teis",
+            Owned::Webhook => "rei_webhooks",
+        }
+    }
+}
+
+/// What a user-bound key needs to reach a path
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TenantAccess {
+    /// Listing or creating; handlers filter by owner
+    Collection,
+    /// A single resource the user must own
+    Resource(Owned, Uuid),
+    /// Cross-tenant endpoint (key management, batch jobs, event registry)
+    OperatorOnly,
+}
+
+/// Classify a request path for a user-bound key
+pub fn tenant_access(path: &str) -> TenantAccess {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    let resource = |owned: Owned, id: &str| {
+        id.parse().map_or(TenantAccess::OperatorOnly, |id| {
+            TenantAccess::Resource(owned, id)
+        })
+    };
+
+    match segments.as_slice() {
+        ["kaiba", "rei"] | ["kaiba", "tei"] | ["kaiba", "webhooks"] | ["kaiba", "search"] => {
+            TenantAccess::Collection
+        }
+        ["kaiba", "rei", id, ..] => resource(Owned::Rei, id),
+        ["kaiba", "tei", id, ..] => resource(Owned::Tei, id),
+        ["kaiba", "webhooks", id] => resource(Owned::Webhook, id),
+        _ => TenantAccess::OperatorOnly,
+    }
+}
+
+/// Resources of a type a caller may list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Visible {
+    All,
+    Only(Vec<Uuid>),
+}
+
+impl Visible {
+    pub fn contains(&self, id: Uuid) -> bool {
+        match self {
+            Visible::All => true,
+            Visible::Only(ids) => ids.contains(&id),
+        }
+    }
+}
+
+/// What a caller limited to `owner` (if any) may list
+pub async fn visible(
+    pool: &PgPool,
+    owned: Owned,
+    owner: Option<Uuid>,
+) -> Result<Visible, sqlx::Error> {
+    match owner {
+        Some(user_id) => Ok(Visible::Only(owned_ids(pool, owned, user_id).await?)),
+        None => Ok(Visible::All),
+    }
+}
+
+/// Whether `user_id` owns the resource
+pub async fn is_owner(
+    pool: &PgPool,
+    owned: Owned,
+    id: Uuid,
+    user_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar::<_, bool>(&format!(
+        "SELECT EXISTS(SELECT 1 FROM {} WHERE id = $1 AND owner_id = $2)",
+        owned.table()
+    ))
+    .bind(id)
+    .bind(user_id)
+    .fetch_one(pool)
+    .await
+}
+
+/// Storage that records a new resource's owner in its INSERT
+///
+/// Setting the owner afterwards would leave the row unowned (shared) until
+/// the second statement ran, and for good if it failed.
+#[async_trait]
+pub trait InsertOwned<T>: Send + Sync {
+    /// Insert a new resource, owned by `owner_id` if set
+    async fn insert_owned(&self, item: &T, owner_id: Option<Uuid>) -> Result<T, DomainError>;
+}
+
+/// IDs of all resources of a type owned by `user_id`
+pub async fn owned_ids(
+    pool: &PgPool,
+    owned: Owned,
+    user_id: Uuid,
+) -> Result<Vec<Uuid>, sqlx::Error> {
+    sqlx::query_scalar::<_, Uuid>(&format!(
+        "SELECT id FROM {} WHERE owner_id = $1",
+        owned.table()
+    ))
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tenant_access() {
+        let id = Uuid::new_v4();
+        assert_eq!(tenant_access("/kaiba/rei"), TenantAccess::Collection);
+        assert_eq!(tenant_access("/kaiba/tei/"), TenantAccess::Collection);
+        assert_eq!(
+            tenant_access(&format!("/kaiba/rei/{}/webhooks/{}", id, Uuid::new_v4())),
+            TenantAccess::Resource(Owned::Rei, id)
+        );
+        assert_eq!(
+            tenant_access(&format!("/kaiba/tei/{}/expertise", id)),
+            TenantAccess::Resource(Owned::Tei, id)
+        );
+        assert_eq!(
+            tenant_access(&format!("/kaiba/webhooks/{}", id)),
+            TenantAccess::Resource(Owned::Webhook, id)
+        );
+        assert_eq!(
+            tenant_access("/kaiba/rei/not-a-uuid"),
+            TenantAccess::OperatorOnly
+        );
+        assert_eq!(
+            tenant_access("/kaiba/learn/all"),
+            TenantAccess::OperatorOnly
+        );
+        assert_eq!(tenant_access("/kaiba/api-keys"), TenantAccess::OperatorOnly);
+        assert_eq!(tenant_access("/kaiba/users"), TenantAccess::OperatorOnly);
+    }
+}
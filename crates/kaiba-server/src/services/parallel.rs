@@ -0,0 +1,121 @@
+//! Parallel Rei Processing - Side by side, within bounds
+//!
+//! Scheduler cycles and `/kaiba/trigger` work on many Reis at once. A
+//! semaphore caps how many run together, each Rei gets a deadline so one
+//! slow LLM call cannot hold up the rest, and a short random delay before
+//! each Rei (but the first) spreads out calls to external APIs.
+
+use futures_util::future::join_all;
+use std::future::Future;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio::time::error::Elapsed;
+
+/// Jitter range in milliseconds (0-3000ms = 0-3sec)
+const JITTER_MAX_MS: u64 = 3000;
+
+/// How many Reis run at once, and for how long each may run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parallelism {
+    pub max_concurrent: usize,
+    pub rei_timeout: Duration,
+}
+
+impl Default for Parallelism {
+    fn default() -> Self {
+        Self {
+            max_concurrent: 4,
+            rei_timeout: Duration::from_secs(600),
+        }
+    }
+}
+
+impl Parallelism {
+    /// Run `f` for every item, at most `max_concurrent` at a time
+    ///
+    /// Results come back in the order of `items`; an item that ran past
+    /// `rei_timeout` yields `Err(Elapsed)` and its work is dropped.
+    pub async fn run<'a, T, F, Fut>(
+        &self,
+        items: &'a [T],
+        jitter: bool,
+        f: F,
+    ) -> Vec<Result<Fut::Output, Elapsed>>
+    where
+        F: Fn(&'a T) -> Fut,
+        Fut: Future,
+    {
+        let semaphore = Semaphore::new(self.max_concurrent.max(1));
+        let (semaphore, f) = (&semaphore, &f);
+
+        join_all(items.iter().enumerate().map(|(idx, item)| async move {
+            let _permit = semaphore
+                .acquire()
+                .await
+                .expect("semaphore is never closed");
+            if jitter && idx > 0 {
+                tokio::time::sleep(jitter_delay(idx)).await;
+            }
+            tokio::time::timeout(self.rei_timeout, f(item)).await
+        }))
+        .await
+    }
+}
+
+/// Simple jitter using timestamp nanos (no external crate needed)
+fn jitter_delay(seed: usize) -> Duration {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .subsec_nanos() as u64;
+    Duration::from_millis((nanos ^ (seed as u64 * 7919)) % JITTER_MAX_MS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn test_run_bounds_concurrency_and_keeps_order() {
+        let parallelism = Parallelism {
+            max_concurrent: 2,
+            rei_timeout: Duration::from_secs(1),
+        };
+        let (running, peak) = (AtomicUsize::new(0), AtomicUsize::new(0));
+
+        let results = parallelism
+            .run(&[1, 2, 3, 4, 5], false, |n| {
+                let (running, peak) = (&running, &peak);
+                async move {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    n * 10
+                }
+            })
+            .await;
+
+        let values: Vec<i32> = results.into_iter().map(Result::unwrap).collect();
+        assert_eq!(values, vec![10, 20, 30, 40, 50]);
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_run_times_out_slow_items() {
+        let parallelism = Parallelism {
+            max_concurrent: 4,
+            rei_timeout: Duration::from_millis(20),
+        };
+
+        let results = parallelism
+            .run(&[1, 1000], false, |ms| async move {
+                tokio::time::sleep(Duration::from_millis(*ms)).await;
+            })
+            .await;
+
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+    }
+}
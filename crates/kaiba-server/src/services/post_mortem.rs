@@ -0,0 +1,260 @@
+//! Post-Mortems - Learning from repeated failures
+//!
+//! Failed searches (per topic) and failed digests are counted per Rei in
+//! `rei_failures`. When one fails [`FAILURE_THRESHOLD`] times in a row, a
+//! short `reflection` memory records the failure pattern, and the Rei backs
+//! off: learning skips the topic, and the decision maker stops choosing a
+//! digest, until [`BACKOFF_HOURS`] have passed since the last failure. A
+//! success clears the streak.
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::adapters::InProcessEventBus;
+use crate::models::{Memory, MemoryType};
+use crate::services::embedding::EmbeddingService;
+use crate::services::qdrant::MemoryKai;
+
+/// Failures in a row that trigger a post-mortem and a back-off
+pub const FAILURE_THRESHOLD: i32 = 3;
+
+/// Hours a failing subject is avoided after its last failure
+pub const BACKOFF_HOURS: i32 = 24;
+
+/// Longest error message quoted in a post-mortem
+const MAX_ERROR_CHARS: usize = 200;
+
+/// What failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Subject {
+    /// Web search for a learning topic
+    Search(String),
+    /// Consolidating learning memories into expertise
+    Digest,
+}
+
+impl Subject {
+    /// Key in `rei_failures.subject`
+    fn key(&self) -> String {
+        match self {
+            Subject::Search(query) => format!("search:{}", query),
+            Subject::Digest => "digest".to_string(),
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "digest" => Some(Subject::Digest),
+            _ => key
+                .strip_prefix("search:")
+                .map(|query| Subject::Search(query.to_string())),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Subject::Search(query) => format!("Searching for \"{}\"", query),
+            Subject::Digest => "Digesting my learning memories".to_string(),
+        }
+    }
+}
+
+/// A subject's current run of failures
+#[derive(Debug, Clone, FromRow)]
+pub struct FailureStreak {
+    subject: String,
+    pub failures: i32,
+    pub last_error: Option<String>,
+}
+
+impl FailureStreak {
+    pub fn subject(&self) -> Option<Subject> {
+        Subject::from_key(&self.subject)
+    }
+
+    /// What went wrong and what the Rei does about it, in its own voice
+    pub fn post_mortem(&self) -> Option<String> {
+        let subject = self.subject()?;
+        let error: String = self
+            .last_error
+            .as_deref()
+            .unwrap_or("unknown")
+            .chars()
+            .take(MAX_ERROR_CHARS)
+            .collect();
+        Some(format!(
+            "{} has failed {} times in a row (last error: {}). I'll leave it alone for {} hours before trying again.",
+            subject.describe(),
+            self.failures,
+            error,
+            BACKOFF_HOURS
+        ))
+    }
+}
+
+/// Failures of one Rei, recorded best effort (a failure to record is logged)
+pub struct FailureLog<'a> {
+    pub pool: &'a PgPool,
+    pub memory_kai: &'a MemoryKai,
+    pub embedding: &'a EmbeddingService,
+    pub event_bus: Option<&'a Arc<InProcessEventBus>>,
+}
+
+impl FailureLog<'_> {
+    /// The subject worked: its streak is over
+    pub async fn succeeded(&self, rei_id: Uuid, subject: &Subject) {
+        if let Err(e) = record_success(self.pool, rei_id, subject).await {
+            tracing::warn!("⚠️  Failed to clear failures of {}: {}", subject.key(), e);
+        }
+    }
+
+    /// The subject failed again: store a post-mortem once it keeps failing
+    pub async fn failed(&self, rei_id: Uuid, subject: &Subject, error: &str) {
+        let streak = match record_failure(self.pool, rei_id, subject, error).await {
+            Ok(Some(streak)) => streak,
+            Ok(None) => return,
+            Err(e) => {
+                tracing::warn!("⚠️  Failed to record failure of {}: {}", subject.key(), e);
+                return;
+            }
+        };
+        if let Err(e) = store(
+            self.memory_kai,
+            self.embedding,
+            self.event_bus,
+            rei_id,
+            &streak,
+        )
+        .await
+        {
+            tracing::warn!("⚠️  Failed to store post-mortem for Rei {}: {}", rei_id, e);
+        }
+    }
+}
+
+/// Clear a subject's failures after it succeeded
+async fn record_success(pool: &PgPool, rei_id: Uuid, subject: &Subject) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM rei_failures WHERE rei_id = $1 AND subject = $2")
+        .bind(rei_id)
+        .bind(subject.key())
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Count a failure; returns the streak when it just reached the threshold
+/// (once per streak), so the caller can store its post-mortem
+async fn record_failure(
+    pool: &PgPool,
+    rei_id: Uuid,
+    subject: &Subject,
+    error: &str,
+) -> Result<Option<FailureStreak>, sqlx::Error> {
+    let streak: FailureStreak = sqlx::query_as(
+        r#"
+        INSERT INTO rei_failures (rei_id, subject, failures, last_error, last_failed_at)
+        VALUES ($1, $2, 1, $3, NOW())
+        ON CONFLICT (rei_id, subject) DO UPDATE
+        SET failures = rei_failures.failures + 1,
+            last_error = EXCLUDED.last_error,
+            last_failed_at = NOW()
+        RETURNING subject, failures, last_error
+        "#,
+    )
+    .bind(rei_id)
+    .bind(subject.key())
+    .bind(error)
+    .fetch_one(pool)
+    .await?;
+
+    // A success deletes the streak, so each one passes the threshold once
+    Ok((streak.failures == FAILURE_THRESHOLD).then_some(streak))
+}
+
+/// Subjects the Rei is currently backing off from
+pub async fn backed_off(pool: &PgPool, rei_id: Uuid) -> Result<Vec<FailureStreak>, sqlx::Error> {
+    sqlx::query_as(
+        r#"
+        SELECT subject, failures, last_error FROM rei_failures
+        WHERE rei_id = $1
+          AND failures >= $2
+          AND last_failed_at > NOW() - make_interval(hours => $3)
+        ORDER BY last_failed_at DESC
+        "#,
+    )
+    .bind(rei_id)
+    .bind(FAILURE_THRESHOLD)
+    .bind(BACKOFF_HOURS)
+    .fetch_all(pool)
+    .await
+}
+
+/// Store a streak's post-mortem as a reflection memory
+async fn store(
+    memory_kai: &MemoryKai,
+    embedding: &EmbeddingService,
+    event_bus: Option<&Arc<InProcessEventBus>>,
+    rei_id: Uuid,
+    streak: &FailureStreak,
+) -> Result<(), String> {
+    let Some(content) = streak.post_mortem() else {
+        return Ok(());
+    };
+    let vector = embedding.embed(&content).await.map_err(|e| e.to_string())?;
+
+    let memory = Memory {
+        id: Uuid::new_v4().to_string(),
+        rei_id: rei_id.to_string(),
+        content,
+        memory_type: MemoryType::Reflection,
+        importance: 0.6,
+        tags: vec!["post_mortem".to_string(), "auto_generated".to_string()],
+        metadata: Some(serde_json::json!({
+            "source": "post_mortem",
+            "subject": streak.subject,
+            "failures": streak.failures,
+        })),
+        created_at: Utc::now(),
+    };
+
+    memory_kai
+        .add_memory(&rei_id.to_string(), memory.clone(), vector)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if let Some(event_bus) = event_bus {
+        event_bus.emit(memory.added_event(rei_id)).await;
+    }
+
+    tracing::info!(
+        "🩹 Post-mortem stored for Rei {}: {}",
+        rei_id,
+        memory.content
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subject_keys() {
+        for subject in [Subject::Search("rust async".to_string()), Subject::Digest] {
+            assert_eq!(Subject::from_key(&subject.key()), Some(subject));
+        }
+        assert_eq!(Subject::from_key("unknown"), None);
+
+        let streak = FailureStreak {
+            subject: "search:rust async".to_string(),
+            failures: 3,
+            last_error: Some("No results found".to_string()),
+        };
+        let post_mortem = streak.post_mortem().unwrap();
+        assert!(post_mortem.starts_with("Searching for \"rust async\" has failed 3 times"));
+        assert!(post_mortem.contains("No results found"));
+    }
+}
@@ -1,7 +1,8 @@
 use chrono::{DateTime, Utc};
 use qdrant_client::qdrant::{
-    Condition, CreateCollectionBuilder, CreateFieldIndexCollectionBuilder, Distance, FieldType,
-    Filter, PointStruct, Range, SearchPointsBuilder, UpsertPointsBuilder, VectorParamsBuilder,
+    Condition, CreateCollectionBuilder, CreateFieldIndexCollectionBuilder, DeleteCollectionBuilder,
+    Distance, FieldType, Filter, GetPointsBuilder, PointId, PointStruct, Range,
+    ScrollPointsBuilder, SearchPointsBuilder, UpsertPointsBuilder, VectorParamsBuilder,
 };
 use qdrant_client::Qdrant;
 use std::collections::HashMap;
@@ -21,8 +22,13 @@ pub struct SearchFilter {
     pub min_importance: Option<f32>,
     /// Filter memories created after this timestamp (for excluding already-digested)
     pub created_after: Option<DateTime<Utc>>,
+    /// Filter memories created at or before this timestamp
+    pub created_before: Option<DateTime<Utc>>,
 }
 
+/// Points fetched per scroll request
+const SCROLL_PAGE_SIZE: u32 = 256;
+
 /// Qdrant client wrapper - Gateway to the Memory Sea (記憶海)
 pub struct MemoryKai {
     client: Qdrant,
@@ -76,6 +82,25 @@ impl MemoryKai {
         Ok(())
     }
 
+    /// Drop a persona's memory collection, if it exists
+    pub async fn delete_persona_collection(
+        &self,
+        persona_id: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let collection_name = format!("{}_memories", persona_id);
+
+        if !self.client.collection_exists(&collection_name).await? {
+            return Ok(());
+        }
+
+        self.client
+            .delete_collection(DeleteCollectionBuilder::new(&collection_name))
+            .await?;
+
+        tracing::info!("🗑️  Deleted collection: {}", collection_name);
+        Ok(())
+    }
+
     /// Ensure required field indexes exist for filtering
     async fn ensure_field_indexes(
         &self,
@@ -117,6 +142,7 @@ impl MemoryKai {
     }
 
     /// Add a memory to the ocean
+    #[tracing::instrument(name = "qdrant.add_memory", skip_all, fields(persona_id = %persona_id))]
     pub async fn add_memory(
         &self,
         persona_id: &str,
@@ -145,6 +171,33 @@ impl MemoryKai {
         Ok(())
     }
 
+    /// Get a single memory by ID
+    #[tracing::instrument(name = "qdrant.get_memory", skip_all, fields(persona_id = %persona_id))]
+    pub async fn get_memory(
+        &self,
+        persona_id: &str,
+        memory_id: &str,
+    ) -> Result<Option<Memory>, Box<dyn std::error::Error>> {
+        let collection_name = format!("{}_memories", persona_id);
+
+        if !self.client.collection_exists(&collection_name).await? {
+            return Ok(None);
+        }
+
+        let ids: Vec<PointId> = vec![memory_id.to_string().into()];
+        let result = self
+            .client
+            .get_points(GetPointsBuilder::new(&collection_name, ids).with_payload(true))
+            .await?;
+
+        let memory = result.result.into_iter().next().and_then(|point| {
+            let payload_json = serde_json::to_value(&point.payload).ok()?;
+            serde_json::from_value(payload_json).ok()
+        });
+
+        Ok(memory)
+    }
+
     /// Search memories in the ocean
     pub async fn search_memories(
         &self,
@@ -164,6 +217,21 @@ impl MemoryKai {
         limit: usize,
         filter: SearchFilter,
     ) -> Result<Vec<Memory>, Box<dyn std::error::Error>> {
+        let scored = self
+            .search_memories_scored(persona_id, query_vector, limit, filter)
+            .await?;
+        Ok(scored.into_iter().map(|(memory, _)| memory).collect())
+    }
+
+    /// Search memories with filter options, keeping each match's similarity score
+    #[tracing::instrument(name = "qdrant.search", skip_all, fields(persona_id = %persona_id, limit = limit))]
+    pub async fn search_memories_scored(
+        &self,
+        persona_id: &str,
+        query_vector: Vec<f32>,
+        limit: usize,
+        filter: SearchFilter,
+    ) -> Result<Vec<(Memory, f32)>, Box<dyn std::error::Error>> {
         let collection_name = format!("{}_memories", persona_id);
 
         // Build filter conditions
@@ -181,12 +249,13 @@ impl MemoryKai {
         let search_result = self.client.search_points(search_builder).await?;
 
         // Parse results
-        let memories: Vec<Memory> = search_result
+        let memories: Vec<(Memory, f32)> = search_result
             .result
             .into_iter()
             .filter_map(|point| {
                 let payload_json = serde_json::to_value(&point.payload).ok()?;
-                serde_json::from_value(payload_json).ok()
+                let memory = serde_json::from_value(payload_json).ok()?;
+                Some((memory, point.score))
             })
             .collect();
 
@@ -199,7 +268,95 @@ impl MemoryKai {
         Ok(memories)
     }
 
+    /// List memories matching a filter without a query vector, newest first
+    #[tracing::instrument(name = "qdrant.list_memories", skip_all, fields(persona_id = %persona_id, limit = limit))]
+    pub async fn list_memories(
+        &self,
+        persona_id: &str,
+        limit: usize,
+        filter: SearchFilter,
+    ) -> Result<Vec<Memory>, Box<dyn std::error::Error>> {
+        let collection_name = format!("{}_memories", persona_id);
+
+        if !self.client.collection_exists(&collection_name).await? {
+            return Ok(vec![]);
+        }
+
+        let mut scroll_builder = ScrollPointsBuilder::new(&collection_name)
+            .limit(limit as u32)
+            .with_payload(true);
+
+        if let Some(f) = self.build_filter(&filter) {
+            scroll_builder = scroll_builder.filter(f);
+        }
+
+        let scroll_result = self.client.scroll(scroll_builder).await?;
+
+        // Scroll returns points in ID order, so sort by creation time
+        let mut memories: Vec<Memory> = scroll_result
+            .result
+            .into_iter()
+            .filter_map(|point| {
+                let payload_json = serde_json::to_value(&point.payload).ok()?;
+                serde_json::from_value(payload_json).ok()
+            })
+            .collect();
+        memories.sort_by_key(|m| std::cmp::Reverse(m.created_at));
+
+        Ok(memories)
+    }
+
+    /// All memories matching a filter, oldest first
+    ///
+    /// Scrolls through every page, so bound the filter (e.g. by creation
+    /// time) to keep the result small.
+    #[tracing::instrument(name = "qdrant.scroll_memories", skip_all, fields(persona_id = %persona_id))]
+    pub async fn scroll_memories(
+        &self,
+        persona_id: &str,
+        filter: SearchFilter,
+    ) -> Result<Vec<Memory>, Box<dyn std::error::Error>> {
+        let collection_name = format!("{}_memories", persona_id);
+
+        if !self.client.collection_exists(&collection_name).await? {
+            return Ok(vec![]);
+        }
+
+        let qdrant_filter = self.build_filter(&filter);
+        let mut memories: Vec<Memory> = Vec::new();
+        let mut offset: Option<PointId> = None;
+
+        loop {
+            let mut scroll_builder = ScrollPointsBuilder::new(&collection_name)
+                .limit(SCROLL_PAGE_SIZE)
+                .with_payload(true);
+            if let Some(f) = &qdrant_filter {
+                scroll_builder = scroll_builder.filter(f.clone());
+            }
+            if let Some(offset) = offset.take() {
+                scroll_builder = scroll_builder.offset(offset);
+            }
+
+            let page = self.client.scroll(scroll_builder).await?;
+            memories.extend(page.result.into_iter().filter_map(|point| {
+                let payload_json = serde_json::to_value(&point.payload).ok()?;
+                serde_json::from_value(payload_json).ok()
+            }));
+
+            match page.next_page_offset {
+                Some(next) => offset = Some(next),
+                None => break,
+            }
+        }
+
+        // Scroll returns points in ID order
+        memories.sort_by_key(|m| m.created_at);
+
+        Ok(memories)
+    }
+
     /// Count total memories for a persona
+    #[tracing::instrument(name = "qdrant.count_memories", skip_all, fields(persona_id = %persona_id))]
     pub async fn count_memories(
         &self,
         persona_id: &str,
@@ -242,17 +399,13 @@ impl MemoryKai {
             ));
         }
 
-        // Created after filter (for excluding already-digested memories)
-        if let Some(created_after) = filter.created_after {
-            // Qdrant datetime filter uses Unix timestamp
-            let timestamp = created_after.timestamp();
+        // Creation time window (for excluding already-digested memories)
+        if filter.created_after.is_some() || filter.created_before.is_some() {
             must_conditions.push(Condition::datetime_range(
                 "created_at",
                 qdrant_client::qdrant::DatetimeRange {
-                    gt: Some(prost_types::Timestamp {
-                        seconds: timestamp,
-                        nanos: 0,
-                    }),
+                    gt: filter.created_after.map(to_timestamp),
+                    lte: filter.created_before.map(to_timestamp),
                     ..Default::default()
                 },
             ));
@@ -297,3 +450,11 @@ impl MemoryKai {
         Some(filter_builder)
     }
 }
+
+/// Qdrant datetime filters take protobuf timestamps
+fn to_timestamp(at: DateTime<Utc>) -> prost_types::Timestamp {
+    prost_types::Timestamp {
+        seconds: at.timestamp(),
+        nanos: at.timestamp_subsec_nanos() as i32,
+    }
+}
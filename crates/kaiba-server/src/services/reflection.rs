@@ -0,0 +1,397 @@
+//! Reflection Service - A Rei journals about its own memories
+//!
+//! Gemini reads the memories a Rei gained since its last reflection, along
+//! with that reflection, and writes a short first-person entry in the Rei's
+//! voice: opinions it formed, what surprised it, questions it wants to follow
+//! up. The entry is stored as a Reflection memory, so later calls and
+//! reflections build on it rather than on raw facts alone.
+
+use crate::adapters::InProcessEventBus;
+use crate::models::{Memory, MemoryType, Rei};
+use crate::services::embedding::EmbeddingService;
+use crate::services::qdrant::{MemoryKai, SearchFilter};
+use chrono::{DateTime, Duration, Utc};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const GEMINI_URL: &str =
+    "https://generativelanguage.googleapis.com/v1beta/models/gemini-2.0-flash:generateContent";
+
+/// Most memories read for one reflection (the newest are kept)
+const MAX_REFLECTION_MEMORIES: usize = 30;
+
+/// How far back a Rei's first reflection looks
+const FIRST_REFLECTION_WINDOW_DAYS: i64 = 7;
+
+/// Energy spent on a reflection
+const REFLECTION_ENERGY_COST: i32 = 10;
+
+/// Reflection result
+#[derive(Debug, Clone, Serialize)]
+pub struct ReflectionResult {
+    pub rei_id: Uuid,
+    pub memories_considered: usize,
+    pub reflection_created: bool,
+    pub reflection: String,
+}
+
+/// Reflection service writing journal entries for Reis
+pub struct ReflectionService {
+    pool: PgPool,
+    memory_kai: Arc<MemoryKai>,
+    embedding: EmbeddingService,
+    client: Client,
+    gemini_api_key: Option<String>,
+    event_bus: Option<Arc<InProcessEventBus>>,
+}
+
+impl ReflectionService {
+    pub fn new(
+        pool: PgPool,
+        memory_kai: Arc<MemoryKai>,
+        embedding: EmbeddingService,
+        gemini_api_key: Option<String>,
+    ) -> Self {
+        Self {
+            pool,
+            memory_kai,
+            embedding,
+            client: Client::new(),
+            gemini_api_key,
+            event_bus: None,
+        }
+    }
+
+    /// Publish MemoryAdded events
+    pub fn with_event_bus(mut self, event_bus: Option<Arc<InProcessEventBus>>) -> Self {
+        self.event_bus = event_bus;
+        self
+    }
+
+    /// Reflect on the memories gained since the last reflection
+    #[tracing::instrument(name = "reflect", skip(self))]
+    pub async fn reflect(&self, rei_id: Uuid) -> Result<ReflectionResult, ReflectionError> {
+        let api_key = self
+            .gemini_api_key
+            .as_deref()
+            .ok_or(ReflectionError::NoApiKey)?;
+        let rei: Rei = sqlx::query_as("SELECT * FROM reis WHERE id = $1")
+            .bind(rei_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| ReflectionError::DatabaseError(e.to_string()))?
+            .ok_or(ReflectionError::ReiNotFound)?;
+
+        let started_at = Utc::now();
+        let since = self
+            .get_last_reflection_at(rei_id)
+            .await?
+            .unwrap_or(started_at - Duration::days(FIRST_REFLECTION_WINDOW_DAYS));
+
+        // The previous reflection was stored after `since`, so it comes
+        // back with the memories
+        let filter = SearchFilter {
+            created_after: Some(since),
+            created_before: Some(started_at),
+            ..Default::default()
+        };
+        let recent = self
+            .memory_kai
+            .scroll_memories(&rei_id.to_string(), filter)
+            .await
+            .map_err(|e| ReflectionError::SearchFailed(e.to_string()))?;
+        let (previous, memories) = split_recent(recent);
+
+        if memories.is_empty() {
+            // Nothing new: come due again only after another interval
+            self.finish(rei_id, started_at, 0).await?;
+            return Ok(ReflectionResult {
+                rei_id,
+                memories_considered: 0,
+                reflection_created: false,
+                reflection: "Nothing new to reflect on".to_string(),
+            });
+        }
+
+        let reflection = self
+            .generate_reflection(api_key, &rei, previous.as_ref(), &memories)
+            .await?;
+        self.store_reflection(rei_id, &reflection, &memories)
+            .await?;
+        self.finish(rei_id, started_at, REFLECTION_ENERGY_COST)
+            .await?;
+
+        tracing::info!(
+            "💭 Reflection written for Rei {}: {} memories considered",
+            rei_id,
+            memories.len()
+        );
+
+        Ok(ReflectionResult {
+            rei_id,
+            memories_considered: memories.len(),
+            reflection_created: true,
+            reflection,
+        })
+    }
+
+    async fn get_last_reflection_at(
+        &self,
+        rei_id: Uuid,
+    ) -> Result<Option<DateTime<Utc>>, ReflectionError> {
+        let result: Option<(Option<DateTime<Utc>>,)> =
+            sqlx::query_as("SELECT last_reflection_at FROM rei_states WHERE rei_id = $1")
+                .bind(rei_id)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(|e| ReflectionError::DatabaseError(e.to_string()))?;
+
+        Ok(result.and_then(|(ts,)| ts))
+    }
+
+    /// Generate the journal entry using Gemini
+    #[tracing::instrument(name = "llm.reflection", skip_all, fields(memories = memories.len()))]
+    async fn generate_reflection(
+        &self,
+        api_key: &str,
+        rei: &Rei,
+        previous: Option<&Memory>,
+        memories: &[Memory],
+    ) -> Result<String, ReflectionError> {
+        let memory_content: String = memories
+            .iter()
+            .map(|m| {
+                format!(
+                    "### {} ({})\n{}\n",
+                    m.created_at.format("%Y-%m-%d"),
+                    m.memory_type,
+                    m.content
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let previous = previous.map_or("(this is your first reflection)", |m| m.content.as_str());
+
+        let prompt = format!(
+            r#"You are {}, {}. Write a short private journal entry reflecting on what you have recently learned and experienced.
+
+## Your Previous Reflection:
+{}
+
+## Recent Memories (oldest first):
+{}
+
+## Your Task:
+Write 1-3 short paragraphs in the first person (in the same language as the memories). Do not just restate the facts. Say what you think about them: opinions you formed or changed, what surprised you, how it connects to your earlier thoughts, and the open questions you want to explore next."#,
+            rei.name, rei.role, previous, memory_content
+        );
+
+        let request = GeminiRequest {
+            contents: vec![GeminiContent {
+                parts: vec![GeminiPart { text: prompt }],
+            }],
+        };
+
+        let response = self
+            .client
+            .post(format!("{}?key={}", GEMINI_URL, api_key))
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| ReflectionError::ApiError(e.to_string()))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(ReflectionError::ApiError(format!("{}: {}", status, body)));
+        }
+
+        let result: GeminiResponse = response
+            .json()
+            .await
+            .map_err(|e| ReflectionError::ParseError(e.to_string()))?;
+
+        result
+            .candidates
+            .first()
+            .and_then(|c| c.content.parts.first())
+            .map(|p| p.text.trim().to_string())
+            .filter(|text| !text.is_empty())
+            .ok_or_else(|| ReflectionError::ParseError("empty reflection".to_string()))
+    }
+
+    /// Store the entry as a Reflection memory linked to its sources
+    async fn store_reflection(
+        &self,
+        rei_id: Uuid,
+        reflection: &str,
+        sources: &[Memory],
+    ) -> Result<(), ReflectionError> {
+        let memory = Memory {
+            id: Uuid::new_v4().to_string(),
+            rei_id: rei_id.to_string(),
+            content: reflection.to_string(),
+            memory_type: MemoryType::Reflection,
+            importance: 0.7,
+            tags: vec!["reflection".to_string(), "auto_generated".to_string()],
+            metadata: Some(serde_json::json!({
+                "sources": sources.iter().map(|m| &m.id).collect::<Vec<_>>(),
+            })),
+            created_at: Utc::now(),
+        };
+
+        let vector = self
+            .embedding
+            .embed(reflection)
+            .await
+            .map_err(|e| ReflectionError::EmbeddingFailed(e.to_string()))?;
+
+        self.memory_kai
+            .add_memory(&rei_id.to_string(), memory.clone(), vector)
+            .await
+            .map_err(|e| ReflectionError::StorageFailed(e.to_string()))?;
+
+        if let Some(event_bus) = &self.event_bus {
+            event_bus.emit(memory.added_event(rei_id)).await;
+        }
+
+        Ok(())
+    }
+
+    /// Record the reflection and spend its energy
+    async fn finish(
+        &self,
+        rei_id: Uuid,
+        reflected_through: DateTime<Utc>,
+        energy_cost: i32,
+    ) -> Result<(), ReflectionError> {
+        sqlx::query(
+            r#"
+            UPDATE rei_states
+            SET last_reflection_at = $2,
+                energy_level = GREATEST(0, energy_level - $3),
+                last_active_at = NOW()
+            WHERE rei_id = $1
+            "#,
+        )
+        .bind(rei_id)
+        .bind(reflected_through)
+        .bind(energy_cost)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| ReflectionError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+/// The newest earlier reflection, and the other memories to reflect on
+/// (the newest `MAX_REFLECTION_MEMORIES`, oldest first)
+fn split_recent(recent: Vec<Memory>) -> (Option<Memory>, Vec<Memory>) {
+    let (reflections, mut memories): (Vec<Memory>, Vec<Memory>) = recent
+        .into_iter()
+        .partition(|m| matches!(m.memory_type, MemoryType::Reflection));
+    let skip = memories.len().saturating_sub(MAX_REFLECTION_MEMORIES);
+    memories.drain(..skip);
+    (reflections.into_iter().last(), memories)
+}
+
+// Gemini API types
+#[derive(Serialize)]
+struct GeminiRequest {
+    contents: Vec<GeminiContent>,
+}
+
+#[derive(Serialize)]
+struct GeminiContent {
+    parts: Vec<GeminiPart>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct GeminiPart {
+    text: String,
+}
+
+#[derive(Deserialize)]
+struct GeminiResponse {
+    candidates: Vec<GeminiCandidate>,
+}
+
+#[derive(Deserialize)]
+struct GeminiCandidate {
+    content: GeminiContentResponse,
+}
+
+#[derive(Deserialize)]
+struct GeminiContentResponse {
+    parts: Vec<GeminiPart>,
+}
+
+/// Reflection error types
+#[derive(Debug, Clone)]
+pub enum ReflectionError {
+    NoApiKey,
+    ReiNotFound,
+    SearchFailed(String),
+    EmbeddingFailed(String),
+    StorageFailed(String),
+    ApiError(String),
+    ParseError(String),
+    DatabaseError(String),
+}
+
+impl std::fmt::Display for ReflectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReflectionError::NoApiKey => write!(f, "No Gemini API key configured"),
+            ReflectionError::ReiNotFound => write!(f, "Rei not found"),
+            ReflectionError::SearchFailed(msg) => write!(f, "Memory search failed: {}", msg),
+            ReflectionError::EmbeddingFailed(msg) => write!(f, "Embedding failed: {}", msg),
+            ReflectionError::StorageFailed(msg) => write!(f, "Storage failed: {}", msg),
+            ReflectionError::ApiError(msg) => write!(f, "API error: {}", msg),
+            ReflectionError::ParseError(msg) => write!(f, "Parse error: {}", msg),
+            ReflectionError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ReflectionError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn memory(content: &str, memory_type: MemoryType) -> Memory {
+        Memory {
+            id: content.to_string(),
+            rei_id: Uuid::nil().to_string(),
+            content: content.to_string(),
+            memory_type,
+            importance: 0.5,
+            tags: vec![],
+            metadata: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_split_recent() {
+        let mut recent = vec![
+            memory("old reflection", MemoryType::Reflection),
+            memory("fact", MemoryType::Fact),
+            memory("newer reflection", MemoryType::Reflection),
+        ];
+        recent.extend(
+            (0..MAX_REFLECTION_MEMORIES).map(|i| memory(&i.to_string(), MemoryType::Learning)),
+        );
+
+        let (previous, memories) = split_recent(recent);
+        assert_eq!(previous.unwrap().content, "newer reflection");
+        assert_eq!(memories.len(), MAX_REFLECTION_MEMORIES);
+        // The oldest memory is dropped first
+        assert_eq!(memories[0].content, "0");
+    }
+}
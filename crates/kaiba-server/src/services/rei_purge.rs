@@ -0,0 +1,78 @@
+//! Rei Purge - Permanently remove soft-deleted Reis
+//!
+//! Deleting a Rei only marks it deleted, so it can be restored. Once an
+//! hour the purger removes Reis deleted longer ago than the retention
+//! window, along with their rows (state, Teis associations, webhooks, ...)
+//! and their memory collection in MemoryKai.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio_util::sync::CancellationToken;
+
+use crate::services::qdrant::MemoryKai;
+use crate::AppReiService;
+
+/// How often to look for Reis to purge
+const CHECK_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Purges soft-deleted Reis after the retention window
+pub struct ReiPurger {
+    rei_service: Arc<AppReiService>,
+    memory_kai: Option<Arc<MemoryKai>>,
+    retention: chrono::Duration,
+}
+
+impl ReiPurger {
+    pub fn new(
+        rei_service: Arc<AppReiService>,
+        memory_kai: Option<Arc<MemoryKai>>,
+        retention_days: u32,
+    ) -> Self {
+        Self {
+            rei_service,
+            memory_kai,
+            retention: chrono::Duration::days(i64::from(retention_days)),
+        }
+    }
+
+    /// Run the purger until shutdown
+    pub async fn run(self: Arc<Self>, shutdown: CancellationToken) {
+        let mut ticker = tokio::time::interval(CHECK_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {}
+                _ = shutdown.cancelled() => return,
+            }
+            self.purge().await;
+        }
+    }
+
+    async fn purge(&self) {
+        let cutoff = chrono::Utc::now() - self.retention;
+        let purged = match self.rei_service.purge_deleted(cutoff).await {
+            Ok(purged) => purged,
+            Err(e) => {
+                tracing::warn!("⚠️  Failed to purge deleted Reis: {}", e);
+                return;
+            }
+        };
+        if purged.is_empty() {
+            return;
+        }
+
+        tracing::info!("🧹 Purged {} deleted Reis", purged.len());
+
+        let Some(memory_kai) = &self.memory_kai else {
+            return;
+        };
+        for rei_id in purged {
+            if let Err(e) = memory_kai
+                .delete_persona_collection(&rei_id.to_string())
+                .await
+            {
+                tracing::warn!("⚠️  Failed to delete memories of Rei {}: {}", rei_id, e);
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+//! Run History - Recording scheduler cycles and triggers
+//!
+//! A run is inserted when it starts and completed when it ends, so one that
+//! crashed or hung is visible as unfinished. Recording is best effort: a
+//! failure to record is logged and never stops the run itself.
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::ReiRunResult;
+
+/// Days runs are kept
+const RUN_RETENTION_DAYS: i32 = 30;
+
+/// What started a run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunKind {
+    /// Global interval cycle
+    Interval,
+    /// Reis whose cron schedule came due
+    Schedule,
+    /// `POST /kaiba/trigger`
+    Trigger,
+}
+
+impl RunKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RunKind::Interval => "interval",
+            RunKind::Schedule => "schedule",
+            RunKind::Trigger => "trigger",
+        }
+    }
+}
+
+/// A run being recorded
+pub struct RunRecord {
+    pool: PgPool,
+    /// Unset when the start could not be recorded
+    id: Option<Uuid>,
+}
+
+impl RunRecord {
+    /// Record the start of a run
+    pub async fn start(pool: &PgPool, kind: RunKind, dry_run: bool) -> Self {
+        let id = sqlx::query_scalar(
+            "INSERT INTO scheduler_runs (kind, dry_run) VALUES ($1, $2) RETURNING id",
+        )
+        .bind(kind.as_str())
+        .bind(dry_run)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| tracing::warn!("⚠️  Failed to record {} run: {}", kind.as_str(), e))
+        .ok();
+
+        Self {
+            pool: pool.clone(),
+            id,
+        }
+    }
+
+    /// Record the end of the run with each Rei's result, and why the run as
+    /// a whole failed, if it did
+    pub async fn finish(self, results: &[ReiRunResult], error: Option<String>) {
+        let Some(id) = self.id else {
+            return;
+        };
+        let errors = results.iter().filter(|r| !r.success).count() as i32;
+
+        if let Err(e) = sqlx::query(
+            r#"
+            UPDATE scheduler_runs
+            SET finished_at = NOW(), reis_processed = $2, errors = $3, error = $4, results = $5
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(results.len() as i32)
+        .bind(errors)
+        .bind(error)
+        .bind(serde_json::json!(results))
+        .execute(&self.pool)
+        .await
+        {
+            tracing::warn!("⚠️  Failed to record end of run {}: {}", id, e);
+        }
+    }
+}
+
+/// Delete runs older than the retention period
+pub async fn prune(pool: &PgPool) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        "DELETE FROM scheduler_runs WHERE started_at < NOW() - make_interval(days => $1)",
+    )
+    .bind(RUN_RETENTION_DAYS)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected())
+}
@@ -0,0 +1,126 @@
+//! Rei Schedules - When a scheduled Rei gets its autonomous cycle
+//!
+//! Schedules are standard 5-field cron expressions (minute, hour,
+//! day-of-month, month, day-of-week) evaluated in an IANA timezone, so
+//! `0 7 * * *` in `Asia/Tokyo` means 07:00 Tokyo time every day, across DST
+//! changes. Day-of-week is 0-7 (0 and 7 are Sunday) or `SUN`-`SAT`.
+
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use std::str::FromStr;
+
+/// Day-of-week names, indexed by their standard cron number
+const WEEKDAYS: [&str; 8] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT", "SUN"];
+
+/// A parsed cron expression with its timezone
+#[derive(Debug, Clone)]
+pub struct CronSchedule {
+    schedule: cron::Schedule,
+    timezone: Tz,
+}
+
+impl CronSchedule {
+    /// Parse a 5-field cron expression in an IANA timezone
+    pub fn parse(expression: &str, timezone: &str) -> Result<Self, String> {
+        let timezone: Tz = timezone
+            .parse()
+            .map_err(|_| format!("unknown timezone: {}", timezone))?;
+
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(
+                "must have 5 fields: minute hour day-of-month month day-of-week".to_string(),
+            );
+        };
+
+        // The cron crate wants seconds first and numbers Sunday as 1
+        let expression = format!(
+            "0 {} {} {} {} {}",
+            minute,
+            hour,
+            day,
+            month,
+            weekday_names(weekday)
+        );
+        let schedule = cron::Schedule::from_str(&expression)
+            .map_err(|e| format!("invalid cron expression: {}", e))?;
+
+        Ok(Self { schedule, timezone })
+    }
+
+    /// First run strictly after `after`
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.schedule
+            .after(&after.with_timezone(&self.timezone))
+            .next()
+            .map(|next| next.with_timezone(&Utc))
+    }
+}
+
+/// Replace standard day-of-week numbers with names (`1-5` -> `MON-FRI`)
+fn weekday_names(field: &str) -> String {
+    field
+        .split(',')
+        .map(|item| {
+            let (range, step) = match item.split_once('/') {
+                Some((range, step)) => (range, Some(step)),
+                None => (item, None),
+            };
+            let range = range
+                .split('-')
+                .map(|part| {
+                    part.parse::<usize>()
+                        .ok()
+                        .and_then(|n| WEEKDAYS.get(n))
+                        .map_or(part, |name| *name)
+                })
+                .collect::<Vec<_>>()
+                .join("-");
+            match step {
+                Some(step) => format!("{}/{}", range, step),
+                None => range,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_next_run_in_timezone() {
+        let schedule = CronSchedule::parse("0 7 * * *", "Asia/Tokyo").unwrap();
+        // 2026-01-05 00:00 UTC is 09:00 in Tokyo, so the next 07:00 is tomorrow
+        let now = Utc.with_ymd_and_hms(2026, 1, 5, 0, 0, 0).unwrap();
+        assert_eq!(
+            schedule.next_after(now),
+            Some(Utc.with_ymd_and_hms(2026, 1, 5, 22, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_standard_weekday_numbers() {
+        assert_eq!(weekday_names("1-5"), "MON-FRI");
+        assert_eq!(weekday_names("0,6"), "SUN,SAT");
+        assert_eq!(weekday_names("*/2"), "*/2");
+        assert_eq!(weekday_names("MON"), "MON");
+
+        // Monday 2026-01-05 -> next Monday
+        let weekly = CronSchedule::parse("30 9 * * 1", "UTC").unwrap();
+        let now = Utc.with_ymd_and_hms(2026, 1, 5, 10, 0, 0).unwrap();
+        assert_eq!(
+            weekly.next_after(now),
+            Some(Utc.with_ymd_and_hms(2026, 1, 12, 9, 30, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid() {
+        assert!(CronSchedule::parse("0 7 * * *", "Mars/Olympus").is_err());
+        assert!(CronSchedule::parse("0 0 7 * * *", "UTC").is_err());
+        assert!(CronSchedule::parse("61 7 * * *", "UTC").is_err());
+    }
+}
@@ -2,25 +2,62 @@
 //!
 //! For each Rei:
 //! 1. Regenerate energy
-//! 2. Decide action (Learn, Digest, Rest)
-//! 3. Execute action
-//! 4. Dispatch webhooks on learning completion
-
-use crate::adapters::{HttpWebhook, PgReiWebhookRepository};
-use crate::models::{MemoryType, Rei, ReiState};
-use crate::services::decision::{Action, DecisionMaker};
-use crate::services::digest::{DigestResult, DigestService};
+//! 2. Decide action (Learn, Digest, Reflect, Rest) under the Rei's decision
+//!    policy
+//! 3. Execute action (services publish events on completion)
+//! 4. Roll up daily digests into weekly, and weekly into monthly, once a
+//!    week or month has ended
+//!
+//! Reis with a schedule (`rei_schedules`) are processed when their cron
+//! expression comes due, checked every minute; the rest are processed once
+//! per interval. Each interval cycle also regenerates energy for all Reis and
+//! prunes webhook delivery records per the retention policy.
+//!
+//! Reis whose autonomy is paused are left out of both. A schedule that comes
+//! due while its Rei is paused runs once on resume; the runs it missed are
+//! not made up.
+//!
+//! Reis are processed side by side within the configured [`Parallelism`]:
+//! a bounded number at once, each with a deadline, and with jitter before
+//! each Rei's external API calls.
+//!
+//! Every cycle and due-schedule run is recorded with each Rei's result (see
+//! [`run_history`](crate::services::run_history)).
+//!
+//! With several replicas, only the [leader](crate::services::leader) runs
+//! cycles; the others skip their ticks until they take over.
+//!
+//! Without a Gemini key the scheduler still runs: learning studies the Reis'
+//! existing memories, and digests and reflections (which need Gemini) are
+//! not chosen.
+
+use crate::adapters::{InProcessEventBus, PgReiWebhookRepository};
+use crate::models::{MemoryType, Rei, ReiRunResult, ReiSchedule, ReiState};
+use crate::services::decision::{self, Action};
+use crate::services::digest::{DigestService, DigestThreshold, DigestTier};
 use crate::services::embedding::EmbeddingService;
+use crate::services::energy;
+use crate::services::leader::{Leadership, SCHEDULER_LOCK_KEY};
+use crate::services::learning_source;
+use crate::services::parallel::Parallelism;
 use crate::services::qdrant::MemoryKai;
-use crate::services::self_learning::{LearningSession, SelfLearningService};
+use crate::services::reflection::ReflectionService;
+use crate::services::run_history::{self, RunKind, RunRecord};
+use crate::services::schedule::CronSchedule;
+use crate::services::self_learning::SelfLearningService;
 use crate::services::web_search::WebSearchAgent;
-use kaiba::{ReiWebhookRepository, TeiWebhook, WebhookEventType, WebhookPayload};
+use crate::supervisor::TaskSupervisor;
+use kaiba::{DeliveryRetention, ReiWebhookRepository};
 use sqlx::PgPool;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::time::interval;
+use tokio::time::{interval, MissedTickBehavior};
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
+/// How often schedules are checked for due runs (cron resolution)
+const SCHEDULE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
 /// Scheduler configuration
 #[derive(Debug, Clone)]
 pub struct SchedulerConfig {
@@ -28,6 +65,12 @@ pub struct SchedulerConfig {
     pub interval: Duration,
     /// Enable/disable scheduler
     pub enabled: bool,
+    /// How many Reis are processed at once, and for how long
+    pub parallelism: Parallelism,
+    /// Webhook delivery record retention
+    pub delivery_retention: DeliveryRetention,
+    /// Least input a digest needs
+    pub digest_threshold: DigestThreshold,
 }
 
 impl Default for SchedulerConfig {
@@ -35,6 +78,9 @@ impl Default for SchedulerConfig {
         Self {
             interval: Duration::from_secs(3600), // 1 hour
             enabled: true,
+            parallelism: Parallelism::default(),
+            delivery_retention: DeliveryRetention::default(),
+            digest_threshold: DigestThreshold::default(),
         }
     }
 }
@@ -44,26 +90,24 @@ pub struct AutonomousScheduler {
     pool: PgPool,
     memory_kai: Arc<MemoryKai>,
     embedding: EmbeddingService,
-    web_search: WebSearchAgent,
+    /// Unset without a Gemini key: learning then studies existing memories
+    web_search: Option<WebSearchAgent>,
     gemini_api_key: Option<String>,
     config: SchedulerConfig,
-    // Webhook support
-    webhook_repo: Option<Arc<PgReiWebhookRepository>>,
-    http_webhook: Option<Arc<HttpWebhook>>,
+    // Event publishing (webhooks, stats, ...)
+    event_bus: Option<Arc<InProcessEventBus>>,
 }
 
 impl AutonomousScheduler {
     /// Creates a new scheduler
-    #[allow(clippy::too_many_arguments)]
     pub fn new(
         pool: PgPool,
         memory_kai: Arc<MemoryKai>,
         embedding: EmbeddingService,
-        web_search: WebSearchAgent,
+        web_search: Option<WebSearchAgent>,
         gemini_api_key: Option<String>,
         config: Option<SchedulerConfig>,
-        webhook_repo: Option<Arc<PgReiWebhookRepository>>,
-        http_webhook: Option<Arc<HttpWebhook>>,
+        event_bus: Option<Arc<InProcessEventBus>>,
     ) -> Self {
         Self {
             pool,
@@ -72,22 +116,18 @@ impl AutonomousScheduler {
             web_search,
             gemini_api_key,
             config: config.unwrap_or_default(),
-            webhook_repo,
-            http_webhook,
+            event_bus,
         }
     }
 
-    /// Start the scheduler (runs in background)
-    pub fn start(self) -> tokio::task::JoinHandle<()> {
-        tokio::spawn(async move {
-            self.run().await;
-        })
-    }
-
-    /// Run the scheduler loop
-    async fn run(self) {
+    /// Run the scheduler loop until shutdown
+    ///
+    /// A cycle in progress finishes the Reis it is working on, then stops.
+    pub async fn run(self: Arc<Self>, shutdown: CancellationToken) {
         if !self.config.enabled {
             tracing::info!("📅 Autonomous scheduler disabled");
+            // Idle rather than return, which the supervisor treats as a crash
+            shutdown.cancelled().await;
             return;
         }
 
@@ -96,42 +136,154 @@ impl AutonomousScheduler {
             self.config.interval
         );
 
-        let mut ticker = interval(self.config.interval);
+        let mut cycle = interval(self.config.interval);
+        let mut schedules = interval(SCHEDULE_CHECK_INTERVAL);
+        // A long cycle must not be followed by a burst of catch-up checks
+        schedules.set_missed_tick_behavior(MissedTickBehavior::Delay);
 
         // Skip the first immediate tick
-        ticker.tick().await;
+        cycle.tick().await;
+
+        let mut leadership = Leadership::new(self.pool.clone(), SCHEDULER_LOCK_KEY);
 
         loop {
-            ticker.tick().await;
-            tracing::info!("🔄 Scheduler: Starting autonomous cycle...");
+            // true for the interval cycle, false for the schedule check
+            let interval_cycle = tokio::select! {
+                _ = cycle.tick() => true,
+                _ = schedules.tick() => false,
+                _ = shutdown.cancelled() => break,
+            };
+            if !leadership.check().await {
+                continue;
+            }
+            if interval_cycle {
+                self.run_cycle(&shutdown).await;
+            } else {
+                self.run_due_schedules(&shutdown).await;
+            }
+        }
 
-            // 1. Regenerate energy for all Reis
-            match self.regenerate_all_energy().await {
-                Ok(count) => tracing::info!("⚡ Regenerated energy for {} Reis", count),
-                Err(e) => tracing::warn!("⚠️  Energy regeneration failed: {}", e),
+        leadership.resign().await;
+    }
+
+    /// Interval cycle: energy, unscheduled Reis, delivery and run pruning
+    async fn run_cycle(&self, shutdown: &CancellationToken) {
+        tracing::info!("🔄 Scheduler: Starting autonomous cycle...");
+        let record = RunRecord::start(&self.pool, RunKind::Interval, false).await;
+
+        // 1. Regenerate energy for all Reis
+        match energy::regenerate(&self.pool, None).await {
+            Ok(count) => tracing::info!("⚡ Regenerated energy for {} Reis", count),
+            Err(e) => tracing::warn!("⚠️  Energy regeneration failed: {}", e),
+        }
+
+        // 2. Process each Rei without a schedule of its own
+        let (results, error) = match self.get_unscheduled_reis().await {
+            Ok(reis) => (self.process_reis(&reis, shutdown).await, None),
+            Err(e) => {
+                tracing::error!("Failed to get Reis: {}", e);
+                (Vec::new(), Some(format!("Failed to get Reis: {}", e)))
             }
+        };
+        if shutdown.is_cancelled() {
+            tracing::info!("🛑 Scheduler: Cycle interrupted by shutdown");
+            record
+                .finish(&results, Some("Interrupted by shutdown".to_string()))
+                .await;
+            return;
+        }
+        record.finish(&results, error).await;
+
+        // 3. Prune old webhook deliveries and run history
+        self.prune_webhook_deliveries().await;
+        match run_history::prune(&self.pool).await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!("🧹 Pruned {} scheduler runs", count),
+            Err(e) => tracing::warn!("⚠️  Scheduler run pruning failed: {}", e),
+        }
 
-            // 2. Get all Reis and process each
-            let reis = match self.get_all_reis().await {
-                Ok(reis) => reis,
-                Err(e) => {
-                    tracing::error!("Failed to get Reis: {}", e);
-                    continue;
-                }
-            };
+        tracing::info!("🔄 Scheduler: Autonomous cycle completed");
+    }
 
-            for rei in reis {
-                if let Err(e) = self.process_rei(&rei).await {
+    /// Process the scheduled Reis whose next run has come
+    async fn run_due_schedules(&self, shutdown: &CancellationToken) {
+        let due = match self.get_due_schedules().await {
+            Ok(due) => due,
+            Err(e) => {
+                tracing::warn!("⚠️  Failed to get due schedules: {}", e);
+                return;
+            }
+        };
+
+        let mut reis = Vec::with_capacity(due.len());
+        for (schedule, rei) in due {
+            // Advance first, so a failing run is not retried every minute
+            if let Err(e) = self.advance_schedule(&schedule).await {
+                tracing::warn!("⚠️  Failed to advance schedule of {}: {}", rei.name, e);
+                continue;
+            }
+            tracing::info!("📅 {} is due ({})", rei.name, schedule.cron);
+            reis.push(rei);
+        }
+        if reis.is_empty() {
+            return;
+        }
+
+        let record = RunRecord::start(&self.pool, RunKind::Schedule, false).await;
+        let results = self.process_reis(&reis, shutdown).await;
+        let error = shutdown
+            .is_cancelled()
+            .then(|| "Interrupted by shutdown".to_string());
+        record.finish(&results, error).await;
+    }
+
+    /// Process Reis side by side, within the configured parallelism
+    ///
+    /// Reis not yet started when shutdown begins are skipped and left out of
+    /// the results.
+    async fn process_reis(&self, reis: &[Rei], shutdown: &CancellationToken) -> Vec<ReiRunResult> {
+        let parallelism = self.config.parallelism;
+        let outcomes = parallelism
+            .run(reis, true, |rei| async move {
+                if shutdown.is_cancelled() {
+                    return None;
+                }
+                Some(self.process_rei(rei).await)
+            })
+            .await;
+
+        let mut results = Vec::with_capacity(reis.len());
+        for (rei, outcome) in reis.iter().zip(outcomes) {
+            match outcome {
+                Ok(None) => {}
+                Ok(Some(Ok(result))) => results.push(result),
+                Ok(Some(Err(e))) => {
                     tracing::warn!("⚠️  Failed to process Rei {}: {}", rei.name, e);
+                    results.push(ReiRunResult::new(rei, "Skip", false, e.to_string()));
+                }
+                Err(_) => {
+                    tracing::warn!(
+                        "⚠️  Processing Rei {} timed out after {:?}",
+                        rei.name,
+                        parallelism.rei_timeout
+                    );
+                    results.push(ReiRunResult::new(
+                        rei,
+                        "Skip",
+                        false,
+                        format!("Timed out after {:?}", parallelism.rei_timeout),
+                    ));
                 }
             }
-
-            tracing::info!("🔄 Scheduler: Autonomous cycle completed");
         }
+        results
     }
 
     /// Process a single Rei - decide and execute action
-    async fn process_rei(&self, rei: &Rei) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    async fn process_rei(
+        &self,
+        rei: &Rei,
+    ) -> Result<ReiRunResult, Box<dyn std::error::Error + Send + Sync>> {
         // Get Rei state
         let state = sqlx::query_as::<_, ReiState>("SELECT * FROM rei_states WHERE rei_id = $1")
             .bind(rei.id)
@@ -142,9 +294,14 @@ impl AutonomousScheduler {
         // Count learning memories (simplified - count recent learnings)
         let memories_count = self.count_learning_memories(rei.id).await.unwrap_or(0);
 
-        // Make decision
-        let decision_maker = DecisionMaker::new(None);
-        let decision = decision_maker.decide(&state, memories_count);
+        // Make and log the decision under the Rei's policy
+        let decision = decision::decide_for_rei(
+            &self.pool,
+            self.gemini_api_key.as_deref(),
+            &state,
+            memories_count,
+        )
+        .await?;
 
         tracing::info!(
             "🧠 {} decides: {} ({})",
@@ -154,33 +311,38 @@ impl AutonomousScheduler {
         );
 
         // Execute action
-        match decision.action {
-            Action::Learn => {
-                self.execute_learn(rei.id).await?;
-            }
-            Action::Digest => {
-                self.execute_digest(rei.id).await?;
-            }
+        let outcome = match decision.action {
+            Action::Learn => self.execute_learn(rei.id).await,
+            Action::Digest => self.execute_digest(rei.id).await,
+            Action::Reflect => self.execute_reflect(rei.id).await,
             Action::Rest => {
                 tracing::info!("  😴 {} is resting", rei.name);
+                Ok(decision.reason.clone())
             }
-        }
+        };
 
-        Ok(())
+        self.roll_up_digests(rei.id).await;
+
+        Ok(match outcome {
+            Ok(details) => ReiRunResult::new(rei, decision.action.label(), true, details),
+            Err(e) => ReiRunResult::new(rei, decision.action.label(), false, e),
+        })
     }
 
-    /// Execute learning action
-    async fn execute_learn(
-        &self,
-        rei_id: Uuid,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    /// Execute learning action, returning what was learned or why it failed
+    async fn execute_learn(&self, rei_id: Uuid) -> Result<String, String> {
         let service = SelfLearningService::new(
             self.pool.clone(),
             self.memory_kai.clone(),
             self.embedding.clone(),
             self.web_search.clone(),
             None,
-        );
+        )
+        .with_event_bus(self.event_bus.clone())
+        .with_sources(learning_source::default_sources(
+            &self.pool,
+            self.gemini_api_key.as_deref(),
+        ));
 
         match service.learn(rei_id).await {
             Ok(session) => {
@@ -189,170 +351,44 @@ impl AutonomousScheduler {
                     session.queries_generated.len(),
                     session.memories_stored
                 );
-
-                // Dispatch webhooks for learning completion
-                self.dispatch_learning_webhooks(&session).await;
+                Ok(format!(
+                    "{} queries, {} memories stored",
+                    session.queries_generated.len(),
+                    session.memories_stored
+                ))
             }
             Err(e) => {
                 tracing::warn!("  ❌ Learning failed: {}", e);
-            }
-        }
-
-        Ok(())
-    }
-
-    /// Dispatch webhooks for learning completion
-    async fn dispatch_learning_webhooks(&self, session: &LearningSession) {
-        let (Some(webhook_repo), Some(http_webhook)) = (&self.webhook_repo, &self.http_webhook)
-        else {
-            return;
-        };
-
-        // Find webhooks subscribed to LearningCompleted event
-        let webhooks = match webhook_repo
-            .find_by_rei_and_event(session.rei_id, &WebhookEventType::LearningCompleted)
-            .await
-        {
-            Ok(w) => w,
-            Err(e) => {
-                tracing::warn!("  ⚠️  Failed to find webhooks: {}", e);
-                return;
-            }
-        };
-
-        if webhooks.is_empty() {
-            return;
-        }
-
-        // Build payload from LearningSession
-        let payload = WebhookPayload::new(
-            WebhookEventType::LearningCompleted,
-            session.rei_id,
-            serde_json::json!({
-                "rei_name": session.rei_name,
-                "queries_generated": session.queries_generated,
-                "searches_completed": session.searches_completed,
-                "memories_stored": session.memories_stored,
-                "errors": session.errors,
-            }),
-        );
-
-        // Deliver to each webhook
-        for webhook in webhooks {
-            tracing::info!("  📤 Dispatching webhook: {}", webhook.name);
-
-            match http_webhook.deliver_with_retry(&webhook, &payload).await {
-                Ok(delivery) => {
-                    // Save delivery record
-                    if let Err(e) = webhook_repo.save_delivery(&delivery).await {
-                        tracing::warn!("  ⚠️  Failed to save delivery record: {}", e);
-                    }
-
-                    if delivery.status == kaiba::DeliveryStatus::Success {
-                        tracing::info!("  ✅ Webhook delivered successfully");
-                    } else {
-                        tracing::warn!(
-                            "  ❌ Webhook delivery failed: {:?}",
-                            delivery.response_body
-                        );
-                    }
-                }
-                Err(e) => {
-                    tracing::warn!("  ❌ Webhook delivery error: {}", e);
-                }
+                Err(e.to_string())
             }
         }
     }
 
-    /// Dispatch webhooks for digest completion
-    async fn dispatch_digest_webhooks(&self, result: &DigestResult) {
-        let (Some(webhook_repo), Some(http_webhook)) = (&self.webhook_repo, &self.http_webhook)
-        else {
-            return;
-        };
-
-        // Find webhooks subscribed to DigestCompleted event
-        let webhooks = match webhook_repo
-            .find_by_rei_and_event(result.rei_id, &WebhookEventType::DigestCompleted)
-            .await
-        {
-            Ok(w) => w,
-            Err(e) => {
-                tracing::warn!("  ⚠️  Failed to find webhooks: {}", e);
-                return;
-            }
-        };
-
-        if webhooks.is_empty() {
-            return;
-        }
-
-        // Build payload from DigestResult
-        let payload = WebhookPayload::new(
-            WebhookEventType::DigestCompleted,
-            result.rei_id,
-            serde_json::json!({
-                "memories_processed": result.memories_processed,
-                "expertise_created": result.expertise_created,
-                "summary": result.summary,
-            }),
-        );
-
-        // Deliver to each webhook
-        for webhook in webhooks {
-            tracing::info!("  📤 Dispatching digest webhook: {}", webhook.name);
-
-            match http_webhook.deliver_with_retry(&webhook, &payload).await {
-                Ok(delivery) => {
-                    // Save delivery record
-                    if let Err(e) = webhook_repo.save_delivery(&delivery).await {
-                        tracing::warn!("  ⚠️  Failed to save delivery record: {}", e);
-                    }
-
-                    if delivery.status == kaiba::DeliveryStatus::Success {
-                        tracing::info!("  ✅ Digest webhook delivered successfully");
-                    } else {
-                        tracing::warn!(
-                            "  ❌ Digest webhook delivery failed: {:?}",
-                            delivery.response_body
-                        );
-                    }
-                }
-                Err(e) => {
-                    tracing::warn!("  ❌ Digest webhook delivery error: {}", e);
-                }
-            }
-        }
-    }
-
-    /// Execute digest action
-    async fn execute_digest(
-        &self,
-        rei_id: Uuid,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    /// Execute digest action, returning what was digested or why it failed
+    async fn execute_digest(&self, rei_id: Uuid) -> Result<String, String> {
         let service = DigestService::new(
             self.pool.clone(),
             self.memory_kai.clone(),
             self.embedding.clone(),
             self.gemini_api_key.clone(),
-        );
+        )
+        .with_event_bus(self.event_bus.clone())
+        .with_threshold(self.config.digest_threshold);
 
-        match service.digest(rei_id).await {
+        let outcome = match service.digest(rei_id).await {
+            Ok(result) if !result.expertise_created => Ok(result.summary),
             Ok(result) => {
                 tracing::info!(
                     "  📝 Digested: {} memories -> expertise",
                     result.memories_processed
                 );
-
-                // Dispatch webhooks for digest completion (only if expertise was created)
-                if result.expertise_created {
-                    self.dispatch_digest_webhooks(&result).await;
-                }
+                Ok(format!("{} memories processed", result.memories_processed))
             }
             Err(e) => {
                 tracing::warn!("  ❌ Digest failed: {}", e);
+                Err(e.to_string())
             }
-        }
+        };
 
         // Reduce energy for digest
         sqlx::query(
@@ -360,9 +396,67 @@ impl AutonomousScheduler {
         )
         .bind(rei_id)
         .execute(&self.pool)
-        .await?;
+        .await
+        .map_err(|e| e.to_string())?;
 
-        Ok(())
+        outcome
+    }
+
+    /// Execute reflection action (the service spends the energy), returning
+    /// what was reflected on or why it failed
+    async fn execute_reflect(&self, rei_id: Uuid) -> Result<String, String> {
+        let service = ReflectionService::new(
+            self.pool.clone(),
+            self.memory_kai.clone(),
+            self.embedding.clone(),
+            self.gemini_api_key.clone(),
+        )
+        .with_event_bus(self.event_bus.clone());
+
+        match service.reflect(rei_id).await {
+            Ok(result) if result.reflection_created => {
+                tracing::info!("  💭 Reflected on {} memories", result.memories_considered);
+                Ok(format!(
+                    "{} memories considered",
+                    result.memories_considered
+                ))
+            }
+            Ok(result) => {
+                tracing::info!("  💭 {}", result.reflection);
+                Ok(result.reflection)
+            }
+            Err(e) => {
+                tracing::warn!("  ❌ Reflection failed: {}", e);
+                Err(e.to_string())
+            }
+        }
+    }
+
+    /// Roll up digests of ended weeks and months (no energy cost)
+    async fn roll_up_digests(&self, rei_id: Uuid) {
+        let service = DigestService::new(
+            self.pool.clone(),
+            self.memory_kai.clone(),
+            self.embedding.clone(),
+            self.gemini_api_key.clone(),
+        )
+        .with_event_bus(self.event_bus.clone());
+
+        for tier in [DigestTier::Weekly, DigestTier::Monthly] {
+            match service.roll_up(rei_id, tier).await {
+                Ok(Some(result)) if result.expertise_created => {
+                    tracing::info!(
+                        "  📚 Rolled up {} digests -> {} digest",
+                        result.memories_processed,
+                        tier
+                    );
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::warn!("  ❌ {} digest failed: {}", tier, e);
+                }
+            }
+        }
     }
 
     /// Count learning memories for a Rei
@@ -388,61 +482,135 @@ impl AutonomousScheduler {
         Ok(count)
     }
 
-    /// Get all Reis
-    async fn get_all_reis(&self) -> Result<Vec<Rei>, Box<dyn std::error::Error + Send + Sync>> {
-        let reis = sqlx::query_as::<_, Rei>("SELECT * FROM reis")
-            .fetch_all(&self.pool)
-            .await?;
+    /// Reis that follow the global interval
+    async fn get_unscheduled_reis(
+        &self,
+    ) -> Result<Vec<Rei>, Box<dyn std::error::Error + Send + Sync>> {
+        let reis = sqlx::query_as::<_, Rei>(
+            r#"
+            SELECT r.* FROM reis r
+            JOIN rei_states st ON st.rei_id = r.id
+            WHERE r.deleted_at IS NULL AND st.autonomy_enabled
+              AND NOT EXISTS (SELECT 1 FROM rei_schedules s WHERE s.rei_id = r.id)
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
         Ok(reis)
     }
 
-    /// Regenerate energy for all Reis
-    async fn regenerate_all_energy(&self) -> Result<u64, Box<dyn std::error::Error + Send + Sync>> {
-        let result = sqlx::query(
+    /// Schedules whose next run is due, with their Reis
+    async fn get_due_schedules(
+        &self,
+    ) -> Result<Vec<(ReiSchedule, Rei)>, Box<dyn std::error::Error + Send + Sync>> {
+        let schedules = sqlx::query_as::<_, ReiSchedule>(
             r#"
-            UPDATE rei_states
-            SET energy_level = LEAST(100, energy_level + energy_regen_per_hour)
-            WHERE energy_regen_per_hour > 0
+            SELECT s.* FROM rei_schedules s
+            JOIN reis r ON r.id = s.rei_id
+            JOIN rei_states st ON st.rei_id = s.rei_id
+            WHERE r.deleted_at IS NULL AND st.autonomy_enabled AND s.next_run_at <= NOW()
+            ORDER BY s.next_run_at
             "#,
         )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut due = Vec::with_capacity(schedules.len());
+        for schedule in schedules {
+            let rei = sqlx::query_as::<_, Rei>("SELECT * FROM reis WHERE id = $1")
+                .bind(schedule.rei_id)
+                .fetch_one(&self.pool)
+                .await?;
+            due.push((schedule, rei));
+        }
+        Ok(due)
+    }
+
+    /// Record a run and compute the next one (missed runs are skipped)
+    async fn advance_schedule(
+        &self,
+        schedule: &ReiSchedule,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let next_run_at = match CronSchedule::parse(&schedule.cron, &schedule.timezone) {
+            Ok(cron) => cron.next_after(chrono::Utc::now()),
+            Err(e) => {
+                tracing::warn!(
+                    "⚠️  Schedule of Rei {} no longer parses ({}); pausing it",
+                    schedule.rei_id,
+                    e
+                );
+                None
+            }
+        };
+
+        sqlx::query(
+            "UPDATE rei_schedules SET last_run_at = NOW(), next_run_at = $2 WHERE rei_id = $1",
+        )
+        .bind(schedule.rei_id)
+        .bind(next_run_at)
         .execute(&self.pool)
         .await?;
+        Ok(())
+    }
+
+    /// Delete webhook delivery records outside the retention policy
+    async fn prune_webhook_deliveries(&self) {
+        let retention = &self.config.delivery_retention;
+        if !retention.is_enabled() {
+            return;
+        }
 
-        Ok(result.rows_affected())
+        let webhook_repo = PgReiWebhookRepository::new(self.pool.clone());
+        match webhook_repo.prune_deliveries(retention).await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!("🧹 Pruned {} webhook deliveries", count),
+            Err(e) => tracing::warn!("⚠️  Webhook delivery pruning failed: {}", e),
+        }
     }
 }
 
-/// Start scheduler if all required services are available
+/// Start scheduler under the supervisor if all required services are available
+///
+/// Returns whether it was started.
 #[allow(clippy::too_many_arguments)]
 pub fn maybe_start_scheduler(
+    tasks: &TaskSupervisor,
     pool: PgPool,
     memory_kai: Option<Arc<MemoryKai>>,
     embedding: Option<EmbeddingService>,
     web_search: Option<WebSearchAgent>,
     gemini_api_key: Option<String>,
     interval_secs: Option<u64>,
-    webhook_repo: Option<Arc<PgReiWebhookRepository>>,
-    http_webhook: Option<Arc<HttpWebhook>>,
-) -> Option<tokio::task::JoinHandle<()>> {
-    let memory_kai = memory_kai?;
-    let embedding = embedding?;
-    let web_search = web_search?;
+    parallelism: Parallelism,
+    delivery_retention: DeliveryRetention,
+    digest_threshold: DigestThreshold,
+    event_bus: Option<Arc<InProcessEventBus>>,
+) -> bool {
+    let (Some(memory_kai), Some(embedding)) = (memory_kai, embedding) else {
+        return false;
+    };
+    if web_search.is_none() {
+        tracing::info!("📅 No web search: Reis learn from their memories only");
+    }
 
     let config = SchedulerConfig {
         interval: Duration::from_secs(interval_secs.unwrap_or(3600)),
         enabled: true,
+        parallelism,
+        delivery_retention,
+        digest_threshold,
     };
 
-    let scheduler = AutonomousScheduler::new(
+    let scheduler = Arc::new(AutonomousScheduler::new(
         pool,
         memory_kai,
         embedding,
         web_search,
         gemini_api_key,
         Some(config),
-        webhook_repo,
-        http_webhook,
-    );
+        event_bus,
+    ));
 
-    Some(scheduler.start())
+    tasks.supervise("scheduler", move |shutdown| scheduler.clone().run(shutdown));
+    true
 }
@@ -0,0 +1,209 @@
+//! Seed Service - Demo data for new deployments
+//!
+//! Creates a demo Rei with two Teis and a few memories, so a fresh instance
+//! has something to call, prompt and browse. Running it again only fills in
+//! what is missing: the Rei and Teis are matched by name, and memories are
+//! added only while the demo Rei has none.
+
+use chrono::Utc;
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::{Memory, MemoryType};
+use crate::services::embedding::EmbeddingService;
+use crate::services::qdrant::MemoryKai;
+use crate::{AppReiService, AppTeiService};
+
+pub const DEMO_REI_NAME: &str = "Kaiba Demo";
+
+/// Demo Teis: (name, provider, model_id, is_fallback, priority)
+const DEMO_TEIS: [(&str, kaiba::Provider, &str, bool, i32); 2] = [
+    (
+        "Demo Claude",
+        kaiba::Provider::Anthropic,
+        "claude-3-5-sonnet-latest",
+        false,
+        0,
+    ),
+    (
+        "Demo Gemini",
+        kaiba::Provider::Google,
+        "gemini-2.0-flash",
+        true,
+        10,
+    ),
+];
+
+/// Demo memories: (content, type, importance, tags)
+const DEMO_MEMORIES: [(&str, MemoryType, f32, &[&str]); 3] = [
+    (
+        "Kaiba separates the persistent Rei (spirit) from the ephemeral Tei (body).",
+        MemoryType::Fact,
+        0.9,
+        &["kaiba", "architecture"],
+    ),
+    (
+        "Prompts for external tools come from GET /kaiba/rei/{id}/prompt.",
+        MemoryType::Learning,
+        0.7,
+        &["kaiba", "prompt"],
+    ),
+    (
+        "A user asked how memories are stored; they live in Qdrant, one collection per Rei.",
+        MemoryType::Conversation,
+        0.5,
+        &["kaiba", "memory"],
+    ),
+];
+
+/// What seeding created
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SeedResult {
+    pub rei_id: Uuid,
+    /// False when the demo Rei already existed
+    pub rei_created: bool,
+    pub tei_ids: Vec<Uuid>,
+    pub teis_created: usize,
+    pub memories_created: usize,
+    /// Why no memories were added, if none were
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memories_skipped: Option<String>,
+}
+
+/// Create whatever part of the demo data is missing
+pub async fn seed_demo(
+    rei_service: &AppReiService,
+    tei_service: &AppTeiService,
+    memory_kai: Option<&MemoryKai>,
+    embedding: Option<&EmbeddingService>,
+) -> Result<SeedResult, String> {
+    let existing = rei_service
+        .list_all()
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|(rei, _)| rei)
+        .find(|rei| rei.name == DEMO_REI_NAME);
+
+    let (rei_id, rei_created) = match existing {
+        Some(rei) => (rei.id, false),
+        None => {
+            let (rei, _) = rei_service
+                .create(
+                    DEMO_REI_NAME.to_string(),
+                    "Friendly guide to Kaiba".to_string(),
+                    None,
+                    Some(serde_json::json!({
+                        "personality": "Curious, concise and a little playful",
+                        "interests": ["persona architecture", "memory systems", "rust"],
+                        "learning_topics": ["retrieval augmented generation"],
+                    })),
+                    None,
+                )
+                .await
+                .map_err(|e| e.to_string())?;
+            (rei.id, true)
+        }
+    };
+
+    let all_teis = tei_service.list_all().await.map_err(|e| e.to_string())?;
+    let associated = tei_service
+        .list_by_rei(rei_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut tei_ids = Vec::with_capacity(DEMO_TEIS.len());
+    let mut teis_created = 0;
+    for (name, provider, model_id, is_fallback, priority) in DEMO_TEIS {
+        let tei_id = match all_teis.iter().find(|tei| tei.name == name) {
+            Some(tei) => tei.id,
+            None => {
+                let tei = tei_service
+                    .create(
+                        name.to_string(),
+                        provider,
+                        model_id.to_string(),
+                        is_fallback,
+                        priority,
+                        None,
+                        None,
+                        None,
+                    )
+                    .await
+                    .map_err(|e| e.to_string())?;
+                teis_created += 1;
+                tei.id
+            }
+        };
+        if !associated.iter().any(|tei| tei.id == tei_id) {
+            tei_service
+                .associate(rei_id, tei_id)
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+        tei_ids.push(tei_id);
+    }
+
+    let (memories_created, memories_skipped) = match (memory_kai, embedding) {
+        (Some(memory_kai), Some(embedding)) => {
+            (seed_memories(rei_id, memory_kai, embedding).await?, None)
+        }
+        _ => (
+            0,
+            Some("MemoryKai or embedding service not configured".to_string()),
+        ),
+    };
+
+    tracing::info!(
+        "🌱 Demo data seeded: Rei {} (created: {}), {} Teis created, {} memories created",
+        rei_id,
+        rei_created,
+        teis_created,
+        memories_created
+    );
+
+    Ok(SeedResult {
+        rei_id,
+        rei_created,
+        tei_ids,
+        teis_created,
+        memories_created,
+        memories_skipped,
+    })
+}
+
+async fn seed_memories(
+    rei_id: Uuid,
+    memory_kai: &MemoryKai,
+    embedding: &EmbeddingService,
+) -> Result<usize, String> {
+    let persona_id = rei_id.to_string();
+    let count = memory_kai
+        .count_memories(&persona_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    if count > 0 {
+        return Ok(0);
+    }
+
+    for (content, memory_type, importance, tags) in DEMO_MEMORIES {
+        let vector = embedding.embed(content).await.map_err(|e| e.to_string())?;
+        let memory = Memory {
+            id: Uuid::new_v4().to_string(),
+            rei_id: persona_id.clone(),
+            content: content.to_string(),
+            memory_type,
+            importance,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            metadata: Some(serde_json::json!({ "source": "seed" })),
+            created_at: Utc::now(),
+        };
+        memory_kai
+            .add_memory(&persona_id, memory, vector)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(DEMO_MEMORIES.len())
+}
@@ -3,28 +3,340 @@
 //! Ghost-like autonomous learning:
 //! 1. Read Rei's personality/interests from manifest
 //! 2. Generate search queries based on interests
-//! 3. Execute WebSearch via Gemini
-//! 4. Store results to MemoryKai (記憶海)
+//! 3. Skip queries MemoryKai already covers, or ask only for what is new
+//! 4. Execute WebSearch via Gemini, dropping references from the manifest's
+//!    `excluded_domains` and citing `preferred_domains` first
+//! 5. Store results to MemoryKai (記憶海)
+//! 6. Pull new items from other learning sources (RSS/Atom feeds listed in
+//!    the manifest) and store their summaries the same way
+//!
+//! Completed sessions are recorded in `learning_sessions` for the history API.
+//!
+//! Each Rei has a daily budget of searches, embeddings and Gemini tokens
+//! (server defaults, overridable per Rei with the manifest's
+//! `learning_budget`). Usage is counted per UTC day in `rei_states`; once a
+//! cap is reached, the remaining queries wait for the next day.
+//!
+//! Sessions rotate through the manifest's topics: those never searched come
+//! first, then the ones searched longest ago (tracked in `learning_topics`),
+//! so every interest gets its turn however low `max_queries` is.
+//!
+//! When Gemini rate-limits a search, a short `retry_after` is waited out once;
+//! otherwise the query and the ones after it are deferred to the next session
+//! (searched before new ones) and web search pauses until the limit lifts.
+//!
+//! A topic whose search keeps failing gets a post-mortem memory and is left
+//! out of rotation for a while (see [`post_mortem`]).
+//!
+//! Without web search (no Gemini key) sessions run in memory-only mode: each
+//! topic is studied from the Rei's own memories instead, re-reading the ones
+//! most related to it and storing a note that links and summarizes them.
 
+use crate::adapters::InProcessEventBus;
 use crate::models::{Memory, MemoryType, Rei, ReiState};
 use crate::services::embedding::EmbeddingService;
-use crate::services::qdrant::MemoryKai;
-use crate::services::web_search::{WebSearchAgent, WebSearchResponse};
+use crate::services::learning_source::{LearningSource, SourceItem};
+use crate::services::post_mortem::{self, FailureLog, Subject};
+use crate::services::qdrant::{MemoryKai, SearchFilter};
+use crate::services::source_policy::SourcePolicy;
+use crate::services::web_search::{WebSearchAgent, WebSearchError, WebSearchResponse};
+use chrono::{DateTime, Duration, Utc};
+use kaiba::{ReiEvent, ReiEventBus, WebhookEventType};
 use serde::{Deserialize, Serialize};
-use sqlx::PgPool;
+use sqlx::types::Json;
+use sqlx::{FromRow, PgPool};
+use std::collections::HashMap;
 use std::sync::Arc;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+/// Where a session learns its topics from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LearningMode {
+    /// Web search via Gemini
+    Web,
+    /// The Rei's existing memories only
+    Memory,
+}
+
+impl LearningMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LearningMode::Web => "web",
+            LearningMode::Memory => "memory",
+        }
+    }
+}
+
 /// Learning session result
 #[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct LearningSession {
+    /// ID in the learning history
+    pub id: Uuid,
     pub rei_id: Uuid,
     pub rei_name: String,
+    pub mode: LearningMode,
     pub queries_generated: Vec<String>,
+    /// Topics searched (in memory mode: studied from memories)
     pub searches_completed: usize,
+    /// Queries skipped because recent memories already cover them
+    pub searches_skipped: usize,
     pub memories_stored: usize,
+    /// Items learned from sources other than web search (e.g. feed entries)
+    pub items_learned: usize,
+    /// URLs of the sources the stored memories cite
+    pub sources: Vec<String>,
     pub errors: Vec<String>,
+    /// Searches Gemini rejected with a rate limit
+    pub rate_limited: usize,
+    /// Queries put off to the next session by a rate limit
+    pub queries_deferred: Vec<String>,
+    /// When web search may resume after a rate limit
+    pub resume_at: Option<DateTime<Utc>>,
+    pub energy_spent: i32,
+    /// What this session consumed from the daily budget
+    pub usage: LearningUsage,
+    pub started_at: DateTime<Utc>,
+}
+
+/// Manifest key of a Rei's own daily budget
+pub const BUDGET_MANIFEST_KEY: &str = "learning_budget";
+
+/// References cited in a search memory
+const MAX_CITED_SOURCES: usize = 5;
+
+/// Embedding calls one query can make (coverage check and storage)
+const EMBEDDINGS_PER_QUERY: i32 = 2;
+
+/// Memories re-read when studying a topic in memory-only mode
+const STUDY_MEMORIES: usize = 8;
+
+/// Fewest related memories worth a study note
+const MIN_STUDY_MEMORIES: usize = 2;
+
+/// Least similarity for a memory to count as related to a topic
+const STUDY_SIMILARITY: f32 = 0.5;
+
+/// Characters quoted from each memory in a study note
+const STUDY_EXCERPT_CHARS: usize = 200;
+
+/// Longest `retry_after` waited out within a session
+const MAX_RATE_LIMIT_WAIT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Pause after a rate limit that gave no `retry_after`
+const DEFAULT_RATE_LIMIT_PAUSE: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// Daily caps on what self-learning may consume for one Rei
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct LearningBudget {
+    /// Web searches per day
+    pub searches: i32,
+    /// Embedding calls per day
+    pub embeddings: i32,
+    /// Gemini tokens per day, as reported by the searches
+    pub tokens: i32,
+}
+
+impl Default for LearningBudget {
+    fn default() -> Self {
+        Self {
+            searches: 20,
+            embeddings: 50,
+            tokens: 100_000,
+        }
+    }
+}
+
+impl LearningBudget {
+    /// The manifest's `learning_budget`, with `defaults` for omitted caps
+    pub fn for_manifest(manifest: &serde_json::Value, defaults: &LearningBudget) -> Self {
+        let overrides = manifest.get(BUDGET_MANIFEST_KEY);
+        let cap = |key: &str, default: i32| {
+            overrides
+                .and_then(|o| o.get(key))
+                .and_then(serde_json::Value::as_i64)
+                .map_or(default, |v| v.clamp(0, i32::MAX as i64) as i32)
+        };
+        Self {
+            searches: cap("searches", defaults.searches),
+            embeddings: cap("embeddings", defaults.embeddings),
+            tokens: cap("tokens", defaults.tokens),
+        }
+    }
+
+    /// The cap another query would break given today's usage, if any
+    fn exhausted_by(&self, used: &LearningUsage) -> Option<&'static str> {
+        if used.searches >= self.searches {
+            Some("searches")
+        } else if used.embeddings + EMBEDDINGS_PER_QUERY > self.embeddings {
+            Some("embeddings")
+        } else if used.tokens >= self.tokens {
+            Some("tokens")
+        } else {
+            None
+        }
+    }
+
+    /// The cap that stops all learning for the day, if any
+    ///
+    /// Sources other than web search need no searches, so only embeddings
+    /// and tokens count here.
+    fn exhausted(&self, used: &LearningUsage) -> Option<&'static str> {
+        if used.embeddings >= self.embeddings {
+            Some("embeddings")
+        } else if used.tokens >= self.tokens {
+            Some("tokens")
+        } else {
+            None
+        }
+    }
+
+    /// Source items still affordable today (one embedding each)
+    fn items_left(&self, used: &LearningUsage) -> usize {
+        match self.exhausted(used) {
+            Some(_) => 0,
+            None => (self.embeddings - used.embeddings) as usize,
+        }
+    }
+}
+
+/// Searches, embeddings and tokens consumed by self-learning
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct LearningUsage {
+    pub searches: i32,
+    pub embeddings: i32,
+    pub tokens: i32,
+}
+
+impl std::ops::Add for LearningUsage {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            searches: self.searches + other.searches,
+            embeddings: self.embeddings + other.embeddings,
+            tokens: self.tokens + other.tokens,
+        }
+    }
+}
+
+/// Learning usage of a Rei so far today (UTC)
+pub async fn usage_today(pool: &PgPool, rei_id: Uuid) -> Result<LearningUsage, sqlx::Error> {
+    let usage = sqlx::query_as::<_, LearningUsage>(
+        r#"
+        SELECT searches_today AS searches, embeddings_today AS embeddings,
+               tokens_today AS tokens
+        FROM rei_states
+        WHERE rei_id = $1 AND budget_day = (NOW() AT TIME ZONE 'UTC')::date
+        "#,
+    )
+    .bind(rei_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(usage.unwrap_or_default())
+}
+
+/// A manifest topic and when learning last took it up
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, FromRow, ToSchema)]
+pub struct LearningTopic {
+    /// Search query generated from the manifest
+    pub query: String,
+    /// Unset if no session has searched it yet
+    pub last_queried_at: Option<DateTime<Utc>>,
+    pub times_queried: i32,
+}
+
+/// The Rei's topics in rotation order: never searched first (in manifest
+/// order), then the longest unsearched
+pub async fn learning_topics(
+    pool: &PgPool,
+    rei_id: Uuid,
+    queries: &[String],
+) -> Result<Vec<LearningTopic>, sqlx::Error> {
+    let history: HashMap<String, LearningTopic> = sqlx::query_as::<_, LearningTopic>(
+        r#"
+        SELECT query, last_queried_at, times_queried
+        FROM learning_topics
+        WHERE rei_id = $1 AND query = ANY($2)
+        "#,
+    )
+    .bind(rei_id)
+    .bind(queries)
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|topic| (topic.query.clone(), topic))
+    .collect();
+
+    Ok(rotate_topics(queries, &history))
+}
+
+/// Order `queries` so the least recently searched come first
+fn rotate_topics(
+    queries: &[String],
+    history: &HashMap<String, LearningTopic>,
+) -> Vec<LearningTopic> {
+    let mut topics: Vec<LearningTopic> = queries
+        .iter()
+        .map(|query| {
+            history.get(query).cloned().unwrap_or(LearningTopic {
+                query: query.clone(),
+                last_queried_at: None,
+                times_queried: 0,
+            })
+        })
+        .collect();
+    // Stable: ties keep the manifest order; None sorts first
+    topics.sort_by_key(|topic| topic.last_queried_at);
+    topics
+}
+
+/// Search queries from the manifest's interests, learning topics and
+/// curiosities, or from the role if it lists none
+pub fn manifest_queries(manifest: &serde_json::Value, role: &str) -> Vec<String> {
+    let mut queries = Vec::new();
+
+    // Extract interests from manifest
+    if let Some(interests) = manifest.get("interests").and_then(|v| v.as_array()) {
+        for interest in interests {
+            if let Some(topic) = interest.as_str() {
+                // Generate contextual query
+                let query = format!("{} latest developments 2025", topic);
+                queries.push(query);
+            }
+        }
+    }
+
+    // Extract learning_topics from manifest
+    if let Some(topics) = manifest.get("learning_topics").and_then(|v| v.as_array()) {
+        for topic in topics {
+            if let Some(topic_str) = topic.as_str() {
+                queries.push(topic_str.to_string());
+            }
+        }
+    }
+
+    // Extract curiosities from manifest
+    if let Some(curiosities) = manifest.get("curiosities").and_then(|v| v.as_array()) {
+        for curiosity in curiosities {
+            if let Some(q) = curiosity.as_str() {
+                queries.push(q.to_string());
+            }
+        }
+    }
+
+    // Fallback: use role as interest if no specific interests defined
+    if queries.is_empty() {
+        let role_query = format!("{} best practices 2025", role);
+        queries.push(role_query);
+    }
+
+    // The same topic listed twice is searched once
+    let mut seen = std::collections::HashSet::new();
+    queries.retain(|q| seen.insert(q.clone()));
+    queries
 }
 
 /// Self-learning service configuration
@@ -39,6 +351,19 @@ pub struct LearningConfig {
     /// Force learning even if energy is low
     #[serde(default)]
     pub force: bool,
+    /// Similarity at which an existing learning memory covers a query
+    #[serde(default = "default_coverage_threshold")]
+    pub coverage_threshold: f32,
+    /// Age after which a covered topic is searched again, asking only for
+    /// what is new since the covering memory
+    #[serde(default = "default_refresh_after_days")]
+    pub refresh_after_days: i64,
+    /// Daily budget of Reis whose manifest sets none
+    #[serde(default)]
+    pub budget: LearningBudget,
+    /// Maximum items taken from each learning source per session
+    #[serde(default = "default_max_source_items")]
+    pub max_source_items: usize,
 }
 
 fn default_max_queries() -> usize {
@@ -49,12 +374,28 @@ fn default_min_energy() -> i32 {
     30
 }
 
+fn default_coverage_threshold() -> f32 {
+    0.85
+}
+
+fn default_refresh_after_days() -> i64 {
+    7
+}
+
+fn default_max_source_items() -> usize {
+    5
+}
+
 impl Default for LearningConfig {
     fn default() -> Self {
         Self {
             max_queries: default_max_queries(),
             min_energy: default_min_energy(),
             force: false,
+            coverage_threshold: default_coverage_threshold(),
+            refresh_after_days: default_refresh_after_days(),
+            budget: LearningBudget::default(),
+            max_source_items: default_max_source_items(),
         }
     }
 }
@@ -64,17 +405,20 @@ pub struct SelfLearningService {
     pool: PgPool,
     memory_kai: Arc<MemoryKai>,
     embedding: EmbeddingService,
-    web_search: WebSearchAgent,
+    /// Unset in memory-only mode
+    web_search: Option<WebSearchAgent>,
     config: LearningConfig,
+    event_bus: Option<Arc<InProcessEventBus>>,
+    sources: Vec<Arc<dyn LearningSource>>,
 }
 
 impl SelfLearningService {
-    /// Creates a new self-learning service
+    /// Creates a new self-learning service (memory-only without web search)
     pub fn new(
         pool: PgPool,
         memory_kai: Arc<MemoryKai>,
         embedding: EmbeddingService,
-        web_search: WebSearchAgent,
+        web_search: Option<WebSearchAgent>,
         config: Option<LearningConfig>,
     ) -> Self {
         Self {
@@ -83,10 +427,25 @@ impl SelfLearningService {
             embedding,
             web_search,
             config: config.unwrap_or_default(),
+            event_bus: None,
+            sources: Vec::new(),
         }
     }
 
+    /// Also learn from these sources after the web searches
+    pub fn with_sources(mut self, sources: Vec<Arc<dyn LearningSource>>) -> Self {
+        self.sources = sources;
+        self
+    }
+
+    /// Publish SearchCompleted / MemoryAdded / LearningCompleted events
+    pub fn with_event_bus(mut self, event_bus: Option<Arc<InProcessEventBus>>) -> Self {
+        self.event_bus = event_bus;
+        self
+    }
+
     /// Execute a learning session for a specific Rei
+    #[tracing::instrument(name = "learn", skip(self))]
     pub async fn learn(&self, rei_id: Uuid) -> Result<LearningSession, SelfLearningError> {
         // 1. Fetch Rei and their state
         let rei = self.get_rei(rei_id).await?;
@@ -100,109 +459,315 @@ impl SelfLearningService {
             });
         }
 
+        // The budget applies even when forced
+        let budget = LearningBudget::for_manifest(&rei.manifest, &self.config.budget);
+        let used_before = usage_today(&self.pool, rei_id)
+            .await
+            .map_err(|e| SelfLearningError::DatabaseError(e.to_string()))?;
+        if let Some(cap) = budget.exhausted(&used_before) {
+            return Err(SelfLearningError::BudgetExhausted(cap));
+        }
+        let backoff = self.get_backoff(rei_id).await?;
+
+        let mode = if self.web_search.is_some() {
+            LearningMode::Web
+        } else {
+            LearningMode::Memory
+        };
         let mut session = LearningSession {
+            id: Uuid::new_v4(),
             rei_id,
             rei_name: rei.name.clone(),
+            mode,
             queries_generated: Vec::new(),
             searches_completed: 0,
+            searches_skipped: 0,
             memories_stored: 0,
+            items_learned: 0,
+            sources: Vec::new(),
             errors: Vec::new(),
+            rate_limited: 0,
+            queries_deferred: Vec::new(),
+            resume_at: None,
+            energy_spent: 0,
+            usage: LearningUsage::default(),
+            started_at: Utc::now(),
         };
 
         // 2. Generate search queries from manifest
-        let queries = self.generate_queries(&rei)?;
+        let queries = manifest_queries(&rei.manifest, &rei.role);
         session.queries_generated = queries.clone();
 
         if queries.is_empty() {
             return Err(SelfLearningError::NoInterests);
         }
 
-        // 3. Execute searches and store results
-        for query in queries.iter().take(self.config.max_queries) {
-            match self.search_and_store(rei_id, query).await {
-                Ok(memories_count) => {
+        // 3. Execute searches and store results: deferred ones first, then
+        //    the topics searched least recently; topics that keep failing
+        //    sit out their back-off
+        let policy = SourcePolicy::from_manifest(&rei.manifest);
+        let failing: Vec<String> = post_mortem::backed_off(&self.pool, rei_id)
+            .await
+            .map_err(|e| SelfLearningError::DatabaseError(e.to_string()))?
+            .iter()
+            .filter_map(|streak| match streak.subject() {
+                Some(Subject::Search(query)) => Some(query),
+                _ => None,
+            })
+            .collect();
+        let rotated: Vec<String> = learning_topics(&self.pool, rei_id, &queries)
+            .await
+            .map_err(|e| SelfLearningError::DatabaseError(e.to_string()))?
+            .into_iter()
+            .map(|topic| topic.query)
+            .filter(|query| !failing.contains(query))
+            .collect();
+        if rotated.is_empty() {
+            tracing::info!("🩹 {} is backing off from all of its topics", rei.name);
+        }
+        let queries = schedule_queries(
+            backoff.deferred_queries.0,
+            &rotated,
+            self.config.max_queries,
+        );
+        let mut visited = Vec::new();
+        for (i, query) in queries.iter().enumerate() {
+            if let Some(resume_at) = backoff.learning_resume_at.filter(|at| *at > Utc::now()) {
+                tracing::info!("⏸️  {} is rate limited until {}", rei.name, resume_at);
+                session.queries_deferred = queries[i..].to_vec();
+                session.resume_at = Some(resume_at);
+                break;
+            }
+
+            if let Some(cap) = budget.exhausted_by(&(used_before + session.usage)) {
+                tracing::info!("💸 {} reached its daily {} budget", rei.name, cap);
+                session.errors.push(format!(
+                    "Daily {} budget reached; remaining queries wait until tomorrow",
+                    cap
+                ));
+                break;
+            }
+
+            visited.push(query.clone());
+            let topic = Subject::Search(query.clone());
+            let Some(query) = self.plan_query(rei_id, query, &mut session.usage).await else {
+                tracing::info!("⏭️  {} already knows about: {}", rei.name, query);
+                session.searches_skipped += 1;
+                continue;
+            };
+
+            let mut outcome = self
+                .learn_topic(rei_id, &query, &policy, &mut session.usage)
+                .await;
+            if let Err(SelfLearningError::RateLimited {
+                retry_after: Some(wait),
+            }) = outcome
+            {
+                if wait <= MAX_RATE_LIMIT_WAIT {
+                    session.rate_limited += 1;
+                    tracing::info!("⏳ Rate limited, retrying '{}' in {:?}", query, wait);
+                    tokio::time::sleep(wait).await;
+                    outcome = self
+                        .learn_topic(rei_id, &query, &policy, &mut session.usage)
+                        .await;
+                }
+            }
+
+            match outcome {
+                Err(SelfLearningError::RateLimited { retry_after }) => {
+                    // Not a failure: the rest waits for the next session
+                    session.rate_limited += 1;
+                    let pause = retry_after.unwrap_or(DEFAULT_RATE_LIMIT_PAUSE);
+                    let resume_at = Utc::now()
+                        + Duration::from_std(pause).unwrap_or_else(|_| Duration::hours(1));
+                    tracing::info!(
+                        "⏸️  {} was rate limited; deferring {} queries until {}",
+                        rei.name,
+                        queries.len() - i,
+                        resume_at
+                    );
+                    visited.pop();
+                    session.queries_deferred = queries[i..].to_vec();
+                    session.resume_at = Some(resume_at);
+                    break;
+                }
+                Ok(sources) => {
+                    self.failure_log().succeeded(rei_id, &topic).await;
                     session.searches_completed += 1;
-                    session.memories_stored += memories_count;
+                    session.memories_stored += 1;
                     tracing::info!(
-                        "🧠 {} learned about: {} ({} memories)",
+                        "🧠 {} learned about: {} ({} sources)",
                         rei.name,
                         query,
-                        memories_count
+                        sources.len()
                     );
+                    session.sources.extend(sources);
                 }
                 Err(e) => {
                     let error_msg = format!("Query '{}': {}", query, e);
                     tracing::warn!("⚠️  Learning error: {}", error_msg);
+                    self.failure_log()
+                        .failed(rei_id, &topic, &e.to_string())
+                        .await;
                     session.errors.push(error_msg);
                 }
             }
         }
 
-        // 4. Update last_active_at and reduce energy
-        self.update_after_learning(rei_id, session.searches_completed)
+        // 4. Learn from the other sources
+        self.learn_from_sources(&rei, &budget, used_before, &mut session)
+            .await;
+
+        // Reduce energy based on searches (10 energy per search or study;
+        // skips are free) and source items (5 each)
+        session.energy_spent =
+            (session.searches_completed as i32) * 10 + (session.items_learned as i32) * 5;
+
+        // 5. Update last_active_at, reduce energy and record the session; the
+        //    completion event is written to the outbox in the same transaction
+        let event = ReiEvent::new(
+            rei_id,
+            WebhookEventType::LearningCompleted,
+            serde_json::json!({
+                "session_id": session.id,
+                "rei_name": session.rei_name,
+                "mode": session.mode,
+                "queries_generated": session.queries_generated,
+                "searches_completed": session.searches_completed,
+                "searches_skipped": session.searches_skipped,
+                "memories_stored": session.memories_stored,
+                "items_learned": session.items_learned,
+                "sources": session.sources,
+                "errors": session.errors,
+                "rate_limited": session.rate_limited,
+                "queries_deferred": session.queries_deferred,
+                "usage": session.usage,
+            }),
+        );
+        self.update_after_learning(&session, &visited, event)
             .await?;
 
         Ok(session)
     }
 
-    /// Generate search queries from Rei's manifest
-    fn generate_queries(&self, rei: &Rei) -> Result<Vec<String>, SelfLearningError> {
-        let manifest = &rei.manifest;
-        let mut queries = Vec::new();
-
-        // Extract interests from manifest
-        if let Some(interests) = manifest.get("interests").and_then(|v| v.as_array()) {
-            for interest in interests {
-                if let Some(topic) = interest.as_str() {
-                    // Generate contextual query
-                    let query = format!("{} latest developments 2025", topic);
-                    queries.push(query);
-                }
-            }
+    fn failure_log(&self) -> FailureLog<'_> {
+        FailureLog {
+            pool: &self.pool,
+            memory_kai: &self.memory_kai,
+            embedding: &self.embedding,
+            event_bus: self.event_bus.as_ref(),
         }
+    }
 
-        // Extract learning_topics from manifest
-        if let Some(topics) = manifest.get("learning_topics").and_then(|v| v.as_array()) {
-            for topic in topics {
-                if let Some(topic_str) = topic.as_str() {
-                    queries.push(topic_str.to_string());
-                }
+    /// Check MemoryKai for a learning memory that already covers `query`
+    ///
+    /// Returns the query to search, reformulated to ask only for news when
+    /// the covering memory is old, or `None` to skip it. A failed lookup
+    /// searches the query as is.
+    async fn plan_query(
+        &self,
+        rei_id: Uuid,
+        query: &str,
+        usage: &mut LearningUsage,
+    ) -> Option<String> {
+        usage.embeddings += 1;
+        let nearest = match self.nearest_learning(rei_id, query).await {
+            Ok(nearest) => nearest,
+            Err(e) => {
+                tracing::warn!("⚠️  Coverage check failed for '{}': {}", query, e);
+                None
             }
-        }
+        };
+        plan_for_coverage(query, nearest, Utc::now(), &self.config)
+    }
 
-        // Extract curiosities from manifest
-        if let Some(curiosities) = manifest.get("curiosities").and_then(|v| v.as_array()) {
-            for curiosity in curiosities {
-                if let Some(q) = curiosity.as_str() {
-                    queries.push(q.to_string());
-                }
-            }
-        }
+    /// Closest existing learning memory to `query` and its similarity
+    async fn nearest_learning(
+        &self,
+        rei_id: Uuid,
+        query: &str,
+    ) -> Result<Option<(Memory, f32)>, SelfLearningError> {
+        let vector = self
+            .embedding
+            .embed(query)
+            .await
+            .map_err(|e| SelfLearningError::EmbeddingFailed(e.to_string()))?;
 
-        // Fallback: use role as interest if no specific interests defined
-        if queries.is_empty() {
-            let role_query = format!("{} best practices 2025", rei.role);
-            queries.push(role_query);
-        }
+        let filter = SearchFilter {
+            memory_type: Some(MemoryType::Learning),
+            ..Default::default()
+        };
+        let nearest = self
+            .memory_kai
+            .search_memories_scored(&rei_id.to_string(), vector, 1, filter)
+            .await
+            .map_err(|e| SelfLearningError::StorageFailed(e.to_string()))?;
 
-        Ok(queries)
+        Ok(nearest.into_iter().next())
+    }
+
+    /// Learn about a topic from the web, or from memories without web search
+    async fn learn_topic(
+        &self,
+        rei_id: Uuid,
+        query: &str,
+        policy: &SourcePolicy,
+        usage: &mut LearningUsage,
+    ) -> Result<Vec<String>, SelfLearningError> {
+        match &self.web_search {
+            Some(web_search) => {
+                self.search_and_store(web_search, rei_id, query, policy, usage)
+                    .await
+            }
+            None => self.study_memories(rei_id, query, usage).await,
+        }
     }
 
-    /// Execute web search and store results as memories
+    /// Execute web search and store the answer as a memory
+    ///
+    /// Returns the URLs of the sources the memory cites.
     async fn search_and_store(
         &self,
+        web_search: &WebSearchAgent,
         rei_id: Uuid,
         query: &str,
-    ) -> Result<usize, SelfLearningError> {
-        // Execute web search
-        let search_result = self
-            .web_search
-            .search(query)
-            .await
-            .map_err(|e| SelfLearningError::SearchFailed(e.to_string()))?;
+        policy: &SourcePolicy,
+        usage: &mut LearningUsage,
+    ) -> Result<Vec<String>, SelfLearningError> {
+        // Execute web search; a rate-limited one never ran
+        let searched = web_search.search(query).await;
+        if !matches!(searched, Err(WebSearchError::RateLimited { .. })) {
+            usage.searches += 1;
+        }
+        let mut search_result = searched?;
+        usage.tokens += search_result.tokens_used;
+
+        // An answer grounded only in excluded sources is not learned
+        let found = search_result.references.len();
+        let ranked = policy.apply(std::mem::take(&mut search_result.references));
+        if found > 0 && ranked.kept.is_empty() {
+            return Err(SelfLearningError::AllSourcesExcluded);
+        }
+        search_result.references = ranked.kept;
+
+        if let Some(event_bus) = &self.event_bus {
+            event_bus
+                .emit(ReiEvent::new(
+                    rei_id,
+                    WebhookEventType::SearchCompleted,
+                    serde_json::json!({
+                        "query": search_result.query,
+                        "answer": search_result.answer,
+                        "references": search_result.references,
+                        "excluded_references": ranked.excluded,
+                    }),
+                ))
+                .await;
+        }
 
         // Store the answer as a memory
         let memory_content = self.format_memory(&search_result);
+        usage.embeddings += 1;
         let vector = self
             .embedding
             .embed(&memory_content)
@@ -210,6 +775,12 @@ impl SelfLearningService {
             .map_err(|e| SelfLearningError::EmbeddingFailed(e.to_string()))?;
 
         let memory_id = Uuid::new_v4();
+        let sources: Vec<String> = search_result
+            .references
+            .iter()
+            .take(MAX_CITED_SOURCES)
+            .map(|r| r.url.clone())
+            .collect();
 
         // Create Memory struct
         let memory = Memory {
@@ -219,19 +790,207 @@ impl SelfLearningService {
             memory_type: MemoryType::Learning,
             importance: 0.7, // Self-learned content has moderate importance
             tags: vec!["self_learning".to_string(), "auto_generated".to_string()],
-            metadata: None,
+            metadata: Some(serde_json::json!({
+                "source": "web_search",
+                "sources": sources,
+                "excluded_sources": ranked.excluded,
+            })),
             created_at: chrono::Utc::now(),
         };
 
         // Use rei_id as persona_id for the collection
         self.memory_kai
-            .add_memory(&rei_id.to_string(), memory, vector)
+            .add_memory(&rei_id.to_string(), memory.clone(), vector)
             .await
             .map_err(|e| SelfLearningError::StorageFailed(e.to_string()))?;
 
-        // Count: 1 for the main answer
-        let stored_count = 1;
-        Ok(stored_count)
+        if let Some(event_bus) = &self.event_bus {
+            event_bus.emit(memory.added_event(rei_id)).await;
+        }
+
+        Ok(sources)
+    }
+
+    /// Re-read the memories most related to `query` and store a note that
+    /// links and summarizes them (memory-only mode)
+    ///
+    /// Earlier study notes are not re-read, so notes do not pile up on
+    /// each other. Returns nothing to cite: the note's sources are memories,
+    /// listed in its `metadata.linked`.
+    async fn study_memories(
+        &self,
+        rei_id: Uuid,
+        query: &str,
+        usage: &mut LearningUsage,
+    ) -> Result<Vec<String>, SelfLearningError> {
+        usage.embeddings += 1;
+        let vector = self
+            .embedding
+            .embed(query)
+            .await
+            .map_err(|e| SelfLearningError::EmbeddingFailed(e.to_string()))?;
+
+        let related: Vec<Memory> = self
+            .memory_kai
+            .search_memories_scored(
+                &rei_id.to_string(),
+                vector,
+                STUDY_MEMORIES,
+                SearchFilter::default(),
+            )
+            .await
+            .map_err(|e| SelfLearningError::StorageFailed(e.to_string()))?
+            .into_iter()
+            .filter(|(memory, similarity)| {
+                *similarity >= STUDY_SIMILARITY && !is_study_note(memory)
+            })
+            .map(|(memory, _)| memory)
+            .collect();
+        if related.len() < MIN_STUDY_MEMORIES {
+            return Err(SelfLearningError::TooFewMemories(related.len()));
+        }
+
+        let content = study_note(query, &related);
+        usage.embeddings += 1;
+        let vector = self
+            .embedding
+            .embed(&content)
+            .await
+            .map_err(|e| SelfLearningError::EmbeddingFailed(e.to_string()))?;
+
+        let memory = Memory {
+            id: Uuid::new_v4().to_string(),
+            rei_id: rei_id.to_string(),
+            content,
+            memory_type: MemoryType::Learning,
+            importance: 0.5,
+            tags: vec![
+                "self_learning".to_string(),
+                "memory_only".to_string(),
+                "auto_generated".to_string(),
+            ],
+            metadata: Some(serde_json::json!({
+                "source": "memory",
+                "query": query,
+                "linked": related.iter().map(|m| &m.id).collect::<Vec<_>>(),
+            })),
+            created_at: Utc::now(),
+        };
+
+        self.memory_kai
+            .add_memory(&rei_id.to_string(), memory.clone(), vector)
+            .await
+            .map_err(|e| SelfLearningError::StorageFailed(e.to_string()))?;
+
+        if let Some(event_bus) = &self.event_bus {
+            event_bus.emit(memory.added_event(rei_id)).await;
+        }
+
+        Ok(Vec::new())
+    }
+
+    /// Store new items from each learning source, within the budget
+    async fn learn_from_sources(
+        &self,
+        rei: &Rei,
+        budget: &LearningBudget,
+        used_before: LearningUsage,
+        session: &mut LearningSession,
+    ) {
+        for source in &self.sources {
+            let limit = budget
+                .items_left(&(used_before + session.usage))
+                .min(self.config.max_source_items);
+            if limit == 0 {
+                break;
+            }
+
+            let pull = source.pull(rei, limit).await;
+            for error in pull.errors {
+                tracing::warn!("⚠️  Learning source error: {}", error);
+                session.errors.push(error);
+            }
+
+            for item in pull.items {
+                session.usage.tokens += item.tokens_used;
+                if let Err(e) = self
+                    .store_item(rei.id, source.name(), &item, &mut session.usage)
+                    .await
+                {
+                    let error_msg = format!("Item '{}': {}", item.title, e);
+                    tracing::warn!("⚠️  Learning error: {}", error_msg);
+                    session.errors.push(error_msg);
+                    continue;
+                }
+
+                session.items_learned += 1;
+                session.memories_stored += 1;
+                session.sources.extend(item.url.clone());
+                tracing::info!(
+                    "📰 {} learned from {}: {}",
+                    rei.name,
+                    source.name(),
+                    item.title
+                );
+
+                // Worst case the item is learned again next time
+                if let Err(e) = source.mark_learned(rei.id, &item).await {
+                    tracing::warn!("⚠️  Failed to mark '{}' as learned: {}", item.title, e);
+                }
+            }
+        }
+    }
+
+    /// Store a source item's summary as a learning memory
+    async fn store_item(
+        &self,
+        rei_id: Uuid,
+        source: &str,
+        item: &SourceItem,
+        usage: &mut LearningUsage,
+    ) -> Result<(), SelfLearningError> {
+        let mut content = format!("## {}\n\n{}", item.title, item.summary);
+        if let Some(url) = &item.url {
+            content.push_str(&format!("\n\n### Source:\n[{}]({})\n", item.title, url));
+        }
+
+        usage.embeddings += 1;
+        let vector = self
+            .embedding
+            .embed(&content)
+            .await
+            .map_err(|e| SelfLearningError::EmbeddingFailed(e.to_string()))?;
+
+        let memory = Memory {
+            id: Uuid::new_v4().to_string(),
+            rei_id: rei_id.to_string(),
+            content,
+            memory_type: MemoryType::Learning,
+            importance: 0.7,
+            tags: vec![
+                "self_learning".to_string(),
+                "auto_generated".to_string(),
+                source.to_string(),
+            ],
+            metadata: Some(serde_json::json!({
+                "source": source,
+                "url": item.url,
+                "origin": item.origin,
+                "published_at": item.published_at,
+            })),
+            created_at: Utc::now(),
+        };
+
+        self.memory_kai
+            .add_memory(&rei_id.to_string(), memory.clone(), vector)
+            .await
+            .map_err(|e| SelfLearningError::StorageFailed(e.to_string()))?;
+
+        if let Some(event_bus) = &self.event_bus {
+            event_bus.emit(memory.added_event(rei_id)).await;
+        }
+
+        Ok(())
     }
 
     /// Format search response as memory content
@@ -241,7 +1000,12 @@ impl SelfLearningService {
 
         if !response.references.is_empty() {
             content.push_str("\n\n### Sources:\n");
-            for (i, reference) in response.references.iter().take(5).enumerate() {
+            for (i, reference) in response
+                .references
+                .iter()
+                .take(MAX_CITED_SOURCES)
+                .enumerate()
+            {
                 content.push_str(&format!(
                     "{}. [{}]({})\n",
                     i + 1,
@@ -256,7 +1020,7 @@ impl SelfLearningService {
 
     /// Get Rei by ID
     async fn get_rei(&self, rei_id: Uuid) -> Result<Rei, SelfLearningError> {
-        sqlx::query_as::<_, Rei>("SELECT * FROM reis WHERE id = $1")
+        sqlx::query_as::<_, Rei>("SELECT * FROM reis WHERE id = $1 AND deleted_at IS NULL")
             .bind(rei_id)
             .fetch_optional(&self.pool)
             .await
@@ -274,14 +1038,31 @@ impl SelfLearningService {
             .ok_or(SelfLearningError::ReiNotFound(rei_id))
     }
 
-    /// Update Rei state after learning
+    /// Queries deferred by the last rate limit, and when searching may resume
+    async fn get_backoff(&self, rei_id: Uuid) -> Result<RateLimitBackoff, SelfLearningError> {
+        sqlx::query_as::<_, RateLimitBackoff>(
+            "SELECT deferred_queries, learning_resume_at FROM rei_states WHERE rei_id = $1",
+        )
+        .bind(rei_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| SelfLearningError::DatabaseError(e.to_string()))?
+        .ok_or(SelfLearningError::ReiNotFound(rei_id))
+    }
+
+    /// Update Rei state after learning and record the session, with the
+    /// topics it took up
     async fn update_after_learning(
         &self,
-        rei_id: Uuid,
-        searches_completed: usize,
+        session: &LearningSession,
+        visited: &[String],
+        event: ReiEvent,
     ) -> Result<(), SelfLearningError> {
-        // Reduce energy based on searches (10 energy per search)
-        let energy_cost = (searches_completed as i32) * 10;
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| SelfLearningError::DatabaseError(e.to_string()))?;
 
         sqlx::query(
             r#"
@@ -289,16 +1070,87 @@ impl SelfLearningService {
             SET energy_level = GREATEST(0, energy_level - $1),
                 last_active_at = NOW(),
                 last_learn_at = NOW(),
+                searches_today = $3 + CASE WHEN budget_day = (NOW() AT TIME ZONE 'UTC')::date
+                    THEN searches_today ELSE 0 END,
+                embeddings_today = $4 + CASE WHEN budget_day = (NOW() AT TIME ZONE 'UTC')::date
+                    THEN embeddings_today ELSE 0 END,
+                tokens_today = $5 + CASE WHEN budget_day = (NOW() AT TIME ZONE 'UTC')::date
+                    THEN tokens_today ELSE 0 END,
+                budget_day = (NOW() AT TIME ZONE 'UTC')::date,
+                deferred_queries = $6,
+                learning_resume_at = $7,
                 updated_at = NOW()
             WHERE rei_id = $2
             "#,
         )
-        .bind(energy_cost)
-        .bind(rei_id)
-        .execute(&self.pool)
+        .bind(session.energy_spent)
+        .bind(session.rei_id)
+        .bind(session.usage.searches)
+        .bind(session.usage.embeddings)
+        .bind(session.usage.tokens)
+        .bind(serde_json::json!(session.queries_deferred))
+        .bind(session.resume_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| SelfLearningError::DatabaseError(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO learning_sessions
+                (id, rei_id, queries, searches_completed, searches_skipped,
+                 memories_stored, items_learned, sources, errors, rate_limited,
+                 queries_deferred, energy_spent, started_at, mode)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+            "#,
+        )
+        .bind(session.id)
+        .bind(session.rei_id)
+        .bind(serde_json::json!(session.queries_generated))
+        .bind(session.searches_completed as i32)
+        .bind(session.searches_skipped as i32)
+        .bind(session.memories_stored as i32)
+        .bind(session.items_learned as i32)
+        .bind(serde_json::json!(session.sources))
+        .bind(serde_json::json!(session.errors))
+        .bind(session.rate_limited as i32)
+        .bind(serde_json::json!(session.queries_deferred))
+        .bind(session.energy_spent)
+        .bind(session.started_at)
+        .bind(session.mode.as_str())
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| SelfLearningError::DatabaseError(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO learning_topics (rei_id, query, last_queried_at, times_queried)
+            SELECT $1, query, NOW(), 1 FROM UNNEST($2::text[]) AS query
+            ON CONFLICT (rei_id, query) DO UPDATE
+            SET last_queried_at = NOW(), times_queried = learning_topics.times_queried + 1
+            "#,
+        )
+        .bind(session.rei_id)
+        .bind(visited)
+        .execute(&mut *tx)
         .await
         .map_err(|e| SelfLearningError::DatabaseError(e.to_string()))?;
 
+        let unstaged = match &self.event_bus {
+            Some(event_bus) => event_bus
+                .stage_in(&mut tx, event)
+                .await
+                .map_err(|e| SelfLearningError::DatabaseError(e.to_string()))?,
+            None => None,
+        };
+
+        tx.commit()
+            .await
+            .map_err(|e| SelfLearningError::DatabaseError(e.to_string()))?;
+
+        if let (Some(event_bus), Some(event)) = (&self.event_bus, unstaged) {
+            event_bus.publish(event);
+        }
+
         Ok(())
     }
 
@@ -320,7 +1172,7 @@ impl SelfLearningService {
 
     /// Get all Reis
     async fn get_all_reis(&self) -> Result<Vec<Rei>, SelfLearningError> {
-        sqlx::query_as::<_, Rei>("SELECT * FROM reis")
+        sqlx::query_as::<_, Rei>("SELECT * FROM reis WHERE deleted_at IS NULL")
             .fetch_all(&self.pool)
             .await
             .map_err(|e| SelfLearningError::DatabaseError(e.to_string()))
@@ -332,8 +1184,21 @@ impl SelfLearningService {
 pub enum SelfLearningError {
     ReiNotFound(Uuid),
     NoInterests,
-    InsufficientEnergy { current: i32, required: i32 },
+    InsufficientEnergy {
+        current: i32,
+        required: i32,
+    },
+    /// The named daily cap (searches, embeddings or tokens) is used up
+    BudgetExhausted(&'static str),
     SearchFailed(String),
+    /// Gemini rejected the search with a rate limit
+    RateLimited {
+        retry_after: Option<std::time::Duration>,
+    },
+    /// Every reference of the answer came from an excluded domain
+    AllSourcesExcluded,
+    /// Memory-only mode found too few related memories to study
+    TooFewMemories(usize),
     EmbeddingFailed(String),
     StorageFailed(String),
     DatabaseError(String),
@@ -351,7 +1216,22 @@ impl std::fmt::Display for SelfLearningError {
                     current, required
                 )
             }
+            SelfLearningError::BudgetExhausted(cap) => {
+                write!(f, "Daily {} budget exhausted", cap)
+            }
             SelfLearningError::SearchFailed(msg) => write!(f, "Search failed: {}", msg),
+            SelfLearningError::RateLimited { retry_after } => match retry_after {
+                Some(wait) => write!(f, "Rate limited, retry after {:?}", wait),
+                None => write!(f, "Rate limited"),
+            },
+            SelfLearningError::AllSourcesExcluded => {
+                write!(f, "All sources are on the excluded domain list")
+            }
+            SelfLearningError::TooFewMemories(found) => write!(
+                f,
+                "Only {} related memories to study (need {})",
+                found, MIN_STUDY_MEMORIES
+            ),
             SelfLearningError::EmbeddingFailed(msg) => write!(f, "Embedding failed: {}", msg),
             SelfLearningError::StorageFailed(msg) => write!(f, "Storage failed: {}", msg),
             SelfLearningError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
@@ -360,3 +1240,285 @@ impl std::fmt::Display for SelfLearningError {
 }
 
 impl std::error::Error for SelfLearningError {}
+
+impl From<WebSearchError> for SelfLearningError {
+    fn from(e: WebSearchError) -> Self {
+        match e {
+            WebSearchError::RateLimited { retry_after } => {
+                SelfLearningError::RateLimited { retry_after }
+            }
+            e => SelfLearningError::SearchFailed(e.to_string()),
+        }
+    }
+}
+
+/// Rate limit state kept in `rei_states` between sessions
+#[derive(Debug, FromRow)]
+struct RateLimitBackoff {
+    deferred_queries: Json<Vec<String>>,
+    learning_resume_at: Option<DateTime<Utc>>,
+}
+
+/// Whether a memory is a note written by memory-only learning
+fn is_study_note(memory: &Memory) -> bool {
+    memory
+        .metadata
+        .as_ref()
+        .and_then(|m| m.get("source"))
+        .and_then(|s| s.as_str())
+        == Some("memory")
+}
+
+/// A study note on `query`: what the related memories say, grouped by
+/// kind, most related first
+fn study_note(query: &str, related: &[Memory]) -> String {
+    let mut note = format!(
+        "What I already know about \"{}\" ({} memories re-read):",
+        query,
+        related.len()
+    );
+    let mut kinds: Vec<MemoryType> = Vec::new();
+    for memory in related {
+        if !kinds.contains(&memory.memory_type) {
+            kinds.push(memory.memory_type.clone());
+        }
+    }
+    for kind in kinds {
+        note.push_str(&format!("\n\n[{}]", kind));
+        for memory in related.iter().filter(|m| m.memory_type == kind) {
+            let excerpt: String = memory.content.chars().take(STUDY_EXCERPT_CHARS).collect();
+            let ellipsis = if excerpt.len() < memory.content.len() {
+                "…"
+            } else {
+                ""
+            };
+            note.push_str(&format!(
+                "\n- {}{} ({})",
+                excerpt.split_whitespace().collect::<Vec<_>>().join(" "),
+                ellipsis,
+                memory.created_at.format("%Y-%m-%d")
+            ));
+        }
+    }
+    note
+}
+
+/// The queries to search this session: those deferred by a rate limit first
+/// (unless the manifest dropped them), then the rest, at most `max`
+fn schedule_queries(deferred: Vec<String>, generated: &[String], max: usize) -> Vec<String> {
+    let mut queries: Vec<String> = deferred
+        .into_iter()
+        .filter(|q| generated.contains(q))
+        .collect();
+    for query in generated {
+        if !queries.contains(query) {
+            queries.push(query.clone());
+        }
+    }
+    queries.truncate(max);
+    queries
+}
+
+/// Decide what to search for `query` given its nearest learning memory
+fn plan_for_coverage(
+    query: &str,
+    nearest: Option<(Memory, f32)>,
+    now: DateTime<Utc>,
+    config: &LearningConfig,
+) -> Option<String> {
+    let Some((memory, score)) = nearest else {
+        return Some(query.to_string());
+    };
+    if score < config.coverage_threshold {
+        return Some(query.to_string());
+    }
+    if now - memory.created_at < Duration::days(config.refresh_after_days) {
+        return None;
+    }
+    Some(format!(
+        "{} (only what is new since {})",
+        query,
+        memory.created_at.format("%Y-%m-%d")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn learned_at(created_at: DateTime<Utc>) -> Memory {
+        Memory {
+            id: Uuid::new_v4().to_string(),
+            rei_id: Uuid::new_v4().to_string(),
+            content: "## Query: Rust latest developments 2025".to_string(),
+            memory_type: MemoryType::Learning,
+            importance: 0.7,
+            tags: vec![],
+            metadata: None,
+            created_at,
+        }
+    }
+
+    #[test]
+    fn test_plan_for_coverage() {
+        let config = LearningConfig::default();
+        let now = Utc.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap();
+        let query = "Rust latest developments 2025";
+
+        // Nothing similar yet
+        assert_eq!(
+            plan_for_coverage(query, None, now, &config),
+            Some(query.to_string())
+        );
+        let yesterday = learned_at(now - Duration::days(1));
+        assert_eq!(
+            plan_for_coverage(query, Some((yesterday.clone(), 0.5)), now, &config),
+            Some(query.to_string())
+        );
+
+        // Covered recently
+        assert_eq!(
+            plan_for_coverage(query, Some((yesterday, 0.95)), now, &config),
+            None
+        );
+
+        // Covered a while ago: ask only for news
+        let last_month = learned_at(Utc.with_ymd_and_hms(2026, 2, 1, 9, 0, 0).unwrap());
+        assert_eq!(
+            plan_for_coverage(query, Some((last_month, 0.95)), now, &config),
+            Some("Rust latest developments 2025 (only what is new since 2026-02-01)".to_string())
+        );
+    }
+
+    #[test]
+    fn test_study_note() {
+        let learning = learned_at(Utc::now());
+        let mut fact = learned_at(Utc::now());
+        fact.memory_type = MemoryType::Fact;
+        fact.content = "x".repeat(STUDY_EXCERPT_CHARS + 1);
+
+        let note = study_note("rust", &[learning.clone(), fact]);
+        assert!(note.starts_with("What I already know about \"rust\" (2 memories re-read):"));
+        assert!(note.contains("[learning]\n- ## Query: Rust latest developments 2025 ("));
+        assert!(note.contains("[fact]\n- "));
+        assert!(note.contains("…"));
+
+        assert!(!is_study_note(&learning));
+        let mut studied = learning;
+        studied.metadata = Some(serde_json::json!({"source": "memory"}));
+        assert!(is_study_note(&studied));
+    }
+
+    #[test]
+    fn test_schedule_queries() {
+        let generated: Vec<String> = ["rust", "wasm", "zig", "go"]
+            .iter()
+            .map(|q| q.to_string())
+            .collect();
+        assert_eq!(
+            schedule_queries(vec![], &generated, 3),
+            ["rust", "wasm", "zig"]
+        );
+
+        // Deferred queries go first, once; dropped interests are forgotten
+        let deferred = vec!["go".to_string(), "zig".to_string(), "cobol".to_string()];
+        assert_eq!(
+            schedule_queries(deferred, &generated, 3),
+            ["go", "zig", "rust"]
+        );
+    }
+
+    #[test]
+    fn test_rotate_topics() {
+        let queries: Vec<String> = ["rust", "wasm", "zig", "go"]
+            .iter()
+            .map(|q| q.to_string())
+            .collect();
+        let searched = |query: &str, day: u32| {
+            let topic = LearningTopic {
+                query: query.to_string(),
+                last_queried_at: Some(Utc.with_ymd_and_hms(2026, 3, day, 0, 0, 0).unwrap()),
+                times_queried: 1,
+            };
+            (query.to_string(), topic)
+        };
+
+        // Nothing searched yet: manifest order
+        let order = |history: &HashMap<String, LearningTopic>| {
+            rotate_topics(&queries, history)
+                .into_iter()
+                .map(|t| t.query)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(order(&HashMap::new()), ["rust", "wasm", "zig", "go"]);
+
+        // The unsearched ones come first, then the oldest
+        let history = HashMap::from([searched("rust", 9), searched("wasm", 2)]);
+        assert_eq!(order(&history), ["zig", "go", "wasm", "rust"]);
+    }
+
+    #[test]
+    fn test_manifest_queries() {
+        let manifest = serde_json::json!({
+            "interests": ["rust"],
+            "learning_topics": ["async traits", "async traits"],
+        });
+        assert_eq!(
+            manifest_queries(&manifest, "engineer"),
+            ["rust latest developments 2025", "async traits"]
+        );
+        assert_eq!(
+            manifest_queries(&serde_json::json!({}), "engineer"),
+            ["engineer best practices 2025"]
+        );
+    }
+
+    #[test]
+    fn test_budget_from_manifest() {
+        let defaults = LearningBudget::default();
+        assert_eq!(
+            LearningBudget::for_manifest(&serde_json::json!({}), &defaults),
+            defaults
+        );
+
+        let manifest = serde_json::json!({"learning_budget": {"searches": 5, "tokens": -1}});
+        assert_eq!(
+            LearningBudget::for_manifest(&manifest, &defaults),
+            LearningBudget {
+                searches: 5,
+                embeddings: defaults.embeddings,
+                tokens: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_budget_exhaustion() {
+        let budget = LearningBudget {
+            searches: 3,
+            embeddings: 6,
+            tokens: 1_000,
+        };
+        let mut used = LearningUsage::default();
+        assert_eq!(budget.exhausted_by(&used), None);
+
+        used.embeddings = 5;
+        assert_eq!(budget.exhausted_by(&used), Some("embeddings"));
+
+        used.embeddings = 4;
+        used.tokens = 1_000;
+        assert_eq!(budget.exhausted_by(&used), Some("tokens"));
+
+        used.searches = 3;
+        assert_eq!(budget.exhausted_by(&used), Some("searches"));
+
+        // Sources only need embeddings and tokens
+        used.tokens = 0;
+        assert_eq!(budget.exhausted(&used), None);
+        assert_eq!(budget.items_left(&used), 2);
+        used.embeddings = 6;
+        assert_eq!(budget.exhausted(&used), Some("embeddings"));
+        assert_eq!(budget.items_left(&used), 0);
+    }
+}
@@ -0,0 +1,173 @@
+//! Source Policy - Which web sources a Rei learns from
+//!
+//! The manifest can list domains under `preferred_domains` and
+//! `excluded_domains`. A domain also covers its subdomains. References from
+//! excluded domains are dropped before a search result is stored; references
+//! from preferred domains are cited first.
+
+use serde_json::Value;
+
+use crate::services::web_search::WebSearchReference;
+
+/// Manifest key listing domains cited first
+pub const PREFERRED_MANIFEST_KEY: &str = "preferred_domains";
+
+/// Manifest key listing domains never learned from
+pub const EXCLUDED_MANIFEST_KEY: &str = "excluded_domains";
+
+/// A Rei's preferred and excluded domains
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourcePolicy {
+    preferred: Vec<String>,
+    excluded: Vec<String>,
+}
+
+/// References left after applying a policy
+#[derive(Debug, Clone, Default)]
+pub struct RankedReferences {
+    /// Allowed references, preferred domains first
+    pub kept: Vec<WebSearchReference>,
+    /// How many references came from excluded domains
+    pub excluded: usize,
+}
+
+impl SourcePolicy {
+    pub fn from_manifest(manifest: &Value) -> Self {
+        let domains = |key: &str| -> Vec<String> {
+            manifest
+                .get(key)
+                .and_then(Value::as_array)
+                .map(|items| {
+                    items
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .map(normalize_domain)
+                        .filter(|d| !d.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        Self {
+            preferred: domains(PREFERRED_MANIFEST_KEY),
+            excluded: domains(EXCLUDED_MANIFEST_KEY),
+        }
+    }
+
+    /// Drop excluded references and move preferred ones to the front
+    pub fn apply(&self, references: Vec<WebSearchReference>) -> RankedReferences {
+        let total = references.len();
+        let (mut preferred, mut others) = (Vec::new(), Vec::new());
+        for reference in references {
+            if matches(&self.excluded, &reference) {
+                continue;
+            }
+            if matches(&self.preferred, &reference) {
+                preferred.push(reference);
+            } else {
+                others.push(reference);
+            }
+        }
+        preferred.append(&mut others);
+
+        RankedReferences {
+            excluded: total - preferred.len(),
+            kept: preferred,
+        }
+    }
+}
+
+/// Whether any of the reference's hosts is in `domains`
+///
+/// Grounded search results often link through a redirect, so the site name
+/// and title (which Gemini sets to the domain) are checked too.
+fn matches(domains: &[String], reference: &WebSearchReference) -> bool {
+    let hosts = [
+        host_of(&reference.url),
+        reference.source.as_deref().map(normalize_domain),
+        Some(normalize_domain(&reference.title)),
+    ];
+    hosts.iter().flatten().any(|host| {
+        domains
+            .iter()
+            .any(|d| host == d || host.ends_with(&format!(".{}", d)))
+    })
+}
+
+/// Lowercase, without scheme, `www.`/`*.` prefix, path or port
+fn normalize_domain(domain: &str) -> String {
+    host_of(domain).unwrap_or_default()
+}
+
+/// Host part of a URL or bare domain
+fn host_of(url: &str) -> Option<String> {
+    let rest = url.trim().split_once("://").map_or(url.trim(), |(_, r)| r);
+    let host = rest
+        .split(['/', '?', '#'])
+        .next()?
+        .rsplit('@')
+        .next()?
+        .split(':')
+        .next()?
+        .trim_start_matches("*.")
+        .trim_start_matches("www.")
+        .to_lowercase();
+    (!host.is_empty()).then_some(host)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn reference(url: &str, title: &str) -> WebSearchReference {
+        WebSearchReference {
+            title: title.to_string(),
+            url: url.to_string(),
+            snippet: None,
+            source: None,
+        }
+    }
+
+    #[test]
+    fn test_host_of() {
+        assert_eq!(
+            host_of("https://www.Blog.Rust-Lang.org:443/2025/post?x=1"),
+            Some("blog.rust-lang.org".to_string())
+        );
+        assert_eq!(host_of("*.example.com"), Some("example.com".to_string()));
+        assert_eq!(host_of(""), None);
+    }
+
+    #[test]
+    fn test_apply_excludes_and_prefers() {
+        let policy = SourcePolicy::from_manifest(&json!({
+            "preferred_domains": ["rust-lang.org"],
+            "excluded_domains": ["https://www.spam.example/"],
+        }));
+        let ranked = policy.apply(vec![
+            reference("https://news.example.com/rust", "news.example.com"),
+            reference("https://cdn.spam.example/a", "Totally legit"),
+            // Redirect URL: the title carries the domain
+            reference(
+                "https://vertexaisearch.cloud.google.com/grounding-api-redirect/abc",
+                "blog.rust-lang.org",
+            ),
+        ]);
+
+        assert_eq!(ranked.excluded, 1);
+        let titles: Vec<&str> = ranked.kept.iter().map(|r| r.title.as_str()).collect();
+        assert_eq!(titles, vec!["blog.rust-lang.org", "news.example.com"]);
+    }
+
+    #[test]
+    fn test_empty_policy_keeps_order() {
+        let policy = SourcePolicy::from_manifest(&json!({}));
+        assert_eq!(policy, SourcePolicy::default());
+        let ranked = policy.apply(vec![
+            reference("https://a.com", "a"),
+            reference("https://b.com", "b"),
+        ]);
+        assert_eq!(ranked.excluded, 0);
+        assert_eq!(ranked.kept[0].url, "https://a.com");
+    }
+}
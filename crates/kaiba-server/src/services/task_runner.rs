@@ -0,0 +1,190 @@
+//! Task Runner - Carrying out scheduled one-off tasks
+//!
+//! Every minute the runner claims tasks that have come due and sends each
+//! instruction through the call pipeline, as if a user had called the Rei.
+//! The response (or the error) is stored on the task and delivered as a
+//! `task_completed` event, so webhooks and the live event stream see it.
+//!
+//! Tasks of paused Reis wait until autonomy is resumed. A task left
+//! `running` by a crash is failed after [`STALE_AFTER_MINUTES`].
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use kaiba::{DomainError, ReiEvent, ReiEventBus, WebhookEventType};
+use tokio_util::sync::CancellationToken;
+
+use crate::models::{CallContext, CallRequest, ReiTask};
+use crate::routes::call::execute_call;
+use crate::AppState;
+
+/// How often to look for due tasks
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Minutes after which a task still `running` is considered interrupted
+pub const STALE_AFTER_MINUTES: i32 = 30;
+
+/// Every status a task can have
+pub const TASK_STATUSES: [&str; 4] = ["pending", "running", "completed", "failed"];
+
+/// Runs due tasks through the call pipeline
+pub struct TaskRunner {
+    state: AppState,
+}
+
+impl TaskRunner {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    /// Run the task runner until shutdown
+    pub async fn run(self: Arc<Self>, shutdown: CancellationToken) {
+        let mut ticker = tokio::time::interval(CHECK_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {}
+                _ = shutdown.cancelled() => return,
+            }
+            self.fail_stale().await;
+            // One task at a time, until none is due (or shutdown)
+            while !shutdown.is_cancelled() {
+                match self.claim_next().await {
+                    Ok(Some(task)) => self.execute(task).await,
+                    Ok(None) => break,
+                    Err(e) => {
+                        tracing::warn!("⚠️  Failed to claim due tasks: {}", e);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Mark the next due task of an active Rei as running
+    ///
+    /// `SKIP LOCKED` keeps several instances from claiming the same task.
+    async fn claim_next(&self) -> Result<Option<ReiTask>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+            UPDATE rei_tasks SET status = 'running', started_at = NOW()
+            WHERE id = (
+                SELECT t.id FROM rei_tasks t
+                JOIN reis r ON r.id = t.rei_id
+                JOIN rei_states st ON st.rei_id = t.rei_id
+                WHERE t.status = 'pending'
+                  AND t.due_at <= NOW()
+                  AND r.deleted_at IS NULL
+                  AND st.autonomy_enabled
+                ORDER BY t.due_at
+                LIMIT 1
+                FOR UPDATE OF t SKIP LOCKED
+            )
+            RETURNING *
+            "#,
+        )
+        .fetch_optional(&self.state.pool)
+        .await
+    }
+
+    async fn execute(&self, task: ReiTask) {
+        tracing::info!("⏰ Running task {} for Rei {}", task.id, task.rei_id);
+
+        let request = CallRequest {
+            tei_ids: vec![],
+            message: task.instruction.clone(),
+            context: Some(CallContext {
+                task_type: Some("scheduled_task".to_string()),
+                include_memories: task.include_memories,
+                ..Default::default()
+            }),
+            session_id: None,
+        };
+        let (result, error) = match execute_call(&self.state, task.rei_id, request).await {
+            Ok(response) => (Some(response.response), None),
+            Err(e) => {
+                tracing::warn!("⚠️  Task {} failed: {}", task.id, e);
+                (None, Some(e.to_string()))
+            }
+        };
+        let status = if error.is_none() {
+            "completed"
+        } else {
+            "failed"
+        };
+
+        if let Err(e) = self.record(&task, status, result, error).await {
+            tracing::warn!("⚠️  Failed to record result of task {}: {}", task.id, e);
+        }
+    }
+
+    /// Store a task's outcome and stage its TaskCompleted event in one transaction
+    ///
+    /// If this fails, the task stays running until `fail_stale` fails it.
+    async fn record(
+        &self,
+        task: &ReiTask,
+        status: &str,
+        result: Option<String>,
+        error: Option<String>,
+    ) -> Result<(), DomainError> {
+        let db_error = |e: sqlx::Error| DomainError::Repository(e.to_string());
+        let mut tx = self.state.pool.begin().await.map_err(db_error)?;
+
+        sqlx::query(
+            r#"
+            UPDATE rei_tasks
+            SET status = $2, result = $3, error = $4, completed_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(task.id)
+        .bind(status)
+        .bind(&result)
+        .bind(&error)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+        let event = ReiEvent::new(
+            task.rei_id,
+            WebhookEventType::TaskCompleted,
+            serde_json::json!({
+                "task_id": task.id,
+                "instruction": task.instruction,
+                "due_at": task.due_at,
+                "status": status,
+                "result": result,
+                "error": error,
+            }),
+        );
+        let unstaged = self.state.event_bus.stage_in(&mut tx, event).await?;
+        tx.commit().await.map_err(db_error)?;
+        if let Some(event) = unstaged {
+            self.state.event_bus.publish(event);
+        }
+        Ok(())
+    }
+
+    /// Fail tasks left running by a crash or restart
+    async fn fail_stale(&self) {
+        let result = sqlx::query(
+            r#"
+            UPDATE rei_tasks
+            SET status = 'failed', error = 'Interrupted', completed_at = NOW()
+            WHERE status = 'running'
+              AND started_at < NOW() - make_interval(mins => $1)
+            "#,
+        )
+        .bind(STALE_AFTER_MINUTES)
+        .execute(&self.state.pool)
+        .await;
+
+        match result {
+            Ok(r) if r.rows_affected() > 0 => {
+                tracing::warn!("⚠️  Failed {} interrupted tasks", r.rows_affected());
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!("⚠️  Failed to clean up interrupted tasks: {}", e),
+        }
+    }
+}
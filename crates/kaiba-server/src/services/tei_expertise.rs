@@ -0,0 +1,156 @@
+//! Tei Expertise - Digests flow into the Teis that serve a Rei
+//!
+//! A Rei whose manifest sets `"sync_tei_expertise": true` has each digest
+//! merged into the `expertise` JSON of its associated Teis, under a
+//! `digests` key:
+//!
+//! ```json
+//! {"digests": {"latest": {...}, "history": [{...}, ...]}}
+//! ```
+//!
+//! `latest` is the newest digest; `history` keeps the previous ones, newest
+//! first, up to `MAX_EXPERTISE_HISTORY`. Other keys are left untouched. Call
+//! prompts surface the selected Tei's latest digest.
+
+use chrono::Utc;
+use serde_json::{json, Map, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::services::digest::DigestResult;
+
+/// Manifest key that opts a Rei in
+pub const SYNC_MANIFEST_KEY: &str = "sync_tei_expertise";
+
+/// Previous digests kept per Tei
+const MAX_EXPERTISE_HISTORY: usize = 10;
+
+/// Merge a digest into every associated Tei, if the Rei opted in
+///
+/// Returns how many Teis were updated.
+pub async fn sync_digest(pool: &PgPool, result: &DigestResult) -> Result<usize, sqlx::Error> {
+    let manifest: Option<Value> =
+        sqlx::query_scalar("SELECT manifest FROM reis WHERE id = $1 AND deleted_at IS NULL")
+            .bind(result.rei_id)
+            .fetch_optional(pool)
+            .await?;
+    let enabled = manifest
+        .as_ref()
+        .and_then(|m| m.get(SYNC_MANIFEST_KEY))
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    if !enabled {
+        return Ok(0);
+    }
+
+    let entry = json!({
+        "rei_id": result.rei_id,
+        "tier": result.tier,
+        "summary": result.summary,
+        "memories_processed": result.memories_processed,
+        "digested_at": Utc::now(),
+    });
+
+    // Lock the rows so concurrent edits are merged rather than lost
+    let mut tx = pool.begin().await?;
+    let teis: Vec<(Uuid, Option<Value>)> = sqlx::query_as(
+        r#"
+        SELECT t.id, t.expertise FROM teis t
+        INNER JOIN rei_teis rt ON t.id = rt.tei_id
+        WHERE rt.rei_id = $1
+        FOR UPDATE OF t
+        "#,
+    )
+    .bind(result.rei_id)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    for (tei_id, expertise) in &teis {
+        sqlx::query("UPDATE teis SET expertise = $2, updated_at = NOW() WHERE id = $1")
+            .bind(tei_id)
+            .bind(merge_digest(expertise.clone(), entry.clone()))
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await?;
+
+    Ok(teis.len())
+}
+
+/// Summary of the latest digest in a Tei's expertise
+pub fn latest_digest(expertise: Option<&Value>) -> Option<&str> {
+    expertise?
+        .get("digests")?
+        .get("latest")?
+        .get("summary")?
+        .as_str()
+}
+
+/// Make `entry` the latest digest, moving the previous one into history
+fn merge_digest(expertise: Option<Value>, entry: Value) -> Value {
+    let mut expertise = match expertise {
+        Some(Value::Object(map)) => map,
+        None | Some(Value::Null) => Map::new(),
+        // Keep hand-written non-object expertise next to the digests
+        Some(other) => Map::from_iter([("notes".to_string(), other)]),
+    };
+
+    let mut digests = match expertise.remove("digests") {
+        Some(Value::Object(map)) => map,
+        _ => Map::new(),
+    };
+    let mut history = match digests.remove("history") {
+        Some(Value::Array(items)) => items,
+        _ => Vec::new(),
+    };
+    if let Some(previous) = digests.remove("latest") {
+        history.insert(0, previous);
+    }
+    history.truncate(MAX_EXPERTISE_HISTORY);
+
+    digests.insert("latest".to_string(), entry);
+    digests.insert("history".to_string(), Value::Array(history));
+    expertise.insert("digests".to_string(), Value::Object(digests));
+
+    Value::Object(expertise)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_keeps_history_and_other_keys() {
+        let first = merge_digest(Some(json!({"domain": "rust"})), json!({"summary": "first"}));
+        assert_eq!(first["domain"], "rust");
+        assert_eq!(latest_digest(Some(&first)), Some("first"));
+        assert_eq!(first["digests"]["history"], json!([]));
+
+        let second = merge_digest(Some(first), json!({"summary": "second"}));
+        assert_eq!(latest_digest(Some(&second)), Some("second"));
+        assert_eq!(second["digests"]["history"], json!([{"summary": "first"}]));
+    }
+
+    #[test]
+    fn test_merge_caps_history() {
+        let mut expertise = None;
+        for i in 0..MAX_EXPERTISE_HISTORY + 5 {
+            expertise = Some(merge_digest(expertise, json!({ "summary": i.to_string() })));
+        }
+        let expertise = expertise.unwrap();
+        let history = expertise["digests"]["history"].as_array().unwrap();
+        assert_eq!(history.len(), MAX_EXPERTISE_HISTORY);
+        assert_eq!(
+            history[0]["summary"],
+            (MAX_EXPERTISE_HISTORY + 3).to_string()
+        );
+    }
+
+    #[test]
+    fn test_merge_wraps_non_object_expertise() {
+        let merged = merge_digest(Some(json!("knows rust")), json!({"summary": "s"}));
+        assert_eq!(merged["notes"], "knows rust");
+        assert_eq!(latest_digest(Some(&merged)), Some("s"));
+        assert_eq!(latest_digest(None), None);
+    }
+}
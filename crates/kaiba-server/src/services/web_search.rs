@@ -2,7 +2,7 @@
 //!
 //! Based on orcs implementation - uses Gemini API with grounding.
 
-use reqwest::{Client, StatusCode};
+use reqwest::{header::RETRY_AFTER, Client, StatusCode};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashSet;
@@ -37,6 +37,7 @@ impl WebSearchAgent {
     }
 
     /// Execute a web search query
+    #[tracing::instrument(name = "llm.web_search", skip_all, fields(model = %self.model))]
     pub async fn search(&self, query: &str) -> Result<WebSearchResponse, WebSearchError> {
         let trimmed = query.trim();
         if trimmed.is_empty() {
@@ -74,11 +75,16 @@ impl WebSearchAgent {
 
         if !response.status().is_success() {
             let status = response.status();
+            let retry_after = response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_retry_after);
             let body = response
                 .text()
                 .await
                 .unwrap_or_else(|_| "Failed to read error body".to_string());
-            return Err(map_http_error(status, body));
+            return Err(map_http_error(status, retry_after, body));
         }
 
         let payload: Value = response
@@ -89,11 +95,16 @@ impl WebSearchAgent {
         let answer = extract_answer(&payload)
             .unwrap_or_else(|| "Google Search returned no answer".to_string());
         let references = extract_references(&payload);
+        let tokens_used = payload
+            .pointer("/usageMetadata/totalTokenCount")
+            .and_then(Value::as_i64)
+            .unwrap_or(0) as i32;
 
         Ok(WebSearchResponse {
             query: query.to_string(),
             answer,
             references,
+            tokens_used,
         })
     }
 }
@@ -146,6 +157,9 @@ pub struct WebSearchResponse {
     pub query: String,
     pub answer: String,
     pub references: Vec<WebSearchReference>,
+    /// Total tokens Gemini reported for the request (0 if not reported)
+    #[serde(default)]
+    pub tokens_used: i32,
 }
 
 /// Web search error types
@@ -289,9 +303,14 @@ fn extract_references(root: &Value) -> Vec<WebSearchReference> {
     references
 }
 
-fn map_http_error(status: StatusCode, body: String) -> WebSearchError {
-    let message = serde_json::from_str::<serde_json::Value>(&body)
-        .ok()
+fn map_http_error(
+    status: StatusCode,
+    retry_after: Option<Duration>,
+    body: String,
+) -> WebSearchError {
+    let json = serde_json::from_str::<serde_json::Value>(&body).ok();
+    let message = json
+        .as_ref()
         .and_then(|json| {
             json.get("error")
                 .and_then(|err| err.get("message"))
@@ -301,7 +320,9 @@ fn map_http_error(status: StatusCode, body: String) -> WebSearchError {
         .unwrap_or_else(|| body.clone());
 
     if status == StatusCode::TOO_MANY_REQUESTS {
-        return WebSearchError::RateLimited { retry_after: None };
+        // Gemini puts the delay in a RetryInfo detail rather than the header
+        let retry_after = retry_after.or_else(|| json.as_ref().and_then(retry_delay));
+        return WebSearchError::RateLimited { retry_after };
     }
 
     WebSearchError::ApiError {
@@ -309,3 +330,59 @@ fn map_http_error(status: StatusCode, body: String) -> WebSearchError {
         message,
     }
 }
+
+/// `Retry-After` in seconds (the HTTP-date form is not used by Gemini)
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    value.trim().parse::<u64>().ok().map(Duration::from_secs)
+}
+
+/// `retryDelay` of the RetryInfo detail in a Gemini error body (e.g. "37s")
+fn retry_delay(body: &Value) -> Option<Duration> {
+    body.pointer("/error/details")?
+        .as_array()?
+        .iter()
+        .filter_map(|detail| detail.get("retryDelay")?.as_str())
+        .find_map(|delay| {
+            let seconds: f64 = delay.strip_suffix('s')?.parse().ok()?;
+            Duration::try_from_secs_f64(seconds).ok()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limit_retry_after() {
+        let body = serde_json::json!({
+            "error": {
+                "code": 429,
+                "message": "Resource has been exhausted",
+                "details": [
+                    {"@type": "type.googleapis.com/google.rpc.QuotaFailure"},
+                    {"@type": "type.googleapis.com/google.rpc.RetryInfo", "retryDelay": "37s"}
+                ]
+            }
+        })
+        .to_string();
+
+        let error = map_http_error(StatusCode::TOO_MANY_REQUESTS, None, body.clone());
+        assert!(matches!(
+            error,
+            WebSearchError::RateLimited { retry_after: Some(d) } if d == Duration::from_secs(37)
+        ));
+
+        // The header wins over the body
+        let error = map_http_error(StatusCode::TOO_MANY_REQUESTS, parse_retry_after("5"), body);
+        assert!(matches!(
+            error,
+            WebSearchError::RateLimited { retry_after: Some(d) } if d == Duration::from_secs(5)
+        ));
+
+        let error = map_http_error(StatusCode::TOO_MANY_REQUESTS, None, "slow down".to_string());
+        assert!(matches!(
+            error,
+            WebSearchError::RateLimited { retry_after: None }
+        ));
+    }
+}
@@ -0,0 +1,103 @@
+//! Webhook Batcher - Periodic delivery for batched webhooks
+//!
+//! Webhooks in `batched` mode have their events queued by the publisher.
+//! Once a minute the batcher finds webhooks whose oldest queued event is a
+//! full batch interval old, and delivers everything queued as a single
+//! `custom:batch` payload.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio_util::sync::CancellationToken;
+
+use kaiba::{ReiWebhook, ReiWebhookRepository, TeiWebhook, WebhookPayload};
+
+use crate::adapters::{HttpWebhook, PgReiWebhookRepository};
+use crate::services::webhook_health;
+
+/// How often to look for due batches
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Flushes queued events for batched webhooks
+pub struct WebhookBatcher {
+    webhook_repo: Arc<PgReiWebhookRepository>,
+    http_webhook: Arc<HttpWebhook>,
+}
+
+impl WebhookBatcher {
+    pub fn new(webhook_repo: Arc<PgReiWebhookRepository>, http_webhook: Arc<HttpWebhook>) -> Self {
+        Self {
+            webhook_repo,
+            http_webhook,
+        }
+    }
+
+    /// Run the batcher until shutdown; a batch in flight is finished first
+    pub async fn run(self: Arc<Self>, shutdown: CancellationToken) {
+        let mut ticker = tokio::time::interval(CHECK_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {}
+                _ = shutdown.cancelled() => return,
+            }
+            self.flush_due().await;
+        }
+    }
+
+    /// Deliver all batches whose interval has elapsed
+    async fn flush_due(&self) {
+        let webhooks = match self.webhook_repo.find_due_batches().await {
+            Ok(webhooks) => webhooks,
+            Err(e) => {
+                tracing::warn!("⚠️  Failed to find due webhook batches: {}", e);
+                return;
+            }
+        };
+
+        for webhook in webhooks {
+            self.flush(&webhook).await;
+        }
+    }
+
+    async fn flush(&self, webhook: &ReiWebhook) {
+        let events = match self.webhook_repo.take_batch_events(webhook.id).await {
+            Ok(events) if !events.is_empty() => events,
+            Ok(_) => return,
+            Err(e) => {
+                tracing::warn!("⚠️  Failed to take batch for {}: {}", webhook.name, e);
+                return;
+            }
+        };
+
+        tracing::info!(
+            "📦 Delivering batch of {} events to webhook: {}",
+            events.len(),
+            webhook.name
+        );
+
+        // Global batches span Reis; the nil ID marks them as unscoped
+        let payload = WebhookPayload::batch(webhook.rei_id.unwrap_or_default(), events);
+        let delivery = match self
+            .http_webhook
+            .deliver_with_retry(webhook, &payload)
+            .await
+        {
+            Ok(delivery) => delivery,
+            Err(e) => {
+                tracing::warn!("  ❌ Batch delivery error: {}", e);
+                return;
+            }
+        };
+
+        if let Err(e) = self.webhook_repo.save_delivery(&delivery).await {
+            tracing::warn!("  ⚠️  Failed to save delivery record: {}", e);
+        }
+        webhook_health::track_delivery_outcome(
+            self.webhook_repo.as_ref(),
+            self.http_webhook.as_ref(),
+            webhook,
+            &delivery,
+        )
+        .await;
+    }
+}
@@ -0,0 +1,119 @@
+//! Webhook Health - Consecutive failure tracking and auto-disable
+//!
+//! Records the final outcome of each delivery. When a webhook reaches its
+//! `max_consecutive_failures` threshold it is disabled, and a StateChanged
+//! event is sent to the Rei's other webhooks so someone notices.
+
+use kaiba::{
+    DeliveryStatus, ReiWebhook, ReiWebhookRepository, TeiWebhook, WebhookDelivery,
+    WebhookEventType, WebhookPayload,
+};
+
+/// Record a final delivery outcome, auto-disabling the webhook if needed
+///
+/// Returns true if the webhook was disabled.
+pub async fn track_delivery_outcome<R, W>(
+    webhook_repo: &R,
+    http_webhook: &W,
+    webhook: &ReiWebhook,
+    delivery: &WebhookDelivery,
+) -> bool
+where
+    R: ReiWebhookRepository,
+    W: TeiWebhook,
+{
+    let success = delivery.status == DeliveryStatus::Success;
+
+    let consecutive_failures = match webhook_repo
+        .record_delivery_outcome(webhook.id, success)
+        .await
+    {
+        Ok(count) => count,
+        Err(e) => {
+            tracing::warn!("  ⚠️  Failed to record delivery outcome: {}", e);
+            return false;
+        }
+    };
+
+    if success || !webhook.should_auto_disable(consecutive_failures) {
+        return false;
+    }
+
+    if let Err(e) = webhook_repo.set_enabled(webhook.id, false).await {
+        tracing::warn!("  ⚠️  Failed to auto-disable webhook: {}", e);
+        return false;
+    }
+
+    tracing::warn!(
+        "  🔌 Webhook auto-disabled after {} consecutive failures: {}",
+        consecutive_failures,
+        webhook.name
+    );
+
+    notify_auto_disabled(webhook_repo, http_webhook, webhook, consecutive_failures).await;
+
+    true
+}
+
+/// Send a StateChanged event to the Rei's remaining webhooks
+///
+/// A disabled global webhook notifies the other global webhooks.
+async fn notify_auto_disabled<R, W>(
+    webhook_repo: &R,
+    http_webhook: &W,
+    disabled: &ReiWebhook,
+    consecutive_failures: i32,
+) where
+    R: ReiWebhookRepository,
+    W: TeiWebhook,
+{
+    let webhooks = match disabled.rei_id {
+        Some(rei_id) => {
+            webhook_repo
+                .find_by_rei_and_event(rei_id, &WebhookEventType::StateChanged)
+                .await
+        }
+        None => webhook_repo.find_global().await.map(|webhooks| {
+            webhooks
+                .into_iter()
+                .filter(|w| w.should_receive(&WebhookEventType::StateChanged))
+                .collect()
+        }),
+    };
+    let webhooks = match webhooks {
+        Ok(w) => w,
+        Err(e) => {
+            tracing::warn!("  ⚠️  Failed to find webhooks: {}", e);
+            return;
+        }
+    };
+
+    let payload = WebhookPayload::new(
+        WebhookEventType::StateChanged,
+        disabled.rei_id.unwrap_or_default(),
+        serde_json::json!({
+            "reason": "webhook_auto_disabled",
+            "webhook_id": disabled.id,
+            "webhook_name": disabled.name,
+            "consecutive_failures": consecutive_failures,
+        }),
+    );
+
+    // Single pass, no failure tracking - avoids cascading auto-disables
+    for webhook in webhooks.iter().filter(|w| w.id != disabled.id) {
+        if !webhook.matches_conditions(&payload.data) {
+            continue;
+        }
+
+        match http_webhook.deliver_with_retry(webhook, &payload).await {
+            Ok(delivery) => {
+                if let Err(e) = webhook_repo.save_delivery(&delivery).await {
+                    tracing::warn!("  ⚠️  Failed to save delivery record: {}", e);
+                }
+            }
+            Err(e) => {
+                tracing::warn!("  ❌ Webhook delivery error: {}", e);
+            }
+        }
+    }
+}
@@ -0,0 +1,186 @@
+//! Webhook Publisher - Fan out Rei events to subscribed webhooks
+//!
+//! Event bus subscriber that turns ReiEvents into webhook deliveries.
+//! Looks up subscribed webhooks, applies data conditions, delivers with
+//! retry, records the delivery, and tracks failures for auto-disable.
+//! Batched webhooks get the event queued instead (see `webhook_batcher`).
+//!
+//! Deliveries run in background tasks, one at a time per webhook, so a slow
+//! endpoint's retries and rate-limit waits never hold up the outbox relay,
+//! live event streams or other webhooks.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use kaiba::{
+    DeliveryStatus, DomainError, ReiEvent, ReiEventSubscriber, ReiWebhook, ReiWebhookRepository,
+    TeiWebhook, WebhookDeliveryMode, WebhookPayload,
+};
+
+use crate::adapters::{HttpWebhook, PgReiWebhookRepository};
+use crate::services::event_stream::EventStream;
+use crate::services::webhook_health;
+use crate::supervisor::TaskSupervisor;
+
+/// Publishes Rei events to webhooks
+#[derive(Clone)]
+pub struct WebhookPublisher {
+    webhook_repo: Arc<PgReiWebhookRepository>,
+    http_webhook: Arc<HttpWebhook>,
+    event_stream: Option<Arc<EventStream>>,
+    tasks: Option<Arc<TaskSupervisor>>,
+    /// Per-webhook locks that keep deliveries to one endpoint sequential
+    lanes: Arc<Mutex<HashMap<Uuid, Arc<tokio::sync::Mutex<()>>>>>,
+}
+
+impl WebhookPublisher {
+    pub fn new(webhook_repo: Arc<PgReiWebhookRepository>, http_webhook: Arc<HttpWebhook>) -> Self {
+        Self {
+            webhook_repo,
+            http_webhook,
+            event_stream: None,
+            tasks: None,
+            lanes: Arc::default(),
+        }
+    }
+
+    /// Report delivery results to live event streams
+    pub fn with_event_stream(mut self, event_stream: Arc<EventStream>) -> Self {
+        self.event_stream = Some(event_stream);
+        self
+    }
+
+    /// Run deliveries under a supervisor, so shutdown waits for them
+    pub fn with_tasks(mut self, tasks: Arc<TaskSupervisor>) -> Self {
+        self.tasks = Some(tasks);
+        self
+    }
+
+    /// Hand an event to all subscribed webhooks
+    ///
+    /// Batched webhooks get it queued; the others get a background delivery.
+    /// Returns the number of deliveries started.
+    #[tracing::instrument(name = "webhook.publish", skip_all, fields(event = %event.event_type, rei_id = %event.rei_id))]
+    pub async fn publish(&self, event: &ReiEvent) -> usize {
+        let webhooks = match self
+            .webhook_repo
+            .find_by_rei_and_event(event.rei_id, &event.event_type)
+            .await
+        {
+            Ok(w) => w,
+            Err(e) => {
+                tracing::warn!("  ⚠️  Failed to find webhooks: {}", e);
+                return 0;
+            }
+        };
+
+        if webhooks.is_empty() {
+            return 0;
+        }
+
+        let payload = event.to_webhook_payload();
+        let mut started = 0;
+
+        for webhook in webhooks {
+            if !webhook.matches_conditions(&payload.data) {
+                tracing::debug!(
+                    "  ⏭️  Skipping webhook (conditions not met): {}",
+                    webhook.name
+                );
+                continue;
+            }
+
+            if webhook.delivery_mode == WebhookDeliveryMode::Batched {
+                match self
+                    .webhook_repo
+                    .enqueue_batch_event(webhook.id, &payload)
+                    .await
+                {
+                    Ok(()) => {
+                        tracing::debug!("  📥 Queued {} for batch: {}", payload.event, webhook.name)
+                    }
+                    Err(e) => tracing::warn!("  ⚠️  Failed to queue batch event: {}", e),
+                }
+                continue;
+            }
+
+            let publisher = self.clone();
+            let payload = payload.clone();
+            let delivery = async move { publisher.deliver(&webhook, &payload).await };
+            match &self.tasks {
+                Some(tasks) => tasks.spawn(delivery),
+                None => {
+                    tokio::spawn(delivery);
+                }
+            }
+            started += 1;
+        }
+
+        started
+    }
+
+    /// The lock serialising deliveries to a webhook
+    fn lane(&self, webhook_id: Uuid) -> Arc<tokio::sync::Mutex<()>> {
+        self.lanes
+            .lock()
+            .expect("Webhook lanes lock poisoned")
+            .entry(webhook_id)
+            .or_default()
+            .clone()
+    }
+
+    /// Deliver with retry once earlier deliveries to the webhook are done,
+    /// then record the outcome
+    async fn deliver(&self, webhook: &ReiWebhook, payload: &WebhookPayload) {
+        let lane = self.lane(webhook.id);
+        let _turn = lane.lock().await;
+
+        tracing::info!(
+            "  📤 Dispatching {} webhook: {}",
+            payload.event,
+            webhook.name
+        );
+
+        match self.http_webhook.deliver_with_retry(webhook, payload).await {
+            Ok(delivery) => {
+                if let Err(e) = self.webhook_repo.save_delivery(&delivery).await {
+                    tracing::warn!("  ⚠️  Failed to save delivery record: {}", e);
+                }
+                if let Some(stream) = &self.event_stream {
+                    stream.delivery(payload.rei_id, &webhook.name, &delivery);
+                }
+                webhook_health::track_delivery_outcome(
+                    self.webhook_repo.as_ref(),
+                    self.http_webhook.as_ref(),
+                    webhook,
+                    &delivery,
+                )
+                .await;
+
+                if delivery.status == DeliveryStatus::Success {
+                    tracing::info!("  ✅ Webhook delivered successfully");
+                } else {
+                    tracing::warn!("  ❌ Webhook delivery failed: {:?}", delivery.response_body);
+                }
+            }
+            Err(e) => {
+                tracing::warn!("  ❌ Webhook delivery error: {}", e);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl ReiEventSubscriber for WebhookPublisher {
+    fn name(&self) -> &str {
+        "webhooks"
+    }
+
+    async fn handle(&self, event: &ReiEvent) -> Result<(), DomainError> {
+        self.publish(event).await;
+        Ok(())
+    }
+}
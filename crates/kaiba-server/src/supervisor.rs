@@ -0,0 +1,236 @@
+//! Task Supervisor - Background loops, in-flight work and shutdown
+//!
+//! Long-running loops (scheduler, outbox relay, webhook batcher) are started
+//! with [`TaskSupervisor::supervise`]: a loop that panics or returns while
+//! the server is running is logged and restarted with backoff instead of
+//! silently dying. One-off work such as event deliveries and inbound
+//! learning runs goes through [`TaskSupervisor::spawn`], so shutdown can
+//! wait for it to finish instead of cutting a delivery off mid-request.
+
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use tokio::task::JoinError;
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
+
+/// First delay before restarting a crashed loop
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Longest delay between restarts
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+/// A loop that ran this long before failing starts over at the initial backoff
+const STABLE_AFTER: Duration = Duration::from_secs(600);
+
+/// Owns every background task and coordinates shutdown
+pub struct TaskSupervisor {
+    shutdown: CancellationToken,
+    tracker: TaskTracker,
+    initial_backoff: Duration,
+}
+
+impl Default for TaskSupervisor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TaskSupervisor {
+    pub fn new() -> Self {
+        Self {
+            shutdown: CancellationToken::new(),
+            tracker: TaskTracker::new(),
+            initial_backoff: INITIAL_BACKOFF,
+        }
+    }
+
+    /// Run a loop until shutdown, restarting it whenever it panics or exits
+    ///
+    /// The loop gets a token that is cancelled on shutdown; it should finish
+    /// the work in hand and return when it fires.
+    pub fn supervise<F, Fut>(&self, name: &'static str, task: F)
+    where
+        F: Fn(CancellationToken) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let shutdown = self.shutdown.clone();
+        let initial_backoff = self.initial_backoff;
+
+        self.tracker.spawn(async move {
+            let mut backoff = initial_backoff;
+            loop {
+                let started = Instant::now();
+                let result = tokio::spawn(task(shutdown.clone())).await;
+                if shutdown.is_cancelled() {
+                    break;
+                }
+
+                if started.elapsed() >= STABLE_AFTER {
+                    backoff = initial_backoff;
+                }
+                match result {
+                    Ok(()) => tracing::warn!(
+                        "⚠️  Background task {} exited, restarting in {:?}",
+                        name,
+                        backoff
+                    ),
+                    Err(e) => tracing::error!(
+                        "💥 Background task {} failed: {}, restarting in {:?}",
+                        name,
+                        panic_message(e),
+                        backoff
+                    ),
+                }
+
+                tokio::select! {
+                    _ = tokio::time::sleep(backoff) => {}
+                    _ = shutdown.cancelled() => break,
+                }
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+            }
+            tracing::info!("🛑 Background task {} stopped", name);
+        });
+    }
+
+    /// Run one-off work that shutdown should wait for
+    pub fn spawn<F>(&self, task: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.tracker.spawn(task);
+    }
+
+    /// Stop all loops and wait for in-flight work, at most `timeout`
+    ///
+    /// Returns `false` if some tasks were still running when time ran out.
+    pub async fn shutdown(&self, timeout: Duration) -> bool {
+        self.shutdown.cancel();
+        self.tracker.close();
+
+        tracing::info!(
+            "🛑 Waiting for {} background tasks to finish...",
+            self.tracker.len()
+        );
+        match tokio::time::timeout(timeout, self.tracker.wait()).await {
+            Ok(()) => true,
+            Err(_) => {
+                tracing::warn!(
+                    "⚠️  {} background tasks still running after {:?}, abandoning them",
+                    self.tracker.len(),
+                    timeout
+                );
+                false
+            }
+        }
+    }
+}
+
+/// Readable cause of a failed task
+fn panic_message(err: JoinError) -> String {
+    if !err.is_panic() {
+        return err.to_string();
+    }
+    let payload = err.into_panic();
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        format!("panicked: {}", message)
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        format!("panicked: {}", message)
+    } else {
+        "panicked".to_string()
+    }
+}
+
+/// Resolves on Ctrl+C or SIGTERM
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::warn!("⚠️  Failed to listen for Ctrl+C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(e) => {
+                tracing::warn!("⚠️  Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+    tracing::info!("🛑 Shutdown signal received");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn supervisor() -> TaskSupervisor {
+        TaskSupervisor {
+            initial_backoff: Duration::from_millis(10),
+            ..TaskSupervisor::new()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_restarts_after_panic() {
+        let supervisor = supervisor();
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let counter = runs.clone();
+        supervisor.supervise("flaky", move |shutdown| {
+            let counter = counter.clone();
+            async move {
+                if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+                    panic!("boom");
+                }
+                shutdown.cancelled().await;
+            }
+        });
+
+        // Panics are slow when backtraces are captured, so wait on the count
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while runs.load(Ordering::SeqCst) < 3 && Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+        assert!(supervisor.shutdown(Duration::from_secs(1)).await);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_waits_for_in_flight_work() {
+        let supervisor = supervisor();
+        let done = Arc::new(AtomicUsize::new(0));
+
+        let finished = done.clone();
+        supervisor.spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            finished.fetch_add(1, Ordering::SeqCst);
+        });
+
+        assert!(supervisor.shutdown(Duration::from_secs(1)).await);
+        assert_eq!(done.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_times_out() {
+        let supervisor = supervisor();
+        supervisor.spawn(std::future::pending());
+        assert!(!supervisor.shutdown(Duration::from_millis(20)).await);
+    }
+}
@@ -0,0 +1,90 @@
+//! Telemetry - Logs and OpenTelemetry traces
+//!
+//! Logs go to stdout as before. When `OTEL_EXPORTER_OTLP_ENDPOINT` is set,
+//! spans are also exported over OTLP (gRPC). Every request gets an
+//! `x-request-id` (kept if the client sent one) recorded on its root span,
+//! so the Postgres, embedding, Qdrant and LLM spans underneath can be
+//! attributed to it, e.g. to find out why a prompt was slow.
+
+use axum::{body::Body, http::Request};
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{runtime, trace::TracerProvider, Resource};
+use tracing::Span;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+
+const SERVICE_NAME: &str = "kaiba-server";
+
+/// Header carrying the request ID, echoed back on responses
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Install the global subscriber (`RUST_LOG` filters, default `info`)
+pub fn init(otlp_endpoint: Option<&str>) {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+
+    let provider = otlp_endpoint.map(|endpoint| (endpoint, tracer_provider(endpoint)));
+    let otel_layer = match &provider {
+        Some((_, Ok(provider))) => {
+            let tracer = provider.tracer(SERVICE_NAME);
+            opentelemetry::global::set_tracer_provider(provider.clone());
+            Some(tracing_opentelemetry::layer().with_tracer(tracer))
+        }
+        _ => None,
+    };
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer())
+        .with(otel_layer)
+        .init();
+
+    match provider {
+        Some((endpoint, Ok(_))) => tracing::info!("🔭 OpenTelemetry export to {}", endpoint),
+        Some((endpoint, Err(e))) => {
+            tracing::warn!("⚠️  OpenTelemetry export to {} disabled: {}", endpoint, e)
+        }
+        None => {}
+    }
+}
+
+fn tracer_provider(endpoint: &str) -> Result<TracerProvider, opentelemetry::trace::TraceError> {
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint)
+        .build()?;
+
+    Ok(TracerProvider::builder()
+        .with_batch_exporter(exporter, runtime::Tokio)
+        .with_resource(Resource::new(vec![KeyValue::new(
+            "service.name",
+            SERVICE_NAME,
+        )]))
+        .build())
+}
+
+/// Flush spans still buffered for export
+pub async fn shutdown() {
+    // Blocks until the batch exporter has flushed, so keep it off the runtime
+    if let Err(e) =
+        tokio::task::spawn_blocking(opentelemetry::global::shutdown_tracer_provider).await
+    {
+        tracing::warn!("⚠️  OpenTelemetry shutdown failed: {}", e);
+    }
+}
+
+/// Root span of an HTTP request
+pub fn request_span(request: &Request<Body>) -> Span {
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+
+    tracing::info_span!(
+        "request",
+        method = %request.method(),
+        path = %request.uri().path(),
+        request_id = %request_id,
+    )
+}
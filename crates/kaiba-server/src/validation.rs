@@ -0,0 +1,460 @@
+//! Request Validation - Field-level checks for create/update payloads
+//!
+//! Handlers take [`ValidJson`] instead of `Json` for payloads that implement
+//! [`Validate`]. Every failing field is reported at once in a `422`
+//! problem response, so clients can fix a form in one round trip.
+
+use async_trait::async_trait;
+use axum::{
+    extract::{FromRequest, Request},
+    Json,
+};
+use serde::de::DeserializeOwned;
+
+use crate::error::{ApiError, FieldError};
+use crate::models::{
+    CreateApiKeyRequest, CreateMemoryRequest, CreateReiRequest, CreateTaskRequest,
+    CreateTeiRequest, CreateUserRequest, CreateWebhookRequest, UpdateMemoryRequest,
+    UpdateReiRequest, UpdateReiStateRequest, UpdateTeiRequest, UpdateWebhookRequest,
+    UpsertDecisionPolicyRequest, UpsertScheduleRequest,
+};
+use crate::services::schedule::CronSchedule;
+use crate::services::{feed_source, self_learning, source_policy, tei_expertise};
+
+/// Longest accepted name for Reis, Teis, webhooks, users and API keys
+pub const MAX_NAME_LEN: usize = 100;
+
+/// Manifest keys read as lists of strings by self-learning
+const MANIFEST_LIST_KEYS: [&str; 6] = [
+    "interests",
+    "learning_topics",
+    "curiosities",
+    feed_source::FEEDS_MANIFEST_KEY,
+    source_policy::PREFERRED_MANIFEST_KEY,
+    source_policy::EXCLUDED_MANIFEST_KEY,
+];
+
+/// Manifest keys read as text by prompt generation
+const MANIFEST_TEXT_KEYS: [&str; 3] = ["personality", "instructions", "quirks"];
+
+/// Manifest keys read as on/off switches
+const MANIFEST_FLAG_KEYS: [&str; 1] = [tei_expertise::SYNC_MANIFEST_KEY];
+
+/// Caps of the manifest's daily learning budget
+const BUDGET_CAP_KEYS: [&str; 3] = ["searches", "embeddings", "tokens"];
+
+/// A payload that can check its own fields
+pub trait Validate {
+    fn validate(&self, errors: &mut Vec<FieldError>);
+}
+
+/// JSON body extractor that runs [`Validate`] after deserializing
+pub struct ValidJson<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for ValidJson<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(payload) = Json::<T>::from_request(req, state)
+            .await
+            .map_err(|rejection| ApiError::new(rejection.status(), rejection.body_text()))?;
+
+        let mut errors = Vec::new();
+        payload.validate(&mut errors);
+        if !errors.is_empty() {
+            return Err(ApiError::validation(errors));
+        }
+        Ok(Self(payload))
+    }
+}
+
+fn check_name(errors: &mut Vec<FieldError>, field: &str, name: &str) {
+    let len = name.trim().chars().count();
+    if len == 0 {
+        errors.push(FieldError::new(field, "must not be empty"));
+    } else if len > MAX_NAME_LEN {
+        errors.push(FieldError::new(
+            field,
+            format!("must be at most {} characters", MAX_NAME_LEN),
+        ));
+    }
+}
+
+fn check_url(errors: &mut Vec<FieldError>, field: &str, url: &str) {
+    match reqwest::Url::parse(url) {
+        Ok(parsed) if !matches!(parsed.scheme(), "http" | "https") => {
+            errors.push(FieldError::new(field, "must be an http or https URL"));
+        }
+        Ok(parsed) if parsed.host_str().is_none() => {
+            errors.push(FieldError::new(field, "must include a host"));
+        }
+        Ok(_) => {}
+        Err(e) => errors.push(FieldError::new(field, format!("invalid URL: {}", e))),
+    }
+}
+
+fn check_importance(errors: &mut Vec<FieldError>, importance: Option<f32>) {
+    if let Some(importance) = importance {
+        if !(0.0..=1.0).contains(&importance) {
+            errors.push(FieldError::new("importance", "must be between 0.0 and 1.0"));
+        }
+    }
+}
+
+fn check_non_negative(errors: &mut Vec<FieldError>, field: &str, value: Option<i32>) {
+    if value.is_some_and(|v| v < 0) {
+        errors.push(FieldError::new(field, "must not be negative"));
+    }
+}
+
+/// A manifest is an object; the keys Kaiba reads must have the expected shape
+fn check_manifest(errors: &mut Vec<FieldError>, manifest: Option<&serde_json::Value>) {
+    let Some(manifest) = manifest else {
+        return;
+    };
+    let Some(obj) = manifest.as_object() else {
+        errors.push(FieldError::new("manifest", "must be a JSON object"));
+        return;
+    };
+
+    for key in MANIFEST_LIST_KEYS {
+        let Some(value) = obj.get(key) else { continue };
+        let is_string_list = value
+            .as_array()
+            .is_some_and(|items| items.iter().all(|item| item.is_string()));
+        if !is_string_list {
+            errors.push(FieldError::new(
+                format!("manifest.{}", key),
+                "must be an array of strings",
+            ));
+        }
+    }
+    for key in MANIFEST_TEXT_KEYS {
+        if obj.get(key).is_some_and(|value| !value.is_string()) {
+            errors.push(FieldError::new(
+                format!("manifest.{}", key),
+                "must be a string",
+            ));
+        }
+    }
+    for key in MANIFEST_FLAG_KEYS {
+        if obj.get(key).is_some_and(|value| !value.is_boolean()) {
+            errors.push(FieldError::new(
+                format!("manifest.{}", key),
+                "must be a boolean",
+            ));
+        }
+    }
+    if let Some(budget) = obj.get(self_learning::BUDGET_MANIFEST_KEY) {
+        let field = format!("manifest.{}", self_learning::BUDGET_MANIFEST_KEY);
+        let Some(caps) = budget.as_object() else {
+            errors.push(FieldError::new(field, "must be a JSON object"));
+            return;
+        };
+        for key in BUDGET_CAP_KEYS {
+            let Some(cap) = caps.get(key) else { continue };
+            if !cap
+                .as_i64()
+                .is_some_and(|v| (0..=i32::MAX as i64).contains(&v))
+            {
+                errors.push(FieldError::new(
+                    format!("{}.{}", field, key),
+                    "must be a non-negative integer",
+                ));
+            }
+        }
+    }
+}
+
+impl Validate for CreateReiRequest {
+    fn validate(&self, errors: &mut Vec<FieldError>) {
+        check_name(errors, "name", &self.name);
+        if let Some(avatar_url) = &self.avatar_url {
+            check_url(errors, "avatar_url", avatar_url);
+        }
+        check_manifest(errors, self.manifest.as_ref());
+    }
+}
+
+impl Validate for UpdateReiRequest {
+    fn validate(&self, errors: &mut Vec<FieldError>) {
+        if let Some(name) = &self.name {
+            check_name(errors, "name", name);
+        }
+        if let Some(avatar_url) = &self.avatar_url {
+            check_url(errors, "avatar_url", avatar_url);
+        }
+        check_manifest(errors, self.manifest.as_ref());
+    }
+}
+
+impl Validate for UpdateReiStateRequest {
+    fn validate(&self, errors: &mut Vec<FieldError>) {
+        if self.energy_level.is_some_and(|e| !(0..=100).contains(&e)) {
+            errors.push(FieldError::new("energy_level", "must be between 0 and 100"));
+        }
+        check_non_negative(errors, "token_budget", self.token_budget);
+        check_non_negative(errors, "tokens_used", self.tokens_used);
+        check_non_negative(errors, "energy_regen_per_hour", self.energy_regen_per_hour);
+    }
+}
+
+impl Validate for CreateTeiRequest {
+    fn validate(&self, errors: &mut Vec<FieldError>) {
+        check_name(errors, "name", &self.name);
+        if self.model_id.trim().is_empty() {
+            errors.push(FieldError::new("model_id", "must not be empty"));
+        }
+    }
+}
+
+impl Validate for UpdateTeiRequest {
+    fn validate(&self, errors: &mut Vec<FieldError>) {
+        if let Some(name) = &self.name {
+            check_name(errors, "name", name);
+        }
+        if self.model_id.as_ref().is_some_and(|m| m.trim().is_empty()) {
+            errors.push(FieldError::new("model_id", "must not be empty"));
+        }
+    }
+}
+
+impl Validate for CreateMemoryRequest {
+    fn validate(&self, errors: &mut Vec<FieldError>) {
+        if self.content.trim().is_empty() {
+            errors.push(FieldError::new("content", "must not be empty"));
+        }
+        check_importance(errors, self.importance);
+    }
+}
+
+impl Validate for UpdateMemoryRequest {
+    fn validate(&self, errors: &mut Vec<FieldError>) {
+        if self.content.as_ref().is_some_and(|c| c.trim().is_empty()) {
+            errors.push(FieldError::new("content", "must not be empty"));
+        }
+        check_importance(errors, self.importance);
+    }
+}
+
+impl Validate for CreateWebhookRequest {
+    fn validate(&self, errors: &mut Vec<FieldError>) {
+        check_name(errors, "name", &self.name);
+        check_url(errors, "url", &self.url);
+        check_non_negative(errors, "max_retries", self.max_retries);
+        check_non_negative(errors, "timeout_ms", self.timeout_ms);
+    }
+}
+
+impl Validate for UpdateWebhookRequest {
+    fn validate(&self, errors: &mut Vec<FieldError>) {
+        if let Some(name) = &self.name {
+            check_name(errors, "name", name);
+        }
+        if let Some(url) = &self.url {
+            check_url(errors, "url", url);
+        }
+        check_non_negative(errors, "max_retries", self.max_retries);
+        check_non_negative(errors, "timeout_ms", self.timeout_ms);
+    }
+}
+
+impl Validate for UpsertScheduleRequest {
+    fn validate(&self, errors: &mut Vec<FieldError>) {
+        let timezone = self.timezone.as_deref().unwrap_or("UTC");
+        if timezone.parse::<chrono_tz::Tz>().is_err() {
+            errors.push(FieldError::new(
+                "timezone",
+                "must be an IANA timezone name (e.g. Asia/Tokyo)",
+            ));
+        } else if let Err(message) = CronSchedule::parse(&self.cron, timezone) {
+            errors.push(FieldError::new("cron", message));
+        }
+    }
+}
+
+impl Validate for CreateTaskRequest {
+    fn validate(&self, errors: &mut Vec<FieldError>) {
+        if self.instruction.trim().is_empty() {
+            errors.push(FieldError::new("instruction", "must not be empty"));
+        }
+    }
+}
+
+impl Validate for UpsertDecisionPolicyRequest {
+    fn validate(&self, errors: &mut Vec<FieldError>) {
+        for (field, energy) in [
+            ("min_energy_learn", self.min_energy_learn),
+            ("min_energy_digest", self.min_energy_digest),
+            ("min_energy_reflect", self.min_energy_reflect),
+        ] {
+            if energy.is_some_and(|e| !(0..=100).contains(&e)) {
+                errors.push(FieldError::new(field, "must be between 0 and 100"));
+            }
+        }
+        check_non_negative(errors, "min_tokens_action", self.min_tokens_action);
+        check_non_negative(errors, "memories_for_digest", self.memories_for_digest);
+        check_non_negative(
+            errors,
+            "reflection_interval_hours",
+            self.reflection_interval_hours,
+        );
+    }
+}
+
+impl Validate for CreateUserRequest {
+    fn validate(&self, errors: &mut Vec<FieldError>) {
+        check_name(errors, "name", &self.name);
+    }
+}
+
+impl Validate for CreateApiKeyRequest {
+    fn validate(&self, errors: &mut Vec<FieldError>) {
+        check_name(errors, "name", &self.name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn errors_of(payload: &impl Validate) -> Vec<String> {
+        let mut errors = Vec::new();
+        payload.validate(&mut errors);
+        errors.into_iter().map(|e| e.field).collect()
+    }
+
+    #[test]
+    fn test_rei_name_and_manifest() {
+        let rei = CreateReiRequest {
+            name: "x".repeat(MAX_NAME_LEN + 1),
+            role: "engineer".to_string(),
+            avatar_url: Some("ftp://example.com/a.png".to_string()),
+            manifest: Some(json!({
+                "interests": ["rust", 1],
+                "personality": "curious",
+                "quirks": ["not", "text"],
+                "sync_tei_expertise": "yes",
+                "learning_budget": {"searches": 10, "tokens": -5},
+                "feeds": "https://example.com/feed.xml",
+                "excluded_domains": [{"domain": "spam.example"}],
+            })),
+        };
+        assert_eq!(
+            errors_of(&rei),
+            vec![
+                "name",
+                "avatar_url",
+                "manifest.interests",
+                "manifest.feeds",
+                "manifest.excluded_domains",
+                "manifest.quirks",
+                "manifest.sync_tei_expertise",
+                "manifest.learning_budget.tokens"
+            ]
+        );
+
+        let rei = CreateReiRequest {
+            name: "shii".to_string(),
+            role: "engineer".to_string(),
+            avatar_url: None,
+            manifest: Some(
+                json!({"interests": ["rust"], "custom": 42, "sync_tei_expertise": true}),
+            ),
+        };
+        assert!(errors_of(&rei).is_empty());
+
+        let update = UpdateReiRequest {
+            name: None,
+            role: None,
+            avatar_url: None,
+            manifest: Some(json!(["not", "an", "object"])),
+        };
+        assert_eq!(errors_of(&update), vec!["manifest"]);
+
+        let update = UpdateReiRequest {
+            name: None,
+            role: None,
+            avatar_url: None,
+            manifest: Some(json!({"learning_budget": 20})),
+        };
+        assert_eq!(errors_of(&update), vec!["manifest.learning_budget"]);
+    }
+
+    #[test]
+    fn test_state_ranges() {
+        let state = UpdateReiStateRequest {
+            energy_level: Some(101),
+            mood: None,
+            token_budget: Some(-1),
+            tokens_used: Some(0),
+            energy_regen_per_hour: None,
+        };
+        assert_eq!(errors_of(&state), vec!["energy_level", "token_budget"]);
+    }
+
+    #[test]
+    fn test_memory_importance() {
+        let memory = UpdateMemoryRequest {
+            content: Some("  ".to_string()),
+            memory_type: None,
+            importance: Some(1.5),
+            tags: None,
+            metadata: None,
+        };
+        assert_eq!(errors_of(&memory), vec!["content", "importance"]);
+    }
+
+    #[test]
+    fn test_schedule() {
+        let schedule = |cron: &str, timezone: Option<&str>| UpsertScheduleRequest {
+            cron: cron.to_string(),
+            timezone: timezone.map(str::to_string),
+        };
+        assert!(errors_of(&schedule("0 7 * * 1-5", Some("Asia/Tokyo"))).is_empty());
+        assert_eq!(errors_of(&schedule("every morning", None)), vec!["cron"]);
+        assert_eq!(
+            errors_of(&schedule("0 7 * * *", Some("Tokyo"))),
+            vec!["timezone"]
+        );
+    }
+
+    #[test]
+    fn test_decision_policy() {
+        assert!(errors_of(&UpsertDecisionPolicyRequest::default()).is_empty());
+
+        let policy = UpsertDecisionPolicyRequest {
+            min_energy_learn: Some(120),
+            min_tokens_action: Some(-1),
+            memories_for_digest: Some(3),
+            reflection_interval_hours: Some(-24),
+            ..Default::default()
+        };
+        assert_eq!(
+            errors_of(&policy),
+            vec![
+                "min_energy_learn",
+                "min_tokens_action",
+                "reflection_interval_hours"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_url() {
+        let mut errors = Vec::new();
+        check_url(&mut errors, "url", "https://example.com/hook");
+        check_url(&mut errors, "url", "http://localhost:8080");
+        assert!(errors.is_empty());
+
+        check_url(&mut errors, "url", "example.com/hook");
+        check_url(&mut errors, "url", "mailto:ops@example.com");
+        assert_eq!(errors.len(), 2);
+    }
+}
@@ -0,0 +1,49 @@
+//! ReiEvent - Something that happened to a Rei
+//!
+//! Published on the event bus by routes and services. Subscribers
+//! (webhooks, platform integrations, stats counters) react to the
+//! same stream instead of each producer notifying them directly.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{WebhookEventType, WebhookPayload};
+
+/// Domain event emitted for a Rei
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReiEvent {
+    /// Unique ID for this event
+    pub id: Uuid,
+    /// Rei the event belongs to
+    pub rei_id: Uuid,
+    /// Event type (shared with webhook subscriptions)
+    pub event_type: WebhookEventType,
+    /// Event-specific data
+    pub data: serde_json::Value,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl ReiEvent {
+    /// Create a new event
+    pub fn new(rei_id: Uuid, event_type: WebhookEventType, data: serde_json::Value) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            rei_id,
+            event_type,
+            data,
+            occurred_at: Utc::now(),
+        }
+    }
+
+    /// Convert to a webhook payload (delivery_id = event id)
+    pub fn to_webhook_payload(&self) -> WebhookPayload {
+        WebhookPayload {
+            delivery_id: self.id,
+            event: self.event_type.clone(),
+            rei_id: self.rei_id,
+            timestamp: self.occurred_at,
+            data: self.data.clone(),
+        }
+    }
+}
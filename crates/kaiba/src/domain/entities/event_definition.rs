@@ -0,0 +1,81 @@
+//! EventDefinition - A registered custom webhook event
+//!
+//! Custom events (`custom:<name>`) must be declared before webhooks can
+//! subscribe to them or clients can trigger them. The definition documents
+//! the event and holds the schema its data is validated against.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::WebhookEventType;
+use crate::domain::value_objects::EventSchema;
+
+/// Names used internally that cannot be registered
+const RESERVED_NAMES: [&str; 2] = ["batch", "test"];
+
+/// Maximum length of a custom event name
+const MAX_NAME_LEN: usize = 64;
+
+/// Declared custom event type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventDefinition {
+    /// Event name without the `custom:` prefix (e.g., "deploy_finished")
+    pub name: String,
+    pub description: Option<String>,
+    /// Schema the event data must satisfy
+    pub schema: EventSchema,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl EventDefinition {
+    /// Create a definition that accepts any data
+    pub fn new(name: String) -> Self {
+        let now = Utc::now();
+        Self {
+            name,
+            description: None,
+            schema: EventSchema::default(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn with_description(mut self, description: String) -> Self {
+        self.description = Some(description);
+        self
+    }
+
+    pub fn with_schema(mut self, schema: EventSchema) -> Self {
+        self.schema = schema;
+        self
+    }
+
+    /// The event type webhooks subscribe to
+    pub fn event_type(&self) -> WebhookEventType {
+        WebhookEventType::Custom(self.name.clone())
+    }
+
+    /// Check a custom event name: lowercase letters, digits, `_`, `-` and `.`
+    pub fn validate_name(name: &str) -> Result<(), String> {
+        if name.is_empty() || name.len() > MAX_NAME_LEN {
+            return Err(format!(
+                "Event name must be 1-{} characters long",
+                MAX_NAME_LEN
+            ));
+        }
+        if !name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "_-.".contains(c))
+        {
+            return Err(format!(
+                "Invalid event name '{}': use lowercase letters, digits, '_', '-' or '.'",
+                name
+            ));
+        }
+        if RESERVED_NAMES.contains(&name) {
+            return Err(format!("Event name '{}' is reserved", name));
+        }
+        Ok(())
+    }
+}
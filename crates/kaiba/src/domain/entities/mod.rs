@@ -8,8 +8,12 @@
 //! - Prompt: Prompt templates
 //! - Message: Platform integration message
 //! - Webhook: Outbound webhook for external actions
+//! - Event: Domain events published on the event bus
+//! - EventDefinition: Registered custom webhook events
 
 mod call;
+mod event;
+mod event_definition;
 mod memory;
 mod message;
 mod prompt;
@@ -18,6 +22,8 @@ mod tei;
 mod webhook;
 
 pub use call::*;
+pub use event::*;
+pub use event_definition::*;
 pub use memory::*;
 pub use message::*;
 pub use prompt::*;
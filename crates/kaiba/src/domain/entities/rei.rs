@@ -35,6 +35,8 @@ pub struct ReiState {
     pub last_digest_at: Option<DateTime<Utc>>,
     /// Last time Learn was completed (for dashboard)
     pub last_learn_at: Option<DateTime<Utc>>,
+    /// Whether the scheduler and trigger act for this Rei (false = paused)
+    pub autonomy_enabled: bool,
 }
 
 impl Rei {
@@ -73,6 +75,7 @@ impl ReiState {
             energy_regen_per_hour: 10,
             last_digest_at: None,
             last_learn_at: None,
+            autonomy_enabled: true,
         }
     }
 
@@ -90,6 +93,7 @@ impl ReiState {
             energy_regen_per_hour: 10,
             last_digest_at: None,
             last_learn_at: None,
+            autonomy_enabled: true,
         }
     }
 }
@@ -11,6 +11,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::domain::value_objects::WebhookCondition;
+
 /// Webhook configuration for a Rei
 ///
 /// Represents an outbound webhook endpoint that Rei can use
@@ -18,7 +20,8 @@ use uuid::Uuid;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReiWebhook {
     pub id: Uuid,
-    pub rei_id: Uuid,
+    /// Owning Rei (None = global webhook receiving events from all Reis)
+    pub rei_id: Option<Uuid>,
     /// Human-readable name for this webhook
     pub name: String,
     /// Target URL for webhook delivery
@@ -29,20 +32,94 @@ pub struct ReiWebhook {
     pub enabled: bool,
     /// Event types this webhook subscribes to
     pub events: Vec<WebhookEventType>,
+    /// Data predicates that must all match for delivery (empty = always deliver)
+    #[serde(default)]
+    pub conditions: Vec<WebhookCondition>,
     /// Custom headers to include (e.g., Authorization)
     #[serde(default)]
     pub headers: serde_json::Value,
-    /// Payload format transformation (e.g., "github_issue")
+    /// Payload format transformation (e.g., "github_issue", "jira_issue", "linear_issue", "discord_embed")
     #[serde(default)]
     pub payload_format: Option<String>,
+    /// Format-specific options (e.g., GitHub labels/assignees for "github_issue")
+    #[serde(default)]
+    pub format_options: serde_json::Value,
+    /// User-defined payload template (Jinja syntax, must render to JSON).
+    /// Takes precedence over payload_format when set.
+    #[serde(default)]
+    pub payload_template: Option<String>,
     /// Retry configuration
     pub max_retries: i32,
     /// Timeout in milliseconds
     pub timeout_ms: i32,
+    /// Failed (dead-lettered) deliveries in a row since the last success
+    #[serde(default)]
+    pub consecutive_failures: i32,
+    /// Auto-disable after this many consecutive failures (None = never)
+    #[serde(default)]
+    pub max_consecutive_failures: Option<i32>,
+    /// Maximum deliveries per minute; excess deliveries wait their turn (None = unlimited)
+    #[serde(default)]
+    pub rate_limit_per_minute: Option<i32>,
+    /// Deliver each event immediately, or accumulate and send them together
+    #[serde(default)]
+    pub delivery_mode: WebhookDeliveryMode,
+    /// Minutes between batched deliveries (only used in batched mode)
+    #[serde(default = "default_batch_interval_minutes")]
+    pub batch_interval_minutes: i32,
+    /// Custom CA / client certificate for receivers behind private CAs
+    #[serde(default)]
+    pub tls: Option<WebhookTlsConfig>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+fn default_batch_interval_minutes() -> i32 {
+    15
+}
+
+/// TLS settings for deliveries into internal networks
+///
+/// Values may be `${secret:name}` references, so private keys need not be
+/// stored with the webhook.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WebhookTlsConfig {
+    /// Additional root CA certificate to trust (PEM)
+    #[serde(default)]
+    pub ca_cert_pem: Option<String>,
+    /// Client certificate chain for mutual TLS (PEM)
+    #[serde(default)]
+    pub client_cert_pem: Option<String>,
+    /// Client private key for mutual TLS (PKCS#8 PEM)
+    #[serde(default)]
+    pub client_key_pem: Option<String>,
+}
+
+impl WebhookTlsConfig {
+    /// Whether nothing is configured
+    pub fn is_empty(&self) -> bool {
+        self.ca_cert_pem.is_none()
+            && self.client_cert_pem.is_none()
+            && self.client_key_pem.is_none()
+    }
+
+    /// Whether a client identity (mutual TLS) is configured
+    pub fn has_client_identity(&self) -> bool {
+        self.client_cert_pem.is_some() || self.client_key_pem.is_some()
+    }
+}
+
+/// How a webhook receives events
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookDeliveryMode {
+    /// One delivery per event
+    #[default]
+    Immediate,
+    /// Events are queued and delivered as a single payload every N minutes
+    Batched,
+}
+
 /// Types of events that can trigger webhooks
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -59,6 +136,8 @@ pub enum WebhookEventType {
     LearningCompleted,
     /// Digest completed - knowledge consolidated into expertise (知識統合完了)
     DigestCompleted,
+    /// Scheduled task finished (successfully or not)
+    TaskCompleted,
     /// Custom event (user-defined)
     Custom(String),
     /// All events
@@ -90,6 +169,9 @@ pub struct WebhookDelivery {
     pub status_code: Option<i32>,
     pub response_body: Option<String>,
     pub attempts: i32,
+    /// Response time of the last attempt in milliseconds
+    #[serde(default)]
+    pub duration_ms: Option<i32>,
     pub created_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
 }
@@ -101,6 +183,8 @@ pub enum DeliveryStatus {
     Success,
     Failed,
     Retrying,
+    /// Retries exhausted - kept for inspection and replay (dead-letter)
+    Dead,
 }
 
 impl ReiWebhook {
@@ -110,27 +194,61 @@ impl ReiWebhook {
         let now = Utc::now();
         Self {
             id: Uuid::new_v4(),
-            rei_id,
+            rei_id: Some(rei_id),
             name,
             url,
             secret: None,
             enabled: true,
             events: vec![WebhookEventType::DigestCompleted],
+            conditions: Vec::new(),
             headers: serde_json::json!({}),
             payload_format: None,
+            format_options: serde_json::json!({}),
+            payload_template: None,
             max_retries: 3,
             timeout_ms: 30000,
+            consecutive_failures: 0,
+            max_consecutive_failures: None,
+            rate_limit_per_minute: None,
+            delivery_mode: WebhookDeliveryMode::Immediate,
+            batch_interval_minutes: default_batch_interval_minutes(),
+            tls: None,
             created_at: now,
             updated_at: now,
         }
     }
 
+    /// Create a global webhook that receives events from every Rei
+    pub fn global(name: String, url: String) -> Self {
+        Self {
+            rei_id: None,
+            ..Self::new(Uuid::nil(), name, url)
+        }
+    }
+
+    /// Whether this webhook is not scoped to a single Rei
+    pub fn is_global(&self) -> bool {
+        self.rei_id.is_none()
+    }
+
     /// Set payload format (e.g., "github_issue")
     pub fn with_payload_format(mut self, format: String) -> Self {
         self.payload_format = Some(format);
         self
     }
 
+    /// Set format-specific options
+    pub fn with_format_options(mut self, options: serde_json::Value) -> Self {
+        self.format_options = options;
+        self
+    }
+
+    /// Set a user-defined payload template
+    pub fn with_payload_template(mut self, template: String) -> Self {
+        self.payload_template = Some(template);
+        self
+    }
+
     /// Create with a signing secret for HMAC-SHA256 verification
     pub fn with_secret(mut self, secret: String) -> Self {
         self.secret = Some(secret);
@@ -143,6 +261,12 @@ impl ReiWebhook {
         self
     }
 
+    /// Set data predicates that payloads must satisfy
+    pub fn with_conditions(mut self, conditions: Vec<WebhookCondition>) -> Self {
+        self.conditions = conditions;
+        self
+    }
+
     /// Add custom headers
     pub fn with_headers(mut self, headers: serde_json::Value) -> Self {
         self.headers = headers;
@@ -156,6 +280,22 @@ impl ReiWebhook {
         }
         self.events.contains(&WebhookEventType::All) || self.events.contains(event)
     }
+
+    /// Check if the consecutive failure threshold has been reached
+    pub fn should_auto_disable(&self, consecutive_failures: i32) -> bool {
+        self.max_consecutive_failures
+            .is_some_and(|max| max > 0 && consecutive_failures >= max)
+    }
+
+    /// Check if a payload's data satisfies all configured conditions
+    pub fn matches_conditions(&self, data: &serde_json::Value) -> bool {
+        self.conditions.iter().all(|c| c.matches(data))
+    }
+
+    /// Check both event subscription and data conditions
+    pub fn should_deliver(&self, payload: &WebhookPayload) -> bool {
+        self.should_receive(&payload.event) && self.matches_conditions(&payload.data)
+    }
 }
 
 impl WebhookPayload {
@@ -169,6 +309,38 @@ impl WebhookPayload {
             data,
         }
     }
+
+    /// Combine queued events into one `custom:batch` payload
+    ///
+    /// Events are listed oldest first under `data.events`. Global webhooks
+    /// batch events from several Reis, so each entry keeps its own rei_id.
+    pub fn batch(rei_id: Uuid, mut events: Vec<WebhookPayload>) -> Self {
+        events.sort_by_key(|e| e.timestamp);
+
+        let entries: Vec<serde_json::Value> = events
+            .iter()
+            .map(|e| {
+                serde_json::json!({
+                    "delivery_id": e.delivery_id,
+                    "rei_id": e.rei_id,
+                    "event": e.event.to_string(),
+                    "timestamp": e.timestamp,
+                    "data": e.data,
+                })
+            })
+            .collect();
+
+        Self::new(
+            WebhookEventType::Custom("batch".to_string()),
+            rei_id,
+            serde_json::json!({
+                "count": entries.len(),
+                "from": events.first().map(|e| e.timestamp),
+                "to": events.last().map(|e| e.timestamp),
+                "events": entries,
+            }),
+        )
+    }
 }
 
 impl WebhookDelivery {
@@ -182,11 +354,18 @@ impl WebhookDelivery {
             status_code: None,
             response_body: None,
             attempts: 0,
+            duration_ms: None,
             created_at: Utc::now(),
             completed_at: None,
         }
     }
 
+    /// Record the response time of the current attempt
+    pub fn with_duration(mut self, duration: std::time::Duration) -> Self {
+        self.duration_ms = Some(duration.as_millis().min(i32::MAX as u128) as i32);
+        self
+    }
+
     /// Mark as successful
     pub fn success(mut self, status_code: i32, response_body: Option<String>) -> Self {
         self.status = DeliveryStatus::Success;
@@ -213,6 +392,113 @@ impl WebhookDelivery {
         self.attempts += 1;
         self
     }
+
+    /// Move to the dead-letter state after all retries are exhausted
+    pub fn dead_letter(mut self) -> Self {
+        self.status = DeliveryStatus::Dead;
+        self.completed_at = Some(Utc::now());
+        self
+    }
+}
+
+/// Retention policy for delivery records
+///
+/// In-flight deliveries (pending, retrying) are never pruned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeliveryRetention {
+    /// Delete deliveries older than this many days (None = keep forever)
+    pub max_age_days: Option<i32>,
+    /// Keep at most this many recent deliveries per webhook (None = unlimited)
+    pub max_per_webhook: Option<i64>,
+}
+
+impl Default for DeliveryRetention {
+    fn default() -> Self {
+        Self {
+            max_age_days: Some(30),
+            max_per_webhook: Some(1000),
+        }
+    }
+}
+
+impl DeliveryRetention {
+    /// Whether any limit is configured
+    pub fn is_enabled(&self) -> bool {
+        self.max_age_days.is_some() || self.max_per_webhook.is_some()
+    }
+}
+
+/// Upper bounds (exclusive, ms) of the delivery latency histogram buckets
+pub const LATENCY_BUCKETS_MS: [i32; 5] = [100, 250, 500, 1000, 5000];
+
+/// Delivery count for one latency histogram bucket
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LatencyBucket {
+    /// Exclusive upper bound in ms (None = overflow bucket)
+    pub lt_ms: Option<i32>,
+    pub count: i64,
+}
+
+/// Aggregated delivery metrics for a webhook
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WebhookDeliveryStats {
+    pub total: i64,
+    pub successful: i64,
+    /// Failed or dead-lettered deliveries
+    pub failed: i64,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub last_failure_at: Option<DateTime<Utc>>,
+    pub latency_p50_ms: Option<f64>,
+    pub latency_p95_ms: Option<f64>,
+    pub latency_p99_ms: Option<f64>,
+    pub latency_histogram: Vec<LatencyBucket>,
+}
+
+impl WebhookDeliveryStats {
+    /// Fraction of finished deliveries that succeeded (None if none finished)
+    pub fn success_rate(&self) -> Option<f64> {
+        let finished = self.successful + self.failed;
+        (finished > 0).then(|| self.successful as f64 / finished as f64)
+    }
+}
+
+impl std::str::FromStr for DeliveryStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "pending" => Ok(DeliveryStatus::Pending),
+            "success" => Ok(DeliveryStatus::Success),
+            "failed" => Ok(DeliveryStatus::Failed),
+            "retrying" => Ok(DeliveryStatus::Retrying),
+            "dead" => Ok(DeliveryStatus::Dead),
+            _ => Err(format!("Unknown delivery status: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for WebhookDeliveryMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Immediate => write!(f, "immediate"),
+            Self::Batched => write!(f, "batched"),
+        }
+    }
+}
+
+impl std::str::FromStr for WebhookDeliveryMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "immediate" => Ok(Self::Immediate),
+            "batched" => Ok(Self::Batched),
+            _ => Err(format!(
+                "Unknown delivery mode: {} (expected immediate or batched)",
+                s
+            )),
+        }
+    }
 }
 
 impl std::fmt::Display for WebhookEventType {
@@ -224,6 +510,7 @@ impl std::fmt::Display for WebhookEventType {
             Self::SearchCompleted => write!(f, "search_completed"),
             Self::LearningCompleted => write!(f, "learning_completed"),
             Self::DigestCompleted => write!(f, "digest_completed"),
+            Self::TaskCompleted => write!(f, "task_completed"),
             Self::Custom(name) => write!(f, "custom:{}", name),
             Self::All => write!(f, "all"),
         }
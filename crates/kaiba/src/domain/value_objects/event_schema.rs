@@ -0,0 +1,246 @@
+//! EventSchema - Payload schema for a registered custom event
+//!
+//! A JSON Schema subset: `type`, `properties`, `required`, `items`, `enum`
+//! and `additionalProperties: false`, plus the annotation keywords
+//! `title`, `description` and `examples`. Enough to document and check
+//! the data of user-defined events without a full JSON Schema validator.
+
+use serde::{Deserialize, Serialize};
+
+/// Keywords accepted in a schema object
+const KEYWORDS: [&str; 9] = [
+    "type",
+    "properties",
+    "required",
+    "items",
+    "enum",
+    "additionalProperties",
+    "title",
+    "description",
+    "examples",
+];
+
+/// Values accepted for `type`
+const TYPES: [&str; 7] = [
+    "object", "array", "string", "number", "integer", "boolean", "null",
+];
+
+/// Validated schema for custom event data (`{}` accepts anything)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(transparent)]
+pub struct EventSchema(serde_json::Value);
+
+impl Default for EventSchema {
+    fn default() -> Self {
+        Self(serde_json::json!({}))
+    }
+}
+
+impl EventSchema {
+    /// Check that a schema only uses supported keywords
+    pub fn parse(schema: serde_json::Value) -> Result<Self, String> {
+        check_schema(&schema, "$")?;
+        Ok(Self(schema))
+    }
+
+    pub fn as_value(&self) -> &serde_json::Value {
+        &self.0
+    }
+
+    /// Validate event data, reporting the first mismatch with its path
+    pub fn validate(&self, data: &serde_json::Value) -> Result<(), String> {
+        validate_at(&self.0, data, "$")
+    }
+}
+
+fn check_schema(schema: &serde_json::Value, path: &str) -> Result<(), String> {
+    let Some(obj) = schema.as_object() else {
+        return Err(format!("{}: schema must be an object", path));
+    };
+
+    for (key, value) in obj {
+        match key.as_str() {
+            "type" => {
+                let names: Vec<&serde_json::Value> = match value {
+                    serde_json::Value::Array(names) => names.iter().collect(),
+                    other => vec![other],
+                };
+                for name in names {
+                    if !name.as_str().is_some_and(|n| TYPES.contains(&n)) {
+                        return Err(format!("{}: unsupported type {}", path, name));
+                    }
+                }
+            }
+            "properties" => {
+                let props = value
+                    .as_object()
+                    .ok_or_else(|| format!("{}: properties must be an object", path))?;
+                for (name, prop) in props {
+                    check_schema(prop, &format!("{}.{}", path, name))?;
+                }
+            }
+            "required" => {
+                if !value
+                    .as_array()
+                    .is_some_and(|names| names.iter().all(|n| n.is_string()))
+                {
+                    return Err(format!("{}: required must be an array of strings", path));
+                }
+            }
+            "items" => check_schema(value, &format!("{}[]", path))?,
+            "enum" => {
+                if !value.is_array() {
+                    return Err(format!("{}: enum must be an array", path));
+                }
+            }
+            "additionalProperties" => {
+                if !value.is_boolean() {
+                    return Err(format!("{}: additionalProperties must be a boolean", path));
+                }
+            }
+            _ if KEYWORDS.contains(&key.as_str()) => {}
+            other => return Err(format!("{}: unsupported keyword '{}'", path, other)),
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_at(
+    schema: &serde_json::Value,
+    data: &serde_json::Value,
+    path: &str,
+) -> Result<(), String> {
+    if let Some(expected) = schema.get("type") {
+        let matches = match expected {
+            serde_json::Value::Array(names) => names.iter().any(|n| type_matches(n, data)),
+            name => type_matches(name, data),
+        };
+        if !matches {
+            return Err(format!("{}: expected {}, got {}", path, expected, data));
+        }
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(|v| v.as_array()) {
+        if !allowed.contains(data) {
+            return Err(format!("{}: {} is not one of {:?}", path, data, allowed));
+        }
+    }
+
+    if let Some(obj) = data.as_object() {
+        let required: Vec<&str> = schema
+            .get("required")
+            .and_then(|v| v.as_array())
+            .map(|names| names.iter().filter_map(|n| n.as_str()).collect())
+            .unwrap_or_default();
+        for name in required {
+            if !obj.contains_key(name) {
+                return Err(format!("{}: missing required field '{}'", path, name));
+            }
+        }
+
+        let properties = schema.get("properties").and_then(|v| v.as_object());
+        for (name, value) in obj {
+            let field_path = format!("{}.{}", path, name);
+            match properties.and_then(|props| props.get(name)) {
+                Some(prop) => validate_at(prop, value, &field_path)?,
+                None if schema.get("additionalProperties") == Some(&serde_json::json!(false)) => {
+                    return Err(format!("{}: unexpected field", field_path));
+                }
+                None => {}
+            }
+        }
+    }
+
+    if let (Some(items), Some(arr)) = (schema.get("items"), data.as_array()) {
+        for (i, item) in arr.iter().enumerate() {
+            validate_at(items, item, &format!("{}[{}]", path, i))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn type_matches(name: &serde_json::Value, data: &serde_json::Value) -> bool {
+    match name.as_str() {
+        Some("object") => data.is_object(),
+        Some("array") => data.is_array(),
+        Some("string") => data.is_string(),
+        Some("number") => data.is_number(),
+        Some("integer") => {
+            data.is_i64() || data.is_u64() || data.as_f64().is_some_and(|f| f.fract() == 0.0)
+        }
+        Some("boolean") => data.is_boolean(),
+        Some("null") => data.is_null(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert!(EventSchema::parse(serde_json::json!({})).is_ok());
+        assert!(EventSchema::parse(serde_json::json!({
+            "type": "object",
+            "properties": { "tags": { "type": "array", "items": { "type": "string" } } }
+        }))
+        .is_ok());
+        assert!(EventSchema::parse(serde_json::json!({ "type": "date" })).is_err());
+        assert!(EventSchema::parse(serde_json::json!({ "oneOf": [] })).is_err());
+        assert!(EventSchema::parse(serde_json::json!({
+            "properties": { "x": { "minimum": 1 } }
+        }))
+        .is_err());
+        assert!(EventSchema::parse(serde_json::json!("object")).is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let schema = EventSchema::parse(serde_json::json!({
+            "type": "object",
+            "required": ["version"],
+            "additionalProperties": false,
+            "properties": {
+                "version": { "type": "string" },
+                "env": { "enum": ["staging", "production"] },
+                "build": { "type": "integer" },
+                "services": { "type": "array", "items": { "type": "string" } }
+            }
+        }))
+        .unwrap();
+
+        assert!(schema
+            .validate(&serde_json::json!({
+                "version": "1.2.0",
+                "env": "production",
+                "build": 42,
+                "services": ["api", "worker"]
+            }))
+            .is_ok());
+
+        let err = schema.validate(&serde_json::json!({})).unwrap_err();
+        assert!(err.contains("missing required field 'version'"));
+
+        let err = schema
+            .validate(&serde_json::json!({ "version": "1", "services": ["api", 3] }))
+            .unwrap_err();
+        assert!(err.starts_with("$.services[1]"));
+
+        assert!(schema
+            .validate(&serde_json::json!({ "version": "1", "env": "dev" }))
+            .is_err());
+        assert!(schema
+            .validate(&serde_json::json!({ "version": "1", "build": 1.5 }))
+            .is_err());
+        assert!(schema
+            .validate(&serde_json::json!({ "version": "1", "extra": true }))
+            .is_err());
+
+        assert!(EventSchema::default()
+            .validate(&serde_json::json!([1, "anything"]))
+            .is_ok());
+    }
+}
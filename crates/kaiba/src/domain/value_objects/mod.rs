@@ -2,10 +2,14 @@
 //!
 //! Immutable objects defined by their attributes rather than identity.
 
+mod event_schema;
 mod memory_type;
 mod provider;
 mod tag_match_mode;
+mod webhook_condition;
 
+pub use event_schema::*;
 pub use memory_type::*;
 pub use provider::*;
 pub use tag_match_mode::*;
+pub use webhook_condition::*;
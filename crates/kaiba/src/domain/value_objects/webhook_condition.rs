@@ -0,0 +1,163 @@
+//! WebhookCondition - Predicate evaluated against webhook payload data
+//!
+//! Lets a webhook suppress events whose data does not match, e.g.
+//! `importance >= 0.8` or `memory_type == "expertise"`.
+
+use serde::{Deserialize, Serialize};
+
+/// Comparison operator for a webhook condition
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConditionOp {
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    /// String contains substring, or array contains element
+    Contains,
+}
+
+/// A single `field op value` predicate
+///
+/// `field` is a dot-separated path into payload data (e.g., "memory.importance").
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WebhookCondition {
+    pub field: String,
+    pub op: ConditionOp,
+    pub value: serde_json::Value,
+}
+
+/// Supported operators (two-char operators before their one-char prefixes)
+const OPERATORS: [(&str, ConditionOp); 7] = [
+    (">=", ConditionOp::Gte),
+    ("<=", ConditionOp::Lte),
+    ("!=", ConditionOp::Ne),
+    ("==", ConditionOp::Eq),
+    (">", ConditionOp::Gt),
+    ("<", ConditionOp::Lt),
+    (" contains ", ConditionOp::Contains),
+];
+
+impl WebhookCondition {
+    /// Evaluate this condition against payload data
+    ///
+    /// A missing field never matches (except for `!=`).
+    pub fn matches(&self, data: &serde_json::Value) -> bool {
+        let Some(actual) = self
+            .field
+            .split('.')
+            .try_fold(data, |current, key| current.get(key))
+        else {
+            return self.op == ConditionOp::Ne;
+        };
+
+        match self.op {
+            ConditionOp::Eq => actual == &self.value,
+            ConditionOp::Ne => actual != &self.value,
+            ConditionOp::Gt | ConditionOp::Gte | ConditionOp::Lt | ConditionOp::Lte => {
+                let (Some(a), Some(b)) = (actual.as_f64(), self.value.as_f64()) else {
+                    return false;
+                };
+                match self.op {
+                    ConditionOp::Gt => a > b,
+                    ConditionOp::Gte => a >= b,
+                    ConditionOp::Lt => a < b,
+                    _ => a <= b,
+                }
+            }
+            ConditionOp::Contains => match (actual, &self.value) {
+                (serde_json::Value::String(s), serde_json::Value::String(needle)) => {
+                    s.contains(needle.as_str())
+                }
+                (serde_json::Value::Array(items), needle) => items.contains(needle),
+                _ => false,
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for ConditionOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConditionOp::Eq => write!(f, "=="),
+            ConditionOp::Ne => write!(f, "!="),
+            ConditionOp::Gt => write!(f, ">"),
+            ConditionOp::Gte => write!(f, ">="),
+            ConditionOp::Lt => write!(f, "<"),
+            ConditionOp::Lte => write!(f, "<="),
+            ConditionOp::Contains => write!(f, "contains"),
+        }
+    }
+}
+
+impl std::fmt::Display for WebhookCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.field, self.op, self.value)
+    }
+}
+
+impl std::str::FromStr for WebhookCondition {
+    type Err = String;
+
+    /// Parse an expression like `importance >= 0.8` or `memory_type == "expertise"`
+    ///
+    /// The right-hand side is parsed as JSON; bare words fall back to strings.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Leftmost operator wins; ties go to the earlier (longer) entry
+        let (pos, symbol, op) = OPERATORS
+            .iter()
+            .filter_map(|(symbol, op)| s.find(symbol).map(|pos| (pos, *symbol, *op)))
+            .min_by_key(|(pos, _, _)| *pos)
+            .ok_or_else(|| format!("No operator in condition: {}", s))?;
+
+        let field = s[..pos].trim();
+        let raw_value = s[pos + symbol.len()..].trim();
+
+        if field.is_empty() || raw_value.is_empty() {
+            return Err(format!("Invalid condition: {}", s));
+        }
+
+        let value = serde_json::from_str(raw_value)
+            .unwrap_or_else(|_| serde_json::Value::String(raw_value.to_string()));
+
+        Ok(Self {
+            field: field.to_string(),
+            op,
+            value,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_match() {
+        let data = serde_json::json!({
+            "importance": 0.9,
+            "memory_type": "expertise",
+            "memory": { "tags": ["rust", "async"] }
+        });
+
+        let cond: WebhookCondition = "importance >= 0.8".parse().unwrap();
+        assert_eq!(cond.op, ConditionOp::Gte);
+        assert!(cond.matches(&data));
+
+        let cond: WebhookCondition = r#"memory_type == "expertise""#.parse().unwrap();
+        assert!(cond.matches(&data));
+
+        let cond: WebhookCondition = "memory_type == fact".parse().unwrap();
+        assert!(!cond.matches(&data));
+
+        let cond: WebhookCondition = r#"memory.tags contains "rust""#.parse().unwrap();
+        assert!(cond.matches(&data));
+
+        let cond: WebhookCondition = "missing > 1".parse().unwrap();
+        assert!(!cond.matches(&data));
+
+        assert!("importance".parse::<WebhookCondition>().is_err());
+    }
+}
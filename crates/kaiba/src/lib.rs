@@ -7,7 +7,7 @@
 //! This crate follows Clean Architecture / Hexagonal Architecture principles:
 //!
 //! - **Domain Layer** (`domain/`): Pure business entities and logic
-//!   - `entities/`: Core domain models (Rei, Tei, Memory, Call, Prompt)
+//!   - `entities/`: Core domain models (Rei, Tei, Memory, Call, Prompt, ReiEvent)
 //!   - `value_objects/`: Immutable value types (MemoryType, TagMatchMode)
 //!   - `errors/`: Domain-specific error types
 //!
@@ -27,9 +27,11 @@ pub mod ports;
 
 // Re-export commonly used types
 pub use domain::{
-    Call, DeliveryStatus, DomainError, Memory, MemoryType, Message, Prompt, Provider, Rei,
-    ReiState, ReiTei, ReiWebhook, TagMatchMode, Tei, WebhookDelivery, WebhookEventType,
-    WebhookPayload,
+    Call, ConditionOp, DeliveryRetention, DeliveryStatus, DomainError, EventDefinition,
+    EventSchema, LatencyBucket, Memory, MemoryType, Message, Prompt, Provider, Rei, ReiEvent,
+    ReiState, ReiTei, ReiWebhook, TagMatchMode, Tei, WebhookCondition, WebhookDelivery,
+    WebhookDeliveryMode, WebhookDeliveryStats, WebhookEventType, WebhookPayload, WebhookTlsConfig,
+    LATENCY_BUCKETS_MS,
 };
 pub use ports::{
     // Tei Services (体 - execution interfaces)
@@ -39,10 +41,13 @@ pub use ports::{
     EmbeddingService,
     IntegrationConfig,
     IntegrationEvent,
+    IntegrationEventHandler,
     // Repositories
     MemoryRepository,
     MemorySearchFilter,
     MessageRole,
+    ReiEventBus,
+    ReiEventSubscriber,
     ReiRepository,
     ReiWebhookRepository,
     TeiIntegration,
@@ -50,7 +55,10 @@ pub use ports::{
     TeiRepository,
     TeiWebhook,
     TokenUsage,
+    VerificationError,
     WebSearchResult,
     WebSearchService,
     WebhookDeliveryConfig,
+    WebhookHeaders,
+    WebhookVerifier,
 };
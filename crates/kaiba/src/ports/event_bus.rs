@@ -0,0 +1,52 @@
+//! Event Bus Port
+//!
+//! Abstract interface for publishing ReiEvents to in-process subscribers.
+//! Producers (routes, services) publish once; webhooks, platform
+//! integrations and stats counters subscribe to the same stream.
+
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::domain::entities::ReiEvent;
+use crate::domain::errors::DomainError;
+
+/// Reacts to events published on a ReiEventBus
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use kaiba::ports::ReiEventSubscriber;
+///
+/// struct AuditLog;
+///
+/// #[async_trait]
+/// impl ReiEventSubscriber for AuditLog {
+///     fn name(&self) -> &str { "audit_log" }
+///
+///     async fn handle(&self, event: &ReiEvent) -> Result<(), DomainError> {
+///         // Persist the event
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait ReiEventSubscriber: Send + Sync {
+    /// Subscriber name (for logging)
+    fn name(&self) -> &str;
+
+    /// Filter events before dispatch (default: all events)
+    fn interested_in(&self, _event: &ReiEvent) -> bool {
+        true
+    }
+
+    /// Handle a single event
+    async fn handle(&self, event: &ReiEvent) -> Result<(), DomainError>;
+}
+
+/// Publishes events to registered subscribers
+pub trait ReiEventBus: Send + Sync {
+    /// Register a subscriber
+    fn subscribe(&self, subscriber: Arc<dyn ReiEventSubscriber>);
+
+    /// Publish an event (fire-and-forget; subscribers run asynchronously)
+    fn publish(&self, event: ReiEvent);
+}
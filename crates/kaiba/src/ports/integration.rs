@@ -6,8 +6,11 @@
 //! Implementations of this trait should live in separate crates
 //! (e.g., kaiba-integration-discord, kaiba-integration-slack).
 
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 use uuid::Uuid;
 
 use crate::domain::entities::{Message, Rei};
@@ -68,6 +71,94 @@ pub trait TeiIntegration: Send + Sync {
     }
 }
 
+/// Handles events that an integration routed to a Rei
+///
+/// Long-running integrations (e.g. a Discord gateway connection) receive
+/// events themselves and hand them to the server through this trait; the
+/// returned text, if any, is posted back where the event came from.
+#[async_trait]
+pub trait IntegrationEventHandler: Send + Sync {
+    /// Handle an event addressed to `rei`, returning the reply to post
+    async fn handle(
+        &self,
+        rei: &Rei,
+        event: IntegrationEvent,
+    ) -> Result<Option<String>, DomainError>;
+}
+
+/// Verifies that an inbound webhook was sent by its platform
+///
+/// Platforms sign payloads differently: GitHub with an HMAC-SHA256 of the
+/// body, Discord with an ed25519 signature over a timestamp and the body,
+/// Telegram with a shared secret header. Each integration implements
+/// its scheme here, so the server checks every inbound route the same way
+/// before parsing the payload.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let headers = WebhookHeaders::from_iter(request_headers);
+/// verifier.verify(&headers, &body)?;
+/// let event = handler.parse_payload(&body)?;
+/// ```
+pub trait WebhookVerifier: Send + Sync {
+    /// Check the raw body against the request's headers
+    fn verify(&self, headers: &WebhookHeaders, body: &[u8]) -> Result<(), VerificationError>;
+}
+
+/// Why an inbound webhook failed verification
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum VerificationError {
+    #[error("Missing {0} header")]
+    MissingHeader(String),
+
+    #[error("Invalid signature")]
+    InvalidSignature,
+
+    #[error("Timestamp outside the accepted window")]
+    Expired,
+
+    #[error("Verification not configured: {0}")]
+    NotConfigured(String),
+}
+
+/// Headers of an inbound webhook request, looked up case-insensitively
+#[derive(Debug, Clone, Default)]
+pub struct WebhookHeaders(HashMap<String, String>);
+
+impl WebhookHeaders {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a header
+    pub fn with(mut self, name: impl AsRef<str>, value: impl Into<String>) -> Self {
+        self.0
+            .insert(name.as_ref().to_ascii_lowercase(), value.into());
+        self
+    }
+
+    /// A header's value
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(&name.to_ascii_lowercase()).map(String::as_str)
+    }
+
+    /// A header's value, or [`VerificationError::MissingHeader`]
+    pub fn require(&self, name: &str) -> Result<&str, VerificationError> {
+        self.get(name)
+            .ok_or_else(|| VerificationError::MissingHeader(name.to_string()))
+    }
+}
+
+impl<K: AsRef<str>, V: Into<String>> FromIterator<(K, V)> for WebhookHeaders {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(Self::new(), |headers, (name, value)| {
+                headers.with(name, value)
+            })
+    }
+}
+
 /// Events received from integration platforms
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -98,6 +189,10 @@ pub enum IntegrationEvent {
         channel_id: String,
         user_id: String,
         emoji: String,
+        /// The reacted message (`content`, `author_name`, ...) when the
+        /// integration fetched it
+        #[serde(default)]
+        metadata: serde_json::Value,
     },
 
     /// A direct message was received
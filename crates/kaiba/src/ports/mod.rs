@@ -5,12 +5,14 @@
 //!
 //! Implementations of these traits live in the infrastructure layer.
 
+pub mod event_bus;
 pub mod integration;
 pub mod repositories;
 pub mod services;
 pub mod webhook;
 
 // Re-exports
+pub use event_bus::*;
 pub use integration::*;
 pub use repositories::*;
 pub use services::*;
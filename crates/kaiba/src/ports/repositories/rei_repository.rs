@@ -3,6 +3,7 @@
 //! Abstract interface for Rei persistence operations.
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 use crate::domain::{errors::DomainError, Rei, ReiState};
@@ -19,15 +20,39 @@ pub trait ReiRepository: Send + Sync {
     /// Save a Rei (insert or update)
     async fn save(&self, rei: &Rei) -> Result<Rei, DomainError>;
 
-    /// Delete a Rei by ID
+    /// Update a Rei only if its `updated_at` still equals `expected`
+    ///
+    /// Returns `None` when the Rei was modified (or deleted) in the meantime.
+    async fn update_if_unchanged(
+        &self,
+        rei: &Rei,
+        expected: DateTime<Utc>,
+    ) -> Result<Option<Rei>, DomainError>;
+
+    /// Soft-delete a Rei by ID (hidden from finds until restored or purged)
     async fn delete(&self, id: Uuid) -> Result<bool, DomainError>;
 
+    /// Restore a soft-deleted Rei
+    async fn restore(&self, id: Uuid) -> Result<bool, DomainError>;
+
+    /// Permanently delete Reis soft-deleted before `cutoff`, returning their IDs
+    async fn purge_deleted(&self, cutoff: DateTime<Utc>) -> Result<Vec<Uuid>, DomainError>;
+
     /// Find state for a Rei
     async fn find_state(&self, rei_id: Uuid) -> Result<Option<ReiState>, DomainError>;
 
     /// Save Rei state
     async fn save_state(&self, state: &ReiState) -> Result<ReiState, DomainError>;
 
+    /// Enable or disable a Rei's autonomy, returning the updated state
+    ///
+    /// Returns `None` when the Rei does not exist.
+    async fn set_autonomy(
+        &self,
+        rei_id: Uuid,
+        enabled: bool,
+    ) -> Result<Option<ReiState>, DomainError>;
+
     /// Create initial state for a new Rei
     async fn create_state(&self, rei_id: Uuid) -> Result<ReiState, DomainError>;
 }
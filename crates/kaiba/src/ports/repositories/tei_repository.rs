@@ -3,6 +3,7 @@
 //! Abstract interface for Tei persistence operations.
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 use crate::domain::{errors::DomainError, ReiTei, Tei};
@@ -19,6 +20,15 @@ pub trait TeiRepository: Send + Sync {
     /// Save a Tei (insert or update)
     async fn save(&self, tei: &Tei) -> Result<Tei, DomainError>;
 
+    /// Update a Tei only if its `updated_at` still equals `expected`
+    ///
+    /// Returns `None` when the Tei was modified (or deleted) in the meantime.
+    async fn update_if_unchanged(
+        &self,
+        tei: &Tei,
+        expected: DateTime<Utc>,
+    ) -> Result<Option<Tei>, DomainError>;
+
     /// Delete a Tei by ID
     async fn delete(&self, id: Uuid) -> Result<bool, DomainError>;
 
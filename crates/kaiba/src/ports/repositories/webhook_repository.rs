@@ -3,9 +3,13 @@
 //! Abstract interface for ReiWebhook persistence operations.
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
-use crate::domain::entities::{ReiWebhook, WebhookDelivery, WebhookEventType};
+use crate::domain::entities::{
+    DeliveryRetention, DeliveryStatus, EventDefinition, ReiWebhook, WebhookDelivery,
+    WebhookDeliveryStats, WebhookEventType, WebhookPayload,
+};
 use crate::domain::errors::DomainError;
 
 /// Repository interface for ReiWebhook entities
@@ -17,7 +21,11 @@ pub trait ReiWebhookRepository: Send + Sync {
     /// Find all webhooks for a Rei
     async fn find_by_rei(&self, rei_id: Uuid) -> Result<Vec<ReiWebhook>, DomainError>;
 
-    /// Find all enabled webhooks for a Rei that subscribe to a specific event
+    /// Find all global (non-Rei-scoped) webhooks
+    async fn find_global(&self) -> Result<Vec<ReiWebhook>, DomainError>;
+
+    /// Find all enabled webhooks for a Rei that subscribe to a specific event,
+    /// including enabled global webhooks
     async fn find_by_rei_and_event(
         &self,
         rei_id: Uuid,
@@ -33,6 +41,12 @@ pub trait ReiWebhookRepository: Send + Sync {
     /// Enable/disable a webhook
     async fn set_enabled(&self, id: Uuid, enabled: bool) -> Result<bool, DomainError>;
 
+    /// Record a final delivery outcome for failure tracking
+    ///
+    /// Resets the consecutive failure counter on success, increments it
+    /// otherwise. Returns the updated counter.
+    async fn record_delivery_outcome(&self, id: Uuid, success: bool) -> Result<i32, DomainError>;
+
     // --- Delivery tracking ---
 
     /// Save a delivery record
@@ -41,6 +55,9 @@ pub trait ReiWebhookRepository: Send + Sync {
         delivery: &WebhookDelivery,
     ) -> Result<WebhookDelivery, DomainError>;
 
+    /// Find a delivery record by ID
+    async fn find_delivery_by_id(&self, id: Uuid) -> Result<Option<WebhookDelivery>, DomainError>;
+
     /// Find recent deliveries for a webhook
     async fn find_deliveries(
         &self,
@@ -48,6 +65,63 @@ pub trait ReiWebhookRepository: Send + Sync {
         limit: i32,
     ) -> Result<Vec<WebhookDelivery>, DomainError>;
 
+    /// Find recent deliveries for a webhook with a given status
+    async fn find_deliveries_by_status(
+        &self,
+        webhook_id: Uuid,
+        status: &DeliveryStatus,
+        limit: i32,
+    ) -> Result<Vec<WebhookDelivery>, DomainError>;
+
     /// Find pending deliveries that need retry
     async fn find_pending_deliveries(&self) -> Result<Vec<WebhookDelivery>, DomainError>;
+
+    /// Aggregate delivery metrics for a webhook (optionally since a point in time)
+    async fn delivery_stats(
+        &self,
+        webhook_id: Uuid,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<WebhookDeliveryStats, DomainError>;
+
+    /// Delete finished delivery records outside the retention policy
+    ///
+    /// Returns the number of records deleted.
+    async fn prune_deliveries(&self, retention: &DeliveryRetention) -> Result<u64, DomainError>;
+
+    // --- Batched delivery ---
+
+    /// Queue an event for the next batched delivery
+    async fn enqueue_batch_event(
+        &self,
+        webhook_id: Uuid,
+        payload: &WebhookPayload,
+    ) -> Result<(), DomainError>;
+
+    /// Find enabled batched webhooks whose oldest queued event is at least
+    /// one batch interval old
+    async fn find_due_batches(&self) -> Result<Vec<ReiWebhook>, DomainError>;
+
+    /// Remove and return all queued events for a webhook
+    async fn take_batch_events(&self, webhook_id: Uuid)
+        -> Result<Vec<WebhookPayload>, DomainError>;
+
+    // --- Custom event registry ---
+
+    /// List registered custom events
+    async fn find_event_definitions(&self) -> Result<Vec<EventDefinition>, DomainError>;
+
+    /// Find a custom event by name (without the `custom:` prefix)
+    async fn find_event_definition(
+        &self,
+        name: &str,
+    ) -> Result<Option<EventDefinition>, DomainError>;
+
+    /// Save a custom event definition (insert or update)
+    async fn save_event_definition(
+        &self,
+        definition: &EventDefinition,
+    ) -> Result<EventDefinition, DomainError>;
+
+    /// Delete a custom event definition
+    async fn delete_event_definition(&self, name: &str) -> Result<bool, DomainError>;
 }